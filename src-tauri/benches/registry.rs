@@ -0,0 +1,70 @@
+//! Criterion benches for transcript parsing, project scans and registry
+//! writes over generated fixtures: `cargo bench --bench registry`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+use codex_monitor_lib::bench_support::{
+    parse_history, write_transcript, Fixture, Registry, Scanner,
+};
+
+fn parse_session_history(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_session_history");
+    group.sample_size(10);
+    for lines in [10_000, 50_000, 100_000] {
+        let fixture = Fixture::new();
+        let transcript = fixture.project_dir().join("history.jsonl");
+        write_transcript(&transcript, "history", &fixture.cwd(), lines);
+        group.throughput(Throughput::Elements(lines as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(lines),
+            &transcript,
+            |b, path| b.iter(|| parse_history(&fixture, path)),
+        );
+    }
+    group.finish();
+}
+
+fn scan_project_dir(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scan_project_dir");
+    group.sample_size(10);
+    for transcripts in [100, 1_000, 2_000] {
+        let fixture = Fixture::new();
+        fixture.write_transcripts(transcripts, 500);
+        group.throughput(Throughput::Elements(transcripts as u64));
+        group.bench_function(BenchmarkId::new("cold", transcripts), |b| {
+            b.iter_batched(
+                Scanner::default,
+                |mut scanner| scanner.scan(&fixture),
+                BatchSize::PerIteration,
+            )
+        });
+        let mut scanner = Scanner::default();
+        scanner.scan(&fixture);
+        group.bench_function(BenchmarkId::new("cached", transcripts), |b| {
+            b.iter(|| scanner.scan(&fixture))
+        });
+    }
+    group.finish();
+}
+
+fn write_registry(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_registry");
+    for sessions in [1_000, 10_000, 50_000] {
+        let fixture = Fixture::new();
+        let registry = Registry::new(sessions, &fixture.cwd());
+        let path = fixture.root().join("threads.json");
+        group.throughput(Throughput::Elements(sessions as u64));
+        group.bench_function(BenchmarkId::from_parameter(sessions), |b| {
+            b.iter(|| registry.write(&path))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    parse_session_history,
+    scan_project_dir,
+    write_registry
+);
+criterion_main!(benches);
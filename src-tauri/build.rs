@@ -1,3 +1,106 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// Name, version and license of every crate in the dependency graph.
+fn crate_acknowledgements() -> Vec<Value> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let Some(metadata) = command_output(
+        &cargo,
+        &["metadata", "--format-version", "1", "--offline", "--locked"],
+    ) else {
+        println!("cargo:warning=cargo metadata failed; crate acknowledgements are empty");
+        return Vec::new();
+    };
+    let metadata: Value = serde_json::from_str(&metadata).unwrap_or_default();
+    let own = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+    let mut crates: Vec<Value> = metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|package| package["name"] != own.as_str())
+        .map(|package| {
+            json!({
+                "name": package["name"],
+                "version": package["version"],
+                "license": package["license"],
+                "repository": package["repository"],
+                "kind": "crate",
+            })
+        })
+        .collect();
+    crates.sort_by_key(|package| package["name"].as_str().unwrap_or_default().to_string());
+    crates
+}
+
+/// The frontend's npm dependencies, as installed in `node_modules`.
+fn npm_acknowledgements(root: &Path) -> Vec<Value> {
+    let read = |path: &Path| -> Value {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    };
+    let manifest = read(&root.join("package.json"));
+    manifest["dependencies"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, wanted)| {
+            let installed = read(&root.join("node_modules").join(name).join("package.json"));
+            let repository = match &installed["repository"] {
+                Value::Object(repository) => repository.get("url").cloned().unwrap_or_default(),
+                other => other.clone(),
+            };
+            json!({
+                "name": name,
+                "version": installed.get("version").unwrap_or(wanted),
+                "license": installed["license"],
+                "repository": repository,
+                "kind": "npm",
+            })
+        })
+        .collect()
+}
+
 fn main() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let root = Path::new(&manifest_dir)
+        .parent()
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+
+    if let Some(commit) = command_output("git", &["rev-parse", "--short", "HEAD"]) {
+        println!("cargo:rustc-env=CLAUDE_MONITOR_COMMIT={commit}");
+    }
+    let built_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=CLAUDE_MONITOR_BUILT_AT={built_at}");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-changed=../package.json");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+
+    let mut acknowledgements = npm_acknowledgements(&root);
+    acknowledgements.extend(crate_acknowledgements());
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set for build scripts");
+    std::fs::write(
+        Path::new(&out_dir).join("acknowledgements.json"),
+        Value::Array(acknowledgements).to_string(),
+    )
+    .expect("write acknowledgements");
+
     tauri_build::build()
 }
@@ -0,0 +1,10 @@
+//! Arbitrary bytes as one line of bridge stdout:
+//! `cargo fuzz run bridge_line`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    codex_monitor_lib::fuzz_support::bridge_line(data);
+});
@@ -0,0 +1,10 @@
+//! Arbitrary bytes as a Claude transcript, read by the project scan and the
+//! session history view: `cargo fuzz run jsonl_transcript`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    codex_monitor_lib::fuzz_support::transcript(data);
+});
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::claude::get_bridge_path;
+use crate::command_metrics::CommandTimer;
+use crate::i18n::format_millis;
+
+/// Third-party crates and npm packages, generated by `build.rs`.
+const ACKNOWLEDGEMENTS: &str = include_str!(concat!(env!("OUT_DIR"), "/acknowledgements.json"));
+
+/// The Agent SDK package the bridge is built on.
+const SDK_PACKAGE: &str = "@anthropic-ai/claude-agent-sdk";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Acknowledgement {
+    name: String,
+    version: String,
+    #[serde(default)]
+    license: Option<String>,
+    #[serde(default)]
+    repository: Option<String>,
+    /// "crate" or "npm".
+    kind: String,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AboutInfo {
+    version: String,
+    commit: Option<String>,
+    /// Formatted in the configured locale.
+    build_date: Option<String>,
+    bridge_version: Option<String>,
+    sdk_version: Option<String>,
+    acknowledgements: Vec<Acknowledgement>,
+}
+
+fn package_version(path: &Path) -> Option<String> {
+    let data = std::fs::read_to_string(path).ok()?;
+    let manifest: Value = serde_json::from_str(&data).ok()?;
+    manifest["version"].as_str().map(str::to_string)
+}
+
+/// Version of the bridge package next to `bridge_path` and of the Agent SDK
+/// it resolves, looking up `node_modules` the way Node does.
+fn bridge_versions(bridge_path: &Path) -> (Option<String>, Option<String>) {
+    let Some(bridge_dir) = bridge_path.parent() else {
+        return (None, None);
+    };
+    let bridge = package_version(&bridge_dir.join("package.json"));
+    let sdk = bridge_dir.ancestors().find_map(|dir| {
+        package_version(
+            &dir.join("node_modules")
+                .join(SDK_PACKAGE)
+                .join("package.json"),
+        )
+    });
+    (bridge, sdk)
+}
+
+fn acknowledgements() -> Vec<Acknowledgement> {
+    serde_json::from_str(ACKNOWLEDGEMENTS).unwrap_or_default()
+}
+
+/// Version, build and license details for the About window.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_about_info(
+    app_handle: AppHandle,
+    _timer: CommandTimer,
+) -> Result<AboutInfo, String> {
+    let (bridge_version, sdk_version) = match get_bridge_path(&app_handle) {
+        Ok(path) => bridge_versions(Path::new(&path)),
+        Err(_) => (None, None),
+    };
+    Ok(AboutInfo {
+        version: app_handle.package_info().version.to_string(),
+        commit: option_env!("CLAUDE_MONITOR_COMMIT").map(str::to_string),
+        build_date: option_env!("CLAUDE_MONITOR_BUILT_AT")
+            .and_then(|millis| millis.parse().ok())
+            .map(format_millis),
+        bridge_version,
+        sdk_version,
+        acknowledgements: acknowledgements(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{acknowledgements, bridge_versions, SDK_PACKAGE};
+    use crate::utils::TempDir;
+
+    #[test]
+    fn build_generates_acknowledgements() {
+        let acknowledgements = acknowledgements();
+        assert!(acknowledgements
+            .iter()
+            .any(|package| package.kind == "crate" && package.name == "serde"));
+        assert!(acknowledgements
+            .iter()
+            .all(|package| package.name != env!("CARGO_PKG_NAME")));
+    }
+
+    #[test]
+    fn reads_bridge_and_sdk_versions() {
+        let root = TempDir::new("about-bridge");
+        let bridge_dir = root.join("claude-bridge");
+        let sdk_dir = root.join("node_modules").join(SDK_PACKAGE);
+        std::fs::create_dir_all(&bridge_dir).unwrap();
+        std::fs::create_dir_all(&sdk_dir).unwrap();
+        std::fs::write(bridge_dir.join("package.json"), r#"{"version":"1.0.0"}"#).unwrap();
+        std::fs::write(sdk_dir.join("package.json"), r#"{"version":"0.2.7"}"#).unwrap();
+
+        let (bridge, sdk) = bridge_versions(&bridge_dir.join("index.ts"));
+        assert_eq!(bridge.as_deref(), Some("1.0.0"));
+        assert_eq!(sdk.as_deref(), Some("0.2.7"));
+    }
+}
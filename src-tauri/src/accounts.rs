@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::secrets::{delete_secret, read_secret, write_secret};
+use crate::state::AppState;
+use crate::storage::{write_settings, write_workspaces};
+use crate::types::{ClaudeAccount, ClaudeAccountKind, ClaudeAuthMode, WorkspaceInfo};
+
+/// Keychain entry for the app-wide key used by API-key mode.
+const API_KEY_SECRET: &str = "anthropic-api-key";
+
+fn account_secret_key(account_id: &str) -> String {
+    format!("claude-account:{account_id}")
+}
+
+/// Auth context handed to the bridge for a workspace's sessions.
+pub(crate) struct AccountContext {
+    pub(crate) account_id: Option<String>,
+    pub(crate) env: HashMap<String, String>,
+}
+
+/// Resolve the account a workspace should use (workspace override, then the
+/// app default) and build the environment the bridge needs to authenticate.
+pub(crate) async fn resolve_account_context(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<AccountContext, String> {
+    let workspace_account = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .and_then(|entry| entry.settings.account_id.clone())
+    };
+    let (account, auth_mode) = {
+        let settings = state.app_settings.lock().await;
+        let account_id = workspace_account.or_else(|| settings.default_account_id.clone());
+        let account = match account_id {
+            Some(id) => Some(
+                settings
+                    .claude_accounts
+                    .iter()
+                    .find(|account| account.id == id)
+                    .cloned()
+                    .ok_or_else(|| format!("Claude account {id} not found"))?,
+            ),
+            None => None,
+        };
+        (account, settings.claude_auth_mode.clone())
+    };
+
+    let mut env = HashMap::new();
+    let Some(account) = account else {
+        if auth_mode == ClaudeAuthMode::ApiKey {
+            let key = read_secret(API_KEY_SECRET).await?.ok_or(
+                "API-key mode is enabled but no Anthropic API key is stored.".to_string(),
+            )?;
+            env.insert("ANTHROPIC_API_KEY".to_string(), key);
+        }
+        return Ok(AccountContext {
+            account_id: None,
+            env,
+        });
+    };
+
+    if let Some(config_dir) = account
+        .config_dir
+        .as_ref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        env.insert("CLAUDE_CONFIG_DIR".to_string(), config_dir.clone());
+    }
+    if account.kind == ClaudeAccountKind::ApiKey {
+        let key = read_secret(&account_secret_key(&account.id))
+            .await?
+            .ok_or_else(|| format!("No API key stored for account \"{}\"", account.label))?;
+        env.insert("ANTHROPIC_API_KEY".to_string(), key);
+    }
+
+    Ok(AccountContext {
+        account_id: Some(account.id),
+        env,
+    })
+}
+
+/// Store (or clear, with `None`) the API key used by API-key mode.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_anthropic_api_key(
+    api_key: Option<String>,
+    _timer: CommandTimer,
+) -> Result<bool, String> {
+    match api_key.as_deref().map(str::trim).filter(|key| !key.is_empty()) {
+        Some(key) => {
+            write_secret(API_KEY_SECRET, key).await?;
+            Ok(true)
+        }
+        None => {
+            delete_secret(API_KEY_SECRET).await?;
+            Ok(false)
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn has_anthropic_api_key(_timer: CommandTimer) -> Result<bool, String> {
+    Ok(read_secret(API_KEY_SECRET).await?.is_some())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_accounts(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<ClaudeAccount>, String> {
+    let settings = state.app_settings.lock().await;
+    Ok(settings.claude_accounts.clone())
+}
+
+/// Create or update an account. `api_key` is written to the keychain and is
+/// only required the first time an API-key account is saved.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn save_account(
+    account: ClaudeAccount,
+    api_key: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<ClaudeAccount>, String> {
+    if account.id.trim().is_empty() {
+        return Err("Account id is required.".to_string());
+    }
+    if let Some(key) = api_key.as_deref().map(str::trim).filter(|key| !key.is_empty()) {
+        if account.kind != ClaudeAccountKind::ApiKey {
+            return Err("Only API-key accounts can store an API key.".to_string());
+        }
+        write_secret(&account_secret_key(&account.id), key).await?;
+    } else if account.kind == ClaudeAccountKind::Subscription {
+        delete_secret(&account_secret_key(&account.id)).await?;
+    }
+
+    let mut settings = state.app_settings.lock().await;
+    match settings
+        .claude_accounts
+        .iter_mut()
+        .find(|existing| existing.id == account.id)
+    {
+        Some(existing) => *existing = account,
+        None => settings.claude_accounts.push(account),
+    }
+    write_settings(&state.settings_path, &settings)?;
+    Ok(settings.claude_accounts.clone())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn remove_account(
+    account_id: String,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<ClaudeAccount>, String> {
+    consume_confirmation(
+        &state,
+        confirmation.as_deref(),
+        "remove_account",
+        &account_id,
+    )
+    .await?;
+    delete_secret(&account_secret_key(&account_id)).await?;
+
+    let accounts = {
+        let mut settings = state.app_settings.lock().await;
+        settings
+            .claude_accounts
+            .retain(|account| account.id != account_id);
+        if settings.default_account_id.as_deref() == Some(account_id.as_str()) {
+            settings.default_account_id = None;
+        }
+        write_settings(&state.settings_path, &settings)?;
+        settings.claude_accounts.clone()
+    };
+
+    let mut workspaces = state.workspaces.lock().await;
+    let mut changed = false;
+    for entry in workspaces.values_mut() {
+        if entry.settings.account_id.as_deref() == Some(account_id.as_str()) {
+            entry.settings.account_id = None;
+            changed = true;
+        }
+    }
+    if changed {
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(&state.storage_path, &list)?;
+    }
+
+    Ok(accounts)
+}
+
+/// Pin a workspace to an account, or pass `None` to fall back to the default.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_workspace_account(
+    workspace_id: String,
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorkspaceInfo, String> {
+    if let Some(ref id) = account_id {
+        let settings = state.app_settings.lock().await;
+        if !settings.claude_accounts.iter().any(|account| &account.id == id) {
+            return Err(format!("Claude account {id} not found"));
+        }
+    }
+
+    let (entry_snapshot, list) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry_snapshot = match workspaces.get_mut(&workspace_id) {
+            Some(entry) => {
+                entry.settings.account_id = account_id;
+                entry.clone()
+            }
+            None => return Err("workspace not found".to_string()),
+        };
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry_snapshot, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+
+    let connected = state.sessions.lock().await.contains_key(&workspace_id);
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+        automation_paused: entry_snapshot.automation_paused,
+    })
+}
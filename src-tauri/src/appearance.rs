@@ -0,0 +1,62 @@
+use tauri::{AppHandle, Emitter, State};
+
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+use crate::types::AppearanceSettings;
+
+const FONT_SIZE_RANGE: std::ops::RangeInclusive<u32> = 10..=24;
+
+/// Check an accent color is `#rrggbb` and the font size is readable.
+pub(crate) fn validate_appearance(appearance: &AppearanceSettings) -> Result<(), String> {
+    if let Some(color) = appearance.accent_color.as_deref() {
+        let hex = color.strip_prefix('#').unwrap_or_default();
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!(
+                "Invalid accent color: {color}. Use a hex color like #4d99ff."
+            ));
+        }
+    }
+    if !FONT_SIZE_RANGE.contains(&appearance.font_size) {
+        return Err(format!(
+            "Font size must be between {} and {} px.",
+            FONT_SIZE_RANGE.start(),
+            FONT_SIZE_RANGE.end()
+        ));
+    }
+    Ok(())
+}
+
+/// Tell every window about new appearance settings.
+pub(crate) fn emit_appearance(app: &AppHandle, appearance: &AppearanceSettings) {
+    let _ = app.emit("appearance-changed", appearance);
+}
+
+/// Theme, accent color and font size, for windows that don't load the
+/// full settings.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_appearance(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<AppearanceSettings, String> {
+    Ok(state.app_settings.lock().await.appearance.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_appearance;
+    use crate::types::AppearanceSettings;
+
+    #[test]
+    fn validates_accent_and_font_size() {
+        let mut appearance = AppearanceSettings::default();
+        assert!(validate_appearance(&appearance).is_ok());
+        appearance.accent_color = Some("#4D99ff".to_string());
+        assert!(validate_appearance(&appearance).is_ok());
+        appearance.accent_color = Some("blue".to_string());
+        assert!(validate_appearance(&appearance).is_err());
+        appearance.accent_color = None;
+        appearance.font_size = 40;
+        assert!(validate_appearance(&appearance).is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+use crate::tokens::{context_window, estimate_tokens};
+
+/// Files larger than this are mentioned rather than inlined.
+const MAX_INLINE_BYTES: u64 = 256 * 1024;
+/// Share of the context window attachments may fill before we warn; the
+/// rest is left for the conversation and the model's own reads.
+const BUDGET_SHARE: f64 = 0.5;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AttachmentMode {
+    /// Paste file contents into the message.
+    #[default]
+    Inline,
+    /// Reference files as `@path` and let Claude read them.
+    Mention,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Attachment {
+    /// Path relative to the workspace root.
+    path: String,
+    bytes: u64,
+    tokens: u64,
+    inlined: bool,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AttachedMessage {
+    message: String,
+    attachments: Vec<Attachment>,
+    total_tokens: u64,
+    budget_tokens: u64,
+    context_window: u64,
+    warnings: Vec<String>,
+}
+
+/// Resolve a user-supplied path inside the workspace, refusing anything
+/// that escapes it, including through symlinks.
+pub(crate) fn resolve_in_workspace(root: &Path, path: &str) -> Result<(PathBuf, String), String> {
+    let root = std::fs::canonicalize(root).map_err(|_| "Workspace path not found".to_string())?;
+    let candidate = Path::new(path.trim());
+    let joined = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        root.join(candidate)
+    };
+    let resolved = std::fs::canonicalize(&joined).map_err(|_| format!("File not found: {path}"))?;
+    let relative = resolved
+        .strip_prefix(&root)
+        .map_err(|_| format!("{path} is outside the workspace"))?;
+    if !resolved.is_file() {
+        return Err(format!("{path} is not a file"));
+    }
+    Ok((
+        resolved.clone(),
+        crate::utils::normalize_git_path(&relative.to_string_lossy()),
+    ))
+}
+
+/// Fence long enough that no backtick run inside `content` can close it.
+fn fence_for(content: &str) -> String {
+    let longest = content
+        .split(|ch| ch != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn inline_block(path: &str, content: &str) -> String {
+    let fence = fence_for(content);
+    let newline = if content.ends_with('\n') { "" } else { "\n" };
+    format!("{path}:\n{fence}\n{content}{newline}{fence}")
+}
+
+/// Append attachments to `message`: inlined contents first, then mentions.
+fn compose(message: &str, inlined: &[(String, String)], mentioned: &[String]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if !message.trim().is_empty() {
+        parts.push(message.trim_end().to_string());
+    }
+    for (path, content) in inlined {
+        parts.push(inline_block(path, content));
+    }
+    if !mentioned.is_empty() {
+        parts.push(
+            mentioned
+                .iter()
+                .map(|path| format!("@{path}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+    parts.join("\n\n")
+}
+
+/// Build the outgoing text for a message with repo files attached. Nothing
+/// is sent; the composer shows the warnings and sends `message` as usual.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn attach_files_to_message(
+    session_id: String,
+    paths: Vec<String>,
+    message: Option<String>,
+    mode: Option<AttachmentMode>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<AttachedMessage, String> {
+    let workspace_id = state
+        .claude_sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|info| info.workspace_id.clone())
+        .ok_or("session not found")?;
+    let root = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(|entry| PathBuf::from(&entry.path))
+        .ok_or("workspace not found")?;
+    let model = state
+        .registry
+        .lock()
+        .await
+        .sessions
+        .get(&session_id)
+        .and_then(|entry| entry.usage.as_ref())
+        .and_then(|usage| usage.model.clone());
+    let mode = mode.unwrap_or_default();
+    let message = message.unwrap_or_default();
+
+    let window = context_window(model.as_deref());
+    let budget = (window as f64 * BUDGET_SHARE) as u64;
+    let mut warnings = Vec::new();
+    let mut attachments = Vec::new();
+    let mut inlined: Vec<(String, String)> = Vec::new();
+    let mut mentioned: Vec<String> = Vec::new();
+    for path in &paths {
+        let (resolved, relative) = resolve_in_workspace(&root, path)?;
+        if attachments.iter().any(|a: &Attachment| a.path == relative) {
+            continue;
+        }
+        let bytes = std::fs::metadata(&resolved)
+            .map(|meta| meta.len())
+            .map_err(|e| e.to_string())?;
+        let content = if mode == AttachmentMode::Inline && bytes <= MAX_INLINE_BYTES {
+            match std::fs::read(&resolved) {
+                Ok(data) if !data.contains(&0) => String::from_utf8(data).ok(),
+                Ok(_) => None,
+                Err(e) => return Err(e.to_string()),
+            }
+        } else {
+            None
+        };
+        let (tokens, inline) = match content {
+            Some(content) => (estimate_tokens(&content), Some(content)),
+            None => {
+                if mode == AttachmentMode::Inline {
+                    warnings.push(format!(
+                        "{relative} is binary or larger than {} KB; mentioned instead",
+                        MAX_INLINE_BYTES / 1024
+                    ));
+                }
+                // Claude reads mentioned files itself; ~4 bytes per token.
+                (bytes.div_ceil(4), None)
+            }
+        };
+        attachments.push(Attachment {
+            path: relative.clone(),
+            bytes,
+            tokens,
+            inlined: inline.is_some(),
+        });
+        match inline {
+            Some(content) => inlined.push((relative, content)),
+            None => mentioned.push(relative),
+        }
+    }
+
+    let composed = compose(&message, &inlined, &mentioned);
+    let total_tokens = estimate_tokens(&message)
+        + attachments
+            .iter()
+            .map(|attachment| attachment.tokens)
+            .sum::<u64>();
+    if total_tokens > window {
+        warnings.push(format!(
+            "Attachments need about {total_tokens} tokens, more than the {window}-token context window"
+        ));
+    } else if total_tokens > budget {
+        warnings.push(format!(
+            "Attachments need about {total_tokens} tokens, over half of the {window}-token context window"
+        ));
+    }
+    Ok(AttachedMessage {
+        message: composed,
+        attachments,
+        total_tokens,
+        budget_tokens: budget,
+        context_window: window,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compose, fence_for, resolve_in_workspace};
+    use crate::utils::TempDir;
+
+    #[test]
+    fn composes_inline_blocks_and_mentions() {
+        let message = compose(
+            "Review these",
+            &[("src/a.rs".to_string(), "let x = 1;".to_string())],
+            &["Cargo.lock".to_string()],
+        );
+        assert_eq!(
+            message,
+            "Review these\n\nsrc/a.rs:\n```\nlet x = 1;\n```\n\n@Cargo.lock"
+        );
+        assert_eq!(fence_for("uses ```rust fences"), "````");
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_workspace() {
+        let root = TempDir::new("attach");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let (_, relative) = resolve_in_workspace(&root, "src/main.rs").unwrap();
+        assert_eq!(relative, "src/main.rs");
+        assert!(resolve_in_workspace(&root, "../").is_err());
+        assert!(resolve_in_workspace(&root, "src").is_err());
+        assert!(resolve_in_workspace(&root, "missing.rs").is_err());
+    }
+}
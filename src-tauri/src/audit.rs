@@ -0,0 +1,122 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+
+const DEFAULT_AUDIT_LIMIT: usize = 200;
+
+/// A decision taken without the user answering a prompt, or a change to
+/// what may be decided that way.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AuditEntry {
+    pub(crate) at: u64,
+    pub(crate) session_id: String,
+    /// e.g. `autoApprove/enabled`, `autoApprove/allowed`, `autoApprove/expired`
+    pub(crate) action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tool_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tool_use_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) detail: Option<String>,
+}
+
+pub(crate) fn audit_path(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("audit.jsonl")
+}
+
+/// Append an entry to the audit trail. The file is only ever appended to.
+pub(crate) fn append_audit(path: &Path, entry: &AuditEntry) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+/// Newest `limit` entries, newest first, optionally for one session.
+fn read_audit(path: &Path, session_id: Option<&str>, limit: usize) -> Vec<AuditEntry> {
+    let Ok(file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<AuditEntry> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(&line).ok())
+        .filter(|entry| session_id.is_none_or(|id| entry.session_id == id))
+        .collect();
+    entries.reverse();
+    entries.truncate(limit);
+    entries
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_audit_log(
+    session_id: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<AuditEntry>, String> {
+    let path = audit_path(&state);
+    tokio::task::spawn_blocking(move || {
+        read_audit(
+            &path,
+            session_id.as_deref(),
+            limit.unwrap_or(DEFAULT_AUDIT_LIMIT),
+        )
+    })
+    .await
+    .map_err(|_| "audit log read failed".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append_audit, read_audit, AuditEntry};
+    use crate::utils::TempDir;
+
+    fn entry(at: u64, session_id: &str, action: &str) -> AuditEntry {
+        AuditEntry {
+            at,
+            session_id: session_id.to_string(),
+            action: action.to_string(),
+            tool_name: None,
+            tool_use_id: None,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn appends_and_reads_newest_first() {
+        let dir = TempDir::new("audit");
+        let path = dir.join("audit.jsonl");
+        append_audit(&path, &entry(1, "s1", "autoApprove/enabled")).unwrap();
+        append_audit(&path, &entry(2, "s2", "autoApprove/enabled")).unwrap();
+        append_audit(&path, &entry(3, "s1", "autoApprove/allowed")).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, b"not json\n"))
+            .unwrap();
+
+        let all = read_audit(&path, None, 10);
+        assert_eq!(all.iter().map(|e| e.at).collect::<Vec<_>>(), [3, 2, 1]);
+        let s1 = read_audit(&path, Some("s1"), 1);
+        assert_eq!(s1, [entry(3, "s1", "autoApprove/allowed")]);
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audit::{append_audit, audit_path, AuditEntry};
+use crate::automation::automation_paused;
+use crate::claude::{BridgeLine, ClaudeBridge};
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::registry::{excerpt, now_millis};
+use crate::session_files::tool_file;
+use crate::state::AppState;
+use crate::types::FileAction;
+
+/// Longest window `enable_auto_approve` accepts, in seconds.
+const MAX_WINDOW_SECS: u64 = 4 * 60 * 60;
+/// Tool input kept in an audit entry.
+const AUDIT_INPUT_CHARS: usize = 500;
+
+/// Permission requests an auto-approve window accepts. File paths must stay
+/// inside the session's working directory, except for `All`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum AutoApproveScope {
+    /// Reading files and searching.
+    Reads,
+    /// Reads, plus editing and writing files.
+    Edits,
+    /// Every tool, including shell commands.
+    All,
+}
+
+impl AutoApproveScope {
+    fn allows(self, tool_name: &str, input: &Value, root: &str) -> bool {
+        if self == Self::All {
+            return true;
+        }
+        match tool_name {
+            "Glob" | "Grep" | "LS" => input
+                .get("path")
+                .and_then(Value::as_str)
+                .is_none_or(|path| inside(path, root)),
+            _ => match tool_file(tool_name, input) {
+                Some((path, FileAction::Read)) => inside(&path, root),
+                Some((path, _)) => self == Self::Edits && inside(&path, root),
+                None => false,
+            },
+        }
+    }
+}
+
+/// Whether `path`, resolved against `root`, stays inside it. `..` is
+/// resolved lexically so it can't climb out, then the part of the path that
+/// exists is canonicalized so a symlink in the root can't point out of it.
+fn inside(path: &str, root: &str) -> bool {
+    if root.is_empty() {
+        return false;
+    }
+    let root = Path::new(root);
+    let mut resolved = PathBuf::new();
+    for component in root.join(path).components() {
+        match component {
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return false;
+                }
+            }
+            Component::CurDir => {}
+            other => resolved.push(other),
+        }
+    }
+    if !resolved.starts_with(root) {
+        return false;
+    }
+    // A root that is gone has no links left to follow.
+    let Ok(real_root) = root.canonicalize() else {
+        return true;
+    };
+    real_path(&resolved).is_some_and(|real| real.starts_with(&real_root))
+}
+
+/// `path` with its nearest existing ancestor canonicalized and the missing
+/// rest appended, so files about to be created are checked too.
+fn real_path(path: &Path) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        match existing.canonicalize() {
+            Ok(mut real) => {
+                for name in missing.iter().rev() {
+                    real.push(name);
+                }
+                return Some(real);
+            }
+            Err(_) => {
+                missing.push(existing.file_name()?);
+                existing = existing.parent()?;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutoApproveWindow {
+    pub(crate) session_id: String,
+    pub(crate) scope: AutoApproveScope,
+    /// Directory file paths must stay inside.
+    pub(crate) root: String,
+    /// Epoch ms when the window closes by itself.
+    pub(crate) expires_at: u64,
+    /// Requests approved so far.
+    pub(crate) approved: usize,
+}
+
+/// Open auto-approve windows by session id.
+pub(crate) type AutoApprovals = HashMap<String, AutoApproveWindow>;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct AutoApproveChanged<'a> {
+    session_id: &'a str,
+    window: Option<&'a AutoApproveWindow>,
+}
+
+fn emit_changed(app_handle: &AppHandle, session_id: &str, window: Option<&AutoApproveWindow>) {
+    let _ = app_handle.emit(
+        "auto-approve-changed",
+        AutoApproveChanged { session_id, window },
+    );
+}
+
+fn audit(
+    state: &AppState,
+    session_id: &str,
+    action: &str,
+    tool: Option<(&str, &str)>,
+    detail: String,
+) {
+    let entry = AuditEntry {
+        at: now_millis(),
+        session_id: session_id.to_string(),
+        action: action.to_string(),
+        tool_name: tool.map(|(name, _)| name.to_string()),
+        tool_use_id: tool.map(|(_, id)| id.to_string()),
+        detail: Some(detail),
+    };
+    if let Err(err) = append_audit(&audit_path(state), &entry) {
+        log_line!("Failed to write audit entry: {err}");
+    }
+}
+
+/// Answer a `permission/request` line from its session's auto-approve
+/// window if the window covers it. Returns whether it was answered; the
+/// answer goes out from a task, as the caller is the bridge reader that
+/// would receive the reply.
+pub(crate) async fn auto_approve(
+    app_handle: &AppHandle,
+    bridge: &Arc<ClaudeBridge>,
+    line: &BridgeLine,
+) -> bool {
+    if line.event_type != "permission/request" {
+        return false;
+    }
+    let state = app_handle.state::<AppState>();
+    if state.app_settings.lock().await.observer_mode {
+        return false;
+    }
+    if automation_paused(&state, &line.workspace_id).await {
+        return false;
+    }
+    let str_field = |key: &str| {
+        line.payload
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    };
+    let (tool_name, tool_use_id) = (str_field("toolName"), str_field("toolUseId"));
+    let input = line.payload.get("input").unwrap_or(&Value::Null);
+    let window = {
+        let mut windows = state.auto_approvals.lock().await;
+        let Some(window) = windows.get_mut(&line.session_id) else {
+            return false;
+        };
+        let open = window.expires_at > now_millis();
+        if !open || !window.scope.allows(tool_name, input, &window.root) {
+            return false;
+        }
+        window.approved += 1;
+        window.clone()
+    };
+    emit_changed(app_handle, &line.session_id, Some(&window));
+    audit(
+        &state,
+        &line.session_id,
+        "autoApprove/allowed",
+        Some((tool_name, tool_use_id)),
+        format!(
+            "{:?} window: {}",
+            window.scope,
+            excerpt(&input.to_string(), AUDIT_INPUT_CHARS)
+        ),
+    );
+    let bridge = Arc::clone(bridge);
+    let params = json!({
+        "sessionId": line.session_id,
+        "toolUseId": tool_use_id,
+        "decision": "allow",
+        "message": null,
+    });
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = bridge.send_request("permission/respond", params).await {
+            log_line!("Failed to auto-approve permission request: {err}");
+        }
+    });
+    true
+}
+
+/// Close the window when it runs out, unless it was replaced or closed.
+fn schedule_expiry(app_handle: AppHandle, session_id: String, expires_at: u64) {
+    tauri::async_runtime::spawn(async move {
+        let wait = expires_at.saturating_sub(now_millis());
+        tokio::time::sleep(Duration::from_millis(wait)).await;
+        let state = app_handle.state::<AppState>();
+        let mut windows = state.auto_approvals.lock().await;
+        let Some(window) = windows
+            .get(&session_id)
+            .filter(|window| window.expires_at == expires_at)
+            .cloned()
+        else {
+            return;
+        };
+        windows.remove(&session_id);
+        drop(windows);
+        audit(
+            &state,
+            &session_id,
+            "autoApprove/expired",
+            None,
+            format!("{:?} window approved {}", window.scope, window.approved),
+        );
+        emit_changed(&app_handle, &session_id, None);
+    });
+}
+
+/// Auto-accept the session's permission requests within `scope` for
+/// `duration` seconds, replacing any open window. Reverts by itself; every
+/// decision is written to the audit trail.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn enable_auto_approve(
+    session_id: String,
+    duration: u64,
+    scope: AutoApproveScope,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<AutoApproveWindow, String> {
+    if duration == 0 || duration > MAX_WINDOW_SECS {
+        return Err(format!(
+            "Auto-approve lasts between 1 second and {} hours.",
+            MAX_WINDOW_SECS / 3600
+        ));
+    }
+    let running_cwd = state
+        .claude_sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|info| info.cwd.clone())
+        .filter(|cwd| !cwd.is_empty());
+    let root = match running_cwd {
+        Some(cwd) => cwd,
+        None => state
+            .registry
+            .lock()
+            .await
+            .sessions
+            .get(&session_id)
+            .map(|session| session.cwd.clone())
+            .filter(|cwd| !cwd.is_empty())
+            .ok_or_else(|| format!("Session {} has no working directory", session_id))?,
+    };
+    let window = AutoApproveWindow {
+        session_id: session_id.clone(),
+        scope,
+        root,
+        expires_at: now_millis() + duration * 1000,
+        approved: 0,
+    };
+    state
+        .auto_approvals
+        .lock()
+        .await
+        .insert(session_id.clone(), window.clone());
+    audit(
+        &state,
+        &session_id,
+        "autoApprove/enabled",
+        None,
+        format!("{scope:?} for {duration}s in {}", window.root),
+    );
+    schedule_expiry(app_handle.clone(), session_id.clone(), window.expires_at);
+    emit_changed(&app_handle, &session_id, Some(&window));
+    Ok(window)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn disable_auto_approve(
+    session_id: String,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let Some(window) = state.auto_approvals.lock().await.remove(&session_id) else {
+        return Ok(());
+    };
+    audit(
+        &state,
+        &session_id,
+        "autoApprove/disabled",
+        None,
+        format!("{:?} window approved {}", window.scope, window.approved),
+    );
+    emit_changed(&app_handle, &session_id, None);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_auto_approve(
+    session_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<AutoApproveWindow>, String> {
+    Ok(state
+        .auto_approvals
+        .lock()
+        .await
+        .get(&session_id)
+        .filter(|window| window.expires_at > now_millis())
+        .cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{inside, AutoApproveScope};
+    use crate::utils::TempDir;
+
+    #[test]
+    fn paths_must_stay_in_the_root() {
+        assert!(inside("src/main.rs", "/repo"));
+        assert!(inside("/repo/src/../README.md", "/repo"));
+        assert!(!inside("../other/file", "/repo"));
+        assert!(!inside("/repo-other/file", "/repo"));
+        assert!(!inside("/etc/passwd", "/repo"));
+        assert!(!inside("file", ""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_must_stay_in_the_root() {
+        let base = TempDir::new("auto-approve");
+        let (root, outside) = (base.join("repo"), base.join("outside"));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+
+        let root_str = root.to_string_lossy();
+        assert!(inside("src/main.rs", &root_str));
+        assert!(inside("src/new/file.rs", &root_str));
+        assert!(!inside("escape/secrets.txt", &root_str));
+        assert!(!inside("escape/new/file.txt", &root_str));
+    }
+
+    #[test]
+    fn scopes_cover_their_tools() {
+        let edit = json!({ "file_path": "/repo/src/lib.rs", "old_string": "a", "new_string": "b" });
+        let outside = json!({ "file_path": "/home/me/.ssh/config" });
+        let read = json!({ "file_path": "src/lib.rs" });
+        let bash = json!({ "command": "rm -rf /" });
+
+        assert!(AutoApproveScope::Reads.allows("Read", &read, "/repo"));
+        assert!(AutoApproveScope::Reads.allows("Grep", &json!({ "pattern": "x" }), "/repo"));
+        assert!(!AutoApproveScope::Reads.allows("Grep", &json!({ "path": "/" }), "/repo"));
+        assert!(!AutoApproveScope::Reads.allows("Edit", &edit, "/repo"));
+
+        assert!(AutoApproveScope::Edits.allows("Edit", &edit, "/repo"));
+        assert!(!AutoApproveScope::Edits.allows("Write", &outside, "/repo"));
+        assert!(!AutoApproveScope::Edits.allows("Bash", &bash, "/repo"));
+
+        assert!(AutoApproveScope::All.allows("Bash", &bash, "/repo"));
+    }
+}
@@ -0,0 +1,111 @@
+use tauri::{AppHandle, Emitter, State};
+
+use crate::command_metrics::CommandTimer;
+use crate::message_queue::advance_queue;
+use crate::state::AppState;
+use crate::storage::write_workspaces;
+use crate::types::WorkspaceInfo;
+use crate::workflows::cancel_workspace_workflows;
+
+/// Whether the workspace's automation is on hold. Unknown workspaces are
+/// not paused.
+pub(crate) async fn automation_paused(state: &AppState, workspace_id: &str) -> bool {
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .is_some_and(|entry| entry.automation_paused)
+}
+
+/// Refuse an automated action while the workspace is paused, the way
+/// `ensure_not_observer` does for observer mode.
+pub(crate) async fn ensure_automation_allowed(
+    state: &AppState,
+    workspace_id: &str,
+    action: &str,
+) -> Result<(), String> {
+    if automation_paused(state, workspace_id).await {
+        return Err(format!(
+            "Automation is paused for this workspace: {action} is on hold."
+        ));
+    }
+    Ok(())
+}
+
+async fn set_paused(
+    state: &AppState,
+    workspace_id: &str,
+    paused: bool,
+) -> Result<WorkspaceInfo, String> {
+    let (entry, list) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get_mut(workspace_id)
+            .ok_or("workspace not found")?;
+        entry.automation_paused = paused;
+        let entry = entry.clone();
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+    let connected = state.sessions.lock().await.contains_key(workspace_id);
+    Ok(WorkspaceInfo {
+        id: entry.id,
+        name: entry.name,
+        path: entry.path,
+        codex_bin: entry.codex_bin,
+        connected,
+        kind: entry.kind,
+        parent_id: entry.parent_id,
+        worktree: entry.worktree,
+        settings: entry.settings,
+        automation_paused: entry.automation_paused,
+    })
+}
+
+/// Put a workspace's automation on hold: running workflows are cancelled,
+/// queued messages wait, and auto-approve windows stop answering. Manual
+/// messages and approvals still work.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn pause_workspace_automation(
+    workspace_id: String,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorkspaceInfo, String> {
+    let info = set_paused(&state, &workspace_id, true).await?;
+    cancel_workspace_workflows(&app_handle, &state, &workspace_id).await;
+    let _ = app_handle.emit("workspace-automation-changed", &info);
+    Ok(info)
+}
+
+/// Lift the hold. Sessions that are idle with messages queued send the
+/// next one right away.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn resume_workspace_automation(
+    workspace_id: String,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorkspaceInfo, String> {
+    let info = set_paused(&state, &workspace_id, false).await?;
+    let session_ids: Vec<String> = state
+        .claude_sessions
+        .lock()
+        .await
+        .values()
+        .filter(|session| session.workspace_id == workspace_id)
+        .map(|session| session.session_id.clone())
+        .collect();
+    for session_id in session_ids {
+        let waiting = state.message_queues.lock().await.waiting(&session_id);
+        if waiting {
+            advance_queue(&app_handle, &session_id).await;
+        }
+    }
+    let _ = app_handle.emit("workspace-automation-changed", &info);
+    Ok(info)
+}
@@ -0,0 +1,74 @@
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::{AppHandle, Manager};
+
+use crate::turns::TurnActivity;
+
+/// Dock badge: pending permission requests when there are any, since they
+/// block a session, else running sessions.
+fn badge_count(activity: TurnActivity) -> Option<i64> {
+    let count = if activity.pending_permissions > 0 {
+        activity.pending_permissions
+    } else {
+        activity.running
+    };
+    (count > 0).then_some(count as i64)
+}
+
+/// Taskbar progress: paused while a session waits on a permission,
+/// indeterminate while turns run, cleared when idle.
+fn progress_status(activity: TurnActivity) -> ProgressBarStatus {
+    if activity.pending_permissions > 0 {
+        ProgressBarStatus::Paused
+    } else if activity.running > 0 {
+        ProgressBarStatus::Indeterminate
+    } else {
+        ProgressBarStatus::None
+    }
+}
+
+/// Reflect session activity on the dock icon (macOS, some Linux docks) and
+/// the taskbar button (Windows). Platforms without either ignore it.
+pub(crate) fn show_activity(app: &AppHandle, activity: TurnActivity) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let _ = window.set_badge_count(badge_count(activity));
+    let _ = window.set_progress_bar(ProgressBarState {
+        status: Some(progress_status(activity)),
+        progress: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{badge_count, progress_status};
+    use crate::turns::TurnActivity;
+    use tauri::window::ProgressBarStatus;
+
+    #[test]
+    fn permissions_win_over_running_turns() {
+        let idle = TurnActivity::default();
+        assert_eq!(badge_count(idle), None);
+        assert!(matches!(progress_status(idle), ProgressBarStatus::None));
+
+        let running = TurnActivity {
+            running: 2,
+            pending_permissions: 0,
+        };
+        assert_eq!(badge_count(running), Some(2));
+        assert!(matches!(
+            progress_status(running),
+            ProgressBarStatus::Indeterminate
+        ));
+
+        let waiting = TurnActivity {
+            running: 2,
+            pending_permissions: 1,
+        };
+        assert_eq!(badge_count(waiting), Some(1));
+        assert!(matches!(
+            progress_status(waiting),
+            ProgressBarStatus::Paused
+        ));
+    }
+}
@@ -0,0 +1,192 @@
+//! Generated fixtures and thin wrappers over the registry internals, shared
+//! by the criterion benches in `benches/` and the `perf_smoke` test. Not
+//! part of the app's API.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::registry::{
+    cwd_to_project_dir_name, parse_session_history, scan_project_dir, write_registry, ScanCache,
+};
+use crate::types::{SessionEntry, SessionStatus, ThreadRegistry, WorkspaceRegistry};
+
+/// A temporary workspace and Claude projects directory, removed on drop.
+pub struct Fixture {
+    root: PathBuf,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "claude-monitor-perf-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir_all(root.join("workspace/src")).unwrap();
+        std::fs::write(root.join("workspace/src/main.rs"), "fn main() {}\n").unwrap();
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn cwd(&self) -> String {
+        self.root.join("workspace").to_string_lossy().to_string()
+    }
+
+    pub fn projects_dir(&self) -> PathBuf {
+        self.root.join("projects")
+    }
+
+    pub fn project_dir(&self) -> PathBuf {
+        self.projects_dir()
+            .join(cwd_to_project_dir_name(&self.cwd()))
+    }
+
+    /// Write `count` transcripts of `lines` entries into the project dir.
+    pub fn write_transcripts(&self, count: usize, lines: usize) {
+        for index in 0..count {
+            let path = self.project_dir().join(format!("session-{index}.jsonl"));
+            write_transcript(&path, &format!("session-{index}"), &self.cwd(), lines);
+        }
+    }
+}
+
+impl Default for Fixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// A transcript of `lines` entries shaped like Claude's JSONL: user and
+/// assistant text with file mentions, tool calls and tool results.
+pub fn write_transcript(path: &Path, session_id: &str, cwd: &str, lines: usize) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut out = BufWriter::new(std::fs::File::create(path).unwrap());
+    for index in 0..lines {
+        let entry = match index % 4 {
+            0 => json!({
+                "type": "user",
+                "sessionId": session_id,
+                "cwd": cwd,
+                "uuid": format!("{session_id}-{index}"),
+                "message": { "role": "user", "content": [
+                    { "type": "text", "text": format!("Step {index}: update src/main.rs and check `Cargo.toml` for the build") }
+                ]},
+            }),
+            1 => json!({
+                "type": "assistant",
+                "sessionId": session_id,
+                "cwd": cwd,
+                "uuid": format!("{session_id}-{index}"),
+                "message": { "role": "assistant", "content": [
+                    { "type": "text", "text": "I'll read src/main.rs first, then make the change.".repeat(3) },
+                    { "type": "tool_use", "id": format!("tool-{index}"), "name": "Read", "input": { "file_path": "src/main.rs" } }
+                ]},
+            }),
+            2 => json!({
+                "type": "user",
+                "sessionId": session_id,
+                "cwd": cwd,
+                "message": { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": format!("tool-{}", index - 1), "content": "fn main() {}\n".repeat(20) }
+                ]},
+            }),
+            _ => json!({
+                "type": "system",
+                "sessionId": session_id,
+                "cwd": cwd,
+                "subtype": "progress",
+            }),
+        };
+        writeln!(out, "{entry}").unwrap();
+    }
+    out.flush().unwrap();
+}
+
+/// Parse `transcript` as the session history view does. Returns the items,
+/// as JSON values.
+pub fn parse_history(fixture: &Fixture, transcript: &Path) -> Vec<serde_json::Value> {
+    parse_session_history("history", transcript, Path::new(&fixture.cwd()), &[])
+        .unwrap()
+        .items
+}
+
+/// Project scans that share one cache, like the app's background refresh.
+#[derive(Default)]
+pub struct Scanner {
+    cache: ScanCache,
+}
+
+impl Scanner {
+    /// Scan the fixture's project dir. Returns the number of sessions found.
+    pub fn scan(&mut self, fixture: &Fixture) -> usize {
+        scan_project_dir(
+            &fixture.projects_dir(),
+            &fixture.cwd(),
+            &mut self.cache,
+            &|_, _, _| {},
+        )
+        .unwrap()
+        .len()
+    }
+}
+
+/// A registry of `sessions` visible sessions in one workspace.
+pub struct Registry {
+    registry: ThreadRegistry,
+}
+
+impl Registry {
+    pub fn new(sessions: usize, cwd: &str) -> Self {
+        let mut registry = ThreadRegistry::default();
+        let mut visible = Vec::with_capacity(sessions);
+        for index in 0..sessions {
+            let session_id = format!("session-{index}");
+            visible.push(session_id.clone());
+            registry.sessions.insert(
+                session_id.clone(),
+                SessionEntry {
+                    session_id,
+                    cwd: cwd.to_string(),
+                    preview: Some(format!("Refactor module {index} and update its tests")),
+                    created_at: 1_700_000_000_000 + index as u64,
+                    last_activity: 1_700_000_100_000 + index as u64,
+                    transcript_path: Some(format!("{cwd}/transcripts/{index}.jsonl")),
+                    project_path: Some(cwd.to_string()),
+                    status: SessionStatus::Active,
+                    usage: None,
+                    summary: None,
+                    files: Vec::new(),
+                    interrupted_turns: Vec::new(),
+                    last_read_message_id: None,
+                    last_read_line: None,
+                    metadata: BTreeMap::new(),
+                    time: BTreeMap::new(),
+                },
+            );
+        }
+        registry.workspaces = HashMap::from([(
+            "workspace".to_string(),
+            WorkspaceRegistry {
+                project_path: Some(cwd.to_string()),
+                visible_session_ids: visible,
+            },
+        )]);
+        Self { registry }
+    }
+
+    /// Write the registry to `path` as the app saves threads.json.
+    pub fn write(&self, path: &Path) {
+        write_registry(&path.to_path_buf(), &self.registry).unwrap();
+    }
+}
@@ -0,0 +1,36 @@
+//! TypeScript bindings for every command in `crate::commands`, written by
+//! tauri-specta to `src/services/bindings.ts`. The file is not committed:
+//! `npm run bindings` (run by the `dev`, `build` and `typecheck` scripts)
+//! generates it through `cargo test`, and debug builds regenerate it on
+//! launch, so a renamed command or argument fails `tsc` instead of failing
+//! at runtime.
+
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use tauri_specta::Builder;
+
+const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/services/bindings.ts");
+
+/// Write `src/services/bindings.ts`. Counts, sizes and timestamps are
+/// 64-bit here but always fit in a JS number.
+pub(crate) fn export(commands: &Builder<tauri::Wry>) -> Result<(), String> {
+    commands
+        .export(
+            Typescript::default().bigint(BigIntExportBehavior::Number),
+            BINDINGS_PATH,
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export, BINDINGS_PATH};
+
+    #[test]
+    fn exports_typescript_bindings() {
+        export(&crate::commands()).unwrap();
+        let bindings = std::fs::read_to_string(BINDINGS_PATH).unwrap();
+        assert!(bindings.contains("async listWorkspaces()"));
+        assert!(bindings.contains("export type WorkspaceInfo"));
+        assert!(bindings.contains("export type BridgeDiedPayload"));
+    }
+}
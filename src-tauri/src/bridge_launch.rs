@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::types::{BridgeLaunchSettings, BridgeRuntime};
+
+/// Stands for the bridge script in custom runtime args.
+const BRIDGE_PLACEHOLDER: &str = "{bridge}";
+
+/// The resolved command line for the bridge process.
+#[derive(Debug, PartialEq)]
+pub(crate) struct BridgeCommand {
+    pub(crate) program: String,
+    pub(crate) args: Vec<String>,
+    pub(crate) env: BTreeMap<String, String>,
+    pub(crate) script: PathBuf,
+}
+
+impl BridgeCommand {
+    pub(crate) fn display(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Build the bridge command for `launch`, with `bridge_path` the bundled
+/// TypeScript entry point.
+pub(crate) fn bridge_command(
+    launch: &BridgeLaunchSettings,
+    bridge_path: &Path,
+) -> Result<BridgeCommand, String> {
+    if let Some(key) = launch
+        .env
+        .keys()
+        .find(|key| key.is_empty() || key.contains(['=', '\0']))
+    {
+        return Err(format!("Invalid bridge environment variable name: {key:?}"));
+    }
+    let script = match non_empty(launch.script.as_deref()) {
+        Some(script) => PathBuf::from(script),
+        None if launch.runtime == BridgeRuntime::Node => bridge_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("dist")
+            .join("index.js"),
+        None => bridge_path.to_path_buf(),
+    };
+    let script_arg = script.to_string_lossy().to_string();
+    let (program, args) = match launch.runtime {
+        BridgeRuntime::Tsx => {
+            let mut args = vec!["tsx".to_string()];
+            args.extend(launch.args.iter().cloned());
+            args.push(script_arg);
+            ("npx".to_string(), args)
+        }
+        BridgeRuntime::Bun | BridgeRuntime::Node => {
+            let mut args = launch.args.clone();
+            args.push(script_arg);
+            let program = if launch.runtime == BridgeRuntime::Bun {
+                "bun"
+            } else {
+                "node"
+            };
+            (program.to_string(), args)
+        }
+        BridgeRuntime::Custom => {
+            let program = non_empty(launch.command.as_deref())
+                .ok_or("The custom bridge runtime needs a command")?;
+            let mut args: Vec<String> = launch
+                .args
+                .iter()
+                .map(|arg| arg.replace(BRIDGE_PLACEHOLDER, &script_arg))
+                .collect();
+            if !launch
+                .args
+                .iter()
+                .any(|arg| arg.contains(BRIDGE_PLACEHOLDER))
+            {
+                args.push(script_arg);
+            }
+            (program.to_string(), args)
+        }
+    };
+    Ok(BridgeCommand {
+        program,
+        args,
+        env: launch.env.clone(),
+        script,
+    })
+}
+
+/// Whether `program` resolves to a file, directly or through `path_env`.
+fn find_program(program: &str, path_env: Option<&str>) -> bool {
+    if program.contains('/') {
+        return Path::new(program).is_file();
+    }
+    path_env
+        .unwrap_or_default()
+        .split(':')
+        .filter(|dir| !dir.is_empty())
+        .any(|dir| Path::new(dir).join(program).is_file())
+}
+
+/// Doctor section for the bridge launch settings: the command resolves, the
+/// script exists, and the runtime answers `--version`. Custom commands are
+/// not run.
+pub(crate) async fn check_bridge_launch(
+    launch: &BridgeLaunchSettings,
+    bridge_path: Result<String, String>,
+    path_env: Option<&str>,
+) -> Value {
+    let command = bridge_path.and_then(|path| bridge_command(launch, Path::new(&path)));
+    let command = match command {
+        Ok(command) => command,
+        Err(err) => {
+            return json!({
+                "bridgeOk": false,
+                "bridgeCommand": null,
+                "bridgeVersion": null,
+                "bridgeDetails": err,
+            });
+        }
+    };
+    let (ok, version, details) = if !command.script.is_file() {
+        (
+            false,
+            None,
+            Some(format!(
+                "Bridge script not found: {}",
+                command.script.display()
+            )),
+        )
+    } else if !find_program(&command.program, path_env) {
+        (
+            false,
+            None,
+            Some(format!("{} not found on PATH.", command.program)),
+        )
+    } else if launch.runtime == BridgeRuntime::Custom {
+        (true, None, None)
+    } else {
+        let mut version_command = Command::new(&command.program);
+        if let Some(path) = path_env {
+            version_command.env("PATH", path);
+        }
+        version_command.arg("--version");
+        match timeout(Duration::from_secs(5), version_command.output()).await {
+            Ok(Ok(output)) if output.status.success() => {
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                (true, Some(version).filter(|v| !v.is_empty()), None)
+            }
+            Ok(Ok(output)) => (
+                false,
+                None,
+                Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            ),
+            Ok(Err(err)) if err.kind() == ErrorKind::NotFound => (
+                false,
+                None,
+                Some(format!("{} not found on PATH.", command.program)),
+            ),
+            Ok(Err(err)) => (false, None, Some(err.to_string())),
+            Err(_) => (
+                false,
+                None,
+                Some(format!("Timed out while checking {}.", command.program)),
+            ),
+        }
+    };
+    json!({
+        "bridgeOk": ok,
+        "bridgeCommand": command.display(),
+        "bridgeVersion": version,
+        "bridgeDetails": details,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    use super::bridge_command;
+    use crate::types::{BridgeLaunchSettings, BridgeRuntime};
+
+    const BRIDGE: &str = "/app/claude-bridge/index.ts";
+
+    fn launch(runtime: BridgeRuntime, args: &[&str]) -> BridgeLaunchSettings {
+        BridgeLaunchSettings {
+            runtime,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            ..BridgeLaunchSettings::default()
+        }
+    }
+
+    fn command_line(launch: &BridgeLaunchSettings) -> String {
+        bridge_command(launch, Path::new(BRIDGE)).unwrap().display()
+    }
+
+    #[test]
+    fn builds_the_command_for_each_runtime() {
+        assert_eq!(
+            command_line(&BridgeLaunchSettings::default()),
+            "npx tsx /app/claude-bridge/index.ts"
+        );
+        assert_eq!(
+            command_line(&launch(BridgeRuntime::Bun, &["--smol"])),
+            "bun --smol /app/claude-bridge/index.ts"
+        );
+        assert_eq!(
+            command_line(&launch(BridgeRuntime::Node, &[])),
+            "node /app/claude-bridge/dist/index.js"
+        );
+
+        let mut custom = launch(BridgeRuntime::Custom, &["run", "{bridge}", "--quiet"]);
+        assert!(bridge_command(&custom, Path::new(BRIDGE)).is_err());
+        custom.command = Some("deno".to_string());
+        assert_eq!(
+            command_line(&custom),
+            "deno run /app/claude-bridge/index.ts --quiet"
+        );
+        custom.args.clear();
+        custom.script = Some("/opt/bridge.js".to_string());
+        assert_eq!(command_line(&custom), "deno /opt/bridge.js");
+    }
+
+    #[test]
+    fn rejects_invalid_env_names() {
+        let mut settings = BridgeLaunchSettings {
+            env: BTreeMap::from([(
+                "NODE_OPTIONS".to_string(),
+                "--max-old-space-size=4096".to_string(),
+            )]),
+            ..BridgeLaunchSettings::default()
+        };
+        assert!(bridge_command(&settings, Path::new(BRIDGE)).is_ok());
+        settings.env.insert("A=B".to_string(), String::new());
+        assert!(bridge_command(&settings, Path::new(BRIDGE)).is_err());
+    }
+}
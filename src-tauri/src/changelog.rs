@@ -0,0 +1,521 @@
+use std::collections::{HashMap, HashSet};
+
+use git2::{BranchType, Oid, Repository, Sort};
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::digest::session_title;
+use crate::git::open_workspace_repo;
+use crate::state::AppState;
+use crate::types::SessionEntry;
+
+/// Commits made this long after a session's last activity still count as
+/// its work, e.g. a commit after reviewing the diff.
+const COMMIT_GRACE_MS: i64 = 2 * 60 * 60 * 1000;
+/// Commits past this are left out of the draft.
+const MAX_COMMITS: usize = 500;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+#[specta(rename = "ChangelogKind")]
+pub(crate) enum ChangeKind {
+    Feature,
+    Fix,
+    Other,
+}
+
+impl ChangeKind {
+    fn heading(self) -> &'static str {
+        match self {
+            ChangeKind::Feature => "Features",
+            ChangeKind::Fix => "Fixes",
+            ChangeKind::Other => "Other changes",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChangelogSession {
+    pub(crate) session_id: String,
+    pub(crate) title: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChangelogEntry {
+    pub(crate) sha: String,
+    /// Commit summary without its conventional-commit prefix.
+    pub(crate) summary: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    pub(crate) kind: ChangeKind,
+    /// Sessions that likely produced the commit; empty for manual commits.
+    pub(crate) sessions: Vec<ChangelogSession>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Changelog {
+    pub(crate) since_ref: String,
+    /// Newest first, merge commits left out.
+    pub(crate) entries: Vec<ChangelogEntry>,
+    /// True when more than `MAX_COMMITS` commits followed `since_ref`.
+    pub(crate) truncated: bool,
+    /// The entries as Markdown, grouped by kind.
+    pub(crate) markdown: String,
+}
+
+/// A non-merge commit after `since_ref`.
+struct CommitInfo {
+    oid: Oid,
+    summary: String,
+    author: String,
+    timestamp: i64,
+    /// Repo-relative paths.
+    files: Vec<String>,
+}
+
+/// A session that may have produced commits: its worktree branch, if any,
+/// and the files it edited.
+struct SessionCandidate {
+    session_id: String,
+    title: String,
+    branch: Option<String>,
+    started_at: i64,
+    last_activity: i64,
+    edited: Vec<String>,
+}
+
+impl SessionCandidate {
+    fn from_entry(entry: &SessionEntry, branch: Option<String>) -> Self {
+        Self {
+            session_id: entry.session_id.clone(),
+            title: session_title(entry),
+            branch,
+            started_at: entry.created_at as i64,
+            last_activity: entry.last_activity as i64,
+            edited: entry
+                .files
+                .iter()
+                .filter(|file| file.edits > 0)
+                .map(|file| file.path.clone())
+                .collect(),
+        }
+    }
+
+    fn active_at(&self, timestamp: i64) -> bool {
+        self.started_at <= timestamp && timestamp <= self.last_activity + COMMIT_GRACE_MS
+    }
+}
+
+/// Whether two paths name the same file, one possibly relative to a
+/// subdirectory of the other's root.
+fn same_file(a: &str, b: &str) -> bool {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    long == short || long.ends_with(&format!("/{short}"))
+}
+
+/// Split off a conventional-commit prefix (`fix(ui): ...`) and classify the
+/// commit, falling back to its first word.
+fn classify(summary: &str) -> (ChangeKind, String) {
+    let (kind, rest) = match summary.split_once(':') {
+        Some((prefix, rest)) => {
+            let kind = match prefix
+                .split(['(', '!'])
+                .next()
+                .unwrap_or("")
+                .to_lowercase()
+                .as_str()
+            {
+                "feat" | "feature" => Some(ChangeKind::Feature),
+                "fix" | "bugfix" | "hotfix" => Some(ChangeKind::Fix),
+                "build" | "chore" | "ci" | "docs" | "perf" | "refactor" | "revert" | "style"
+                | "test" => Some(ChangeKind::Other),
+                _ => None,
+            };
+            match kind {
+                Some(kind) => (Some(kind), rest.trim()),
+                None => (None, summary.trim()),
+            }
+        }
+        None => (None, summary.trim()),
+    };
+    let kind = kind.unwrap_or_else(|| {
+        let first = rest.split_whitespace().next().unwrap_or("").to_lowercase();
+        match first.as_str() {
+            "add" | "adds" | "added" | "implement" | "implements" | "introduce" | "support" => {
+                ChangeKind::Feature
+            }
+            "fix" | "fixes" | "fixed" | "resolve" | "resolves" | "correct" | "prevent" => {
+                ChangeKind::Fix
+            }
+            _ => ChangeKind::Other,
+        }
+    });
+    let mut chars = rest.chars();
+    let summary = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    (kind, summary)
+}
+
+/// Sessions that produced `commit`. A commit on a worktree branch belongs
+/// to that worktree's sessions; any other commit to the sessions active
+/// around its time that edited one of its files.
+fn attribute<'a>(
+    commit: &CommitInfo,
+    branches: &[&str],
+    sessions: &'a [SessionCandidate],
+) -> Vec<&'a SessionCandidate> {
+    let on_branch: Vec<&SessionCandidate> = sessions
+        .iter()
+        .filter(|session| {
+            session
+                .branch
+                .as_deref()
+                .is_some_and(|branch| branches.contains(&branch))
+        })
+        .collect();
+    if !on_branch.is_empty() {
+        let active: Vec<_> = on_branch
+            .iter()
+            .copied()
+            .filter(|session| session.active_at(commit.timestamp))
+            .collect();
+        return if active.is_empty() { on_branch } else { active };
+    }
+    sessions
+        .iter()
+        .filter(|session| session.active_at(commit.timestamp))
+        .filter(|session| {
+            session.edited.iter().any(|edited| {
+                commit
+                    .files
+                    .iter()
+                    .any(|changed| same_file(changed, edited))
+            })
+        })
+        .collect()
+}
+
+fn render_markdown(entries: &[ChangelogEntry]) -> String {
+    let mut markdown = String::new();
+    for kind in [ChangeKind::Feature, ChangeKind::Fix, ChangeKind::Other] {
+        let lines: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .map(|entry| {
+                format!(
+                    "- {} ({})",
+                    entry.summary,
+                    &entry.sha[..7.min(entry.sha.len())]
+                )
+            })
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        if !markdown.is_empty() {
+            markdown.push('\n');
+        }
+        markdown.push_str(&format!("## {}\n\n{}\n", kind.heading(), lines.join("\n")));
+    }
+    markdown
+}
+
+/// Non-merge commits reachable from HEAD but not `since_ref`, newest first.
+fn commits_since(repo: &Repository, since_ref: &str) -> Result<(Vec<CommitInfo>, bool), String> {
+    let since = repo
+        .revparse_single(since_ref)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| format!("Unknown revision: {since_ref}"))?;
+    let mut walk = repo.revwalk().map_err(|e| e.to_string())?;
+    walk.push_head().map_err(|e| e.to_string())?;
+    walk.hide(since.id()).map_err(|e| e.to_string())?;
+    walk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
+    let mut commits = Vec::new();
+    for oid in walk {
+        let commit = repo
+            .find_commit(oid.map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        if commits.len() == MAX_COMMITS {
+            return Ok((commits, true));
+        }
+        let parent_tree = commit
+            .parents()
+            .next()
+            .map(|parent| parent.tree())
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        let tree = commit.tree().map_err(|e| e.to_string())?;
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| e.to_string())?;
+        let files = diff
+            .deltas()
+            .filter_map(|delta| {
+                delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())
+                    .and_then(|path| path.to_str())
+                    .map(str::to_string)
+            })
+            .collect();
+        commits.push(CommitInfo {
+            oid: commit.id(),
+            summary: commit.summary().unwrap_or("").to_string(),
+            author: commit.author().name().unwrap_or("").to_string(),
+            timestamp: commit.time().seconds() * 1000,
+            files,
+        });
+    }
+    Ok((commits, false))
+}
+
+/// Commits on each still-present local branch in `branches` since
+/// `since_ref`, keyed by commit.
+fn branch_commits(
+    repo: &Repository,
+    since_ref: &str,
+    branches: &HashSet<String>,
+) -> HashMap<Oid, Vec<String>> {
+    let mut by_commit: HashMap<Oid, Vec<String>> = HashMap::new();
+    let Ok(since) = repo
+        .revparse_single(since_ref)
+        .and_then(|object| object.peel_to_commit())
+    else {
+        return by_commit;
+    };
+    for name in branches {
+        let Some(tip) = repo
+            .find_branch(name, BranchType::Local)
+            .ok()
+            .and_then(|branch| branch.get().target())
+        else {
+            continue;
+        };
+        let Ok(mut walk) = repo.revwalk() else {
+            continue;
+        };
+        if walk.push(tip).is_err() || walk.hide(since.id()).is_err() {
+            continue;
+        }
+        for oid in walk.take(MAX_COMMITS).flatten() {
+            by_commit.entry(oid).or_default().push(name.clone());
+        }
+    }
+    by_commit
+}
+
+fn build_changelog(
+    path: &str,
+    since_ref: &str,
+    sessions: &[SessionCandidate],
+) -> Result<Changelog, String> {
+    let (repo, _) = open_workspace_repo(path)?;
+    let (commits, truncated) = commits_since(&repo, since_ref)?;
+    let branches: HashSet<String> = sessions
+        .iter()
+        .filter_map(|session| session.branch.clone())
+        .collect();
+    let on_branches = branch_commits(&repo, since_ref, &branches);
+    let entries: Vec<ChangelogEntry> = commits
+        .iter()
+        .map(|commit| {
+            let branches: Vec<&str> = on_branches
+                .get(&commit.oid)
+                .map(|names| names.iter().map(String::as_str).collect())
+                .unwrap_or_default();
+            let (kind, summary) = classify(&commit.summary);
+            ChangelogEntry {
+                sha: commit.oid.to_string(),
+                summary,
+                author: commit.author.clone(),
+                timestamp: commit.timestamp,
+                kind,
+                sessions: attribute(commit, &branches, sessions)
+                    .into_iter()
+                    .map(|session| ChangelogSession {
+                        session_id: session.session_id.clone(),
+                        title: session.title.clone(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    Ok(Changelog {
+        since_ref: since_ref.to_string(),
+        markdown: render_markdown(&entries),
+        entries,
+        truncated,
+    })
+}
+
+/// Draft release notes for the commits since `since_ref` (a tag, branch or
+/// sha), grouped into features, fixes and other changes. Each commit lists
+/// the sessions of the workspace or its worktrees that produced it.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn generate_changelog(
+    workspace_id: String,
+    since_ref: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Changelog, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
+    workspace_changelog(&state, &workspace_id, &since_ref).await
+}
+
+/// The changelog of `workspace_id` since `since_ref`. Callers hold the
+/// workspace's git lock.
+pub(crate) async fn workspace_changelog(
+    state: &AppState,
+    workspace_id: &str,
+    since_ref: &str,
+) -> Result<Changelog, String> {
+    let (path, branches) = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces.get(workspace_id).ok_or("workspace not found")?;
+        let branches: HashMap<String, Option<String>> = workspaces
+            .values()
+            .filter(|other| {
+                other.id == workspace_id || other.parent_id.as_deref() == Some(workspace_id)
+            })
+            .map(|other| {
+                (
+                    other.id.clone(),
+                    other.worktree.as_ref().map(|info| info.branch.clone()),
+                )
+            })
+            .collect();
+        (entry.path.clone(), branches)
+    };
+    let sessions: Vec<SessionCandidate> = {
+        let registry = state.registry.lock().await;
+        branches
+            .iter()
+            .filter_map(|(id, branch)| Some((registry.workspaces.get(id)?, branch)))
+            .flat_map(|(workspace, branch)| {
+                workspace
+                    .visible_session_ids
+                    .iter()
+                    .filter_map(|id| registry.sessions.get(id))
+                    .map(|entry| SessionCandidate::from_entry(entry, branch.clone()))
+            })
+            .collect()
+    };
+    let since_ref = since_ref.trim().to_string();
+    tokio::task::spawn_blocking(move || build_changelog(&path, &since_ref, &sessions))
+        .await
+        .map_err(|_| "changelog generation failed".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::Oid;
+
+    use super::{attribute, classify, render_markdown, ChangeKind, CommitInfo, SessionCandidate};
+
+    fn session(id: &str, branch: Option<&str>, edited: &[&str]) -> SessionCandidate {
+        SessionCandidate {
+            session_id: id.to_string(),
+            title: id.to_string(),
+            branch: branch.map(str::to_string),
+            started_at: 1_000,
+            last_activity: 5_000,
+            edited: edited.iter().map(|path| path.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn classifies_commit_summaries() {
+        assert_eq!(
+            classify("feat(ui): snippet picker"),
+            (ChangeKind::Feature, "Snippet picker".to_string())
+        );
+        assert_eq!(
+            classify("Fix crash on wake"),
+            (ChangeKind::Fix, "Fix crash on wake".to_string())
+        );
+        assert_eq!(
+            classify("chore: bump deps"),
+            (ChangeKind::Other, "Bump deps".to_string())
+        );
+        assert_eq!(
+            classify("Note: nothing to see"),
+            (ChangeKind::Other, "Note: nothing to see".to_string())
+        );
+        assert_eq!(
+            classify("Add glossary: per workspace"),
+            (
+                ChangeKind::Feature,
+                "Add glossary: per workspace".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn attributes_commits_to_sessions() {
+        let sessions = [
+            session("main", None, &["src/lib.rs"]),
+            session("worktree", Some("feature-x"), &[]),
+        ];
+        let commit = |timestamp, files: &[&str]| CommitInfo {
+            oid: Oid::zero(),
+            summary: String::new(),
+            author: String::new(),
+            timestamp,
+            files: files.iter().map(|path| path.to_string()).collect(),
+        };
+        let ids = |found: Vec<&SessionCandidate>| -> Vec<String> {
+            found.iter().map(|s| s.session_id.clone()).collect()
+        };
+
+        // A workspace in a repo subdirectory records paths relative to it.
+        assert_eq!(
+            ids(attribute(
+                &commit(2_000, &["app/src/lib.rs"]),
+                &[],
+                &sessions
+            )),
+            ["main"]
+        );
+        assert!(attribute(&commit(2_000, &["app/src/main.rs"]), &[], &sessions).is_empty());
+        assert!(attribute(&commit(500, &["src/lib.rs"]), &[], &sessions).is_empty());
+        assert_eq!(
+            ids(attribute(
+                &commit(99_999_999, &["README.md"]),
+                &["feature-x"],
+                &sessions
+            )),
+            ["worktree"]
+        );
+    }
+
+    #[test]
+    fn renders_grouped_markdown() {
+        let entry = |sha: &str, summary: &str, kind| super::ChangelogEntry {
+            sha: sha.to_string(),
+            summary: summary.to_string(),
+            author: String::new(),
+            timestamp: 0,
+            kind,
+            sessions: Vec::new(),
+        };
+        let markdown = render_markdown(&[
+            entry("aaaaaaaaaa", "Fix crash", ChangeKind::Fix),
+            entry("bbbbbbbbbb", "Snippets", ChangeKind::Feature),
+        ]);
+        assert_eq!(
+            markdown,
+            "## Features\n\n- Snippets (bbbbbbb)\n\n## Fixes\n\n- Fix crash (aaaaaaa)\n"
+        );
+    }
+}
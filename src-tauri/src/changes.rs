@@ -0,0 +1,399 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::git::workspace_diffs;
+use crate::state::AppState;
+use crate::types::GitFileDiff;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ChangeKind {
+    Source,
+    Tests,
+    Config,
+    Docs,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileChangeSummary {
+    path: String,
+    language: String,
+    kind: ChangeKind,
+    /// "added", "deleted" or "modified".
+    change: String,
+    additions: usize,
+    deletions: usize,
+    /// Public items introduced by added lines (functions, types, exports).
+    new_symbols: Vec<String>,
+    removed_symbols: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChangeTotals {
+    files: usize,
+    additions: usize,
+    deletions: usize,
+}
+
+impl ChangeTotals {
+    fn add(&mut self, file: &FileChangeSummary) {
+        self.files += 1;
+        self.additions += file.additions;
+        self.deletions += file.deletions;
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChangeSummary {
+    files: Vec<FileChangeSummary>,
+    by_language: BTreeMap<String, ChangeTotals>,
+    by_kind: BTreeMap<String, ChangeTotals>,
+    totals: ChangeTotals,
+    /// One-line description suitable for a PR body.
+    headline: String,
+}
+
+fn language_for_path(path: &str) -> &'static str {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    if file_name == "Dockerfile" {
+        return "Docker";
+    }
+    if file_name == "Makefile" {
+        return "Make";
+    }
+    let extension = file_name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+    match extension.to_ascii_lowercase().as_str() {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" => "Python",
+        "go" => "Go",
+        "swift" => "Swift",
+        "kt" | "kts" => "Kotlin",
+        "java" => "Java",
+        "rb" => "Ruby",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" => "C++",
+        "cs" => "C#",
+        "css" | "scss" | "sass" => "CSS",
+        "html" | "htm" => "HTML",
+        "sh" | "bash" | "zsh" => "Shell",
+        "sql" => "SQL",
+        "md" | "mdx" => "Markdown",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "yaml" | "yml" => "YAML",
+        _ => "Other",
+    }
+}
+
+fn kind_for_path(path: &str) -> ChangeKind {
+    let lower = path.to_ascii_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+    let in_dir =
+        |dir: &str| lower.starts_with(&format!("{dir}/")) || lower.contains(&format!("/{dir}/"));
+
+    if in_dir("tests")
+        || in_dir("test")
+        || in_dir("__tests__")
+        || file_name.starts_with("test_")
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        || file_name.contains("_test.")
+    {
+        return ChangeKind::Tests;
+    }
+    if in_dir("docs")
+        || file_name.ends_with(".md")
+        || file_name.ends_with(".mdx")
+        || file_name.ends_with(".rst")
+        || file_name.ends_with(".txt")
+        || file_name.starts_with("license")
+    {
+        return ChangeKind::Docs;
+    }
+    if lower.starts_with(".github/")
+        || file_name.starts_with('.')
+        || file_name == "dockerfile"
+        || file_name == "makefile"
+        || file_name.ends_with(".lock")
+        || [
+            ".toml", ".json", ".yaml", ".yml", ".ini", ".cfg", ".conf", ".plist",
+        ]
+        .iter()
+        .any(|ext| file_name.ends_with(ext))
+    {
+        return ChangeKind::Config;
+    }
+    ChangeKind::Source
+}
+
+/// Name of the public item declared on a line, for the handful of
+/// declaration shapes we recognise per language.
+fn declared_symbol(language: &str, line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    let prefixes: &[&str] = match language {
+        "Rust" => &[
+            "pub fn ",
+            "pub async fn ",
+            "pub struct ",
+            "pub enum ",
+            "pub trait ",
+            "pub type ",
+            "pub const ",
+            "pub mod ",
+            "pub(crate) fn ",
+            "pub(crate) async fn ",
+            "pub(crate) struct ",
+            "pub(crate) enum ",
+            "pub(crate) trait ",
+        ],
+        "TypeScript" | "JavaScript" => &[
+            "export default function ",
+            "export async function ",
+            "export function ",
+            "export class ",
+            "export interface ",
+            "export type ",
+            "export const ",
+            "export enum ",
+        ],
+        // Only top-level definitions count as API in Python.
+        "Python" if indent == 0 => &["def ", "async def ", "class "],
+        "Go" => &["func ", "type "],
+        "Swift" => &[
+            "public func ",
+            "public struct ",
+            "public class ",
+            "public enum ",
+        ],
+        _ => &[],
+    };
+    let rest = prefixes
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))?;
+    // Go methods: `func (r *Recv) Name(`.
+    let rest = if language == "Go" && rest.starts_with('(') {
+        rest.split_once(')')?.1.trim_start()
+    } else {
+        rest
+    };
+    let name: String = rest
+        .chars()
+        .take_while(|ch| ch.is_alphanumeric() || *ch == '_')
+        .collect();
+    if name.is_empty() || name.starts_with('_') {
+        return None;
+    }
+    if language == "Go" && !name.starts_with(|ch: char| ch.is_ascii_uppercase()) {
+        return None;
+    }
+    Some(name)
+}
+
+fn summarize_file(diff: &GitFileDiff) -> FileChangeSummary {
+    let language = language_for_path(&diff.path);
+    let mut change = "modified";
+    let mut additions = 0;
+    let mut deletions = 0;
+    let mut new_symbols = Vec::new();
+    let mut removed_symbols = Vec::new();
+    // `+++`/`---` are file headers only before the first hunk; inside one
+    // they are content lines such as `+++i` or a removed `-- comment`.
+    let mut in_hunk = false;
+    for line in diff.diff.lines() {
+        if line.starts_with("@@") {
+            in_hunk = true;
+        } else if !in_hunk {
+            if line.starts_with("new file mode") {
+                change = "added";
+            } else if line.starts_with("deleted file mode") {
+                change = "deleted";
+            }
+        } else if let Some(added) = line.strip_prefix('+') {
+            additions += 1;
+            if let Some(symbol) = declared_symbol(language, added) {
+                new_symbols.push(symbol);
+            }
+        } else if let Some(removed) = line.strip_prefix('-') {
+            deletions += 1;
+            if let Some(symbol) = declared_symbol(language, removed) {
+                removed_symbols.push(symbol);
+            }
+        }
+    }
+    // A symbol on both sides was edited in place, not added or removed.
+    let edited: Vec<String> = new_symbols
+        .iter()
+        .filter(|symbol| removed_symbols.contains(symbol))
+        .cloned()
+        .collect();
+    new_symbols.retain(|symbol| !edited.contains(symbol));
+    removed_symbols.retain(|symbol| !edited.contains(symbol));
+
+    FileChangeSummary {
+        path: diff.path.clone(),
+        language: language.to_string(),
+        kind: kind_for_path(&diff.path),
+        change: change.to_string(),
+        additions,
+        deletions,
+        new_symbols,
+        removed_symbols,
+    }
+}
+
+fn kind_label(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Source => "source",
+        ChangeKind::Tests => "tests",
+        ChangeKind::Config => "config",
+        ChangeKind::Docs => "docs",
+    }
+}
+
+pub(crate) fn summarize_diffs(diffs: &[GitFileDiff]) -> ChangeSummary {
+    let mut summary = ChangeSummary::default();
+    for diff in diffs {
+        let file = summarize_file(diff);
+        summary
+            .by_language
+            .entry(file.language.clone())
+            .or_default()
+            .add(&file);
+        summary
+            .by_kind
+            .entry(kind_label(file.kind).to_string())
+            .or_default()
+            .add(&file);
+        summary.totals.add(&file);
+        summary.files.push(file);
+    }
+
+    let new_api: usize = summary.files.iter().map(|f| f.new_symbols.len()).sum();
+    let mut parts = vec![format!(
+        "{} file{} changed (+{} -{})",
+        summary.totals.files,
+        if summary.totals.files == 1 { "" } else { "s" },
+        summary.totals.additions,
+        summary.totals.deletions
+    )];
+    if new_api > 0 {
+        parts.push(format!(
+            "{new_api} new public item{}",
+            if new_api == 1 { "" } else { "s" }
+        ));
+    }
+    let kinds: Vec<String> = summary
+        .by_kind
+        .iter()
+        .map(|(kind, totals)| format!("{kind}: {}", totals.files))
+        .collect();
+    if !kinds.is_empty() {
+        parts.push(kinds.join(", "));
+    }
+    summary.headline = parts.join("; ");
+    summary
+}
+
+/// Classify the working diff by language and kind for the review panel and
+/// PR descriptions.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn summarize_changes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ChangeSummary, String> {
+    let path = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .path
+            .clone()
+    };
+    tokio::task::spawn_blocking(move || workspace_diffs(&path).map(|diffs| summarize_diffs(&diffs)))
+        .await
+        .map_err(|_| "change summary failed".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{declared_symbol, kind_for_path, summarize_diffs, ChangeKind};
+    use crate::types::GitFileDiff;
+
+    #[test]
+    fn classifies_paths_by_kind() {
+        assert_eq!(kind_for_path("src/lib.rs"), ChangeKind::Source);
+        assert_eq!(kind_for_path("src/utils/format.test.ts"), ChangeKind::Tests);
+        assert_eq!(kind_for_path("crates/core/tests/api.rs"), ChangeKind::Tests);
+        assert_eq!(kind_for_path("README.md"), ChangeKind::Docs);
+        assert_eq!(kind_for_path("Cargo.toml"), ChangeKind::Config);
+        assert_eq!(
+            kind_for_path(".github/workflows/ci.yml"),
+            ChangeKind::Config
+        );
+    }
+
+    #[test]
+    fn detects_declarations() {
+        assert_eq!(
+            declared_symbol("Rust", "pub fn run() {"),
+            Some("run".to_string())
+        );
+        assert_eq!(
+            declared_symbol("TypeScript", "export const useThing = () => {"),
+            Some("useThing".to_string())
+        );
+        assert_eq!(declared_symbol("Python", "    def helper():"), None);
+        assert_eq!(
+            declared_symbol("Go", "func (s *Server) Start() error {"),
+            Some("Start".to_string())
+        );
+        assert_eq!(declared_symbol("Go", "func start() {"), None);
+    }
+
+    #[test]
+    fn summarizes_added_file() {
+        let diff = GitFileDiff {
+            path: "src/api.rs".to_string(),
+            diff: "diff --git a/src/api.rs b/src/api.rs\nnew file mode 100644\n--- /dev/null\n+++ b/src/api.rs\n@@ -0,0 +1,2 @@\n+pub struct Client;\n+pub fn connect() {}\n".to_string(),
+            owners: Vec::new(),
+            highlighted: None,
+            intraline: None,
+            truncated: None,
+            lfs: None,
+        };
+        let summary = summarize_diffs(&[diff]);
+        assert_eq!(summary.files[0].change, "added");
+        assert_eq!(summary.files[0].additions, 2);
+        assert_eq!(summary.files[0].new_symbols, vec!["Client", "connect"]);
+        assert_eq!(summary.by_language["Rust"].files, 1);
+        assert_eq!(summary.by_kind["source"].additions, 2);
+    }
+
+    #[test]
+    fn counts_content_lines_that_look_like_headers() {
+        let diff = GitFileDiff {
+            path: "schema.sql".to_string(),
+            diff: "diff --git a/schema.sql b/schema.sql\n--- a/schema.sql\n+++ b/schema.sql\n@@ -1,2 +1,2 @@\n--- old comment\n+++ new comment\n select 1;\n".to_string(),
+            owners: Vec::new(),
+            highlighted: None,
+            intraline: None,
+            truncated: None,
+            lfs: None,
+        };
+        let summary = summarize_diffs(&[diff]);
+        assert_eq!(summary.files[0].change, "modified");
+        assert_eq!(summary.files[0].additions, 1);
+        assert_eq!(summary.files[0].deletions, 1);
+    }
+}
@@ -1,25 +1,62 @@
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::io::ErrorKind;
 use std::path::Path;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, ChildStdin, Command};
+use tokio::io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
 use tokio::sync::{oneshot, Mutex};
-use tokio::time::timeout;
-
+use tokio::time::{sleep, timeout};
+
+use crate::accounts::resolve_account_context;
+use crate::auto_approve::auto_approve;
+use crate::badge::show_activity;
+use crate::bridge_launch::{bridge_command, check_bridge_launch};
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::crash::log_line;
+use crate::deep_link::refresh_recent_menu;
+use crate::events::{
+    BridgeDiedPayload, BridgeReconnectedPayload, BridgeStderrPayload, ClaudeEventPayload,
+    ErrorPayload, UsageUpdatedPayload,
+};
+use crate::focus::hold_during_focus;
+use crate::glossary::glossary_context;
+use crate::i18n::tr;
+use crate::lan_share::broadcast;
+use crate::message_queue::{advance_queue, clear_queue};
+use crate::power::ensure_session_slot;
+use crate::prompt_history::record_prompt;
+use crate::providers::{check_provider, gateway_env, missing_provider_fields, provider_env};
+use crate::proxy::apply_network_env;
+use crate::read_receipts::refresh_unread;
 use crate::registry::{derive_project_paths, now_millis, write_registry};
-use crate::types::{SessionEntry, SessionStatus, WorkspaceRegistry};
+use crate::replay::capture_event;
+use crate::session_files::record_tool_files;
+use crate::settings::ensure_not_observer;
+use crate::slack::{mirror_event, mirror_user_message};
+use crate::team_prompts::team_permissions;
+use crate::telemetry::record_event;
+use crate::time_tracking::add_agent_time;
+use crate::turn_lanes::{
+    acquire_turn_slot, clear_turn_slots, close_turn_slots, release_turn_slot, Lane,
+};
+use crate::turns::save_interrupted_turn;
+use crate::types::{
+    BridgeRuntime, ClaudeAuthMode, ProcessPriority, SessionEntry, SessionStatus, SessionUsage,
+    WorkspaceRegistry,
+};
+use crate::workflows::{session_closed, turn_finished, turn_lost};
 
 /// Event emitted to the frontend from the Claude bridge.
 /// Flattened structure for frontend consumption.
-#[derive(Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct ClaudeEvent {
     /// Event type (e.g., "session/started", "message/delta")
@@ -32,7 +69,7 @@ pub struct ClaudeEvent {
     /// Event timestamp (ms)
     pub timestamp: i64,
     /// Event-specific payload
-    pub payload: Value,
+    pub payload: ClaudeEventPayload,
 }
 
 /// Information about a Claude session.
@@ -42,17 +79,129 @@ pub struct ClaudeSessionInfo {
     pub workspace_id: String,
     pub cwd: String,
     pub started_at: u64,
+    /// `totalCostUsd` from the session's last `result`. The SDK reports a
+    /// running total for the bridge session, not the cost of the turn.
+    #[serde(default)]
+    pub reported_cost_usd: f64,
+}
+
+const INIT_TIMEOUT: Duration = Duration::from_secs(30);
+/// First wait before restarting a bridge that exited on its own.
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+/// Restart attempts after an unexpected exit before giving up.
+const MAX_RESTART_ATTEMPTS: u32 = 8;
+/// A bridge that stays up this long resets the restart backoff; one that
+/// dies sooner continues it.
+const HEALTHY_UPTIME: Duration = Duration::from_secs(60);
+
+/// Error codes for requests that ended without a reply. They lead the
+/// error text as `CODE: message` so the frontend can tell them apart from
+/// errors the bridge reported.
+pub(crate) const REQUEST_TIMEOUT: &str = "REQUEST_TIMEOUT";
+pub(crate) const REQUEST_CANCELED: &str = "REQUEST_CANCELED";
+/// The bridge process exited before replying.
+pub(crate) const BRIDGE_DIED: &str = "BRIDGE_DIED";
+
+pub(crate) fn request_error(code: &str, message: &str) -> String {
+    format!("{code}: {message}")
+}
+
+/// The error in a bridge reply. A handler that throws is answered with
+/// `{"error": …}` rather than a failed request.
+pub(crate) fn reply_error(response: &Value) -> Option<String> {
+    response.get("error").map(|error| {
+        error
+            .as_str()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| error.to_string())
+    })
+}
+
+fn bridge_died_error(method: &str) -> String {
+    request_error(
+        BRIDGE_DIED,
+        &format!("{method} failed because the Claude bridge exited"),
+    )
+}
+
+/// How long each bridge method may go without a reply.
+#[derive(Debug, Default)]
+pub(crate) struct RequestTimeouts {
+    /// Method -> timeout; `None` waits indefinitely.
+    by_method: HashMap<String, Option<Duration>>,
+    /// For methods not listed, from the `*` entry.
+    fallback: Option<Duration>,
+}
+
+impl RequestTimeouts {
+    /// From method -> seconds, where `*` covers unlisted methods and 0
+    /// waits indefinitely.
+    pub(crate) fn new(seconds: &HashMap<String, u64>) -> Self {
+        let timeout = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        Self {
+            by_method: seconds
+                .iter()
+                .filter(|(method, _)| method.as_str() != "*")
+                .map(|(method, secs)| (method.clone(), timeout(*secs)))
+                .collect(),
+            fallback: seconds.get("*").copied().and_then(timeout),
+        }
+    }
+
+    pub(crate) fn for_method(&self, method: &str) -> Option<Duration> {
+        self.by_method.get(method).copied().unwrap_or(self.fallback)
+    }
+}
+
+/// A request waiting for the bridge's reply.
+pub(crate) struct PendingRequest {
+    method: String,
+    started_at: u64,
+    tx: oneshot::Sender<Result<Value, String>>,
+}
+
+/// An in-flight request, for `claude_list_requests`.
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingRequestInfo {
+    pub(crate) id: u64,
+    pub(crate) method: String,
+    pub(crate) started_at: u64,
 }
 
 /// The Claude bridge process that wraps the Agent SDK.
 pub struct ClaudeBridge {
-    pub(crate) child: Mutex<Child>,
-    pub(crate) stdin: Mutex<ChildStdin>,
-    pub(crate) pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
+    /// `None` when the bridge runs in-process, as the test mock does.
+    pub(crate) child: Mutex<Option<Child>>,
+    pub(crate) stdin: Mutex<Box<dyn AsyncWrite + Send + Unpin>>,
+    pub(crate) pending: Mutex<HashMap<u64, PendingRequest>>,
     pub(crate) next_id: AtomicU64,
+    timeouts: RequestTimeouts,
+    /// Cleared by `mark_dead` once stdout closes; checked and changed only
+    /// while holding `pending`, so no request is left waiting on a dead
+    /// bridge.
+    alive: AtomicBool,
+    started_at: Instant,
 }
 
 impl ClaudeBridge {
+    pub(crate) fn new(
+        child: Option<Child>,
+        stdin: impl AsyncWrite + Send + Unpin + 'static,
+        timeouts: RequestTimeouts,
+    ) -> Self {
+        Self {
+            child: Mutex::new(child),
+            stdin: Mutex::new(Box::new(stdin)),
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            timeouts,
+            alive: AtomicBool::new(true),
+            started_at: Instant::now(),
+        }
+    }
+
     /// Write a JSON message to stdin.
     async fn write_message(&self, value: Value) -> Result<(), String> {
         let mut stdin = self.stdin.lock().await;
@@ -64,14 +213,131 @@ impl ClaudeBridge {
             .map_err(|e| e.to_string())
     }
 
-    /// Send a request and wait for a response.
+    /// Send a request and wait for a response, up to the method's
+    /// configured timeout.
     pub async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let wait = self.timeouts.for_method(method);
+        self.send_request_with_timeout(method, params, wait).await
+    }
+
+    /// Send a request and wait up to `wait` for a response. A request that
+    /// times out is forgotten, so a late reply is not delivered.
+    pub(crate) async fn send_request_with_timeout(
+        &self,
+        method: &str,
+        params: Value,
+        wait: Option<Duration>,
+    ) -> Result<Value, String> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = oneshot::channel();
-        self.pending.lock().await.insert(id, tx);
-        self.write_message(json!({ "id": id, "method": method, "params": params }))
-            .await?;
-        rx.await.map_err(|_| "request canceled".to_string())
+        let request = PendingRequest {
+            method: method.to_string(),
+            started_at: now_millis(),
+            tx,
+        };
+        {
+            let mut pending = self.pending.lock().await;
+            if !self.alive.load(Ordering::SeqCst) {
+                return Err(bridge_died_error(method));
+            }
+            pending.insert(id, request);
+        }
+        if let Err(err) = self
+            .write_message(json!({ "id": id, "method": method, "params": params }))
+            .await
+        {
+            self.pending.lock().await.remove(&id);
+            return Err(err);
+        }
+        let reply = match wait {
+            Some(wait) => match timeout(wait, rx).await {
+                Ok(reply) => reply,
+                Err(_) => {
+                    self.pending.lock().await.remove(&id);
+                    return Err(request_error(
+                        REQUEST_TIMEOUT,
+                        &format!(
+                            "{method} got no reply from the Claude bridge within {}s",
+                            wait.as_secs()
+                        ),
+                    ));
+                }
+            },
+            None => rx.await,
+        };
+        reply.unwrap_or_else(|_| {
+            Err(request_error(
+                REQUEST_CANCELED,
+                &format!("{method} was canceled"),
+            ))
+        })
+    }
+
+    /// Stop waiting for request `id`; its caller gets `REQUEST_CANCELED`.
+    /// Returns false when the request already finished.
+    pub(crate) async fn cancel_request(&self, id: u64) -> bool {
+        let Some(request) = self.pending.lock().await.remove(&id) else {
+            return false;
+        };
+        let _ = request.tx.send(Err(request_error(
+            REQUEST_CANCELED,
+            &format!("{} was canceled", request.method),
+        )));
+        true
+    }
+
+    /// Requests still waiting for a reply, oldest first.
+    pub(crate) async fn pending_requests(&self) -> Vec<PendingRequestInfo> {
+        let mut requests: Vec<PendingRequestInfo> = self
+            .pending
+            .lock()
+            .await
+            .iter()
+            .map(|(id, request)| PendingRequestInfo {
+                id: *id,
+                method: request.method.clone(),
+                started_at: request.started_at,
+            })
+            .collect();
+        requests.sort_by_key(|request| request.id);
+        requests
+    }
+
+    pub(crate) fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    /// Record that the bridge process is gone: fail every request still
+    /// waiting on it with `BRIDGE_DIED`, as well as any sent later.
+    /// Returns the requests that were failed.
+    pub(crate) async fn mark_dead(&self) -> Vec<PendingRequestInfo> {
+        let mut pending = self.pending.lock().await;
+        self.alive.store(false, Ordering::SeqCst);
+        let mut failed: Vec<PendingRequestInfo> = pending
+            .drain()
+            .map(|(id, request)| {
+                let _ = request.tx.send(Err(bridge_died_error(&request.method)));
+                PendingRequestInfo {
+                    id,
+                    method: request.method,
+                    started_at: request.started_at,
+                }
+            })
+            .collect();
+        failed.sort_by_key(|request| request.id);
+        failed
+    }
+
+    /// The exit code of the bridge process, once it has exited. `None` for
+    /// an in-process bridge, a process killed by a signal, or one that is
+    /// still shutting down after closing stdout.
+    async fn exit_code(&self) -> Option<i32> {
+        let mut child = self.child.lock().await;
+        let status = timeout(Duration::from_secs(1), child.as_mut()?.wait())
+            .await
+            .ok()?
+            .ok()?;
+        status.code()
     }
 
     /// Send a notification (no response expected).
@@ -87,8 +353,34 @@ impl ClaudeBridge {
 
     /// Kill the bridge process.
     pub async fn kill(&self) -> Result<(), String> {
-        let mut child = self.child.lock().await;
-        child.kill().await.map_err(|e| e.to_string())
+        match self.child.lock().await.as_mut() {
+            Some(child) => child.kill().await.map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Hand a `response` line to the request waiting on it. Returns false
+    /// for other events and for responses nobody is waiting on, which are
+    /// forwarded like any event.
+    pub(crate) async fn resolve_response(&self, line: &BridgeLine) -> bool {
+        if line.event_type != "response" {
+            return false;
+        }
+        let Some(id) = line.payload.get("id").and_then(|id| id.as_u64()) else {
+            return false;
+        };
+        let Some(request) = self.pending.lock().await.remove(&id) else {
+            return false;
+        };
+        let response = if let Some(error) = line.payload.get("error") {
+            json!({ "error": error })
+        } else if let Some(result) = line.payload.get("result") {
+            json!({ "result": result })
+        } else {
+            json!({ "result": null })
+        };
+        let _ = request.tx.send(Ok(response));
+        true
     }
 }
 
@@ -152,7 +444,7 @@ fn build_node_path_env(claude_bin: Option<&str>) -> Option<String> {
 }
 
 /// Get the path to the bridge script.
-fn get_bridge_path(app_handle: &AppHandle) -> Result<String, String> {
+pub(crate) fn get_bridge_path(app_handle: &AppHandle) -> Result<String, String> {
     use std::path::PathBuf;
     use std::path::Path;
 
@@ -202,30 +494,250 @@ fn get_bridge_path(app_handle: &AppHandle) -> Result<String, String> {
     Err("Could not find claude-bridge/index.ts".to_string())
 }
 
+/// Coalesces high-frequency bridge events per session so the webview gets
+/// one `claude-event-batch` per window instead of one IPC message each.
+pub(crate) struct EventBatcher {
+    /// Event type -> coalescing window; types not listed are never batched.
+    windows: HashMap<String, Duration>,
+    /// Open batches in the order their first event arrived.
+    pending: Vec<PendingBatch>,
+}
+
+struct PendingBatch {
+    session_id: String,
+    deadline: Instant,
+    events: Vec<ClaudeEvent>,
+}
+
+impl EventBatcher {
+    pub(crate) fn new(windows_ms: &HashMap<String, u64>) -> Self {
+        Self {
+            windows: windows_ms
+                .iter()
+                .filter(|(_, ms)| **ms > 0)
+                .map(|(event_type, ms)| (event_type.clone(), Duration::from_millis(*ms)))
+                .collect(),
+            pending: Vec::new(),
+        }
+    }
+
+    pub(crate) fn batches(&self, event_type: &str) -> bool {
+        self.windows.contains_key(event_type)
+    }
+
+    /// Add a batchable event; the session's batch closes one window after
+    /// its first event.
+    pub(crate) fn push(&mut self, event: ClaudeEvent, now: Instant) {
+        if let Some(batch) = self
+            .pending
+            .iter_mut()
+            .find(|batch| batch.session_id == event.session_id)
+        {
+            batch.events.push(event);
+            return;
+        }
+        let window = self
+            .windows
+            .get(&event.event_type)
+            .copied()
+            .unwrap_or_default();
+        self.pending.push(PendingBatch {
+            session_id: event.session_id.clone(),
+            deadline: now + window,
+            events: vec![event],
+        });
+    }
+
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.pending.iter().map(|batch| batch.deadline).min()
+    }
+
+    pub(crate) fn take_due(&mut self, now: Instant) -> Vec<Vec<ClaudeEvent>> {
+        let (due, open): (Vec<PendingBatch>, Vec<PendingBatch>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|batch| batch.deadline <= now);
+        self.pending = open;
+        due.into_iter().map(|batch| batch.events).collect()
+    }
+
+    pub(crate) fn take_all(&mut self) -> Vec<Vec<ClaudeEvent>> {
+        std::mem::take(&mut self.pending)
+            .into_iter()
+            .map(|batch| batch.events)
+            .collect()
+    }
+}
+
+/// Channel suffix for events not tied to a workspace, such as bridge
+/// lifecycle and stderr.
+const BRIDGE_CHANNEL: &str = "bridge";
+
+/// Emit on `channel` and on `{channel}:{workspace_id}`, so a window showing
+/// one workspace can listen to just its own events. Tauri only delivers to
+/// webviews listening on a name, so the unused copy costs nothing.
+pub(crate) fn emit_scoped<S: Serialize + Clone>(
+    app_handle: &AppHandle,
+    channel: &str,
+    workspace_id: &str,
+    payload: S,
+) {
+    let scope = if workspace_id.is_empty() {
+        BRIDGE_CHANNEL
+    } else {
+        workspace_id
+    };
+    let _ = app_handle.emit(&format!("{channel}:{scope}"), payload.clone());
+    let _ = app_handle.emit(channel, payload);
+}
+
+fn emit_batches(app_handle: &AppHandle, batches: Vec<Vec<ClaudeEvent>>) {
+    for events in batches {
+        // Batches are per session, so every event shares one workspace.
+        let workspace_id = events
+            .first()
+            .map(|event| event.workspace_id.clone())
+            .unwrap_or_default();
+        emit_scoped(app_handle, "claude-event-batch", &workspace_id, events);
+    }
+}
+
+/// Emit an event, batching it when configured. Anything emitted directly
+/// first flushes open batches so the webview sees events in bridge order.
+fn dispatch_event(app_handle: &AppHandle, batcher: &mut EventBatcher, event: ClaudeEvent) {
+    broadcast(app_handle, &event);
+    mirror_event(app_handle, &event);
+    record_event(app_handle, &event);
+    capture_event(app_handle, &event);
+    let Some(event) = hold_during_focus(app_handle, event) else {
+        return;
+    };
+    if batcher.batches(&event.event_type) {
+        batcher.push(event, Instant::now());
+        emit_batches(app_handle, batcher.take_due(Instant::now()));
+        return;
+    }
+    emit_batches(app_handle, batcher.take_all());
+    let workspace_id = event.workspace_id.clone();
+    emit_scoped(app_handle, "claude-event", &workspace_id, event);
+}
+
+/// Envelope fields of one bridge stdout line.
+#[derive(Debug)]
+pub(crate) struct BridgeLine {
+    pub(crate) event_type: String,
+    pub(crate) session_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) timestamp: i64,
+    pub(crate) payload: Value,
+}
+
+/// Parse one line of bridge stdout. Invalid UTF-8 is replaced and missing
+/// or mistyped envelope fields fall back to defaults; only text that is not
+/// JSON at all is an error. Blank lines give `None`.
+pub(crate) fn parse_bridge_line(line: &[u8]) -> Result<Option<BridgeLine>, String> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let value: Value = serde_json::from_str(line).map_err(|err| err.to_string())?;
+    let field = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+    Ok(Some(BridgeLine {
+        event_type: field("type").unwrap_or_else(|| "unknown".to_string()),
+        session_id: field("sessionId").unwrap_or_default(),
+        workspace_id: field("workspaceId").unwrap_or_default(),
+        timestamp: value
+            .get("timestamp")
+            .and_then(|t| t.as_i64())
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
+        payload: value.get("payload").cloned().unwrap_or(Value::Null),
+    }))
+}
+
+/// Error event for bridge output that is not JSON.
+pub(crate) fn parse_error_event(err: &str) -> ClaudeEvent {
+    ClaudeEvent {
+        event_type: "error".to_string(),
+        session_id: String::new(),
+        workspace_id: String::new(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        payload: ClaudeEventPayload::Error(ErrorPayload {
+            code: "PARSE_ERROR".to_string(),
+            message: format!("Failed to parse bridge output: {err}"),
+            recoverable: true,
+        }),
+    }
+}
+
+/// Frontend events for a bridge line. A payload that no longer matches its
+/// schema is still forwarded raw, but preceded by a `SCHEMA_MISMATCH` error
+/// so bridge shape changes do not go unnoticed.
+pub(crate) fn bridge_events(line: &BridgeLine) -> Vec<ClaudeEvent> {
+    let mut events = Vec::new();
+    let payload = match ClaudeEventPayload::from_bridge(&line.event_type, &line.payload) {
+        Ok(payload) => payload,
+        Err(err) => {
+            log_line!("Claude bridge schema mismatch: {err}");
+            events.push(ClaudeEvent {
+                event_type: "error".to_string(),
+                session_id: line.session_id.clone(),
+                workspace_id: line.workspace_id.clone(),
+                timestamp: line.timestamp,
+                payload: ClaudeEventPayload::Error(ErrorPayload {
+                    code: "SCHEMA_MISMATCH".to_string(),
+                    message: err,
+                    recoverable: true,
+                }),
+            });
+            ClaudeEventPayload::Raw(line.payload.clone())
+        }
+    };
+    events.push(ClaudeEvent {
+        event_type: line.event_type.clone(),
+        session_id: line.session_id.clone(),
+        workspace_id: line.workspace_id.clone(),
+        timestamp: line.timestamp,
+        payload,
+    });
+    events
+}
+
 /// Spawn the Claude bridge process.
 pub async fn spawn_claude_bridge(
     app_handle: AppHandle,
 ) -> Result<Arc<ClaudeBridge>, String> {
     let bridge_path = get_bridge_path(&app_handle)?;
     let path_env = build_node_path_env(None);
+    let launch = {
+        let state: tauri::State<'_, crate::state::AppState> = app_handle.state();
+        let settings = state.app_settings.lock().await;
+        settings.bridge_launch.clone()
+    };
+    let launch_command = bridge_command(&launch, Path::new(&bridge_path))?;
 
-    // Build tsx command to run TypeScript bridge
-    let mut command = Command::new("npx");
+    let mut command = Command::new(&launch_command.program);
     if let Some(ref path) = path_env {
         command.env("PATH", path);
     }
-    command.arg("tsx");
-    command.arg(&bridge_path);
+    command.args(&launch_command.args);
+    apply_network_env(&mut command);
+    command.envs(&launch_command.env);
     command.stdin(std::process::Stdio::piped());
     command.stdout(std::process::Stdio::piped());
     command.stderr(std::process::Stdio::piped());
 
     let mut child = command.spawn().map_err(|e| {
-        if e.kind() == ErrorKind::NotFound {
-            "npx not found. Please install Node.js 18+ and ensure it's on your PATH."
-                .to_string()
+        if e.kind() != ErrorKind::NotFound {
+            tr("bridge.spawnFailed", &[("error", &e.to_string())])
+        } else if launch.runtime == BridgeRuntime::Tsx {
+            tr("bridge.npxNotFound", &[])
         } else {
-            format!("Failed to spawn Claude bridge: {e}")
+            tr("bridge.notFound", &[("program", &launch_command.program)])
         }
     })?;
 
@@ -233,94 +745,80 @@ pub async fn spawn_claude_bridge(
     let stdout = child.stdout.take().ok_or("missing stdout")?;
     let stderr = child.stderr.take().ok_or("missing stderr")?;
 
-    let bridge = Arc::new(ClaudeBridge {
-        child: Mutex::new(child),
-        stdin: Mutex::new(stdin),
-        pending: Mutex::new(HashMap::new()),
-        next_id: AtomicU64::new(1),
-    });
+    let (batch_windows, timeouts) = {
+        let state: tauri::State<'_, crate::state::AppState> = app_handle.state();
+        let settings = state.app_settings.lock().await;
+        (
+            settings.event_batch_windows.clone(),
+            RequestTimeouts::new(&settings.request_timeouts),
+        )
+    };
+    let bridge = Arc::new(ClaudeBridge::new(Some(child), stdin, timeouts));
 
     // Spawn stdout reader task
     let bridge_clone = Arc::clone(&bridge);
     let app_handle_clone = app_handle.clone();
     tauri::async_runtime::spawn(async move {
-        let mut lines = BufReader::new(stdout).lines();
-        while let Ok(Some(line)) = lines.next_line().await {
-            if line.trim().is_empty() {
-                continue;
-            }
+        let mut batcher = EventBatcher::new(&batch_windows);
+        // Split on raw bytes: `lines()` fails on invalid UTF-8, which would
+        // end the reader for good.
+        let mut lines = BufReader::new(stdout).split(b'\n');
+        loop {
+            // Wake for the next batch deadline even if the bridge goes quiet.
+            let next = match batcher.next_deadline() {
+                Some(deadline) => {
+                    let wait = deadline.saturating_duration_since(Instant::now());
+                    match timeout(wait, lines.next_segment()).await {
+                        Ok(next) => next,
+                        Err(_) => {
+                            emit_batches(&app_handle_clone, batcher.take_due(Instant::now()));
+                            continue;
+                        }
+                    }
+                }
+                None => lines.next_segment().await,
+            };
+            let Ok(Some(line)) = next else {
+                break;
+            };
 
-            let value: Value = match serde_json::from_str(&line) {
-                Ok(value) => value,
+            let line = match parse_bridge_line(&line) {
+                Ok(Some(parsed)) => parsed,
+                Ok(None) => continue,
                 Err(err) => {
-                    eprintln!("Claude bridge parse error: {err}, line: {line}");
-                    let event = ClaudeEvent {
-                        event_type: "error".to_string(),
-                        session_id: String::new(),
-                        workspace_id: String::new(),
-                        timestamp: chrono::Utc::now().timestamp_millis(),
-                        payload: json!({
-                            "code": "PARSE_ERROR",
-                            "message": format!("Failed to parse bridge output: {err}"),
-                            "recoverable": true
-                        }),
-                    };
-                    let _ = app_handle_clone.emit("claude-event", event);
+                    let line = String::from_utf8_lossy(&line);
+                    log_line!("Claude bridge parse error: {err}, line: {line}");
+                    dispatch_event(&app_handle_clone, &mut batcher, parse_error_event(&err));
                     continue;
                 }
             };
 
-            // Extract fields from bridge event
-            let event_type = value
-                .get("type")
-                .and_then(|t| t.as_str())
-                .unwrap_or("unknown")
-                .to_string();
-            let session_id = value
-                .get("sessionId")
-                .and_then(|s| s.as_str())
-                .unwrap_or("")
-                .to_string();
-            let workspace_id = value
-                .get("workspaceId")
-                .and_then(|w| w.as_str())
-                .unwrap_or("")
-                .to_string();
-            let timestamp = value
-                .get("timestamp")
-                .and_then(|t| t.as_i64())
-                .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
-            let payload = value
-                .get("payload")
-                .cloned()
-                .unwrap_or(Value::Null);
-
-            // Check if this is a response to a pending request
-            if event_type == "response" {
-                if let Some(id) = payload.get("id").and_then(|id| id.as_u64()) {
-                    if let Some(tx) = bridge_clone.pending.lock().await.remove(&id) {
-                        let response = if let Some(error) = payload.get("error") {
-                            json!({ "error": error })
-                        } else if let Some(result) = payload.get("result") {
-                            json!({ "result": result })
-                        } else {
-                            json!({ "result": null })
-                        };
-                        let _ = tx.send(response);
-                        continue;
-                    }
-                }
+            if bridge_clone.resolve_response(&line).await {
+                continue;
             }
-
-            // Emit flattened event to frontend
-            let event = ClaudeEvent {
-                event_type: event_type.clone(),
-                session_id: session_id.clone(),
-                workspace_id: workspace_id.clone(),
-                timestamp,
-                payload: payload.clone(),
+            if auto_approve(&app_handle_clone, &bridge_clone, &line).await {
+                continue;
+            }
+            for event in bridge_events(&line) {
+                dispatch_event(&app_handle_clone, &mut batcher, event);
+            }
+            let changed = {
+                let state: tauri::State<'_, crate::state::AppState> = app_handle_clone.state();
+                let mut turns = state.turns.lock().await;
+                let before = turns.activity();
+                turns.observe(&line);
+                Some(turns.activity()).filter(|activity| *activity != before)
             };
-            let _ = app_handle_clone.emit("claude-event", event);
+            if let Some(activity) = changed {
+                show_activity(&app_handle_clone, activity);
+            }
+            let BridgeLine {
+                event_type,
+                session_id,
+                workspace_id,
+                payload,
+                ..
+            } = line;
 
             // Handle registry updates for session lifecycle events
             if event_type == "session/started" {
@@ -336,6 +834,7 @@ pub async fn spawn_claude_bridge(
                             .unwrap_or("")
                             .to_string(),
                         started_at: chrono::Utc::now().timestamp_millis() as u64,
+                        reported_cost_usd: 0.0,
                     };
                     state
                         .claude_sessions
@@ -352,18 +851,43 @@ pub async fn spawn_claude_bridge(
                 )
                 .await
                 {
-                    eprintln!("Failed to register session: {e}");
+                    log_line!("Failed to register session: {e}");
+                }
+                refresh_recent_menu(&app_handle_clone).await;
+            } else if event_type == "tool/started" {
+                if let Err(e) =
+                    record_tool_files(&app_handle_clone, &session_id, &payload).await
+                {
+                    log_line!("Failed to record session files: {e}");
                 }
             } else if event_type == "result" {
                 // Update session activity on completion
                 if let Err(e) =
                     handle_session_activity_update(&app_handle_clone, &session_id, &payload).await
                 {
-                    eprintln!("Failed to update session activity: {e}");
+                    log_line!("Failed to update session activity: {e}");
                 }
+                refresh_unread(&app_handle_clone, &session_id).await;
+                let success = payload
+                    .get("success")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                turn_finished(&app_handle_clone, &session_id, success).await;
+                let state: tauri::State<'_, crate::state::AppState> = app_handle_clone.state();
+                release_turn_slot(&state, &session_id).await;
+                advance_queue(&app_handle_clone, &session_id).await;
+            } else if event_type == "session/closed" {
+                clear_queue(&app_handle_clone, &session_id).await;
+                session_closed(&app_handle_clone, &session_id).await;
+                let state: tauri::State<'_, crate::state::AppState> = app_handle_clone.state();
+                state.autonomous_turns.lock().await.closed(&session_id);
+                close_turn_slots(&state, &session_id).await;
             }
         }
-        eprintln!("Claude bridge stdout reader exited");
+        emit_batches(&app_handle_clone, batcher.take_all());
+        log_line!("Claude bridge stdout reader exited");
+        let failed_requests = bridge_clone.mark_dead().await;
+        supervise_exit(&app_handle_clone, &bridge_clone, failed_requests).await;
     });
 
     // Spawn stderr reader task (for logging)
@@ -375,15 +899,15 @@ pub async fn spawn_claude_bridge(
                 continue;
             }
             // Log to console and optionally emit as event
-            eprintln!("Claude bridge stderr: {line}");
+            log_line!("Claude bridge stderr: {line}");
             let event = ClaudeEvent {
                 event_type: "bridge/stderr".to_string(),
                 session_id: String::new(),
                 workspace_id: String::new(),
                 timestamp: chrono::Utc::now().timestamp_millis(),
-                payload: json!({ "message": line }),
+                payload: ClaudeEventPayload::BridgeStderr(BridgeStderrPayload { message: line }),
             };
-            let _ = app_handle_clone.emit("claude-event", event);
+            emit_scoped(&app_handle_clone, "claude-event", "", event);
         }
     });
 
@@ -395,14 +919,12 @@ pub async fn spawn_claude_bridge(
         }
     });
 
-    let init_result = timeout(
-        Duration::from_secs(30),
-        bridge.send_request("initialize", init_params),
-    )
-    .await;
+    let init_result = bridge
+        .send_request_with_timeout("initialize", init_params, Some(INIT_TIMEOUT))
+        .await;
 
     match init_result {
-        Ok(Ok(response)) => {
+        Ok(response) => {
             if response.get("error").is_some() {
                 let _ = bridge.kill().await;
                 return Err(format!(
@@ -416,19 +938,22 @@ pub async fn spawn_claude_bridge(
                 session_id: String::new(),
                 workspace_id: String::new(),
                 timestamp: chrono::Utc::now().timestamp_millis(),
-                payload: response.get("result").cloned().unwrap_or(Value::Null),
+                payload: ClaudeEventPayload::Raw(
+                    response.get("result").cloned().unwrap_or(Value::Null),
+                ),
             };
-            let _ = app_handle.emit("claude-event", event);
+            emit_scoped(&app_handle, "claude-event", "", event);
         }
-        Ok(Err(e)) => {
+        Err(e) if e.starts_with(REQUEST_TIMEOUT) => {
             let _ = bridge.kill().await;
-            return Err(format!("Claude bridge initialization failed: {e}"));
+            return Err(format!(
+                "Claude bridge did not respond to initialize within {} seconds.",
+                INIT_TIMEOUT.as_secs()
+            ));
         }
-        Err(_) => {
+        Err(e) => {
             let _ = bridge.kill().await;
-            return Err(
-                "Claude bridge did not respond to initialize within 30 seconds.".to_string(),
-            );
+            return Err(format!("Claude bridge initialization failed: {e}"));
         }
     }
 
@@ -474,7 +999,7 @@ async fn handle_session_started_registry(
         }
     }
 
-    let session = SessionEntry {
+    let mut session = SessionEntry {
         session_id: session_id.to_string(),
         cwd,
         preview: None,
@@ -483,10 +1008,31 @@ async fn handle_session_started_registry(
         transcript_path,
         project_path,
         status: SessionStatus::Active,
+        usage: None,
+        summary: None,
+        files: Vec::new(),
+        interrupted_turns: Vec::new(),
+        last_read_message_id: None,
+        last_read_line: None,
+        metadata: BTreeMap::new(),
+        time: BTreeMap::new(),
     };
 
-    // Add to registry
+    // Add to registry, keeping usage, files and metadata from before a resume
     let mut registry = state.registry.lock().await;
+    if let Some(existing) = registry.sessions.get(session_id) {
+        session.usage = existing.usage.clone();
+        session.files = existing.files.clone();
+        session.metadata = existing.metadata.clone();
+        session.time = existing.time.clone();
+    }
+    if let Some(model) = payload
+        .get("model")
+        .and_then(|m| m.as_str())
+        .filter(|m| !m.is_empty())
+    {
+        session.usage.get_or_insert_with(SessionUsage::default).model = Some(model.to_string());
+    }
     registry
         .sessions
         .insert(session_id.to_string(), session);
@@ -507,51 +1053,267 @@ async fn handle_session_started_registry(
     Ok(())
 }
 
-/// Handle result event by updating session activity timestamp.
+/// Read the per-turn usage block from a `result` payload. `cost_usd` is
+/// still the session's running total; see `turn_cost`.
+fn usage_from_result(payload: &Value) -> SessionUsage {
+    let usage = payload.get("usage");
+    let tokens = |key: &str| {
+        usage
+            .and_then(|u| u.get(key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+    };
+    SessionUsage {
+        input_tokens: tokens("inputTokens"),
+        output_tokens: tokens("outputTokens"),
+        cache_read_input_tokens: tokens("cacheReadInputTokens"),
+        cache_creation_input_tokens: tokens("cacheCreationInputTokens"),
+        cost_usd: payload
+            .get("totalCostUsd")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0),
+        requests: 1,
+        failed_requests: match payload.get("success").and_then(|v| v.as_bool()) {
+            Some(false) => 1,
+            _ => 0,
+        },
+        duration_ms: payload
+            .get("durationMs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        last_outcome: payload
+            .get("subtype")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        model: None,
+    }
+}
+
+/// Cost of a turn from the session's running total, remembering the total
+/// for the next turn. A total below the last one means the bridge session
+/// started over (e.g. after a restart), so all of it is new.
+fn turn_cost(reported: &mut f64, total: f64) -> f64 {
+    let cost = if total >= *reported {
+        total - *reported
+    } else {
+        total
+    };
+    *reported = total;
+    cost
+}
+
+/// Handle result event by updating session activity timestamp and usage.
 async fn handle_session_activity_update(
     app_handle: &AppHandle,
     session_id: &str,
-    _payload: &Value,
+    payload: &Value,
 ) -> Result<(), String> {
     use tauri::Manager;
 
     let state: tauri::State<'_, crate::state::AppState> = app_handle.state();
+    let mut request_usage = usage_from_result(payload);
+    let workspace_id = {
+        let mut sessions = state.claude_sessions.lock().await;
+        match sessions.get_mut(session_id) {
+            Some(info) => {
+                request_usage.cost_usd =
+                    turn_cost(&mut info.reported_cost_usd, request_usage.cost_usd);
+                info.workspace_id.clone()
+            }
+            None => String::new(),
+        }
+    };
     let mut registry = state.registry.lock().await;
 
+    let mut session_total = None;
     if let Some(session) = registry.sessions.get_mut(session_id) {
         session.last_activity = now_millis();
+        let total = session.usage.get_or_insert_with(SessionUsage::default);
+        total.input_tokens += request_usage.input_tokens;
+        total.output_tokens += request_usage.output_tokens;
+        total.cache_read_input_tokens += request_usage.cache_read_input_tokens;
+        total.cache_creation_input_tokens += request_usage.cache_creation_input_tokens;
+        total.cost_usd += request_usage.cost_usd;
+        total.requests += 1;
+        total.failed_requests += request_usage.failed_requests;
+        total.duration_ms += request_usage.duration_ms;
+        if request_usage.last_outcome.is_some() {
+            total.last_outcome = request_usage.last_outcome.clone();
+        }
+        session_total = Some(total.clone());
+        add_agent_time(session, request_usage.duration_ms);
     }
 
     // Persist
     write_registry(&state.registry_path, &registry)?;
+    drop(registry);
+
+    // API-key mode bills per token, so surface the cost of every request.
+    let billing = {
+        let settings = state.app_settings.lock().await;
+        settings.claude_auth_mode.clone()
+    };
+    let event = ClaudeEvent {
+        event_type: "usage/updated".to_string(),
+        session_id: session_id.to_string(),
+        workspace_id: workspace_id.clone(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        payload: ClaudeEventPayload::UsageUpdated(UsageUpdatedPayload {
+            billing,
+            request_cost_usd: request_usage.cost_usd,
+            request: request_usage,
+            session: session_total,
+        }),
+    };
+    broadcast(app_handle, &event);
+    emit_scoped(app_handle, "claude-event", &workspace_id, event);
 
     Ok(())
 }
 
+/// Account id and environment handed to the bridge for a workspace session:
+/// account credentials, cloud provider routing, and any custom gateway.
+pub(crate) async fn build_session_env(
+    state: &crate::state::AppState,
+    workspace_id: &str,
+) -> Result<(Option<String>, HashMap<String, String>), String> {
+    let account = resolve_account_context(state, workspace_id).await?;
+    let mut env = account.env;
+    {
+        let settings = state.app_settings.lock().await;
+        let missing =
+            missing_provider_fields(&settings.claude_provider, &settings.provider_settings);
+        if !missing.is_empty() {
+            return Err(format!(
+                "Provider settings are incomplete: missing {}",
+                missing.join(", ")
+            ));
+        }
+        env.extend(provider_env(
+            &settings.claude_provider,
+            &settings.provider_settings,
+        ));
+    }
+    {
+        let workspaces = state.workspaces.lock().await;
+        if let Some(entry) = workspaces.get(workspace_id) {
+            env.extend(gateway_env(&entry.settings));
+        }
+    }
+    Ok((account.account_id, env))
+}
+
+/// Nice level for a workspace's sessions, from its process priority.
+async fn workspace_niceness(state: &crate::state::AppState, workspace_id: &str) -> i32 {
+    let workspaces = state.workspaces.lock().await;
+    workspaces
+        .get(workspace_id)
+        .and_then(|entry| entry.settings.process_priority)
+        .unwrap_or_default()
+        .niceness()
+}
+
+/// Run a one-shot, tool-less completion through the bridge (summaries,
+/// extraction). Uses the workspace's account/provider env when given.
+pub(crate) async fn complete_text(
+    app_handle: &AppHandle,
+    state: &tauri::State<'_, crate::state::AppState>,
+    workspace_id: Option<&str>,
+    model: &str,
+    system_prompt: &str,
+    prompt: String,
+) -> Result<String, String> {
+    let bridge = ensure_bridge_running(app_handle, state).await?;
+    let (_, env) = build_session_env(state, workspace_id.unwrap_or("")).await?;
+    let (claude_code_bin, cwd) = {
+        let settings = state.app_settings.lock().await;
+        let workspaces = state.workspaces.lock().await;
+        (
+            settings.claude_code_bin.clone(),
+            workspace_id
+                .and_then(|id| workspaces.get(id))
+                .map(|entry| entry.path.clone()),
+        )
+    };
+    let response = bridge
+        .send_request(
+            "text/complete",
+            json!({
+                "prompt": prompt,
+                "systemPrompt": system_prompt,
+                "model": model,
+                "cwd": cwd,
+                "claudeCodeBin": claude_code_bin,
+                "env": env,
+            }),
+        )
+        .await?;
+    if let Some(error) = reply_error(&response) {
+        return Err(error);
+    }
+    response
+        .get("result")
+        .and_then(|r| r.get("text"))
+        .and_then(|t| t.as_str())
+        .map(|t| t.trim().to_string())
+        .ok_or_else(|| "Completion returned no text".to_string())
+}
+
 // ============================================================================
 // Tauri Commands
 // ============================================================================
 
 /// Check if Claude Code / Node.js is properly installed.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_doctor(
     claude_code_bin: Option<String>,
+    app_handle: AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     // Get default bin from settings if not provided
-    let default_bin = {
+    let (default_bin, provider_check, launch) = {
         let settings = state.app_settings.lock().await;
-        settings.claude_code_bin.clone()
+        (
+            settings.claude_code_bin.clone(),
+            check_provider(&settings.claude_provider, &settings.provider_settings),
+            settings.bridge_launch.clone(),
+        )
     };
     let resolved_bin = claude_code_bin
         .filter(|v| !v.trim().is_empty())
         .or(default_bin);
 
-    check_claude_installation(resolved_bin.as_deref()).await
+    let mut result = check_claude_installation(resolved_bin.as_deref()).await?;
+    let provider_ok = provider_check
+        .get("providerOk")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let bridge_check = check_bridge_launch(
+        &launch,
+        get_bridge_path(&app_handle),
+        build_node_path_env(None).as_deref(),
+    )
+    .await;
+    let bridge_ok = bridge_check
+        .get("bridgeOk")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if let Some(object) = result.as_object_mut() {
+        let ok = object.get("ok").and_then(|v| v.as_bool()).unwrap_or(false);
+        object.insert("ok".to_string(), json!(ok && provider_ok && bridge_ok));
+        object.insert("provider".to_string(), provider_check);
+        if let Some(bridge) = bridge_check.as_object() {
+            object.extend(bridge.clone());
+        }
+    }
+    Ok(result)
 }
 
 /// Start a new Claude session for a workspace.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_start_session(
     workspace_id: String,
     cwd: String,
@@ -565,17 +1327,32 @@ pub async fn claude_start_session(
     agents: Option<Value>,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
+    ensure_session_slot(&state).await?;
     // Ensure bridge is running
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
 
-    let (default_permission_mode, claude_code_bin) = {
+    let (default_permission_mode, claude_code_bin, api_model) = {
         let settings = state.app_settings.lock().await;
+        let api_model = if settings.claude_auth_mode == ClaudeAuthMode::ApiKey {
+            settings.api_model.clone()
+        } else {
+            None
+        };
         (
             settings.default_permission_mode.clone(),
             settings.claude_code_bin.clone(),
+            api_model,
         )
     };
+    let (account_id, env) = build_session_env(&state, &workspace_id).await?;
+    let team_permissions = team_permissions(&state).await;
+    let niceness = workspace_niceness(&state, &workspace_id).await;
+    let append_system_prompt = glossary_context(&state, &workspace_id).await;
+    // Model ids are passed through verbatim; API-key mode falls back to the
+    // configured API model rather than the CLI's subscription default.
+    let model = model.filter(|value| !value.trim().is_empty()).or(api_model);
 
     let params = json!({
         "workspaceId": workspace_id,
@@ -583,10 +1360,16 @@ pub async fn claude_start_session(
         "model": model,
         "permissionMode": permission_mode.unwrap_or(default_permission_mode),
         "claudeCodeBin": claude_code_bin,
+        "accountId": account_id,
+        "env": env,
         "enableFileCheckpointing": enable_file_checkpointing,
         "mcpServers": mcp_servers,
         "plugins": plugins,
         "agents": agents,
+        "allowedTools": team_permissions.allow,
+        "disallowedTools": team_permissions.deny,
+        "niceness": niceness,
+        "appendSystemPrompt": append_system_prompt,
     });
 
     let response = bridge.send_request("session/start", params).await?;
@@ -594,20 +1377,16 @@ pub async fn claude_start_session(
     Ok(response)
 }
 
-/// Resume an existing Claude session.
-#[tauri::command]
-pub async fn claude_resume_session(
-    workspace_id: String,
-    session_id: String,
-    app_handle: tauri::AppHandle,
-    state: tauri::State<'_, crate::state::AppState>,
+/// `session/resume` params for a workspace session.
+async fn resume_params(
+    state: &crate::state::AppState,
+    workspace_id: &str,
+    session_id: &str,
 ) -> Result<Value, String> {
-    let bridge = ensure_bridge_running(&app_handle, &state).await?;
-
     let cwd = {
         let workspaces = state.workspaces.lock().await;
         let workspace = workspaces
-            .get(&workspace_id)
+            .get(workspace_id)
             .ok_or_else(|| format!("Workspace {} not found", workspace_id))?;
         workspace.path.clone()
     };
@@ -615,19 +1394,44 @@ pub async fn claude_resume_session(
         let settings = state.app_settings.lock().await;
         settings.claude_code_bin.clone()
     };
+    let (account_id, env) = build_session_env(state, workspace_id).await?;
+    let team_permissions = team_permissions(state).await;
+    let niceness = workspace_niceness(state, workspace_id).await;
 
-    let params = json!({
+    Ok(json!({
         "workspaceId": workspace_id,
         "sessionId": session_id,
         "cwd": cwd,
         "claudeCodeBin": claude_code_bin,
-    });
+        "accountId": account_id,
+        "env": env,
+        "allowedTools": team_permissions.allow,
+        "disallowedTools": team_permissions.deny,
+        "niceness": niceness,
+    }))
+}
 
+/// Resume an existing Claude session.
+#[tauri::command]
+#[specta::specta]
+pub async fn claude_resume_session(
+    workspace_id: String,
+    session_id: String,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
+) -> Result<Value, String> {
+    if !state.claude_sessions.lock().await.contains_key(&session_id) {
+        ensure_session_slot(&state).await?;
+    }
+    let bridge = ensure_bridge_running(&app_handle, &state).await?;
+    let params = resume_params(&state, &workspace_id, &session_id).await?;
     bridge.send_request("session/resume", params).await
 }
 
 /// Send a message to a Claude session.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_send_message(
     session_id: String,
     workspace_id: String,
@@ -636,6 +1440,7 @@ pub async fn claude_send_message(
     message_id: Option<String>,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
 
@@ -647,15 +1452,38 @@ pub async fn claude_send_message(
         "messageId": message_id,
     });
 
-    bridge.send_request("message/send", params).await
+    acquire_turn_slot(&state, &session_id, Lane::Interactive).await?;
+    // Marked before sending: the turn's `result` can beat the reply.
+    state.message_queues.lock().await.started(&session_id);
+    state.autonomous_turns.lock().await.user_input(&session_id);
+    let sent = bridge
+        .send_request("message/send", params)
+        .await
+        .and_then(|response| match reply_error(&response) {
+            Some(error) => Err(error),
+            None => Ok(response),
+        });
+    let response = match sent {
+        Ok(response) => response,
+        Err(err) => {
+            state.message_queues.lock().await.idle(&session_id);
+            release_turn_slot(&state, &session_id).await;
+            return Err(err);
+        }
+    };
+    mirror_user_message(&app_handle, &session_id, &message);
+    record_prompt(&state, &workspace_id, &message).await;
+    Ok(response)
 }
 
 /// Interrupt the current processing in a Claude session.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_interrupt(
     session_id: String,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
 
@@ -663,11 +1491,43 @@ pub async fn claude_interrupt(
         "sessionId": session_id,
     });
 
-    bridge.send_request("message/interrupt", params).await
+    // Taken first: the bridge may end the turn with a `result` before it
+    // answers the interrupt.
+    let turn = state.turns.lock().await.take(&session_id);
+    let response = match bridge.send_request("message/interrupt", params).await {
+        Ok(response) => response,
+        Err(err) => {
+            if let Some(turn) = turn {
+                state.turns.lock().await.restore(&session_id, turn);
+            }
+            return Err(err);
+        }
+    };
+    if let Some(turn) = turn {
+        let interrupted = save_interrupted_turn(&state, &session_id, turn).await?;
+        let workspace_id = state
+            .claude_sessions
+            .lock()
+            .await
+            .get(&session_id)
+            .map(|info| info.workspace_id.clone())
+            .unwrap_or_default();
+        let event = ClaudeEvent {
+            event_type: "turn/interrupted".to_string(),
+            session_id: session_id.clone(),
+            workspace_id: workspace_id.clone(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            payload: ClaudeEventPayload::TurnInterrupted(interrupted),
+        };
+        broadcast(&app_handle, &event);
+        emit_scoped(&app_handle, "claude-event", &workspace_id, event);
+    }
+    Ok(response)
 }
 
 /// Respond to a permission request.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_respond_permission(
     session_id: String,
     tool_use_id: String,
@@ -675,7 +1535,11 @@ pub async fn claude_respond_permission(
     message: Option<String>,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
+    if decision == "allow" {
+        ensure_not_observer(&state, "Approving tool use").await?;
+    }
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
 
     let params = json!({
@@ -685,15 +1549,45 @@ pub async fn claude_respond_permission(
         "message": message,
     });
 
-    bridge.send_request("permission/respond", params).await
+    let response = bridge.send_request("permission/respond", params).await?;
+    let activity = {
+        let mut turns = state.turns.lock().await;
+        turns.permission_answered(&session_id, &tool_use_id);
+        turns.activity()
+    };
+    show_activity(&app_handle, activity);
+    Ok(response)
+}
+
+/// Change the CPU priority of a running session's process and its tools.
+/// Raising it back to normal may need elevated rights on Unix.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_session_priority(
+    session_id: String,
+    level: ProcessPriority,
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
+) -> Result<Value, String> {
+    let bridge = ensure_bridge_running(&app_handle, &state).await?;
+
+    let params = json!({
+        "sessionId": session_id,
+        "niceness": level.niceness(),
+    });
+
+    bridge.send_request("session/setPriority", params).await
 }
 
 /// Get list of available models (requires active session).
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_list_models(
     session_id: Option<String>,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
     let resolved_session_id = match session_id.filter(|id| !id.trim().is_empty()) {
@@ -712,10 +1606,12 @@ pub async fn claude_list_models(
 
 /// Get list of available slash commands (skills).
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_list_commands(
     session_id: Option<String>,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
     let params = json!({
@@ -726,10 +1622,12 @@ pub async fn claude_list_commands(
 
 /// Get MCP server status for a session.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_mcp_status(
     session_id: String,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
     let params = json!({
@@ -741,13 +1639,20 @@ pub async fn claude_mcp_status(
 /// Rewind files to a previous state (Phase 3).
 /// Requires enableFileCheckpointing to have been set on session start.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_rewind_files(
     session_id: String,
     user_message_id: String,
     dry_run: Option<bool>,
+    confirmation: Option<String>,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
+    if !dry_run.unwrap_or(false) {
+        ensure_not_observer(&state, "Rewinding files").await?;
+        consume_confirmation(&state, confirmation.as_deref(), "rewind_files", &session_id).await?;
+    }
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
     let params = json!({
         "sessionId": session_id,
@@ -759,11 +1664,13 @@ pub async fn claude_rewind_files(
 
 /// Dynamically update MCP servers for a session (Phase 4).
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_set_mcp_servers(
     session_id: String,
     servers: Value,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
     let params = json!({
@@ -775,10 +1682,12 @@ pub async fn claude_set_mcp_servers(
 
 /// Close a Claude session.
 #[tauri::command]
+#[specta::specta]
 pub async fn claude_close_session(
     session_id: String,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let bridge = ensure_bridge_running(&app_handle, &state).await?;
 
@@ -794,8 +1703,38 @@ pub async fn claude_close_session(
     result
 }
 
+/// Bridge requests still waiting for a reply.
+#[tauri::command]
+#[specta::specta]
+pub async fn claude_list_requests(
+    state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<PendingRequestInfo>, String> {
+    let bridge = state.claude_bridge.lock().await.clone();
+    match bridge {
+        Some(bridge) => Ok(bridge.pending_requests().await),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Stop waiting for a bridge request; the command that sent it fails with
+/// `REQUEST_CANCELED`. Returns false when it already finished.
+#[tauri::command]
+#[specta::specta]
+pub async fn claude_cancel_request(
+    request_id: u64,
+    state: tauri::State<'_, crate::state::AppState>,
+    _timer: CommandTimer,
+) -> Result<bool, String> {
+    let bridge = state.claude_bridge.lock().await.clone();
+    match bridge {
+        Some(bridge) => Ok(bridge.cancel_request(request_id).await),
+        None => Ok(false),
+    }
+}
+
 /// Ensure the Claude bridge is running, starting it if necessary.
-async fn ensure_bridge_running(
+pub(crate) async fn ensure_bridge_running(
     app_handle: &tauri::AppHandle,
     state: &tauri::State<'_, crate::state::AppState>,
 ) -> Result<Arc<ClaudeBridge>, String> {
@@ -808,16 +1747,182 @@ async fn ensure_bridge_running(
     // Start the bridge
     let bridge = spawn_claude_bridge(app_handle.clone()).await?;
     *bridge_guard = Some(Arc::clone(&bridge));
+    crate::heartbeat::spawn_heartbeat(app_handle.clone(), Arc::clone(&bridge));
 
     Ok(bridge)
 }
 
+/// Wait before restart attempt `attempt` (from 0): doubles from
+/// `RESTART_DELAY` up to `MAX_RESTART_DELAY`.
+fn restart_delay(attempt: u32) -> Duration {
+    RESTART_DELAY
+        .saturating_mul(1 << attempt.min(16))
+        .min(MAX_RESTART_DELAY)
+}
+
+/// Called when a bridge's stdout closes, after its pending requests have
+/// failed. If it was still the app's bridge, it died on its own (or was
+/// killed by the heartbeat) rather than shut down: end the turns its
+/// sessions had running, emit `bridge/died` and start a new one in the
+/// background.
+async fn supervise_exit(
+    app_handle: &AppHandle,
+    bridge: &Arc<ClaudeBridge>,
+    failed_requests: Vec<PendingRequestInfo>,
+) {
+    let state: tauri::State<'_, crate::state::AppState> = app_handle.state();
+    {
+        let mut current = state.claude_bridge.lock().await;
+        if !current
+            .as_ref()
+            .is_some_and(|current| Arc::ptr_eq(current, bridge))
+        {
+            return;
+        }
+        *current = None;
+    }
+    let sessions: Vec<ClaudeSessionInfo> = state
+        .claude_sessions
+        .lock()
+        .await
+        .drain()
+        .map(|(_, info)| info)
+        .collect();
+    let session_ids: Vec<String> = sessions
+        .iter()
+        .map(|info| info.session_id.clone())
+        .collect();
+    // Turns running on it never get their `result`.
+    clear_turn_slots(&state, &session_ids).await;
+    {
+        let mut queues = state.message_queues.lock().await;
+        for session_id in &session_ids {
+            queues.idle(session_id);
+        }
+    }
+    for session_id in &session_ids {
+        turn_lost(app_handle, session_id).await;
+    }
+    let attempts = {
+        let mut attempts = state.bridge_restarts.lock().await;
+        if bridge.started_at.elapsed() >= HEALTHY_UPTIME {
+            *attempts = 0;
+        }
+        *attempts
+    };
+    let exit_code = bridge.exit_code().await;
+    log_line!(
+        "Claude bridge exited unexpectedly (code {exit_code:?}) with {} open session(s) \
+         and {} pending request(s); restarting",
+        sessions.len(),
+        failed_requests.len()
+    );
+    let event = ClaudeEvent {
+        event_type: "bridge/died".to_string(),
+        session_id: String::new(),
+        workspace_id: String::new(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        payload: ClaudeEventPayload::BridgeDied(BridgeDiedPayload {
+            exit_code,
+            failed_requests,
+            sessions: session_ids,
+        }),
+    };
+    emit_scoped(app_handle, "claude-event", "", event);
+    tauri::async_runtime::spawn(restart_after_exit(app_handle.clone(), sessions, attempts));
+}
+
+/// Restart the bridge with exponential backoff, resume the sessions the old
+/// one had open, and emit `bridge/reconnected`. The backoff starts at
+/// `first_attempt`, the attempts made since a bridge last stayed up for
+/// `HEALTHY_UPTIME`, so a bridge that keeps dying right after starting is
+/// given up on after `MAX_RESTART_ATTEMPTS` in total; the next command
+/// then starts the bridge itself.
+async fn restart_after_exit(
+    app_handle: AppHandle,
+    sessions: Vec<ClaudeSessionInfo>,
+    first_attempt: u32,
+) {
+    let state: tauri::State<'_, crate::state::AppState> = app_handle.state();
+    for attempt in first_attempt..MAX_RESTART_ATTEMPTS {
+        *state.bridge_restarts.lock().await = attempt + 1;
+        sleep(restart_delay(attempt)).await;
+        let bridge = match ensure_bridge_running(&app_handle, &state).await {
+            Ok(bridge) => bridge,
+            Err(err) => {
+                log_line!(
+                    "Claude bridge restart attempt {} failed: {err}",
+                    attempt + 1
+                );
+                continue;
+            }
+        };
+        let mut resumed = Vec::new();
+        let mut failed = Vec::new();
+        for info in sessions {
+            // Reopened by the user while the bridge was restarting.
+            let reopened = state
+                .claude_sessions
+                .lock()
+                .await
+                .contains_key(&info.session_id);
+            if reopened {
+                resumed.push(info.session_id);
+                continue;
+            }
+            let result = match resume_params(&state, &info.workspace_id, &info.session_id).await {
+                Ok(params) => bridge.send_request("session/resume", params).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(response) if response.get("error").is_none() => {
+                    state
+                        .claude_sessions
+                        .lock()
+                        .await
+                        .insert(info.session_id.clone(), info.clone());
+                    resumed.push(info.session_id);
+                }
+                Ok(response) => {
+                    log_line!(
+                        "Could not resume session {} after bridge restart: {:?}",
+                        info.session_id,
+                        response.get("error")
+                    );
+                    failed.push(info.session_id);
+                }
+                Err(err) => {
+                    log_line!(
+                        "Could not resume session {} after bridge restart: {err}",
+                        info.session_id
+                    );
+                    failed.push(info.session_id);
+                }
+            }
+        }
+        let event = ClaudeEvent {
+            event_type: "bridge/reconnected".to_string(),
+            session_id: String::new(),
+            workspace_id: String::new(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            payload: ClaudeEventPayload::BridgeReconnected(BridgeReconnectedPayload {
+                attempts: attempt + 1,
+                resumed,
+                failed,
+            }),
+        };
+        emit_scoped(&app_handle, "claude-event", "", event);
+        return;
+    }
+    log_line!("Giving up on restarting the Claude bridge after {MAX_RESTART_ATTEMPTS} attempts");
+}
+
 // ============================================================================
 // Internal Functions
 // ============================================================================
 
 /// Check if Claude Code / Node.js is properly installed.
-async fn check_claude_installation(claude_bin: Option<&str>) -> Result<Value, String> {
+pub(crate) async fn check_claude_installation(claude_bin: Option<&str>) -> Result<Value, String> {
     let path_env = build_node_path_env(claude_bin);
 
     // Check Node.js
@@ -926,3 +2031,100 @@ async fn check_claude_installation(claude_bin: Option<&str>) -> Result<Value, St
         "path": path_env,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+
+    use serde_json::Value;
+
+    use super::{
+        reply_error, restart_delay, turn_cost, ClaudeEvent, ClaudeEventPayload, EventBatcher,
+        RequestTimeouts,
+    };
+
+    fn event(event_type: &str, session_id: &str) -> ClaudeEvent {
+        ClaudeEvent {
+            event_type: event_type.to_string(),
+            session_id: session_id.to_string(),
+            workspace_id: "ws".to_string(),
+            timestamp: 0,
+            payload: ClaudeEventPayload::Raw(Value::Null),
+        }
+    }
+
+    #[test]
+    fn batches_per_session_until_window_closes() {
+        let windows = HashMap::from([
+            ("message/delta".to_string(), 16),
+            ("tool/progress".to_string(), 0),
+        ]);
+        let mut batcher = EventBatcher::new(&windows);
+        assert!(batcher.batches("message/delta"));
+        assert!(!batcher.batches("tool/progress"));
+
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        batcher.push(event("message/delta", "a"), at(0));
+        batcher.push(event("message/delta", "b"), at(10));
+        batcher.push(event("message/delta", "a"), at(12));
+        assert_eq!(batcher.next_deadline(), Some(at(16)));
+        assert!(batcher.take_due(at(15)).is_empty());
+
+        let due = batcher.take_due(at(16));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].len(), 2);
+        assert_eq!(due[0][0].session_id, "a");
+        let rest = batcher.take_all();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0][0].session_id, "b");
+        assert!(batcher.next_deadline().is_none());
+    }
+
+    #[test]
+    fn restart_delay_backs_off_exponentially() {
+        let delays: Vec<u64> = (0..8)
+            .map(|attempt| restart_delay(attempt).as_secs())
+            .collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(restart_delay(u32::MAX).as_secs(), 60);
+    }
+
+    #[test]
+    fn request_timeouts_fall_back_to_the_wildcard() {
+        let seconds = HashMap::from([
+            ("*".to_string(), 60),
+            ("text/complete".to_string(), 300),
+            ("session/start".to_string(), 0),
+        ]);
+        let timeouts = RequestTimeouts::new(&seconds);
+        assert_eq!(
+            timeouts.for_method("text/complete"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(timeouts.for_method("ping"), Some(Duration::from_secs(60)));
+        assert_eq!(timeouts.for_method("session/start"), None);
+        assert_eq!(RequestTimeouts::default().for_method("ping"), None);
+    }
+
+    #[test]
+    fn turn_cost_is_the_rise_in_the_running_total() {
+        let mut reported = 0.0;
+        assert_eq!(turn_cost(&mut reported, 0.25), 0.25);
+        assert_eq!(turn_cost(&mut reported, 0.75), 0.5);
+        assert_eq!(turn_cost(&mut reported, 0.75), 0.0);
+        // The bridge session started over.
+        assert_eq!(turn_cost(&mut reported, 0.125), 0.125);
+        assert_eq!(reported, 0.125);
+    }
+
+    #[test]
+    fn error_replies_are_failures() {
+        let error = serde_json::json!({ "error": "Session not found" });
+        assert_eq!(reply_error(&error).as_deref(), Some("Session not found"));
+        let structured = serde_json::json!({ "error": { "code": 1 } });
+        assert_eq!(reply_error(&structured).as_deref(), Some("{\"code\":1}"));
+        assert_eq!(reply_error(&serde_json::json!({ "result": {} })), None);
+    }
+}
@@ -0,0 +1,378 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::State;
+
+use crate::claude::build_session_env;
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+
+/// Claude settings scopes from lowest to highest precedence.
+const SETTINGS_SCOPES: &[&str] = &["global", "project", "local"];
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsFileInfo {
+    scope: String,
+    path: String,
+    exists: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EffectiveSetting {
+    /// Dotted path, e.g. `permissions.defaultMode`.
+    key: String,
+    value: Value,
+    /// Scope whose value wins (for arrays, the highest contributing scope).
+    source: String,
+    /// Every scope that sets the key, lowest precedence first.
+    defined_in: Vec<String>,
+    /// Arrays are concatenated across scopes rather than overridden.
+    merged: bool,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SettingsConflict {
+    key: String,
+    source: String,
+    message: String,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClaudeSettingsInspection {
+    files: Vec<SettingsFileInfo>,
+    effective: Value,
+    settings: Vec<EffectiveSetting>,
+    conflicts: Vec<SettingsConflict>,
+}
+
+/// Settings file for a scope: "global" (~/.claude/settings.json),
+/// "project" (.claude/settings.json) or "local" (.claude/settings.local.json).
+pub(crate) async fn settings_path(
+    state: &State<'_, AppState>,
+    workspace_id: Option<&str>,
+    scope: &str,
+) -> Result<PathBuf, String> {
+    if scope == "global" {
+        let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
+        return Ok(home.join(".claude").join("settings.json"));
+    }
+    let file_name = match scope {
+        "project" => "settings.json",
+        "local" => "settings.local.json",
+        _ => return Err(format!("Unknown settings scope: {scope}")),
+    };
+    let workspace_id = workspace_id.ok_or("workspace_id is required for workspace settings")?;
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces.get(workspace_id).ok_or("workspace not found")?;
+    Ok(PathBuf::from(&entry.path).join(".claude").join(file_name))
+}
+
+pub(crate) fn read_settings_object(path: &Path) -> Result<Map<String, Value>, String> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if data.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_json::from_str::<Value>(&data).map_err(|e| e.to_string())? {
+        Value::Object(map) => Ok(map),
+        _ => Err(format!("{} is not a JSON object", path.display())),
+    }
+}
+
+/// Leaf values keyed by dotted path; objects recurse, arrays are leaves.
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, child, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+fn insert_dotted(root: &mut Map<String, Value>, key: &str, value: Value) {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let last = parts.pop().unwrap_or(key);
+    let mut current = root;
+    for part in parts {
+        let entry = current
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        let Value::Object(next) = entry else {
+            unreachable!();
+        };
+        current = next;
+    }
+    current.insert(last.to_string(), value);
+}
+
+/// Merge scopes the way Claude Code does: higher scopes override scalars and
+/// objects key by key, arrays (permission rules) concatenate without repeats.
+pub(crate) fn merge_settings(
+    layers: &[(String, Map<String, Value>)],
+) -> (Value, Vec<EffectiveSetting>) {
+    let mut merged: BTreeMap<String, EffectiveSetting> = BTreeMap::new();
+    for (scope, settings) in layers {
+        let mut leaves = BTreeMap::new();
+        flatten("", &Value::Object(settings.clone()), &mut leaves);
+        for (key, value) in leaves {
+            if key.is_empty() {
+                continue;
+            }
+            match merged.get_mut(&key) {
+                Some(existing) => {
+                    existing.defined_in.push(scope.clone());
+                    existing.source = scope.clone();
+                    match (&mut existing.value, value) {
+                        (Value::Array(current), Value::Array(extra)) => {
+                            for item in extra {
+                                if !current.contains(&item) {
+                                    current.push(item);
+                                }
+                            }
+                            existing.merged = true;
+                        }
+                        (current, value) => *current = value,
+                    }
+                }
+                None => {
+                    merged.insert(
+                        key.clone(),
+                        EffectiveSetting {
+                            key,
+                            value,
+                            source: scope.clone(),
+                            defined_in: vec![scope.clone()],
+                            merged: false,
+                        },
+                    );
+                }
+            }
+        }
+    }
+    let mut effective = Map::new();
+    for setting in merged.values() {
+        insert_dotted(&mut effective, &setting.key, setting.value.clone());
+    }
+    (Value::Object(effective), merged.into_values().collect())
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    ["KEY", "TOKEN", "SECRET", "PASSWORD", "HEADERS"]
+        .iter()
+        .any(|hint| upper.contains(hint))
+}
+
+/// Places where Claude settings override or fight what the monitor sets up
+/// for the workspace.
+fn detect_conflicts(
+    settings: &[EffectiveSetting],
+    monitor_env: &BTreeMap<String, String>,
+    permission_mode: &str,
+    api_model: Option<&str>,
+    api_key_mode: bool,
+) -> Vec<SettingsConflict> {
+    let mut conflicts = Vec::new();
+    for setting in settings {
+        if let Some(name) = setting.key.strip_prefix("env.") {
+            let Some(monitor_value) = monitor_env.get(name) else {
+                continue;
+            };
+            if setting.value.as_str() == Some(monitor_value.as_str()) {
+                continue;
+            }
+            let message = if is_secret_name(name) {
+                format!("{name} is set by both the monitor and Claude settings with different values; Claude settings win.")
+            } else {
+                format!(
+                    "{name} is {} in Claude settings but the monitor sets {monitor_value}; Claude settings win.",
+                    setting.value
+                )
+            };
+            conflicts.push(SettingsConflict {
+                key: setting.key.clone(),
+                source: setting.source.clone(),
+                message,
+            });
+            continue;
+        }
+        match setting.key.as_str() {
+            "permissions.defaultMode" => {
+                if let Some(mode) = setting.value.as_str() {
+                    if mode != permission_mode {
+                        conflicts.push(SettingsConflict {
+                            key: setting.key.clone(),
+                            source: setting.source.clone(),
+                            message: format!(
+                                "Claude settings default to \"{mode}\" permissions; the monitor starts sessions in \"{permission_mode}\"."
+                            ),
+                        });
+                    }
+                }
+            }
+            "model" => {
+                if let (Some(model), Some(api_model)) = (setting.value.as_str(), api_model) {
+                    if model != api_model {
+                        conflicts.push(SettingsConflict {
+                            key: setting.key.clone(),
+                            source: setting.source.clone(),
+                            message: format!(
+                                "Claude settings select model \"{model}\"; the monitor's API model is \"{api_model}\"."
+                            ),
+                        });
+                    }
+                }
+            }
+            "apiKeyHelper" if api_key_mode => conflicts.push(SettingsConflict {
+                key: setting.key.clone(),
+                source: setting.source.clone(),
+                message:
+                    "apiKeyHelper overrides the API key the monitor supplies from the keychain."
+                        .to_string(),
+            }),
+            _ => {}
+        }
+    }
+    conflicts
+}
+
+/// Effective Claude configuration for a workspace, where each value comes
+/// from, and where it disagrees with the monitor's own settings.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn inspect_claude_settings(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ClaudeSettingsInspection, String> {
+    let mut files = Vec::new();
+    let mut layers = Vec::new();
+    for scope in SETTINGS_SCOPES {
+        let path = settings_path(&state, Some(&workspace_id), scope).await?;
+        let (settings, error) = match read_settings_object(&path) {
+            Ok(settings) => (settings, None),
+            Err(error) => (Map::new(), Some(error)),
+        };
+        files.push(SettingsFileInfo {
+            scope: scope.to_string(),
+            exists: path.exists(),
+            path: path.to_string_lossy().to_string(),
+            error,
+        });
+        layers.push((scope.to_string(), settings));
+    }
+    let (effective, settings) = merge_settings(&layers);
+
+    // Incomplete provider settings already surface elsewhere; compare what we can.
+    let monitor_env: BTreeMap<String, String> = build_session_env(&state, &workspace_id)
+        .await
+        .map(|(_, env)| env.into_iter().collect())
+        .unwrap_or_default();
+    let (permission_mode, api_model, api_key_mode) = {
+        let app_settings = state.app_settings.lock().await;
+        (
+            app_settings.default_permission_mode.clone(),
+            app_settings.api_model.clone(),
+            app_settings.claude_auth_mode == crate::types::ClaudeAuthMode::ApiKey,
+        )
+    };
+    let conflicts = detect_conflicts(
+        &settings,
+        &monitor_env,
+        &permission_mode,
+        api_model.as_deref(),
+        api_key_mode,
+    );
+    Ok(ClaudeSettingsInspection {
+        files,
+        effective,
+        settings,
+        conflicts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::{json, Map, Value};
+
+    use super::{detect_conflicts, merge_settings};
+
+    fn layer(scope: &str, value: Value) -> (String, Map<String, Value>) {
+        (scope.to_string(), value.as_object().unwrap().clone())
+    }
+
+    #[test]
+    fn merges_scopes_with_precedence() {
+        let (effective, settings) = merge_settings(&[
+            layer(
+                "global",
+                json!({ "model": "opus", "permissions": { "allow": ["Bash(ls)"] }, "env": { "A": "1" } }),
+            ),
+            layer(
+                "project",
+                json!({ "permissions": { "allow": ["Bash(ls)", "Read"], "defaultMode": "plan" } }),
+            ),
+            layer("local", json!({ "model": "sonnet" })),
+        ]);
+        assert_eq!(effective["model"], "sonnet");
+        assert_eq!(
+            effective["permissions"]["allow"],
+            json!(["Bash(ls)", "Read"])
+        );
+        assert_eq!(effective["env"]["A"], "1");
+        let model = settings.iter().find(|s| s.key == "model").unwrap();
+        assert_eq!(model.source, "local");
+        assert_eq!(model.defined_in, vec!["global", "local"]);
+        let allow = settings
+            .iter()
+            .find(|s| s.key == "permissions.allow")
+            .unwrap();
+        assert!(allow.merged);
+    }
+
+    #[test]
+    fn flags_monitor_conflicts() {
+        let (_, settings) = merge_settings(&[layer(
+            "project",
+            json!({
+                "env": { "ANTHROPIC_BASE_URL": "https://other.example.com", "ANTHROPIC_API_KEY": "x" },
+                "permissions": { "defaultMode": "acceptEdits" }
+            }),
+        )]);
+        let mut monitor_env = BTreeMap::new();
+        monitor_env.insert(
+            "ANTHROPIC_BASE_URL".to_string(),
+            "https://llm.example.com".to_string(),
+        );
+        let conflicts = detect_conflicts(&settings, &monitor_env, "default", None, false);
+        let keys: Vec<&str> = conflicts.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["env.ANTHROPIC_BASE_URL", "permissions.defaultMode"]
+        );
+    }
+}
@@ -14,6 +14,7 @@ use tokio::process::{Child, ChildStdin, Command};
 use tokio::sync::{oneshot, Mutex};
 use tokio::time::timeout;
 
+use crate::command_metrics::CommandTimer;
 use crate::state::AppState;
 use crate::types::WorkspaceEntry;
 
@@ -129,7 +130,7 @@ fn build_codex_command_with_bin(codex_bin: Option<String>) -> Command {
     command
 }
 
-async fn check_codex_installation(codex_bin: Option<String>) -> Result<Option<String>, String> {
+pub(crate) async fn check_codex_installation(codex_bin: Option<String>) -> Result<Option<String>, String> {
     let mut command = build_codex_command_with_bin(codex_bin);
     command.arg("--version");
     command.stdout(std::process::Stdio::piped());
@@ -317,9 +318,11 @@ pub(crate) async fn spawn_workspace_session(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn codex_doctor(
     codex_bin: Option<String>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let default_bin = {
         let settings = state.app_settings.lock().await;
@@ -412,9 +415,11 @@ pub(crate) async fn codex_doctor(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn start_thread(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -428,10 +433,12 @@ pub(crate) async fn start_thread(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn resume_thread(
     workspace_id: String,
     thread_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -444,11 +451,13 @@ pub(crate) async fn resume_thread(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn list_threads(
     workspace_id: String,
     cursor: Option<String>,
     limit: Option<u32>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -462,10 +471,12 @@ pub(crate) async fn list_threads(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn archive_thread(
     workspace_id: String,
     thread_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -478,6 +489,7 @@ pub(crate) async fn archive_thread(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn send_user_message(
     workspace_id: String,
     thread_id: String,
@@ -487,6 +499,7 @@ pub(crate) async fn send_user_message(
     access_mode: Option<String>,
     images: Option<Vec<String>>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -548,11 +561,13 @@ pub(crate) async fn send_user_message(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn turn_interrupt(
     workspace_id: String,
     thread_id: String,
     turn_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -566,12 +581,14 @@ pub(crate) async fn turn_interrupt(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn start_review(
     workspace_id: String,
     thread_id: String,
     target: Value,
     delivery: Option<String>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -589,9 +606,11 @@ pub(crate) async fn start_review(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn model_list(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -602,9 +621,11 @@ pub(crate) async fn model_list(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn account_rate_limits(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -616,9 +637,11 @@ pub(crate) async fn account_rate_limits(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn skills_list(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Value, String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -631,11 +654,13 @@ pub(crate) async fn skills_list(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn respond_to_server_request(
     workspace_id: String,
     request_id: u64,
     result: Value,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
     let sessions = state.sessions.lock().await;
     let session = sessions
@@ -0,0 +1,308 @@
+//! Per-command latency histograms and slow-command logging, all measured in
+//! the backend: every command takes a `CommandTimer` argument, which Tauri
+//! builds before the command runs and drops with the command's other
+//! arguments when it returns, or, for async commands, when its future
+//! completes. The frontend only reads the results (`get_command_metrics`).
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::ipc::{CommandArg, CommandItem, InvokeBody, InvokeError};
+use tauri::{AppHandle, Manager, State};
+
+use crate::crash::log_line;
+use crate::state::AppState;
+
+/// Upper bounds (ms) of the latency histogram buckets; a last bucket
+/// counts everything slower.
+const BUCKET_BOUNDS_MS: &[f64] = &[5.0, 25.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 10_000.0];
+/// Invocations slower than this are logged with their arguments.
+const SLOW_COMMAND_MS: f64 = 500.0;
+/// String arguments longer than this are cut in slow-command logs.
+const MAX_LOGGED_CHARS: usize = 80;
+/// Argument names whose values are never logged.
+const SENSITIVE_KEYS: &[&str] = &[
+    "token", "secret", "password", "key", "headers", "env", "message", "prompt", "content", "text",
+];
+
+/// Times the command it is passed to; records on drop.
+pub(crate) struct CommandTimer {
+    command: &'static str,
+    /// Redacted up front, so a large payload is neither copied nor kept.
+    args: Value,
+    started: Instant,
+    /// `None` for calls made from Rust, which aren't recorded.
+    app: Option<AppHandle>,
+}
+
+impl CommandTimer {
+    /// For calling a command directly from another part of the backend.
+    pub(crate) fn untimed() -> Self {
+        Self {
+            command: "",
+            args: Value::Null,
+            started: Instant::now(),
+            app: None,
+        }
+    }
+}
+
+impl<'de> CommandArg<'de, tauri::Wry> for CommandTimer {
+    fn from_command(command: CommandItem<'de, tauri::Wry>) -> Result<Self, InvokeError> {
+        let args = match command.message.payload() {
+            InvokeBody::Json(args) => redact(args),
+            InvokeBody::Raw(bytes) => Value::String(format!("[{} bytes]", bytes.len())),
+        };
+        Ok(Self {
+            command: command.name,
+            args,
+            started: Instant::now(),
+            app: Some(command.message.webview().app_handle().clone()),
+        })
+    }
+}
+
+/// Not part of the command's TypeScript signature.
+impl specta::function::FunctionArg for CommandTimer {
+    fn to_datatype(_: &mut specta::TypeCollection) -> Option<specta::datatype::DataType> {
+        None
+    }
+}
+
+impl Drop for CommandTimer {
+    fn drop(&mut self) {
+        let duration_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+        let Some(state) = self
+            .app
+            .as_ref()
+            .and_then(|app| app.try_state::<AppState>())
+        else {
+            return;
+        };
+        let line = match state.command_metrics.lock() {
+            Ok(mut metrics) => metrics.record(self.command, duration_ms, &self.args),
+            Err(_) => return,
+        };
+        if let Some(line) = line {
+            log_line!("{line}");
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct CommandStats {
+    count: u64,
+    slow: u64,
+    total_ms: f64,
+    max_ms: f64,
+    buckets: Vec<u64>,
+}
+
+/// Latency histograms per command since launch.
+#[derive(Debug, Default)]
+pub(crate) struct CommandMetrics {
+    commands: BTreeMap<String, CommandStats>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CommandMetric {
+    command: String,
+    count: u64,
+    slow: u64,
+    mean_ms: f64,
+    max_ms: f64,
+    /// Upper bound of the bucket holding the median; `None` past the last.
+    p50_ms: Option<f64>,
+    p95_ms: Option<f64>,
+    buckets: Vec<u64>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CommandMetricsReport {
+    bucket_bounds_ms: Vec<f64>,
+    slow_threshold_ms: f64,
+    /// Slowest total time first.
+    commands: Vec<CommandMetric>,
+}
+
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEYS
+        .iter()
+        .any(|sensitive| key.contains(sensitive))
+}
+
+/// Arguments safe to log: secrets and message bodies are replaced, long
+/// strings cut and arrays reduced to their length.
+fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let value = if is_sensitive(key) {
+                        Value::String("[redacted]".to_string())
+                    } else {
+                        redact(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::String(format!("[{} items]", items.len())),
+        Value::String(text) if text.chars().count() > MAX_LOGGED_CHARS => {
+            let cut: String = text.chars().take(MAX_LOGGED_CHARS).collect();
+            Value::String(format!("{cut}…"))
+        }
+        other => other.clone(),
+    }
+}
+
+fn percentile(buckets: &[u64], count: u64, fraction: f64) -> Option<f64> {
+    let target = (count as f64 * fraction).ceil().max(1.0) as u64;
+    let mut seen = 0;
+    for (index, bucket) in buckets.iter().enumerate() {
+        seen += bucket;
+        if seen >= target {
+            return BUCKET_BOUNDS_MS.get(index).copied();
+        }
+    }
+    None
+}
+
+impl CommandMetrics {
+    /// Add a timing; returns a log line when the invocation was slow.
+    fn record(&mut self, command: &str, duration_ms: f64, args: &Value) -> Option<String> {
+        let stats = self.commands.entry(command.to_string()).or_default();
+        if stats.buckets.is_empty() {
+            stats.buckets = vec![0; BUCKET_BOUNDS_MS.len() + 1];
+        }
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| duration_ms <= *bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        stats.buckets[bucket] += 1;
+        stats.count += 1;
+        stats.total_ms += duration_ms;
+        stats.max_ms = stats.max_ms.max(duration_ms);
+        if duration_ms <= SLOW_COMMAND_MS {
+            return None;
+        }
+        stats.slow += 1;
+        Some(format!(
+            "Slow command {command} took {duration_ms:.0}ms {args}"
+        ))
+    }
+
+    fn report(&self) -> CommandMetricsReport {
+        let mut commands: Vec<CommandMetric> = self
+            .commands
+            .iter()
+            .map(|(command, stats)| CommandMetric {
+                command: command.clone(),
+                count: stats.count,
+                slow: stats.slow,
+                mean_ms: stats.total_ms / stats.count.max(1) as f64,
+                max_ms: stats.max_ms,
+                p50_ms: percentile(&stats.buckets, stats.count, 0.5),
+                p95_ms: percentile(&stats.buckets, stats.count, 0.95),
+                buckets: stats.buckets.clone(),
+            })
+            .collect();
+        commands
+            .sort_by(|a, b| (b.mean_ms * b.count as f64).total_cmp(&(a.mean_ms * a.count as f64)));
+        CommandMetricsReport {
+            bucket_bounds_ms: BUCKET_BOUNDS_MS.to_vec(),
+            slow_threshold_ms: SLOW_COMMAND_MS,
+            commands,
+        }
+    }
+}
+
+/// Latency per command since launch.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_command_metrics(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<CommandMetricsReport, String> {
+    let metrics = state.command_metrics.lock().map_err(|e| e.to_string())?;
+    Ok(metrics.report())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{redact, CommandMetrics};
+    use serde_json::json;
+
+    #[test]
+    fn records_histograms_and_logs_slow_calls() {
+        let mut metrics = CommandMetrics::default();
+        for _ in 0..9 {
+            assert!(metrics.record("list_workspaces", 3.0, &json!({})).is_none());
+        }
+        assert!(metrics
+            .record("list_workspaces", 40.0, &json!({}))
+            .is_none());
+        let args = redact(&json!({ "workspaceId": "w1", "apiKey": "sk-1" }));
+        let line = metrics.record("register_session", 900.0, &args).unwrap();
+        assert!(line.contains("register_session took 900ms"));
+        assert!(line.contains("w1") && !line.contains("sk-1"));
+
+        let report = metrics.report();
+        assert_eq!(report.commands[0].command, "register_session");
+        assert_eq!(report.commands[0].slow, 1);
+        let list = &report.commands[1];
+        assert_eq!((list.count, list.max_ms), (10, 40.0));
+        assert_eq!(list.p50_ms, Some(5.0));
+        assert_eq!(list.p95_ms, Some(100.0));
+    }
+
+    #[test]
+    fn redacts_secrets_and_message_bodies() {
+        let redacted = redact(&json!({
+            "sessionId": "s1",
+            "message": "private",
+            "settings": { "apiHeaders": { "x": "y" }, "theme": "dark" },
+            "images": ["a", "b"],
+            "path": "x".repeat(100),
+        }));
+        assert_eq!(redacted["sessionId"], "s1");
+        assert_eq!(redacted["message"], "[redacted]");
+        assert_eq!(redacted["settings"]["apiHeaders"], "[redacted]");
+        assert_eq!(redacted["settings"]["theme"], "dark");
+        assert_eq!(redacted["images"], "[2 items]");
+        assert_eq!(redacted["path"].as_str().unwrap().chars().count(), 81);
+    }
+
+    #[test]
+    fn every_command_is_timed() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut untimed = Vec::new();
+        for entry in std::fs::read_dir(src).unwrap() {
+            let path = entry.unwrap().path();
+            let source = std::fs::read_to_string(&path).unwrap();
+            for (start, _) in source.match_indices("#[tauri::command]\n") {
+                let signature = &source[start..];
+                let signature = &signature[..signature.find('{').unwrap_or(signature.len())];
+                if !signature.contains("CommandTimer") {
+                    untimed.push(format!(
+                        "{}: {}",
+                        path.display(),
+                        signature.lines().nth(2).unwrap_or("")
+                    ));
+                }
+            }
+        }
+        assert!(
+            untimed.is_empty(),
+            "Commands without a CommandTimer:\n{}",
+            untimed.join("\n")
+        );
+    }
+}
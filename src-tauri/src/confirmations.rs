@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tokio::sync::oneshot;
+
+use crate::command_metrics::CommandTimer;
+use crate::release::{parse_release_target, release_tag};
+use crate::stale_worktrees::parse_cleanup_target;
+use crate::state::AppState;
+use crate::team_prompts::pending_permissions;
+use crate::workflows::{parse_workflow, workflow_commands};
+
+/// How long a granted confirmation stays usable.
+const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+pub(crate) struct ConfirmationGrant {
+    action: String,
+    target: String,
+    expires_at: Instant,
+}
+
+/// Single-use confirmation tokens, keyed by token.
+pub(crate) type ConfirmationGrants = HashMap<String, ConfirmationGrant>;
+
+struct ConfirmationPrompt {
+    title: &'static str,
+    message: String,
+    ok_label: &'static str,
+}
+
+/// Wording is built here from backend state, never taken from the webview,
+/// so a compromised page cannot disguise what the user is agreeing to.
+async fn prompt_for(
+    state: &State<'_, AppState>,
+    action: &str,
+    target: &str,
+) -> Result<ConfirmationPrompt, String> {
+    match action {
+        "remove_workspace" => {
+            let workspaces = state.workspaces.lock().await;
+            let entry = workspaces.get(target).ok_or("workspace not found")?;
+            let worktrees = workspaces
+                .values()
+                .filter(|workspace| workspace.parent_id.as_deref() == Some(target))
+                .count();
+            let detail = match worktrees {
+                0 => String::new(),
+                1 => "\n\nThis will also delete 1 worktree on disk.".to_string(),
+                count => format!("\n\nThis will also delete {count} worktrees on disk."),
+            };
+            Ok(ConfirmationPrompt {
+                title: "Delete Workspace",
+                message: format!(
+                    "Are you sure you want to delete \"{}\"?\n\nThis will remove the workspace from Claude Monitor.{detail}",
+                    entry.name
+                ),
+                ok_label: "Delete",
+            })
+        }
+        "remove_worktree" => {
+            let workspaces = state.workspaces.lock().await;
+            let entry = workspaces.get(target).ok_or("workspace not found")?;
+            Ok(ConfirmationPrompt {
+                title: "Delete Worktree",
+                message: format!(
+                    "Are you sure you want to delete \"{}\"?\n\nThis will close the agent, remove its worktree, and delete it from Claude Monitor.",
+                    entry.name
+                ),
+                ok_label: "Delete",
+            })
+        }
+        "clean_up_worktrees" => {
+            let (ids, delete_branches) = parse_cleanup_target(target);
+            let workspaces = state.workspaces.lock().await;
+            let names = ids
+                .iter()
+                .map(|id| {
+                    let entry = workspaces.get(id).ok_or("workspace not found")?;
+                    Ok(format!("• {}", entry.name))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            let branches = if delete_branches {
+                "\n\nTheir branches will be deleted too, unless they have unmerged commits."
+            } else {
+                ""
+            };
+            Ok(ConfirmationPrompt {
+                title: "Clean Up Worktrees",
+                message: format!(
+                    "Delete {} worktree(s)?\n\n{}\n\nTheir agents will be closed and their folders removed, including uncommitted changes.{branches}",
+                    names.len(),
+                    names.join("\n")
+                ),
+                ok_label: "Delete",
+            })
+        }
+        "accept_team_permissions" => {
+            let pending = pending_permissions(state, Some(target))
+                .await?
+                .ok_or("team permissions are already accepted")?;
+            let list = |rules: &[String]| {
+                rules
+                    .iter()
+                    .map(|rule| format!("• {rule}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            let mut sections = Vec::new();
+            if !pending.added.is_empty() {
+                sections.push(format!("Allowed without asking:\n{}", list(&pending.added)));
+            }
+            if !pending.removed.is_empty() {
+                sections.push(format!("No longer allowed:\n{}", list(&pending.removed)));
+            }
+            Ok(ConfirmationPrompt {
+                title: "Accept Team Permissions",
+                message: format!(
+                    "Apply the team repository's tool rules from commit {}?\n\n{}\n\nSessions started after this will use them.",
+                    &pending.commit[..pending.commit.len().min(12)],
+                    sections.join("\n\n")
+                ),
+                ok_label: "Accept",
+            })
+        }
+        "rewind_files" => Ok(ConfirmationPrompt {
+            title: "Rewind Files",
+            message:
+                "Restore files to this message?\n\nChanges Claude made after it will be discarded."
+                    .to_string(),
+            ok_label: "Rewind",
+        }),
+        "run_hook" => Ok(ConfirmationPrompt {
+            title: "Run Hook Command",
+            message: format!("Run this command in the workspace?\n\n{target}"),
+            ok_label: "Run",
+        }),
+        "save_hooks" => {
+            let message = if target.is_empty() {
+                "Remove all hooks from these settings?".to_string()
+            } else {
+                let list = target
+                    .lines()
+                    .map(|command| format!("• {command}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("Save hooks that run these commands during Claude sessions, without asking?\n\n{list}")
+            };
+            Ok(ConfirmationPrompt {
+                title: "Save Hooks",
+                message,
+                ok_label: "Save",
+            })
+        }
+        "run_workflow" => {
+            let workflow = parse_workflow(target)?;
+            let commands = workflow_commands(&workflow);
+            let detail = if commands.is_empty() {
+                "It only sends prompts to the session.".to_string()
+            } else {
+                let list = commands
+                    .iter()
+                    .map(|command| format!("• {command}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("It runs these commands in the workspace:\n{list}")
+            };
+            Ok(ConfirmationPrompt {
+                title: "Run Workflow",
+                message: format!("Run the workflow \"{}\"?\n\n{detail}", workflow.name),
+                ok_label: "Run",
+            })
+        }
+        "create_release" => {
+            let (workspace_id, version) =
+                parse_release_target(target).ok_or("invalid release target")?;
+            let tag = release_tag(version)?;
+            let workspaces = state.workspaces.lock().await;
+            let entry = workspaces.get(workspace_id).ok_or("workspace not found")?;
+            Ok(ConfirmationPrompt {
+                title: "Create Release",
+                message: format!(
+                    "Release \"{}\" as {tag}?\n\nThis will tag HEAD, push the tag and publish a GitHub release.",
+                    entry.name
+                ),
+                ok_label: "Release",
+            })
+        }
+        "remove_account" => {
+            let settings = state.app_settings.lock().await;
+            let account = settings
+                .claude_accounts
+                .iter()
+                .find(|account| account.id == target)
+                .ok_or("account not found")?;
+            Ok(ConfirmationPrompt {
+                title: "Remove Account",
+                message: format!(
+                    "Remove the account \"{}\"?\n\nIts stored API key will be deleted from the keychain.",
+                    account.label
+                ),
+                ok_label: "Remove",
+            })
+        }
+        _ => Err(format!("Unknown confirmation action: {action}")),
+    }
+}
+
+fn check_grant(
+    grant: Option<ConfirmationGrant>,
+    action: &str,
+    target: &str,
+    now: Instant,
+) -> Result<(), String> {
+    match grant {
+        Some(grant)
+            if grant.action == action && grant.target == target && grant.expires_at > now =>
+        {
+            Ok(())
+        }
+        _ => Err("confirmation required".to_string()),
+    }
+}
+
+/// Consume the token a destructive command was called with. Tokens are
+/// single-use and bound to one action and target.
+pub(crate) async fn consume_confirmation(
+    state: &AppState,
+    token: Option<&str>,
+    action: &str,
+    target: &str,
+) -> Result<(), String> {
+    let grant = match token {
+        Some(token) => state.confirmations.lock().await.remove(token),
+        None => None,
+    };
+    check_grant(grant, action, target, Instant::now())
+}
+
+/// Ask the user in a native dialog, outside the webview, whether to allow
+/// a destructive action. Returns a short-lived token on approval.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn request_confirmation(
+    action: String,
+    target: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<String>, String> {
+    let prompt = prompt_for(&state, &action, &target).await?;
+    let (tx, rx) = oneshot::channel();
+    app.dialog()
+        .message(prompt.message)
+        .title(prompt.title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            prompt.ok_label.to_string(),
+            "Cancel".to_string(),
+        ))
+        .show(move |confirmed| {
+            let _ = tx.send(confirmed);
+        });
+    if !rx.await.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let token = uuid::Uuid::new_v4().to_string();
+    let now = Instant::now();
+    let mut grants = state.confirmations.lock().await;
+    grants.retain(|_, grant| grant.expires_at > now);
+    grants.insert(
+        token.clone(),
+        ConfirmationGrant {
+            action,
+            target,
+            expires_at: now + CONFIRMATION_TTL,
+        },
+    );
+    Ok(Some(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{check_grant, ConfirmationGrant};
+
+    #[test]
+    fn grants_match_action_target_and_expiry() {
+        let now = Instant::now();
+        let grant = || {
+            Some(ConfirmationGrant {
+                action: "remove_worktree".to_string(),
+                target: "ws-1".to_string(),
+                expires_at: now + Duration::from_secs(60),
+            })
+        };
+        assert!(check_grant(grant(), "remove_worktree", "ws-1", now).is_ok());
+        assert!(check_grant(grant(), "remove_worktree", "ws-2", now).is_err());
+        assert!(check_grant(grant(), "remove_workspace", "ws-1", now).is_err());
+        assert!(check_grant(
+            grant(),
+            "remove_worktree",
+            "ws-1",
+            now + Duration::from_secs(61)
+        )
+        .is_err());
+        assert!(check_grant(None, "remove_worktree", "ws-1", now).is_err());
+    }
+}
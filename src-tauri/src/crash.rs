@@ -0,0 +1,272 @@
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::registry::now_millis;
+use crate::state::AppState;
+use crate::utils::{curl_config_value, run_curl};
+
+/// Log lines kept for the next crash report.
+const LOG_LINES: usize = 200;
+
+static RECENT_LOG: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Mirrors `crashReports.enabled`; read from the panic hook, which has no
+/// access to app state.
+static CAPTURE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `eprintln!` that also keeps the line for crash reports.
+macro_rules! log_line {
+    ($($arg:tt)*) => {{
+        let line = format!($($arg)*);
+        eprintln!("{line}");
+        $crate::crash::remember_log_line(line);
+    }};
+}
+pub(crate) use log_line;
+
+pub(crate) fn remember_log_line(line: String) {
+    if let Ok(mut log) = RECENT_LOG.lock() {
+        push_capped(&mut log, line, LOG_LINES);
+    }
+}
+
+/// The last `LOG_LINES` log lines, oldest first.
+pub(crate) fn recent_log() -> Vec<String> {
+    RECENT_LOG
+        .lock()
+        .map(|log| log.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn push_capped(log: &mut VecDeque<String>, line: String, cap: usize) {
+    if log.len() >= cap {
+        log.pop_front();
+    }
+    log.push_back(line);
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CrashReport {
+    id: String,
+    created_at: u64,
+    app_version: String,
+    os: String,
+    arch: String,
+    thread: String,
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+    backtrace: String,
+    log: Vec<String>,
+    #[serde(default)]
+    submitted: bool,
+}
+
+/// Turn capture on or off, following the setting.
+pub(crate) fn set_capture_enabled(enabled: bool) {
+    CAPTURE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+fn panic_message(info: &std::panic::PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+fn report_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err("Invalid crash report id".to_string());
+    }
+    Ok(dir.join(format!("{id}.json")))
+}
+
+fn write_report(dir: &Path, report: &CrashReport) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let data = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+    std::fs::write(report_path(dir, &report.id)?, data).map_err(|e| e.to_string())
+}
+
+/// Reports in `dir`, newest first. Unreadable files are skipped.
+fn read_reports(dir: &Path) -> Vec<CrashReport> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<CrashReport> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|data| serde_json::from_str(&data).ok())
+        .collect();
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    reports
+}
+
+/// Install a panic hook that writes a report to `crash_dir` when capture is
+/// enabled. The default hook still prints the panic.
+pub(crate) fn install_panic_hook(crash_dir: PathBuf, enabled: bool) {
+    set_capture_enabled(enabled);
+    if CRASH_DIR.set(crash_dir).is_err() {
+        return;
+    }
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+        if !CAPTURE_ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let Some(dir) = CRASH_DIR.get() else {
+            return;
+        };
+        let log = recent_log();
+        let report = CrashReport {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at: now_millis(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            message: panic_message(info),
+            location: info
+                .location()
+                .map(|location| format!("{}:{}", location.file(), location.line())),
+            backtrace: Backtrace::force_capture().to_string(),
+            log,
+            submitted: false,
+        };
+        if let Err(err) = write_report(dir, &report) {
+            eprintln!("Failed to write crash report: {err}");
+        }
+    }));
+}
+
+fn crash_dir(state: &AppState) -> PathBuf {
+    CRASH_DIR.get().cloned().unwrap_or_else(|| {
+        state
+            .storage_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("crash-reports")
+    })
+}
+
+/// Crash reports written so far, newest first.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_crash_reports(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<CrashReport>, String> {
+    let dir = crash_dir(&state);
+    tokio::task::spawn_blocking(move || read_reports(&dir))
+        .await
+        .map_err(|_| "crash report read failed".to_string())
+}
+
+/// Send a report to `crashReports.submitUrl` as JSON and mark it submitted.
+/// Nothing is sent without an explicit call.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn submit_crash_report(
+    id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<CrashReport, String> {
+    let url = state
+        .app_settings
+        .lock()
+        .await
+        .crash_reports
+        .submit_url
+        .clone()
+        .filter(|url| !url.trim().is_empty())
+        .ok_or("No crash report URL is configured")?;
+    let dir = crash_dir(&state);
+    let path = report_path(&dir, &id)?;
+    let data = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut report: CrashReport = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+    let config = format!("url = {}\n", curl_config_value(url.trim()));
+    let args = [
+        "--fail".to_string(),
+        "-H".to_string(),
+        "Content-Type: application/json".to_string(),
+        "--data-binary".to_string(),
+        data,
+    ];
+    run_curl(&config, &args).await?;
+    report.submitted = true;
+    write_report(&dir, &report)?;
+    Ok(report)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn delete_crash_report(
+    id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let path = report_path(&crash_dir(&state), &id)?;
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::{push_capped, read_reports, report_path, write_report, CrashReport};
+    use crate::utils::TempDir;
+
+    fn report(id: &str, created_at: u64) -> CrashReport {
+        CrashReport {
+            id: id.to_string(),
+            created_at,
+            app_version: "0.1.0".to_string(),
+            os: "macos".to_string(),
+            arch: "aarch64".to_string(),
+            thread: "main".to_string(),
+            message: "boom".to_string(),
+            location: Some("src/lib.rs:1".to_string()),
+            backtrace: String::new(),
+            log: vec!["line".to_string()],
+            submitted: false,
+        }
+    }
+
+    #[test]
+    fn keeps_the_last_log_lines() {
+        let mut log = VecDeque::new();
+        for n in 0..5 {
+            push_capped(&mut log, n.to_string(), 3);
+        }
+        assert_eq!(log, ["2", "3", "4"]);
+    }
+
+    #[test]
+    fn reports_round_trip_newest_first() {
+        let dir = TempDir::new("crash-reports");
+        write_report(&dir, &report("a1", 1)).unwrap();
+        write_report(&dir, &report("b2", 2)).unwrap();
+        std::fs::write(dir.join("junk.json"), "not json").unwrap();
+
+        let ids: Vec<String> = read_reports(&dir).into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, ["b2", "a1"]);
+        assert!(report_path(&dir, "../settings").is_err());
+    }
+}
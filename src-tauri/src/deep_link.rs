@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::menu::{MenuItemBuilder, MenuItemKind};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::jump_list;
+use crate::state::AppState;
+use crate::types::{ThreadRegistry, WorkspaceEntry};
+
+pub(crate) const SCHEME: &str = "claude-monitor";
+/// Menu item ids carrying a deep link start with this.
+pub(crate) const MENU_PREFIX: &str = "deep-link:";
+/// The File menu's "Open Recent" submenu.
+pub(crate) const RECENT_MENU: &str = "recent-workspaces";
+pub(crate) const FILE_MENU: &str = "file";
+/// Workspaces listed under "Open Recent".
+const RECENT_LIMIT: usize = 5;
+
+/// Link passed on the command line at launch, kept until the frontend asks
+/// for it (it isn't listening yet when the app starts).
+static LAUNCH_LINK: Mutex<Option<DeepLink>> = Mutex::new(None);
+
+/// A `claude-monitor://` link, sent to the frontend as `deep-link`.
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub(crate) enum DeepLink {
+    /// `claude-monitor://workspace/<id>`
+    #[serde(rename_all = "camelCase")]
+    OpenWorkspace { workspace_id: String },
+    /// `claude-monitor://new-session/<id>`
+    #[serde(rename_all = "camelCase")]
+    NewSession { workspace_id: String },
+}
+
+impl DeepLink {
+    pub(crate) fn url(&self) -> String {
+        match self {
+            Self::OpenWorkspace { workspace_id } => {
+                format!("{SCHEME}://workspace/{workspace_id}")
+            }
+            Self::NewSession { workspace_id } => {
+                format!("{SCHEME}://new-session/{workspace_id}")
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_deep_link(url: &str) -> Result<DeepLink, String> {
+    let invalid = || format!("Unsupported link: {url}");
+    let rest = url
+        .trim()
+        .strip_prefix(SCHEME)
+        .and_then(|rest| rest.strip_prefix("://"))
+        .ok_or_else(invalid)?;
+    let (action, workspace_id) = rest
+        .trim_end_matches('/')
+        .split_once('/')
+        .ok_or_else(invalid)?;
+    if workspace_id.is_empty() || workspace_id.contains(['/', '?', '#']) {
+        return Err(invalid());
+    }
+    let workspace_id = workspace_id.to_string();
+    match action {
+        "workspace" => Ok(DeepLink::OpenWorkspace { workspace_id }),
+        "new-session" => Ok(DeepLink::NewSession { workspace_id }),
+        _ => Err(invalid()),
+    }
+}
+
+/// Bring the main window forward and hand it the link.
+pub(crate) fn handle_deep_link(app: &AppHandle, url: &str) {
+    let link = match parse_deep_link(url) {
+        Ok(link) => link,
+        Err(err) => {
+            log_line!("{err}");
+            return;
+        }
+    };
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let _ = app.emit("deep-link", link);
+}
+
+/// Route `claude-monitor://` links the OS opens, or that a second instance
+/// forwards through the single-instance plugin, to `handle_deep_link`.
+#[cfg(desktop)]
+pub(crate) fn listen(app: &AppHandle) {
+    use tauri_plugin_deep_link::DeepLinkExt;
+
+    // Installers register the scheme; this covers dev builds and portable
+    // copies. macOS only registers it from the bundle's Info.plist.
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    if let Err(err) = app.deep_link().register_all() {
+        log_line!("Failed to register the {SCHEME} scheme: {err}");
+    }
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        remember_launch_link(urls.into_iter().map(|url| url.to_string()));
+    }
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_deep_link(&handle, url.as_str());
+        }
+    });
+}
+
+/// Keep a link given as a launch argument, e.g. by a jump list task or a
+/// `.desktop` action that starts the app with the URL.
+pub(crate) fn remember_launch_link(args: impl IntoIterator<Item = String>) {
+    let link = args
+        .into_iter()
+        .find(|arg| arg.starts_with(SCHEME))
+        .and_then(|url| parse_deep_link(&url).ok());
+    if let (Some(link), Ok(mut slot)) = (link, LAUNCH_LINK.lock()) {
+        *slot = Some(link);
+    }
+}
+
+/// Workspaces by latest session activity, most recent first. Workspaces
+/// without sessions are left out.
+fn recent_workspaces<'a>(
+    workspaces: &'a HashMap<String, WorkspaceEntry>,
+    registry: &ThreadRegistry,
+    limit: usize,
+) -> Vec<&'a WorkspaceEntry> {
+    let mut recent: Vec<(u64, &WorkspaceEntry)> = workspaces
+        .values()
+        .filter_map(|workspace| {
+            let last = registry
+                .workspaces
+                .get(&workspace.id)?
+                .visible_session_ids
+                .iter()
+                .filter_map(|id| registry.sessions.get(id))
+                .map(|session| session.last_activity)
+                .max()?;
+            Some((last, workspace))
+        })
+        .collect();
+    recent.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    recent.truncate(limit);
+    recent.into_iter().map(|(_, workspace)| workspace).collect()
+}
+
+/// Title and link for each recent-workspace entry.
+fn recent_entries(workspaces: &[&WorkspaceEntry]) -> Vec<(String, DeepLink)> {
+    workspaces
+        .iter()
+        .flat_map(|workspace| {
+            let workspace_id = workspace.id.clone();
+            [
+                (
+                    workspace.name.clone(),
+                    DeepLink::OpenWorkspace {
+                        workspace_id: workspace_id.clone(),
+                    },
+                ),
+                (
+                    format!("New Session in {}", workspace.name),
+                    DeepLink::NewSession { workspace_id },
+                ),
+            ]
+        })
+        .collect()
+}
+
+/// Rebuild File > Open Recent, the dock menu and the jump list from
+/// workspace and session activity. Their items go through
+/// `handle_deep_link`, like links opened from outside the app.
+pub(crate) async fn refresh_recent_menu(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let entries = {
+        let workspaces = state.workspaces.lock().await;
+        let registry = state.registry.lock().await;
+        recent_entries(&recent_workspaces(&workspaces, &registry, RECENT_LIMIT))
+    };
+    jump_list::update(app, &entries);
+    let submenu = app
+        .menu()
+        .and_then(|menu| menu.get(FILE_MENU))
+        .and_then(|file| file.as_submenu().and_then(|file| file.get(RECENT_MENU)));
+    let Some(MenuItemKind::Submenu(submenu)) = submenu else {
+        return;
+    };
+    if let Ok(items) = submenu.items() {
+        for item in items {
+            let _ = submenu.remove(&item);
+        }
+    }
+    let _ = submenu.set_enabled(!entries.is_empty());
+    for (title, link) in entries {
+        let id = format!("{MENU_PREFIX}{}", link.url());
+        match MenuItemBuilder::with_id(id, title).build(app) {
+            Ok(item) => {
+                let _ = submenu.append(&item);
+            }
+            Err(err) => log_line!("Failed to build recent menu item: {err}"),
+        }
+    }
+}
+
+/// The launch link, once; `None` when the app was started normally.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn take_launch_deep_link(
+    _timer: CommandTimer,
+) -> Result<Option<DeepLink>, String> {
+    Ok(LAUNCH_LINK.lock().map_err(|e| e.to_string())?.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{parse_deep_link, recent_entries, recent_workspaces, DeepLink};
+    use crate::types::{SessionEntry, ThreadRegistry, WorkspaceEntry, WorkspaceRegistry};
+
+    #[test]
+    fn parses_and_builds_links() {
+        let link = DeepLink::NewSession {
+            workspace_id: "w1".to_string(),
+        };
+        assert_eq!(link.url(), "claude-monitor://new-session/w1");
+        assert_eq!(parse_deep_link(&link.url()), Ok(link));
+        assert_eq!(
+            parse_deep_link("claude-monitor://workspace/w2/"),
+            Ok(DeepLink::OpenWorkspace {
+                workspace_id: "w2".to_string()
+            })
+        );
+        assert!(parse_deep_link("claude-monitor://delete/w1").is_err());
+        assert!(parse_deep_link("claude-monitor://workspace/").is_err());
+        assert!(parse_deep_link("https://workspace/w1").is_err());
+    }
+
+    fn workspace(id: &str, name: &str) -> WorkspaceEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "path": format!("/src/{name}"),
+            "codex_bin": null,
+        }))
+        .unwrap()
+    }
+
+    fn session(id: &str, last_activity: u64) -> SessionEntry {
+        serde_json::from_value(serde_json::json!({
+            "sessionId": id,
+            "cwd": "/src",
+            "createdAt": 0,
+            "lastActivity": last_activity,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn lists_workspaces_by_latest_session() {
+        let workspaces = HashMap::from([
+            ("w1".to_string(), workspace("w1", "api")),
+            ("w2".to_string(), workspace("w2", "web")),
+            ("w3".to_string(), workspace("w3", "docs")),
+        ]);
+        let mut registry = ThreadRegistry::default();
+        for (workspace_id, sessions) in [("w1", vec!["s1"]), ("w2", vec!["s2", "s3"])] {
+            registry.workspaces.insert(
+                workspace_id.to_string(),
+                WorkspaceRegistry {
+                    project_path: None,
+                    visible_session_ids: sessions.into_iter().map(str::to_string).collect(),
+                },
+            );
+        }
+        registry
+            .sessions
+            .insert("s1".to_string(), session("s1", 200));
+        registry
+            .sessions
+            .insert("s2".to_string(), session("s2", 100));
+        registry
+            .sessions
+            .insert("s3".to_string(), session("s3", 300));
+
+        let recent = recent_workspaces(&workspaces, &registry, 5);
+        let names: Vec<&str> = recent.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, ["web", "api"]);
+        let titles: Vec<String> = recent_entries(&recent[..1])
+            .into_iter()
+            .map(|(title, _)| title)
+            .collect();
+        assert_eq!(titles, ["web", "New Session in web"]);
+    }
+}
@@ -0,0 +1,647 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::git::{read_head_file, workspace_diffs};
+use crate::state::AppState;
+
+#[derive(Debug, Clone, PartialEq)]
+struct DeclaredDependency {
+    version: String,
+    dev: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DependencyChange {
+    manifest: String,
+    ecosystem: String,
+    name: String,
+    /// "added", "removed" or "updated".
+    change: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_version: Option<String>,
+    dev: bool,
+    /// SPDX-ish license id from locally installed package metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+    /// Unknown or copyleft license on an added/updated package.
+    needs_review: bool,
+}
+
+type DependencyMap = BTreeMap<String, DeclaredDependency>;
+
+fn ecosystem_for_manifest(path: &str) -> Option<&'static str> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    match file_name {
+        "Cargo.toml" => Some("cargo"),
+        "package.json" => Some("npm"),
+        "go.mod" => Some("go"),
+        name if name.starts_with("requirements") && name.ends_with(".txt") => Some("pypi"),
+        _ => None,
+    }
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches(|ch| ch == '"' || ch == '\'')
+        .to_string()
+}
+
+/// Dependencies declared in a Cargo.toml, from `[dependencies]`-style
+/// sections (including target-specific and `[dependencies.name]` tables).
+fn parse_cargo_toml(content: &str) -> DependencyMap {
+    let mut deps = DependencyMap::new();
+    let mut section: Option<bool> = None;
+    let mut table_dependency: Option<String> = None;
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            let header = line.trim_matches(|ch| ch == '[' || ch == ']');
+            table_dependency = None;
+            section = None;
+            for (suffix, dev) in [
+                ("dependencies", false),
+                ("build-dependencies", false),
+                ("dev-dependencies", true),
+            ] {
+                if header == suffix || header.ends_with(&format!(".{suffix}")) {
+                    section = Some(dev);
+                } else if let Some(index) = header.find(&format!("{suffix}.")) {
+                    let preceded_ok = index == 0 || header[..index].ends_with('.');
+                    if preceded_ok {
+                        let name = &header[index + suffix.len() + 1..];
+                        deps.insert(
+                            name.to_string(),
+                            DeclaredDependency {
+                                version: String::new(),
+                                dev,
+                            },
+                        );
+                        table_dependency = Some(name.to_string());
+                    }
+                }
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = unquote(key);
+        if let Some(name) = table_dependency.as_ref() {
+            if key == "version" {
+                if let Some(dep) = deps.get_mut(name) {
+                    dep.version = unquote(value);
+                }
+            }
+            continue;
+        }
+        let Some(dev) = section else {
+            continue;
+        };
+        let value = value.trim();
+        let version = if value.starts_with('{') {
+            inline_table_value(value, "version").unwrap_or_else(|| {
+                inline_table_value(value, "git")
+                    .or_else(|| inline_table_value(value, "path"))
+                    .unwrap_or_default()
+            })
+        } else {
+            unquote(value)
+        };
+        deps.insert(key, DeclaredDependency { version, dev });
+    }
+    deps
+}
+
+fn inline_table_value(table: &str, key: &str) -> Option<String> {
+    table
+        .trim_matches(|ch| ch == '{' || ch == '}')
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| unquote(value))
+}
+
+fn parse_package_json(content: &str) -> DependencyMap {
+    let mut deps = DependencyMap::new();
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return deps;
+    };
+    for (field, dev) in [
+        ("dependencies", false),
+        ("peerDependencies", false),
+        ("optionalDependencies", false),
+        ("devDependencies", true),
+    ] {
+        let Some(map) = value.get(field).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, version) in map {
+            deps.entry(name.clone()).or_insert(DeclaredDependency {
+                version: version.as_str().unwrap_or("").to_string(),
+                dev,
+            });
+        }
+    }
+    deps
+}
+
+fn parse_go_mod(content: &str) -> DependencyMap {
+    let mut deps = DependencyMap::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        let spec = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else if let Some(rest) = line.strip_prefix("require ") {
+            rest
+        } else {
+            continue;
+        };
+        let mut parts = spec.split_whitespace();
+        if let (Some(name), Some(version)) = (parts.next(), parts.next()) {
+            deps.insert(
+                name.to_string(),
+                DeclaredDependency {
+                    version: version.to_string(),
+                    dev: false,
+                },
+            );
+        }
+    }
+    deps
+}
+
+fn parse_requirements(content: &str) -> DependencyMap {
+    let mut deps = DependencyMap::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() || line.starts_with('-') {
+            continue;
+        }
+        let split_at = line
+            .find(|ch: char| matches!(ch, '=' | '<' | '>' | '~' | '!' | ';' | '[' | ' '))
+            .unwrap_or(line.len());
+        let name = line[..split_at].trim().to_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+        let version = line[split_at..]
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        deps.insert(
+            name,
+            DeclaredDependency {
+                version,
+                dev: false,
+            },
+        );
+    }
+    deps
+}
+
+fn parse_manifest(ecosystem: &str, content: &str) -> DependencyMap {
+    match ecosystem {
+        "cargo" => parse_cargo_toml(content),
+        "npm" => parse_package_json(content),
+        "go" => parse_go_mod(content),
+        "pypi" => parse_requirements(content),
+        _ => DependencyMap::new(),
+    }
+}
+
+/// Best-effort license id from a LICENSE file's text.
+fn sniff_license_text(text: &str) -> Option<String> {
+    let head: String = text.chars().take(2000).collect::<String>().to_lowercase();
+    let license = if head.contains("gnu affero general public license") {
+        "AGPL-3.0"
+    } else if head.contains("gnu lesser general public license") {
+        "LGPL"
+    } else if head.contains("gnu general public license") {
+        "GPL"
+    } else if head.contains("mozilla public license") {
+        "MPL-2.0"
+    } else if head.contains("apache license") {
+        "Apache-2.0"
+    } else if head.contains("permission is hereby granted, free of charge") {
+        "MIT"
+    } else if head.contains("redistribution and use in source and binary forms") {
+        "BSD"
+    } else if head.contains("isc license") {
+        "ISC"
+    } else if head.contains("unlicense") {
+        "Unlicense"
+    } else {
+        return None;
+    };
+    Some(license.to_string())
+}
+
+fn license_from_dir(dir: &Path) -> Option<String> {
+    [
+        "LICENSE",
+        "LICENSE.md",
+        "LICENSE.txt",
+        "LICENSE-MIT",
+        "COPYING",
+    ]
+    .iter()
+    .find_map(|name| std::fs::read_to_string(dir.join(name)).ok())
+    .and_then(|text| sniff_license_text(&text))
+}
+
+fn npm_license(workspace: &Path, name: &str) -> Option<String> {
+    let package_dir = workspace.join("node_modules").join(name);
+    let manifest = std::fs::read_to_string(package_dir.join("package.json")).ok();
+    manifest
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|value| {
+            let license = value.get("license")?;
+            license
+                .as_str()
+                .or_else(|| license.get("type").and_then(|t| t.as_str()))
+                .map(|s| s.to_string())
+        })
+        .or_else(|| license_from_dir(&package_dir))
+}
+
+/// Versions of `name` pinned in a Cargo.lock, in file order.
+fn parse_cargo_lock(content: &str, name: &str) -> Vec<String> {
+    let mut versions = Vec::new();
+    let mut current: Option<String> = None;
+    for line in content.lines().map(str::trim) {
+        if line == "[[package]]" {
+            current = None;
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "name" => current = Some(unquote(value)),
+            "version" if current.as_deref() == Some(name) => versions.push(unquote(value)),
+            _ => {}
+        }
+    }
+    versions
+}
+
+/// Numeric release components, so `1.10.0` sorts after `1.9.0`.
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or(version)
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// The locked version a manifest requirement resolves to: the newest one
+/// sharing the requirement's leftmost non-zero component, as cargo's
+/// default caret requirements do.
+fn pick_locked_version(versions: &[String], requirement: &str) -> Option<String> {
+    let wanted = version_key(requirement.trim_start_matches(|ch: char| !ch.is_ascii_digit()));
+    let significant = wanted
+        .iter()
+        .position(|part| *part != 0)
+        .unwrap_or(wanted.len().saturating_sub(1));
+    let compatible = |version: &&String| {
+        let key = version_key(version);
+        wanted
+            .iter()
+            .take(significant + 1)
+            .enumerate()
+            .all(|(i, part)| key.get(i) == Some(part))
+    };
+    let newest = |candidates: Vec<&String>| {
+        candidates
+            .into_iter()
+            .max_by_key(|version| version_key(version))
+            .cloned()
+    };
+    newest(versions.iter().filter(compatible).collect())
+        .or_else(|| newest(versions.iter().collect()))
+}
+
+/// The version of `name` in the Cargo.lock nearest to `manifest`, walking
+/// up to the workspace root for a cargo workspace's shared lockfile.
+fn cargo_locked_version(
+    workspace: &Path,
+    manifest: &str,
+    name: &str,
+    requirement: &str,
+) -> Option<String> {
+    let manifest_path = workspace.join(manifest);
+    let lock = manifest_path
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(workspace))
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file())?;
+    let content = std::fs::read_to_string(lock).ok()?;
+    pick_locked_version(&parse_cargo_lock(&content, name), requirement)
+}
+
+/// Unpacked copy of a crate in the local cargo registry cache: the locked
+/// version when there is one, otherwise the newest cached version.
+fn cargo_package_dir(name: &str, locked: Option<&str>) -> Option<PathBuf> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))?;
+    let prefix = format!("{name}-");
+    let candidates = std::fs::read_dir(cargo_home.join("registry").join("src"))
+        .ok()?
+        .flatten()
+        .filter_map(|index| std::fs::read_dir(index.path()).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_str()?.to_string();
+            let version = file_name.strip_prefix(&prefix)?;
+            version
+                .starts_with(|ch: char| ch.is_ascii_digit())
+                .then(|| (version.to_string(), entry.path()))
+        });
+    match locked {
+        Some(locked) => candidates
+            .filter(|(version, _)| version == locked)
+            .map(|(_, path)| path)
+            .next(),
+        None => candidates
+            .max_by_key(|(version, _)| version_key(version))
+            .map(|(_, path)| path),
+    }
+}
+
+fn cargo_license(
+    workspace: &Path,
+    manifest: &str,
+    name: &str,
+    requirement: &str,
+) -> Option<String> {
+    let locked = cargo_locked_version(workspace, manifest, name, requirement);
+    let dir = cargo_package_dir(name, locked.as_deref())?;
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| {
+            content.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "license").then(|| unquote(value))
+            })
+        })
+        .or_else(|| license_from_dir(&dir))
+}
+
+fn go_license(name: &str, version: &str) -> Option<String> {
+    let gopath = std::env::var_os("GOPATH")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join("go")))?;
+    let dir = gopath
+        .join("pkg")
+        .join("mod")
+        .join(format!("{name}@{version}"));
+    license_from_dir(&dir)
+}
+
+fn resolve_license(
+    ecosystem: &str,
+    workspace: &Path,
+    manifest: &str,
+    name: &str,
+    version: &str,
+) -> Option<String> {
+    match ecosystem {
+        "npm" => npm_license(workspace, name),
+        "cargo" => cargo_license(workspace, manifest, name, version),
+        "go" => go_license(name, version),
+        _ => None,
+    }
+}
+
+fn is_copyleft(license: &str) -> bool {
+    let upper = license.to_uppercase();
+    ["GPL", "AGPL", "LGPL", "MPL", "EUPL", "SSPL", "CC-BY-SA"]
+        .iter()
+        .any(|id| upper.contains(id))
+}
+
+fn diff_manifests(
+    manifest: &str,
+    ecosystem: &str,
+    old: &DependencyMap,
+    new: &DependencyMap,
+) -> Vec<DependencyChange> {
+    let mut changes = Vec::new();
+    for (name, dep) in new {
+        let (change, old_version) = match old.get(name) {
+            None => ("added", None),
+            Some(previous) if previous.version != dep.version => {
+                ("updated", Some(previous.version.clone()))
+            }
+            Some(_) => continue,
+        };
+        changes.push(DependencyChange {
+            manifest: manifest.to_string(),
+            ecosystem: ecosystem.to_string(),
+            name: name.clone(),
+            change: change.to_string(),
+            old_version,
+            new_version: Some(dep.version.clone()),
+            dev: dep.dev,
+            license: None,
+            needs_review: false,
+        });
+    }
+    for (name, dep) in old {
+        if !new.contains_key(name) {
+            changes.push(DependencyChange {
+                manifest: manifest.to_string(),
+                ecosystem: ecosystem.to_string(),
+                name: name.clone(),
+                change: "removed".to_string(),
+                old_version: Some(dep.version.clone()),
+                new_version: None,
+                dev: dep.dev,
+                license: None,
+                needs_review: false,
+            });
+        }
+    }
+    changes
+}
+
+fn collect_dependency_changes(workspace_path: &str) -> Result<Vec<DependencyChange>, String> {
+    let workspace = Path::new(workspace_path);
+    let mut changes = Vec::new();
+    for diff in workspace_diffs(workspace_path)? {
+        let Some(ecosystem) = ecosystem_for_manifest(&diff.path) else {
+            continue;
+        };
+        let old = read_head_file(workspace_path, &diff.path)?
+            .map(|content| parse_manifest(ecosystem, &content))
+            .unwrap_or_default();
+        let new = std::fs::read_to_string(workspace.join(&diff.path))
+            .map(|content| parse_manifest(ecosystem, &content))
+            .unwrap_or_default();
+        for mut change in diff_manifests(&diff.path, ecosystem, &old, &new) {
+            if change.change != "removed" {
+                let version = change.new_version.clone().unwrap_or_default();
+                change.license =
+                    resolve_license(ecosystem, workspace, &diff.path, &change.name, &version);
+                change.needs_review = change.license.as_deref().map_or(true, is_copyleft);
+            }
+            changes.push(change);
+        }
+    }
+    Ok(changes)
+}
+
+/// Dependencies the working diff adds, removes or bumps across known
+/// manifests, with licenses resolved from local package caches.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_dependency_changes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<DependencyChange>, String> {
+    let path = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .path
+            .clone()
+    };
+    tokio::task::spawn_blocking(move || collect_dependency_changes(&path))
+        .await
+        .map_err(|_| "dependency scan failed".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        diff_manifests, parse_cargo_lock, parse_cargo_toml, parse_go_mod, parse_requirements,
+        pick_locked_version,
+    };
+
+    #[test]
+    fn parses_cargo_dependency_sections() {
+        let deps = parse_cargo_toml(
+            r#"
+[package]
+name = "demo"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+dirs = "5"
+local = { path = "../local" }
+
+[dependencies.tokio]
+version = "1.38"
+features = ["rt"]
+
+[target."cfg(unix)".dependencies]
+libc = "0.2"
+
+[dev-dependencies]
+tempfile = "3" # test only
+"#,
+        );
+        assert_eq!(deps["serde"].version, "1");
+        assert_eq!(deps["dirs"].version, "5");
+        assert_eq!(deps["local"].version, "../local");
+        assert_eq!(deps["tokio"].version, "1.38");
+        assert_eq!(deps["libc"].version, "0.2");
+        assert!(deps["tempfile"].dev);
+        assert!(!deps.contains_key("name"));
+    }
+
+    #[test]
+    fn parses_go_mod_and_requirements() {
+        let go = parse_go_mod(
+            "module x\n\nrequire github.com/a/b v1.2.0\nrequire (\n\tgolang.org/x/sys v0.20.0 // indirect\n)\n",
+        );
+        assert_eq!(go["github.com/a/b"].version, "v1.2.0");
+        assert_eq!(go["golang.org/x/sys"].version, "v0.20.0");
+
+        let py =
+            parse_requirements("Requests>=2.31\n# comment\n-r base.txt\nuvicorn[standard]==0.30\n");
+        assert_eq!(py["requests"].version, ">=2.31");
+        assert!(py.contains_key("uvicorn"));
+        assert_eq!(py.len(), 2);
+    }
+
+    #[test]
+    fn diffs_old_and_new_manifests() {
+        let old = parse_cargo_toml("[dependencies]\nserde = \"1\"\nrand = \"0.8\"\n");
+        let new =
+            parse_cargo_toml("[dependencies]\nserde = \"1\"\nrand = \"0.9\"\nregex = \"1\"\n");
+        let changes = diff_manifests("Cargo.toml", "cargo", &old, &new);
+        let summary: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.change.as_str()))
+            .collect();
+        assert_eq!(summary, vec![("rand", "updated"), ("regex", "added")]);
+    }
+
+    #[test]
+    fn resolves_the_locked_cargo_version() {
+        let lock = r#"
+version = 3
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.9.10"
+
+[[package]]
+name = "rand"
+version = "0.9.2"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+"#;
+        let versions = parse_cargo_lock(lock, "rand");
+        assert_eq!(versions, vec!["0.8.5", "0.9.10", "0.9.2"]);
+        assert_eq!(
+            pick_locked_version(&versions, "0.8").as_deref(),
+            Some("0.8.5")
+        );
+        assert_eq!(
+            pick_locked_version(&versions, "^0.9.1").as_deref(),
+            Some("0.9.10")
+        );
+        assert_eq!(
+            pick_locked_version(&versions, "2").as_deref(),
+            Some("0.9.10")
+        );
+        assert_eq!(pick_locked_version(&[], "1"), None);
+    }
+}
@@ -0,0 +1,619 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate, TimeZone, Timelike};
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+use tokio::time::sleep;
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::git::workspace_diffs;
+use crate::i18n::{format_date, format_millis, tr, tr_count};
+use crate::power::schedulers_paused;
+use crate::secrets::{delete_secret, read_secret, write_secret};
+use crate::state::AppState;
+use crate::types::{DigestDelivery, DigestSettings, GitFileDiff, SessionEntry, SessionStatus};
+use crate::utils::{curl_config_value, run_curl, TempFile};
+
+/// SMTP password or Mailgun API key, depending on the delivery.
+const DIGEST_SECRET: &str = "digest-credential";
+const SCHEDULE_POLL: Duration = Duration::from_secs(10 * 60);
+/// Changed files listed per workspace, largest first.
+const NOTABLE_FILES: usize = 5;
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+struct DigestSession {
+    session_id: String,
+    title: String,
+    missing: bool,
+    last_activity: u64,
+    /// Lifetime cost of the session, not just the day's share.
+    cost_usd: f64,
+    requests: u64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+struct DigestFileChange {
+    path: String,
+    added: usize,
+    removed: usize,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+struct DigestWorkspace {
+    workspace_id: String,
+    name: String,
+    sessions: Vec<DigestSession>,
+    cost_usd: f64,
+    /// Uncommitted changes when the digest was built.
+    changes: Vec<DigestFileChange>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Digest {
+    date: String,
+    workspaces: Vec<DigestWorkspace>,
+    session_count: usize,
+    cost_usd: f64,
+    markdown: String,
+    html: String,
+}
+
+pub(crate) fn day_bounds(date: NaiveDate) -> (u64, u64) {
+    let start = |day: NaiveDate| {
+        day.and_hms_opt(0, 0, 0)
+            .and_then(|time| Local.from_local_datetime(&time).earliest())
+            .map(|time| time.timestamp_millis().max(0) as u64)
+            .unwrap_or(0)
+    };
+    (start(date), start(date.succ_opt().unwrap_or(date)))
+}
+
+fn parse_date(date: Option<&str>) -> Result<NaiveDate, String> {
+    match date {
+        Some(date) => {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Invalid date: {date}"))
+        }
+        None => Ok(Local::now().date_naive()),
+    }
+}
+
+pub(crate) fn session_title(entry: &SessionEntry) -> String {
+    entry
+        .summary
+        .as_ref()
+        .map(|summary| summary.text.clone())
+        .or_else(|| entry.preview.clone())
+        .map(|text| text.lines().next().unwrap_or("").trim().to_string())
+        .filter(|text| !text.is_empty())
+        .unwrap_or_else(|| "Untitled session".to_string())
+}
+
+fn notable_changes(diffs: &[GitFileDiff]) -> Vec<DigestFileChange> {
+    let mut changes: Vec<DigestFileChange> = diffs
+        .iter()
+        .map(|diff| {
+            let mut change = DigestFileChange {
+                path: diff.path.clone(),
+                added: 0,
+                removed: 0,
+            };
+            for line in diff.diff.lines() {
+                if line.starts_with('+') && !line.starts_with("+++") {
+                    change.added += 1;
+                } else if line.starts_with('-') && !line.starts_with("---") {
+                    change.removed += 1;
+                }
+            }
+            change
+        })
+        .collect();
+    changes.sort_by(|a, b| {
+        (b.added + b.removed)
+            .cmp(&(a.added + a.removed))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    changes.truncate(NOTABLE_FILES);
+    changes
+}
+
+/// A digest date (`YYYY-MM-DD`) in the configured locale.
+fn display_date(date: &str) -> String {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(format_date)
+        .unwrap_or_else(|_| date.to_string())
+}
+
+fn summary_line(workspaces: &[DigestWorkspace]) -> String {
+    let sessions: usize = workspaces.iter().map(|w| w.sessions.len()).sum();
+    tr(
+        "digest.summary",
+        &[
+            ("sessions", &tr_count("count.session", sessions)),
+            ("workspaces", &tr_count("count.workspace", workspaces.len())),
+        ],
+    )
+}
+
+fn session_line(session: &DigestSession) -> String {
+    format!(
+        "{} · ${:.2} · {}",
+        tr_count("count.request", session.requests as usize),
+        session.cost_usd,
+        format_millis(session.last_activity as i64)
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_markdown(date: &str, workspaces: &[DigestWorkspace], cost: f64) -> String {
+    let title = tr("digest.title", &[("date", &display_date(date))]);
+    let mut out = format!("# {title}\n\n");
+    if workspaces.is_empty() {
+        out.push_str(&format!("{}\n", tr("digest.empty", &[])));
+        return out;
+    }
+    out.push_str(&format!("{} · ${cost:.2}\n", summary_line(workspaces)));
+    for workspace in workspaces {
+        out.push_str(&format!(
+            "\n## {}\n\n{} · ${:.2}\n\n",
+            workspace.name,
+            tr_count("count.session", workspace.sessions.len()),
+            workspace.cost_usd
+        ));
+        for session in &workspace.sessions {
+            let missing = if session.missing {
+                format!(" {}", tr("digest.transcriptMissing", &[]))
+            } else {
+                String::new()
+            };
+            out.push_str(&format!(
+                "- {}{missing} · {}\n",
+                session.title,
+                session_line(session)
+            ));
+        }
+        if !workspace.changes.is_empty() {
+            out.push_str(&format!("\n{}\n\n", tr("digest.uncommitted", &[])));
+            for change in &workspace.changes {
+                out.push_str(&format!(
+                    "- `{}` +{} −{}\n",
+                    change.path, change.added, change.removed
+                ));
+            }
+        }
+    }
+    out
+}
+
+fn render_html(date: &str, workspaces: &[DigestWorkspace], cost: f64) -> String {
+    let title = tr("digest.title", &[("date", &display_date(date))]);
+    let mut out = format!(
+        "<!doctype html><html><body style=\"font-family:-apple-system,sans-serif\"><h1>{}</h1>",
+        escape_html(&title)
+    );
+    if workspaces.is_empty() {
+        out.push_str(&format!(
+            "<p>{}</p></body></html>",
+            escape_html(&tr("digest.empty", &[]))
+        ));
+        return out;
+    }
+    out.push_str(&format!(
+        "<p>{} · ${cost:.2}</p>",
+        escape_html(&summary_line(workspaces))
+    ));
+    for workspace in workspaces {
+        out.push_str(&format!(
+            "<h2>{}</h2><p>{} · ${:.2}</p><ul>",
+            escape_html(&workspace.name),
+            escape_html(&tr_count("count.session", workspace.sessions.len())),
+            workspace.cost_usd
+        ));
+        for session in &workspace.sessions {
+            let missing = if session.missing {
+                format!(
+                    " <em>{}</em>",
+                    escape_html(&tr("digest.transcriptMissing", &[]))
+                )
+            } else {
+                String::new()
+            };
+            out.push_str(&format!(
+                "<li>{}{missing} · {}</li>",
+                escape_html(&session.title),
+                escape_html(&session_line(session))
+            ));
+        }
+        out.push_str("</ul>");
+        if !workspace.changes.is_empty() {
+            out.push_str(&format!(
+                "<p>{}</p><ul>",
+                escape_html(&tr("digest.uncommitted", &[]))
+            ));
+            for change in &workspace.changes {
+                out.push_str(&format!(
+                    "<li><code>{}</code> +{} −{}</li>",
+                    escape_html(&change.path),
+                    change.added,
+                    change.removed
+                ));
+            }
+            out.push_str("</ul>");
+        }
+    }
+    out.push_str("</body></html>");
+    out
+}
+
+fn assemble(date: &str, mut workspaces: Vec<DigestWorkspace>) -> Digest {
+    workspaces.retain(|workspace| !workspace.sessions.is_empty());
+    workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+    let cost_usd = workspaces.iter().map(|w| w.cost_usd).sum();
+    Digest {
+        date: date.to_string(),
+        session_count: workspaces.iter().map(|w| w.sessions.len()).sum(),
+        markdown: render_markdown(date, &workspaces, cost_usd),
+        html: render_html(date, &workspaces, cost_usd),
+        workspaces,
+        cost_usd,
+    }
+}
+
+/// Summarize sessions active on `date` (local time), per workspace.
+async fn build_digest(state: &AppState, date: NaiveDate) -> Result<Digest, String> {
+    let (start, end) = day_bounds(date);
+    let names: HashMap<String, (String, String)> = state
+        .workspaces
+        .lock()
+        .await
+        .values()
+        .map(|entry| (entry.id.clone(), (entry.name.clone(), entry.path.clone())))
+        .collect();
+    let mut workspaces = Vec::new();
+    {
+        let registry = state.registry.lock().await;
+        for (workspace_id, workspace) in &registry.workspaces {
+            let Some((name, _)) = names.get(workspace_id) else {
+                continue;
+            };
+            let mut sessions: Vec<DigestSession> = workspace
+                .visible_session_ids
+                .iter()
+                .filter_map(|id| registry.sessions.get(id))
+                .filter(|entry| entry.last_activity >= start && entry.last_activity < end)
+                .map(|entry| {
+                    let usage = entry.usage.clone().unwrap_or_default();
+                    DigestSession {
+                        session_id: entry.session_id.clone(),
+                        title: session_title(entry),
+                        missing: entry.status == SessionStatus::Missing,
+                        last_activity: entry.last_activity,
+                        cost_usd: usage.cost_usd,
+                        requests: usage.requests,
+                    }
+                })
+                .collect();
+            sessions.sort_by_key(|session| session.last_activity);
+            workspaces.push(DigestWorkspace {
+                workspace_id: workspace_id.clone(),
+                name: name.clone(),
+                cost_usd: sessions.iter().map(|s| s.cost_usd).sum(),
+                sessions,
+                changes: Vec::new(),
+            });
+        }
+    }
+    for workspace in &mut workspaces {
+        if workspace.sessions.is_empty() {
+            continue;
+        }
+        let path = names[&workspace.workspace_id].1.clone();
+        let diffs = tokio::task::spawn_blocking(move || workspace_diffs(&path).unwrap_or_default())
+            .await
+            .unwrap_or_default();
+        workspace.changes = notable_changes(&diffs);
+    }
+    Ok(assemble(&date.format("%Y-%m-%d").to_string(), workspaces))
+}
+
+fn digest_folder(state: &AppState, settings: &DigestSettings) -> PathBuf {
+    settings
+        .folder
+        .as_deref()
+        .filter(|folder| !folder.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            state
+                .storage_path
+                .parent()
+                .unwrap_or(Path::new("."))
+                .join("digests")
+        })
+}
+
+fn mime_message(settings: &DigestSettings, subject: &str, digest: &Digest) -> String {
+    let boundary = format!("digest-{}", uuid::Uuid::new_v4().simple());
+    let lines = [
+        format!("From: {}", settings.from.as_deref().unwrap_or("")),
+        format!("To: {}", settings.to.join(", ")),
+        format!("Subject: {subject}"),
+        format!("Date: {}", Local::now().to_rfc2822()),
+        "MIME-Version: 1.0".to_string(),
+        format!("Content-Type: multipart/alternative; boundary=\"{boundary}\""),
+        String::new(),
+        format!("--{boundary}"),
+        "Content-Type: text/plain; charset=utf-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+        String::new(),
+        digest.markdown.clone(),
+        format!("--{boundary}"),
+        "Content-Type: text/html; charset=utf-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+        String::new(),
+        digest.html.clone(),
+        format!("--{boundary}--"),
+        String::new(),
+    ];
+    lines.join("\n").replace("\r\n", "\n").replace('\n', "\r\n")
+}
+
+/// Deliver a digest as configured. Returns where it went.
+async fn deliver(state: &AppState, digest: &Digest) -> Result<String, String> {
+    let settings = state.app_settings.lock().await.digest.clone();
+    let subject = tr("digest.title", &[("date", &display_date(&digest.date))]);
+    let needs_recipients = settings.delivery != DigestDelivery::Folder;
+    if needs_recipients && (settings.to.is_empty() || settings.from.is_none()) {
+        return Err("Digest email needs a sender and at least one recipient".to_string());
+    }
+    match settings.delivery {
+        DigestDelivery::Folder => {
+            let folder = digest_folder(state, &settings);
+            std::fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+            let base = folder.join(format!("digest-{}", digest.date));
+            std::fs::write(base.with_extension("md"), &digest.markdown)
+                .map_err(|e| e.to_string())?;
+            std::fs::write(base.with_extension("html"), &digest.html).map_err(|e| e.to_string())?;
+            Ok(folder.to_string_lossy().to_string())
+        }
+        DigestDelivery::Smtp => {
+            let url = settings
+                .smtp_url
+                .clone()
+                .filter(|url| !url.trim().is_empty())
+                .ok_or("SMTP server is not configured")?;
+            let mut config = format!("url = {}\n", curl_config_value(&url));
+            if let Some(user) = settings.smtp_user.as_deref() {
+                let password = read_secret(DIGEST_SECRET).await?.unwrap_or_default();
+                config.push_str(&format!(
+                    "user = {}\n",
+                    curl_config_value(&format!("{user}:{password}"))
+                ));
+            }
+            let message = TempFile::with_contents(
+                "digest",
+                mime_message(&settings, &subject, digest).as_bytes(),
+            )?;
+            let mut args = vec![
+                "--ssl-reqd".to_string(),
+                "--mail-from".to_string(),
+                settings.from.clone().unwrap_or_default(),
+            ];
+            for to in &settings.to {
+                args.extend(["--mail-rcpt".to_string(), to.clone()]);
+            }
+            args.extend([
+                "--upload-file".to_string(),
+                message.path().to_string_lossy().to_string(),
+            ]);
+            run_curl(&config, &args).await?;
+            Ok(format!("SMTP ({})", settings.to.join(", ")))
+        }
+        DigestDelivery::Mailgun => {
+            let domain = settings
+                .mailgun_domain
+                .clone()
+                .filter(|domain| !domain.trim().is_empty())
+                .ok_or("Mailgun domain is not configured")?;
+            let key = read_secret(DIGEST_SECRET)
+                .await?
+                .ok_or("Mailgun API key is not set")?;
+            let config = format!(
+                "url = {}\nuser = {}\n",
+                curl_config_value(&format!("https://api.mailgun.net/v3/{domain}/messages")),
+                curl_config_value(&format!("api:{key}"))
+            );
+            let mut args = Vec::new();
+            let mut field = |name: &str, value: &str| {
+                args.extend(["--form-string".to_string(), format!("{name}={value}")]);
+            };
+            field("from", settings.from.as_deref().unwrap_or(""));
+            for to in &settings.to {
+                field("to", to);
+            }
+            field("subject", &subject);
+            // The bodies go in as files (`name=<path`): a busy day of sessions
+            // can be too much for argv.
+            let text = TempFile::with_contents("digest-text", digest.markdown.as_bytes())?;
+            let html = TempFile::with_contents("digest-html", digest.html.as_bytes())?;
+            for (name, file) in [("text", &text), ("html", &html)] {
+                args.extend([
+                    "--form".to_string(),
+                    format!("{name}=<{}", file.path().display()),
+                ]);
+            }
+            run_curl(&config, &args).await?;
+            Ok(format!("Mailgun ({})", settings.to.join(", ")))
+        }
+    }
+}
+
+fn last_sent_path(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("digest-last-sent")
+}
+
+/// Send each day's digest once, after the configured hour.
+pub(crate) fn spawn_digest_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(SCHEDULE_POLL).await;
+            let state = app.state::<AppState>();
+            if schedulers_paused(&state).await {
+                continue;
+            }
+            let settings = state.app_settings.lock().await.digest.clone();
+            let now = Local::now();
+            if !settings.enabled || now.hour() < u32::from(settings.hour) {
+                continue;
+            }
+            let today = now.format("%Y-%m-%d").to_string();
+            let path = last_sent_path(&state);
+            let last_sent = std::fs::read_to_string(&path).unwrap_or_default();
+            if last_sent.trim() == today {
+                continue;
+            }
+            let result = match build_digest(&state, now.date_naive()).await {
+                Ok(digest) => deliver(&state, &digest).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(_) => {
+                    let _ = std::fs::write(&path, &today);
+                }
+                Err(err) => log_line!("Daily digest failed: {err}"),
+            }
+        }
+    });
+}
+
+/// Build the digest for `date` (YYYY-MM-DD, default today) without sending.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn generate_digest(
+    date: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Digest, String> {
+    build_digest(&state, parse_date(date.as_deref())?).await
+}
+
+/// Build and deliver the digest for `date` now. Returns where it went.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn send_digest(
+    date: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<String, String> {
+    let digest = build_digest(&state, parse_date(date.as_deref())?).await?;
+    deliver(&state, &digest).await
+}
+
+/// Store (or clear, with `None`) the SMTP password or Mailgun API key.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_digest_credential(
+    secret: Option<String>,
+    _timer: CommandTimer,
+) -> Result<bool, String> {
+    match secret
+        .as_deref()
+        .map(str::trim)
+        .filter(|secret| !secret.is_empty())
+    {
+        Some(secret) => {
+            write_secret(DIGEST_SECRET, secret).await?;
+            Ok(true)
+        }
+        None => {
+            delete_secret(DIGEST_SECRET).await?;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assemble, notable_changes, DigestSession, DigestWorkspace};
+    use crate::types::GitFileDiff;
+
+    #[test]
+    fn renders_workspaces_with_sessions_and_changes() {
+        let changes = notable_changes(&[
+            GitFileDiff {
+                path: "small.rs".to_string(),
+                diff: "--- a/small.rs\n+++ b/small.rs\n+one\n".to_string(),
+                owners: Vec::new(),
+                highlighted: None,
+                intraline: None,
+                truncated: None,
+                lfs: None,
+            },
+            GitFileDiff {
+                path: "big.rs".to_string(),
+                diff: "+a\n+b\n-c\n".to_string(),
+                owners: Vec::new(),
+                highlighted: None,
+                intraline: None,
+                truncated: None,
+                lfs: None,
+            },
+        ]);
+        assert_eq!(changes[0].path, "big.rs");
+        assert_eq!((changes[0].added, changes[0].removed), (2, 1));
+        assert_eq!((changes[1].added, changes[1].removed), (1, 0));
+
+        let session = DigestSession {
+            session_id: "s1".to_string(),
+            title: "Fix <login> bug".to_string(),
+            missing: false,
+            last_activity: 1,
+            cost_usd: 0.5,
+            requests: 3,
+        };
+        let digest = assemble(
+            "2026-01-02",
+            vec![
+                DigestWorkspace {
+                    workspace_id: "w1".to_string(),
+                    name: "api".to_string(),
+                    sessions: vec![session],
+                    cost_usd: 0.5,
+                    changes,
+                },
+                DigestWorkspace {
+                    workspace_id: "w2".to_string(),
+                    name: "idle".to_string(),
+                    sessions: Vec::new(),
+                    cost_usd: 0.0,
+                    changes: Vec::new(),
+                },
+            ],
+        );
+        assert_eq!(digest.workspaces.len(), 1);
+        assert_eq!(digest.session_count, 1);
+        assert!(digest.markdown.contains("## api"));
+        assert!(digest
+            .markdown
+            .contains("- Fix <login> bug · 3 requests · $0.50"));
+        assert!(digest.html.contains("Fix &lt;login&gt; bug"));
+
+        let empty = assemble("2026-01-03", Vec::new());
+        assert!(empty.markdown.contains("No agent sessions."));
+    }
+}
@@ -0,0 +1,237 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::attachments::resolve_in_workspace;
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+use crate::types::EditorKind;
+
+/// Editors tried, in order, when none is configured.
+const DETECT_ORDER: [EditorKind; 4] = [
+    EditorKind::Cursor,
+    EditorKind::Vscode,
+    EditorKind::Zed,
+    EditorKind::Jetbrains,
+];
+
+fn cli_names(editor: EditorKind) -> &'static [&'static str] {
+    match editor {
+        EditorKind::Vscode => &["code"],
+        EditorKind::Cursor => &["cursor"],
+        EditorKind::Zed => &["zed", "zeditor"],
+        EditorKind::Jetbrains => &[
+            "idea",
+            "rustrover",
+            "webstorm",
+            "pycharm",
+            "goland",
+            "clion",
+            "phpstorm",
+            "rubymine",
+        ],
+        EditorKind::System => &[],
+    }
+}
+
+/// Directories searched on top of `PATH`, which is minimal for GUI apps.
+fn search_dirs() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    paths.push(PathBuf::from("/opt/homebrew/bin"));
+    paths.push(PathBuf::from("/usr/local/bin"));
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.join(".local/bin"));
+        // JetBrains Toolbox shell scripts.
+        paths.push(home.join("Library/Application Support/JetBrains/Toolbox/scripts"));
+        paths.push(home.join(".local/share/JetBrains/Toolbox/scripts"));
+    }
+    paths
+}
+
+fn find_cli(editor: EditorKind) -> Option<PathBuf> {
+    let suffixes: &[&str] = if cfg!(windows) {
+        &[".cmd", ".exe", ""]
+    } else {
+        &[""]
+    };
+    let dirs = search_dirs();
+    cli_names(editor).iter().find_map(|name| {
+        dirs.iter().find_map(|dir| {
+            suffixes
+                .iter()
+                .map(|suffix| dir.join(format!("{name}{suffix}")))
+                .find(|candidate| candidate.is_file())
+        })
+    })
+}
+
+/// Arguments that open `file` at `line` for the editor's CLI.
+fn cli_args(editor: EditorKind, file: &str, line: Option<u32>) -> Vec<String> {
+    match (editor, line) {
+        (EditorKind::Vscode | EditorKind::Cursor, Some(line)) => {
+            vec!["--goto".to_string(), format!("{file}:{line}")]
+        }
+        (EditorKind::Zed, Some(line)) => vec![format!("{file}:{line}")],
+        (EditorKind::Jetbrains, Some(line)) => {
+            vec!["--line".to_string(), line.to_string(), file.to_string()]
+        }
+        _ => vec![file.to_string()],
+    }
+}
+
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// URL-scheme fallback for editors installed without their CLI.
+fn editor_url(
+    editor: EditorKind,
+    root: &Path,
+    file: &str,
+    relative: &str,
+    line: Option<u32>,
+) -> Option<String> {
+    let line = line.unwrap_or(1);
+    let absolute = encode_path(file);
+    let absolute = absolute.trim_start_matches('/');
+    match editor {
+        EditorKind::Vscode => Some(format!("vscode://file/{absolute}:{line}")),
+        EditorKind::Cursor => Some(format!("cursor://file/{absolute}:{line}")),
+        EditorKind::Zed => Some(format!("zed://file/{absolute}:{line}")),
+        EditorKind::Jetbrains => {
+            let project = root.file_name()?.to_string_lossy().to_string();
+            Some(format!(
+                "jetbrains://idea/navigate/reference?project={}&path={}:{}",
+                encode_path(&project),
+                encode_path(relative),
+                line.saturating_sub(1)
+            ))
+        }
+        EditorKind::System => None,
+    }
+}
+
+/// Editors whose CLI is installed, in detection order.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_editors(_timer: CommandTimer) -> Result<Vec<EditorKind>, String> {
+    Ok(DETECT_ORDER
+        .into_iter()
+        .filter(|editor| find_cli(*editor).is_some())
+        .collect())
+}
+
+/// Open a workspace file in the configured editor, at `line` when the
+/// editor supports it. Without a configured editor the first installed CLI
+/// is used, then the OS default app.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn open_in_editor(
+    workspace_id: String,
+    path: String,
+    line: Option<u32>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let root = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(|entry| PathBuf::from(&entry.path))
+        .ok_or("workspace not found")?;
+    let (resolved, relative) = resolve_in_workspace(&root, &path)?;
+    let file = resolved.to_string_lossy().to_string();
+    let configured = state.app_settings.lock().await.editor;
+
+    let (editor, cli) = match configured {
+        Some(editor) => (editor, find_cli(editor)),
+        None => DETECT_ORDER
+            .into_iter()
+            .find_map(|editor| find_cli(editor).map(|cli| (editor, Some(cli))))
+            .unwrap_or((EditorKind::System, None)),
+    };
+    if let Some(cli) = cli {
+        return std::process::Command::new(&cli)
+            .args(cli_args(editor, &file, line))
+            .current_dir(&root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("Failed to launch {}: {e}", cli.display()));
+    }
+    let opener = app.opener();
+    match editor_url(editor, &root, &file, &relative, line) {
+        Some(url) => opener
+            .open_url(url, None::<&str>)
+            .map_err(|e| e.to_string()),
+        None => opener
+            .open_path(file, None::<&str>)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cli_args, editor_url};
+    use crate::types::EditorKind;
+    use std::path::Path;
+
+    #[test]
+    fn builds_line_aware_cli_args_and_urls() {
+        assert_eq!(
+            cli_args(EditorKind::Vscode, "/repo/src/a.rs", Some(12)),
+            ["--goto", "/repo/src/a.rs:12"]
+        );
+        assert_eq!(
+            cli_args(EditorKind::Jetbrains, "/repo/src/a.rs", Some(12)),
+            ["--line", "12", "/repo/src/a.rs"]
+        );
+        assert_eq!(
+            cli_args(EditorKind::Zed, "/repo/a.rs", None),
+            ["/repo/a.rs"]
+        );
+
+        let root = Path::new("/repo");
+        assert_eq!(
+            editor_url(
+                EditorKind::Cursor,
+                root,
+                "/repo/my file.rs",
+                "my file.rs",
+                Some(3)
+            ),
+            Some("cursor://file/repo/my%20file.rs:3".to_string())
+        );
+        assert_eq!(
+            editor_url(
+                EditorKind::Jetbrains,
+                root,
+                "/repo/src/a.rs",
+                "src/a.rs",
+                Some(3)
+            ),
+            Some("jetbrains://idea/navigate/reference?project=repo&path=src/a.rs:2".to_string())
+        );
+        assert_eq!(
+            editor_url(EditorKind::System, root, "/repo/a.rs", "a.rs", None),
+            None
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::claude::PendingRequestInfo;
+use crate::guardrails::GuardrailTriggeredPayload;
+use crate::heartbeat::BridgeHealth;
+use crate::message_queue::QueuedMessage;
+use crate::types::{ClaudeAuthMode, InterruptedTurn, SessionUsage};
+
+// Payload schemas for `claude-event`. Bridge payloads mirror
+// src/claude-bridge/types.ts; the frontend types are generated from these
+// into src/services/bindings.ts (see `crate::bindings`).
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct McpServerStatus {
+    pub(crate) name: String,
+    pub(crate) status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionStartedPayload {
+    pub(crate) model: String,
+    pub(crate) tools: Vec<String>,
+    pub(crate) cwd: String,
+    pub(crate) claude_code_version: String,
+    pub(crate) permission_mode: String,
+    pub(crate) mcp_servers: Vec<McpServerStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) transcript_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) project_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum SessionCloseReason {
+    User,
+    Error,
+    Completed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionClosedPayload {
+    pub(crate) reason: SessionCloseReason,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageDeltaPayload {
+    /// Raw stream event from the Agent SDK.
+    pub(crate) event: Value,
+    #[serde(default)]
+    pub(crate) parent_tool_use_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) uuid: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageCompletePayload {
+    pub(crate) uuid: String,
+    /// Complete message from the Agent SDK.
+    pub(crate) message: Value,
+    #[serde(default)]
+    pub(crate) parent_tool_use_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ToolStartedPayload {
+    pub(crate) tool_name: String,
+    pub(crate) tool_use_id: String,
+    pub(crate) input: Value,
+    #[serde(default)]
+    pub(crate) parent_tool_use_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ToolProgressPayload {
+    pub(crate) tool_name: String,
+    pub(crate) tool_use_id: String,
+    pub(crate) elapsed_seconds: f64,
+    #[serde(default)]
+    pub(crate) parent_tool_use_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ToolCompletedPayload {
+    pub(crate) tool_name: String,
+    pub(crate) tool_use_id: String,
+    pub(crate) output: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PermissionRequestPayload {
+    pub(crate) tool_name: String,
+    pub(crate) tool_use_id: String,
+    pub(crate) input: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) suggestions: Option<Vec<Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) blocked_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) decision_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) agent_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResultUsage {
+    pub(crate) input_tokens: u64,
+    pub(crate) output_tokens: u64,
+    pub(crate) cache_read_input_tokens: u64,
+    pub(crate) cache_creation_input_tokens: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResultPayload {
+    pub(crate) success: bool,
+    pub(crate) subtype: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) result: Option<String>,
+    pub(crate) duration_ms: u64,
+    pub(crate) num_turns: u64,
+    pub(crate) total_cost_usd: f64,
+    pub(crate) usage: ResultUsage,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) errors: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ErrorPayload {
+    pub(crate) code: String,
+    pub(crate) message: String,
+    pub(crate) recoverable: bool,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BridgeStderrPayload {
+    pub(crate) message: String,
+}
+
+/// Sent when the bridge exits on its own, before it is restarted.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BridgeDiedPayload {
+    /// `None` if the process was killed by a signal or had not exited yet.
+    pub(crate) exit_code: Option<i32>,
+    /// Requests that were waiting on it; each failed with `BRIDGE_DIED`.
+    pub(crate) failed_requests: Vec<PendingRequestInfo>,
+    /// Sessions open on it, to be resumed once it is back.
+    pub(crate) sessions: Vec<String>,
+}
+
+/// Sent once a bridge that exited on its own has been replaced.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BridgeReconnectedPayload {
+    /// Restart attempts since a bridge last stayed up, starting at 1.
+    pub(crate) attempts: u32,
+    /// Sessions open on the old bridge and resumed on the new one.
+    pub(crate) resumed: Vec<String>,
+    /// Sessions open on the old bridge that could not be resumed.
+    pub(crate) failed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageUpdatedPayload {
+    pub(crate) billing: ClaudeAuthMode,
+    pub(crate) request_cost_usd: f64,
+    pub(crate) request: SessionUsage,
+    pub(crate) session: Option<SessionUsage>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScanProgressPayload {
+    pub(crate) done: usize,
+    pub(crate) total: usize,
+    pub(crate) cached: usize,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexUpdatedPayload {
+    pub(crate) session_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SlackReplyPayload {
+    /// Slack user id of the author.
+    pub(crate) user: Option<String>,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UnreadChangedPayload {
+    /// Replies since the session's last read message.
+    pub(crate) unread_count: usize,
+}
+
+/// Payload of a `ClaudeEvent`. Serialized untagged so the wire shape stays
+/// `{ type, sessionId, workspaceId, timestamp, payload }`; the variant must
+/// agree with the event's `type`.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(untagged)]
+pub(crate) enum ClaudeEventPayload {
+    SessionStarted(SessionStartedPayload),
+    SessionClosed(SessionClosedPayload),
+    MessageDelta(MessageDeltaPayload),
+    MessageComplete(MessageCompletePayload),
+    ToolStarted(ToolStartedPayload),
+    ToolProgress(ToolProgressPayload),
+    ToolCompleted(ToolCompletedPayload),
+    PermissionRequest(PermissionRequestPayload),
+    Result(ResultPayload),
+    Error(ErrorPayload),
+    BridgeStderr(BridgeStderrPayload),
+    UsageUpdated(UsageUpdatedPayload),
+    ScanProgress(ScanProgressPayload),
+    IndexUpdated(IndexUpdatedPayload),
+    QueueDispatched(QueuedMessage),
+    SlackReply(SlackReplyPayload),
+    TurnInterrupted(InterruptedTurn),
+    UnreadChanged(UnreadChangedPayload),
+    GuardrailTriggered(GuardrailTriggeredPayload),
+    BridgeHealth(BridgeHealth),
+    BridgeDied(BridgeDiedPayload),
+    BridgeReconnected(BridgeReconnectedPayload),
+    /// Event types without a schema (e.g. `bridge/connected`), or bridge
+    /// payloads that failed validation, forwarded unchanged.
+    Raw(Value),
+}
+
+fn parse<T: DeserializeOwned>(event_type: &str, payload: &Value) -> Result<T, String> {
+    T::deserialize(payload).map_err(|e| format!("Invalid {event_type} payload: {e}"))
+}
+
+impl ClaudeEventPayload {
+    /// Validate a payload read from bridge stdout against its event type.
+    pub(crate) fn from_bridge(event_type: &str, payload: &Value) -> Result<Self, String> {
+        Ok(match event_type {
+            "session/started" => Self::SessionStarted(parse(event_type, payload)?),
+            "session/closed" => Self::SessionClosed(parse(event_type, payload)?),
+            "message/delta" => Self::MessageDelta(parse(event_type, payload)?),
+            "message/complete" => Self::MessageComplete(parse(event_type, payload)?),
+            "tool/started" => Self::ToolStarted(parse(event_type, payload)?),
+            "tool/progress" => Self::ToolProgress(parse(event_type, payload)?),
+            "tool/completed" => Self::ToolCompleted(parse(event_type, payload)?),
+            "permission/request" => Self::PermissionRequest(parse(event_type, payload)?),
+            "result" => Self::Result(parse(event_type, payload)?),
+            "error" => Self::Error(parse(event_type, payload)?),
+            _ => Self::Raw(payload.clone()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ClaudeEventPayload;
+
+    #[test]
+    fn validates_bridge_payloads_by_type() {
+        let delta = json!({ "event": { "type": "content_block_delta" }, "parentToolUseId": null });
+        let parsed = ClaudeEventPayload::from_bridge("message/delta", &delta).unwrap();
+        assert!(matches!(parsed, ClaudeEventPayload::MessageDelta(_)));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), delta);
+
+        let err = ClaudeEventPayload::from_bridge("tool/progress", &json!({ "toolName": "Bash" }))
+            .unwrap_err();
+        assert!(err.starts_with("Invalid tool/progress payload"));
+
+        let connected = json!({ "version": "1" });
+        let parsed = ClaudeEventPayload::from_bridge("bridge/connected", &connected).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), connected);
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+use crate::types::WorkspaceInfo;
+use crate::utils::PathKey;
+use crate::workspaces::add_workspace;
+
+/// Outcome of dropping a folder on the window, emitted as
+/// "workspace-dropped".
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceDrop {
+    /// The folder as dropped.
+    path: String,
+    /// Repository root when the folder is inside a git checkout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_root: Option<String>,
+    /// Newly added workspace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace: Option<WorkspaceInfo>,
+    /// Id of the workspace that already covers the folder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    existing_id: Option<String>,
+    start_session: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FilesDrop {
+    paths: Vec<String>,
+}
+
+/// Work tree root of the repository containing `path`, if any.
+fn git_root(path: &Path) -> Option<PathBuf> {
+    let repo = git2::Repository::discover(path).ok()?;
+    let workdir = repo.workdir()?;
+    Some(workdir.components().collect())
+}
+
+async fn add_dropped_folder(app: &AppHandle, folder: PathBuf) -> WorkspaceDrop {
+    let state = app.state::<AppState>();
+    let git_root = git_root(&folder);
+    let target = git_root.clone().unwrap_or_else(|| folder.clone());
+    let target = target.to_string_lossy().to_string();
+    let mut outcome = WorkspaceDrop {
+        path: folder.to_string_lossy().to_string(),
+        git_root: git_root.map(|root| root.to_string_lossy().to_string()),
+        workspace: None,
+        existing_id: None,
+        start_session: state.app_settings.lock().await.start_session_on_drop,
+        error: None,
+    };
+    let key = PathKey::new(&target);
+    let existing = state
+        .workspaces
+        .lock()
+        .await
+        .values()
+        .find(|entry| key.matches(&PathKey::new(&entry.path)))
+        .map(|entry| entry.id.clone());
+    if let Some(id) = existing {
+        outcome.existing_id = Some(id);
+        return outcome;
+    }
+    match add_workspace(target, None, state, app.clone(), CommandTimer::untimed()).await {
+        Ok(workspace) => outcome.workspace = Some(workspace),
+        Err(err) => outcome.error = Some(err),
+    }
+    outcome
+}
+
+/// Route an OS file drop: folders become workspaces (at their repo root),
+/// other files go to the frontend for the open session's composer.
+pub(crate) fn handle_drop(app: &AppHandle, paths: &[PathBuf]) {
+    let (folders, files): (Vec<PathBuf>, Vec<PathBuf>) =
+        paths.iter().cloned().partition(|path| path.is_dir());
+    if !files.is_empty() {
+        let paths = files
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let _ = app.emit("files-dropped", FilesDrop { paths });
+    }
+    if folders.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for folder in folders {
+            let outcome = add_dropped_folder(&app, folder).await;
+            let _ = app.emit("workspace-dropped", outcome);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::git_root;
+    use crate::utils::TempDir;
+
+    #[test]
+    fn finds_repo_root_from_a_subfolder() {
+        let root = TempDir::new("drop");
+        let nested = root.join("src/components");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(git_root(&nested), None);
+
+        git2::Repository::init(&root).unwrap();
+        let found = git_root(&nested).unwrap();
+        assert_eq!(
+            std::fs::canonicalize(found).unwrap(),
+            std::fs::canonicalize(&root).unwrap()
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use chrono::{Local, Timelike};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::time::sleep;
+
+use crate::claude::{emit_scoped, ClaudeEvent};
+use crate::command_metrics::CommandTimer;
+use crate::registry::now_millis;
+use crate::state::AppState;
+use crate::storage::write_settings;
+use crate::types::DoNotDisturbSettings;
+
+/// How often focus periods are checked for starting or ending.
+const FOCUS_POLL: Duration = Duration::from_secs(15);
+
+/// Do-not-disturb as of the last check, with the permission requests it is
+/// holding back.
+#[derive(Debug, Default)]
+pub(crate) struct FocusQueue {
+    active: bool,
+    until: Option<u64>,
+    held: Vec<ClaudeEvent>,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FocusStatus {
+    pub(crate) active: bool,
+    /// Epoch ms when a manual focus period ends.
+    pub(crate) until: Option<u64>,
+    /// Permission requests waiting for the focus period to end.
+    pub(crate) held: usize,
+}
+
+impl FocusQueue {
+    fn status(&self) -> FocusStatus {
+        FocusStatus {
+            active: self.active,
+            until: self.until,
+            held: self.held.len(),
+        }
+    }
+
+    /// Hold a permission request while focus is on, and forget held
+    /// requests of turns that have since ended. Returns the event if it
+    /// should go out now, and whether the held count changed.
+    fn hold(&mut self, event: ClaudeEvent) -> (Option<ClaudeEvent>, bool) {
+        match event.event_type.as_str() {
+            "permission/request" if self.active => {
+                self.held.push(event);
+                (None, true)
+            }
+            "result" | "session/closed" => {
+                let before = self.held.len();
+                self.held.retain(|held| held.session_id != event.session_id);
+                let changed = self.held.len() != before;
+                (Some(event), changed)
+            }
+            _ => (Some(event), false),
+        }
+    }
+}
+
+/// Whether do-not-disturb is on at `now` (epoch ms), `minute` being the
+/// local minutes past midnight.
+pub(crate) fn is_active(settings: &DoNotDisturbSettings, now: u64, minute: u16) -> bool {
+    let manual = settings.enabled && settings.until.is_none_or(|until| now < until);
+    let scheduled = settings.schedule.is_some_and(|hours| {
+        if hours.start <= hours.end {
+            (hours.start..hours.end).contains(&minute)
+        } else {
+            minute >= hours.start || minute < hours.end
+        }
+    });
+    manual || scheduled
+}
+
+/// Pass a bridge event through do-not-disturb. Returns `None` for a
+/// permission request held until the focus period ends.
+pub(crate) fn hold_during_focus(app_handle: &AppHandle, event: ClaudeEvent) -> Option<ClaudeEvent> {
+    let Some(state) = app_handle.try_state::<AppState>() else {
+        return Some(event);
+    };
+    let Ok(mut queue) = state.focus.lock() else {
+        return Some(event);
+    };
+    let (event, changed) = queue.hold(event);
+    if changed {
+        let _ = app_handle.emit("focus-changed", queue.status());
+    }
+    event
+}
+
+/// Recompute do-not-disturb from settings and the clock. When it ends, held
+/// requests go out in the order they arrived. Emits `focus-changed` when
+/// anything changed.
+pub(crate) async fn refresh_focus(app_handle: &AppHandle) -> FocusStatus {
+    let state = app_handle.state::<AppState>();
+    let settings = state.app_settings.lock().await.do_not_disturb.clone();
+    let local = Local::now();
+    let minute = (local.hour() * 60 + local.minute()) as u16;
+    let active = is_active(&settings, now_millis(), minute);
+    let until = settings.until.filter(|_| settings.enabled);
+    let (status, released, changed) = {
+        let Ok(mut queue) = state.focus.lock() else {
+            return FocusStatus::default();
+        };
+        let changed = queue.active != active || queue.until != until;
+        queue.active = active;
+        queue.until = until;
+        let released = if active {
+            Vec::new()
+        } else {
+            std::mem::take(&mut queue.held)
+        };
+        let changed = changed || !released.is_empty();
+        (queue.status(), released, changed)
+    };
+    for event in released {
+        let workspace_id = event.workspace_id.clone();
+        emit_scoped(app_handle, "claude-event", &workspace_id, event);
+    }
+    if changed {
+        let _ = app_handle.emit("focus-changed", status.clone());
+    }
+    status
+}
+
+/// Start and end focus periods on time, not just on settings changes.
+pub(crate) fn spawn_focus_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh_focus(&app).await;
+            sleep(FOCUS_POLL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_do_not_disturb(
+    app_handle: AppHandle,
+    _timer: CommandTimer,
+) -> Result<FocusStatus, String> {
+    Ok(refresh_focus(&app_handle).await)
+}
+
+/// Turn do-not-disturb on or off by hand. `minutes` bounds the focus
+/// period; without it, it lasts until turned off. Quiet hours still apply.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_do_not_disturb(
+    enabled: bool,
+    minutes: Option<u64>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<FocusStatus, String> {
+    {
+        let mut settings = state.app_settings.lock().await;
+        settings.do_not_disturb.enabled = enabled;
+        settings.do_not_disturb.until = minutes
+            .filter(|_| enabled)
+            .map(|minutes| now_millis() + minutes * 60_000);
+        write_settings(&state.settings_path, &settings)?;
+    }
+    Ok(refresh_focus(&app_handle).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{is_active, FocusQueue};
+    use crate::claude::ClaudeEvent;
+    use crate::events::ClaudeEventPayload;
+    use crate::types::{DoNotDisturbSettings, QuietHours};
+
+    fn event(event_type: &str, session_id: &str) -> ClaudeEvent {
+        ClaudeEvent {
+            event_type: event_type.to_string(),
+            session_id: session_id.to_string(),
+            workspace_id: "w1".to_string(),
+            timestamp: 0,
+            payload: ClaudeEventPayload::Raw(json!({})),
+        }
+    }
+
+    #[test]
+    fn manual_periods_and_quiet_hours() {
+        let mut settings = DoNotDisturbSettings::default();
+        assert!(!is_active(&settings, 1_000, 600));
+
+        settings.enabled = true;
+        assert!(is_active(&settings, 1_000, 600));
+        settings.until = Some(2_000);
+        assert!(is_active(&settings, 1_999, 600));
+        assert!(!is_active(&settings, 2_000, 600));
+
+        settings.enabled = false;
+        // 22:00 to 07:00 wraps past midnight.
+        settings.schedule = Some(QuietHours {
+            start: 22 * 60,
+            end: 7 * 60,
+        });
+        assert!(is_active(&settings, 0, 23 * 60));
+        assert!(is_active(&settings, 0, 60));
+        assert!(!is_active(&settings, 0, 7 * 60));
+        settings.schedule = Some(QuietHours {
+            start: 9 * 60,
+            end: 12 * 60,
+        });
+        assert!(is_active(&settings, 0, 10 * 60));
+        assert!(!is_active(&settings, 0, 13 * 60));
+    }
+
+    #[test]
+    fn holds_permission_requests_until_their_turn_ends() {
+        let mut queue = FocusQueue::default();
+        let (passed, changed) = queue.hold(event("permission/request", "s1"));
+        assert!(passed.is_some() && !changed);
+
+        queue.active = true;
+        assert!(queue.hold(event("permission/request", "s1")).0.is_none());
+        assert!(queue.hold(event("permission/request", "s2")).0.is_none());
+        assert!(queue.hold(event("message/delta", "s1")).0.is_some());
+        assert_eq!(queue.status().held, 2);
+
+        let (passed, changed) = queue.hold(event("result", "s1"));
+        assert!(passed.is_some() && changed);
+        assert_eq!(queue.held[0].session_id, "s2");
+    }
+}
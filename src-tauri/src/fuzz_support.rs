@@ -0,0 +1,58 @@
+//! Entry points for the `cargo fuzz` targets in `fuzz/`. Each runs a parser
+//! on arbitrary bytes and asserts what the proptests in `fuzz_tests` do:
+//! no panics, and whatever comes out is well formed. Not part of the app's
+//! API.
+
+use std::path::{Path, PathBuf};
+
+use crate::claude::parse_bridge_line;
+use crate::registry::{extract_session_from_jsonl, parse_session_history};
+use crate::utils::PathKey;
+
+/// Parse `line` as bridge stdout.
+pub fn bridge_line(line: &[u8]) {
+    let text = String::from_utf8_lossy(line);
+    let is_json = serde_json::from_str::<serde_json::Value>(text.trim()).is_ok();
+    match parse_bridge_line(line) {
+        Ok(Some(_)) => assert!(is_json),
+        Ok(None) => assert!(text.trim().is_empty()),
+        Err(_) => assert!(!is_json),
+    }
+}
+
+/// Write `bytes` as a transcript and read it back as a scan and as the
+/// session history view do.
+pub fn transcript(bytes: &[u8]) {
+    let dir = fuzz_dir();
+    let path = dir.join("transcript.jsonl");
+    std::fs::write(&path, bytes).unwrap();
+    let cwd = dir.to_string_lossy().to_string();
+    check_transcript(&path, &cwd, &dir);
+}
+
+fn fuzz_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("claude-monitor-fuzz-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Parse the transcript at `path` both ways and check the results.
+pub(crate) fn check_transcript(path: &Path, cwd: &str, project_dir: &Path) {
+    if let Ok(session) = extract_session_from_jsonl(path, "fuzz", &PathKey::new(cwd), project_dir) {
+        let preview = session.preview.unwrap_or_default();
+        assert!(preview.chars().count() <= 103);
+    }
+    let history = parse_session_history("fuzz", path, Path::new(cwd), &[]).unwrap();
+    for item in &history.items {
+        match item["kind"].as_str() {
+            Some("message") => {
+                assert!(item["text"].as_str().is_some_and(|text| !text.is_empty()))
+            }
+            Some("tool") => assert!(item["id"]
+                .as_str()
+                .is_some_and(|id| id.starts_with("tool-"))),
+            Some("reasoning") => assert!(item["content"].is_string()),
+            other => panic!("unexpected history item kind {other:?}"),
+        }
+    }
+}
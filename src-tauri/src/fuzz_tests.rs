@@ -0,0 +1,244 @@
+//! Property tests for transcript and bridge line parsing. Inputs are valid
+//! entries mangled the way real files are: truncated mid-line (and
+//! mid-character), stray non-UTF-8 bytes, unexpected entry types and fields
+//! of the wrong type. The parsers must never panic and must keep whatever
+//! is still readable. Set `PROPTEST_CASES` to run more; the `cargo fuzz`
+//! targets in `fuzz/` run the same checks on unstructured bytes.
+
+use std::io::Write;
+
+use proptest::prelude::*;
+use proptest::sample::{select, Index};
+use serde_json::{json, Value};
+
+use crate::claude::parse_bridge_line;
+use crate::fuzz_support::{bridge_line, check_transcript};
+use crate::registry::{extract_session_from_jsonl, extract_text_from_message};
+use crate::utils::{PathKey, TempDir};
+
+const WORDS: &[&str] = &[
+    "",
+    "text",
+    "user",
+    "assistant",
+    "tool_use",
+    "ünïcödé",
+    "日本語",
+    "🦀",
+    "\u{0}",
+    "\\",
+    "\"",
+    "src/main.rs",
+    "@README.md",
+    "line\nbreak",
+];
+
+/// Stands in for the workspace path, which is only known once the test
+/// has made its temp dir.
+const CWD: &str = "$CWD";
+
+fn word() -> impl Strategy<Value = String> {
+    prop_oneof![
+        3 => (select(WORDS), 0..4usize).prop_map(|(word, count)| word.repeat(count)),
+        1 => any::<String>(),
+    ]
+}
+
+fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<f64>().prop_filter_map("not finite", |n| {
+            serde_json::Number::from_f64(n).map(Value::Number)
+        }),
+        word().prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 32, 4, |inner| {
+        let keys = select(
+            &[
+                "type", "text", "content", "message", "cwd", "role", "payload",
+            ][..],
+        );
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::vec((keys.prop_map(str::to_string), inner), 0..5)
+                .prop_map(|fields| Value::Object(fields.into_iter().collect())),
+        ]
+    })
+}
+
+/// A well-formed transcript entry, with some fields swapped for random values.
+fn transcript_entry() -> impl Strategy<Value = Value> {
+    let kind = select(
+        &[
+            "user",
+            "assistant",
+            "system",
+            "summary",
+            "file-history-snapshot",
+        ][..],
+    );
+    let swapped = prop::collection::vec(
+        (
+            select(&["type", "cwd", "message", "uuid"][..]),
+            json_value(),
+        ),
+        0..2,
+    );
+    (
+        kind,
+        word(),
+        1..40usize,
+        any::<u64>(),
+        swapped,
+        prop::option::weighted(0.1, json_value()),
+    )
+        .prop_map(|(kind, text, repeat, uuid, swapped, content)| {
+            let mut entry = json!({
+                "type": kind,
+                "sessionId": "fuzz",
+                "cwd": CWD,
+                "uuid": format!("u{uuid}"),
+                "message": {
+                    "role": kind,
+                    "content": [
+                        { "type": "text", "text": text.repeat(repeat) },
+                        { "type": "tool_use", "name": "Read", "input": { "file_path": "src/main.rs" } },
+                    ],
+                },
+            });
+            for (key, value) in swapped {
+                entry[key] = value;
+            }
+            if let (Some(content), true) = (content, entry["message"].is_object()) {
+                entry["message"]["content"] = content;
+            }
+            entry
+        })
+}
+
+fn bridge_event() -> impl Strategy<Value = Value> {
+    let kind = select(&["message/delta", "response", "session/started", "error"][..]);
+    let swapped = prop::collection::vec(
+        (
+            select(&["type", "sessionId", "workspaceId", "timestamp"][..]),
+            json_value(),
+        ),
+        0..2,
+    );
+    (kind, json_value(), swapped).prop_map(|(kind, payload, swapped)| {
+        let mut event = json!({
+            "type": kind,
+            "sessionId": "s1",
+            "workspaceId": "w1",
+            "timestamp": 1_700_000_000_000i64,
+            "payload": payload,
+        });
+        for (key, value) in swapped {
+            event[key] = value;
+        }
+        event
+    })
+}
+
+/// Damage done to a serialized line.
+#[derive(Clone, Debug)]
+enum Damage {
+    /// Cut the line short, possibly inside a multi-byte character.
+    Truncate(Index),
+    /// Insert invalid UTF-8 or a stray control byte.
+    Inject(Index, &'static [u8]),
+    /// Replace the whole line with random bytes.
+    Replace(Vec<u8>),
+}
+
+fn damage() -> impl Strategy<Value = Vec<Damage>> {
+    let junk: &'static [&'static [u8]] = &[b"\xff", b"\xc3", b"\xe6\x97", b"\x00", b"\r"];
+    let one = prop_oneof![
+        4 => any::<Index>().prop_map(Damage::Truncate),
+        3 => (any::<Index>(), select(junk)).prop_map(|(at, junk)| Damage::Inject(at, junk)),
+        1 => prop::collection::vec(any::<u8>(), 0..64).prop_map(Damage::Replace),
+    ];
+    prop_oneof![
+        3 => Just(Vec::new()),
+        1 => prop::collection::vec(one, 1..3),
+    ]
+}
+
+fn mangle(value: &Value, damage: &[Damage]) -> Vec<u8> {
+    let mut bytes = value.to_string().into_bytes();
+    for damage in damage {
+        match damage {
+            Damage::Truncate(at) => bytes.truncate(at.index(bytes.len() + 1)),
+            Damage::Inject(at, junk) => {
+                let at = at.index(bytes.len() + 1);
+                bytes.splice(at..at, junk.iter().copied());
+            }
+            Damage::Replace(random) => bytes = random.clone(),
+        }
+    }
+    bytes
+}
+
+proptest! {
+    #[test]
+    fn extract_text_from_message_accepts_any_json(message in json_value()) {
+        let text = extract_text_from_message(&message);
+        if !message.is_object() {
+            prop_assert!(text.is_empty());
+        }
+    }
+
+    #[test]
+    fn bridge_lines_never_panic(event in bridge_event(), damage in damage()) {
+        bridge_line(&mangle(&event, &damage));
+    }
+
+    #[test]
+    fn transcripts_degrade_gracefully(
+        lines in prop::collection::vec((transcript_entry(), damage(), any::<bool>()), 0..60)
+    ) {
+        let root = TempDir::new("fuzz");
+        let cwd = root.to_string_lossy().to_string();
+        let path = root.join("fuzz.jsonl");
+        let mut file = std::fs::File::create(&path).unwrap();
+        for (mut entry, damage, crlf) in lines {
+            if entry["cwd"] == CWD {
+                entry["cwd"] = json!(cwd);
+            }
+            file.write_all(&mangle(&entry, &damage)).unwrap();
+            let end: &[u8] = if crlf { b"\r\n" } else { b"\n" };
+            file.write_all(end).unwrap();
+        }
+        drop(file);
+        check_transcript(&path, &cwd, &root);
+    }
+}
+
+#[test]
+fn bridge_lines_that_are_not_events_parse_with_defaults() {
+    let parsed = parse_bridge_line(b"[1, 2]").unwrap().unwrap();
+    assert_eq!(parsed.event_type, "unknown");
+    assert!(parsed.session_id.is_empty());
+    assert_eq!(parse_bridge_line(b" \r").unwrap().map(|_| ()), None);
+}
+
+#[test]
+fn invalid_utf8_costs_a_character_not_the_entry() {
+    let root = TempDir::new("fuzz");
+    let cwd = root.to_string_lossy().to_string();
+    let path = root.join("latin1.jsonl");
+    let mut line = json!({
+        "type": "user",
+        "cwd": cwd,
+        "message": { "content": [{ "type": "text", "text": "caf_" }] },
+    })
+    .to_string()
+    .into_bytes();
+    let at = line.windows(4).position(|bytes| bytes == b"caf_").unwrap();
+    line[at + 3] = 0xe9;
+    std::fs::write(&path, &line).unwrap();
+    let session = extract_session_from_jsonl(&path, "latin1", &PathKey::new(&cwd), &root).unwrap();
+    assert_eq!(session.preview.as_deref(), Some("caf\u{fffd}"));
+}
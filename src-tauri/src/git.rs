@@ -1,19 +1,32 @@
+use std::collections::BTreeMap;
+
 use git2::{BranchType, DiffOptions, Repository, Sort, Status, StatusOptions, Tree};
+use serde::Deserialize;
 use serde_json::json;
 use tauri::State;
 use tokio::process::Command;
 
+use crate::command_metrics::CommandTimer;
+use crate::highlight::highlight_diff;
+use crate::lfs::lfs_info;
+use crate::proxy::apply_network_env;
 use crate::state::AppState;
 use crate::types::{
-    BranchInfo, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse, GitLogEntry,
-    GitLogResponse,
+    BranchInfo, DiffTruncation, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse,
+    GitLogEntry, GitLogResponse,
 };
 use crate::utils::normalize_git_path;
+use crate::word_diff::intraline_changes;
+
+/// Per-file caps on what `get_git_diffs` sends to the webview; generated
+/// files can produce patches large enough to stall rendering.
+const MAX_DIFF_LINES: usize = 3000;
+const MAX_DIFF_BYTES: usize = 512 * 1024;
 
 fn commit_to_entry(commit: git2::Commit) -> GitLogEntry {
     let summary = commit.summary().unwrap_or("").to_string();
     let author = commit.author().name().unwrap_or("").to_string();
-    let timestamp = commit.time().seconds();
+    let timestamp = commit.time().seconds() * 1000;
     GitLogEntry {
         sha: commit.id().to_string(),
         summary,
@@ -31,6 +44,126 @@ fn checkout_branch(repo: &Repository, name: &str) -> Result<(), git2::Error> {
     Ok(())
 }
 
+/// Open the repository containing a workspace. Workspaces may be rooted in
+/// a subdirectory of a repo (e.g. `packages/api` in a monorepo); in that case
+/// the repo-relative subdirectory is returned so status and diffs can be
+/// limited to it.
+pub(crate) fn open_workspace_repo(path: &str) -> Result<(Repository, Option<String>), String> {
+    let repo = Repository::discover(path).map_err(|e| e.to_string())?;
+    let subdir = repo.workdir().and_then(|root| {
+        let root = std::fs::canonicalize(root).ok()?;
+        let workspace = std::fs::canonicalize(path).ok()?;
+        let relative = workspace.strip_prefix(&root).ok()?;
+        let relative = normalize_git_path(&relative.to_string_lossy());
+        (!relative.is_empty()).then_some(relative)
+    });
+    Ok((repo, subdir))
+}
+
+/// Repo-relative path as seen from a subdirectory workspace.
+pub(crate) fn workspace_relative_path(path: &str, subdir: Option<&str>) -> String {
+    let path = normalize_git_path(path);
+    match subdir {
+        Some(subdir) => path
+            .strip_prefix(subdir)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map(|rest| rest.to_string())
+            .unwrap_or(path),
+        None => path,
+    }
+}
+
+/// A single CODEOWNERS line: a gitignore-style pattern and its owners.
+struct CodeOwnersRule {
+    pattern: String,
+    owners: Vec<String>,
+}
+
+/// Locations GitHub and GitLab look for CODEOWNERS, in priority order.
+const CODEOWNERS_PATHS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+fn read_codeowners(repo: &Repository) -> Option<Vec<CodeOwnersRule>> {
+    let root = repo.workdir()?;
+    CODEOWNERS_PATHS
+        .iter()
+        .find_map(|path| std::fs::read_to_string(root.join(path)).ok())
+        .map(|content| parse_codeowners(&content))
+}
+
+fn parse_codeowners(content: &str) -> Vec<CodeOwnersRule> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        // Skip GitLab section headers like `[Docs]` or `^[Docs]`.
+        .filter(|line| !line.starts_with('[') && !line.starts_with("^["))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pattern = parts.next()?.to_string();
+            let owners = parts
+                .take_while(|part| !part.starts_with('#'))
+                .map(|owner| owner.to_string())
+                .collect();
+            Some(CodeOwnersRule { pattern, owners })
+        })
+        .collect()
+}
+
+/// Glob match where `*` and `?` stay within a path segment and `**` spans
+/// segments (`**/` may also match nothing).
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            let rest = &pattern[2..];
+            if rest.first() == Some(&b'/') && glob_match(&rest[1..], text) {
+                return true;
+            }
+            (0..=text.len()).any(|index| glob_match(rest, &text[index..]))
+        }
+        Some(b'*') => {
+            for index in 0..=text.len() {
+                if glob_match(&pattern[1..], &text[index..]) {
+                    return true;
+                }
+                if index < text.len() && text[index] == b'/' {
+                    break;
+                }
+            }
+            false
+        }
+        Some(b'?') => {
+            !text.is_empty() && text[0] != b'/' && glob_match(&pattern[1..], &text[1..])
+        }
+        Some(ch) => text.first() == Some(ch) && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+fn codeowners_pattern_matches(pattern: &str, path: &str) -> bool {
+    // Like .gitignore: a slash at the start or middle anchors the pattern
+    // to the repo root; otherwise it matches at any depth.
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let mut glob = pattern.trim_start_matches('/').to_string();
+    if glob.ends_with('/') {
+        glob.push_str("**");
+    }
+    if !anchored {
+        glob = format!("**/{glob}");
+    }
+    glob_match(glob.as_bytes(), path.as_bytes())
+        || glob_match(format!("{glob}/**").as_bytes(), path.as_bytes())
+}
+
+/// Owners for a repo-relative path; the last matching rule wins.
+fn owners_for_path(rules: &[CodeOwnersRule], path: &str) -> Vec<String> {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| codeowners_pattern_matches(&rule.pattern, path))
+        .map(|rule| rule.owners.clone())
+        .unwrap_or_default()
+}
+
 fn diff_stats_for_path(
     repo: &Repository,
     head_tree: Option<&Tree>,
@@ -66,7 +199,7 @@ fn diff_stats_for_path(
     Ok((additions, deletions))
 }
 
-fn diff_patch_to_string(patch: &mut git2::Patch) -> Result<String, git2::Error> {
+pub(crate) fn diff_patch_to_string(patch: &mut git2::Patch) -> Result<String, git2::Error> {
     let buf = patch.to_buf()?;
     Ok(buf
         .as_str()
@@ -97,17 +230,21 @@ fn parse_github_repo(remote_url: &str) -> Option<String> {
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_git_status(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<serde_json::Value, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
+    let large_file_threshold = state.app_settings.lock().await.lfs.threshold_bytes();
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
 
-    let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
+    let (repo, subdir) = open_workspace_repo(&entry.path)?;
 
     let branch_name = repo
         .head()
@@ -122,12 +259,16 @@ pub(crate) async fn get_git_status(
         .renames_head_to_index(true)
         .renames_index_to_workdir(true)
         .include_ignored(false);
+    if let Some(subdir) = subdir.as_deref() {
+        status_options.pathspec(subdir);
+    }
 
     let statuses = repo
         .statuses(Some(&mut status_options))
         .map_err(|e| e.to_string())?;
 
     let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let codeowners = read_codeowners(&repo).unwrap_or_default();
 
     let mut files = Vec::new();
     let mut total_additions = 0i64;
@@ -153,7 +294,7 @@ pub(crate) async fn get_git_status(
         } else {
             "--"
         };
-        let normalized_path = normalize_git_path(path);
+        let normalized_path = workspace_relative_path(path, subdir.as_deref());
         let include_index = status.intersects(
             Status::INDEX_NEW
                 | Status::INDEX_MODIFIED
@@ -178,16 +319,20 @@ pub(crate) async fn get_git_status(
         .map_err(|e| e.to_string())?;
         total_additions += additions;
         total_deletions += deletions;
+        let lfs = lfs_info(&repo, subdir.as_deref(), &normalized_path, large_file_threshold);
         files.push(GitFileStatus {
             path: normalized_path,
             status: status_str.to_string(),
             additions,
             deletions,
+            owners: owners_for_path(&codeowners, &normalize_git_path(path)),
+            lfs,
         });
     }
 
     Ok(json!({
         "branchName": branch_name,
+        "subdirectory": subdir,
         "files": files,
         "totalAdditions": total_additions,
         "totalDeletions": total_deletions,
@@ -195,17 +340,178 @@ pub(crate) async fn get_git_status(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_git_diffs(
     workspace_id: String,
+    highlight: Option<bool>,
+    word_diff: Option<bool>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Vec<GitFileDiff>, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
+
+    let highlight = highlight.unwrap_or(false);
+    let word_diff = word_diff.unwrap_or(false);
+    let large_file_threshold = state.app_settings.lock().await.lfs.threshold_bytes();
+    // Diffing and annotating a large change set is CPU-bound; keep it off
+    // the runtime.
+    tokio::task::spawn_blocking(move || {
+        let mut diffs = workspace_diffs(&entry.path)?;
+        let (repo, subdir) = open_workspace_repo(&entry.path)?;
+        for diff in &mut diffs {
+            diff.lfs = lfs_info(&repo, subdir.as_deref(), &diff.path, large_file_threshold);
+            truncate_diff(diff);
+            if highlight {
+                diff.highlighted = highlight_diff(&diff.path, &diff.diff);
+            }
+            if word_diff {
+                diff.intraline = Some(intraline_changes(&diff.diff));
+            }
+        }
+        Ok(diffs)
+    })
+    .await
+    .map_err(|_| "diff annotation failed".to_string())?
+}
+
+/// Hunks `[start, end)` of a file's patch, counted from 0.
+#[derive(Debug, Deserialize, Clone, Copy, specta::Type)]
+pub(crate) struct HunkRange {
+    start: usize,
+    end: usize,
+}
+
+/// Header lines and `@@` hunks of a single-file patch.
+fn split_hunks(diff: &str) -> (Vec<&str>, Vec<Vec<&str>>) {
+    let mut header = Vec::new();
+    let mut hunks: Vec<Vec<&str>> = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("@@") {
+            hunks.push(vec![line]);
+        } else if let Some(hunk) = hunks.last_mut() {
+            hunk.push(line);
+        } else {
+            header.push(line);
+        }
+    }
+    (header, hunks)
+}
+
+/// Cut an oversized patch back to the whole hunks that fit the caps (or
+/// the start of the first hunk, if even that is too big) and end it with a
+/// `\` marker line. The rest is available through `get_file_diff`.
+fn truncate_diff(diff: &mut GitFileDiff) {
+    let total_lines = diff.diff.lines().count();
+    if total_lines <= MAX_DIFF_LINES && diff.diff.len() <= MAX_DIFF_BYTES {
+        return;
+    }
+    let (header, hunks) = split_hunks(&diff.diff);
+    let mut kept: Vec<&str> = header;
+    let mut bytes: usize = kept.iter().map(|line| line.len() + 1).sum();
+    let mut shown_hunks = 0;
+    for hunk in &hunks {
+        let hunk_bytes: usize = hunk.iter().map(|line| line.len() + 1).sum();
+        if kept.len() + hunk.len() <= MAX_DIFF_LINES && bytes + hunk_bytes <= MAX_DIFF_BYTES {
+            kept.extend(hunk);
+            bytes += hunk_bytes;
+            shown_hunks += 1;
+            continue;
+        }
+        if shown_hunks == 0 {
+            for line in hunk {
+                if kept.len() >= MAX_DIFF_LINES || bytes + line.len() + 1 > MAX_DIFF_BYTES {
+                    break;
+                }
+                kept.push(line);
+                bytes += line.len() + 1;
+            }
+        }
+        break;
+    }
+    let shown_lines = kept.len();
+    let marker = format!(
+        "\\ Diff truncated: showing {shown_lines} of {total_lines} lines ({shown_hunks} of {} hunks)",
+        hunks.len()
+    );
+    let mut text = kept.join("\n");
+    text.push('\n');
+    text.push_str(&marker);
+    text.push('\n');
+    diff.truncated = Some(DiffTruncation {
+        total_lines,
+        shown_lines,
+        total_hunks: hunks.len(),
+        shown_hunks,
+    });
+    diff.diff = text;
+}
 
-    let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
+/// One file's full diff, with `context` lines around each change and only
+/// the hunks in `range` when given. Fetches what `get_git_diffs` truncated
+/// or expands context around a change.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_file_diff(
+    workspace_id: String,
+    path: String,
+    context: Option<u32>,
+    range: Option<HunkRange>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<GitFileDiff, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
+    let workspace_path = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(|entry| entry.path.clone())
+        .ok_or("workspace not found")?;
+    let path = normalize_git_path(path.trim().trim_start_matches("./"));
+    tokio::task::spawn_blocking(move || {
+        let mut diff = collect_workspace_diffs(&workspace_path, Some(&path), context)?
+            .into_iter()
+            .find(|diff| diff.path == path)
+            .ok_or_else(|| format!("No changes in {path}"))?;
+        if let Some(range) = range {
+            let (header, hunks) = split_hunks(&diff.diff);
+            if range.start >= range.end || range.start >= hunks.len() {
+                return Err(format!("{path} has {} hunks", hunks.len()));
+            }
+            let mut lines = header;
+            for hunk in &hunks[range.start..range.end.min(hunks.len())] {
+                lines.extend(hunk);
+            }
+            let mut text = lines.join("\n");
+            text.push('\n');
+            diff.diff = text;
+        }
+        Ok(diff)
+    })
+    .await
+    .map_err(|_| "diff failed".to_string())?
+}
+
+/// Per-file patches for a workspace's uncommitted changes (staged, unstaged
+/// and untracked) against HEAD.
+pub(crate) fn workspace_diffs(workspace_path: &str) -> Result<Vec<GitFileDiff>, String> {
+    collect_workspace_diffs(workspace_path, None, None)
+}
+
+/// `workspace_diffs`, limited to one workspace-relative `file` and with
+/// `context` lines around each change instead of git's default three.
+fn collect_workspace_diffs(
+    workspace_path: &str,
+    file: Option<&str>,
+    context: Option<u32>,
+) -> Result<Vec<GitFileDiff>, String> {
+    let (repo, subdir) = open_workspace_repo(workspace_path)?;
     let head_tree = repo
         .head()
         .ok()
@@ -216,6 +522,24 @@ pub(crate) async fn get_git_diffs(
         .include_untracked(true)
         .recurse_untracked_dirs(true)
         .show_untracked_content(true);
+    if let Some(context) = context {
+        options.context_lines(context);
+    }
+    match (file, subdir.as_deref()) {
+        (Some(file), Some(subdir)) => {
+            options
+                .pathspec(format!("{subdir}/{file}"))
+                .disable_pathspec_match(true);
+        }
+        (Some(file), None) => {
+            options.pathspec(file).disable_pathspec_match(true);
+        }
+        (None, Some(subdir)) => {
+            options.pathspec(subdir);
+        }
+        (None, None) => {}
+    }
+    let codeowners = read_codeowners(&repo).unwrap_or_default();
 
     let diff = match head_tree.as_ref() {
         Some(tree) => repo
@@ -249,28 +573,125 @@ pub(crate) async fn get_git_diffs(
         if content.trim().is_empty() {
             continue;
         }
+        let repo_path = normalize_git_path(path.to_string_lossy().as_ref());
         results.push(GitFileDiff {
-            path: normalize_git_path(path.to_string_lossy().as_ref()),
+            path: workspace_relative_path(&repo_path, subdir.as_deref()),
             diff: content,
+            owners: owners_for_path(&codeowners, &repo_path),
+            highlighted: None,
+            intraline: None,
+            truncated: None,
+            lfs: None,
         });
     }
 
     Ok(results)
 }
 
+/// Contents of a workspace-relative file as of HEAD, or `None` when the
+/// file is new (or the repo has no commits yet).
+pub(crate) fn read_head_file(
+    workspace_path: &str,
+    relative_path: &str,
+) -> Result<Option<String>, String> {
+    let (repo, subdir) = open_workspace_repo(workspace_path)?;
+    let Some(tree) = repo.head().ok().and_then(|head| head.peel_to_tree().ok()) else {
+        return Ok(None);
+    };
+    let repo_path = match subdir {
+        Some(subdir) => format!("{subdir}/{relative_path}"),
+        None => relative_path.to_string(),
+    };
+    let Ok(entry) = tree.get_path(std::path::Path::new(&repo_path)) else {
+        return Ok(None);
+    };
+    let blob = entry
+        .to_object(&repo)
+        .and_then(|object| object.peel_to_blob())
+        .map_err(|e| e.to_string())?;
+    Ok(Some(String::from_utf8_lossy(blob.content()).to_string()))
+}
+
+/// Group the workspace's changed files by CODEOWNERS owner, so reviewers
+/// can be picked before opening a PR.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_changed_files_owners(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<serde_json::Value, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+
+    let (repo, subdir) = open_workspace_repo(&entry.path)?;
+    let codeowners = read_codeowners(&repo);
+    let mut status_options = StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    if let Some(subdir) = subdir.as_deref() {
+        status_options.pathspec(subdir);
+    }
+    let statuses = repo
+        .statuses(Some(&mut status_options))
+        .map_err(|e| e.to_string())?;
+
+    let mut by_owner: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut unowned = Vec::new();
+    for status in statuses.iter() {
+        let Some(path) = status.path() else {
+            continue;
+        };
+        let repo_path = normalize_git_path(path);
+        let display_path = workspace_relative_path(&repo_path, subdir.as_deref());
+        let owners = codeowners
+            .as_deref()
+            .map(|rules| owners_for_path(rules, &repo_path))
+            .unwrap_or_default();
+        if owners.is_empty() {
+            unowned.push(display_path);
+            continue;
+        }
+        for owner in owners {
+            by_owner.entry(owner).or_default().push(display_path.clone());
+        }
+    }
+
+    let owners: Vec<_> = by_owner
+        .into_iter()
+        .map(|(owner, files)| json!({ "owner": owner, "files": files }))
+        .collect();
+    Ok(json!({
+        "hasCodeowners": codeowners.is_some(),
+        "owners": owners,
+        "unowned": unowned,
+    }))
+}
+
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_git_log(
     workspace_id: String,
     limit: Option<usize>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<GitLogResponse, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
 
-    let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
+    let (repo, _) = open_workspace_repo(&entry.path)?;
     let max_items = limit.unwrap_or(40);
     let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
     revwalk.push_head().map_err(|e| e.to_string())?;
@@ -366,9 +787,11 @@ pub(crate) async fn get_git_log(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_git_remote(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Option<String>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
@@ -376,29 +799,40 @@ pub(crate) async fn get_git_remote(
         .ok_or("workspace not found")?
         .clone();
 
-    let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
-    let remotes = repo.remotes().map_err(|e| e.to_string())?;
-    let name = if remotes.iter().any(|remote| remote == Some("origin")) {
-        "origin".to_string()
-    } else {
-        remotes
-            .iter()
-            .flatten()
-            .next()
-            .unwrap_or("")
-            .to_string()
-    };
-    if name.is_empty() {
+    let (repo, _) = open_workspace_repo(&entry.path)?;
+    let Some(name) = default_remote(&repo)? else {
         return Ok(None);
-    }
+    };
     let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
     Ok(remote.url().map(|url| url.to_string()))
 }
 
+/// `origin`, or else the first configured remote.
+fn default_remote(repo: &Repository) -> Result<Option<String>, String> {
+    let remotes = repo.remotes().map_err(|e| e.to_string())?;
+    if remotes.iter().any(|remote| remote == Some("origin")) {
+        return Ok(Some("origin".to_string()));
+    }
+    Ok(remotes.iter().flatten().next().map(str::to_string))
+}
+
+/// The default remote of the repository at `path` and the `owner/name` of
+/// the GitHub repository it points to.
+pub(crate) fn github_remote(path: &str) -> Result<(String, String), String> {
+    let (repo, _) = open_workspace_repo(path)?;
+    let name = default_remote(&repo)?.ok_or("No git remote configured.")?;
+    let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
+    let remote_url = remote.url().ok_or("Remote has no URL configured.")?;
+    let repo_name = parse_github_repo(remote_url).ok_or("Remote is not a GitHub repository.")?;
+    Ok((name, repo_name))
+}
+
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_github_issues(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<GitHubIssuesResponse, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
@@ -406,30 +840,11 @@ pub(crate) async fn get_github_issues(
         .ok_or("workspace not found")?
         .clone();
 
-    let repo_name = {
-        let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
-        let remotes = repo.remotes().map_err(|e| e.to_string())?;
-        let name = if remotes.iter().any(|remote| remote == Some("origin")) {
-            "origin".to_string()
-        } else {
-            remotes
-                .iter()
-                .flatten()
-                .next()
-                .unwrap_or("")
-                .to_string()
-        };
-        if name.is_empty() {
-            return Err("No git remote configured.".to_string());
-        }
-        let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
-        let remote_url = remote
-            .url()
-            .ok_or("Remote has no URL configured.")?;
-        parse_github_repo(remote_url).ok_or("Remote is not a GitHub repository.")?
-    };
+    let (_, repo_name) = github_remote(&entry.path)?;
 
-    let output = Command::new("gh")
+    let mut command = Command::new("gh");
+    apply_network_env(&mut command);
+    let output = command
         .args([
             "issue",
             "list",
@@ -464,7 +879,9 @@ pub(crate) async fn get_github_issues(
 
     let search_query = format!("repo:{repo_name} is:issue is:open");
     let search_query = search_query.replace(' ', "+");
-    let total = match Command::new("gh")
+    let mut command = Command::new("gh");
+    apply_network_env(&mut command);
+    let total = match command
         .args([
             "api",
             &format!("/search/issues?q={search_query}"),
@@ -486,16 +903,19 @@ pub(crate) async fn get_github_issues(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn list_git_branches(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<serde_json::Value, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
-    let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
+    let (repo, _) = open_workspace_repo(&entry.path)?;
     let mut branches = Vec::new();
     let refs = repo
         .branches(Some(BranchType::Local))
@@ -510,7 +930,7 @@ pub(crate) async fn list_git_branches(
             .get()
             .target()
             .and_then(|oid| repo.find_commit(oid).ok())
-            .map(|commit| commit.time().seconds())
+            .map(|commit| commit.time().seconds() * 1000)
             .unwrap_or(0);
         branches.push(BranchInfo { name, last_commit });
     }
@@ -519,35 +939,116 @@ pub(crate) async fn list_git_branches(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn checkout_git_branch(
     workspace_id: String,
     name: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
+    let _git = state.git_locks.write(&workspace_id).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
-    let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
+    let (repo, _) = open_workspace_repo(&entry.path)?;
     checkout_branch(&repo, &name).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn create_git_branch(
     workspace_id: String,
     name: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
+    let _git = state.git_locks.write(&workspace_id).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
-    let repo = Repository::open(&entry.path).map_err(|e| e.to_string())?;
+    let (repo, _) = open_workspace_repo(&entry.path)?;
     let head = repo.head().map_err(|e| e.to_string())?;
     let target = head.peel_to_commit().map_err(|e| e.to_string())?;
     repo.branch(&name, &target, false)
         .map_err(|e| e.to_string())?;
     checkout_branch(&repo, &name).map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{owners_for_path, parse_codeowners, split_hunks, truncate_diff, MAX_DIFF_LINES};
+    use crate::types::{DiffTruncation, GitFileDiff};
+
+    #[test]
+    fn codeowners_last_matching_rule_wins() {
+        let rules = parse_codeowners(
+            "# comment\n\
+             * @org/everyone\n\
+             *.md @org/docs\n\
+             /packages/api/ @org/api @alice\n\
+             docs/ @org/docs-team # trailing comment\n",
+        );
+        assert_eq!(owners_for_path(&rules, "src/main.rs"), vec!["@org/everyone"]);
+        assert_eq!(owners_for_path(&rules, "packages/web/README.md"), vec!["@org/docs"]);
+        assert_eq!(
+            owners_for_path(&rules, "packages/api/src/lib.rs"),
+            vec!["@org/api", "@alice"]
+        );
+        assert_eq!(owners_for_path(&rules, "site/docs/intro.txt"), vec!["@org/docs-team"]);
+    }
+
+    #[test]
+    fn codeowners_anchored_patterns_only_match_from_root() {
+        let rules = parse_codeowners("/build/logs/ @ops\napps/*.rs @rust\n");
+        assert_eq!(owners_for_path(&rules, "build/logs/out.txt"), vec!["@ops"]);
+        assert!(owners_for_path(&rules, "x/build/logs/out.txt").is_empty());
+        assert_eq!(owners_for_path(&rules, "apps/main.rs"), vec!["@rust"]);
+        assert!(owners_for_path(&rules, "apps/nested/main.rs").is_empty());
+    }
+
+    #[test]
+    fn truncates_large_diffs_at_hunk_boundaries() {
+        let hunk = |n: usize| {
+            let mut lines = vec![format!("@@ -{n},1000 +{n},1000 @@")];
+            lines.extend((0..1000).map(|i| format!("+line {i}")));
+            lines.join("\n")
+        };
+        let text = format!(
+            "diff --git a/gen.rs b/gen.rs\n{}\n{}\n{}\n{}\n",
+            hunk(1),
+            hunk(2001),
+            hunk(4001),
+            hunk(6001)
+        );
+        let (header, hunks) = split_hunks(&text);
+        assert_eq!((header.len(), hunks.len()), (1, 4));
+
+        let mut diff = GitFileDiff {
+            path: "gen.rs".to_string(),
+            diff: text,
+            owners: Vec::new(),
+            highlighted: None,
+            intraline: None,
+            truncated: None,
+            lfs: None,
+        };
+        truncate_diff(&mut diff);
+        assert_eq!(
+            diff.truncated,
+            Some(DiffTruncation {
+                total_lines: 4005,
+                shown_lines: 2003,
+                total_hunks: 4,
+                shown_hunks: 2,
+            })
+        );
+        assert!(diff.diff.lines().count() <= MAX_DIFF_LINES + 1);
+        assert!(diff
+            .diff
+            .ends_with("\\ Diff truncated: showing 2003 of 4005 lines (2 of 4 hunks)\n"));
+    }
+}
@@ -0,0 +1,288 @@
+//! Git hooks that run the large-file and secret checks on every commit and
+//! push in a workspace, including the ones the agent makes from its shell. Each hook
+//! is a short script that runs this binary with `--git-hook <name>`; `main`
+//! hands those invocations to `run_hook` instead of starting the app.
+
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use git2::{Oid, Repository};
+use tauri::{AppHandle, Manager};
+
+use crate::crash::log_line;
+use crate::git::open_workspace_repo;
+use crate::lfs::{large_commit_error, pushed_large_objects, staged_large_objects, LargeObject};
+use crate::secret_scan::{describe_findings, staged_secrets};
+use crate::state::AppState;
+use crate::storage::read_settings;
+use crate::types::AppSettings;
+
+/// Marks the hooks written here. Hooks without it belong to the user and
+/// are never overwritten or removed.
+const HOOK_MARKER: &str = "# Managed by Claude Monitor";
+const HOOKS: [&str; 2] = ["pre-commit", "pre-push"];
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn hook_script(exe: &Path, settings_path: &Path, hook: &str) -> String {
+    format!(
+        "#!/bin/sh\n{HOOK_MARKER}\nexec {} --git-hook {hook} --settings {} \"$@\"\n",
+        shell_quote(&exe.to_string_lossy()),
+        shell_quote(&settings_path.to_string_lossy()),
+    )
+}
+
+fn is_managed(path: &Path) -> bool {
+    std::fs::read_to_string(path).is_ok_and(|script| script.contains(HOOK_MARKER))
+}
+
+/// Hooks live in the common git dir, so linked worktrees share them.
+fn hooks_dir(repo: &Repository) -> Result<PathBuf, String> {
+    let config = repo.config().map_err(|e| e.to_string())?;
+    if config.get_path("core.hooksPath").is_ok() {
+        return Err("core.hooksPath is set, so the hooks there are left alone".to_string());
+    }
+    Ok(repo.commondir().join("hooks"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// Write the hooks into the repo at `path`, or remove them when `script`
+/// is `None`. Takes the exe and settings paths the hooks should call.
+fn sync_repo(path: &str, script: Option<(&Path, &Path)>) -> Result<(), String> {
+    let (repo, _) = open_workspace_repo(path)?;
+    let dir = hooks_dir(&repo)?;
+    for hook in HOOKS {
+        let file = dir.join(hook);
+        if file.exists() && !is_managed(&file) {
+            if script.is_some() {
+                log_line!("Not replacing the {hook} hook in {path}: it was not written by the app");
+            }
+            continue;
+        }
+        match script {
+            Some((exe, settings_path)) => {
+                std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+                std::fs::write(&file, hook_script(exe, settings_path, hook))
+                    .map_err(|e| e.to_string())?;
+                make_executable(&file)?;
+            }
+            None if file.exists() => {
+                std::fs::remove_file(&file).map_err(|e| e.to_string())?;
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Whether any setting needs the hooks installed.
+pub(crate) fn hooks_wanted(settings: &AppSettings) -> bool {
+    settings.lfs.git_hooks || settings.block_secret_commits
+}
+
+/// Install or remove the hooks in every workspace to match `lfs.gitHooks`
+/// and `blockSecretCommits`. Also run at launch, so the hooks follow the
+/// app when it moves.
+pub(crate) async fn sync_git_hooks(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let enabled = hooks_wanted(&state.app_settings.lock().await);
+    let paths: Vec<String> = state
+        .workspaces
+        .lock()
+        .await
+        .values()
+        .map(|entry| entry.path.clone())
+        .collect();
+    let settings_path = state.settings_path.clone();
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(err) => {
+            log_line!("Cannot install git hooks: {err}");
+            return;
+        }
+    };
+    let _ = tokio::task::spawn_blocking(move || {
+        for path in paths {
+            let script = enabled.then_some((exe.as_path(), settings_path.as_path()));
+            if let Err(err) = sync_repo(&path, script) {
+                log_line!("Failed to update git hooks in {path}: {err}");
+            }
+        }
+    })
+    .await;
+}
+
+fn describe(objects: &[LargeObject]) -> String {
+    objects
+        .iter()
+        .map(|object| {
+            let mb = object.size as f64 / (1024.0 * 1024.0);
+            let lfs = if object.lfs {
+                ", should be an LFS pointer"
+            } else {
+                ""
+            };
+            match &object.commit {
+                Some(commit) => format!("  {} ({mb:.1} MB, {commit}{lfs})", object.path),
+                None => format!("  {} ({mb:.1} MB{lfs})", object.path),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Refuse the commit when it adds credentials and `blockSecretCommits` is
+/// set. With `lfs.gitHooks`, also warn about staged files over the limit,
+/// refusing them when `blockLargeCommits` is set.
+fn pre_commit(settings: &AppSettings) -> Result<i32, String> {
+    let repo = Repository::open_from_env().map_err(|e| e.to_string())?;
+    if settings.block_secret_commits {
+        let findings = staged_secrets(&repo).map_err(|e| e.to_string())?;
+        if !findings.is_empty() {
+            eprintln!(
+                "Commit refused: the staged changes add what look like credentials:\n{}",
+                describe_findings(&findings)
+            );
+            return Ok(1);
+        }
+    }
+    if !settings.lfs.git_hooks {
+        return Ok(0);
+    }
+    let found = staged_large_objects(&repo, None, settings.lfs.threshold_bytes())
+        .map_err(|e| e.to_string())?;
+    if found.is_empty() {
+        return Ok(0);
+    }
+    if settings.lfs.block_large_commits {
+        eprintln!(
+            "{}\n{}",
+            large_commit_error(&settings.lfs, &found),
+            describe(&found)
+        );
+        return Ok(1);
+    }
+    eprintln!(
+        "Warning: staged files exceed {} MB and will be committed as plain git objects:\n{}",
+        settings.lfs.large_file_mb,
+        describe(&found)
+    );
+    Ok(0)
+}
+
+/// Warn about large objects in the commits being pushed. git passes one
+/// `<local ref> <local sha> <remote ref> <remote sha>` line per ref.
+fn pre_push(settings: &AppSettings, refs: impl BufRead) -> Result<i32, String> {
+    if !settings.lfs.git_hooks {
+        return Ok(0);
+    }
+    let repo = Repository::open_from_env().map_err(|e| e.to_string())?;
+    let tips: Vec<Oid> = refs
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| {
+            let sha = line.split_whitespace().nth(1)?;
+            Oid::from_str(sha).ok().filter(|oid| !oid.is_zero())
+        })
+        .collect();
+    if tips.is_empty() {
+        return Ok(0);
+    }
+    let found = pushed_large_objects(&repo, None, settings.lfs.threshold_bytes(), &tips)
+        .map_err(|e| e.to_string())?;
+    if !found.is_empty() {
+        eprintln!(
+            "Warning: this push uploads objects over {} MB as plain git objects:\n{}",
+            settings.lfs.large_file_mb,
+            describe(&found)
+        );
+    }
+    Ok(0)
+}
+
+/// Run a hook for `--git-hook <name> --settings <path>`. Returns its exit
+/// code, or `None` when `args` are not a hook invocation. A check that
+/// fails to run never blocks the commit or push.
+pub(crate) fn run_hook(args: &[String]) -> Option<i32> {
+    let [flag, hook, settings_flag, settings_path, ..] = args else {
+        return None;
+    };
+    if flag != "--git-hook" || settings_flag != "--settings" {
+        return None;
+    }
+    let settings = read_settings(&PathBuf::from(settings_path)).unwrap_or_default();
+    let result = match hook.as_str() {
+        "pre-commit" => pre_commit(&settings),
+        "pre-push" => pre_push(&settings, std::io::stdin().lock()),
+        _ => Ok(0),
+    };
+    Some(result.unwrap_or_else(|err| {
+        eprintln!("Claude Monitor {hook} check failed: {err}");
+        0
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hook_script, is_managed, run_hook, sync_repo, HOOK_MARKER};
+    use crate::utils::TempDir;
+    use std::path::Path;
+
+    #[test]
+    fn hook_scripts_quote_paths_and_forward_arguments() {
+        let script = hook_script(
+            Path::new("/Applications/Claude's Monitor.app/monitor"),
+            Path::new("/data/settings.json"),
+            "pre-push",
+        );
+        assert!(script.starts_with(&format!("#!/bin/sh\n{HOOK_MARKER}\n")));
+        assert!(script.contains("exec '/Applications/Claude'\\''s Monitor.app/monitor'"));
+        assert!(script.ends_with("--git-hook pre-push --settings '/data/settings.json' \"$@\"\n"));
+    }
+
+    #[test]
+    fn leaves_hooks_it_did_not_write() {
+        let root = TempDir::new("git-hooks");
+        git2::Repository::init(&root).unwrap();
+        let hooks = root.join(".git").join("hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        std::fs::write(hooks.join("pre-push"), "#!/bin/sh\nmake lint\n").unwrap();
+
+        let path = root.to_string_lossy().to_string();
+        let (exe, settings) = (Path::new("/bin/monitor"), Path::new("/data/settings.json"));
+        sync_repo(&path, Some((exe, settings))).unwrap();
+        assert!(is_managed(&hooks.join("pre-commit")));
+        assert!(!is_managed(&hooks.join("pre-push")));
+
+        sync_repo(&path, None).unwrap();
+        assert!(!hooks.join("pre-commit").exists());
+        assert!(hooks.join("pre-push").exists());
+    }
+
+    #[test]
+    fn only_hook_arguments_are_handled() {
+        assert_eq!(run_hook(&["--flag".to_string()]), None);
+        let args = [
+            "--git-hook",
+            "post-merge",
+            "--settings",
+            "/missing/settings.json",
+        ]
+        .map(String::from);
+        assert_eq!(run_hook(&args), Some(0));
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock};
+
+/// Per-workspace locks over git operations. Reads (status, diffs, log)
+/// share a workspace's lock; writes (checkout, branch and worktree changes)
+/// take it alone. Tokio's `RwLock` is fair, so a queued write is not
+/// starved by status polling and reads that arrive after it wait for it.
+#[derive(Default)]
+pub(crate) struct GitLocks {
+    locks: Mutex<HashMap<String, Arc<RwLock<()>>>>,
+}
+
+impl GitLocks {
+    fn lock_for(&self, workspace_id: &str) -> Arc<RwLock<()>> {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        locks.entry(workspace_id.to_string()).or_default().clone()
+    }
+
+    pub(crate) async fn read(&self, workspace_id: &str) -> OwnedRwLockReadGuard<()> {
+        self.lock_for(workspace_id).read_owned().await
+    }
+
+    pub(crate) async fn write(&self, workspace_id: &str) -> OwnedRwLockWriteGuard<()> {
+        self.lock_for(workspace_id).write_owned().await
+    }
+
+    /// Drop the lock of a removed workspace.
+    pub(crate) fn forget(&self, workspace_id: &str) {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        locks.remove(workspace_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GitLocks;
+
+    #[test]
+    fn reads_share_and_writes_exclude() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let locks = GitLocks::default();
+            let first = locks.read("a").await;
+            let second = locks.read("a").await;
+            assert!(locks.lock_for("a").try_write().is_err());
+            drop((first, second));
+
+            let write = locks.write("a").await;
+            assert!(locks.lock_for("a").try_read().is_err());
+            // Other workspaces are unaffected.
+            assert!(locks.lock_for("b").try_write().is_ok());
+            drop(write);
+            assert!(locks.lock_for("a").try_read().is_ok());
+        });
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::registry::now_millis;
+use crate::state::AppState;
+
+/// Context blocks longer than this drop the remaining terms.
+const MAX_CONTEXT_CHARS: usize = 4000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GlossaryTerm {
+    pub(crate) id: String,
+    pub(crate) term: String,
+    pub(crate) definition: String,
+    pub(crate) updated_at: u64,
+}
+
+/// A workspace's domain terms. Enabled glossaries are appended to the
+/// system prompt of every session the workspace starts.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceGlossary {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) terms: Vec<GlossaryTerm>,
+}
+
+impl WorkspaceGlossary {
+    /// The terms as a compact system prompt block, or `None` when disabled
+    /// or empty.
+    fn context(&self) -> Option<String> {
+        if !self.enabled || self.terms.is_empty() {
+            return None;
+        }
+        let mut terms: Vec<&GlossaryTerm> = self.terms.iter().collect();
+        terms.sort_by_key(|term| term.term.to_lowercase());
+        let mut block =
+            "Project glossary. These terms have the following meanings in this codebase:"
+                .to_string();
+        for term in terms {
+            let line = format!(
+                "\n- {}: {}",
+                term.term,
+                term.definition
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+            if block.len() + line.len() > MAX_CONTEXT_CHARS {
+                break;
+            }
+            block.push_str(&line);
+        }
+        Some(block)
+    }
+}
+
+/// Glossaries by workspace id, kept in `glossary.json`.
+#[derive(Debug, Default)]
+pub(crate) struct Glossaries {
+    workspaces: HashMap<String, WorkspaceGlossary>,
+}
+
+impl Glossaries {
+    pub(crate) fn load(path: &Path) -> Self {
+        let workspaces = match std::fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|err| {
+                log_line!("Ignoring {}: {err}", path.display());
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self { workspaces }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(&self.workspaces).map_err(|e| e.to_string())?;
+        std::fs::write(path, data).map_err(|e| e.to_string())
+    }
+
+    /// Add a term, or replace the definition of the one with the same
+    /// `id` or, without an id, the same name.
+    fn upsert(
+        &mut self,
+        workspace_id: &str,
+        id: Option<&str>,
+        term: &str,
+        definition: &str,
+        now: u64,
+    ) -> Result<GlossaryTerm, String> {
+        let term = term.trim();
+        let definition = definition.trim();
+        if term.is_empty() || definition.is_empty() {
+            return Err("A glossary term needs a name and a definition".to_string());
+        }
+        let terms = &mut self
+            .workspaces
+            .entry(workspace_id.to_string())
+            .or_default()
+            .terms;
+        if terms.iter().any(|existing| {
+            existing.term.eq_ignore_ascii_case(term) && id.is_some_and(|id| existing.id != id)
+        }) {
+            return Err(format!("{term} is already in the glossary"));
+        }
+        let existing = terms.iter_mut().find(|existing| match id {
+            Some(id) => existing.id == id,
+            None => existing.term.eq_ignore_ascii_case(term),
+        });
+        match existing {
+            Some(entry) => {
+                entry.term = term.to_string();
+                entry.definition = definition.to_string();
+                entry.updated_at = now;
+                Ok(entry.clone())
+            }
+            None if id.is_some() => Err(format!("Glossary term {term} not found")),
+            None => {
+                let entry = GlossaryTerm {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    term: term.to_string(),
+                    definition: definition.to_string(),
+                    updated_at: now,
+                };
+                terms.push(entry.clone());
+                Ok(entry)
+            }
+        }
+    }
+
+    fn remove(&mut self, workspace_id: &str, id: &str) -> Result<(), String> {
+        let terms = &mut self
+            .workspaces
+            .get_mut(workspace_id)
+            .ok_or_else(|| format!("Glossary term {id} not found"))?
+            .terms;
+        let before = terms.len();
+        terms.retain(|term| term.id != id);
+        if terms.len() == before {
+            return Err(format!("Glossary term {id} not found"));
+        }
+        Ok(())
+    }
+}
+
+fn glossary_path(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("glossary.json")
+}
+
+/// System prompt addition for a session in `workspace_id`. Worktrees
+/// without a glossary of their own use their parent's.
+pub(crate) async fn glossary_context(state: &AppState, workspace_id: &str) -> Option<String> {
+    let parent_id = state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .and_then(|entry| entry.parent_id.clone());
+    let glossaries = state.glossaries.lock().await;
+    glossaries
+        .workspaces
+        .get(workspace_id)
+        .filter(|glossary| !glossary.terms.is_empty())
+        .or_else(|| glossaries.workspaces.get(parent_id.as_deref()?))
+        .and_then(WorkspaceGlossary::context)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_glossary(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorkspaceGlossary, String> {
+    let mut glossary = state
+        .glossaries
+        .lock()
+        .await
+        .workspaces
+        .get(&workspace_id)
+        .cloned()
+        .unwrap_or_default();
+    glossary.terms.sort_by_key(|term| term.term.to_lowercase());
+    Ok(glossary)
+}
+
+/// Add a term, or update the term with `id`.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn upsert_glossary_term(
+    workspace_id: String,
+    term: String,
+    definition: String,
+    id: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<GlossaryTerm, String> {
+    let mut glossaries = state.glossaries.lock().await;
+    let entry = glossaries.upsert(
+        &workspace_id,
+        id.as_deref(),
+        &term,
+        &definition,
+        now_millis(),
+    )?;
+    glossaries.save(&glossary_path(&state))?;
+    Ok(entry)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn delete_glossary_term(
+    workspace_id: String,
+    id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let mut glossaries = state.glossaries.lock().await;
+    glossaries.remove(&workspace_id, &id)?;
+    glossaries.save(&glossary_path(&state))
+}
+
+/// Turn appending the glossary to new sessions on or off.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_glossary_enabled(
+    workspace_id: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let mut glossaries = state.glossaries.lock().await;
+    glossaries
+        .workspaces
+        .entry(workspace_id)
+        .or_default()
+        .enabled = enabled;
+    glossaries.save(&glossary_path(&state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Glossaries;
+
+    #[test]
+    fn builds_context_from_enabled_glossaries() {
+        let mut glossaries = Glossaries::default();
+        let lane = glossaries
+            .upsert("ws", None, "Lane", "A queue of turns", 1)
+            .unwrap();
+        glossaries
+            .upsert("ws", None, "bridge", "The Node\n  sidecar process", 2)
+            .unwrap();
+        assert!(glossaries.upsert("ws", None, " ", "blank", 3).is_err());
+        assert!(glossaries
+            .upsert("ws", Some("other"), "lane", "duplicate", 3)
+            .is_err());
+        // Re-adding a term by name updates it.
+        let updated = glossaries
+            .upsert("ws", None, "lane", "A priority queue of turns", 4)
+            .unwrap();
+        assert_eq!(updated.id, lane.id);
+
+        let glossary = glossaries.workspaces.get_mut("ws").unwrap();
+        assert_eq!(glossary.terms.len(), 2);
+        assert_eq!(glossary.context(), None);
+        glossary.enabled = true;
+        assert_eq!(
+            glossary.context().unwrap(),
+            "Project glossary. These terms have the following meanings in this codebase:\
+             \n- bridge: The Node sidecar process\n- lane: A priority queue of turns"
+        );
+
+        glossaries.remove("ws", &lane.id).unwrap();
+        assert!(glossaries.remove("ws", &lane.id).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::claude::{emit_scoped, ClaudeEvent};
+use crate::events::ClaudeEventPayload;
+use crate::lan_share::broadcast;
+use crate::state::AppState;
+
+/// Turns started by automation (workflows, retries) since the user last
+/// sent a message, by session id.
+#[derive(Debug, Default)]
+pub(crate) struct AutonomousTurns {
+    counts: HashMap<String, u32>,
+}
+
+impl AutonomousTurns {
+    /// The user sent a message; automation may run `limit` more turns.
+    pub(crate) fn user_input(&mut self, session_id: &str) {
+        self.counts.remove(session_id);
+    }
+
+    /// Count an automated turn. Returns the turns already run if `limit`
+    /// (0 for no limit) would be exceeded, without counting it.
+    fn start(&mut self, session_id: &str, limit: u32) -> Result<(), u32> {
+        let count = self.counts.entry(session_id.to_string()).or_default();
+        if limit > 0 && *count >= limit {
+            return Err(*count);
+        }
+        *count += 1;
+        Ok(())
+    }
+
+    pub(crate) fn closed(&mut self, session_id: &str) {
+        self.counts.remove(session_id);
+    }
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GuardrailTriggeredPayload {
+    /// `maxAutonomousTurns` at the time.
+    pub(crate) limit: u32,
+    /// What tried to start the turn, e.g. "workflow".
+    pub(crate) source: String,
+}
+
+/// Check the autonomous turn cap before automation starts a turn. When the
+/// cap is hit, emits `guardrail/triggered` and returns an error; the count
+/// resets once the user sends a message themselves.
+pub(crate) async fn start_autonomous_turn(
+    app_handle: &AppHandle,
+    session_id: &str,
+    source: &str,
+) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let limit = state.app_settings.lock().await.max_autonomous_turns;
+    let Err(turns) = state
+        .autonomous_turns
+        .lock()
+        .await
+        .start(session_id, limit)
+    else {
+        return Ok(());
+    };
+    let workspace_id = state
+        .claude_sessions
+        .lock()
+        .await
+        .get(session_id)
+        .map(|info| info.workspace_id.clone())
+        .unwrap_or_default();
+    let event = ClaudeEvent {
+        event_type: "guardrail/triggered".to_string(),
+        session_id: session_id.to_string(),
+        workspace_id: workspace_id.clone(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        payload: ClaudeEventPayload::GuardrailTriggered(GuardrailTriggeredPayload {
+            limit,
+            source: source.to_string(),
+        }),
+    };
+    broadcast(app_handle, &event);
+    emit_scoped(app_handle, "claude-event", &workspace_id, event);
+    Err(format!(
+        "Stopped after {turns} turns without user input (maxAutonomousTurns)"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AutonomousTurns;
+
+    #[test]
+    fn caps_turns_until_user_input() {
+        let mut turns = AutonomousTurns::default();
+        assert_eq!(turns.start("s1", 2), Ok(()));
+        assert_eq!(turns.start("s1", 2), Ok(()));
+        assert_eq!(turns.start("s1", 2), Err(2));
+        assert_eq!(turns.start("s2", 2), Ok(()));
+
+        turns.user_input("s1");
+        assert_eq!(turns.start("s1", 2), Ok(()));
+
+        for _ in 0..100 {
+            assert_eq!(turns.start("s3", 0), Ok(()));
+        }
+    }
+}
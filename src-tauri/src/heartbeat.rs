@@ -0,0 +1,240 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+use tokio::time::sleep;
+
+use crate::claude::{emit_scoped, ClaudeBridge, ClaudeEvent};
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::events::ClaudeEventPayload;
+use crate::registry::now_millis;
+use crate::state::AppState;
+
+const PING_INTERVAL: Duration = Duration::from_secs(15);
+/// A pong slower than this counts as missed.
+const PONG_TIMEOUT: Duration = Duration::from_secs(5);
+/// Missed pongs in a row before the bridge is restarted.
+const MAX_MISSED_PONGS: u32 = 3;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum BridgeHealthStatus {
+    #[default]
+    Healthy,
+    /// At least one pong was missed.
+    Degraded,
+    /// Too many pongs were missed; the bridge is being replaced.
+    Restarting,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BridgeHealth {
+    pub(crate) status: BridgeHealthStatus,
+    /// Round trip of the last answered ping.
+    pub(crate) latency_ms: Option<u64>,
+    pub(crate) missed_pongs: u32,
+    pub(crate) last_pong_at: Option<u64>,
+}
+
+impl BridgeHealth {
+    fn pong(&mut self, latency: Duration, now: u64) {
+        self.status = BridgeHealthStatus::Healthy;
+        self.latency_ms = Some(latency.as_millis() as u64);
+        self.missed_pongs = 0;
+        self.last_pong_at = Some(now);
+    }
+
+    /// Count a missed pong. Returns true once the bridge should be restarted.
+    fn missed(&mut self) -> bool {
+        self.missed_pongs += 1;
+        if self.missed_pongs >= MAX_MISSED_PONGS {
+            self.status = BridgeHealthStatus::Restarting;
+            true
+        } else {
+            self.status = BridgeHealthStatus::Degraded;
+            false
+        }
+    }
+}
+
+/// Ping the bridge and time the answer.
+async fn ping(bridge: &ClaudeBridge) -> Result<Duration, String> {
+    let started = Instant::now();
+    let response = bridge
+        .send_request_with_timeout("ping", json!({}), Some(PONG_TIMEOUT))
+        .await?;
+    if let Some(error) = response.get("error") {
+        return Err(error.to_string());
+    }
+    Ok(started.elapsed())
+}
+
+fn emit_health(app: &AppHandle, health: &BridgeHealth) {
+    let event = ClaudeEvent {
+        event_type: "bridge/health".to_string(),
+        session_id: String::new(),
+        workspace_id: String::new(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        payload: ClaudeEventPayload::BridgeHealth(health.clone()),
+    };
+    emit_scoped(app, "claude-event", "", event);
+}
+
+/// Whether `bridge` is still the one in app state.
+async fn is_current(state: &AppState, bridge: &Arc<ClaudeBridge>) -> bool {
+    state
+        .claude_bridge
+        .lock()
+        .await
+        .as_ref()
+        .is_some_and(|current| Arc::ptr_eq(current, bridge))
+}
+
+/// Kill an unresponsive bridge. Its stdout reader then sees it exit, and it
+/// is replaced by the same supervisor as a bridge that exited on its own:
+/// its turns fail, `bridge/died` is emitted and its sessions are resumed on
+/// the new bridge.
+async fn restart_bridge(app: &AppHandle, bridge: &Arc<ClaudeBridge>) {
+    let state = app.state::<AppState>();
+    if !is_current(&state, bridge).await {
+        return;
+    }
+    if let Err(err) = bridge.kill().await {
+        log_line!("Failed to kill unresponsive Claude bridge: {err}");
+    }
+}
+
+/// Ping the running bridge now, outside the heartbeat schedule, and restart
+/// it right away if it doesn't answer. Returns whether it answered, or
+/// `None` when no bridge is running.
+pub(crate) async fn check_bridge(app: &AppHandle) -> Option<bool> {
+    let state = app.state::<AppState>();
+    let bridge = state.claude_bridge.lock().await.clone()?;
+    let result = ping(&bridge).await;
+    let ok = result.is_ok();
+    let (health, changed) = {
+        let mut health = state.bridge_health.lock().await;
+        let before = health.status;
+        match result {
+            Ok(latency) => health.pong(latency, now_millis()),
+            Err(err) => {
+                log_line!("Claude bridge ping failed: {err}");
+                health.status = BridgeHealthStatus::Restarting;
+            }
+        }
+        (health.clone(), health.status != before)
+    };
+    if changed {
+        emit_health(app, &health);
+    }
+    if !ok {
+        restart_bridge(app, &bridge).await;
+    }
+    Some(ok)
+}
+
+/// Ping `bridge` until it is replaced, stopped or exits. Emits `bridge/health`
+/// when its status changes and restarts it after `MAX_MISSED_PONGS`
+/// missed pongs in a row.
+pub(crate) fn spawn_heartbeat(app: AppHandle, bridge: Arc<ClaudeBridge>) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        *state.bridge_health.lock().await = BridgeHealth::default();
+        loop {
+            sleep(PING_INTERVAL).await;
+            // A bridge that exited is restarted by its stdout reader.
+            if !bridge.is_alive() || !is_current(&state, &bridge).await {
+                return;
+            }
+            let result = ping(&bridge).await;
+            let (health, changed, restart) = {
+                let mut health = state.bridge_health.lock().await;
+                let before = health.status;
+                let restart = match result {
+                    Ok(latency) => {
+                        health.pong(latency, now_millis());
+                        false
+                    }
+                    Err(err) => {
+                        log_line!("Claude bridge ping failed: {err}");
+                        health.missed()
+                    }
+                };
+                (health.clone(), health.status != before, restart)
+            };
+            if changed {
+                emit_health(&app, &health);
+            }
+            if restart {
+                restart_bridge(&app, &bridge).await;
+                return;
+            }
+        }
+    });
+}
+
+/// Latest heartbeat result for the running bridge.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_bridge_health(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<BridgeHealth, String> {
+    Ok(state.bridge_health.lock().await.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{ping, BridgeHealth, BridgeHealthStatus, MAX_MISSED_PONGS};
+    use crate::mock_bridge::MockBridge;
+
+    fn run(test: impl Future<Output = ()>) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(test);
+    }
+
+    #[test]
+    fn restarts_after_missed_pongs() {
+        let mut health = BridgeHealth::default();
+        for _ in 1..MAX_MISSED_PONGS {
+            assert!(!health.missed());
+            assert_eq!(health.status, BridgeHealthStatus::Degraded);
+        }
+        assert!(health.missed());
+        assert_eq!(health.status, BridgeHealthStatus::Restarting);
+
+        health.pong(Duration::from_millis(12), 1_000);
+        assert_eq!(health.status, BridgeHealthStatus::Healthy);
+        assert_eq!(health.missed_pongs, 0);
+        assert_eq!(health.latency_ms, Some(12));
+    }
+
+    #[test]
+    fn mock_bridge_answers_pings() {
+        run(async {
+            let mock = MockBridge::connect();
+            mock.bridge
+                .send_request(
+                    "initialize",
+                    json!({ "clientInfo": { "name": "test", "version": "0" } }),
+                )
+                .await
+                .unwrap();
+            assert!(ping(&mock.bridge).await.is_ok());
+            mock.crash();
+            assert!(ping(&mock.bridge).await.is_err());
+        });
+    }
+}
@@ -0,0 +1,284 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet};
+use tauri::State;
+
+use crate::attachments::resolve_in_workspace;
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+
+/// Larger inputs are left for the webview to show unhighlighted.
+const MAX_HIGHLIGHT_BYTES: usize = 2 * 1024 * 1024;
+
+/// Prism token classes for TextMate scopes, so the webview's existing
+/// theme styles the output. Checked innermost scope first.
+const PRISM_CLASSES: &[(&str, &str)] = &[
+    ("constant.numeric", "number"),
+    ("constant.language", "boolean"),
+    ("constant.character", "char"),
+    ("constant", "constant"),
+    ("support.constant", "constant"),
+    ("keyword.operator", "operator"),
+    ("keyword", "keyword"),
+    ("storage", "keyword"),
+    ("entity.name.function", "function"),
+    ("support.function", "function"),
+    ("variable.function", "function"),
+    ("entity.name.type", "class-name"),
+    ("entity.name.class", "class-name"),
+    ("entity.other.inherited-class", "class-name"),
+    ("support.type", "class-name"),
+    ("support.class", "class-name"),
+    ("entity.name.tag", "tag"),
+    ("entity.other.attribute-name", "attr-name"),
+    ("markup.inserted", "inserted"),
+    ("markup.deleted", "deleted"),
+    ("variable", "variable"),
+    ("punctuation", "punctuation"),
+];
+
+/// Scopes that colour everything inside them, so the quotes of a string
+/// and the `//` of a comment share its token.
+const OUTER_CLASSES: &[(&str, &str)] = &[("comment", "comment"), ("string", "string")];
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn compile(classes: &[(&str, &'static str)]) -> Vec<(Scope, &'static str)> {
+    classes
+        .iter()
+        .filter_map(|(scope, class)| Some((Scope::new(scope).ok()?, *class)))
+        .collect()
+}
+
+fn prism_class(stack: &[Scope]) -> Option<&'static str> {
+    static OUTER: OnceLock<Vec<(Scope, &'static str)>> = OnceLock::new();
+    static INNER: OnceLock<Vec<(Scope, &'static str)>> = OnceLock::new();
+    let class_of = |classes: &[(Scope, &'static str)], inner: &Scope| {
+        classes
+            .iter()
+            .find(|(scope, _)| scope.is_prefix_of(*inner))
+            .map(|(_, class)| *class)
+    };
+    let outer = OUTER.get_or_init(|| compile(OUTER_CLASSES));
+    let inner = INNER.get_or_init(|| compile(PRISM_CLASSES));
+    stack
+        .iter()
+        .find_map(|scope| class_of(outer, scope))
+        .or_else(|| stack.iter().rev().find_map(|scope| class_of(inner, scope)))
+}
+
+fn syntax_for_path(path: &str) -> Option<&'static SyntaxReference> {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    let token = match extension.as_deref() {
+        // syntect ships no TypeScript grammar; JavaScript's covers most of it.
+        Some("ts" | "tsx" | "mts" | "cts") => "js",
+        Some(extension) => extension,
+        None => name,
+    };
+    syntax_set().find_syntax_by_extension(token)
+}
+
+/// Parser and scope state carried from one line to the next.
+#[derive(Clone)]
+struct LineState {
+    parser: ParseState,
+    scopes: ScopeStack,
+}
+
+impl LineState {
+    fn new(syntax: &SyntaxReference) -> Self {
+        Self {
+            parser: ParseState::new(syntax),
+            scopes: ScopeStack::new(),
+        }
+    }
+}
+
+fn push_escaped(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+fn escaped(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    push_escaped(&mut out, text);
+    out
+}
+
+fn push_token(out: &mut String, class: Option<&str>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let Some(class) = class else {
+        push_escaped(out, text);
+        return;
+    };
+    out.push_str("<span class=\"token ");
+    out.push_str(class);
+    out.push_str("\">");
+    push_escaped(out, text);
+    out.push_str("</span>");
+}
+
+/// HTML for one line, merging neighbouring text with the same class into
+/// one token. A line the grammar fails on is returned escaped.
+fn highlight_line(line: &str, state: &mut LineState) -> String {
+    let Ok(ops) = state.parser.parse_line(&format!("{line}\n"), syntax_set()) else {
+        return escaped(line);
+    };
+    let mut out = String::with_capacity(line.len() * 2);
+    let mut class = prism_class(state.scopes.as_slice());
+    let mut start = 0;
+    for (index, op) in ops {
+        let index = index.min(line.len());
+        if state.scopes.apply(&op).is_err() {
+            continue;
+        }
+        let next = prism_class(state.scopes.as_slice());
+        if next != class && index > start {
+            push_token(&mut out, class, &line[start..index]);
+            start = index;
+        }
+        class = next;
+    }
+    push_token(&mut out, class, &line[start..]);
+    out
+}
+
+/// One HTML string per line of `diff`, aligned with `diff.split('\n')`.
+/// Content lines exclude their `+`/`-`/space prefix; other lines are
+/// escaped as-is. Removed and added lines are lexed as separate streams so
+/// a comment opened on one side does not bleed into the other.
+pub(crate) fn highlight_diff(path: &str, diff: &str) -> Option<Vec<String>> {
+    let syntax = syntax_for_path(path)?;
+    if diff.len() > MAX_HIGHLIGHT_BYTES {
+        return None;
+    }
+    let mut old_state = LineState::new(syntax);
+    let mut new_state = LineState::new(syntax);
+    let mut in_hunk = false;
+    let mut lines = Vec::new();
+    for line in diff.split('\n') {
+        if line.starts_with("@@") {
+            old_state = LineState::new(syntax);
+            new_state = LineState::new(syntax);
+            in_hunk = true;
+            lines.push(escaped(line));
+            continue;
+        }
+        if !in_hunk {
+            lines.push(escaped(line));
+            continue;
+        }
+        let html = match line.chars().next() {
+            Some('+') => highlight_line(&line[1..], &mut new_state),
+            Some('-') => highlight_line(&line[1..], &mut old_state),
+            Some(' ') => {
+                let html = highlight_line(&line[1..], &mut new_state);
+                old_state = new_state.clone();
+                html
+            }
+            _ => escaped(line),
+        };
+        lines.push(html);
+    }
+    Some(lines)
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HighlightedFile {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    /// HTML per line; plain escaped text when the language is unknown.
+    lines: Vec<String>,
+}
+
+/// Highlighted lines of a workspace file for previews.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn highlight_file(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<HighlightedFile, String> {
+    let root = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(|entry| PathBuf::from(&entry.path))
+        .ok_or("workspace not found")?;
+    let (resolved, relative) = resolve_in_workspace(&root, &path)?;
+    let content = std::fs::read_to_string(&resolved).map_err(|e| e.to_string())?;
+    if content.len() > MAX_HIGHLIGHT_BYTES {
+        return Err(format!(
+            "{relative} is over {} MB and is not highlighted",
+            MAX_HIGHLIGHT_BYTES / (1024 * 1024)
+        ));
+    }
+    let syntax = syntax_for_path(&relative);
+    let lines: Vec<String> = tokio::task::spawn_blocking(move || match syntax {
+        Some(syntax) => {
+            let mut line_state = LineState::new(syntax);
+            content
+                .lines()
+                .map(|line| highlight_line(line, &mut line_state))
+                .collect()
+        }
+        None => content.lines().map(escaped).collect(),
+    })
+    .await
+    .map_err(|_| "highlighting failed".to_string())?;
+    Ok(HighlightedFile {
+        path: relative,
+        language: syntax.map(|syntax| syntax.name.to_lowercase()),
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{highlight_diff, highlight_line, syntax_for_path, LineState};
+
+    #[test]
+    fn highlights_tokens_and_carries_block_comments() {
+        let mut state = LineState::new(syntax_for_path("src/lib.rs").unwrap());
+        let html = highlight_line("let x = \"a<b\"; // hi", &mut state);
+        assert!(html.starts_with("<span class=\"token keyword\">let</span>"));
+        assert!(html.contains("<span class=\"token string\">\"a&lt;b\"</span>"));
+        assert!(html.ends_with("<span class=\"token comment\">// hi</span>"));
+
+        highlight_line("/* open", &mut state);
+        let html = highlight_line("still */ 1", &mut state);
+        assert!(html.starts_with("<span class=\"token comment\">still */</span>"));
+        assert!(html.ends_with("<span class=\"token number\">1</span>"));
+    }
+
+    #[test]
+    fn aligns_diff_lines_and_skips_unknown_languages() {
+        let diff = "diff --git a/x.rs b/x.rs\n@@ -1 +1 @@\n-fn a() {}\n+fn b() {}\n";
+        let lines = highlight_diff("src/x.rs", diff).unwrap();
+        assert_eq!(lines.len(), diff.split('\n').count());
+        assert_eq!(lines[0], "diff --git a/x.rs b/x.rs");
+        assert!(lines[3].starts_with("<span class=\"token keyword\">fn</span>"));
+        assert!(highlight_diff("notes.unknown", diff).is_none());
+        assert!(syntax_for_path("src/App.tsx").is_some());
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::State;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::claude_settings::{read_settings_object, settings_path};
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::state::AppState;
+
+/// Hook events Claude Code dispatches.
+const HOOK_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+/// Events whose entries are filtered by a tool-name `matcher`.
+const TOOL_EVENTS: &[&str] = &["PreToolUse", "PostToolUse"];
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+pub(crate) struct HookCommand {
+    #[serde(rename = "type")]
+    pub(crate) kind: String,
+    pub(crate) command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+pub(crate) struct HookMatcher {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) matcher: Option<String>,
+    pub(crate) hooks: Vec<HookCommand>,
+}
+
+/// Event name -> matcher entries, as stored under `hooks` in settings.json.
+pub(crate) type HooksMap = BTreeMap<String, Vec<HookMatcher>>;
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HooksConfig {
+    scope: String,
+    path: String,
+    exists: bool,
+    hooks: HooksMap,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HookTemplate {
+    id: String,
+    name: String,
+    description: String,
+    event: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matcher: Option<String>,
+    command: String,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HookTestResult {
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    /// Exit code 2 blocks the tool call (PreToolUse) or prompt.
+    blocked: bool,
+    timed_out: bool,
+    duration_ms: u64,
+    /// The simulated payload sent on stdin.
+    payload: Value,
+}
+
+/// The confirmation target for saving `hooks`: their commands, one per
+/// line, in event and matcher order.
+pub(crate) fn hooks_target(hooks: &HooksMap) -> String {
+    hooks
+        .values()
+        .flatten()
+        .flat_map(|matcher| &matcher.hooks)
+        .map(|hook| hook.command.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Errors that would make Claude Code ignore or misread the hooks.
+pub(crate) fn validate_hooks(hooks: &HooksMap) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (event, matchers) in hooks {
+        if !HOOK_EVENTS.contains(&event.as_str()) {
+            errors.push(format!("Unknown hook event: {event}"));
+            continue;
+        }
+        for (index, entry) in matchers.iter().enumerate() {
+            let label = format!("{event}[{index}]");
+            if entry.matcher.is_some() && !TOOL_EVENTS.contains(&event.as_str()) {
+                errors.push(format!("{label}: matcher only applies to tool events"));
+            }
+            if entry.hooks.is_empty() {
+                errors.push(format!("{label}: no hooks defined"));
+            }
+            for hook in &entry.hooks {
+                if hook.kind != "command" {
+                    errors.push(format!("{label}: unsupported hook type \"{}\"", hook.kind));
+                }
+                if hook.command.trim().is_empty() {
+                    errors.push(format!("{label}: empty command"));
+                }
+                if hook.timeout == Some(0) {
+                    errors.push(format!("{label}: timeout must be positive"));
+                }
+            }
+        }
+    }
+    errors
+}
+
+fn templates() -> Vec<HookTemplate> {
+    let template = |id: &str,
+                    name: &str,
+                    description: &str,
+                    event: &str,
+                    matcher: Option<&str>,
+                    command: &str| HookTemplate {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: description.to_string(),
+        event: event.to_string(),
+        matcher: matcher.map(str::to_string),
+        command: command.to_string(),
+    };
+    vec![
+        template(
+            "block-rm-rf",
+            "Block rm -rf",
+            "Reject Bash commands that recursively force-delete.",
+            "PreToolUse",
+            Some("Bash"),
+            "jq -r '.tool_input.command' | grep -Eq 'rm[[:space:]]+-[a-zA-Z]*(rf|fr)' && { echo 'Blocked: rm -rf is not allowed' >&2; exit 2; } || exit 0",
+        ),
+        template(
+            "format-after-edit",
+            "Format after edit",
+            "Run the project formatter on files Claude edits.",
+            "PostToolUse",
+            Some("Edit|MultiEdit|Write"),
+            "f=$(jq -r '.tool_input.file_path // empty'); case \"$f\" in *.rs) rustfmt \"$f\";; *.ts|*.tsx|*.js|*.jsx|*.json|*.css|*.md) npx --no-install prettier --write \"$f\";; esac; exit 0",
+        ),
+        template(
+            "protect-env",
+            "Protect .env files",
+            "Reject edits to .env files and secrets directories.",
+            "PreToolUse",
+            Some("Edit|MultiEdit|Write"),
+            "jq -r '.tool_input.file_path // empty' | grep -Eq '(^|/)(\\.env[^/]*|secrets/)' && { echo 'Blocked: protected file' >&2; exit 2; } || exit 0",
+        ),
+        template(
+            "notify-desktop",
+            "Desktop notification",
+            "Show a system notification when Claude needs input.",
+            "Notification",
+            None,
+            "msg=$(jq -r '.message'); osascript -e \"display notification \\\"$msg\\\" with title \\\"Claude\\\"\" 2>/dev/null || notify-send Claude \"$msg\" 2>/dev/null; exit 0",
+        ),
+    ]
+}
+
+/// Stdin payload Claude Code would send for `event`.
+fn simulated_payload(event: &str, cwd: &str, tool_name: Option<&str>, tool_input: Value) -> Value {
+    let mut payload = json!({
+        "session_id": "hook-dry-run",
+        "transcript_path": "",
+        "cwd": cwd,
+        "hook_event_name": event,
+    });
+    match event {
+        "PreToolUse" | "PostToolUse" => {
+            payload["tool_name"] = json!(tool_name.unwrap_or("Bash"));
+            payload["tool_input"] = tool_input;
+            if event == "PostToolUse" {
+                payload["tool_response"] = json!({ "success": true });
+            }
+        }
+        "UserPromptSubmit" => payload["prompt"] = tool_input,
+        "Notification" => payload["message"] = json!("Claude needs your permission to use Bash"),
+        "Stop" | "SubagentStop" => payload["stop_hook_active"] = json!(false),
+        "PreCompact" => payload["trigger"] = json!("manual"),
+        "SessionStart" => payload["source"] = json!("startup"),
+        "SessionEnd" => payload["reason"] = json!("other"),
+        _ => {}
+    }
+    payload
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_hooks(
+    workspace_id: Option<String>,
+    scope: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<HooksConfig, String> {
+    let path = settings_path(&state, workspace_id.as_deref(), &scope).await?;
+    let settings = read_settings_object(&path)?;
+    let hooks = match settings.get("hooks") {
+        Some(value) => serde_json::from_value(value.clone()).map_err(|e| e.to_string())?,
+        None => HooksMap::new(),
+    };
+    Ok(HooksConfig {
+        scope,
+        exists: path.exists(),
+        path: path.to_string_lossy().to_string(),
+        hooks,
+    })
+}
+
+/// Replace the `hooks` key, keeping every other setting in the file.
+/// Claude Code runs these commands unprompted, so saving needs a
+/// `hooks_target` confirmation.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn save_hooks(
+    workspace_id: Option<String>,
+    scope: String,
+    hooks: HooksMap,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<HooksConfig, String> {
+    consume_confirmation(
+        &state,
+        confirmation.as_deref(),
+        "save_hooks",
+        &hooks_target(&hooks),
+    )
+    .await?;
+    let errors = validate_hooks(&hooks);
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    let path = settings_path(&state, workspace_id.as_deref(), &scope).await?;
+    let mut settings = read_settings_object(&path)?;
+    let hooks: HooksMap = hooks
+        .into_iter()
+        .filter(|(_, matchers)| !matchers.is_empty())
+        .collect();
+    if hooks.is_empty() {
+        settings.remove("hooks");
+    } else {
+        settings.insert(
+            "hooks".to_string(),
+            serde_json::to_value(&hooks).map_err(|e| e.to_string())?,
+        );
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(&Value::Object(settings)).map_err(|e| e.to_string())?;
+    std::fs::write(&path, data).map_err(|e| e.to_string())?;
+    Ok(HooksConfig {
+        scope,
+        exists: true,
+        path: path.to_string_lossy().to_string(),
+        hooks,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) fn list_hook_templates(_timer: CommandTimer) -> Vec<HookTemplate> {
+    templates()
+}
+
+/// Run a hook command against a simulated event payload, the way Claude
+/// Code would, without a live session.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn test_hook(
+    workspace_id: String,
+    event: String,
+    command: String,
+    tool_name: Option<String>,
+    tool_input: Option<Value>,
+    timeout_secs: Option<u64>,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<HookTestResult, String> {
+    consume_confirmation(&state, confirmation.as_deref(), "run_hook", &command).await?;
+    if !HOOK_EVENTS.contains(&event.as_str()) {
+        return Err(format!("Unknown hook event: {event}"));
+    }
+    let cwd = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .path
+            .clone()
+    };
+    let payload = simulated_payload(
+        &event,
+        &cwd,
+        tool_name.as_deref(),
+        tool_input.unwrap_or_else(|| json!({ "command": "echo hello" })),
+    );
+
+    let started = Instant::now();
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .current_dir(&cwd)
+        .env("CLAUDE_PROJECT_DIR", &cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run hook: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let data = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+        // A hook may exit without reading stdin; that is not an error.
+        let _ = stdin.write_all(&data).await;
+    }
+
+    let limit = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS));
+    let (output, timed_out) = match timeout(limit, child.wait_with_output()).await {
+        Ok(result) => (Some(result.map_err(|e| e.to_string())?), false),
+        Err(_) => (None, true),
+    };
+    let exit_code = output.as_ref().and_then(|output| output.status.code());
+    Ok(HookTestResult {
+        exit_code,
+        stdout: output
+            .as_ref()
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default(),
+        stderr: output
+            .as_ref()
+            .map(|output| String::from_utf8_lossy(&output.stderr).to_string())
+            .unwrap_or_default(),
+        blocked: exit_code == Some(2),
+        timed_out,
+        duration_ms: started.elapsed().as_millis() as u64,
+        payload,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hooks_target, templates, validate_hooks, HooksMap};
+
+    #[test]
+    fn validates_hook_config() {
+        let hooks: HooksMap = serde_json::from_value(serde_json::json!({
+            "PreToolUse": [{ "matcher": "Bash", "hooks": [{ "type": "command", "command": "true" }] }],
+            "Stop": [{ "matcher": "Bash", "hooks": [{ "type": "command", "command": " " }] }],
+            "BeforeEverything": []
+        }))
+        .unwrap();
+        assert_eq!(
+            validate_hooks(&hooks),
+            vec![
+                "Unknown hook event: BeforeEverything",
+                "Stop[0]: matcher only applies to tool events",
+                "Stop[0]: empty command",
+            ]
+        );
+        assert_eq!(hooks_target(&hooks), "true\n ");
+    }
+
+    #[test]
+    fn templates_are_valid() {
+        let mut hooks = HooksMap::new();
+        for template in templates() {
+            hooks
+                .entry(template.event.clone())
+                .or_default()
+                .push(super::HookMatcher {
+                    matcher: template.matcher.clone(),
+                    hooks: vec![super::HookCommand {
+                        kind: "command".to_string(),
+                        command: template.command.clone(),
+                        timeout: None,
+                    }],
+                });
+        }
+        assert!(validate_hooks(&hooks).is_empty());
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+
+/// English text for backend-generated strings, by key. `{name}` marks an
+/// argument. Keys ending in `.one`/`.other` are plural forms for `tr_count`.
+///
+/// Translations live in `<data dir>/locales/<tag>.json` (e.g. `de.json` or
+/// `pt-BR.json`) as a flat key-to-text object; missing keys fall back to
+/// English.
+const MESSAGES: &[(&str, &str)] = &[
+    (
+        "bridge.notFound",
+        "{program} not found. Check the bridge runtime in settings.",
+    ),
+    (
+        "bridge.npxNotFound",
+        "npx not found. Please install Node.js 18+ and ensure it's on your PATH.",
+    ),
+    (
+        "bridge.spawnFailed",
+        "Failed to spawn Claude bridge: {error}",
+    ),
+    ("count.request.one", "1 request"),
+    ("count.request.other", "{count} requests"),
+    ("count.session.one", "1 session"),
+    ("count.session.other", "{count} sessions"),
+    ("count.workspace.one", "1 workspace"),
+    ("count.workspace.other", "{count} workspaces"),
+    ("digest.empty", "No agent sessions."),
+    ("digest.summary", "{sessions} across {workspaces}"),
+    ("digest.title", "Agent activity for {date}"),
+    ("digest.transcriptMissing", "(transcript missing)"),
+    ("digest.uncommitted", "Uncommitted changes:"),
+];
+
+/// Where timestamps are shown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Zone {
+    Local,
+    Utc,
+    Fixed(FixedOffset),
+}
+
+#[derive(Debug)]
+struct Locale {
+    /// BCP 47 tag, e.g. `en-US`; empty for ISO formats.
+    tag: String,
+    zone: Zone,
+    messages: HashMap<String, String>,
+}
+
+fn current() -> &'static RwLock<Locale> {
+    static LOCALE: OnceLock<RwLock<Locale>> = OnceLock::new();
+    LOCALE.get_or_init(|| {
+        RwLock::new(Locale {
+            tag: String::new(),
+            zone: Zone::Local,
+            messages: HashMap::new(),
+        })
+    })
+}
+
+/// Parse the `timeZone` setting: `local` (or empty), `UTC`, or a fixed
+/// offset such as `+05:30`.
+pub(crate) fn parse_zone(value: Option<&str>) -> Result<Zone, String> {
+    let value = value.map(str::trim).unwrap_or_default();
+    if value.is_empty() || value.eq_ignore_ascii_case("local") {
+        return Ok(Zone::Local);
+    }
+    if value.eq_ignore_ascii_case("utc") || value == "Z" {
+        return Ok(Zone::Utc);
+    }
+    value.parse::<FixedOffset>().map(Zone::Fixed).map_err(|_| {
+        format!("Invalid time zone: {value}. Use local, UTC or an offset like +02:00.")
+    })
+}
+
+pub(crate) fn validate_locale(tag: &str) -> Result<(), String> {
+    let valid = tag.split('-').enumerate().all(|(index, part)| {
+        let len_ok = if index == 0 {
+            (2..=3).contains(&part.len())
+        } else {
+            (2..=8).contains(&part.len())
+        };
+        len_ok && part.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid locale: {tag}. Use a tag like en-US or de."
+        ))
+    }
+}
+
+/// Read the translations for `tag` from `locales_dir`, preferring an exact
+/// match (`pt-BR.json`) over the language (`pt.json`).
+fn load_messages(locales_dir: &Path, tag: &str) -> HashMap<String, String> {
+    let language = tag.split('-').next().unwrap_or_default();
+    for name in [tag, language] {
+        if name.is_empty() {
+            continue;
+        }
+        let path = locales_dir.join(format!("{name}.json"));
+        let Ok(data) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        match serde_json::from_str(&data) {
+            Ok(messages) => return messages,
+            Err(err) => log_line!("Ignoring {}: {err}", path.display()),
+        }
+    }
+    HashMap::new()
+}
+
+/// Follow the `locale` and `timeZone` settings. Invalid values fall back to
+/// ISO formats in local time.
+pub(crate) fn set_locale(tag: Option<&str>, zone: Option<&str>, locales_dir: &Path) {
+    let tag = tag
+        .map(str::trim)
+        .filter(|tag| validate_locale(tag).is_ok())
+        .unwrap_or_default()
+        .to_string();
+    let locale = Locale {
+        messages: load_messages(locales_dir, &tag),
+        zone: parse_zone(zone).unwrap_or(Zone::Local),
+        tag,
+    };
+    if let Ok(mut current) = current().write() {
+        *current = locale;
+    }
+}
+
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+fn lookup(messages: &HashMap<String, String>, key: &str) -> String {
+    messages
+        .get(key)
+        .map(String::as_str)
+        .or_else(|| {
+            MESSAGES
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, text)| *text)
+        })
+        .unwrap_or(key)
+        .to_string()
+}
+
+/// The text for `key` in the current locale, with `{name}` arguments filled.
+pub(crate) fn tr(key: &str, args: &[(&str, &str)]) -> String {
+    let template = match current().read() {
+        Ok(locale) => lookup(&locale.messages, key),
+        Err(_) => lookup(&HashMap::new(), key),
+    };
+    fill(&template, args)
+}
+
+/// `tr` for a counted noun: `<key>.one` for 1, `<key>.other` otherwise.
+pub(crate) fn tr_count(key: &str, count: usize) -> String {
+    let form = if count == 1 { "one" } else { "other" };
+    tr(&format!("{key}.{form}"), &[("count", &count.to_string())])
+}
+
+/// Date and time patterns for a locale; ISO 8601 when unknown.
+fn patterns(tag: &str) -> (&'static str, &'static str) {
+    let language = tag.split('-').next().unwrap_or_default();
+    match tag {
+        "en-US" | "en-PH" => return ("%m/%d/%Y", "%-I:%M %p"),
+        "en-CA" | "fr-CA" => return ("%Y-%m-%d", "%H:%M"),
+        _ => {}
+    }
+    match language {
+        "en" => ("%d/%m/%Y", "%H:%M"),
+        "fr" | "es" | "it" | "pt" | "el" | "vi" => ("%d/%m/%Y", "%H:%M"),
+        "de" | "ru" | "pl" | "tr" | "fi" | "nb" | "no" | "cs" | "uk" | "da" => {
+            ("%d.%m.%Y", "%H:%M")
+        }
+        "nl" => ("%d-%m-%Y", "%H:%M"),
+        "ja" | "zh" => ("%Y/%m/%d", "%H:%M"),
+        "ko" => ("%Y. %m. %d.", "%H:%M"),
+        "sv" | "lt" => ("%Y-%m-%d", "%H:%M"),
+        _ => ("%Y-%m-%d", "%H:%M"),
+    }
+}
+
+fn format_with(millis: i64, tag: &str, zone: Zone) -> String {
+    let Some(time) = DateTime::<Utc>::from_timestamp_millis(millis) else {
+        return String::new();
+    };
+    let (date, clock) = patterns(tag);
+    let pattern = format!("{date} {clock}");
+    match zone {
+        Zone::Local => time.with_timezone(&Local).format(&pattern).to_string(),
+        Zone::Utc => format!("{} UTC", time.format(&pattern)),
+        Zone::Fixed(offset) => format!("{} {offset}", time.with_timezone(&offset).format(&pattern)),
+    }
+}
+
+/// Format epoch millis as a date and time for people, in the configured
+/// locale and time zone. Machine-readable exports keep RFC 3339.
+pub(crate) fn format_millis(millis: i64) -> String {
+    match current().read() {
+        Ok(locale) => format_with(millis, &locale.tag, locale.zone),
+        Err(_) => format_with(millis, "", Zone::Local),
+    }
+}
+
+/// Format a calendar date in the configured locale.
+pub(crate) fn format_date(date: NaiveDate) -> String {
+    let tag = current()
+        .read()
+        .map(|locale| locale.tag.clone())
+        .unwrap_or_default();
+    date.format(patterns(&tag).0).to_string()
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocaleInfo {
+    locale: Option<String>,
+    /// Keys with a translation for the locale.
+    translated: usize,
+    messages: usize,
+}
+
+/// The locale in effect and how much of it is translated.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_locale_info(_timer: CommandTimer) -> Result<LocaleInfo, String> {
+    let locale = current().read().map_err(|e| e.to_string())?;
+    Ok(LocaleInfo {
+        locale: Some(locale.tag.clone()).filter(|tag| !tag.is_empty()),
+        translated: MESSAGES
+            .iter()
+            .filter(|(key, _)| locale.messages.contains_key(*key))
+            .count(),
+        messages: MESSAGES.len(),
+    })
+}
+
+/// Format epoch millis the way backend exports and digests do.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn format_timestamp(millis: i64, _timer: CommandTimer) -> Result<String, String> {
+    Ok(format_millis(millis))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::FixedOffset;
+
+    use super::{fill, format_with, lookup, parse_zone, validate_locale, Zone};
+
+    // 2026-03-04T17:05:00Z
+    const MILLIS: i64 = 1_772_643_900_000;
+
+    #[test]
+    fn formats_per_locale_and_zone() {
+        assert_eq!(format_with(MILLIS, "", Zone::Utc), "2026-03-04 17:05 UTC");
+        assert_eq!(
+            format_with(MILLIS, "en-US", Zone::Utc),
+            "03/04/2026 5:05 PM UTC"
+        );
+        assert_eq!(
+            format_with(MILLIS, "de-DE", Zone::Utc),
+            "04.03.2026 17:05 UTC"
+        );
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(
+            format_with(MILLIS, "en-GB", Zone::Fixed(offset)),
+            "04/03/2026 19:05 +02:00"
+        );
+    }
+
+    #[test]
+    fn parses_settings() {
+        assert_eq!(parse_zone(None), Ok(Zone::Local));
+        assert_eq!(parse_zone(Some("utc")), Ok(Zone::Utc));
+        assert!(matches!(parse_zone(Some("+05:30")), Ok(Zone::Fixed(_))));
+        assert!(parse_zone(Some("Mars/Olympus")).is_err());
+        assert!(validate_locale("pt-BR").is_ok());
+        assert!(validate_locale("zh-Hant-TW").is_ok());
+        assert!(validate_locale("english").is_err());
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        let messages = HashMap::from([(
+            "digest.title".to_string(),
+            "Agentenaktivität am {date}".to_string(),
+        )]);
+        assert_eq!(
+            fill(
+                &lookup(&messages, "digest.title"),
+                &[("date", "04.03.2026")]
+            ),
+            "Agentenaktivität am 04.03.2026"
+        );
+        assert_eq!(lookup(&messages, "digest.empty"), "No agent sessions.");
+        assert_eq!(lookup(&messages, "no.such.key"), "no.such.key");
+    }
+}
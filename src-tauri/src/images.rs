@@ -0,0 +1,237 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::State;
+use tokio::process::Command;
+
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+
+/// Longest edge the API uses without downscaling it itself.
+const MAX_EDGE: u32 = 1568;
+/// Raw size that stays under the API's 5 MB limit once base64-encoded.
+const MAX_IMAGE_BYTES: u64 = 3_750_000;
+/// Cached attachments older than this are removed on the next store.
+const CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// An image in the attachment cache. `path` goes straight into
+/// `claude_send_message`'s `images`.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImageAttachment {
+    path: String,
+    media_type: String,
+    bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+}
+
+fn attachment_cache_dir(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("attachments")
+}
+
+fn prune_cache(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > CACHE_TTL);
+        if expired {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Fresh path in the attachment cache, dropping expired entries first.
+pub(crate) fn new_attachment_path(state: &AppState, extension: &str) -> Result<PathBuf, String> {
+    let dir = attachment_cache_dir(state);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    prune_cache(&dir);
+    Ok(dir.join(format!("{}.{extension}", uuid::Uuid::new_v4())))
+}
+
+fn png_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if data.len() < 24 || !data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return None;
+    }
+    let width = u32::from_be_bytes(data[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(data[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+fn media_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some("image/jpeg")
+    } else {
+        None
+    }
+}
+
+async fn run_tool(program: &str, args: &[&str]) -> bool {
+    Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Downscale to `MAX_EDGE`, then fall back to JPEG if still too large, using
+/// `sips` on macOS and ImageMagick elsewhere when it is installed.
+async fn shrink(path: &Path) -> PathBuf {
+    let data = std::fs::read(path).unwrap_or_default();
+    let oversized = png_dimensions(&data).is_some_and(|(w, h)| w.max(h) > MAX_EDGE);
+    let file = path.to_string_lossy().to_string();
+    let edge = MAX_EDGE.to_string();
+    let geometry = format!("{MAX_EDGE}x{MAX_EDGE}>");
+    if oversized {
+        if cfg!(target_os = "macos") {
+            run_tool("sips", &["-Z", &edge, &file]).await;
+        } else if !run_tool("magick", &[&file, "-resize", &geometry, &file]).await {
+            run_tool("convert", &[&file, "-resize", &geometry, &file]).await;
+        }
+    }
+    let size = std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    if size <= MAX_IMAGE_BYTES {
+        return path.to_path_buf();
+    }
+    let jpeg = path.with_extension("jpg");
+    let out = jpeg.to_string_lossy().to_string();
+    let converted = if cfg!(target_os = "macos") {
+        let args: [&str; 9] = [
+            "-s",
+            "format",
+            "jpeg",
+            "-s",
+            "formatOptions",
+            "80",
+            &file,
+            "--out",
+            &out,
+        ];
+        run_tool("sips", &args).await
+    } else {
+        run_tool("magick", &[&file, "-quality", "80", &out]).await
+            || run_tool("convert", &[&file, "-quality", "80", &out]).await
+    };
+    if converted && jpeg.is_file() {
+        let _ = std::fs::remove_file(path);
+        jpeg
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Validate a freshly written image, shrink it for the API, and describe it.
+pub(crate) async fn finish_image(path: PathBuf) -> Result<ImageAttachment, String> {
+    let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+    if media_type(&data).is_none() {
+        let _ = std::fs::remove_file(&path);
+        return Err("Not a PNG or JPEG image".to_string());
+    }
+    let path = shrink(&path).await;
+    let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+    if data.len() as u64 > MAX_IMAGE_BYTES {
+        let _ = std::fs::remove_file(&path);
+        return Err(format!(
+            "Image is {} KB, over the {} KB limit, and could not be compressed",
+            data.len() / 1024,
+            MAX_IMAGE_BYTES / 1024
+        ));
+    }
+    let dimensions = png_dimensions(&data);
+    Ok(ImageAttachment {
+        path: path.to_string_lossy().to_string(),
+        media_type: media_type(&data).unwrap_or("image/png").to_string(),
+        bytes: data.len() as u64,
+        width: dimensions.map(|(width, _)| width),
+        height: dimensions.map(|(_, height)| height),
+    })
+}
+
+/// Encode 8-bit RGBA pixels as PNG.
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(rgba).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(data)
+}
+
+/// Write the clipboard's image to `path` as PNG.
+#[cfg(desktop)]
+async fn save_clipboard_png(path: &Path) -> Result<(), String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut clipboard = arboard::Clipboard::new().map_err(|e| e.to_string())?;
+        let image = clipboard.get_image().map_err(|err| match err {
+            arboard::Error::ContentNotAvailable => "No image on the clipboard".to_string(),
+            err => err.to_string(),
+        })?;
+        let data = encode_png(image.width as u32, image.height as u32, &image.bytes)?;
+        std::fs::write(&path, data).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(mobile)]
+async fn save_clipboard_png(_path: &Path) -> Result<(), String> {
+    Err("Pasting images is not supported on this platform".to_string())
+}
+
+/// Save the clipboard's image to the attachment cache for sending.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn read_clipboard_image(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ImageAttachment, String> {
+    let path = new_attachment_path(&state, "png")?;
+    if let Err(err) = save_clipboard_png(&path).await {
+        let _ = std::fs::remove_file(&path);
+        return Err(err);
+    }
+    finish_image(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_png, media_type, png_dimensions};
+
+    #[test]
+    fn reads_png_header() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend_from_slice(&2560u32.to_be_bytes());
+        png.extend_from_slice(&1440u32.to_be_bytes());
+        assert_eq!(png_dimensions(&png), Some((2560, 1440)));
+        assert_eq!(media_type(&png), Some("image/png"));
+        assert_eq!(media_type(&[0xff, 0xd8, 0xff, 0xe0]), Some("image/jpeg"));
+        assert_eq!(png_dimensions(b"GIF89a"), None);
+        assert_eq!(media_type(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn encodes_clipboard_pixels_as_png() {
+        let png = encode_png(3, 2, &[255; 3 * 2 * 4]).unwrap();
+        assert_eq!(media_type(&png), Some("image/png"));
+        assert_eq!(png_dimensions(&png), Some((3, 2)));
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rusqlite::Connection;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::time::timeout;
+
+use crate::claude::ClaudeEvent;
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::events::{ClaudeEventPayload, IndexUpdatedPayload};
+use crate::power::power_status;
+use crate::registry::{best_preview, is_generic_preview, resolve_transcript_path, write_registry};
+use crate::semantic::{ensure_indexed, loaded_model, open_store, store_path};
+use crate::state::AppState;
+use crate::transcript_index::{index_path, indexed_transcript};
+use crate::transcripts::read_transcript_stats;
+use crate::types::SessionUsage;
+
+/// How often transcripts are re-checked when nothing wakes the indexer.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+struct IndexTarget {
+    workspace_id: String,
+    session_id: String,
+    transcript: PathBuf,
+    index_file: PathBuf,
+    len: u64,
+    needs_preview: bool,
+    needs_usage: bool,
+}
+
+struct IndexResult {
+    target: IndexTarget,
+    preview: Option<String>,
+    usage: Option<SessionUsage>,
+}
+
+/// Precompute history offsets, previews, usage and (once semantic search is
+/// in use) search vectors for every visible session: once at startup, then
+/// whenever a transcript changes.
+pub(crate) fn spawn_indexer(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // transcript -> size when last indexed
+        let mut indexed: HashMap<PathBuf, u64> = HashMap::new();
+        loop {
+            let state = app.state::<AppState>();
+            // The power watcher wakes the indexer when throttling ends.
+            if !power_status(&state).await.indexing_paused {
+                if let Err(err) = index_pass(&app, &state, &mut indexed).await {
+                    log_line!("Session indexer failed: {err}");
+                }
+            }
+            let _ = timeout(POLL_INTERVAL, state.indexer_wake.notified()).await;
+        }
+    });
+}
+
+async fn collect_targets(
+    state: &State<'_, AppState>,
+    indexed: &HashMap<PathBuf, u64>,
+) -> Vec<IndexTarget> {
+    let registry = state.registry.lock().await;
+    let mut seen = HashSet::new();
+    let mut targets = Vec::new();
+    for (workspace_id, workspace) in &registry.workspaces {
+        for session_id in &workspace.visible_session_ids {
+            if !seen.insert(session_id.clone()) {
+                continue;
+            }
+            let Ok(transcript) = resolve_transcript_path(&registry, session_id) else {
+                continue;
+            };
+            let Ok(metadata) = std::fs::metadata(&transcript) else {
+                continue;
+            };
+            if indexed.get(&transcript) == Some(&metadata.len()) {
+                continue;
+            }
+            let entry = registry.sessions.get(session_id);
+            targets.push(IndexTarget {
+                workspace_id: workspace_id.clone(),
+                session_id: session_id.clone(),
+                index_file: index_path(state, session_id),
+                transcript,
+                len: metadata.len(),
+                needs_preview: entry
+                    .is_some_and(|e| e.preview.as_deref().is_none_or(is_generic_preview)),
+                needs_usage: entry.is_some_and(|e| e.usage.is_none()),
+            });
+        }
+    }
+    targets
+}
+
+fn index_target(target: IndexTarget, semantic: Option<&mut Connection>) -> IndexResult {
+    if let Err(err) = indexed_transcript(&target.index_file, &target.transcript) {
+        log_line!("Failed to index {}: {err}", target.transcript.display());
+    }
+    let preview = target
+        .needs_preview
+        .then(|| best_preview(&target.transcript))
+        .flatten();
+    let usage = target
+        .needs_usage
+        .then(|| read_transcript_stats(&target.transcript).ok())
+        .flatten()
+        .map(|stats| stats.usage)
+        .filter(|usage| usage.requests > 0);
+    if let (Some(model), Some(conn)) = (loaded_model(), semantic) {
+        let indexed = ensure_indexed(
+            model,
+            conn,
+            &target.session_id,
+            &target.transcript,
+            target.len,
+        );
+        if let Err(err) = indexed {
+            log_line!("Failed to embed {}: {err}", target.transcript.display());
+        }
+    }
+    IndexResult {
+        target,
+        preview,
+        usage,
+    }
+}
+
+async fn index_pass(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    indexed: &mut HashMap<PathBuf, u64>,
+) -> Result<(), String> {
+    let targets = collect_targets(state, indexed).await;
+    if targets.is_empty() {
+        return Ok(());
+    }
+    let store = store_path(state);
+    let results = tokio::task::spawn_blocking(move || {
+        // Vectors go straight to the store; none are kept in memory.
+        let mut semantic = loaded_model().and_then(|_| open_store(&store).ok());
+        targets
+            .into_iter()
+            .map(|target| index_target(target, semantic.as_mut()))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|_| "indexer task failed".to_string())?;
+
+    let mut updated_by_workspace: HashMap<String, Vec<String>> = HashMap::new();
+    {
+        let mut registry = state.registry.lock().await;
+        let mut registry_changed = false;
+        for result in results {
+            let target = result.target;
+            if let Some(session) = registry.sessions.get_mut(&target.session_id) {
+                if let Some(preview) = result.preview {
+                    session.preview = Some(preview);
+                    registry_changed = true;
+                }
+                if let Some(usage) = result.usage {
+                    if session.usage.is_none() {
+                        session.usage = Some(usage);
+                        registry_changed = true;
+                    }
+                }
+            }
+            indexed.insert(target.transcript, target.len);
+            updated_by_workspace
+                .entry(target.workspace_id)
+                .or_default()
+                .push(target.session_id);
+        }
+        if registry_changed {
+            write_registry(&state.registry_path, &registry)?;
+        }
+    }
+
+    for (workspace_id, session_ids) in updated_by_workspace {
+        let _ = app.emit(
+            "claude-event",
+            ClaudeEvent {
+                event_type: "index/updated".to_string(),
+                session_id: String::new(),
+                workspace_id,
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                payload: ClaudeEventPayload::IndexUpdated(IndexUpdatedPayload { session_ids }),
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Ask the background indexer to run now instead of at its next poll.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn reindex_sessions(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    state.indexer_wake.notify_one();
+    Ok(())
+}
@@ -0,0 +1,202 @@
+//! Recent workspaces in the macOS dock menu and the Windows jump list.
+//! Tauri has no API for either, so both are set up natively here; their
+//! items open the same `claude-monitor://` links as File > Open Recent.
+
+use tauri::AppHandle;
+
+use crate::deep_link::DeepLink;
+
+/// Replace the dock menu or jump list with `entries` (title and link).
+pub(crate) fn update(app: &AppHandle, entries: &[(String, DeepLink)]) {
+    let entries: Vec<(String, String)> = entries
+        .iter()
+        .map(|(title, link)| (title.clone(), link.url()))
+        .collect();
+    #[cfg(target_os = "macos")]
+    dock::update(app, entries);
+    #[cfg(target_os = "windows")]
+    {
+        let _ = app;
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(err) = windows_jump_list::update(&entries) {
+                crate::crash::log_line!("Failed to update the jump list: {err}");
+            }
+        });
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let _ = (app, entries);
+}
+
+/// The dock menu comes from the app delegate's `applicationDockMenu:`.
+/// tao owns the delegate, so the method is added to its class at runtime,
+/// along with the action the menu items send.
+#[cfg(target_os = "macos")]
+mod dock {
+    use std::ffi::{c_char, CStr, CString};
+    use std::ptr::null_mut;
+    use std::sync::atomic::{AtomicPtr, Ordering};
+    use std::sync::{Once, OnceLock};
+
+    use objc2::ffi;
+    use objc2::runtime::{AnyObject, Sel};
+    use objc2::{class, msg_send, sel};
+    use tauri::AppHandle;
+
+    use crate::crash::log_line;
+    use crate::deep_link::handle_deep_link;
+
+    static APP: OnceLock<AppHandle> = OnceLock::new();
+    static INSTALL: Once = Once::new();
+    /// The menu returned to AppKit. Only touched on the main thread.
+    static MENU: AtomicPtr<AnyObject> = AtomicPtr::new(null_mut());
+
+    extern "C" fn dock_menu(_this: &AnyObject, _cmd: Sel, _app: *mut AnyObject) -> *mut AnyObject {
+        MENU.load(Ordering::Acquire)
+    }
+
+    extern "C" fn open_link(_this: &AnyObject, _cmd: Sel, sender: *mut AnyObject) {
+        let Some(app) = APP.get() else {
+            return;
+        };
+        let url = unsafe {
+            let url: *mut AnyObject = msg_send![sender, representedObject];
+            if url.is_null() {
+                return;
+            }
+            let utf8: *const c_char = msg_send![url, UTF8String];
+            CStr::from_ptr(utf8).to_string_lossy().into_owned()
+        };
+        handle_deep_link(app, &url);
+    }
+
+    unsafe fn ns_string(value: &str) -> *mut AnyObject {
+        let value = CString::new(value).unwrap_or_default();
+        msg_send![class!(NSString), stringWithUTF8String: value.as_ptr()]
+    }
+
+    unsafe fn app_delegate() -> *mut AnyObject {
+        let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+        msg_send![app, delegate]
+    }
+
+    unsafe fn install(delegate: *mut AnyObject) {
+        let class =
+            ffi::object_getClass(delegate as *const ffi::objc_object) as *mut ffi::objc_class;
+        let methods: [(Sel, ffi::IMP, &CStr); 2] = [
+            (
+                sel!(applicationDockMenu:),
+                Some(std::mem::transmute::<
+                    extern "C" fn(&AnyObject, Sel, *mut AnyObject) -> *mut AnyObject,
+                    unsafe extern "C" fn(),
+                >(dock_menu)),
+                c"@@:@",
+            ),
+            (
+                sel!(claudeMonitorOpenLink:),
+                Some(std::mem::transmute::<
+                    extern "C" fn(&AnyObject, Sel, *mut AnyObject),
+                    unsafe extern "C" fn(),
+                >(open_link)),
+                c"v@:@",
+            ),
+        ];
+        for (selector, imp, types) in methods {
+            if ffi::class_addMethod(class, selector.as_ptr(), imp, types.as_ptr()) == ffi::NO {
+                log_line!("The app delegate already implements {selector:?}");
+            }
+        }
+    }
+
+    unsafe fn build_menu(delegate: *mut AnyObject, entries: &[(String, String)]) -> *mut AnyObject {
+        let menu: *mut AnyObject = msg_send![class!(NSMenu), new];
+        for (title, url) in entries {
+            let item: *mut AnyObject = msg_send![class!(NSMenuItem), alloc];
+            let item: *mut AnyObject = msg_send![
+                item,
+                initWithTitle: ns_string(title),
+                action: sel!(claudeMonitorOpenLink:),
+                keyEquivalent: ns_string("")
+            ];
+            let _: () = msg_send![item, setTarget: delegate];
+            let _: () = msg_send![item, setRepresentedObject: ns_string(url)];
+            let _: () = msg_send![menu, addItem: item];
+            let _: () = msg_send![item, release];
+        }
+        menu
+    }
+
+    pub(super) fn update(app: &AppHandle, entries: Vec<(String, String)>) {
+        let _ = APP.set(app.clone());
+        let result = app.run_on_main_thread(move || unsafe {
+            let delegate = app_delegate();
+            if delegate.is_null() {
+                return;
+            }
+            INSTALL.call_once(|| install(delegate));
+            let previous = MENU.swap(build_menu(delegate, &entries), Ordering::AcqRel);
+            if !previous.is_null() {
+                let _: () = msg_send![previous, release];
+            }
+        });
+        if let Err(err) = result {
+            log_line!("Failed to update the dock menu: {err}");
+        }
+    }
+}
+
+/// A "Recent Workspaces" category of shortcuts that start the app with a
+/// link as its argument; the running instance picks it up through the
+/// single-instance plugin.
+#[cfg(target_os = "windows")]
+mod windows_jump_list {
+    use windows::core::{w, Interface, HSTRING};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+
+    pub(super) fn update(entries: &[(String, String)]) -> Result<(), String> {
+        let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+        unsafe {
+            // Fails harmlessly when the thread is already in an apartment.
+            let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+            let list: ICustomDestinationList =
+                CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)
+                    .map_err(|e| e.to_string())?;
+            let mut slots = 0u32;
+            let _removed: IObjectArray = list.BeginList(&mut slots).map_err(|e| e.to_string())?;
+            if !entries.is_empty() {
+                let items: IObjectCollection =
+                    CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)
+                        .map_err(|e| e.to_string())?;
+                for (title, url) in entries {
+                    let shortcut: IShellLinkW =
+                        CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)
+                            .map_err(|e| e.to_string())?;
+                    shortcut
+                        .SetPath(&HSTRING::from(exe.as_os_str()))
+                        .map_err(|e| e.to_string())?;
+                    shortcut
+                        .SetArguments(&HSTRING::from(url.as_str()))
+                        .map_err(|e| e.to_string())?;
+                    let store: IPropertyStore = shortcut.cast().map_err(|e| e.to_string())?;
+                    store
+                        .SetValue(&PKEY_Title, &PROPVARIANT::from(title.as_str()))
+                        .map_err(|e| e.to_string())?;
+                    store.Commit().map_err(|e| e.to_string())?;
+                    items.AddObject(&shortcut).map_err(|e| e.to_string())?;
+                }
+                let items: IObjectArray = items.cast().map_err(|e| e.to_string())?;
+                list.AppendCategory(w!("Recent Workspaces"), &items)
+                    .map_err(|e| e.to_string())?;
+            }
+            list.CommitList().map_err(|e| e.to_string())
+        }
+    }
+}
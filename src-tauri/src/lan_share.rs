@@ -0,0 +1,523 @@
+use std::collections::HashMap;
+use std::io::{BufReader, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::claude::ClaudeEvent;
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+
+// Wire protocol: length-prefixed frames, each a random nonce followed by
+// a ChaCha20-Poly1305 ciphertext of one JSON object, keyed by a hash of
+// the share token. The token itself never crosses the network. The viewer
+// sends `{"sessionId"}`, the host answers `{"ok","error"?}` and then only
+// writes events. Nothing a viewer sends after the handshake is read, which
+// is what keeps the share read-only.
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+/// A viewer that cannot keep up is dropped rather than stalling the bridge.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+const ACCEPT_POLL: Duration = Duration::from_millis(200);
+/// Events queued per viewer before it counts as unable to keep up.
+const VIEWER_BACKLOG: usize = 256;
+/// Connections still handshaking; more are closed straight away.
+const MAX_PENDING_HANDSHAKES: usize = 8;
+const MAX_HANDSHAKE_FRAME: usize = 4096;
+const MAX_EVENT_FRAME: usize = 16 * 1024 * 1024;
+const NONCE_LEN: usize = 12;
+
+type Frame = Arc<Vec<u8>>;
+
+/// An admitted viewer. Its writer thread owns the other half of `frames`;
+/// `stream` is kept to disconnect it.
+struct Viewer {
+    frames: SyncSender<Frame>,
+    stream: TcpStream,
+}
+
+struct HostedShare {
+    token: String,
+    port: u16,
+    cipher: ChaCha20Poly1305,
+    viewers: Arc<Mutex<Vec<Viewer>>>,
+    stop: Arc<AtomicBool>,
+}
+
+/// Sessions this instance is streaming, and remote sessions it is following.
+#[derive(Default)]
+pub(crate) struct LanShares {
+    hosted: HashMap<String, HostedShare>,
+    following: HashMap<String, TcpStream>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LanShareInfo {
+    session_id: String,
+    port: u16,
+    token: String,
+    /// Best guess at the address a teammate should connect to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    viewers: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Handshake {
+    session_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HandshakeReply {
+    ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct LanShareEvent {
+    viewer_id: String,
+    event: Value,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct LanShareClosed {
+    viewer_id: String,
+    reason: String,
+}
+
+fn share_cipher(token: &str) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(b"claude-monitor lan share v1\0");
+    hasher.update(token.as_bytes());
+    ChaCha20Poly1305::new(&hasher.finalize())
+}
+
+/// Encrypt `value` into a frame: big-endian length, nonce, ciphertext.
+fn seal<T: Serialize>(cipher: &ChaCha20Poly1305, value: &T) -> Result<Vec<u8>, String> {
+    let plaintext = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| "encryption failed".to_string())?;
+    let len = (NONCE_LEN + ciphertext.len()) as u32;
+    let mut frame = Vec::with_capacity(4 + len as usize);
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(&nonce);
+    frame.extend_from_slice(&ciphertext);
+    Ok(frame)
+}
+
+/// Decrypt a frame body (nonce and ciphertext). Fails for anyone who
+/// doesn't hold the token.
+fn unseal(cipher: &ChaCha20Poly1305, body: &[u8]) -> Result<Vec<u8>, String> {
+    if body.len() < NONCE_LEN {
+        return Err("truncated frame".to_string());
+    }
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "invalid token".to_string())
+}
+
+fn read_frame(reader: &mut impl Read, max_len: usize) -> std::io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > max_len {
+        return Err(std::io::Error::other("frame too large"));
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+    Ok(body)
+}
+
+fn check_handshake(cipher: &ChaCha20Poly1305, body: &[u8], session_id: &str) -> Result<(), String> {
+    let handshake: Handshake = serde_json::from_slice(&unseal(cipher, body)?)
+        .map_err(|_| "malformed handshake".to_string())?;
+    if handshake.session_id != session_id {
+        return Err("unknown session".to_string());
+    }
+    Ok(())
+}
+
+/// Local address on the interface that routes outward. Connecting a UDP
+/// socket sends nothing; it only picks the route.
+fn lan_address() -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    Some(socket.local_addr().ok()?.ip().to_string())
+}
+
+fn admit_viewer(
+    mut stream: TcpStream,
+    session_id: &str,
+    cipher: &ChaCha20Poly1305,
+    viewers: &Mutex<Vec<Viewer>>,
+) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+    let result = read_frame(&mut stream, MAX_HANDSHAKE_FRAME)
+        .map_err(|e| e.to_string())
+        .and_then(|body| check_handshake(cipher, &body, session_id));
+    let reply = HandshakeReply {
+        ok: result.is_ok(),
+        error: result.err(),
+    };
+    let sent = seal(cipher, &reply)
+        .ok()
+        .is_some_and(|frame| stream.write_all(&frame).is_ok());
+    if !sent || !reply.ok {
+        let _ = stream.shutdown(Shutdown::Both);
+        return;
+    }
+    let _ = stream.shutdown(Shutdown::Read);
+    let (Ok(writer), Ok(mut viewers)) = (stream.try_clone(), viewers.lock()) else {
+        return;
+    };
+    let (frames, queue) = sync_channel(VIEWER_BACKLOG);
+    std::thread::spawn(move || write_frames(writer, queue));
+    viewers.push(Viewer { frames, stream });
+}
+
+/// Feed one viewer its queued frames. Exits, dropping the receiver, when
+/// a write fails or the share is stopped.
+fn write_frames(mut stream: TcpStream, queue: Receiver<Frame>) {
+    for frame in queue {
+        if stream.write_all(&frame).is_err() {
+            let _ = stream.shutdown(Shutdown::Both);
+            return;
+        }
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    session_id: String,
+    cipher: ChaCha20Poly1305,
+    viewers: Arc<Mutex<Vec<Viewer>>>,
+    stop: Arc<AtomicBool>,
+) {
+    let pending = Arc::new(AtomicUsize::new(0));
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if pending.fetch_add(1, Ordering::AcqRel) >= MAX_PENDING_HANDSHAKES {
+                    pending.fetch_sub(1, Ordering::AcqRel);
+                    let _ = stream.shutdown(Shutdown::Both);
+                    continue;
+                }
+                let (session_id, cipher, viewers, pending) = (
+                    session_id.clone(),
+                    cipher.clone(),
+                    viewers.clone(),
+                    pending.clone(),
+                );
+                std::thread::spawn(move || {
+                    admit_viewer(stream, &session_id, &cipher, &viewers);
+                    pending.fetch_sub(1, Ordering::AcqRel);
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL);
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+/// Forward an event to anyone following its session. Called for every
+/// event the bridge emits; a no-op unless the session is being shared.
+/// Never blocks on the network: each viewer has its own writer thread, and
+/// one whose queue is full is dropped.
+pub(crate) fn broadcast(app_handle: &AppHandle, event: &ClaudeEvent) {
+    let Some(state) = app_handle.try_state::<AppState>() else {
+        return;
+    };
+    let (cipher, viewers) = {
+        let Ok(shares) = state.lan_shares.lock() else {
+            return;
+        };
+        match shares.hosted.get(&event.session_id) {
+            Some(share) => (share.cipher.clone(), share.viewers.clone()),
+            None => return,
+        }
+    };
+    if viewers.lock().map(|v| v.is_empty()).unwrap_or(true) {
+        return;
+    }
+    let Ok(frame) = seal(&cipher, event) else {
+        return;
+    };
+    let frame = Arc::new(frame);
+    let Ok(mut viewers) = viewers.lock() else {
+        return;
+    };
+    viewers.retain(|viewer| match viewer.frames.try_send(frame.clone()) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+            let _ = viewer.stream.shutdown(Shutdown::Both);
+            false
+        }
+    });
+}
+
+fn share_info(session_id: &str, share: &HostedShare) -> LanShareInfo {
+    LanShareInfo {
+        session_id: session_id.to_string(),
+        port: share.port,
+        token: share.token.clone(),
+        address: lan_address(),
+        viewers: share.viewers.lock().map(|v| v.len()).unwrap_or(0),
+    }
+}
+
+/// Start streaming a session's events to viewers on the local network.
+/// Requires the `lanSharing` setting; calling it again returns the existing
+/// share.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn start_lan_share(
+    session_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<LanShareInfo, String> {
+    if !state.app_settings.lock().await.lan_sharing {
+        return Err("LAN sharing is turned off in settings".to_string());
+    }
+    if !state
+        .registry
+        .lock()
+        .await
+        .sessions
+        .contains_key(&session_id)
+    {
+        return Err(format!("Session {session_id} not found"));
+    }
+    let mut shares = state.lan_shares.lock().map_err(|e| e.to_string())?;
+    if let Some(share) = shares.hosted.get(&session_id) {
+        return Ok(share_info(&session_id, share));
+    }
+
+    let listener = TcpListener::bind("0.0.0.0:0").map_err(|e| e.to_string())?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    let share = HostedShare {
+        cipher: share_cipher(&token),
+        token,
+        port,
+        viewers: Arc::new(Mutex::new(Vec::new())),
+        stop: Arc::new(AtomicBool::new(false)),
+    };
+    let (id, cipher, viewers, stop) = (
+        session_id.clone(),
+        share.cipher.clone(),
+        share.viewers.clone(),
+        share.stop.clone(),
+    );
+    std::thread::spawn(move || accept_loop(listener, id, cipher, viewers, stop));
+    let info = share_info(&session_id, &share);
+    shares.hosted.insert(session_id, share);
+    Ok(info)
+}
+
+/// Stop sharing a session and disconnect its viewers.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn stop_lan_share(
+    session_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let share = state
+        .lan_shares
+        .lock()
+        .map_err(|e| e.to_string())?
+        .hosted
+        .remove(&session_id);
+    if let Some(share) = share {
+        share.stop.store(true, Ordering::Relaxed);
+        if let Ok(mut viewers) = share.viewers.lock() {
+            // Dropping the senders ends each writer thread.
+            for viewer in viewers.drain(..) {
+                let _ = viewer.stream.shutdown(Shutdown::Both);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_lan_shares(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<LanShareInfo>, String> {
+    let shares = state.lan_shares.lock().map_err(|e| e.to_string())?;
+    Ok(shares
+        .hosted
+        .iter()
+        .map(|(session_id, share)| share_info(session_id, share))
+        .collect())
+}
+
+/// Follow a session shared from another instance. Its events arrive on
+/// `lan-share-event` until the host stops or `leave_lan_share` is called.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn join_lan_share(
+    host: String,
+    port: u16,
+    session_id: String,
+    token: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<String, String> {
+    let cipher = share_cipher(&token);
+    let handshake_cipher = cipher.clone();
+    let stream = tokio::task::spawn_blocking(move || -> Result<TcpStream, String> {
+        let cipher = handshake_cipher;
+        let addr = format!("{host}:{port}");
+        let mut stream = std::net::ToSocketAddrs::to_socket_addrs(&addr)
+            .map_err(|e| e.to_string())?
+            .find_map(|addr| TcpStream::connect_timeout(&addr, HANDSHAKE_TIMEOUT).ok())
+            .ok_or_else(|| format!("Could not connect to {addr}"))?;
+        stream
+            .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+            .map_err(|e| e.to_string())?;
+        let frame = seal(&cipher, &Handshake { session_id })?;
+        stream.write_all(&frame).map_err(|e| e.to_string())?;
+        // A host that can't decrypt the handshake closes the connection.
+        let body = read_frame(&mut stream, MAX_HANDSHAKE_FRAME)
+            .map_err(|_| "The share refused the token".to_string())?;
+        let reply: HandshakeReply = serde_json::from_slice(&unseal(&cipher, &body)?)
+            .map_err(|_| "Not a session share".to_string())?;
+        if !reply.ok {
+            return Err(reply.error.unwrap_or_else(|| "share refused".to_string()));
+        }
+        stream.set_read_timeout(None).map_err(|e| e.to_string())?;
+        Ok(stream)
+    })
+    .await
+    .map_err(|_| "join failed".to_string())??;
+
+    let viewer_id = uuid::Uuid::new_v4().to_string();
+    let reader = stream.try_clone().map_err(|e| e.to_string())?;
+    let _ = stream.shutdown(Shutdown::Write);
+    state
+        .lan_shares
+        .lock()
+        .map_err(|e| e.to_string())?
+        .following
+        .insert(viewer_id.clone(), stream);
+
+    let id = viewer_id.clone();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        while let Ok(body) = read_frame(&mut reader, MAX_EVENT_FRAME) {
+            let Ok(plaintext) = unseal(&cipher, &body) else {
+                break;
+            };
+            let Ok(event) = serde_json::from_slice::<Value>(&plaintext) else {
+                continue;
+            };
+            let _ = app.emit(
+                "lan-share-event",
+                LanShareEvent {
+                    viewer_id: id.clone(),
+                    event,
+                },
+            );
+        }
+        if let Some(state) = app.try_state::<AppState>() {
+            if let Ok(mut shares) = state.lan_shares.lock() {
+                shares.following.remove(&id);
+            }
+        }
+        let _ = app.emit(
+            "lan-share-closed",
+            LanShareClosed {
+                viewer_id: id,
+                reason: "disconnected".to_string(),
+            },
+        );
+    });
+    Ok(viewer_id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn leave_lan_share(
+    viewer_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let stream = state
+        .lan_shares
+        .lock()
+        .map_err(|e| e.to_string())?
+        .following
+        .remove(&viewer_id);
+    if let Some(stream) = stream {
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_handshake, read_frame, seal, share_cipher, unseal, Handshake};
+
+    fn handshake_body(token: &str, session_id: &str) -> Vec<u8> {
+        let frame = seal(
+            &share_cipher(token),
+            &Handshake {
+                session_id: session_id.to_string(),
+            },
+        )
+        .unwrap();
+        read_frame(&mut frame.as_slice(), 4096).unwrap()
+    }
+
+    #[test]
+    fn handshake_requires_matching_session_and_token() {
+        let cipher = share_cipher("abc123");
+        assert!(check_handshake(&cipher, &handshake_body("abc123", "s1"), "s1").is_ok());
+        assert!(check_handshake(&cipher, &handshake_body("abc123", "s2"), "s1").is_err());
+        assert!(check_handshake(&cipher, &handshake_body("other", "s1"), "s1").is_err());
+        assert!(check_handshake(&cipher, b"GET / HTTP/1.1", "s1").is_err());
+    }
+
+    #[test]
+    fn frames_hide_their_contents_and_reject_tampering() {
+        let cipher = share_cipher("abc123");
+        let frame = seal(&cipher, &serde_json::json!({ "secret": "diff" })).unwrap();
+        assert!(!String::from_utf8_lossy(&frame).contains("secret"));
+
+        let mut body = read_frame(&mut frame.as_slice(), 4096).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&unseal(&cipher, &body).unwrap()).unwrap(),
+            serde_json::json!({ "secret": "diff" })
+        );
+        let last = body.len() - 1;
+        body[last] ^= 1;
+        assert!(unseal(&cipher, &body).is_err());
+        assert!(read_frame(&mut frame.as_slice(), 8).is_err());
+    }
+}
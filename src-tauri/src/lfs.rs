@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use git2::{AttrCheckFlags, Delta, Oid, Repository};
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::git::{open_workspace_repo, workspace_relative_path};
+use crate::state::AppState;
+use crate::types::{LfsInfo, LfsSettings};
+
+const POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/v1";
+/// Pointer files are a few lines; anything larger is real content.
+const MAX_POINTER_BYTES: u64 = 1024;
+/// Unpushed commits scanned by `check_push_objects`.
+const MAX_PUSH_COMMITS: usize = 500;
+
+/// A blob over the large-file threshold.
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LargeObject {
+    pub(crate) path: String,
+    pub(crate) size: u64,
+    /// Short sha of the commit that introduced it; absent for staged files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) commit: Option<String>,
+    /// Matched by a `filter=lfs` attribute, so it should have been a pointer.
+    pub(crate) lfs: bool,
+}
+
+pub(crate) fn is_lfs_tracked(repo: &Repository, path: &str) -> bool {
+    matches!(
+        repo.get_attr(Path::new(path), "filter", AttrCheckFlags::FILE_THEN_INDEX),
+        Ok(Some("lfs"))
+    )
+}
+
+fn is_pointer(bytes: &[u8]) -> bool {
+    bytes.len() as u64 <= MAX_POINTER_BYTES && bytes.starts_with(POINTER_PREFIX)
+}
+
+/// LFS flags for a changed file, from its working copy. `path` is relative
+/// to the workspace, which sits at `subdir` inside the repo.
+pub(crate) fn lfs_info(
+    repo: &Repository,
+    subdir: Option<&str>,
+    path: &str,
+    threshold: u64,
+) -> Option<LfsInfo> {
+    let repo_path = match subdir {
+        Some(subdir) => format!("{subdir}/{path}"),
+        None => path.to_string(),
+    };
+    let file = repo.workdir()?.join(&repo_path);
+    // Deleted files have nothing left to upload.
+    let size = std::fs::metadata(&file).ok()?.len();
+    if is_lfs_tracked(repo, &repo_path) {
+        let pointer_edited =
+            size <= MAX_POINTER_BYTES && std::fs::read(&file).is_ok_and(|bytes| is_pointer(&bytes));
+        return Some(LfsInfo {
+            tracked: true,
+            pointer_edited,
+            oversized: None,
+        });
+    }
+    (size > threshold).then_some(LfsInfo {
+        tracked: false,
+        pointer_edited: false,
+        oversized: Some(size),
+    })
+}
+
+fn blob_size(repo: &Repository, id: git2::Oid) -> Result<u64, git2::Error> {
+    Ok(repo.odb()?.read_header(id)?.0 as u64)
+}
+
+/// Blobs over `threshold` in commits on the current branch that no
+/// remote-tracking branch has, i.e. what a push would upload as plain git
+/// objects.
+fn unpushed_large_objects(
+    repo: &Repository,
+    subdir: Option<&str>,
+    threshold: u64,
+) -> Result<Vec<LargeObject>, git2::Error> {
+    if repo.remotes()?.is_empty() {
+        return Ok(Vec::new());
+    }
+    match repo.head().ok().and_then(|head| head.target()) {
+        Some(head) => pushed_large_objects(repo, subdir, threshold, &[head]),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Blobs over `threshold` in commits reachable from `tips` that no
+/// remote-tracking branch has.
+pub(crate) fn pushed_large_objects(
+    repo: &Repository,
+    subdir: Option<&str>,
+    threshold: u64,
+    tips: &[Oid],
+) -> Result<Vec<LargeObject>, git2::Error> {
+    let mut walk = repo.revwalk()?;
+    for tip in tips {
+        walk.push(*tip)?;
+    }
+    walk.hide_glob("refs/remotes/*")?;
+
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    for oid in walk.take(MAX_PUSH_COMMITS) {
+        let commit = repo.find_commit(oid?)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        for delta in diff.deltas() {
+            if !matches!(delta.status(), Delta::Added | Delta::Modified) {
+                continue;
+            }
+            let file = delta.new_file();
+            let Some(path) = file.path().and_then(|path| path.to_str()) else {
+                continue;
+            };
+            if !seen.insert(file.id()) {
+                continue;
+            }
+            let size = blob_size(repo, file.id())?;
+            if size > threshold {
+                found.push(LargeObject {
+                    path: workspace_relative_path(path, subdir),
+                    size,
+                    commit: Some(commit.id().to_string()[..7].to_string()),
+                    lfs: is_lfs_tracked(repo, path),
+                });
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Staged blobs over `threshold`.
+pub(crate) fn staged_large_objects(
+    repo: &Repository,
+    subdir: Option<&str>,
+    threshold: u64,
+) -> Result<Vec<LargeObject>, git2::Error> {
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
+    let mut found = Vec::new();
+    for delta in diff.deltas() {
+        if !matches!(delta.status(), Delta::Added | Delta::Modified) {
+            continue;
+        }
+        let file = delta.new_file();
+        let Some(path) = file.path().and_then(|path| path.to_str()) else {
+            continue;
+        };
+        let size = blob_size(repo, file.id())?;
+        if size > threshold {
+            found.push(LargeObject {
+                path: workspace_relative_path(path, subdir),
+                size,
+                commit: None,
+                lfs: is_lfs_tracked(repo, path),
+            });
+        }
+    }
+    Ok(found)
+}
+
+async fn workspace_and_settings(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+) -> Result<(String, LfsSettings), String> {
+    let path = state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .map(|entry| entry.path.clone())
+        .ok_or("workspace not found")?;
+    let settings = state.app_settings.lock().await.lfs.clone();
+    Ok((path, settings))
+}
+
+/// Large objects a push of the current branch would upload.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn check_push_objects(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<LargeObject>, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
+    let (path, settings) = workspace_and_settings(&state, &workspace_id).await?;
+    tokio::task::spawn_blocking(move || {
+        let (repo, subdir) = open_workspace_repo(&path)?;
+        unpushed_large_objects(&repo, subdir.as_deref(), settings.threshold_bytes())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|_| "push check failed".to_string())?
+}
+
+/// Staged files over the large-file limit. With `blockLargeCommits` set
+/// this fails instead, so commit paths can use it as a gate.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn check_commit_size(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<LargeObject>, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
+    let (path, settings) = workspace_and_settings(&state, &workspace_id).await?;
+    let threshold = settings.threshold_bytes();
+    let found = tokio::task::spawn_blocking(move || {
+        let (repo, subdir) = open_workspace_repo(&path)?;
+        staged_large_objects(&repo, subdir.as_deref(), threshold).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|_| "commit check failed".to_string())??;
+    if settings.block_large_commits && !found.is_empty() {
+        return Err(large_commit_error(&settings, &found));
+    }
+    Ok(found)
+}
+
+/// Why a commit of `found` is refused under `blockLargeCommits`.
+pub(crate) fn large_commit_error(settings: &LfsSettings, found: &[LargeObject]) -> String {
+    let paths: Vec<&str> = found.iter().map(|object| object.path.as_str()).collect();
+    format!(
+        "Staged files exceed {} MB: {}. Track them with git lfs or unstage them.",
+        settings.large_file_mb,
+        paths.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_lfs_tracked, lfs_info, staged_large_objects};
+    use crate::utils::TempDir;
+    use git2::Repository;
+
+    #[test]
+    fn flags_lfs_pointers_and_large_untracked_files() {
+        let root = TempDir::new("lfs");
+        let repo = Repository::init(&root).unwrap();
+        std::fs::write(
+            root.join(".gitattributes"),
+            "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("model.bin"),
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 12\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("data.csv"), vec![b'x'; 2048]).unwrap();
+        std::fs::write(root.join("small.txt"), "hi").unwrap();
+
+        assert!(is_lfs_tracked(&repo, "model.bin"));
+        assert!(!is_lfs_tracked(&repo, "data.csv"));
+        let pointer = lfs_info(&repo, None, "model.bin", 1024).unwrap();
+        assert!(pointer.tracked && pointer.pointer_edited);
+        assert_eq!(
+            lfs_info(&repo, None, "data.csv", 1024).unwrap().oversized,
+            Some(2048)
+        );
+        assert!(lfs_info(&repo, None, "small.txt", 1024).is_none());
+
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("data.csv")).unwrap();
+        index.add_path(std::path::Path::new("small.txt")).unwrap();
+        index.write().unwrap();
+        let staged = staged_large_objects(&repo, None, 1024).unwrap();
+        let paths: Vec<_> = staged.iter().map(|object| object.path.as_str()).collect();
+        assert_eq!(paths, ["data.csv"]);
+    }
+}
@@ -1,17 +1,314 @@
 use tauri::menu::{Menu, MenuItemBuilder, PredefinedMenuItem, Submenu};
 use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
 
+mod about;
+mod accounts;
+mod appearance;
+mod attachments;
+mod audit;
+mod auto_approve;
+mod automation;
+mod badge;
+#[doc(hidden)]
+pub mod bench_support;
+mod bindings;
+mod bridge_launch;
+mod bundles;
+mod changelog;
+mod changes;
 mod claude;
+mod claude_settings;
 mod codex;
+mod command_metrics;
+mod confirmations;
+mod crash;
+mod deep_link;
+mod dependencies;
+mod digest;
+mod editors;
+mod events;
+mod file_drop;
+mod focus;
+#[doc(hidden)]
+pub mod fuzz_support;
+#[cfg(test)]
+mod fuzz_tests;
 mod git;
+mod git_hooks;
+mod git_locks;
+mod glossary;
+mod guardrails;
+mod heartbeat;
+mod help;
+mod highlight;
+mod hooks;
+mod i18n;
+mod images;
+mod indexer;
+mod jump_list;
+mod lan_share;
+mod lfs;
+mod memory;
+mod message_queue;
+#[cfg(test)]
+mod mock_bridge;
+mod notes;
+mod observer;
+#[cfg(test)]
+mod perf_tests;
+mod power;
+mod pricing;
+mod projects;
+mod prompt_history;
 mod prompts;
+mod providers;
+mod proxy;
+mod read_receipts;
+mod references;
 mod registry;
+mod release;
+mod replay;
+mod repo_stats;
+mod screenshots;
+mod secret_scan;
+mod secrets;
+mod semantic;
+mod session_files;
 mod settings;
+mod setup;
+mod slack;
+mod snippets;
+mod stale_worktrees;
 mod state;
 mod storage;
+mod team_prompts;
+mod telemetry;
+mod tickets;
+mod time_tracking;
+mod tokens;
+mod transcript_index;
+mod transcripts;
+mod turn_lanes;
+mod turns;
 mod types;
+mod ui_prefs;
+mod usage;
 mod utils;
+mod word_diff;
+mod workflows;
 mod workspaces;
+mod worktree_overview;
+
+/// Every command the frontend can call. `src/services/bindings.ts` is
+/// generated from the same list (see `bindings`), along with the
+/// `claude-event` payload types.
+pub(crate) fn commands() -> tauri_specta::Builder<tauri::Wry> {
+    tauri_specta::Builder::<tauri::Wry>::new()
+        // Commands reject with the error string, as plain `invoke` did.
+        .error_handling(tauri_specta::ErrorHandlingMode::Throw)
+        .typ::<claude::ClaudeEvent>()
+        .commands(tauri_specta::collect_commands![
+            settings::get_app_settings,
+            settings::update_app_settings,
+            appearance::get_appearance,
+            focus::get_do_not_disturb,
+            focus::set_do_not_disturb,
+            auto_approve::enable_auto_approve,
+            auto_approve::disable_auto_approve,
+            auto_approve::get_auto_approve,
+            audit::get_audit_log,
+            codex::codex_doctor,
+            workspaces::list_workspaces,
+            workspaces::is_state_ready,
+            workspaces::add_workspace,
+            workspaces::add_worktree,
+            workspaces::remove_workspace,
+            workspaces::remove_worktree,
+            worktree_overview::get_worktree_overview,
+            stale_worktrees::list_stale_worktrees,
+            stale_worktrees::clean_up_worktrees,
+            workspaces::update_workspace_settings,
+            workspaces::update_workspace_codex_bin,
+            workspaces::relocate_workspace,
+            automation::pause_workspace_automation,
+            automation::resume_workspace_automation,
+            codex::start_thread,
+            codex::send_user_message,
+            codex::turn_interrupt,
+            codex::start_review,
+            codex::respond_to_server_request,
+            codex::resume_thread,
+            codex::list_threads,
+            codex::archive_thread,
+            workspaces::connect_workspace,
+            git::get_git_status,
+            git::get_git_diffs,
+            git::get_file_diff,
+            lfs::check_push_objects,
+            lfs::check_commit_size,
+            git::get_changed_files_owners,
+            changes::summarize_changes,
+            secret_scan::scan_diff_for_secrets,
+            dependencies::get_dependency_changes,
+            git::get_git_log,
+            changelog::generate_changelog,
+            release::create_release,
+            repo_stats::get_repo_stats,
+            command_metrics::get_command_metrics,
+            git::get_git_remote,
+            git::get_github_issues,
+            workspaces::list_workspace_files,
+            git::list_git_branches,
+            git::checkout_git_branch,
+            git::create_git_branch,
+            codex::model_list,
+            codex::account_rate_limits,
+            codex::skills_list,
+            prompts::prompts_list,
+            prompt_history::search_prompt_history,
+            prompt_history::get_recent_prompts,
+            snippets::list_snippets,
+            snippets::create_snippet,
+            snippets::update_snippet,
+            snippets::delete_snippet,
+            snippets::insert_snippet,
+            glossary::get_glossary,
+            glossary::upsert_glossary_term,
+            glossary::delete_glossary_term,
+            glossary::set_glossary_enabled,
+            team_prompts::sync_team_prompts,
+            team_prompts::get_team_prompts_status,
+            team_prompts::accept_team_permissions,
+            registry::get_visible_sessions,
+            registry::scan_available_sessions,
+            registry::import_sessions,
+            registry::registry_archive_session,
+            registry::register_session,
+            registry::update_session_activity,
+            registry::get_session_history,
+            registry::set_session_metadata,
+            tickets::set_ticket_credentials,
+            tickets::get_ticket_status,
+            tickets::search_tickets,
+            tickets::link_session_ticket,
+            tickets::link_workspace_ticket,
+            tickets::get_session_ticket,
+            tickets::post_ticket_summary,
+            time_tracking::set_attended_session,
+            time_tracking::get_time_report,
+            replay::export_session_replay,
+            replay::replay_session,
+            replay::stop_replay,
+            crash::list_crash_reports,
+            crash::submit_crash_report,
+            crash::delete_crash_report,
+            heartbeat::get_bridge_health,
+            proxy::get_proxy_status,
+            proxy::test_proxy,
+            proxy::check_ca_bundle,
+            i18n::get_locale_info,
+            i18n::format_timestamp,
+            ui_prefs::get_system_ui_prefs,
+            about::get_about_info,
+            deep_link::take_launch_deep_link,
+            power::get_power_status,
+            transcript_index::get_session_history_page,
+            read_receipts::mark_session_read,
+            registry::get_archived_sessions,
+            registry::registry_unarchive_session,
+            registry::backfill_previews,
+            bundles::export_session_bundle,
+            bundles::import_session_bundle,
+            lan_share::start_lan_share,
+            lan_share::stop_lan_share,
+            lan_share::list_lan_shares,
+            lan_share::join_lan_share,
+            lan_share::leave_lan_share,
+            slack::set_slack_credentials,
+            slack::get_slack_status,
+            slack::start_slack_mirror,
+            slack::stop_slack_mirror,
+            digest::generate_digest,
+            digest::send_digest,
+            digest::set_digest_credential,
+            telemetry::set_telemetry_headers,
+            usage::usage_summary,
+            usage::export_usage_csv,
+            usage::export_usage,
+            pricing::get_model_pricing,
+            pricing::refresh_model_pricing,
+            attachments::attach_files_to_message,
+            tokens::count_tokens,
+            images::read_clipboard_image,
+            screenshots::capture_screen_region,
+            session_files::get_session_files,
+            editors::open_in_editor,
+            editors::list_editors,
+            highlight::highlight_file,
+            indexer::reindex_sessions,
+            transcripts::compare_sessions,
+            transcripts::summarize_session,
+            transcripts::verify_transcripts,
+            semantic::semantic_search,
+            notes::extract_decisions,
+            notes::append_workspace_notes,
+            memory::list_memory_files,
+            memory::preview_memory_file,
+            memory::write_memory_file,
+            hooks::get_hooks,
+            hooks::save_hooks,
+            hooks::list_hook_templates,
+            hooks::test_hook,
+            claude_settings::inspect_claude_settings,
+            confirmations::request_confirmation,
+            // Claude Agent SDK commands
+            claude::claude_doctor,
+            claude::claude_start_session,
+            claude::claude_resume_session,
+            claude::claude_send_message,
+            message_queue::queue_message,
+            message_queue::list_queued_messages,
+            message_queue::reorder_queued_message,
+            message_queue::drop_queued_message,
+            workflows::list_workflows,
+            workflows::start_workflow,
+            workflows::cancel_workflow,
+            workflows::list_workflow_runs,
+            claude::claude_interrupt,
+            claude::claude_respond_permission,
+            claude::set_session_priority,
+            claude::claude_list_models,
+            claude::claude_list_commands,
+            claude::claude_mcp_status,
+            claude::claude_rewind_files,
+            claude::claude_set_mcp_servers,
+            claude::claude_close_session,
+            claude::claude_list_requests,
+            claude::claude_cancel_request,
+            accounts::list_accounts,
+            accounts::save_account,
+            accounts::remove_account,
+            accounts::set_workspace_account,
+            accounts::set_anthropic_api_key,
+            accounts::has_anthropic_api_key,
+            providers::test_model_endpoint,
+            setup::setup_detect_clis,
+            setup::setup_list_project_candidates,
+            setup::setup_check_claude_dir,
+            setup::setup_create_workspace,
+            setup::setup_run_test_session,
+            projects::scan_existing_projects,
+            projects::import_existing_projects,
+            projects::scan_all_claude_projects
+        ])
+}
+
+/// Run a git hook installed by the app (`--git-hook <name> ...`) in place
+/// of the app. Returns its exit code, or `None` for a normal launch.
+pub fn run_git_hook() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    git_hooks::run_hook(&args)
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -23,7 +320,29 @@ pub fn run() {
         }
     }
 
-    tauri::Builder::default()
+    let commands = commands();
+    #[cfg(debug_assertions)]
+    if let Err(err) = bindings::export(&commands) {
+        eprintln!("Failed to export TypeScript bindings: {err}");
+    }
+    // Observer mode is enforced here, before any command runs.
+    let handler = commands.invoke_handler();
+
+    let builder = tauri::Builder::default();
+    // Registered first, so a second launch (e.g. from a `claude-monitor://`
+    // link or a jump list item) hands its arguments over and exits.
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.unminimize();
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }));
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_deep_link::init());
+
+    builder
         .enable_macos_default_menu(false)
         .menu(|handle| {
             let app_name = handle.package_info().name.clone();
@@ -45,11 +364,16 @@ pub fn run() {
                 ],
             )?;
 
-            let file_menu = Submenu::with_items(
+            let recent_menu =
+                Submenu::with_id_and_items(handle, deep_link::RECENT_MENU, "Open Recent", false, &[])?;
+            let file_menu = Submenu::with_id_and_items(
                 handle,
+                deep_link::FILE_MENU,
                 "File",
                 true,
                 &[
+                    &recent_menu,
+                    &PredefinedMenuItem::separator(handle)?,
                     &PredefinedMenuItem::close_window(handle, None)?,
                     #[cfg(not(target_os = "macos"))]
                     &PredefinedMenuItem::quit(handle, None)?,
@@ -90,7 +414,25 @@ pub fn run() {
                 ],
             )?;
 
-            let help_menu = Submenu::with_items(handle, "Help", true, &[])?;
+            let help_menu = Submenu::with_items(
+                handle,
+                "Help",
+                true,
+                &[
+                    &MenuItemBuilder::with_id(help::RUN_DOCTOR, "Run Doctor").build(handle)?,
+                    &MenuItemBuilder::with_id(help::KEYBOARD_SHORTCUTS, "Keyboard Shortcuts")
+                        .accelerator("CmdOrCtrl+/")
+                        .build(handle)?,
+                    &PredefinedMenuItem::separator(handle)?,
+                    &MenuItemBuilder::with_id(help::OPEN_LOGS, "Open Logs Folder")
+                        .build(handle)?,
+                    &MenuItemBuilder::with_id(help::EXPORT_DIAGNOSTICS, "Export Diagnostics")
+                        .build(handle)?,
+                    &PredefinedMenuItem::separator(handle)?,
+                    &MenuItemBuilder::with_id(help::REPORT_ISSUE, "Report an Issue")
+                        .build(handle)?,
+                ],
+            )?;
 
             Menu::with_items(
                 handle,
@@ -105,6 +447,13 @@ pub fn run() {
             )
         })
         .on_menu_event(|app, event| {
+            if help::handle_menu_event(app, event.id().as_ref()) {
+                return;
+            }
+            if let Some(url) = event.id().as_ref().strip_prefix(deep_link::MENU_PREFIX) {
+                deep_link::handle_deep_link(app, url);
+                return;
+            }
             if event.id() == "about" {
                 if let Some(window) = app.get_webview_window("about") {
                     let _ = window.show();
@@ -114,18 +463,39 @@ pub fn run() {
                 let _ = WebviewWindowBuilder::new(
                     app,
                     "about",
-                    WebviewUrl::App("index.html".into()),
+                    WebviewUrl::App("about.html".into()),
                 )
                 .title("About Codex Monitor")
                 .resizable(false)
-                .inner_size(360.0, 240.0)
+                .inner_size(420.0, 480.0)
                 .center()
                 .build();
             }
         })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) = event {
+                file_drop::handle_drop(window.app_handle(), paths);
+            }
+        })
         .setup(|app| {
             let state = state::AppState::load(&app.handle());
             app.manage(state);
+            state::spawn_loader(app.handle().clone());
+            indexer::spawn_indexer(app.handle().clone());
+            team_prompts::spawn_team_prompt_sync(app.handle().clone());
+            digest::spawn_digest_scheduler(app.handle().clone());
+            focus::spawn_focus_watcher(app.handle().clone());
+            ui_prefs::spawn_ui_prefs_watcher(app.handle().clone());
+            power::spawn_sleep_watcher(app.handle().clone());
+            power::spawn_power_watcher(app.handle().clone());
+            deep_link::remember_launch_link(std::env::args().skip(1));
+            #[cfg(desktop)]
+            deep_link::listen(app.handle());
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                deep_link::refresh_recent_menu(&handle).await;
+            });
+            telemetry::spawn_telemetry_exporter(app.handle().clone());
             #[cfg(desktop)]
             app.handle()
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
@@ -134,62 +504,15 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
-        .invoke_handler(tauri::generate_handler![
-            settings::get_app_settings,
-            settings::update_app_settings,
-            codex::codex_doctor,
-            workspaces::list_workspaces,
-            workspaces::add_workspace,
-            workspaces::add_worktree,
-            workspaces::remove_workspace,
-            workspaces::remove_worktree,
-            workspaces::update_workspace_settings,
-            workspaces::update_workspace_codex_bin,
-            codex::start_thread,
-            codex::send_user_message,
-            codex::turn_interrupt,
-            codex::start_review,
-            codex::respond_to_server_request,
-            codex::resume_thread,
-            codex::list_threads,
-            codex::archive_thread,
-            workspaces::connect_workspace,
-            git::get_git_status,
-            git::get_git_diffs,
-            git::get_git_log,
-            git::get_git_remote,
-            git::get_github_issues,
-            workspaces::list_workspace_files,
-            git::list_git_branches,
-            git::checkout_git_branch,
-            git::create_git_branch,
-            codex::model_list,
-            codex::account_rate_limits,
-            codex::skills_list,
-            prompts::prompts_list,
-            registry::get_visible_sessions,
-            registry::scan_available_sessions,
-            registry::import_sessions,
-            registry::registry_archive_session,
-            registry::register_session,
-            registry::update_session_activity,
-            registry::get_session_history,
-            registry::get_archived_sessions,
-            registry::registry_unarchive_session,
-            // Claude Agent SDK commands
-            claude::claude_doctor,
-            claude::claude_start_session,
-            claude::claude_resume_session,
-            claude::claude_send_message,
-            claude::claude_interrupt,
-            claude::claude_respond_permission,
-            claude::claude_list_models,
-            claude::claude_list_commands,
-            claude::claude_mcp_status,
-            claude::claude_rewind_files,
-            claude::claude_set_mcp_servers,
-            claude::claude_close_session
-        ])
+        .invoke_handler(
+            move |invoke| match observer::refused(invoke.message.command()) {
+                Some(error) => {
+                    invoke.resolver.reject(error);
+                    true
+                }
+                None => handler(invoke),
+            },
+        )
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,238 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::git::diff_patch_to_string;
+use crate::state::AppState;
+
+/// Memory files editable per workspace.
+const WORKSPACE_MEMORY_FILES: &[&str] = &["CLAUDE.md", "AGENTS.md"];
+/// Claude Code warns about memory files past roughly this size.
+const LARGE_MEMORY_CHARS: usize = 40_000;
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemoryFile {
+    /// "workspace" or "global".
+    scope: String,
+    name: String,
+    path: String,
+    exists: bool,
+    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemoryFilePreview {
+    path: String,
+    /// Unified diff from the file on disk to the proposed content.
+    diff: String,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemoryFileWrite {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backup_path: Option<String>,
+}
+
+fn global_memory_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
+    Ok(home.join(".claude").join("CLAUDE.md"))
+}
+
+/// Resolve a memory file; `workspace_id: None` means the global file.
+async fn memory_path(
+    state: &State<'_, AppState>,
+    workspace_id: Option<&str>,
+    name: &str,
+) -> Result<PathBuf, String> {
+    let Some(workspace_id) = workspace_id else {
+        if name != "CLAUDE.md" {
+            return Err(format!("Unsupported global memory file: {name}"));
+        }
+        return global_memory_path();
+    };
+    if !WORKSPACE_MEMORY_FILES.contains(&name) {
+        return Err(format!("Unsupported memory file: {name}"));
+    }
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces.get(workspace_id).ok_or("workspace not found")?;
+    Ok(PathBuf::from(&entry.path).join(name))
+}
+
+fn read_memory_file(scope: &str, name: &str, path: &Path) -> MemoryFile {
+    let metadata = std::fs::metadata(path).ok();
+    MemoryFile {
+        scope: scope.to_string(),
+        name: name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        exists: metadata.is_some(),
+        content: std::fs::read_to_string(path).unwrap_or_default(),
+        modified_at: metadata
+            .and_then(|meta| meta.modified().ok())
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64),
+    }
+}
+
+/// Problems worth surfacing before saving: oversized files, unclosed code
+/// fences and `@path` imports that do not resolve.
+fn validate_memory(content: &str, base_dir: &Path) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if content.chars().count() > LARGE_MEMORY_CHARS {
+        warnings.push(format!(
+            "File is over {LARGE_MEMORY_CHARS} characters; large memory files use context on every turn."
+        ));
+    }
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        for word in trimmed.split_whitespace() {
+            let Some(import) = word.strip_prefix('@') else {
+                continue;
+            };
+            let import = import.trim_end_matches([',', '.', ';', ':', ')']);
+            if !(import.starts_with("./")
+                || import.starts_with("../")
+                || import.starts_with("~/")
+                || import.starts_with('/')
+                || import.contains('.'))
+            {
+                continue;
+            }
+            let resolved = match import.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+                None => Some(base_dir.join(import)),
+            };
+            if !resolved.is_some_and(|path| path.exists()) {
+                warnings.push(format!("Line {}: import @{import} not found", index + 1));
+            }
+        }
+    }
+    if in_fence {
+        warnings.push("Unclosed code fence.".to_string());
+    }
+    warnings
+}
+
+fn memory_diff(path: &Path, old: &str, new: &str) -> Result<String, String> {
+    let name = path.file_name().and_then(|name| name.to_str());
+    let mut patch = git2::Patch::from_buffers(old.as_bytes(), name, new.as_bytes(), name, None)
+        .map_err(|e| e.to_string())?;
+    diff_patch_to_string(&mut patch).map_err(|e| e.to_string())
+}
+
+/// Workspace CLAUDE.md / AGENTS.md plus the global ~/.claude/CLAUDE.md.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_memory_files(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<MemoryFile>, String> {
+    let mut files = Vec::new();
+    if let Some(workspace_id) = workspace_id.as_deref() {
+        for name in WORKSPACE_MEMORY_FILES {
+            let path = memory_path(&state, Some(workspace_id), name).await?;
+            files.push(read_memory_file("workspace", name, &path));
+        }
+    }
+    files.push(read_memory_file(
+        "global",
+        "CLAUDE.md",
+        &global_memory_path()?,
+    ));
+    Ok(files)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn preview_memory_file(
+    workspace_id: Option<String>,
+    name: String,
+    content: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<MemoryFilePreview, String> {
+    let path = memory_path(&state, workspace_id.as_deref(), &name).await?;
+    let current = std::fs::read_to_string(&path).unwrap_or_default();
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    Ok(MemoryFilePreview {
+        path: path.to_string_lossy().to_string(),
+        diff: memory_diff(&path, &current, &content)?,
+        warnings: validate_memory(&content, base_dir),
+    })
+}
+
+/// Save a memory file, first copying the current version to
+/// `<app data>/memory-backups/`.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn write_memory_file(
+    workspace_id: Option<String>,
+    name: String,
+    content: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<MemoryFileWrite, String> {
+    let path = memory_path(&state, workspace_id.as_deref(), &name).await?;
+    let backup_path = if path.is_file() {
+        let backups_dir = state
+            .storage_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("memory-backups");
+        std::fs::create_dir_all(&backups_dir).map_err(|e| e.to_string())?;
+        let scope = workspace_id.as_deref().unwrap_or("global");
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let backup = backups_dir.join(format!("{scope}-{stamp}-{name}"));
+        std::fs::copy(&path, &backup).map_err(|e| e.to_string())?;
+        Some(backup.to_string_lossy().to_string())
+    } else {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        None
+    };
+    std::fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(MemoryFileWrite {
+        path: path.to_string_lossy().to_string(),
+        backup_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_memory;
+    use crate::utils::TempDir;
+
+    #[test]
+    fn flags_missing_imports_and_open_fences() {
+        let dir = TempDir::new("memory-test");
+        std::fs::write(dir.join("docs.md"), "x").unwrap();
+
+        let content = "See @docs.md and @missing/guide.md, ping @alice.\n```\n@ignored.md\n";
+        let warnings = validate_memory(content, &dir);
+        assert_eq!(
+            warnings,
+            vec![
+                "Line 1: import @missing/guide.md not found".to_string(),
+                "Unclosed code fence.".to_string(),
+            ]
+        );
+    }
+}
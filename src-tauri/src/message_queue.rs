@@ -0,0 +1,435 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::automation::automation_paused;
+use crate::claude::{emit_scoped, ensure_bridge_running, reply_error, ClaudeEvent};
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::events::{ClaudeEventPayload, ErrorPayload};
+use crate::lan_share::broadcast;
+use crate::prompt_history::record_prompt;
+use crate::registry::now_millis;
+use crate::slack::mirror_user_message;
+use crate::state::AppState;
+use crate::turn_lanes::{acquire_turn_slot, release_turn_slot, Lane};
+
+/// A user message waiting for its session's running turn to end.
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QueuedMessage {
+    /// Also the `messageId` it is sent with.
+    pub(crate) id: String,
+    pub(crate) text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) images: Vec<String>,
+    pub(crate) created_at: u64,
+}
+
+/// Queued messages by session id, and the sessions with a turn in flight.
+#[derive(Debug, Default)]
+pub(crate) struct MessageQueues {
+    queues: HashMap<String, Vec<QueuedMessage>>,
+    running: HashSet<String>,
+}
+
+impl MessageQueues {
+    /// Note a turn started outside the queue, so queued messages wait for it.
+    pub(crate) fn started(&mut self, session_id: &str) {
+        self.running.insert(session_id.to_string());
+    }
+
+    /// Note the session has no turn in flight, e.g. its message failed to send.
+    pub(crate) fn idle(&mut self, session_id: &str) {
+        self.running.remove(session_id);
+    }
+
+    pub(crate) fn is_running(&self, session_id: &str) -> bool {
+        self.running.contains(session_id)
+    }
+
+    /// Whether the session is idle with messages queued, as after a pause.
+    pub(crate) fn waiting(&self, session_id: &str) -> bool {
+        !self.running.contains(session_id)
+            && self.queues.get(session_id).is_some_and(|q| !q.is_empty())
+    }
+
+    /// Queue a message without sending it, even if the session is idle.
+    fn hold(&mut self, session_id: &str, message: QueuedMessage) {
+        self.queues
+            .entry(session_id.to_string())
+            .or_default()
+            .push(message);
+    }
+
+    /// Queue a message. Returns it instead if the session is idle, to be
+    /// sent right away.
+    fn push(&mut self, session_id: &str, message: QueuedMessage) -> Option<QueuedMessage> {
+        if self.running.insert(session_id.to_string()) {
+            return Some(message);
+        }
+        self.queues
+            .entry(session_id.to_string())
+            .or_default()
+            .push(message);
+        None
+    }
+
+    /// End the session's turn. Returns the next message to send, which
+    /// starts the next turn.
+    fn finished(&mut self, session_id: &str) -> Option<QueuedMessage> {
+        let queue = self.queues.get_mut(session_id).filter(|q| !q.is_empty());
+        let Some(queue) = queue else {
+            self.running.remove(session_id);
+            return None;
+        };
+        Some(queue.remove(0))
+    }
+
+    /// Put back a message that could not be sent; the session is idle.
+    fn requeue(&mut self, session_id: &str, message: QueuedMessage) {
+        self.running.remove(session_id);
+        self.queues
+            .entry(session_id.to_string())
+            .or_default()
+            .insert(0, message);
+    }
+
+    /// Forget a closed session. Returns whether messages were dropped.
+    fn closed(&mut self, session_id: &str) -> bool {
+        self.running.remove(session_id);
+        self.queues
+            .remove(session_id)
+            .is_some_and(|queue| !queue.is_empty())
+    }
+
+    fn list(&self, session_id: &str) -> Vec<QueuedMessage> {
+        self.queues.get(session_id).cloned().unwrap_or_default()
+    }
+
+    /// Move a queued message to `index`, clamped to the end of the queue.
+    fn reorder(&mut self, session_id: &str, message_id: &str, index: usize) -> Result<(), String> {
+        let found = self.queues.get_mut(session_id).and_then(|queue| {
+            let position = queue.iter().position(|message| message.id == message_id)?;
+            Some((queue, position))
+        });
+        let Some((queue, position)) = found else {
+            return Err(format!("Queued message {} not found", message_id));
+        };
+        let message = queue.remove(position);
+        queue.insert(index.min(queue.len()), message);
+        Ok(())
+    }
+
+    fn remove(&mut self, session_id: &str, message_id: &str) -> Option<QueuedMessage> {
+        let queue = self.queues.get_mut(session_id)?;
+        let position = queue.iter().position(|message| message.id == message_id)?;
+        Some(queue.remove(position))
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct QueueChanged<'a> {
+    session_id: &'a str,
+    queue: &'a [QueuedMessage],
+}
+
+fn emit_changed(app_handle: &AppHandle, session_id: &str, queue: &[QueuedMessage]) {
+    let _ = app_handle.emit("message-queue-changed", QueueChanged { session_id, queue });
+}
+
+fn session_event(
+    session_id: &str,
+    workspace_id: &str,
+    event_type: &str,
+    payload: ClaudeEventPayload,
+) -> ClaudeEvent {
+    ClaudeEvent {
+        event_type: event_type.to_string(),
+        session_id: session_id.to_string(),
+        workspace_id: workspace_id.to_string(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        payload,
+    }
+}
+
+/// Send a queued message from a task; the caller may be the bridge reader
+/// that would receive the reply. `queue/dispatched` goes out first so the
+/// message shows before the turn's output. A failed send is put back.
+fn dispatch(app_handle: &AppHandle, session_id: String, message: QueuedMessage) {
+    let app = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let workspace_id = session_workspace(&state, &session_id).await;
+        let event = session_event(
+            &session_id,
+            &workspace_id,
+            "queue/dispatched",
+            ClaudeEventPayload::QueueDispatched(message.clone()),
+        );
+        broadcast(&app, &event);
+        emit_scoped(&app, "claude-event", &workspace_id, event);
+
+        let params = json!({
+            "sessionId": session_id,
+            "workspaceId": workspace_id,
+            "message": message.text,
+            "images": Some(&message.images).filter(|images| !images.is_empty()),
+            "messageId": message.id,
+        });
+        let sent = match acquire_turn_slot(&state, &session_id, Lane::Interactive).await {
+            Ok(()) => match ensure_bridge_running(&app, &state).await {
+                Ok(bridge) => bridge.send_request("message/send", params).await,
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(err),
+        };
+        // The bridge answers a send it could not make with `{"error": …}`.
+        let sent = sent.and_then(|response| reply_error(&response).map_or(Ok(()), Err));
+        if let Err(err) = sent {
+            release_turn_slot(&state, &session_id).await;
+            let queue = {
+                let mut queues = state.message_queues.lock().await;
+                queues.requeue(&session_id, message);
+                queues.list(&session_id)
+            };
+            emit_changed(&app, &session_id, &queue);
+            let event = session_event(
+                &session_id,
+                &workspace_id,
+                "error",
+                ClaudeEventPayload::Error(ErrorPayload {
+                    code: "QUEUE_SEND_FAILED".to_string(),
+                    message: format!("Queued message was not sent: {err}"),
+                    recoverable: false,
+                }),
+            );
+            broadcast(&app, &event);
+            emit_scoped(&app, "claude-event", &workspace_id, event);
+            log_line!("Failed to send queued message: {err}");
+            return;
+        }
+        mirror_user_message(&app, &session_id, &message.text);
+        record_prompt(&state, &workspace_id, &message.text).await;
+    });
+}
+
+async fn session_workspace(state: &AppState, session_id: &str) -> String {
+    state
+        .claude_sessions
+        .lock()
+        .await
+        .get(session_id)
+        .map(|info| info.workspace_id.clone())
+        .unwrap_or_default()
+}
+
+/// A turn ended: send the session's next queued message, if any. Nothing
+/// is sent in observer mode; the queue waits for it to be turned off and
+/// the next turn to end. While the workspace's automation is paused the
+/// queue waits for it to be resumed.
+pub(crate) async fn advance_queue(app_handle: &AppHandle, session_id: &str) {
+    let state = app_handle.state::<AppState>();
+    let observer = state.app_settings.lock().await.observer_mode;
+    let paused = automation_paused(&state, &session_workspace(&state, session_id).await).await;
+    let (next, queue) = {
+        let mut queues = state.message_queues.lock().await;
+        let next = if observer || paused {
+            queues.idle(session_id);
+            None
+        } else {
+            queues.finished(session_id)
+        };
+        (next, queues.list(session_id))
+    };
+    let Some(next) = next else {
+        return;
+    };
+    emit_changed(app_handle, session_id, &queue);
+    dispatch(app_handle, session_id.to_string(), next);
+}
+
+/// Drop a closed session's queue.
+pub(crate) async fn clear_queue(app_handle: &AppHandle, session_id: &str) {
+    let state = app_handle.state::<AppState>();
+    if state.message_queues.lock().await.closed(session_id) {
+        emit_changed(app_handle, session_id, &[]);
+    }
+}
+
+/// Queue a message for the session's next turn. Sent right away if no turn
+/// is running; otherwise sent when the queued messages ahead of it are done.
+/// While the workspace's automation is paused it waits for the resume.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn queue_message(
+    session_id: String,
+    text: String,
+    images: Option<Vec<String>>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<QueuedMessage, String> {
+    let images = images.unwrap_or_default();
+    let text = text.trim().to_string();
+    if text.is_empty() && images.is_empty() {
+        return Err("Queued message is empty".to_string());
+    }
+    let message = QueuedMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        text,
+        images,
+        created_at: now_millis(),
+    };
+    state.autonomous_turns.lock().await.user_input(&session_id);
+    let paused = automation_paused(&state, &session_workspace(&state, &session_id).await).await;
+    let (now, queue) = {
+        let mut queues = state.message_queues.lock().await;
+        let now = if paused {
+            queues.hold(&session_id, message.clone());
+            None
+        } else {
+            queues.push(&session_id, message.clone())
+        };
+        (now, queues.list(&session_id))
+    };
+    match now {
+        Some(message) => dispatch(&app_handle, session_id, message),
+        None => emit_changed(&app_handle, &session_id, &queue),
+    }
+    Ok(message)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_queued_messages(
+    session_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<QueuedMessage>, String> {
+    Ok(state.message_queues.lock().await.list(&session_id))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn reorder_queued_message(
+    session_id: String,
+    message_id: String,
+    index: usize,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<QueuedMessage>, String> {
+    let queue = {
+        let mut queues = state.message_queues.lock().await;
+        queues.reorder(&session_id, &message_id, index)?;
+        queues.list(&session_id)
+    };
+    emit_changed(&app_handle, &session_id, &queue);
+    Ok(queue)
+}
+
+/// Remove a message that hasn't been sent yet. Returns it, or `None` if it
+/// already went out.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn drop_queued_message(
+    session_id: String,
+    message_id: String,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<QueuedMessage>, String> {
+    let (dropped, queue) = {
+        let mut queues = state.message_queues.lock().await;
+        let dropped = queues.remove(&session_id, &message_id);
+        (dropped, queues.list(&session_id))
+    };
+    if dropped.is_some() {
+        emit_changed(&app_handle, &session_id, &queue);
+    }
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MessageQueues, QueuedMessage};
+
+    fn message(id: &str) -> QueuedMessage {
+        QueuedMessage {
+            id: id.to_string(),
+            text: format!("message {id}"),
+            images: Vec::new(),
+            created_at: 0,
+        }
+    }
+
+    fn ids(queues: &MessageQueues, session_id: &str) -> Vec<String> {
+        queues
+            .list(session_id)
+            .into_iter()
+            .map(|message| message.id)
+            .collect()
+    }
+
+    #[test]
+    fn sends_one_message_per_turn() {
+        let mut queues = MessageQueues::default();
+        // Idle: the first message goes straight out and starts a turn.
+        assert_eq!(queues.push("s1", message("a")), Some(message("a")));
+        assert_eq!(queues.push("s1", message("b")), None);
+        assert_eq!(queues.push("s1", message("c")), None);
+        assert_eq!(queues.push("s2", message("d")), Some(message("d")));
+        assert_eq!(ids(&queues, "s1"), ["b", "c"]);
+
+        assert_eq!(queues.finished("s1"), Some(message("b")));
+        assert_eq!(queues.finished("s1"), Some(message("c")));
+        assert_eq!(queues.finished("s1"), None);
+        // The session is idle again.
+        assert_eq!(queues.push("s1", message("e")), Some(message("e")));
+
+        queues.started("s2");
+        assert_eq!(queues.push("s2", message("f")), None);
+        queues.requeue("s2", message("g"));
+        assert_eq!(ids(&queues, "s2"), ["g", "f"]);
+        assert!(queues.closed("s2"));
+        assert!(ids(&queues, "s2").is_empty());
+        assert_eq!(queues.push("s2", message("h")), Some(message("h")));
+    }
+
+    #[test]
+    fn held_messages_wait_while_idle() {
+        let mut queues = MessageQueues::default();
+        queues.hold("s1", message("a"));
+        queues.hold("s1", message("b"));
+        assert!(queues.waiting("s1"));
+        assert!(!queues.waiting("s2"));
+        // Resuming sends the first held message and starts a turn.
+        assert_eq!(queues.finished("s1"), Some(message("a")));
+        queues.started("s1");
+        assert!(!queues.waiting("s1"));
+        assert_eq!(ids(&queues, "s1"), ["b"]);
+    }
+
+    #[test]
+    fn reorders_and_drops_queued_messages() {
+        let mut queues = MessageQueues::default();
+        queues.started("s1");
+        for id in ["a", "b", "c"] {
+            queues.push("s1", message(id));
+        }
+        queues.reorder("s1", "c", 0).unwrap();
+        assert_eq!(ids(&queues, "s1"), ["c", "a", "b"]);
+        queues.reorder("s1", "c", 10).unwrap();
+        assert_eq!(ids(&queues, "s1"), ["a", "b", "c"]);
+        assert!(queues.reorder("s1", "gone", 0).is_err());
+        assert!(queues.reorder("s2", "a", 0).is_err());
+
+        assert_eq!(queues.remove("s1", "b"), Some(message("b")));
+        assert_eq!(queues.remove("s1", "b"), None);
+        assert_eq!(ids(&queues, "s1"), ["a", "c"]);
+    }
+}
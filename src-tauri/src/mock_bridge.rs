@@ -0,0 +1,723 @@
+//! In-process stand-in for the Node bridge. It speaks the bridge's JSON-line
+//! protocol over an in-memory pipe: `initialize`, session start, resume and
+//! close, streamed message turns, permission prompts and error responses.
+//! The app side is a real `ClaudeBridge` read by the same parse, routing
+//! and validation steps as the stdout loop in `spawn_claude_bridge`, so
+//! protocol changes are caught in CI without Node. `crash` drops the mock's
+//! end of the pipe the way a dead bridge process closes its stdio.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader, DuplexStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::claude::{
+    bridge_events, parse_bridge_line, parse_error_event, ClaudeBridge, ClaudeEvent, RequestTimeouts,
+};
+
+/// Message text that makes the mock ask for a tool permission first.
+const PERMISSION_TRIGGER: &str = "permission";
+/// Message text that makes the mock write a line that is not JSON.
+const GARBAGE_TRIGGER: &str = "garbage";
+/// Message text that makes the mock send a delta missing its `event`.
+const SCHEMA_TRIGGER: &str = "schema";
+/// Method the mock never answers.
+const HANG_METHOD: &str = "mock/hang";
+
+pub(crate) struct MockBridge {
+    pub(crate) bridge: Arc<ClaudeBridge>,
+    events: mpsc::UnboundedReceiver<ClaudeEvent>,
+    server: JoinHandle<()>,
+}
+
+impl MockBridge {
+    /// Start a mock and the reader that feeds `next_event`. Needs a running
+    /// tokio runtime.
+    pub(crate) fn connect() -> Self {
+        let (app_side, mock_side) = tokio::io::duplex(64 * 1024);
+        let (stdout, stdin) = tokio::io::split(app_side);
+        let bridge = Arc::new(ClaudeBridge::new(None, stdin, RequestTimeouts::default()));
+        let (tx, events) = mpsc::unbounded_channel();
+        tokio::spawn(read_events(stdout, Arc::clone(&bridge), tx));
+        let server = tokio::spawn(serve(mock_side));
+        Self {
+            bridge,
+            events,
+            server,
+        }
+    }
+
+    /// The next event the app would emit; `None` once the bridge is gone.
+    pub(crate) async fn next_event(&mut self) -> Option<ClaudeEvent> {
+        tokio::time::timeout(Duration::from_secs(5), self.events.recv())
+            .await
+            .expect("timed out waiting for a bridge event")
+    }
+
+    /// Events up to and including the next one of `event_type`.
+    pub(crate) async fn events_until(&mut self, event_type: &str) -> Vec<ClaudeEvent> {
+        let mut events = Vec::new();
+        while let Some(event) = self.next_event().await {
+            let done = event.event_type == event_type;
+            events.push(event);
+            if done {
+                return events;
+            }
+        }
+        panic!("bridge closed before {event_type}: {events:?}");
+    }
+
+    /// Kill the mock mid-session.
+    pub(crate) fn crash(&self) {
+        self.server.abort();
+    }
+}
+
+/// The app side of the stdout loop, minus batching, the registry hooks and
+/// restarts.
+async fn read_events(
+    stdout: impl AsyncRead + Unpin,
+    bridge: Arc<ClaudeBridge>,
+    tx: mpsc::UnboundedSender<ClaudeEvent>,
+) {
+    let mut lines = BufReader::new(stdout).split(b'\n');
+    while let Ok(Some(line)) = lines.next_segment().await {
+        let events = match parse_bridge_line(&line) {
+            Ok(Some(line)) => {
+                if bridge.resolve_response(&line).await {
+                    continue;
+                }
+                bridge_events(&line)
+            }
+            Ok(None) => continue,
+            Err(err) => vec![parse_error_event(&err)],
+        };
+        for event in events {
+            if tx.send(event).is_err() {
+                return;
+            }
+        }
+    }
+    bridge.mark_dead().await;
+}
+
+#[derive(Default)]
+struct MockState {
+    initialized: bool,
+    /// Session id to workspace id.
+    sessions: HashMap<String, String>,
+    next_id: u64,
+    /// Tool use id to the session waiting on it.
+    permissions: HashMap<String, String>,
+}
+
+/// Bridge output for one command: its response, then any events.
+struct Reply {
+    result: Result<Value, String>,
+    lines: Vec<String>,
+}
+
+impl Reply {
+    fn ok(result: Value) -> Self {
+        Self {
+            result: Ok(result),
+            lines: Vec::new(),
+        }
+    }
+
+    fn err(message: impl Into<String>) -> Self {
+        Self {
+            result: Err(message.into()),
+            lines: Vec::new(),
+        }
+    }
+
+    fn event(
+        mut self,
+        event_type: &str,
+        session_id: &str,
+        workspace_id: &str,
+        payload: Value,
+    ) -> Self {
+        self.lines.push(
+            json!({
+                "type": event_type,
+                "sessionId": session_id,
+                "workspaceId": workspace_id,
+                "timestamp": 1_700_000_000_000i64,
+                "payload": payload,
+            })
+            .to_string(),
+        );
+        self
+    }
+}
+
+async fn serve(stream: DuplexStream) {
+    let (stdin, mut stdout) = tokio::io::split(stream);
+    let mut lines = BufReader::new(stdin).lines();
+    let mut state = MockState::default();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Ok(command) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let method = command["method"].as_str().unwrap_or_default();
+        if method == HANG_METHOD {
+            continue;
+        }
+        let reply = state.handle(method, &command["params"]);
+        let mut out = String::new();
+        if let Some(id) = command["id"].as_u64() {
+            let payload = match reply.result {
+                Ok(result) => json!({ "id": id, "result": result }),
+                Err(error) => json!({ "id": id, "error": error }),
+            };
+            out.push_str(&json!({ "type": "response", "payload": payload }).to_string());
+            out.push('\n');
+        }
+        for line in reply.lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        if stdout.write_all(out.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+impl MockState {
+    fn handle(&mut self, method: &str, params: &Value) -> Reply {
+        if method == "ping" {
+            return Reply::ok(json!({ "pong": true }));
+        }
+        if method == "initialize" {
+            if self.initialized {
+                return Reply::err("Bridge already initialized");
+            }
+            self.initialized = true;
+            return Reply::ok(json!({
+                "version": "mock",
+                "capabilities": ["session/start", "session/resume", "message/send", "permission/respond"],
+            }));
+        }
+        if !self.initialized {
+            return Reply::err("Bridge not initialized");
+        }
+        let str_param = |key: &str| params[key].as_str().unwrap_or_default().to_string();
+        match method {
+            "session/start" | "session/resume" => {
+                let session_id = if method == "session/resume" {
+                    str_param("sessionId")
+                } else {
+                    self.next_id += 1;
+                    format!("mock-session-{}", self.next_id)
+                };
+                let workspace_id = str_param("workspaceId");
+                self.sessions
+                    .insert(session_id.clone(), workspace_id.clone());
+                Reply::ok(json!({ "sessionId": session_id })).event(
+                    "session/started",
+                    &session_id,
+                    &workspace_id,
+                    json!({
+                        "model": params["model"].as_str().unwrap_or("claude-sonnet"),
+                        "tools": ["Read", "Edit", "Bash"],
+                        "cwd": str_param("cwd"),
+                        "claudeCodeVersion": "mock",
+                        "permissionMode": params["permissionMode"].as_str().unwrap_or("default"),
+                        "mcpServers": [],
+                    }),
+                )
+            }
+            "session/close" => {
+                let session_id = str_param("sessionId");
+                match self.sessions.remove(&session_id) {
+                    Some(workspace_id) => Reply::ok(json!({ "success": true })).event(
+                        "session/closed",
+                        &session_id,
+                        &workspace_id,
+                        json!({ "reason": "user" }),
+                    ),
+                    None => Reply::err(format!("Session not found: {session_id}")),
+                }
+            }
+            "message/send" => {
+                let session_id = str_param("sessionId");
+                let Some(workspace_id) = self.sessions.get(&session_id).cloned() else {
+                    return Reply::err(format!("Session not found: {session_id}"));
+                };
+                let message = str_param("message");
+                let mut reply = Reply::ok(json!({ "success": true }));
+                if message.contains(PERMISSION_TRIGGER) {
+                    self.next_id += 1;
+                    let tool_use_id = format!("tool-{}", self.next_id);
+                    self.permissions
+                        .insert(tool_use_id.clone(), session_id.clone());
+                    return reply.event(
+                        "permission/request",
+                        &session_id,
+                        &workspace_id,
+                        json!({
+                            "toolName": "Bash",
+                            "toolUseId": tool_use_id,
+                            "input": { "command": "cargo test" },
+                        }),
+                    );
+                }
+                if message.contains(GARBAGE_TRIGGER) {
+                    reply.lines.push("{not json".to_string());
+                }
+                if message.contains(SCHEMA_TRIGGER) {
+                    reply = reply.event(
+                        "message/delta",
+                        &session_id,
+                        &workspace_id,
+                        json!({ "parentToolUseId": null }),
+                    );
+                }
+                turn(
+                    reply,
+                    &session_id,
+                    &workspace_id,
+                    &format!("echo: {message}"),
+                )
+            }
+            "permission/respond" => {
+                let tool_use_id = str_param("toolUseId");
+                let Some(session_id) = self.permissions.remove(&tool_use_id) else {
+                    return Reply::err(format!("No pending permission request: {tool_use_id}"));
+                };
+                let workspace_id = self.sessions.get(&session_id).cloned().unwrap_or_default();
+                let reply = Reply::ok(json!({ "success": true }));
+                if params["decision"] != "allow" {
+                    return turn(reply, &session_id, &workspace_id, "Permission denied.");
+                }
+                let reply = reply
+                    .event(
+                        "tool/started",
+                        &session_id,
+                        &workspace_id,
+                        json!({
+                            "toolName": "Bash",
+                            "toolUseId": tool_use_id,
+                            "input": { "command": "cargo test" },
+                            "parentToolUseId": null,
+                        }),
+                    )
+                    .event(
+                        "tool/completed",
+                        &session_id,
+                        &workspace_id,
+                        json!({ "toolName": "Bash", "toolUseId": tool_use_id, "output": "ok" }),
+                    );
+                turn(reply, &session_id, &workspace_id, "Tests pass.")
+            }
+            _ => Reply::err(format!("Unknown method: {method}")),
+        }
+    }
+}
+
+/// An assistant turn: one delta per word, the complete message, the result.
+fn turn(mut reply: Reply, session_id: &str, workspace_id: &str, text: &str) -> Reply {
+    for word in text.split_inclusive(' ') {
+        reply = reply.event(
+            "message/delta",
+            session_id,
+            workspace_id,
+            json!({
+                "event": {
+                    "type": "content_block_delta",
+                    "index": 0,
+                    "delta": { "type": "text_delta", "text": word },
+                },
+                "parentToolUseId": null,
+            }),
+        );
+    }
+    reply
+        .event(
+            "message/complete",
+            session_id,
+            workspace_id,
+            json!({
+                "uuid": format!("{session_id}-{}", text.len()),
+                "message": { "role": "assistant", "content": [{ "type": "text", "text": text }] },
+                "parentToolUseId": null,
+            }),
+        )
+        .event(
+            "result",
+            session_id,
+            workspace_id,
+            json!({
+                "success": true,
+                "subtype": "success",
+                "result": text,
+                "durationMs": 5,
+                "numTurns": 1,
+                "totalCostUsd": 0.001,
+                "usage": {
+                    "inputTokens": 10,
+                    "outputTokens": text.split_whitespace().count(),
+                    "cacheReadInputTokens": 0,
+                    "cacheCreationInputTokens": 0,
+                },
+            }),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+
+    use super::{MockBridge, HANG_METHOD};
+    use crate::claude::{BRIDGE_DIED, REQUEST_CANCELED, REQUEST_TIMEOUT};
+    use crate::events::ClaudeEventPayload;
+
+    fn run(test: impl Future<Output = ()>) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+            .block_on(test);
+    }
+
+    async fn start_session(mock: &MockBridge) -> String {
+        let response = mock
+            .bridge
+            .send_request("initialize", json!({ "version": "1" }))
+            .await
+            .unwrap();
+        assert!(response["result"]["capabilities"].is_array());
+        let response = mock
+            .bridge
+            .send_request(
+                "session/start",
+                json!({ "workspaceId": "w1", "cwd": "/repo", "model": "claude-opus" }),
+            )
+            .await
+            .unwrap();
+        response["result"]["sessionId"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn text_of(payload: &ClaudeEventPayload) -> String {
+        match payload {
+            ClaudeEventPayload::MessageDelta(delta) => {
+                delta.event["delta"]["text"].as_str().unwrap().to_string()
+            }
+            other => panic!("expected a delta, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn session_lifecycle_round_trip() {
+        run(async {
+            let mut mock = MockBridge::connect();
+            let session_id = start_session(&mock).await;
+
+            let started = mock.next_event().await.unwrap();
+            assert_eq!(started.event_type, "session/started");
+            assert_eq!(
+                (started.session_id.as_str(), started.workspace_id.as_str()),
+                (session_id.as_str(), "w1")
+            );
+            let ClaudeEventPayload::SessionStarted(ref payload) = started.payload else {
+                panic!("untyped session/started: {started:?}");
+            };
+            assert_eq!(
+                (payload.model.as_str(), payload.cwd.as_str()),
+                ("claude-opus", "/repo")
+            );
+
+            let response = mock
+                .bridge
+                .send_request("session/close", json!({ "sessionId": session_id }))
+                .await
+                .unwrap();
+            assert_eq!(response["result"]["success"], true);
+            let closed = mock.next_event().await.unwrap();
+            assert!(matches!(
+                closed.payload,
+                ClaudeEventPayload::SessionClosed(ref payload) if payload.reason == "user"
+            ));
+        });
+    }
+
+    #[test]
+    fn message_turn_streams_typed_events() {
+        run(async {
+            let mut mock = MockBridge::connect();
+            let session_id = start_session(&mock).await;
+            mock.events_until("session/started").await;
+
+            let response = mock
+                .bridge
+                .send_request(
+                    "message/send",
+                    json!({ "sessionId": session_id, "message": "hello there" }),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response["result"]["success"], true);
+
+            let events = mock.events_until("result").await;
+            let types: Vec<&str> = events.iter().map(|e| e.event_type.as_str()).collect();
+            assert_eq!(
+                types,
+                [
+                    "message/delta",
+                    "message/delta",
+                    "message/delta",
+                    "message/complete",
+                    "result"
+                ]
+            );
+            let streamed: String = events[..3].iter().map(|e| text_of(&e.payload)).collect();
+            assert_eq!(streamed, "echo: hello there");
+            assert!(matches!(
+                events[3].payload,
+                ClaudeEventPayload::MessageComplete(_)
+            ));
+            let ClaudeEventPayload::Result(ref result) = events[4].payload else {
+                panic!("untyped result: {:?}", events[4]);
+            };
+            assert!(result.success);
+            assert_eq!(result.usage.output_tokens, 3);
+        });
+    }
+
+    #[test]
+    fn permission_request_waits_for_response() {
+        run(async {
+            let mut mock = MockBridge::connect();
+            let session_id = start_session(&mock).await;
+            mock.events_until("session/started").await;
+
+            mock.bridge
+                .send_request(
+                    "message/send",
+                    json!({ "sessionId": session_id, "message": "needs permission" }),
+                )
+                .await
+                .unwrap();
+            let request = mock.next_event().await.unwrap();
+            let ClaudeEventPayload::PermissionRequest(ref payload) = request.payload else {
+                panic!("untyped permission/request: {request:?}");
+            };
+            assert_eq!(payload.tool_name, "Bash");
+            let tool_use_id = payload.tool_use_id.clone();
+
+            let response = mock
+                .bridge
+                .send_request(
+                    "permission/respond",
+                    json!({ "sessionId": session_id, "toolUseId": tool_use_id, "decision": "allow" }),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response["result"]["success"], true);
+            let events = mock.events_until("result").await;
+            assert!(matches!(
+                events[0].payload,
+                ClaudeEventPayload::ToolStarted(_)
+            ));
+            assert!(matches!(
+                events[1].payload,
+                ClaudeEventPayload::ToolCompleted(_)
+            ));
+
+            // Answered prompts can't be answered again.
+            let response = mock
+                .bridge
+                .send_request(
+                    "permission/respond",
+                    json!({ "sessionId": session_id, "toolUseId": tool_use_id, "decision": "allow" }),
+                )
+                .await
+                .unwrap();
+            assert!(response["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("No pending permission"));
+        });
+    }
+
+    #[test]
+    fn errors_reach_the_caller_and_the_event_stream() {
+        run(async {
+            let mut mock = MockBridge::connect();
+            let response = mock
+                .bridge
+                .send_request("session/start", json!({ "workspaceId": "w1" }))
+                .await
+                .unwrap();
+            assert_eq!(response, json!({ "error": "Bridge not initialized" }));
+
+            let session_id = start_session(&mock).await;
+            mock.events_until("session/started").await;
+            let response = mock
+                .bridge
+                .send_request("session/fork", Value::Null)
+                .await
+                .unwrap();
+            assert_eq!(response["error"], "Unknown method: session/fork");
+
+            mock.bridge
+                .send_request(
+                    "message/send",
+                    json!({ "sessionId": session_id, "message": "garbage and schema" }),
+                )
+                .await
+                .unwrap();
+            let events = mock.events_until("result").await;
+            let ClaudeEventPayload::Error(ref parse) = events[0].payload else {
+                panic!("expected a parse error: {:?}", events[0]);
+            };
+            assert_eq!(parse.code, "PARSE_ERROR");
+            let ClaudeEventPayload::Error(ref mismatch) = events[1].payload else {
+                panic!("expected a schema error: {:?}", events[1]);
+            };
+            assert_eq!(mismatch.code, "SCHEMA_MISMATCH");
+            assert_eq!(events[1].session_id, session_id);
+            // The bad payload is still forwarded, raw, and the turn goes on.
+            assert_eq!(events[2].event_type, "message/delta");
+            assert!(matches!(events[2].payload, ClaudeEventPayload::Raw(_)));
+            assert!(matches!(
+                events[3].payload,
+                ClaudeEventPayload::MessageDelta(_)
+            ));
+        });
+    }
+
+    #[test]
+    fn crash_closes_the_stream_and_a_new_bridge_resumes() {
+        run(async {
+            let mut mock = MockBridge::connect();
+            let session_id = start_session(&mock).await;
+            mock.events_until("session/started").await;
+
+            mock.crash();
+            assert!(mock.next_event().await.is_none());
+            assert!(!mock.bridge.is_alive());
+            let err = mock
+                .bridge
+                .send_request(
+                    "message/send",
+                    json!({ "sessionId": session_id, "message": "still there?" }),
+                )
+                .await
+                .unwrap_err();
+            assert!(err.starts_with(BRIDGE_DIED), "{err}");
+
+            // A restarted bridge has no sessions until they are resumed.
+            let mut restarted = MockBridge::connect();
+            restarted
+                .bridge
+                .send_request("initialize", json!({ "version": "1" }))
+                .await
+                .unwrap();
+            let response = restarted
+                .bridge
+                .send_request(
+                    "message/send",
+                    json!({ "sessionId": session_id, "message": "hello" }),
+                )
+                .await
+                .unwrap();
+            assert!(response["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("Session not found"));
+            restarted
+                .bridge
+                .send_request(
+                    "session/resume",
+                    json!({ "sessionId": session_id, "workspaceId": "w1", "cwd": "/repo" }),
+                )
+                .await
+                .unwrap();
+            let started = restarted.next_event().await.unwrap();
+            assert_eq!(started.session_id, session_id);
+            restarted
+                .bridge
+                .send_request(
+                    "message/send",
+                    json!({ "sessionId": session_id, "message": "hello" }),
+                )
+                .await
+                .unwrap();
+            let events = restarted.events_until("result").await;
+            assert!(matches!(
+                events.last().unwrap().payload,
+                ClaudeEventPayload::Result(_)
+            ));
+        });
+    }
+
+    #[test]
+    fn unanswered_requests_time_out_or_are_canceled() {
+        run(async {
+            let mock = MockBridge::connect();
+            let err = mock
+                .bridge
+                .send_request_with_timeout(HANG_METHOD, json!({}), Some(Duration::from_millis(20)))
+                .await
+                .unwrap_err();
+            assert!(err.starts_with(REQUEST_TIMEOUT), "{err}");
+            assert!(mock.bridge.pending_requests().await.is_empty());
+
+            let bridge = Arc::clone(&mock.bridge);
+            let waiting = tokio::spawn(async move {
+                bridge
+                    .send_request_with_timeout(HANG_METHOD, json!({}), None)
+                    .await
+            });
+            let request = loop {
+                if let Some(request) = mock.bridge.pending_requests().await.pop() {
+                    break request;
+                }
+                tokio::task::yield_now().await;
+            };
+            assert_eq!(request.method, HANG_METHOD);
+            assert!(mock.bridge.cancel_request(request.id).await);
+            assert!(!mock.bridge.cancel_request(request.id).await);
+            let err = waiting.await.unwrap().unwrap_err();
+            assert!(err.starts_with(REQUEST_CANCELED), "{err}");
+        });
+    }
+
+    #[test]
+    fn requests_in_flight_fail_when_the_bridge_dies() {
+        run(async {
+            let mut mock = MockBridge::connect();
+            let bridge = Arc::clone(&mock.bridge);
+            let waiting = tokio::spawn(async move {
+                bridge
+                    .send_request_with_timeout(HANG_METHOD, json!({}), None)
+                    .await
+            });
+            while mock.bridge.pending_requests().await.is_empty() {
+                tokio::task::yield_now().await;
+            }
+
+            mock.crash();
+            assert!(mock.next_event().await.is_none());
+            let err = waiting.await.unwrap().unwrap_err();
+            assert!(err.starts_with(BRIDGE_DIED), "{err}");
+            assert!(err.contains(HANG_METHOD), "{err}");
+            assert!(mock.bridge.pending_requests().await.is_empty());
+            assert!(mock.bridge.mark_dead().await.is_empty());
+        });
+    }
+}
@@ -0,0 +1,232 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::claude::complete_text;
+use crate::command_metrics::CommandTimer;
+use crate::registry::resolve_transcript_path;
+use crate::state::AppState;
+use crate::transcripts::{chunk_messages, transcript_messages, SUMMARY_CHUNK_CHARS, SUMMARY_MODEL};
+
+/// Notes files we append to, in order of preference when none is named.
+const NOTES_FILES: &[&str] = &["CLAUDE.md", "NOTES.md"];
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DecisionExtraction {
+    session_id: String,
+    workspace_id: String,
+    /// File the decisions would be appended to.
+    notes_path: String,
+    notes_exists: bool,
+    /// Decisions not already present in the notes file.
+    decisions: Vec<String>,
+}
+
+/// Bullet lines from a model reply, minus markers and "none" replies.
+fn parse_bullets(reply: &str) -> Vec<String> {
+    reply
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            let text = trimmed
+                .strip_prefix("- ")
+                .or_else(|| trimmed.strip_prefix("* "))?
+                .trim();
+            (!text.is_empty()).then(|| text.to_string())
+        })
+        .filter(|text| !text.eq_ignore_ascii_case("none"))
+        .collect()
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches('.')
+        .to_lowercase()
+}
+
+/// Drop decisions repeated across chunks or already recorded in the notes.
+fn new_decisions(candidates: Vec<String>, existing_notes: &str) -> Vec<String> {
+    let existing = normalize(existing_notes);
+    let mut seen: Vec<String> = Vec::new();
+    let mut decisions = Vec::new();
+    for candidate in candidates {
+        let key = normalize(&candidate);
+        if key.is_empty() || seen.contains(&key) || existing.contains(&key) {
+            continue;
+        }
+        seen.push(key);
+        decisions.push(candidate);
+    }
+    decisions
+}
+
+fn notes_path_for(workspace_path: &Path, file_name: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(name) = file_name {
+        if !NOTES_FILES.contains(&name) {
+            return Err(format!("Unsupported notes file: {name}"));
+        }
+        return Ok(workspace_path.join(name));
+    }
+    Ok(NOTES_FILES
+        .iter()
+        .map(|name| workspace_path.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| workspace_path.join(NOTES_FILES[0])))
+}
+
+fn render_notes_section(session_id: &str, decisions: &[String]) -> String {
+    let short_id: String = session_id.chars().take(8).collect();
+    let date = chrono::Local::now().format("%Y-%m-%d");
+    let mut section = format!("\n## Decisions from session {short_id} ({date})\n\n");
+    for decision in decisions {
+        section.push_str(&format!("- {decision}\n"));
+    }
+    section
+}
+
+async fn workspace_for_session(
+    state: &State<'_, AppState>,
+    session_id: &str,
+) -> Result<(String, PathBuf), String> {
+    let workspace_id = {
+        let registry = state.registry.lock().await;
+        registry
+            .workspaces
+            .iter()
+            .find(|(_, workspace)| {
+                workspace
+                    .visible_session_ids
+                    .iter()
+                    .any(|id| id == session_id)
+            })
+            .map(|(id, _)| id.clone())
+            .ok_or("Session is not attached to a workspace.")?
+    };
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces.get(&workspace_id).ok_or("workspace not found")?;
+    Ok((workspace_id, PathBuf::from(&entry.path)))
+}
+
+/// Propose decisions and conventions stated in a session. Nothing is
+/// written; the UI confirms and calls `append_workspace_notes`.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn extract_decisions(
+    session_id: String,
+    notes_file: Option<String>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<DecisionExtraction, String> {
+    let (workspace_id, workspace_path) = workspace_for_session(&state, &session_id).await?;
+    let path = {
+        let registry = state.registry.lock().await;
+        resolve_transcript_path(&registry, &session_id)?
+    };
+    let messages = tokio::task::spawn_blocking(move || transcript_messages(&path))
+        .await
+        .map_err(|_| "transcript read failed".to_string())??;
+
+    let system_prompt = "You extract durable project knowledge from coding-agent sessions: \
+        decisions made, conventions agreed, constraints discovered. Ignore progress updates, \
+        one-off debugging and anything only true during the session. Reply with one short, \
+        self-contained bullet per item starting with \"- \", or \"- none\".";
+    let mut candidates = Vec::new();
+    for chunk in chunk_messages(&messages, SUMMARY_CHUNK_CHARS) {
+        let reply = complete_text(
+            &app_handle,
+            &state,
+            Some(&workspace_id),
+            SUMMARY_MODEL,
+            system_prompt,
+            chunk,
+        )
+        .await?;
+        candidates.extend(parse_bullets(&reply));
+    }
+
+    let notes_path = notes_path_for(&workspace_path, notes_file.as_deref())?;
+    let existing = std::fs::read_to_string(&notes_path).unwrap_or_default();
+    Ok(DecisionExtraction {
+        session_id,
+        workspace_id,
+        notes_exists: notes_path.is_file(),
+        notes_path: notes_path.to_string_lossy().to_string(),
+        decisions: new_decisions(candidates, &existing),
+    })
+}
+
+/// Append confirmed decisions to the workspace notes file, creating it if
+/// needed. Returns the path written.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn append_workspace_notes(
+    workspace_id: String,
+    session_id: String,
+    decisions: Vec<String>,
+    notes_file: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<String, String> {
+    let decisions: Vec<String> = decisions
+        .into_iter()
+        .map(|decision| decision.trim().to_string())
+        .filter(|decision| !decision.is_empty())
+        .collect();
+    if decisions.is_empty() {
+        return Err("No decisions to append.".to_string());
+    }
+    let workspace_path = {
+        let workspaces = state.workspaces.lock().await;
+        PathBuf::from(
+            &workspaces
+                .get(&workspace_id)
+                .ok_or("workspace not found")?
+                .path,
+        )
+    };
+    let notes_path = notes_path_for(&workspace_path, notes_file.as_deref())?;
+    let mut contents = std::fs::read_to_string(&notes_path).unwrap_or_default();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str(&render_notes_section(&session_id, &decisions));
+    std::fs::write(&notes_path, contents).map_err(|e| e.to_string())?;
+    Ok(notes_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_decisions, parse_bullets};
+
+    #[test]
+    fn parses_bullets_and_skips_none() {
+        let reply = "Here you go:\n- Use pnpm, not npm.\n* Keep threads.json backwards compatible\n- none\n";
+        assert_eq!(
+            parse_bullets(reply),
+            vec![
+                "Use pnpm, not npm.",
+                "Keep threads.json backwards compatible"
+            ]
+        );
+        assert!(parse_bullets("- None").is_empty());
+    }
+
+    #[test]
+    fn skips_duplicates_and_recorded_decisions() {
+        let existing = "# Notes\n\n- Use pnpm,  not npm\n";
+        let decisions = new_decisions(
+            vec![
+                "Use pnpm, not npm.".to_string(),
+                "Errors are plain strings".to_string(),
+                "errors are plain strings.".to_string(),
+            ],
+            existing,
+        );
+        assert_eq!(decisions, vec!["Errors are plain strings"]);
+    }
+}
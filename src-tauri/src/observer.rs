@@ -0,0 +1,164 @@
+//! Observer mode: a read-only app for screensharing, demos or a teammate
+//! watching a session. Every IPC call is checked here before it reaches its
+//! command, so only the commands in `READ_ONLY_COMMANDS` run while it is on
+//! and new commands are refused until they are listed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::settings::OBSERVER_MODE_ERROR;
+
+static OBSERVER_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Commands that only read state, plus `update_app_settings` so observer
+/// mode can be turned off again. The last few check their own arguments
+/// with `ensure_not_observer`: denials and dry runs are allowed.
+const READ_ONLY_COMMANDS: &[&str] = &[
+    "get_app_settings",
+    "update_app_settings",
+    "get_appearance",
+    "get_do_not_disturb",
+    "set_do_not_disturb",
+    "get_auto_approve",
+    "get_audit_log",
+    "codex_doctor",
+    "list_workspaces",
+    "is_state_ready",
+    "connect_workspace",
+    "get_worktree_overview",
+    "list_stale_worktrees",
+    "list_threads",
+    "get_git_status",
+    "get_git_diffs",
+    "get_file_diff",
+    "check_push_objects",
+    "check_commit_size",
+    "get_changed_files_owners",
+    "summarize_changes",
+    "scan_diff_for_secrets",
+    "get_dependency_changes",
+    "get_git_log",
+    "generate_changelog",
+    "get_repo_stats",
+    "get_command_metrics",
+    "get_git_remote",
+    "get_github_issues",
+    "list_workspace_files",
+    "list_git_branches",
+    "model_list",
+    "account_rate_limits",
+    "skills_list",
+    "prompts_list",
+    "search_prompt_history",
+    "get_recent_prompts",
+    "list_snippets",
+    "get_glossary",
+    "get_team_prompts_status",
+    "get_visible_sessions",
+    "scan_available_sessions",
+    "get_session_history",
+    "get_archived_sessions",
+    "get_ticket_status",
+    "search_tickets",
+    "get_session_ticket",
+    "set_attended_session",
+    "get_time_report",
+    "replay_session",
+    "stop_replay",
+    "list_crash_reports",
+    "get_bridge_health",
+    "get_proxy_status",
+    "test_proxy",
+    "check_ca_bundle",
+    "get_locale_info",
+    "format_timestamp",
+    "get_system_ui_prefs",
+    "get_about_info",
+    "take_launch_deep_link",
+    "get_power_status",
+    "get_session_history_page",
+    "mark_session_read",
+    "list_lan_shares",
+    "join_lan_share",
+    "leave_lan_share",
+    "get_slack_status",
+    "generate_digest",
+    "usage_summary",
+    "get_model_pricing",
+    "count_tokens",
+    "get_session_files",
+    "list_editors",
+    "highlight_file",
+    "reindex_sessions",
+    "compare_sessions",
+    "semantic_search",
+    "list_memory_files",
+    "preview_memory_file",
+    "get_hooks",
+    "list_hook_templates",
+    "inspect_claude_settings",
+    "request_confirmation",
+    "claude_doctor",
+    "list_queued_messages",
+    "list_workflows",
+    "list_workflow_runs",
+    "claude_list_models",
+    "claude_list_commands",
+    "claude_mcp_status",
+    "claude_list_requests",
+    "list_accounts",
+    "has_anthropic_api_key",
+    "setup_detect_clis",
+    "setup_list_project_candidates",
+    "setup_check_claude_dir",
+    "scan_existing_projects",
+    "scan_all_claude_projects",
+    "claude_respond_permission",
+    "claude_rewind_files",
+    "create_release",
+];
+
+/// Turn observer mode on or off, following the setting.
+pub(crate) fn set_observer_mode(enabled: bool) {
+    OBSERVER_MODE.store(enabled, Ordering::Relaxed);
+}
+
+fn refusal(command: &str, observer: bool) -> Option<String> {
+    if !observer || READ_ONLY_COMMANDS.contains(&command) {
+        return None;
+    }
+    Some(format!(
+        "{OBSERVER_MODE_ERROR}: {command} is disabled in observer mode."
+    ))
+}
+
+/// The error to reject `command` with, or `None` when it may run.
+pub(crate) fn refused(command: &str) -> Option<String> {
+    refusal(command, OBSERVER_MODE.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::refusal;
+
+    #[test]
+    fn only_read_only_commands_run_in_observer_mode() {
+        assert_eq!(refusal("claude_start_session", false), None);
+        assert_eq!(refusal("get_git_diffs", true), None);
+        assert_eq!(refusal("update_app_settings", true), None);
+        assert_eq!(
+            refusal("claude_set_mcp_servers", true).as_deref(),
+            Some("OBSERVER_MODE: claude_set_mcp_servers is disabled in observer mode.")
+        );
+        for command in [
+            "claude_start_session",
+            "set_session_priority",
+            "import_session_bundle",
+            "sync_team_prompts",
+            "post_ticket_summary",
+            "add_workspace",
+            "update_workspace_settings",
+        ] {
+            assert!(refusal(command, true).is_some(), "{command}");
+        }
+    }
+}
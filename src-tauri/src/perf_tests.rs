@@ -0,0 +1,61 @@
+//! Timing checks for transcript parsing, project scans and registry writes
+//! over generated fixtures. `perf_smoke` runs with `cargo test` against
+//! budgets far above normal timings, so only a real regression trips it.
+//! Timings for larger inputs come from the criterion benches:
+//! `cargo bench --bench registry`.
+
+use std::time::{Duration, Instant};
+
+use crate::bench_support::{parse_history, write_transcript, Fixture, Registry, Scanner};
+
+fn time<T>(label: &str, budget: Duration, run: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = run();
+    let elapsed = start.elapsed();
+    println!("{label}: {elapsed:?}");
+    assert!(
+        elapsed <= budget,
+        "{label} took {elapsed:?}, over its {budget:?} budget"
+    );
+    result
+}
+
+#[test]
+fn perf_smoke() {
+    // Debug builds on a loaded CI machine; release timings are far lower.
+    let budget = Duration::from_secs(10);
+
+    let fixture = Fixture::new();
+    let transcript = fixture.project_dir().join("history.jsonl");
+    write_transcript(&transcript, "history", &fixture.cwd(), 10_000);
+    let items = time("parse_session_history 10000 lines", budget, || {
+        parse_history(&fixture, &transcript)
+    });
+    // User and assistant text and tool calls become items; tool results
+    // complete their call and system entries are skipped.
+    assert_eq!(items.len(), 10_000 / 4 * 3);
+    assert!(items
+        .iter()
+        .filter(|item| item["kind"] == "tool")
+        .all(|item| item["status"] == "completed"));
+
+    let fixture = Fixture::new();
+    fixture.write_transcripts(200, 100);
+    let mut scanner = Scanner::default();
+    let sessions = time("scan_project_dir 200 transcripts", budget, || {
+        scanner.scan(&fixture)
+    });
+    assert_eq!(sessions, 200);
+    // A second scan is served from the cache.
+    time("scan_project_dir cached", budget / 4, || {
+        scanner.scan(&fixture)
+    });
+
+    let fixture = Fixture::new();
+    let registry = Registry::new(5_000, &fixture.cwd());
+    let path = fixture.root().join("threads.json");
+    time("write_registry 5000 sessions", budget, || {
+        registry.write(&path)
+    });
+    assert!(std::fs::metadata(&path).unwrap().len() > 0);
+}
@@ -0,0 +1,373 @@
+#[cfg(any(not(any(target_os = "macos", target_os = "windows")), test))]
+use std::path::Path;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::time::sleep;
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::heartbeat::check_bridge;
+use crate::state::AppState;
+use crate::types::PowerSavingSettings;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use crate::ui_prefs::query;
+
+/// How often the wall clock is checked against the timer. There is no
+/// sleep/wake notification without native bindings, so a wake shows up as
+/// a tick that took far longer on the wall clock than it was set for.
+const SLEEP_POLL: Duration = Duration::from_secs(5);
+/// Extra wall-clock time in one tick that counts as a sleep rather than a
+/// busy runtime or a clock correction.
+const MIN_SLEEP: Duration = Duration::from_secs(30);
+/// How long schedulers hold off after a wake when `pauseSchedulersOnSleep`
+/// is on, so the network and the bridge are back before they run.
+const SCHEDULER_GRACE: Duration = Duration::from_secs(120);
+
+/// How often the power source and thermal state are re-read.
+const POWER_POLL: Duration = Duration::from_secs(30);
+
+/// When the system last woke up.
+static WOKE_AT: Mutex<Option<Instant>> = Mutex::new(None);
+/// Latest power reading, so session starts don't wait on a query.
+static SOURCE: RwLock<PowerSource> = RwLock::new(PowerSource {
+    on_battery: false,
+    thermal_pressure: false,
+});
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct PowerSource {
+    on_battery: bool,
+    thermal_pressure: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PowerStatus {
+    on_battery: bool,
+    thermal_pressure: bool,
+    /// Power saving is on and the machine is on battery or throttled.
+    throttled: bool,
+    /// Open sessions allowed right now; `None` for no limit.
+    max_sessions: Option<u32>,
+    indexing_paused: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SystemWake {
+    slept_ms: u64,
+    /// Turns that were running when the system went to sleep; their
+    /// requests may have been dropped.
+    running_turns: usize,
+    /// Whether the bridge answered a ping after waking; `None` when it
+    /// wasn't running.
+    bridge_ok: Option<bool>,
+}
+
+/// Time asleep during a tick of `expected` that took `wall` on the wall
+/// clock, if long enough to count.
+fn slept_for(expected: Duration, wall: Duration) -> Option<Duration> {
+    wall.checked_sub(expected).filter(|gap| *gap >= MIN_SLEEP)
+}
+
+/// Whether schedulers should skip their run: `pauseSchedulersOnSleep` is on
+/// and the system woke less than `SCHEDULER_GRACE` ago.
+pub(crate) async fn schedulers_paused(state: &AppState) -> bool {
+    if !state.app_settings.lock().await.pause_schedulers_on_sleep {
+        return false;
+    }
+    WOKE_AT
+        .lock()
+        .ok()
+        .and_then(|woke_at| *woke_at)
+        .is_some_and(|woke_at| woke_at.elapsed() < SCHEDULER_GRACE)
+}
+
+/// Check the bridge and emit `system-wake` so the UI can refresh workspaces
+/// and rate limits and warn about interrupted turns.
+async fn handle_wake(app: &AppHandle, slept: Duration) {
+    if let Ok(mut woke_at) = WOKE_AT.lock() {
+        *woke_at = Some(Instant::now());
+    }
+    let state = app.state::<AppState>();
+    let running_turns = state.turns.lock().await.activity().running;
+    log_line!(
+        "System woke after {}s asleep with {running_turns} turns running",
+        slept.as_secs()
+    );
+    let bridge_ok = check_bridge(app).await;
+    let _ = app.emit(
+        "system-wake",
+        SystemWake {
+            slept_ms: slept.as_millis() as u64,
+            running_turns,
+            bridge_ok,
+        },
+    );
+}
+
+/// Watch for the system waking from sleep.
+pub(crate) fn spawn_sleep_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let before = SystemTime::now();
+            sleep(SLEEP_POLL).await;
+            // A clock set backwards gives an error; it isn't a sleep.
+            let Ok(wall) = SystemTime::now().duration_since(before) else {
+                continue;
+            };
+            if let Some(slept) = slept_for(SLEEP_POLL, wall) {
+                handle_wake(&app, slept).await;
+            }
+        }
+    });
+}
+
+/// `CPU_Speed_Limit` from `pmset -g therm`; below 100 while throttled.
+#[cfg(any(target_os = "macos", test))]
+fn cpu_speed_limit(output: &str) -> Option<u32> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "CPU_Speed_Limit")
+            .then(|| value.trim().parse().ok())
+            .flatten()
+    })
+}
+
+#[cfg(target_os = "macos")]
+async fn read_source() -> PowerSource {
+    let battery = query("pmset", &["-g", "batt"]).await.unwrap_or_default();
+    let thermal = query("pmset", &["-g", "therm"]).await.unwrap_or_default();
+    PowerSource {
+        on_battery: battery.contains("'Battery Power'"),
+        thermal_pressure: cpu_speed_limit(&thermal).is_some_and(|limit| limit < 100),
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn read_source() -> PowerSource {
+    // BatteryStatus 1 is "discharging". Thermal throttling isn't exposed
+    // without native bindings.
+    let status = query(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "(Get-CimInstance Win32_Battery).BatteryStatus",
+        ],
+    )
+    .await
+    .unwrap_or_default();
+    PowerSource {
+        on_battery: status.lines().any(|line| line.trim() == "1"),
+        thermal_pressure: false,
+    }
+}
+
+#[cfg(any(not(any(target_os = "macos", target_os = "windows")), test))]
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+/// On battery when `power_supply` lists a battery and no mains or USB
+/// supply is online. Desktops without a battery never are.
+#[cfg(any(not(any(target_os = "macos", target_os = "windows")), test))]
+fn sysfs_on_battery(power_supply: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(power_supply) else {
+        return false;
+    };
+    let mut battery = false;
+    let mut plugged = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match read_trimmed(&path.join("type")).as_deref() {
+            Some("Battery") => battery = true,
+            Some(_) => plugged |= read_trimmed(&path.join("online")).as_deref() == Some("1"),
+            None => {}
+        }
+    }
+    battery && !plugged
+}
+
+/// Whether any thermal zone is at or past a passive trip point, where the
+/// kernel starts slowing the CPU down.
+#[cfg(any(not(any(target_os = "macos", target_os = "windows")), test))]
+fn sysfs_thermal_pressure(thermal: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(thermal) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let zone = entry.path();
+        let Some(temp) = read_trimmed(&zone.join("temp")).and_then(|t| t.parse::<i64>().ok())
+        else {
+            return false;
+        };
+        (0..)
+            .map_while(|index| {
+                let kind = read_trimmed(&zone.join(format!("trip_point_{index}_type")))?;
+                let trip = read_trimmed(&zone.join(format!("trip_point_{index}_temp")));
+                Some((kind, trip.and_then(|t| t.parse::<i64>().ok())))
+            })
+            .any(|(kind, trip)| kind == "passive" && trip.is_some_and(|trip| temp >= trip))
+    })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+async fn read_source() -> PowerSource {
+    PowerSource {
+        on_battery: sysfs_on_battery(Path::new("/sys/class/power_supply")),
+        thermal_pressure: sysfs_thermal_pressure(Path::new("/sys/class/thermal")),
+    }
+}
+
+fn status_for(source: PowerSource, settings: &PowerSavingSettings) -> PowerStatus {
+    let throttled = settings.enabled && (source.on_battery || source.thermal_pressure);
+    PowerStatus {
+        on_battery: source.on_battery,
+        thermal_pressure: source.thermal_pressure,
+        throttled,
+        max_sessions: (throttled && settings.max_sessions > 0).then_some(settings.max_sessions),
+        indexing_paused: throttled && settings.pause_indexing,
+    }
+}
+
+pub(crate) async fn power_status(state: &AppState) -> PowerStatus {
+    let settings = state.app_settings.lock().await.power_saving.clone();
+    let source = SOURCE.read().map(|source| *source).unwrap_or_default();
+    status_for(source, &settings)
+}
+
+/// Refuse to open another session while power saving caps how many may be
+/// open.
+pub(crate) async fn ensure_session_slot(state: &AppState) -> Result<(), String> {
+    let status = power_status(state).await;
+    let Some(max) = status.max_sessions else {
+        return Ok(());
+    };
+    if state.claude_sessions.lock().await.len() < max as usize {
+        return Ok(());
+    }
+    let reason = if status.on_battery {
+        "on battery"
+    } else {
+        "the system is thermally throttled"
+    };
+    Err(format!(
+        "Power saving allows {max} open sessions while {reason}. Close a session or turn off power saving in settings."
+    ))
+}
+
+/// Re-read the power source and emit `power-status-changed` when the
+/// throttling state changes.
+pub(crate) fn spawn_power_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last = PowerStatus::default();
+        loop {
+            let source = read_source().await;
+            if let Ok(mut current) = SOURCE.write() {
+                *current = source;
+            }
+            let state = app.state::<AppState>();
+            let status = power_status(&state).await;
+            if status != last {
+                if last.indexing_paused && !status.indexing_paused {
+                    state.indexer_wake.notify_one();
+                }
+                let _ = app.emit("power-status-changed", &status);
+                last = status;
+            }
+            sleep(POWER_POLL).await;
+        }
+    });
+}
+
+/// Power source, thermal state and the limits power saving applies.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_power_status(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<PowerStatus, String> {
+    Ok(power_status(&state).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        cpu_speed_limit, slept_for, status_for, sysfs_on_battery, sysfs_thermal_pressure,
+        PowerSource, SLEEP_POLL,
+    };
+    use crate::types::PowerSavingSettings;
+    use crate::utils::TempDir;
+
+    #[test]
+    fn only_long_gaps_count_as_sleep() {
+        let secs = Duration::from_secs;
+        assert_eq!(slept_for(SLEEP_POLL, SLEEP_POLL + secs(2)), None);
+        assert_eq!(slept_for(SLEEP_POLL, secs(1)), None);
+        assert_eq!(
+            slept_for(SLEEP_POLL, SLEEP_POLL + secs(3600)),
+            Some(secs(3600))
+        );
+    }
+    #[test]
+    fn throttles_only_when_enabled() {
+        let battery = PowerSource {
+            on_battery: true,
+            thermal_pressure: false,
+        };
+        let status = status_for(battery, &PowerSavingSettings::default());
+        assert!(status.throttled);
+        assert_eq!(status.max_sessions, Some(2));
+        assert!(status.indexing_paused);
+
+        let status = status_for(PowerSource::default(), &PowerSavingSettings::default());
+        assert!(!status.throttled);
+        assert_eq!(status.max_sessions, None);
+
+        let settings = PowerSavingSettings {
+            enabled: false,
+            ..PowerSavingSettings::default()
+        };
+        assert!(!status_for(battery, &settings).throttled);
+    }
+
+    #[test]
+    fn reads_platform_power_state() {
+        let therm = "Note: No thermal warning level has been recorded\n\tCPU_Scheduler_Limit \t= 100\n\tCPU_Speed_Limit \t= 63\n";
+        assert_eq!(cpu_speed_limit(therm), Some(63));
+        assert_eq!(
+            cpu_speed_limit("No CPU power status has been recorded"),
+            None
+        );
+
+        let root = TempDir::new("power");
+        let write = |path: &str, value: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, value).unwrap();
+        };
+        write("supply/BAT0/type", "Battery\n");
+        write("supply/AC/type", "Mains\n");
+        write("supply/AC/online", "0\n");
+        assert!(sysfs_on_battery(&root.join("supply")));
+        write("supply/AC/online", "1\n");
+        assert!(!sysfs_on_battery(&root.join("supply")));
+
+        write("thermal/thermal_zone0/temp", "85000\n");
+        write("thermal/thermal_zone0/trip_point_0_type", "critical\n");
+        write("thermal/thermal_zone0/trip_point_0_temp", "80000\n");
+        write("thermal/thermal_zone0/trip_point_1_type", "passive\n");
+        write("thermal/thermal_zone0/trip_point_1_temp", "90000\n");
+        assert!(!sysfs_thermal_pressure(&root.join("thermal")));
+        write("thermal/thermal_zone0/temp", "95000\n");
+        assert!(sysfs_thermal_pressure(&root.join("thermal")));
+    }
+}
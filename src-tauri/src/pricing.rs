@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::registry::now_millis;
+use crate::state::AppState;
+use crate::types::{ModelRate, SessionUsage};
+use crate::utils::{curl_config_value, run_curl};
+
+/// Published Anthropic rates (USD per million tokens) at release time.
+/// Cache writes are the 5-minute TTL rate.
+const BUNDLED_RATES: &[(&str, f64, f64, f64, f64)] = &[
+    ("claude-opus-4-5", 5.0, 25.0, 0.5, 6.25),
+    ("claude-opus-4-1", 15.0, 75.0, 1.5, 18.75),
+    ("claude-opus-4", 15.0, 75.0, 1.5, 18.75),
+    ("claude-sonnet-4", 3.0, 15.0, 0.3, 3.75),
+    ("claude-haiku-4-5", 1.0, 5.0, 0.1, 1.25),
+    ("claude-3-7-sonnet", 3.0, 15.0, 0.3, 3.75),
+    ("claude-3-5-sonnet", 3.0, 15.0, 0.3, 3.75),
+    ("claude-3-5-haiku", 0.8, 4.0, 0.08, 1.0),
+    ("claude-3-opus", 15.0, 75.0, 1.5, 18.75),
+    ("claude-3-haiku", 0.25, 1.25, 0.03, 0.3),
+];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PricingSource {
+    Bundled,
+    Remote,
+    Override,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PricedModel {
+    #[serde(flatten)]
+    rate: ModelRate,
+    source: PricingSource,
+}
+
+/// The effective pricing table, highest precedence first.
+#[derive(Debug, Serialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelPricing {
+    models: Vec<PricedModel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_fetched_at: Option<u64>,
+}
+
+/// Remote table format, also used for the local cache.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct RemotePricing {
+    models: Vec<ModelRate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fetched_at: Option<u64>,
+}
+
+fn bundled_rates() -> Vec<ModelRate> {
+    BUNDLED_RATES
+        .iter()
+        .map(
+            |(model, input, output, cache_read, cache_write)| ModelRate {
+                model: model.to_string(),
+                input: *input,
+                output: *output,
+                cache_read: *cache_read,
+                cache_write: *cache_write,
+            },
+        )
+        .collect()
+}
+
+fn pricing_cache_path(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("pricing.json")
+}
+
+fn read_cached(state: &AppState) -> Option<RemotePricing> {
+    let content = std::fs::read_to_string(pricing_cache_path(state)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn parse_remote(content: &str) -> Result<Vec<ModelRate>, String> {
+    let pricing: RemotePricing =
+        serde_json::from_str(content).map_err(|e| format!("Invalid pricing JSON: {e}"))?;
+    let valid = |value: f64| value.is_finite() && value >= 0.0;
+    let models: Vec<ModelRate> = pricing
+        .models
+        .into_iter()
+        .filter(|rate| {
+            !rate.model.trim().is_empty()
+                && valid(rate.input)
+                && valid(rate.output)
+                && valid(rate.cache_read)
+                && valid(rate.cache_write)
+        })
+        .collect();
+    if models.is_empty() {
+        return Err("Pricing JSON lists no usable models".to_string());
+    }
+    Ok(models)
+}
+
+impl ModelPricing {
+    fn build(overrides: &[ModelRate], remote: Option<RemotePricing>, url: Option<String>) -> Self {
+        let tag = |source| move |rate: ModelRate| PricedModel { rate, source };
+        let mut models: Vec<PricedModel> = overrides
+            .iter()
+            .cloned()
+            .map(tag(PricingSource::Override))
+            .collect();
+        let remote_fetched_at = remote.as_ref().and_then(|remote| remote.fetched_at);
+        if let Some(remote) = remote {
+            models.extend(remote.models.into_iter().map(tag(PricingSource::Remote)));
+        }
+        models.extend(bundled_rates().into_iter().map(tag(PricingSource::Bundled)));
+        ModelPricing {
+            models,
+            remote_url: url,
+            remote_fetched_at,
+        }
+    }
+
+    /// Rate for a model id. Within the highest-precedence source that has a
+    /// match, the longest id prefix wins, so `claude-opus-4-5` beats
+    /// `claude-opus-4`. Provider ids like `us.anthropic.claude-…-v1:0` are
+    /// matched from their `claude-` part.
+    pub(crate) fn rate_for(&self, model: &str) -> Option<&ModelRate> {
+        let model = model.trim();
+        let candidates = [
+            model,
+            model.find("claude-").map_or(model, |at| &model[at..]),
+        ];
+        let matches = |priced: &&PricedModel| {
+            candidates
+                .iter()
+                .any(|candidate| candidate.starts_with(priced.rate.model.as_str()))
+        };
+        let source = self.models.iter().find(matches)?.source;
+        self.models
+            .iter()
+            .filter(|priced| priced.source == source)
+            .filter(matches)
+            .max_by_key(|priced| priced.rate.model.len())
+            .map(|priced| &priced.rate)
+    }
+
+    /// Estimated USD cost of a session's tokens, if its model is priced.
+    pub(crate) fn estimate(&self, usage: &SessionUsage) -> Option<f64> {
+        let rate = self.rate_for(usage.model.as_deref()?)?;
+        let cost = usage.input_tokens as f64 * rate.input
+            + usage.output_tokens as f64 * rate.output
+            + usage.cache_read_input_tokens as f64 * rate.cache_read
+            + usage.cache_creation_input_tokens as f64 * rate.cache_write;
+        Some(cost / 1_000_000.0)
+    }
+}
+
+pub(crate) async fn model_pricing(state: &AppState) -> ModelPricing {
+    let (overrides, url) = {
+        let settings = state.app_settings.lock().await;
+        (
+            settings.pricing_overrides.clone(),
+            settings.pricing_url.clone(),
+        )
+    };
+    ModelPricing::build(&overrides, read_cached(state), url)
+}
+
+/// The pricing table used for cost estimates, with each rate's source.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_model_pricing(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ModelPricing, String> {
+    Ok(model_pricing(&state).await)
+}
+
+/// Download the configured pricing JSON and cache it for later estimates.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn refresh_model_pricing(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ModelPricing, String> {
+    let url = state
+        .app_settings
+        .lock()
+        .await
+        .pricing_url
+        .clone()
+        .filter(|url| !url.trim().is_empty())
+        .ok_or("No pricing URL configured")?;
+    let config = format!("url = {}\n", curl_config_value(url.trim()));
+    let body = run_curl(&config, &["--fail".to_string(), "--location".to_string()]).await?;
+    let cached = RemotePricing {
+        models: parse_remote(&body)?,
+        fetched_at: Some(now_millis()),
+    };
+    let path = pricing_cache_path(&state);
+    let content = serde_json::to_string_pretty(&cached).map_err(|e| e.to_string())?;
+    std::fs::write(path, content).map_err(|e| e.to_string())?;
+    Ok(model_pricing(&state).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_remote, ModelPricing, RemotePricing};
+    use crate::types::{ModelRate, SessionUsage};
+
+    fn rate(model: &str, input: f64) -> ModelRate {
+        ModelRate {
+            model: model.to_string(),
+            input,
+            output: 0.0,
+            cache_read: 0.0,
+            cache_write: 0.0,
+        }
+    }
+
+    #[test]
+    fn prefers_overrides_then_longest_prefix() {
+        let pricing = ModelPricing::build(&[], None, None);
+        assert_eq!(
+            pricing.rate_for("claude-opus-4-5-20251101").unwrap().input,
+            5.0
+        );
+        assert_eq!(
+            pricing.rate_for("claude-opus-4-20250514").unwrap().input,
+            15.0
+        );
+        assert_eq!(
+            pricing
+                .rate_for("us.anthropic.claude-sonnet-4-5-20250929-v1:0")
+                .unwrap()
+                .output,
+            15.0
+        );
+        assert!(pricing.rate_for("gpt-4o").is_none());
+
+        let remote = RemotePricing {
+            models: vec![rate("claude-sonnet-4", 2.0)],
+            fetched_at: Some(1),
+        };
+        let pricing = ModelPricing::build(&[rate("claude-sonnet", 1.0)], Some(remote), None);
+        assert_eq!(pricing.rate_for("claude-sonnet-4-5").unwrap().input, 1.0);
+        assert_eq!(pricing.rate_for("claude-haiku-4-5").unwrap().input, 1.0);
+    }
+
+    #[test]
+    fn estimates_cost_from_token_counts() {
+        let pricing = ModelPricing::build(&[], None, None);
+        let usage = SessionUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_read_input_tokens: 1_000_000,
+            model: Some("claude-sonnet-4-5".to_string()),
+            ..SessionUsage::default()
+        };
+        let cost = pricing.estimate(&usage).unwrap();
+        assert!((cost - 4.8).abs() < 1e-9);
+        assert!(pricing.estimate(&SessionUsage::default()).is_none());
+    }
+
+    #[test]
+    fn rejects_remote_tables_without_usable_rates() {
+        assert!(parse_remote(r#"{ "models": [] }"#).is_err());
+        assert!(
+            parse_remote(r#"{ "models": [{ "model": "", "input": 1, "output": 1 }] }"#).is_err()
+        );
+        let models =
+            parse_remote(r#"{ "models": [{ "model": "claude-x", "input": 1, "output": 2 }] }"#)
+                .unwrap();
+        assert_eq!(models[0].cache_read, 0.0);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::registry::{
+    list_claude_project_dirs, list_transcripts, read_transcript_cwd, scan_project_sessions,
+    write_registry,
+};
+use crate::state::AppState;
+use crate::types::{WorkspaceInfo, WorkspaceRegistry};
+use crate::utils::PathKey;
+use crate::workspaces::create_workspace_entry;
+
+/// Transcripts found under ~/.claude/projects for one decoded cwd.
+#[derive(Debug, Default)]
+pub(crate) struct ProjectGroup {
+    pub(crate) project_dirs: Vec<PathBuf>,
+    pub(crate) transcripts: Vec<PathBuf>,
+}
+
+/// Group every transcript under ~/.claude/projects by the cwd it recorded.
+/// Transcripts without a cwd are grouped under the project directory path.
+pub(crate) fn group_transcripts_by_cwd(
+    project_dirs: &[PathBuf],
+) -> BTreeMap<String, ProjectGroup> {
+    let mut groups: BTreeMap<String, ProjectGroup> = BTreeMap::new();
+    for project_dir in project_dirs {
+        for transcript in list_transcripts(project_dir) {
+            let cwd = read_transcript_cwd(&transcript)
+                .map(|cwd| cwd.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| project_dir.to_string_lossy().to_string());
+            let group = groups.entry(cwd).or_default();
+            if !group.project_dirs.contains(project_dir) {
+                group.project_dirs.push(project_dir.clone());
+            }
+            group.transcripts.push(transcript);
+        }
+    }
+    groups
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectProposal {
+    cwd: String,
+    name: String,
+    session_count: usize,
+    last_activity: u64,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExistingProjectsScan {
+    /// True when nothing has been registered yet, i.e. the first launch.
+    first_launch: bool,
+    proposals: Vec<ProjectProposal>,
+}
+
+fn modified_millis(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClaudeProjectSummary {
+    project_dir: String,
+    dir_name: String,
+    /// Cwd recorded in the transcripts, when any transcript has one.
+    cwd: Option<String>,
+    cwd_exists: bool,
+    session_count: usize,
+    size_bytes: u64,
+    last_activity: u64,
+    workspace_id: Option<String>,
+}
+
+fn summarize_project_dir(
+    project_dir: &Path,
+    workspaces: &[(String, PathKey)],
+) -> ClaudeProjectSummary {
+    let transcripts = list_transcripts(project_dir);
+    let cwd = transcripts
+        .iter()
+        .find_map(|t| read_transcript_cwd(t))
+        .map(|cwd| cwd.trim_end_matches('/').to_string());
+    let mut size_bytes = 0u64;
+    let mut last_activity = 0u64;
+    for transcript in &transcripts {
+        size_bytes += std::fs::metadata(transcript).map(|m| m.len()).unwrap_or(0);
+        last_activity = last_activity.max(modified_millis(transcript));
+    }
+    let workspace_id = cwd.as_ref().and_then(|cwd| {
+        let cwd = PathKey::new(cwd);
+        workspaces
+            .iter()
+            .find(|(_, key)| key.matches(&cwd))
+            .map(|(id, _)| id.clone())
+    });
+    ClaudeProjectSummary {
+        project_dir: project_dir.to_string_lossy().to_string(),
+        dir_name: project_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        cwd_exists: cwd.as_ref().map_or(false, |cwd| Path::new(cwd).is_dir()),
+        cwd,
+        session_count: transcripts.len(),
+        size_bytes,
+        last_activity,
+        workspace_id,
+    }
+}
+
+/// Every project directory under ~/.claude/projects, registered or not, so
+/// history for deleted or moved repos can still be found.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn scan_all_claude_projects(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<ClaudeProjectSummary>, String> {
+    let workspaces: Vec<(String, String)> = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .values()
+            .map(|entry| (entry.id.clone(), entry.path.clone()))
+            .collect()
+    };
+    tokio::task::spawn_blocking(move || -> Result<Vec<ClaudeProjectSummary>, String> {
+        let workspaces: Vec<(String, PathKey)> = workspaces
+            .into_iter()
+            .map(|(id, path)| (id, PathKey::new(&path)))
+            .collect();
+        let mut summaries: Vec<ClaudeProjectSummary> = list_claude_project_dirs()?
+            .iter()
+            .map(|dir| summarize_project_dir(dir, &workspaces))
+            .collect();
+        summaries.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        Ok(summaries)
+    })
+    .await
+    .map_err(|_| "project scan failed".to_string())?
+}
+
+/// Propose workspaces for every directory with Claude Code history that
+/// still exists on disk and is not registered yet.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn scan_existing_projects(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ExistingProjectsScan, String> {
+    let registered: Vec<String> = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces.values().map(|entry| entry.path.clone()).collect()
+    };
+    let first_launch = registered.is_empty() && state.registry.lock().await.sessions.is_empty();
+
+    let proposals = tokio::task::spawn_blocking(move || -> Result<Vec<ProjectProposal>, String> {
+        let registered: Vec<PathKey> = registered.iter().map(|path| PathKey::new(path)).collect();
+        let project_dirs = list_claude_project_dirs()?;
+        let mut proposals: Vec<ProjectProposal> = group_transcripts_by_cwd(&project_dirs)
+            .into_iter()
+            .filter(|(cwd, _)| {
+                if !Path::new(cwd).is_dir() {
+                    return false;
+                }
+                let cwd = PathKey::new(cwd);
+                !registered.iter().any(|key| key.matches(&cwd))
+            })
+            .map(|(cwd, group)| ProjectProposal {
+                name: Path::new(&cwd)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| cwd.clone()),
+                session_count: group.transcripts.len(),
+                last_activity: group
+                    .transcripts
+                    .iter()
+                    .map(|t| modified_millis(t))
+                    .max()
+                    .unwrap_or(0),
+                cwd,
+            })
+            .collect();
+        proposals.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        Ok(proposals)
+    })
+    .await
+    .map_err(|_| "project scan failed".to_string())??;
+
+    Ok(ExistingProjectsScan {
+        first_launch,
+        proposals,
+    })
+}
+
+/// Create workspaces for the chosen directories (reusing existing ones) and
+/// make all of their Claude sessions visible.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn import_existing_projects(
+    paths: Vec<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<WorkspaceInfo>, String> {
+    let mut imported = Vec::new();
+    for path in paths {
+        let path = path.trim_end_matches('/').to_string();
+        let existing_id = {
+            let key = PathKey::new(&path);
+            let workspaces = state.workspaces.lock().await;
+            workspaces
+                .values()
+                .find(|entry| key.matches(&PathKey::new(&entry.path)))
+                .map(|entry| entry.id.clone())
+        };
+        let workspace_id = match existing_id {
+            Some(id) => id,
+            None => {
+                let info = create_workspace_entry(&state, &path).await?;
+                let id = info.id.clone();
+                imported.push(info);
+                id
+            }
+        };
+
+        let scan_path = path.clone();
+        let sessions = tokio::task::spawn_blocking(move || scan_project_sessions(&scan_path))
+            .await
+            .map_err(|_| "session scan failed".to_string())??;
+
+        let mut registry = state.registry.lock().await;
+        let mut ids = Vec::new();
+        for session in sessions {
+            ids.push(session.session_id.clone());
+            registry
+                .sessions
+                .entry(session.session_id.clone())
+                .or_insert(session);
+        }
+        let workspace_reg = registry
+            .workspaces
+            .entry(workspace_id)
+            .or_insert_with(WorkspaceRegistry::default);
+        for id in ids {
+            if !workspace_reg.visible_session_ids.contains(&id) {
+                workspace_reg.visible_session_ids.push(id);
+            }
+        }
+        write_registry(&state.registry_path, &registry)?;
+    }
+    Ok(imported)
+}
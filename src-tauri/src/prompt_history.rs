@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::registry::now_millis;
+use crate::state::AppState;
+
+/// Entries beyond this are dropped, least recently used first.
+const MAX_ENTRIES: usize = 5000;
+const DEFAULT_LIMIT: usize = 50;
+
+/// A prompt as sent in one workspace. Sending the same text again updates
+/// the entry instead of adding one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptHistoryEntry {
+    pub(crate) text: String,
+    pub(crate) workspace_id: String,
+    pub(crate) first_used_at: u64,
+    pub(crate) last_used_at: u64,
+    pub(crate) use_count: u32,
+}
+
+/// Every prompt the user has sent, kept in `prompt-history.json`.
+#[derive(Debug, Default)]
+pub(crate) struct PromptHistory {
+    entries: Vec<PromptHistoryEntry>,
+}
+
+impl PromptHistory {
+    pub(crate) fn load(path: &Path) -> Self {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|err| {
+                log_line!("Ignoring {}: {err}", path.display());
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { entries }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_string(&self.entries).map_err(|e| e.to_string())?;
+        std::fs::write(path, data).map_err(|e| e.to_string())
+    }
+
+    /// Note `text` was sent in `workspace_id`. Returns false for blank text.
+    fn record(&mut self, workspace_id: &str, text: &str, now: u64) -> bool {
+        let text = text.trim();
+        if text.is_empty() {
+            return false;
+        }
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.workspace_id == workspace_id && entry.text == text)
+        {
+            Some(entry) => {
+                entry.last_used_at = now;
+                entry.use_count += 1;
+            }
+            None => self.entries.push(PromptHistoryEntry {
+                text: text.to_string(),
+                workspace_id: workspace_id.to_string(),
+                first_used_at: now,
+                last_used_at: now,
+                use_count: 1,
+            }),
+        }
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries
+                .sort_by_key(|entry| std::cmp::Reverse(entry.last_used_at));
+            self.entries.truncate(MAX_ENTRIES);
+        }
+        true
+    }
+
+    /// Entries passing `keep`, most recently used first.
+    fn latest(
+        &self,
+        limit: usize,
+        keep: impl Fn(&PromptHistoryEntry) -> bool,
+    ) -> Vec<PromptHistoryEntry> {
+        let mut entries: Vec<_> = self.entries.iter().filter(|entry| keep(entry)).collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used_at));
+        entries.into_iter().take(limit).cloned().collect()
+    }
+
+    fn recent(&self, workspace_id: &str, limit: usize) -> Vec<PromptHistoryEntry> {
+        self.latest(limit, |entry| entry.workspace_id == workspace_id)
+    }
+
+    /// Prompts containing every word of `query`, ignoring case.
+    fn search(
+        &self,
+        query: &str,
+        workspace_id: Option<&str>,
+        limit: usize,
+    ) -> Vec<PromptHistoryEntry> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.latest(limit, |entry| {
+            let text = entry.text.to_lowercase();
+            workspace_id.is_none_or(|id| entry.workspace_id == id)
+                && terms.iter().all(|term| text.contains(term.as_str()))
+        })
+    }
+}
+
+fn history_path(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("prompt-history.json")
+}
+
+/// Add a sent prompt to the history.
+pub(crate) async fn record_prompt(state: &AppState, workspace_id: &str, text: &str) {
+    let mut history = state.prompt_history.lock().await;
+    if history.record(workspace_id, text, now_millis()) {
+        if let Err(err) = history.save(&history_path(state)) {
+            log_line!("Failed to save prompt history: {err}");
+        }
+    }
+}
+
+/// Past prompts containing every word of `query`, newest first, across all
+/// workspaces unless `workspace_id` is given.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn search_prompt_history(
+    query: String,
+    workspace_id: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<PromptHistoryEntry>, String> {
+    Ok(state.prompt_history.lock().await.search(
+        &query,
+        workspace_id.as_deref(),
+        limit.unwrap_or(DEFAULT_LIMIT),
+    ))
+}
+
+/// The prompts last sent in a workspace, newest first.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_recent_prompts(
+    workspace_id: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<PromptHistoryEntry>, String> {
+    Ok(state
+        .prompt_history
+        .lock()
+        .await
+        .recent(&workspace_id, limit.unwrap_or(DEFAULT_LIMIT)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PromptHistory;
+
+    #[test]
+    fn dedups_and_searches_prompts() {
+        let mut history = PromptHistory::default();
+        assert!(history.record("ws-1", "Fix the failing tests", 1));
+        assert!(history.record("ws-1", "Bump dependencies", 2));
+        assert!(history.record("ws-2", "fix the LINT errors", 3));
+        assert!(history.record("ws-1", "  Fix the failing tests\n", 4));
+        assert!(!history.record("ws-1", "   ", 5));
+
+        let recent = history.recent("ws-1", 10);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].text, "Fix the failing tests");
+        assert_eq!(recent[0].use_count, 2);
+        assert_eq!(recent[0].first_used_at, 1);
+
+        let found: Vec<_> = history
+            .search("FIX the", None, 10)
+            .into_iter()
+            .map(|entry| entry.workspace_id)
+            .collect();
+        assert_eq!(found, ["ws-1", "ws-2"]);
+        assert_eq!(history.search("fix lint", Some("ws-1"), 10).len(), 0);
+        assert_eq!(history.search("", None, 1).len(), 1);
+    }
+}
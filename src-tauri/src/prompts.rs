@@ -2,9 +2,14 @@ use serde::Serialize;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use tauri::State;
 use tokio::task;
 
-#[derive(Serialize, Clone)]
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+use crate::team_prompts::{team_prompt_dirs, team_repo_dir};
+
+#[derive(Serialize, Clone, specta::Type)]
 pub(crate) struct CustomPromptEntry {
     pub(crate) name: String,
     pub(crate) path: String,
@@ -12,6 +17,8 @@ pub(crate) struct CustomPromptEntry {
     #[serde(rename = "argumentHint")]
     pub(crate) argument_hint: Option<String>,
     pub(crate) content: String,
+    /// "local", or "team" for read-only prompts synced from the team repo.
+    pub(crate) source: String,
 }
 
 fn resolve_home_dir() -> Option<PathBuf> {
@@ -107,7 +114,7 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     (description, argument_hint, body)
 }
 
-fn discover_prompts_in(dir: &Path) -> Vec<CustomPromptEntry> {
+pub(crate) fn discover_prompts_in(dir: &Path) -> Vec<CustomPromptEntry> {
     let mut out: Vec<CustomPromptEntry> = Vec::new();
     let entries = match fs::read_dir(dir) {
         Ok(entries) => entries,
@@ -146,6 +153,7 @@ fn discover_prompts_in(dir: &Path) -> Vec<CustomPromptEntry> {
             description,
             argument_hint,
             content: body,
+            source: "local".to_string(),
         });
     }
 
@@ -153,12 +161,37 @@ fn discover_prompts_in(dir: &Path) -> Vec<CustomPromptEntry> {
     out
 }
 
+/// Local prompts plus the team library. A local prompt overrides a team
+/// prompt of the same name.
 #[tauri::command]
-pub(crate) async fn prompts_list(_workspace_id: String) -> Result<Vec<CustomPromptEntry>, String> {
-    let Some(dir) = default_prompts_dir() else {
-        return Ok(Vec::new());
+#[specta::specta]
+pub(crate) async fn prompts_list(
+    _workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<CustomPromptEntry>, String> {
+    let local_dir = default_prompts_dir();
+    let team_dirs = if state.app_settings.lock().await.team_prompts_repo.is_some() {
+        team_prompt_dirs(&team_repo_dir(&state))
+    } else {
+        Vec::new()
     };
-    task::spawn_blocking(move || discover_prompts_in(&dir))
-        .await
-        .map_err(|_| "prompt discovery failed".to_string())
+    task::spawn_blocking(move || {
+        let mut prompts = local_dir
+            .map(|dir| discover_prompts_in(&dir))
+            .unwrap_or_default();
+        for dir in team_dirs {
+            for mut prompt in discover_prompts_in(&dir) {
+                if prompts.iter().any(|existing| existing.name == prompt.name) {
+                    continue;
+                }
+                prompt.source = "team".to_string();
+                prompts.push(prompt);
+            }
+        }
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
+        prompts
+    })
+    .await
+    .map_err(|_| "prompt discovery failed".to_string())
 }
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+use crate::types::{ClaudeProvider, ProviderSettings, WorkspaceSettings};
+use crate::utils::{curl_config_value, run_curl};
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Environment variables Claude Code reads to route requests through the
+/// selected cloud provider. Anthropic needs nothing extra.
+pub(crate) fn provider_env(
+    provider: &ClaudeProvider,
+    config: &ProviderSettings,
+) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    match provider {
+        ClaudeProvider::Anthropic => {}
+        ClaudeProvider::Bedrock => {
+            env.insert("CLAUDE_CODE_USE_BEDROCK".to_string(), "1".to_string());
+            if let Some(region) = non_empty(&config.aws_region) {
+                env.insert("AWS_REGION".to_string(), region.to_string());
+            }
+            if let Some(profile) = non_empty(&config.aws_profile) {
+                env.insert("AWS_PROFILE".to_string(), profile.to_string());
+            }
+        }
+        ClaudeProvider::Vertex => {
+            env.insert("CLAUDE_CODE_USE_VERTEX".to_string(), "1".to_string());
+            if let Some(region) = non_empty(&config.vertex_region) {
+                env.insert("CLOUD_ML_REGION".to_string(), region.to_string());
+            }
+            if let Some(project) = non_empty(&config.vertex_project_id) {
+                env.insert("ANTHROPIC_VERTEX_PROJECT_ID".to_string(), project.to_string());
+            }
+        }
+    }
+    env
+}
+
+/// Environment for a workspace's custom API gateway, if one is configured.
+/// Headers use Claude Code's newline-separated `Name: Value` format.
+pub(crate) fn gateway_env(settings: &WorkspaceSettings) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    if let Some(base_url) = non_empty(&settings.api_base_url) {
+        env.insert(
+            "ANTHROPIC_BASE_URL".to_string(),
+            base_url.trim_end_matches('/').to_string(),
+        );
+    }
+    if let Some(headers) = settings.api_headers.as_ref().filter(|h| !h.is_empty()) {
+        let mut lines: Vec<String> = headers
+            .iter()
+            .filter(|(name, _)| !name.trim().is_empty())
+            .map(|(name, value)| format!("{}: {}", name.trim(), value.trim()))
+            .collect();
+        lines.sort();
+        if !lines.is_empty() {
+            env.insert("ANTHROPIC_CUSTOM_HEADERS".to_string(), lines.join("\n"));
+        }
+    }
+    env
+}
+
+/// Required provider fields that are missing from settings.
+pub(crate) fn missing_provider_fields(
+    provider: &ClaudeProvider,
+    config: &ProviderSettings,
+) -> Vec<&'static str> {
+    let mut missing = Vec::new();
+    match provider {
+        ClaudeProvider::Anthropic => {}
+        ClaudeProvider::Bedrock => {
+            if non_empty(&config.aws_region).is_none() {
+                missing.push("awsRegion");
+            }
+        }
+        ClaudeProvider::Vertex => {
+            if non_empty(&config.vertex_region).is_none() {
+                missing.push("vertexRegion");
+            }
+            if non_empty(&config.vertex_project_id).is_none() {
+                missing.push("vertexProjectId");
+            }
+        }
+    }
+    missing
+}
+
+fn aws_credentials_present(profile: Option<&str>) -> bool {
+    if std::env::var_os("AWS_ACCESS_KEY_ID").is_some() && profile.is_none() {
+        return true;
+    }
+    let Some(home) = dirs::home_dir() else {
+        return false;
+    };
+    let profile = profile.unwrap_or("default");
+    let sections = [format!("[{profile}]"), format!("[profile {profile}]")];
+    [".aws/credentials", ".aws/config"].iter().any(|file| {
+        std::fs::read_to_string(home.join(file))
+            .map(|content| {
+                content
+                    .lines()
+                    .any(|line| sections.iter().any(|section| line.trim() == section))
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// gcloud's config directory: `CLOUDSDK_CONFIG`, else `%APPDATA%\gcloud` on
+/// Windows and `~/.config/gcloud` everywhere else (including macOS).
+fn gcloud_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("CLOUDSDK_CONFIG") {
+        return Some(PathBuf::from(dir));
+    }
+    if cfg!(windows) {
+        std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("gcloud"))
+    } else {
+        dirs::home_dir().map(|home| home.join(".config").join("gcloud"))
+    }
+}
+
+fn gcloud_credentials_present() -> bool {
+    if let Some(path) = std::env::var_os("GOOGLE_APPLICATION_CREDENTIALS") {
+        return PathBuf::from(path).exists();
+    }
+    gcloud_config_dir()
+        .map(|dir| dir.join("application_default_credentials.json").exists())
+        .unwrap_or(false)
+}
+
+/// Doctor section for the configured provider: settings completeness plus a
+/// local credentials check (no network calls).
+pub(crate) fn check_provider(provider: &ClaudeProvider, config: &ProviderSettings) -> Value {
+    let missing = missing_provider_fields(provider, config);
+    let (credentials_ok, credentials_details) = match provider {
+        ClaudeProvider::Anthropic => (true, None),
+        ClaudeProvider::Bedrock => {
+            if aws_credentials_present(non_empty(&config.aws_profile)) {
+                (true, None)
+            } else {
+                (
+                    false,
+                    Some("No AWS credentials found for the configured profile.".to_string()),
+                )
+            }
+        }
+        ClaudeProvider::Vertex => {
+            if gcloud_credentials_present() {
+                (true, None)
+            } else {
+                (
+                    false,
+                    Some(
+                        "No Google application default credentials found. Run `gcloud auth application-default login`."
+                            .to_string(),
+                    ),
+                )
+            }
+        }
+    };
+    json!({
+        "provider": provider,
+        "providerOk": missing.is_empty() && credentials_ok,
+        "missingFields": missing,
+        "credentialsOk": credentials_ok,
+        "credentialsDetails": credentials_details,
+    })
+}
+
+/// Probe a workspace's model endpoint. Any HTTP response counts as reachable;
+/// auth failures are reported through the status code.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn test_model_endpoint(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Value, String> {
+    let settings = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .settings
+            .clone()
+    };
+    let base_url = non_empty(&settings.api_base_url)
+        .unwrap_or("https://api.anthropic.com")
+        .trim_end_matches('/')
+        .to_string();
+    let url = format!("{base_url}/v1/models");
+
+    // Headers often carry the API key, so they go through curl's stdin
+    // config rather than its arguments.
+    let mut config = format!("url = {}\n", curl_config_value(&url));
+    config.push_str(&format!(
+        "header = {}\n",
+        curl_config_value("anthropic-version: 2023-06-01")
+    ));
+    if let Some(headers) = settings.api_headers.as_ref() {
+        for (name, value) in headers {
+            config.push_str(&format!(
+                "header = {}\n",
+                curl_config_value(&format!("{}: {}", name.trim(), value.trim()))
+            ));
+        }
+    }
+    // The body is captured rather than sent to /dev/null, which Windows
+    // lacks; the status and timing follow it on a line of their own.
+    let args = ["--max-time", "10", "-w", "\\n%{http_code} %{time_total}"].map(String::from);
+
+    let stdout = match run_curl(&config, &args).await {
+        Ok(stdout) => stdout,
+        Err(details) => {
+            return Ok(json!({
+                "ok": false,
+                "url": url,
+                "status": null,
+                "latencyMs": null,
+                "details": details,
+            }))
+        }
+    };
+    let mut parts = stdout.lines().last().unwrap_or("").split_whitespace();
+    let status = parts.next().and_then(|code| code.parse::<u16>().ok());
+    let latency_ms = parts
+        .next()
+        .and_then(|secs| secs.parse::<f64>().ok())
+        .map(|secs| (secs * 1000.0).round() as u64);
+    let reachable = matches!(status, Some(code) if code != 0);
+    let details = if reachable {
+        None
+    } else {
+        Some("No HTTP response from the endpoint.".to_string())
+    };
+
+    Ok(json!({
+        "ok": reachable,
+        "url": url,
+        "status": status,
+        "latencyMs": latency_ms,
+        "details": details,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{gateway_env, missing_provider_fields, provider_env};
+    use crate::types::{ClaudeProvider, ProviderSettings, WorkspaceSettings};
+
+    #[test]
+    fn bedrock_env_includes_region_and_profile() {
+        let config = ProviderSettings {
+            aws_region: Some("us-east-1".to_string()),
+            aws_profile: Some("work".to_string()),
+            ..ProviderSettings::default()
+        };
+        let env = provider_env(&ClaudeProvider::Bedrock, &config);
+        assert_eq!(env.get("CLAUDE_CODE_USE_BEDROCK").map(String::as_str), Some("1"));
+        assert_eq!(env.get("AWS_REGION").map(String::as_str), Some("us-east-1"));
+        assert_eq!(env.get("AWS_PROFILE").map(String::as_str), Some("work"));
+        assert!(missing_provider_fields(&ClaudeProvider::Bedrock, &config).is_empty());
+    }
+
+    #[test]
+    fn vertex_requires_project_and_region() {
+        let config = ProviderSettings {
+            vertex_region: Some("  ".to_string()),
+            ..ProviderSettings::default()
+        };
+        assert_eq!(
+            missing_provider_fields(&ClaudeProvider::Vertex, &config),
+            vec!["vertexRegion", "vertexProjectId"]
+        );
+        assert!(provider_env(&ClaudeProvider::Anthropic, &config).is_empty());
+    }
+
+    #[test]
+    fn gateway_env_formats_base_url_and_headers() {
+        let mut headers = HashMap::new();
+        headers.insert("X-Team".to_string(), "infra".to_string());
+        headers.insert("X-Api-Key".to_string(), " secret ".to_string());
+        let settings = WorkspaceSettings {
+            api_base_url: Some("https://llm.example.com/".to_string()),
+            api_headers: Some(headers),
+            ..WorkspaceSettings::default()
+        };
+        let env = gateway_env(&settings);
+        assert_eq!(
+            env.get("ANTHROPIC_BASE_URL").map(String::as_str),
+            Some("https://llm.example.com")
+        );
+        assert_eq!(
+            env.get("ANTHROPIC_CUSTOM_HEADERS").map(String::as_str),
+            Some("X-Api-Key: secret\nX-Team: infra")
+        );
+        assert!(gateway_env(&WorkspaceSettings::default()).is_empty());
+    }
+}
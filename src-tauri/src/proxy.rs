@@ -0,0 +1,434 @@
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use base64::Engine;
+use serde::Serialize;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::types::ProxySettings;
+
+/// Reached by `test_proxy` when no URL is given.
+const DEFAULT_TEST_URL: &str = "https://api.anthropic.com";
+
+/// Mirrors the `proxy` setting; read when building child processes, which
+/// have no access to app state.
+static PROXY: RwLock<ProxySettings> = RwLock::new(ProxySettings {
+    url: None,
+    no_proxy: None,
+});
+/// Mirrors the `caBundle` setting, for the same reason.
+static CA_BUNDLE: RwLock<Option<CaBundle>> = RwLock::new(None);
+/// File in the data dir holding the system roots plus `caBundle`.
+const COMBINED_CA_BUNDLE: &str = "ca-bundle.pem";
+
+#[derive(Debug, Clone)]
+struct CaBundle {
+    /// The bundle as configured.
+    custom: String,
+    /// The system store followed by the bundle; `None` when the system
+    /// store could not be read.
+    combined: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProxyStatus {
+    url: String,
+    no_proxy: Option<String>,
+    /// "settings" or "environment".
+    source: String,
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn first_env(names: &[&str], env: &impl Fn(&str) -> Option<String>) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| non_empty(env(name).as_deref()))
+}
+
+pub(crate) fn validate_proxy_url(url: &str) -> Result<(), String> {
+    let url = url.trim();
+    let scheme_ok = ["http://", "https://", "socks5://", "socks5h://"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme));
+    if !scheme_ok
+        || url
+            .split_once("://")
+            .is_some_and(|(_, rest)| rest.is_empty())
+    {
+        return Err(format!(
+            "Invalid proxy URL: {url}. Use http://, https:// or socks5://host:port."
+        ));
+    }
+    Ok(())
+}
+
+/// The proxy in effect: the setting when set, else the usual environment
+/// variables.
+fn resolve(settings: &ProxySettings, env: impl Fn(&str) -> Option<String>) -> Option<ProxyStatus> {
+    if let Some(url) = non_empty(settings.url.as_deref()) {
+        return Some(ProxyStatus {
+            url,
+            no_proxy: non_empty(settings.no_proxy.as_deref())
+                .or_else(|| first_env(&["NO_PROXY", "no_proxy"], &env)),
+            source: "settings".to_string(),
+        });
+    }
+    let url = first_env(
+        &[
+            "HTTPS_PROXY",
+            "https_proxy",
+            "HTTP_PROXY",
+            "http_proxy",
+            "ALL_PROXY",
+            "all_proxy",
+        ],
+        &env,
+    )?;
+    Some(ProxyStatus {
+        url,
+        no_proxy: first_env(&["NO_PROXY", "no_proxy"], &env),
+        source: "environment".to_string(),
+    })
+}
+
+/// Follow the `proxy` setting for child processes started from now on.
+pub(crate) fn set_proxy(settings: &ProxySettings) {
+    if let Ok(mut proxy) = PROXY.write() {
+        *proxy = settings.clone();
+    }
+}
+
+pub(crate) fn proxy_status() -> Option<ProxyStatus> {
+    let settings = PROXY.read().map(|proxy| proxy.clone()).unwrap_or_default();
+    resolve(&settings, |name| std::env::var(name).ok())
+}
+
+/// Proxy variables for a child process when the in-app setting is used.
+/// Proxies from the environment are inherited as is.
+fn proxy_env(status: Option<&ProxyStatus>) -> Vec<(&'static str, String)> {
+    let Some(status) = status.filter(|status| status.source == "settings") else {
+        return Vec::new();
+    };
+    let mut env: Vec<(&'static str, String)> =
+        ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]
+            .into_iter()
+            .map(|name| (name, status.url.clone()))
+            .collect();
+    if let Some(no_proxy) = &status.no_proxy {
+        env.push(("NO_PROXY", no_proxy.clone()));
+        env.push(("no_proxy", no_proxy.clone()));
+    }
+    env
+}
+
+fn pem_certificate(der: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+    let mut rest = encoded.as_str();
+    while !rest.is_empty() {
+        let (line, tail) = rest.split_at(rest.len().min(64));
+        pem.push_str(line);
+        pem.push('\n');
+        rest = tail;
+    }
+    pem.push_str("-----END CERTIFICATE-----\n");
+    pem
+}
+
+/// Write the system store followed by the bundle at `custom` into
+/// `data_dir`, returning the new file's path.
+fn write_combined_bundle(custom: &str, data_dir: &Path) -> Result<String, String> {
+    let custom_pem = std::fs::read_to_string(custom)
+        .map_err(|e| format!("Cannot read CA bundle {custom}: {e}"))?;
+    let native = rustls_native_certs::load_native_certs();
+    if native.certs.is_empty() {
+        let errors: Vec<String> = native.errors.iter().map(|e| e.to_string()).collect();
+        return Err(format!(
+            "No system certificates found ({})",
+            errors.join("; ")
+        ));
+    }
+    let mut combined: String = native
+        .certs
+        .iter()
+        .map(|cert| pem_certificate(cert))
+        .collect();
+    combined.push_str(&custom_pem);
+    if !combined.ends_with('\n') {
+        combined.push('\n');
+    }
+    std::fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+    let path = data_dir.join(COMBINED_CA_BUNDLE);
+    std::fs::write(&path, combined).map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Trust the certificates in `caBundle` on top of the system store. The
+/// merged copy is rebuilt in `data_dir` each time, so edits to the bundle
+/// and to the system store are picked up on the next save or launch.
+pub(crate) fn set_ca_bundle(path: Option<&str>, data_dir: &Path) {
+    let bundle = non_empty(path).map(|custom| {
+        let combined = match write_combined_bundle(&custom, data_dir) {
+            Ok(combined) => Some(combined),
+            Err(err) => {
+                log_line!("Passing the CA bundle without the system store: {err}");
+                None
+            }
+        };
+        CaBundle { custom, combined }
+    });
+    if let Ok(mut current) = CA_BUNDLE.write() {
+        *current = bundle;
+    }
+}
+
+/// CA variables for a child process. curl and Go tools (gh) replace their
+/// store with the file they are given, so they get the merged bundle; Node
+/// adds the bundle to its built-in store.
+fn ca_env(bundle: Option<&CaBundle>) -> Vec<(&'static str, String)> {
+    let Some(bundle) = bundle else {
+        return Vec::new();
+    };
+    let replacement = bundle.combined.as_ref().unwrap_or(&bundle.custom);
+    vec![
+        ("CURL_CA_BUNDLE", replacement.clone()),
+        ("SSL_CERT_FILE", replacement.clone()),
+        ("NODE_EXTRA_CA_CERTS", bundle.custom.clone()),
+    ]
+}
+
+/// Check a CA bundle is a readable PEM file. Returns its certificate count.
+pub(crate) fn validate_ca_bundle(path: &str) -> Result<usize, String> {
+    let data = std::fs::read_to_string(path.trim())
+        .map_err(|e| format!("Cannot read CA bundle {path}: {e}"))?;
+    let certificates = data.matches("-----BEGIN CERTIFICATE-----").count();
+    if certificates == 0 {
+        return Err(format!("No PEM certificates found in {path}"));
+    }
+    Ok(certificates)
+}
+
+/// Route a child process (curl, gh, the bridge) through the configured
+/// proxy and CA bundle.
+pub(crate) fn apply_network_env(command: &mut Command) {
+    command.envs(proxy_env(proxy_status().as_ref()));
+    let bundle = CA_BUNDLE.read().ok().and_then(|bundle| bundle.clone());
+    command.envs(ca_env(bundle.as_ref()));
+}
+
+/// The proxy in effect, or `None` for direct connections.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_proxy_status(_timer: CommandTimer) -> Result<Option<ProxyStatus>, String> {
+    Ok(proxy_status())
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CaBundleInfo {
+    path: String,
+    certificates: usize,
+}
+
+/// Validate a CA bundle before saving it as `caBundle`.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn check_ca_bundle(
+    path: String,
+    _timer: CommandTimer,
+) -> Result<CaBundleInfo, String> {
+    let certificates = validate_ca_bundle(&path)?;
+    Ok(CaBundleInfo {
+        path: path.trim().to_string(),
+        certificates,
+    })
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProxyTestResult {
+    ok: bool,
+    url: String,
+    proxy: Option<String>,
+    status: Option<u16>,
+    latency_ms: Option<u64>,
+    details: Option<String>,
+}
+
+/// Reach `url` (the Anthropic API by default) through `proxy`, or the proxy
+/// in effect when omitted. Any HTTP response counts as success.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn test_proxy(
+    proxy: Option<String>,
+    url: Option<String>,
+    _timer: CommandTimer,
+) -> Result<ProxyTestResult, String> {
+    let url = non_empty(url.as_deref()).unwrap_or_else(|| DEFAULT_TEST_URL.to_string());
+    let mut command = Command::new("curl");
+    command.args([
+        "-sS",
+        "-o",
+        "/dev/null",
+        "--max-time",
+        "10",
+        "-w",
+        "%{http_code} %{time_total}",
+    ]);
+    let proxy = match non_empty(proxy.as_deref()) {
+        Some(proxy) => {
+            validate_proxy_url(&proxy)?;
+            command.args(["--proxy", &proxy]);
+            Some(proxy)
+        }
+        None => {
+            apply_network_env(&mut command);
+            proxy_status().map(|status| status.url)
+        }
+    };
+    command.arg(&url);
+
+    let Ok(output) = timeout(Duration::from_secs(15), command.output()).await else {
+        return Ok(ProxyTestResult {
+            ok: false,
+            url,
+            proxy,
+            status: None,
+            latency_ms: None,
+            details: Some("Timed out while connecting.".to_string()),
+        });
+    };
+    let output = output.map_err(|e| format!("Failed to run curl: {e}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut parts = stdout.split_whitespace();
+    let status = parts
+        .next()
+        .and_then(|code| code.parse::<u16>().ok())
+        .filter(|code| *code != 0);
+    let latency_ms = parts
+        .next()
+        .and_then(|secs| secs.parse::<f64>().ok())
+        .map(|secs| (secs * 1000.0).round() as u64);
+    let details = status
+        .is_none()
+        .then(|| String::from_utf8_lossy(&output.stderr).trim().to_string());
+    Ok(ProxyTestResult {
+        ok: status.is_some(),
+        url,
+        proxy,
+        status,
+        latency_ms,
+        details,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        ca_env, pem_certificate, proxy_env, resolve, validate_ca_bundle, validate_proxy_url,
+        CaBundle,
+    };
+    use crate::types::ProxySettings;
+    use crate::utils::TempDir;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn settings_win_over_the_environment() {
+        let settings = ProxySettings {
+            url: Some("http://proxy.corp:3128".to_string()),
+            no_proxy: None,
+        };
+        let status = resolve(
+            &settings,
+            env(&[
+                ("https_proxy", "http://other:8080"),
+                ("NO_PROXY", "localhost"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(status.url, "http://proxy.corp:3128");
+        assert_eq!(status.no_proxy.as_deref(), Some("localhost"));
+        assert_eq!(status.source, "settings");
+        assert!(proxy_env(Some(&status))
+            .contains(&("HTTPS_PROXY", "http://proxy.corp:3128".to_string())));
+
+        let status = resolve(
+            &ProxySettings::default(),
+            env(&[("https_proxy", "http://other:8080")]),
+        )
+        .unwrap();
+        assert_eq!(status.source, "environment");
+        assert!(proxy_env(Some(&status)).is_empty());
+        assert!(resolve(&ProxySettings::default(), env(&[])).is_none());
+    }
+
+    #[test]
+    fn validates_proxy_urls() {
+        assert!(validate_proxy_url("http://proxy:3128").is_ok());
+        assert!(validate_proxy_url("socks5h://127.0.0.1:1080").is_ok());
+        assert!(validate_proxy_url("proxy:3128").is_err());
+        assert!(validate_proxy_url("http://").is_err());
+    }
+
+    #[test]
+    fn ca_bundle_reaches_every_client() {
+        assert!(ca_env(None).is_empty());
+        let bundle = CaBundle {
+            custom: "/etc/corp-ca.pem".to_string(),
+            combined: Some("/data/ca-bundle.pem".to_string()),
+        };
+        assert_eq!(
+            ca_env(Some(&bundle)),
+            [
+                ("CURL_CA_BUNDLE", "/data/ca-bundle.pem".to_string()),
+                ("SSL_CERT_FILE", "/data/ca-bundle.pem".to_string()),
+                ("NODE_EXTRA_CA_CERTS", "/etc/corp-ca.pem".to_string()),
+            ]
+        );
+        let custom_only = CaBundle {
+            combined: None,
+            ..bundle
+        };
+        assert!(ca_env(Some(&custom_only))
+            .iter()
+            .all(|(_, path)| path == "/etc/corp-ca.pem"));
+
+        let dir = TempDir::new("ca-bundle");
+        let path = dir.join("bundle.pem");
+        std::fs::write(&path, "not a certificate").unwrap();
+        assert!(validate_ca_bundle(&path.to_string_lossy()).is_err());
+        let cert = "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n";
+        std::fs::write(&path, cert.repeat(2)).unwrap();
+        assert_eq!(validate_ca_bundle(&path.to_string_lossy()), Ok(2));
+    }
+
+    #[test]
+    fn wraps_der_certificates_as_pem() {
+        let pem = pem_certificate(&[0u8; 60]);
+        let lines: Vec<&str> = pem.lines().collect();
+        assert_eq!(lines[0], "-----BEGIN CERTIFICATE-----");
+        assert_eq!(lines[1].len(), 64);
+        assert_eq!(lines[2], "AAAAAAAAAAAAAAAA");
+        assert_eq!(lines[3], "-----END CERTIFICATE-----");
+    }
+}
@@ -0,0 +1,210 @@
+use std::path::Path;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::claude::{emit_scoped, ClaudeEvent};
+use crate::command_metrics::CommandTimer;
+use crate::events::{ClaudeEventPayload, UnreadChangedPayload};
+use crate::lan_share::broadcast;
+use crate::registry::{resolve_transcript_path, write_registry};
+use crate::state::AppState;
+use crate::transcript_index::{index_path, indexed_transcript};
+use crate::types::{SessionEntry, ThreadRegistry};
+
+/// Replies since the session's last read message. Sessions never marked
+/// read have nothing unread, so imported history doesn't light up.
+pub(crate) fn unread_count(session: &SessionEntry, index_file: &Path, transcript: &Path) -> usize {
+    let Some(line) = session.last_read_line else {
+        return 0;
+    };
+    indexed_transcript(index_file, transcript)
+        .map(|index| index.replies_after(line))
+        .unwrap_or(0)
+}
+
+/// Workspace listing the session in its sidebar.
+fn visible_workspace(registry: &ThreadRegistry, session_id: &str) -> String {
+    registry
+        .workspaces
+        .iter()
+        .find(|(_, workspace)| {
+            workspace
+                .visible_session_ids
+                .iter()
+                .any(|id| id == session_id)
+        })
+        .map(|(id, _)| id.clone())
+        .unwrap_or_default()
+}
+
+fn emit_unread_changed(
+    app_handle: &AppHandle,
+    session_id: &str,
+    workspace_id: &str,
+    unread_count: usize,
+) {
+    let event = ClaudeEvent {
+        event_type: "unread/changed".to_string(),
+        session_id: session_id.to_string(),
+        workspace_id: workspace_id.to_string(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        payload: ClaudeEventPayload::UnreadChanged(UnreadChangedPayload { unread_count }),
+    };
+    broadcast(app_handle, &event);
+    emit_scoped(app_handle, "claude-event", workspace_id, event);
+}
+
+/// Recount a session's unread replies after a turn and tell the sidebar.
+pub(crate) async fn refresh_unread(app_handle: &AppHandle, session_id: &str) {
+    let state: State<'_, AppState> = app_handle.state();
+    let (session, transcript, workspace_id) = {
+        let registry = state.registry.lock().await;
+        let Some(session) = registry.sessions.get(session_id).cloned() else {
+            return;
+        };
+        if session.last_read_line.is_none() {
+            return;
+        }
+        let Ok(transcript) = resolve_transcript_path(&registry, session_id) else {
+            return;
+        };
+        let workspace_id = visible_workspace(&registry, session_id);
+        (session, transcript, workspace_id)
+    };
+    let index_file = index_path(&state, session_id);
+    let Ok(count) =
+        tokio::task::spawn_blocking(move || unread_count(&session, &index_file, &transcript)).await
+    else {
+        return;
+    };
+    emit_unread_changed(app_handle, session_id, &workspace_id, count);
+}
+
+/// Line to mark read: `message_id` if the transcript has it, else the
+/// newest message. Returns the line and the replies after it.
+fn read_line(
+    session_id: &str,
+    index_file: &Path,
+    transcript: &Path,
+    message_id: Option<&str>,
+) -> Result<(Option<usize>, usize), String> {
+    let index = indexed_transcript(index_file, transcript)?;
+    let line = match message_id {
+        Some(id) => index.message_line(session_id, transcript, id)?,
+        None => None,
+    }
+    .or(index.last_message_line());
+    let unread = line.map(|line| index.replies_after(line)).unwrap_or(0);
+    Ok((line, unread))
+}
+
+/// Record the newest message the user has seen in a session. Without a
+/// `message_id`, or if the transcript doesn't have it yet, the whole
+/// session is marked read. Returns the replies still unread.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn mark_session_read(
+    session_id: String,
+    message_id: Option<String>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<usize, String> {
+    let transcript = {
+        let registry = state.registry.lock().await;
+        resolve_transcript_path(&registry, &session_id)?
+    };
+    let index_file = index_path(&state, &session_id);
+    let (line, unread) = {
+        let session_id = session_id.clone();
+        let message_id = message_id.clone();
+        tokio::task::spawn_blocking(move || {
+            read_line(&session_id, &index_file, &transcript, message_id.as_deref())
+        })
+        .await
+        .map_err(|_| "mark read failed".to_string())??
+    };
+
+    let workspace_id = {
+        let mut registry = state.registry.lock().await;
+        let session = registry
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| format!("Session {} not found", session_id))?;
+        if session.last_read_line != line || session.last_read_message_id != message_id {
+            session.last_read_line = line;
+            session.last_read_message_id = message_id;
+            write_registry(&state.registry_path, &registry)?;
+        }
+        visible_workspace(&registry, &session_id)
+    };
+    emit_unread_changed(&app_handle, &session_id, &workspace_id, unread);
+    Ok(unread)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{read_line, unread_count};
+    use crate::types::{SessionEntry, SessionStatus};
+    use crate::utils::TempDir;
+
+    fn session(last_read_line: Option<usize>) -> SessionEntry {
+        SessionEntry {
+            session_id: "s1".to_string(),
+            cwd: "/tmp".to_string(),
+            preview: None,
+            created_at: 0,
+            last_activity: 0,
+            transcript_path: None,
+            project_path: None,
+            status: SessionStatus::Active,
+            usage: None,
+            summary: None,
+            files: Vec::new(),
+            interrupted_turns: Vec::new(),
+            last_read_message_id: None,
+            last_read_line,
+            metadata: BTreeMap::new(),
+            time: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn counts_replies_after_the_read_message() {
+        let dir = TempDir::new("read-receipts");
+        let transcript = dir.join("s1.jsonl");
+        let index_file = dir.join("index.json");
+        let line = |entry_type: &str, uuid: &str, text: &str| {
+            format!(
+                "{{\"type\":\"{entry_type}\",\"uuid\":\"{uuid}\",\"message\":{{\"content\":[{{\"type\":\"text\",\"text\":\"{text}\"}}]}}}}\n"
+            )
+        };
+        let contents = [
+            line("user", "u1", "hi"),
+            line("assistant", "a1", "hello"),
+            "{\"type\":\"system\"}\n".to_string(),
+            line("user", "u2", "run the tests"),
+            line("assistant", "a2", "running"),
+            line("assistant", "a3", "all green"),
+        ]
+        .concat();
+        std::fs::write(&transcript, contents).unwrap();
+
+        assert_eq!(
+            read_line("s1", &index_file, &transcript, Some("a1")).unwrap(),
+            (Some(1), 2)
+        );
+        assert_eq!(
+            read_line("s1", &index_file, &transcript, Some("gone")).unwrap(),
+            (Some(5), 0)
+        );
+        assert_eq!(
+            read_line("s1", &index_file, &transcript, None).unwrap(),
+            (Some(5), 0)
+        );
+        assert_eq!(unread_count(&session(Some(1)), &index_file, &transcript), 2);
+        assert_eq!(unread_count(&session(None), &index_file, &transcript), 0);
+    }
+}
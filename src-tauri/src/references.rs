@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// A file path mentioned in message text.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileReference {
+    /// The reference as written, e.g. `@src/lib.rs` or `src/lib.rs:10-20`.
+    text: String,
+    /// Relative to the workspace root when inside it.
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<u32>,
+    /// Whether the file exists inside the workspace.
+    exists: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Candidate<'a> {
+    text: &'a str,
+    path: &'a str,
+    line: Option<u32>,
+    end_line: Option<u32>,
+    mention: bool,
+}
+
+fn is_delimiter(ch: char) -> bool {
+    ch.is_whitespace() || "()[]{}<>\"'`,;|*".contains(ch)
+}
+
+/// Split `:12`, `:12-20`, `:12:5` or `#L12-L20` off the end of a token.
+fn split_line_suffix(token: &str) -> (&str, Option<u32>, Option<u32>) {
+    if let Some((path, anchor)) = token.rsplit_once("#L") {
+        let (start, end) = match anchor.split_once('-') {
+            Some((start, end)) => (start, Some(end.trim_start_matches('L'))),
+            None => (anchor, None),
+        };
+        if let Ok(line) = start.parse() {
+            return (path, Some(line), end.and_then(|end| end.parse().ok()));
+        }
+        return (token, None, None);
+    }
+    let mut parts = token.splitn(3, ':');
+    let path = parts.next().unwrap_or(token);
+    let Some(range) = parts.next() else {
+        return (token, None, None);
+    };
+    // A trailing `:col` is accepted and ignored.
+    if parts.next().is_some_and(|col| col.parse::<u32>().is_err()) {
+        return (token, None, None);
+    }
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (start, end.parse().ok()),
+        None => (range, None),
+    };
+    match start.parse() {
+        Ok(line) => (path, Some(line), end),
+        Err(_) => (token, None, None),
+    }
+}
+
+/// Last segment has an extension such as `.rs` or `.tsx`, not `e.g`.
+fn has_file_extension(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) => {
+            !stem.is_empty()
+                && (2..=8).contains(&ext.len())
+                && ext.starts_with(|ch: char| ch.is_ascii_alphabetic())
+                && ext.chars().all(|ch| ch.is_ascii_alphanumeric())
+        }
+        None => false,
+    }
+}
+
+fn candidates(text: &str) -> Vec<Candidate<'_>> {
+    let mut found = Vec::new();
+    for token in text.split(is_delimiter) {
+        let token = token.trim_end_matches(['.', ':', '!', '?']);
+        if token.len() < 3 || token.contains("://") {
+            continue;
+        }
+        let (mention, body) = match token.strip_prefix('@') {
+            Some(body) => (true, body),
+            None => (false, token),
+        };
+        let (path, line, end_line) = split_line_suffix(body);
+        let plausible = if mention {
+            !path.is_empty() && !path.contains('@')
+        } else {
+            has_file_extension(path)
+                && !path.contains('@')
+                && path.chars().any(|ch| ch.is_ascii_alphabetic())
+        };
+        if plausible {
+            found.push(Candidate {
+                text: token,
+                path,
+                line,
+                end_line,
+                mention,
+            });
+        }
+    }
+    found
+}
+
+/// Workspace-relative form of `path`, if it stays inside `root`.
+fn resolve(root: &Path, path: &str) -> Option<(String, bool)> {
+    let candidate = Path::new(path.strip_prefix("./").unwrap_or(path));
+    let joined = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        root.join(candidate)
+    };
+    match std::fs::canonicalize(&joined) {
+        Ok(resolved) => {
+            let relative = resolved.strip_prefix(root).ok()?;
+            Some((
+                crate::utils::normalize_git_path(&relative.to_string_lossy()),
+                resolved.is_file(),
+            ))
+        }
+        Err(_) => {
+            let relative = joined.strip_prefix(root).ok()?;
+            Some((
+                crate::utils::normalize_git_path(&relative.to_string_lossy()),
+                false,
+            ))
+        }
+    }
+}
+
+/// Collects file references across a transcript, checking each distinct
+/// path against the filesystem once.
+pub(crate) struct ReferenceResolver {
+    root: Option<PathBuf>,
+    cache: HashMap<String, Option<(String, bool)>>,
+}
+
+impl ReferenceResolver {
+    pub(crate) fn new(root: &Path) -> Self {
+        Self {
+            root: std::fs::canonicalize(root).ok(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// References in `text`. Bare paths are kept only when the file exists,
+    /// so prose like `config.yaml` in a sentence about another project does
+    /// not become a dead link; `@` mentions and paths with a directory are
+    /// always kept and flagged.
+    pub(crate) fn references(&mut self, text: &str) -> Vec<FileReference> {
+        let Some(root) = self.root.as_deref() else {
+            return Vec::new();
+        };
+        let mut references: Vec<FileReference> = Vec::new();
+        for candidate in candidates(text) {
+            if references.iter().any(|r| r.text == candidate.text) {
+                continue;
+            }
+            let resolved = self
+                .cache
+                .entry(candidate.path.to_string())
+                .or_insert_with(|| resolve(root, candidate.path))
+                .clone();
+            let (path, exists) = match resolved {
+                Some(resolved) => resolved,
+                None => (candidate.path.to_string(), false),
+            };
+            if !exists && !candidate.mention && !candidate.path.contains('/') {
+                continue;
+            }
+            references.push(FileReference {
+                text: candidate.text.to_string(),
+                path,
+                line: candidate.line,
+                end_line: candidate.end_line,
+                exists,
+            });
+        }
+        references
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{candidates, split_line_suffix, ReferenceResolver};
+    use crate::utils::TempDir;
+
+    #[test]
+    fn parses_line_suffixes() {
+        assert_eq!(
+            split_line_suffix("src/a.rs:12"),
+            ("src/a.rs", Some(12), None)
+        );
+        assert_eq!(
+            split_line_suffix("src/a.rs:12-20"),
+            ("src/a.rs", Some(12), Some(20))
+        );
+        assert_eq!(
+            split_line_suffix("src/a.rs:12:5"),
+            ("src/a.rs", Some(12), None)
+        );
+        assert_eq!(
+            split_line_suffix("src/a.rs#L3-L9"),
+            ("src/a.rs", Some(3), Some(9))
+        );
+        assert_eq!(
+            split_line_suffix("localhost:port"),
+            ("localhost:port", None, None)
+        );
+    }
+
+    #[test]
+    fn finds_mentions_and_paths_in_text() {
+        let found = candidates(
+            "See @src/lib.rs and `src/main.rs:40`, e.g. https://x.io/a.rs or (README.md).",
+        );
+        let paths: Vec<_> = found.iter().map(|c| c.path).collect();
+        assert_eq!(paths, ["src/lib.rs", "src/main.rs", "README.md"]);
+        assert!(found[0].mention);
+        assert_eq!(found[1].line, Some(40));
+    }
+
+    #[test]
+    fn validates_references_against_the_workspace() {
+        let root = TempDir::new("refs");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+
+        let mut resolver = ReferenceResolver::new(&root);
+        let refs = resolver.references(
+            "Edited src/lib.rs:3 and Cargo.toml; see src/gone.rs, notes.txt and ../secret.rs",
+        );
+        let summary: Vec<_> = refs
+            .iter()
+            .map(|r| (r.path.as_str(), r.line, r.exists))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("src/lib.rs", Some(3), true),
+                ("Cargo.toml", None, true),
+                ("src/gone.rs", None, false),
+                ("../secret.rs", None, false),
+            ]
+        );
+    }
+}
@@ -1,22 +1,37 @@
+use std::collections::{BTreeMap, HashMap};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::Serialize;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
-use crate::state::AppState;
-use crate::types::{SessionEntry, SessionStatus, ThreadRegistry, WorkspaceRegistry};
+use crate::claude::ClaudeEvent;
+use crate::command_metrics::CommandTimer;
+use crate::events::{ClaudeEventPayload, ScanProgressPayload};
+use crate::read_receipts::unread_count;
+use crate::references::ReferenceResolver;
 
-#[derive(Debug, Serialize)]
+use crate::state::AppState;
+use crate::tickets::session_completed;
+use crate::transcript_index::index_path;
+use crate::turns::interrupted_turn_item;
+use crate::types::{
+    InterruptedTurn, SessionEntry, SessionStatus, ThinkingBlocks, ThreadRegistry, WorkspaceRegistry,
+};
+use crate::utils::{canonicalize_path, path_key, trim_trailing_separators, PathKey};
+
+#[derive(Debug, Serialize, specta::Type)]
 pub(crate) struct SessionHistory {
-    items: Vec<serde_json::Value>,
+    pub(crate) items: Vec<serde_json::Value>,
     preview: Option<String>,
     #[serde(rename = "lastActivity")]
     last_activity: u64,
 }
 
-fn extract_text_from_message(message: &serde_json::Value) -> String {
+pub(crate) fn extract_text_from_message(message: &serde_json::Value) -> String {
     if let Some(content) = message.get("content").and_then(|c| c.as_array()) {
         let mut parts = Vec::new();
         for item in content {
@@ -36,20 +51,179 @@ fn extract_text_from_message(message: &serde_json::Value) -> String {
     String::new()
 }
 
-fn parse_session_history(
+/// History item for a transcript entry at `line_index`, or `None` for
+/// entries that are not user/assistant text.
+pub(crate) fn history_item(
+    session_id: &str,
+    line_index: usize,
+    entry: &serde_json::Value,
+) -> Option<serde_json::Value> {
+    let entry_type = entry.get("type").and_then(|t| t.as_str()).unwrap_or("");
+    if entry_type != "user" && entry_type != "assistant" {
+        return None;
+    }
+    let message = entry.get("message").unwrap_or(entry);
+    let text = extract_text_from_message(message);
+    if text.is_empty() {
+        return None;
+    }
+    let role = if entry_type == "assistant" {
+        "assistant"
+    } else {
+        "user"
+    };
+    let message_id = entry
+        .get("uuid")
+        .and_then(|u| u.as_str())
+        .map(|u| u.to_string())
+        .unwrap_or_else(|| format!("{}:{}", session_id, line_index));
+    Some(serde_json::json!({
+        "id": message_id,
+        "kind": "message",
+        "role": role,
+        "text": text,
+    }))
+}
+
+/// Longest tool input or output kept in a history item.
+const TOOL_EXCERPT_CHARS: usize = 2_000;
+
+pub(crate) fn excerpt(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Reasoning and tool items for the thinking and `tool_use` blocks of an
+/// assistant entry, shaped like the items live sessions build. Tools start
+/// out `running`; `apply_tool_result` fills in their outcome.
+fn block_items(
+    session_id: &str,
+    line_index: usize,
+    entry: &serde_json::Value,
+) -> Vec<serde_json::Value> {
+    if entry.get("type").and_then(|t| t.as_str()) != Some("assistant") {
+        return Vec::new();
+    }
+    let Some(content) = entry
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())
+    else {
+        return Vec::new();
+    };
+    let message_id = entry
+        .get("uuid")
+        .and_then(|u| u.as_str())
+        .map(|u| u.to_string())
+        .unwrap_or_else(|| format!("{}:{}", session_id, line_index));
+    let mut items = Vec::new();
+    for (index, block) in content.iter().enumerate() {
+        match block.get("type").and_then(|t| t.as_str()) {
+            Some("thinking") => {
+                let thinking = block.get("thinking").and_then(|t| t.as_str()).unwrap_or("");
+                if thinking.trim().is_empty() {
+                    continue;
+                }
+                // No summary: the view titles reasoning by its content.
+                items.push(serde_json::json!({
+                    "id": format!("{message_id}-thinking-{index}"),
+                    "kind": "reasoning",
+                    "summary": "",
+                    "content": thinking,
+                }));
+            }
+            Some("tool_use") => {
+                let Some(tool_use_id) = block.get("id").and_then(|id| id.as_str()) else {
+                    continue;
+                };
+                let name = block.get("name").and_then(|n| n.as_str()).unwrap_or("tool");
+                let input = block.get("input").cloned().unwrap_or(serde_json::json!({}));
+                let detail = serde_json::to_string_pretty(&input).unwrap_or_default();
+                items.push(serde_json::json!({
+                    "id": format!("tool-{tool_use_id}"),
+                    "kind": "tool",
+                    "toolType": name,
+                    "title": name,
+                    "detail": excerpt(&detail, TOOL_EXCERPT_CHARS),
+                    "status": "running",
+                }));
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+/// Record the `tool_result` blocks of a user entry on the tool items they
+/// answer, found through `tools` (tool use id to index in `items`).
+fn apply_tool_result(
+    entry: &serde_json::Value,
+    items: &mut [serde_json::Value],
+    tools: &HashMap<String, usize>,
+) {
+    let Some(content) = entry
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())
+    else {
+        return;
+    };
+    for block in content {
+        if block.get("type").and_then(|t| t.as_str()) != Some("tool_result") {
+            continue;
+        }
+        let Some(item) = block
+            .get("tool_use_id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| tools.get(id))
+            .and_then(|index| items.get_mut(*index))
+        else {
+            continue;
+        };
+        let output = match block.get("content") {
+            Some(serde_json::Value::String(text)) => text.clone(),
+            Some(content) => extract_text_from_message(&serde_json::json!({ "content": content })),
+            None => String::new(),
+        };
+        let failed = block.get("is_error").and_then(|e| e.as_bool()) == Some(true);
+        item["status"] = serde_json::json!(if failed { "failed" } else { "completed" });
+        item["output"] = serde_json::json!(excerpt(&output, TOOL_EXCERPT_CHARS));
+    }
+}
+
+/// Transcript lines with invalid UTF-8 replaced, so a stray byte costs a
+/// character rather than the whole entry.
+fn transcript_lines(reader: impl BufRead) -> impl Iterator<Item = String> {
+    reader.split(b'\n').map_while(Result::ok).map(|bytes| {
+        let line = String::from_utf8_lossy(&bytes);
+        line.strip_suffix('\r').unwrap_or(&line).to_string()
+    })
+}
+
+/// Parse a transcript into history items: messages, each carrying the file
+/// `references` in its text checked against `cwd`, plus reasoning and tool
+/// items in transcript order. `interrupted` turns are placed at the
+/// transcript line they were saved at.
+pub(crate) fn parse_session_history(
     session_id: &str,
     transcript_path: &Path,
+    cwd: &Path,
+    interrupted: &[InterruptedTurn],
 ) -> Result<SessionHistory, String> {
     let file = std::fs::File::open(transcript_path).map_err(|e| e.to_string())?;
     let reader = BufReader::new(file);
     let mut items = Vec::new();
+    let mut tools: HashMap<String, usize> = HashMap::new();
     let mut preview: Option<String> = None;
+    let mut resolver = ReferenceResolver::new(cwd);
+    let mut interrupted = interrupted.iter().peekable();
 
-    for (index, line) in reader.lines().enumerate() {
-        let line = match line {
-            Ok(l) => l,
-            Err(_) => continue,
-        };
+    for (index, line) in transcript_lines(reader).enumerate() {
+        while let Some(turn) = interrupted.next_if(|turn| turn.transcript_line <= index) {
+            items.push(interrupted_turn_item(turn));
+        }
         if line.is_empty() {
             continue;
         }
@@ -57,35 +231,37 @@ fn parse_session_history(
             Ok(v) => v,
             Err(_) => continue,
         };
-        let entry_type = entry.get("type").and_then(|t| t.as_str()).unwrap_or("");
-        if entry_type != "user" && entry_type != "assistant" {
-            continue;
-        }
-        let message = entry.get("message").unwrap_or(&entry);
-        let text = extract_text_from_message(message);
-        if text.is_empty() {
-            continue;
+        apply_tool_result(&entry, &mut items, &tools);
+        // Reasoning goes before the reply, tool calls after it.
+        let (tool_calls, reasoning): (Vec<_>, Vec<_>) = block_items(session_id, index, &entry)
+            .into_iter()
+            .partition(|item| item["kind"] == "tool");
+        items.extend(reasoning);
+        if let Some(mut item) = history_item(session_id, index, &entry) {
+            let references = item
+                .get("text")
+                .and_then(|t| t.as_str())
+                .map(|text| resolver.references(text))
+                .unwrap_or_default();
+            if !references.is_empty() {
+                item["references"] = serde_json::json!(references);
+            }
+            if preview.is_none() && item.get("role").and_then(|r| r.as_str()) == Some("user") {
+                preview = item
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .map(|t| t.to_string());
+            }
+            items.push(item);
         }
-        if preview.is_none() && entry_type == "user" {
-            preview = Some(text.clone());
+        for item in tool_calls {
+            if let Some(id) = item["id"].as_str().and_then(|id| id.strip_prefix("tool-")) {
+                tools.insert(id.to_string(), items.len());
+            }
+            items.push(item);
         }
-        let role = if entry_type == "assistant" {
-            "assistant"
-        } else {
-            "user"
-        };
-        let message_id = entry
-            .get("uuid")
-            .and_then(|u| u.as_str())
-            .map(|u| u.to_string())
-            .unwrap_or_else(|| format!("{}:{}", session_id, index));
-        items.push(serde_json::json!({
-            "id": message_id,
-            "kind": "message",
-            "role": role,
-            "text": text,
-        }));
     }
+    items.extend(interrupted.map(interrupted_turn_item));
 
     if preview.is_none() {
         preview = items
@@ -132,7 +308,7 @@ pub(crate) fn write_registry(path: &PathBuf, registry: &ThreadRegistry) -> Resul
 
 /// Convert a workspace cwd path to Claude's project directory name.
 /// Claude uses a format like: /Users/foo/bar -> -Users-foo-bar
-fn cwd_to_project_dir_name(cwd: &str) -> String {
+pub(crate) fn cwd_to_project_dir_name(cwd: &str) -> String {
     // Replace path separators with dashes and prepend with dash
     let normalized = cwd.replace('/', "-").replace('\\', "-");
     // Handle the case where path starts with / (most Unix paths)
@@ -149,6 +325,12 @@ fn get_claude_projects_dir() -> Result<PathBuf, String> {
     Ok(home.join(".claude").join("projects"))
 }
 
+/// Claude project directory for a cwd (may not exist yet).
+pub(crate) fn claude_project_dir(cwd: &str) -> Option<PathBuf> {
+    let claude_projects = get_claude_projects_dir().ok()?;
+    Some(claude_projects.join(cwd_to_project_dir_name(cwd)))
+}
+
 /// Derive Claude project/transcript paths from cwd + session_id.
 pub(crate) fn derive_project_paths(
     cwd: &str,
@@ -164,67 +346,198 @@ pub(crate) fn derive_project_paths(
     ))
 }
 
+/// Extraction result for one transcript, valid while its mtime and size
+/// are unchanged. `None` records files that did not belong to the cwd.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedSessionScan {
+    modified: SystemTime,
+    len: u64,
+    session: Option<SessionEntry>,
+}
+
+/// transcript path -> last extraction, held on `AppState`.
+pub(crate) type ScanCache = HashMap<PathBuf, CachedSessionScan>;
+
 /// Scan Claude projects directory for sessions matching a workspace cwd.
 /// Returns sessions from the project directory that matches the cwd.
 pub(crate) fn scan_project_sessions(cwd: &str) -> Result<Vec<SessionEntry>, String> {
+    scan_project_sessions_cached(cwd, &mut ScanCache::new(), &|_, _, _| {})
+}
+
+/// Like `scan_project_sessions`, but parses transcripts on worker threads,
+/// reuses `cache` entries for unchanged files, and reports
+/// `(done, total, cached)` as files complete.
+pub(crate) fn scan_project_sessions_cached(
+    cwd: &str,
+    cache: &mut ScanCache,
+    on_progress: &(dyn Fn(usize, usize, usize) + Sync),
+) -> Result<Vec<SessionEntry>, String> {
     let claude_projects = get_claude_projects_dir()?;
+    scan_project_dir(&claude_projects, cwd, cache, on_progress)
+}
 
+/// `scan_project_sessions_cached` against a given projects directory
+/// rather than ~/.claude/projects.
+pub(crate) fn scan_project_dir(
+    claude_projects: &Path,
+    cwd: &str,
+    cache: &mut ScanCache,
+    on_progress: &(dyn Fn(usize, usize, usize) + Sync),
+) -> Result<Vec<SessionEntry>, String> {
     if !claude_projects.exists() {
         return Ok(Vec::new());
     }
 
-    // Convert cwd to Claude project directory name
-    let project_dir_name = cwd_to_project_dir_name(cwd);
-    let project_dir = claude_projects.join(&project_dir_name);
+    // Claude names the project directory after the cwd it saw, which is the
+    // symlink-resolved path (e.g. /private/var rather than /var on macOS),
+    // so check both spellings.
+    let mut pending = Vec::new();
+    let mut sessions = Vec::new();
+    let mut cached = 0;
+    for dir_name in project_dir_names(cwd) {
+        let project_dir = claude_projects.join(&dir_name);
+        if !project_dir.is_dir() {
+            continue;
+        }
+        for path in list_transcripts(&project_dir) {
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            match cache.get(&path) {
+                Some(hit) if hit.modified == modified && hit.len == metadata.len() => {
+                    cached += 1;
+                    sessions.extend(hit.session.clone());
+                }
+                _ => pending.push((path, project_dir.clone(), modified, metadata.len())),
+            }
+        }
+    }
 
-    if !project_dir.exists() || !project_dir.is_dir() {
-        // No project directory for this cwd
-        return Ok(Vec::new());
+    let total = pending.len() + cached;
+    on_progress(cached, total, cached);
+    let cwd_key = PathKey::new(cwd);
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(cached);
+    let results = Mutex::new(Vec::with_capacity(pending.len()));
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(pending.len().max(1));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some((path, project_dir, modified, len)) = pending.get(index) else {
+                    break;
+                };
+                let session = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|session_id| {
+                        extract_session_from_jsonl(path, session_id, &cwd_key, project_dir).ok()
+                    });
+                if let Ok(mut results) = results.lock() {
+                    results.push((
+                        path.clone(),
+                        CachedSessionScan {
+                            modified: *modified,
+                            len: *len,
+                            session,
+                        },
+                    ));
+                }
+                on_progress(done.fetch_add(1, Ordering::Relaxed) + 1, total, cached);
+            });
+        }
+    });
+
+    for (path, scan) in results.into_inner().unwrap_or_default() {
+        sessions.extend(scan.session.clone());
+        cache.insert(path, scan);
     }
+    sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+    Ok(sessions)
+}
 
-    scan_project_dir(&project_dir, cwd)
+/// The `cache` entries for transcripts in `cwd`'s project directories, so a
+/// scan can work on a copy without hiding other workspaces' entries from
+/// scans running at the same time.
+fn cache_for_cwd(cache: &ScanCache, claude_projects: &Path, cwd: &str) -> ScanCache {
+    let project_dirs: Vec<PathBuf> = project_dir_names(cwd)
+        .into_iter()
+        .map(|name| claude_projects.join(name))
+        .collect();
+    cache
+        .iter()
+        .filter(|(path, _)| {
+            path.parent()
+                .is_some_and(|dir| project_dirs.iter().any(|project| project == dir))
+        })
+        .map(|(path, scan)| (path.clone(), scan.clone()))
+        .collect()
 }
 
-/// Scan a single project directory for .jsonl session files.
-/// Claude stores sessions as {uuid}.jsonl files.
-fn scan_project_dir(project_dir: &Path, cwd: &str) -> Result<Vec<SessionEntry>, String> {
-    let mut sessions = Vec::new();
+/// Project directory names a cwd may have been recorded under.
+fn project_dir_names(cwd: &str) -> Vec<String> {
+    let mut names = vec![cwd_to_project_dir_name(cwd.trim_end_matches('/'))];
+    let canonical = cwd_to_project_dir_name(&canonicalize_path(cwd));
+    if !names.contains(&canonical) {
+        names.push(canonical);
+    }
+    names
+}
 
-    let entries = std::fs::read_dir(project_dir).map_err(|e| e.to_string())?;
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        // Look for .jsonl files (session transcripts)
-        if path.is_file() {
-            if let Some(ext) = path.extension() {
-                if ext == "jsonl" {
-                    // Extract session ID from filename (UUID)
-                    if let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) {
-                        // Parse the JSONL to extract session info
-                        if let Ok(session) =
-                            extract_session_from_jsonl(&path, session_id, cwd, project_dir)
-                        {
-                            sessions.push(session);
-                        }
-                    }
-                }
-            }
-        }
+/// List every project directory under ~/.claude/projects.
+pub(crate) fn list_claude_project_dirs() -> Result<Vec<PathBuf>, String> {
+    let claude_projects = get_claude_projects_dir()?;
+    if !claude_projects.exists() {
+        return Ok(Vec::new());
     }
+    let entries = std::fs::read_dir(&claude_projects).map_err(|e| e.to_string())?;
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
 
-    // Sort by last activity (most recent first)
-    sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+/// List the .jsonl transcripts in a project directory.
+pub(crate) fn list_transcripts(project_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(project_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().map_or(false, |ext| ext == "jsonl"))
+        .collect()
+}
 
-    Ok(sessions)
+/// Read the cwd recorded in a transcript's first entries, if any.
+/// Project directory names are lossy ("-" can't be told apart from "/"),
+/// so this is the reliable way to decode where a session ran.
+pub(crate) fn read_transcript_cwd(jsonl_path: &Path) -> Option<String> {
+    let file = std::fs::File::open(jsonl_path).ok()?;
+    transcript_lines(BufReader::new(file))
+        .take(50)
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+        .find_map(|entry| {
+            entry
+                .get("cwd")
+                .and_then(|c| c.as_str())
+                .map(|c| c.to_string())
+        })
 }
 
 /// Extract session metadata from a JSONL transcript file.
 /// Claude's JSONL format: each line is a JSON object with type, sessionId, cwd, message, etc.
-fn extract_session_from_jsonl(
+pub(crate) fn extract_session_from_jsonl(
     jsonl_path: &Path,
     session_id: &str,
-    expected_cwd: &str,
+    expected_cwd: &PathKey,
     project_dir: &Path,
 ) -> Result<SessionEntry, String> {
     let file = std::fs::File::open(jsonl_path).map_err(|e| e.to_string())?;
@@ -250,13 +563,8 @@ fn extract_session_from_jsonl(
     let mut actual_cwd: Option<String> = None;
 
     // Parse JSONL lines to find session info
-    for line in reader.lines().take(50) {
+    for line in transcript_lines(reader).take(50) {
         // Only scan first 50 lines
-        let line = match line {
-            Ok(l) => l,
-            Err(_) => continue,
-        };
-
         if line.is_empty() {
             continue;
         }
@@ -305,29 +613,141 @@ fn extract_session_from_jsonl(
 
     // Verify the session's cwd matches the expected cwd
     if let Some(ref session_cwd) = actual_cwd {
-        // Normalize paths for comparison (remove trailing slashes)
-        let normalized_expected = expected_cwd.trim_end_matches('/');
-        let normalized_actual = session_cwd.trim_end_matches('/');
-        if normalized_expected != normalized_actual {
+        // Compare through symlinks and platform case rules, not raw strings
+        if !expected_cwd.matches(&PathKey::new(session_cwd)) {
             return Err(format!(
                 "Session cwd mismatch: expected {}, got {}",
-                expected_cwd, session_cwd
+                expected_cwd.path(),
+                session_cwd
             ));
         }
     }
 
     Ok(SessionEntry {
         session_id: session_id.to_string(),
-        cwd: actual_cwd.unwrap_or_else(|| expected_cwd.to_string()),
+        cwd: actual_cwd.unwrap_or_else(|| expected_cwd.path().to_string()),
         preview,
         created_at,
         last_activity,
         transcript_path: Some(jsonl_path.to_string_lossy().to_string()),
         project_path: Some(project_dir.to_string_lossy().to_string()),
         status: SessionStatus::Active,
+        usage: None,
+        summary: None,
+        files: Vec::new(),
+        interrupted_turns: Vec::new(),
+        last_read_message_id: None,
+        last_read_line: None,
+        metadata: BTreeMap::new(),
+        time: BTreeMap::new(),
     })
 }
 
+/// Transcript path for a registered session, derived from its cwd when the
+/// registry entry predates stored paths.
+pub(crate) fn resolve_transcript_path(
+    registry: &ThreadRegistry,
+    session_id: &str,
+) -> Result<PathBuf, String> {
+    let session = registry
+        .sessions
+        .get(session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    let transcript_path = session
+        .transcript_path
+        .clone()
+        .or_else(|| derive_project_paths(&session.cwd, session_id).map(|(_, t)| t))
+        .ok_or_else(|| format!("Session {} has no transcript path", session_id))?;
+    let path = PathBuf::from(&transcript_path);
+    if !path.exists() {
+        return Err(format!("Transcript file not found: {}", transcript_path));
+    }
+    Ok(path)
+}
+
+fn looks_like_stack_trace(text: &str) -> bool {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let trace_lines = lines
+        .iter()
+        .filter(|line| {
+            line.starts_with("at ")
+                || line.starts_with("File \"")
+                || line.starts_with("Traceback")
+                || line.contains("panicked at")
+                || line.starts_with("Caused by:")
+        })
+        .count();
+    trace_lines >= 2 && trace_lines * 3 >= lines.len()
+}
+
+/// Previews that say nothing about the session: slash commands, command
+/// output wrappers, pasted stack traces and near-empty text.
+pub(crate) fn is_generic_preview(text: &str) -> bool {
+    let trimmed = text.trim();
+    if trimmed.chars().count() < 4 {
+        return true;
+    }
+    let first_line = trimmed.lines().next().unwrap_or("").trim();
+    first_line.starts_with('/')
+        || first_line.starts_with("<command-")
+        || first_line.starts_with("<local-command-")
+        || first_line.starts_with("Caveat:")
+        || first_line.starts_with("```")
+        || first_line.starts_with("[Request interrupted")
+        || looks_like_stack_trace(trimmed)
+}
+
+fn format_preview(line: &str) -> String {
+    let collapsed = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    let truncated: String = collapsed.chars().take(100).collect();
+    if collapsed.chars().count() > 100 {
+        format!("{}...", truncated)
+    } else {
+        truncated
+    }
+}
+
+/// First informative line of a user prompt, skipping slash commands, code
+/// blocks and pasted traces.
+fn preview_from_prompt(text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    if trimmed.starts_with("<command-") || trimmed.starts_with('/') {
+        // A slash command's arguments often carry the real request.
+        let args = match trimmed.split_once("<command-args>") {
+            Some((_, rest)) => rest.split("</command-args>").next().unwrap_or(""),
+            None if trimmed.starts_with('/') => trimmed
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, rest)| rest),
+            None => "",
+        };
+        return (!is_generic_preview(args)).then(|| format_preview(args));
+    }
+    if looks_like_stack_trace(trimmed) {
+        return None;
+    }
+    let mut in_fence = false;
+    for line in trimmed.lines().map(str::trim) {
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || is_generic_preview(line) {
+            continue;
+        }
+        return Some(format_preview(line));
+    }
+    None
+}
+
+/// Best preview from the user prompts in a transcript.
+pub(crate) fn best_preview(jsonl_path: &Path) -> Option<String> {
+    let messages = crate::transcripts::transcript_messages(jsonl_path).ok()?;
+    messages
+        .iter()
+        .filter(|(role, _)| role == "user")
+        .find_map(|(_, text)| preview_from_prompt(text))
+}
+
 /// Get current timestamp in milliseconds
 pub(crate) fn now_millis() -> u64 {
     SystemTime::now()
@@ -366,9 +786,171 @@ pub(crate) fn create_session_entry(
         transcript_path,
         project_path,
         status: SessionStatus::Active,
+        usage: None,
+        summary: None,
+        files: Vec::new(),
+        interrupted_turns: Vec::new(),
+        last_read_message_id: None,
+        last_read_line: None,
+        metadata: BTreeMap::new(),
+        time: BTreeMap::new(),
     }
 }
 
+/// Rewrite a session cwd that lived under `old_root` so it lives under
+/// `new_root` instead. Components compare as `path_key` does, so case (where
+/// the filesystem ignores it), separators and trailing slashes don't
+/// matter. Returns `None` for cwds outside the old root.
+pub(crate) fn remap_cwd(cwd: &str, old_root: &str, new_root: &str) -> Option<String> {
+    fn components(path: &str) -> Vec<&str> {
+        path.split(['/', '\\'])
+            .filter(|component| !component.is_empty())
+            .collect()
+    }
+    let is_absolute = |path: &str| path.starts_with(['/', '\\']);
+    let cwd_components = components(cwd);
+    let root_components = components(old_root);
+    let under_root = is_absolute(cwd) == is_absolute(old_root)
+        && cwd_components.len() >= root_components.len()
+        && root_components
+            .iter()
+            .zip(&cwd_components)
+            .all(|(root, cwd)| path_key(root) == path_key(cwd));
+    if !under_root {
+        return None;
+    }
+    // Join with the separator the new root is written with.
+    let separator = if new_root.contains('\\') && !new_root.contains('/') {
+        '\\'
+    } else {
+        '/'
+    };
+    let mut remapped = trim_trailing_separators(new_root).to_string();
+    for component in &cwd_components[root_components.len()..] {
+        if !remapped.ends_with(['/', '\\']) {
+            remapped.push(separator);
+        }
+        remapped.push_str(component);
+    }
+    Some(remapped)
+}
+
+/// Where a session recorded under a moved workspace root goes.
+pub(crate) struct SessionMove {
+    session_id: String,
+    cwd: String,
+    project_path: String,
+    transcript_path: String,
+    old_transcript: Option<String>,
+}
+
+/// The sessions recorded under `old_root`, with their cwd and transcript
+/// under `new_root`. Only reads the registry; see `move_transcripts`.
+pub(crate) fn plan_session_moves(
+    registry: &ThreadRegistry,
+    old_root: &str,
+    new_root: &str,
+) -> Vec<SessionMove> {
+    registry
+        .sessions
+        .values()
+        .filter_map(|session| {
+            let cwd = remap_cwd(&session.cwd, old_root, new_root)?;
+            let (project_path, transcript_path) = derive_project_paths(&cwd, &session.session_id)?;
+            let old_transcript = session
+                .transcript_path
+                .clone()
+                .or_else(|| derive_project_paths(&session.cwd, &session.session_id).map(|p| p.1));
+            Some(SessionMove {
+                session_id: session.session_id.clone(),
+                cwd,
+                project_path,
+                transcript_path,
+                old_transcript,
+            })
+        })
+        .collect()
+}
+
+/// What `move_transcripts` did on disk.
+#[derive(Debug, Default)]
+pub(crate) struct MovedTranscripts {
+    /// Sessions whose transcript is at its new path, at the size it had
+    /// before the move.
+    present: std::collections::HashSet<String>,
+    /// Transcripts that could not be renamed (e.g. across volumes) and were
+    /// copied instead, leaving the original behind.
+    pub(crate) copied: Vec<String>,
+    /// Transcripts that could be neither renamed nor copied.
+    pub(crate) failed: Vec<String>,
+}
+
+/// Move transcripts into the new Claude project directory so resume finds
+/// them, checking each one arrived whole. Blocking; run it without the
+/// registry locked.
+pub(crate) fn move_transcripts(moves: &[SessionMove]) -> MovedTranscripts {
+    let mut moved = MovedTranscripts::default();
+    for session in moves {
+        let new = Path::new(&session.transcript_path);
+        let old = session
+            .old_transcript
+            .as_ref()
+            .filter(|old| Path::new(old) != new);
+        let size = old
+            .and_then(|old| std::fs::metadata(old).ok())
+            .map(|metadata| metadata.len());
+        let intact =
+            |size: u64| std::fs::metadata(new).is_ok_and(|metadata| metadata.len() == size);
+        let present = match (old, size) {
+            (Some(old), Some(size)) if !new.exists() => {
+                let _ = std::fs::create_dir_all(&session.project_path);
+                if std::fs::rename(old, new).is_ok() {
+                    intact(size)
+                } else if std::fs::copy(old, new).is_ok() && intact(size) {
+                    moved.copied.push(old.clone());
+                    true
+                } else {
+                    // Leave no partial copy for resume to pick up; the
+                    // original is still in place.
+                    let _ = std::fs::remove_file(new);
+                    moved.failed.push(old.clone());
+                    false
+                }
+            }
+            _ => new.exists(),
+        };
+        if present {
+            moved.present.insert(session.session_id.clone());
+        }
+    }
+    moved
+}
+
+/// Point the moved sessions at their new paths, marking the ones whose
+/// transcript is not there Missing. Returns the number remapped.
+pub(crate) fn apply_session_moves(
+    registry: &mut ThreadRegistry,
+    moves: Vec<SessionMove>,
+    moved: &MovedTranscripts,
+) -> usize {
+    let mut remapped = 0;
+    for session_move in moves {
+        let Some(session) = registry.sessions.get_mut(&session_move.session_id) else {
+            continue;
+        };
+        session.status = if moved.present.contains(&session_move.session_id) {
+            SessionStatus::Active
+        } else {
+            SessionStatus::Missing
+        };
+        session.cwd = session_move.cwd;
+        session.project_path = Some(session_move.project_path);
+        session.transcript_path = Some(session_move.transcript_path);
+        remapped += 1;
+    }
+    remapped
+}
+
 /// Register a session directly (for internal use by bridge).
 /// This bypasses the Tauri command interface for efficiency.
 pub(crate) async fn register_session_internal(
@@ -414,6 +996,21 @@ pub(crate) async fn update_session_activity_internal(
     write_registry(registry_path, registry)
 }
 
+/// Longest accepted metadata key; values are unbounded.
+const MAX_METADATA_KEY_LEN: usize = 64;
+
+/// Whether the session carries every key/value pair in `filter`.
+pub(crate) fn matches_metadata(
+    session: &SessionEntry,
+    filter: Option<&BTreeMap<String, String>>,
+) -> bool {
+    filter.is_none_or(|filter| {
+        filter
+            .iter()
+            .all(|(key, value)| session.metadata.get(key) == Some(value))
+    })
+}
+
 /// Mark a session as missing (transcript not found).
 pub(crate) async fn mark_session_missing(
     registry: &mut ThreadRegistry,
@@ -431,12 +1028,24 @@ pub(crate) async fn mark_session_missing(
 // Tauri Commands
 // ============================================================================
 
+/// A visible session with its unread reply count.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+pub(crate) struct VisibleSession {
+    #[serde(flatten)]
+    session: SessionEntry,
+    #[serde(rename = "unreadCount")]
+    unread_count: usize,
+}
+
 /// Get visible sessions for a workspace
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_visible_sessions(
     workspace_id: String,
+    metadata: Option<BTreeMap<String, String>>,
     state: State<'_, AppState>,
-) -> Result<Vec<SessionEntry>, String> {
+    _timer: CommandTimer,
+) -> Result<Vec<VisibleSession>, String> {
     let mut registry = state.registry.lock().await;
     let workspaces = state.workspaces.lock().await;
 
@@ -444,6 +1053,7 @@ pub(crate) async fn get_visible_sessions(
     let _workspace = workspaces
         .get(&workspace_id)
         .ok_or_else(|| format!("Workspace {} not found", workspace_id))?;
+    drop(workspaces);
 
     let workspace_registry = registry.workspaces.get(&workspace_id);
     let visible_ids: Vec<String> = workspace_registry
@@ -467,40 +1077,106 @@ pub(crate) async fn get_visible_sessions(
 
     // Persist if any sessions were marked missing
     if needs_persist {
-        drop(workspaces); // Release lock before writing
         write_registry(&state.registry_path, &registry)?;
     }
 
-    // Collect and return sessions
-    let sessions: Vec<SessionEntry> = visible_ids
+    // Collect sessions, with the transcripts to count unread replies in
+    let sessions: Vec<(SessionEntry, Option<(PathBuf, PathBuf)>)> = visible_ids
         .iter()
         .filter_map(|id| registry.sessions.get(id).cloned())
+        .filter(|session| matches_metadata(session, metadata.as_ref()))
+        .map(|session| {
+            let paths = session
+                .last_read_line
+                .and_then(|_| resolve_transcript_path(&registry, &session.session_id).ok())
+                .map(|transcript| (index_path(&state, &session.session_id), transcript));
+            (session, paths)
+        })
         .collect();
-
-    Ok(sessions)
+    drop(registry);
+
+    tokio::task::spawn_blocking(move || {
+        sessions
+            .into_iter()
+            .map(|(session, paths)| {
+                let unread = paths
+                    .map(|(index_file, transcript)| {
+                        unread_count(&session, &index_file, &transcript)
+                    })
+                    .unwrap_or(0);
+                VisibleSession {
+                    session,
+                    unread_count: unread,
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|_| "unread count failed".to_string())
 }
 
-/// Scan for importable sessions from Claude projects
+/// Scan for importable sessions from Claude projects.
+/// Emits `scan/progress` events while transcripts are parsed; unchanged
+/// files come from the scan cache.
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn scan_available_sessions(
     workspace_id: String,
+    app_handle: AppHandle,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Vec<SessionEntry>, String> {
-    let workspaces = state.workspaces.lock().await;
-    let workspace = workspaces
-        .get(&workspace_id)
-        .ok_or_else(|| format!("Workspace {} not found", workspace_id))?;
-
-    scan_project_sessions(&workspace.path)
+    let workspace_path = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| format!("Workspace {} not found", workspace_id))?
+            .path
+            .clone()
+    };
+
+    let mut cache = match get_claude_projects_dir() {
+        Ok(projects) => cache_for_cwd(&state.scan_cache.lock().await, &projects, &workspace_path),
+        Err(_) => ScanCache::new(),
+    };
+    let (result, cache) = tokio::task::spawn_blocking(move || {
+        let emit_progress = |done: usize, total: usize, cached: usize| {
+            // Every file on small scans, then in steps so large scans don't flood the UI.
+            if total <= 50 || done.is_multiple_of(25) || done == total {
+                let _ = app_handle.emit(
+                    "claude-event",
+                    ClaudeEvent {
+                        event_type: "scan/progress".to_string(),
+                        session_id: String::new(),
+                        workspace_id: workspace_id.clone(),
+                        timestamp: chrono::Utc::now().timestamp_millis(),
+                        payload: ClaudeEventPayload::ScanProgress(ScanProgressPayload {
+                            done,
+                            total,
+                            cached,
+                        }),
+                    },
+                );
+            }
+        };
+        let result = scan_project_sessions_cached(&workspace_path, &mut cache, &emit_progress);
+        (result, cache)
+    })
+    .await
+    .map_err(|_| "session scan failed".to_string())?;
+    state.scan_cache.lock().await.extend(cache);
+    result
 }
 
 /// Import sessions into visibility list
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn import_sessions(
     workspace_id: String,
     session_ids: Vec<String>,
     sessions_data: Vec<SessionEntry>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
     let mut registry = state.registry.lock().await;
 
@@ -523,16 +1199,20 @@ pub(crate) async fn import_sessions(
 
     // Persist
     write_registry(&state.registry_path, &registry)?;
+    state.indexer_wake.notify_one();
 
     Ok(())
 }
 
 /// Archive a session (remove from visibility, keep in sessions)
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn registry_archive_session(
     workspace_id: String,
     session_id: String,
+    app_handle: AppHandle,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
     let mut registry = state.registry.lock().await;
 
@@ -544,16 +1224,20 @@ pub(crate) async fn registry_archive_session(
 
     // Persist
     write_registry(&state.registry_path, &registry)?;
+    drop(registry);
 
+    session_completed(&app_handle, &workspace_id, &session_id);
     Ok(())
 }
 
 /// Register a new session (called when Claude bridge creates one)
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn register_session(
     workspace_id: String,
     session: SessionEntry,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
     let mut registry = state.registry.lock().await;
 
@@ -580,10 +1264,12 @@ pub(crate) async fn register_session(
 
 /// Update session activity timestamp and preview
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn update_session_activity(
     session_id: String,
     preview: Option<String>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
     let mut registry = state.registry.lock().await;
 
@@ -600,11 +1286,52 @@ pub(crate) async fn update_session_activity(
     Ok(())
 }
 
+/// Set a free-form metadata field on a session, e.g. a ticket id or
+/// reviewer. An empty or missing value removes the key. Returns the
+/// session's metadata after the change.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_session_metadata(
+    session_id: String,
+    key: String,
+    value: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<BTreeMap<String, String>, String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Metadata key is empty".to_string());
+    }
+    if key.len() > MAX_METADATA_KEY_LEN {
+        return Err(format!(
+            "Metadata key is longer than {MAX_METADATA_KEY_LEN} bytes"
+        ));
+    }
+    let mut registry = state.registry.lock().await;
+    let session = registry
+        .sessions
+        .get_mut(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    match value.filter(|value| !value.is_empty()) {
+        Some(value) => {
+            session.metadata.insert(key.to_string(), value);
+        }
+        None => {
+            session.metadata.remove(key);
+        }
+    }
+    let metadata = session.metadata.clone();
+    write_registry(&state.registry_path, &registry)?;
+    Ok(metadata)
+}
+
 /// Load session history from Claude transcript JSONL.
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_session_history(
     session_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<SessionHistory, String> {
     let mut registry = state.registry.lock().await;
     let session = registry
@@ -612,9 +1339,10 @@ pub(crate) async fn get_session_history(
         .get(&session_id)
         .ok_or_else(|| format!("Session {} not found", session_id))?;
     let mut transcript_path = session.transcript_path.clone();
+    let cwd = session.cwd.clone();
     if transcript_path.is_none() {
         if let Some((derived_project, derived_transcript)) =
-            derive_project_paths(&session.cwd, &session_id)
+            derive_project_paths(&cwd, &session_id)
         {
             transcript_path = Some(derived_transcript);
             if let Some(session) = registry.sessions.get_mut(&session_id) {
@@ -639,16 +1367,43 @@ pub(crate) async fn get_session_history(
         }
         return Err(format!("Transcript file not found: {}", transcript_path));
     }
-
-    parse_session_history(&session_id, path)
+    let interrupted = registry
+        .sessions
+        .get(&session_id)
+        .map(|session| session.interrupted_turns.clone())
+        .unwrap_or_default();
+    let workspace_id = registry
+        .workspaces
+        .iter()
+        .find(|(_, workspace)| workspace.visible_session_ids.contains(&session_id))
+        .map(|(id, _)| id.clone());
+    drop(registry);
+
+    let mut history = parse_session_history(&session_id, path, Path::new(&cwd), &interrupted)?;
+    let thinking_blocks = match workspace_id {
+        Some(id) => state
+            .workspaces
+            .lock()
+            .await
+            .get(&id)
+            .and_then(|entry| entry.settings.thinking_blocks)
+            .unwrap_or_default(),
+        None => ThinkingBlocks::default(),
+    };
+    if thinking_blocks == ThinkingBlocks::Discard {
+        history.items.retain(|item| item["kind"] != "reasoning");
+    }
+    Ok(history)
 }
 
 /// Get archived (hidden) sessions for a workspace.
 /// These are sessions in the sessions map but NOT in visible_session_ids.
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn get_archived_sessions(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Vec<SessionEntry>, String> {
     let registry = state.registry.lock().await;
 
@@ -661,15 +1416,21 @@ pub(crate) async fn get_archived_sessions(
     // We need to match by cwd since sessions store their workspace cwd
     let workspaces = state.workspaces.lock().await;
     let workspace = workspaces.get(&workspace_id);
-    let workspace_cwd = workspace.map(|w| w.path.as_str());
+    let Some(workspace_key) = workspace.map(|w| PathKey::new(&w.path)) else {
+        return Ok(Vec::new());
+    };
 
+    // Sessions share a handful of cwds, so resolve each one only once
+    let mut cwd_matches: HashMap<&str, bool> = HashMap::new();
     let archived: Vec<SessionEntry> = registry
         .sessions
         .values()
         .filter(|session| {
             // Session belongs to this workspace (by cwd) and is not visible
-            workspace_cwd.map_or(false, |cwd| session.cwd == cwd)
-                && !visible_ids.contains(&session.session_id)
+            !visible_ids.contains(&session.session_id)
+                && *cwd_matches
+                    .entry(session.cwd.as_str())
+                    .or_insert_with(|| workspace_key.matches(&PathKey::new(&session.cwd)))
         })
         .cloned()
         .collect();
@@ -679,10 +1440,12 @@ pub(crate) async fn get_archived_sessions(
 
 /// Unarchive a session (add back to visibility list).
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn registry_unarchive_session(
     workspace_id: String,
     session_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
     let mut registry = state.registry.lock().await;
 
@@ -711,9 +1474,74 @@ pub(crate) async fn registry_unarchive_session(
 // Tests
 // ============================================================================
 
+#[derive(Debug, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PreviewBackfill {
+    scanned: usize,
+    updated: Vec<SessionEntry>,
+}
+
+/// Re-derive previews for sessions whose preview is missing or generic.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn backfill_previews(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<PreviewBackfill, String> {
+    let candidates: Vec<(String, PathBuf)> = {
+        let registry = state.registry.lock().await;
+        let workspace_reg = registry
+            .workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        workspace_reg
+            .visible_session_ids
+            .iter()
+            .filter(|id| {
+                registry
+                    .sessions
+                    .get(*id)
+                    .map(|session| session.preview.as_deref().is_none_or(is_generic_preview))
+                    .unwrap_or(false)
+            })
+            .filter_map(|id| {
+                resolve_transcript_path(&registry, id)
+                    .ok()
+                    .map(|path| (id.clone(), path))
+            })
+            .collect()
+    };
+    let scanned = candidates.len();
+    let previews = tokio::task::spawn_blocking(move || {
+        candidates
+            .into_iter()
+            .filter_map(|(id, path)| best_preview(&path).map(|preview| (id, preview)))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|_| "preview backfill failed".to_string())?;
+
+    let mut registry = state.registry.lock().await;
+    let mut updated = Vec::new();
+    for (id, preview) in previews {
+        if let Some(session) = registry.sessions.get_mut(&id) {
+            if session.preview.as_deref() != Some(preview.as_str()) {
+                session.preview = Some(preview);
+                updated.push(session.clone());
+            }
+        }
+    }
+    if !updated.is_empty() {
+        write_registry(&state.registry_path, &registry)?;
+    }
+    Ok(PreviewBackfill { scanned, updated })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::TempDir;
 
     #[test]
     fn test_cwd_to_project_dir_name() {
@@ -743,6 +1571,164 @@ mod tests {
         assert_eq!(cwd_to_project_dir_name("/project"), "-project");
     }
 
+    #[test]
+    fn test_remap_cwd() {
+        assert_eq!(
+            remap_cwd("/old/repo", "/old/repo/", "/new/repo"),
+            Some("/new/repo".to_string())
+        );
+        assert_eq!(
+            remap_cwd("/old/repo/packages/web", "/old/repo", "/new/repo"),
+            Some("/new/repo/packages/web".to_string())
+        );
+        // Sibling directories sharing a prefix are not remapped.
+        assert_eq!(remap_cwd("/old/repo-2", "/old/repo", "/new/repo"), None);
+        assert_eq!(
+            remap_cwd("/old//repo/src/", "\\old\\repo\\", "/new/repo/"),
+            Some("/new/repo/src".to_string())
+        );
+        let differently_cased = remap_cwd("/Old/Repo/src", "/old/repo", "/new/repo");
+        if cfg!(any(target_os = "macos", target_os = "windows")) {
+            assert_eq!(differently_cased, Some("/new/repo/src".to_string()));
+        } else {
+            assert_eq!(differently_cased, None);
+        }
+        assert_eq!(remap_cwd("old/repo", "/old/repo", "/new/repo"), None);
+    }
+
+    #[test]
+    fn moves_transcripts_and_checks_them() {
+        let root = TempDir::new("relocate");
+        let old_project = root.join("old-project");
+        std::fs::create_dir_all(&old_project).unwrap();
+        std::fs::write(old_project.join("s1.jsonl"), "{}\n{}\n").unwrap();
+        let new_project = root.join("new-project");
+        let path = |dir: &Path, name: &str| dir.join(name).to_string_lossy().to_string();
+        let moves = vec![
+            SessionMove {
+                session_id: "s1".to_string(),
+                cwd: "/new/repo".to_string(),
+                project_path: new_project.to_string_lossy().to_string(),
+                transcript_path: path(&new_project, "s1.jsonl"),
+                old_transcript: Some(path(&old_project, "s1.jsonl")),
+            },
+            SessionMove {
+                session_id: "gone".to_string(),
+                cwd: "/new/repo".to_string(),
+                project_path: new_project.to_string_lossy().to_string(),
+                transcript_path: path(&new_project, "gone.jsonl"),
+                old_transcript: Some(path(&old_project, "gone.jsonl")),
+            },
+        ];
+        let moved = move_transcripts(&moves);
+        assert!(moved.present.contains("s1"));
+        assert!(!moved.present.contains("gone"));
+        assert!(moved.copied.is_empty() && moved.failed.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(new_project.join("s1.jsonl")).unwrap(),
+            "{}\n{}\n"
+        );
+        assert!(!old_project.join("s1.jsonl").exists());
+    }
+
+    #[test]
+    fn test_preview_selection() {
+        assert!(is_generic_preview("/clear"));
+        assert!(is_generic_preview(
+            "TypeError: x is undefined\n    at foo (a.js:1:2)\n    at bar (b.js:3:4)"
+        ));
+        assert!(!is_generic_preview("Add retry logic to the uploader"));
+        assert_eq!(
+            preview_from_prompt("/fix flaky upload test"),
+            Some("flaky upload test".to_string())
+        );
+        assert_eq!(
+            preview_from_prompt(
+                "<command-name>/review</command-name>\n<command-args>the auth module</command-args>"
+            ),
+            Some("the auth module".to_string())
+        );
+        assert_eq!(
+            preview_from_prompt("```\nlog output\n```\nWhy does   this fail?"),
+            Some("Why does this fail?".to_string())
+        );
+    }
+
+    #[test]
+    fn test_history_tool_and_reasoning_items() {
+        let dir = TempDir::new("history");
+        let path = dir.join("s1.jsonl");
+        let lines = [
+            serde_json::json!({ "type": "user", "uuid": "u1", "message": { "content": [{ "type": "text", "text": "Run the tests" }] } }),
+            serde_json::json!({ "type": "assistant", "uuid": "a1", "message": { "content": [
+                { "type": "thinking", "thinking": "The user wants tests.\nUse cargo." },
+                { "type": "text", "text": "Running them." },
+                { "type": "tool_use", "id": "t1", "name": "Bash", "input": { "command": "cargo test" } },
+                { "type": "tool_use", "id": "t2", "name": "Read", "input": { "file_path": "x" } },
+            ]}}),
+            serde_json::json!({ "type": "user", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "t1", "content": [{ "type": "text", "text": "ok" }] },
+                { "type": "tool_result", "tool_use_id": "t2", "content": "missing", "is_error": true },
+            ]}}),
+        ];
+        let data: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        std::fs::write(&path, data.join("\n")).unwrap();
+
+        let history = parse_session_history("s1", &path, &dir, &[]).unwrap();
+        let kinds: Vec<&str> = history
+            .items
+            .iter()
+            .map(|item| item["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, ["message", "reasoning", "message", "tool", "tool"]);
+        assert_eq!(
+            history.items[1]["content"],
+            "The user wants tests.\nUse cargo."
+        );
+        let bash = &history.items[3];
+        assert_eq!(bash["id"], "tool-t1");
+        assert_eq!(bash["title"], "Bash");
+        assert_eq!(bash["status"], "completed");
+        assert_eq!(bash["output"], "ok");
+        assert_eq!(history.items[4]["status"], "failed");
+
+        // Interrupted turns go where the transcript stood when they were saved.
+        let turn = InterruptedTurn {
+            id: "interrupted-1".to_string(),
+            interrupted_at: 1,
+            transcript_line: 1,
+            text: "Partial".to_string(),
+            tools: Vec::new(),
+            input_tokens: 5,
+            output_tokens: 2,
+        };
+        let history = parse_session_history("s1", &path, &dir, &[turn]).unwrap();
+        assert_eq!(history.items[1]["kind"], "interrupted");
+        assert_eq!(history.items[1]["outputTokens"], 2);
+        assert_eq!(history.items[2]["kind"], "reasoning");
+    }
+
+    #[test]
+    fn test_matches_metadata() {
+        let mut session = create_session_entry("s1".to_string(), "/tmp".to_string(), None);
+        session
+            .metadata
+            .insert("ticket".to_string(), "ENG-12".to_string());
+        session
+            .metadata
+            .insert("reviewer".to_string(), "sam".to_string());
+
+        assert!(matches_metadata(&session, None));
+        let mut filter = BTreeMap::new();
+        filter.insert("ticket".to_string(), "ENG-12".to_string());
+        assert!(matches_metadata(&session, Some(&filter)));
+        filter.insert("reviewer".to_string(), "alex".to_string());
+        assert!(!matches_metadata(&session, Some(&filter)));
+        filter.clear();
+        filter.insert("experiment".to_string(), "a".to_string());
+        assert!(!matches_metadata(&session, Some(&filter)));
+    }
+
     #[test]
     fn test_create_session_entry() {
         let session = create_session_entry(
@@ -772,6 +1758,14 @@ mod tests {
             transcript_path: Some("/path/to/transcript.jsonl".to_string()),
             project_path: Some("/path/to/project".to_string()),
             status: SessionStatus::Active,
+            usage: None,
+            summary: None,
+            files: Vec::new(),
+            interrupted_turns: Vec::new(),
+            last_read_message_id: None,
+            last_read_line: None,
+            metadata: BTreeMap::new(),
+            time: BTreeMap::new(),
         };
 
         registry.sessions.insert("session-1".to_string(), session);
@@ -791,4 +1785,26 @@ mod tests {
         let session = parsed.sessions.get("session-1").unwrap();
         assert_eq!(session.preview, Some("Test preview".to_string()));
     }
+
+    #[test]
+    fn scan_cache_copy_covers_only_the_workspace() {
+        let projects = Path::new("/home/me/.claude/projects");
+        let scan = CachedSessionScan {
+            modified: SystemTime::UNIX_EPOCH,
+            len: 1,
+            session: None,
+        };
+        let mut cache = ScanCache::new();
+        for path in [
+            "/home/me/.claude/projects/-work-app/a.jsonl",
+            "/home/me/.claude/projects/-work-app-two/b.jsonl",
+            "/home/me/.claude/projects/-work-other/c.jsonl",
+        ] {
+            cache.insert(PathBuf::from(path), scan.clone());
+        }
+        let copy = cache_for_cwd(&cache, projects, "/work/app");
+        assert_eq!(copy.len(), 1);
+        assert!(copy.contains_key(Path::new("/home/me/.claude/projects/-work-app/a.jsonl")));
+        assert_eq!(cache.len(), 3);
+    }
 }
@@ -0,0 +1,456 @@
+use serde::Serialize;
+use tauri::State;
+use tokio::process::Command;
+
+use crate::changelog::workspace_changelog;
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::git::{github_remote, open_workspace_repo};
+use crate::proxy::apply_network_env;
+use crate::settings::ensure_not_observer;
+use crate::state::AppState;
+use crate::utils::TempFile;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ReleaseStepStatus {
+    Done,
+    /// Would run; the release is a dry run.
+    Planned,
+    Failed,
+    /// Not attempted because an earlier step failed.
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReleaseStep {
+    /// `tag`, `push`, `release`, or `untag` when a failed push is undone.
+    pub(crate) name: String,
+    pub(crate) status: ReleaseStepStatus,
+    /// The command that ran or would run, or why it failed.
+    pub(crate) detail: String,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReleaseResult {
+    pub(crate) tag: String,
+    pub(crate) previous_tag: Option<String>,
+    pub(crate) notes: String,
+    pub(crate) dry_run: bool,
+    pub(crate) steps: Vec<ReleaseStep>,
+    /// The GitHub release page, once created.
+    pub(crate) url: Option<String>,
+}
+
+/// The tag for `version`: `1.2.0` and `v1.2.0` both become `v1.2.0`.
+pub(crate) fn release_tag(version: &str) -> Result<String, String> {
+    let version = version.trim();
+    let bare = version.strip_prefix('v').unwrap_or(version);
+    let valid = bare.starts_with(|c: char| c.is_ascii_digit())
+        && !bare.ends_with('.')
+        && !bare.contains("..")
+        && bare
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+' | '_'));
+    if !valid {
+        return Err(format!(
+            "Invalid release version: {version:?}. Use a version like 1.2.0 or v1.2.0-beta.1."
+        ));
+    }
+    Ok(format!("v{bare}"))
+}
+
+/// The confirmation target for releasing `version` from `workspace_id`.
+pub(crate) fn release_target(workspace_id: &str, version: &str) -> String {
+    format!("{workspace_id}:{version}")
+}
+
+/// The workspace id and version in a `release_target`.
+pub(crate) fn parse_release_target(target: &str) -> Option<(&str, &str)> {
+    target.split_once(':')
+}
+
+/// Notes for the release: the caller's, else the changelog since the
+/// previous tag.
+fn release_notes(notes: Option<String>, changelog: Option<String>) -> String {
+    notes
+        .map(|notes| notes.trim().to_string())
+        .filter(|notes| !notes.is_empty())
+        .or(changelog.filter(|markdown| !markdown.trim().is_empty()))
+        .unwrap_or_else(|| "Initial release.".to_string())
+}
+
+/// What `preflight` found.
+struct Preflight {
+    previous_tag: Option<String>,
+    remote: String,
+    /// `owner/name` on GitHub.
+    repo_name: String,
+    /// The tag already points at HEAD, left by an earlier attempt whose
+    /// `gh release create` failed.
+    tagged: bool,
+}
+
+/// Checks that must pass before anything is written: the tag is new, or
+/// already on HEAD from an earlier attempt, and the workspace has a GitHub
+/// remote.
+fn preflight(path: &str, tag: &str) -> Result<Preflight, String> {
+    let (repo, _) = open_workspace_repo(path)?;
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|_| "The repository has no commits to release.".to_string())?;
+    let tagged = match repo.revparse_single(&format!("refs/tags/{tag}")) {
+        Ok(existing) => {
+            let target = existing
+                .peel_to_commit()
+                .map_err(|e| e.message().to_string())?;
+            if target.id() != head.id() {
+                return Err(format!("Tag {tag} already exists on another commit"));
+            }
+            true
+        }
+        Err(_) => false,
+    };
+    let mut options = git2::DescribeOptions::new();
+    options.describe_tags();
+    let describe = |commit: &git2::Commit| {
+        commit
+            .as_object()
+            .describe(&options)
+            .and_then(|describe| {
+                describe.format(Some(git2::DescribeFormatOptions::new().abbreviated_size(0)))
+            })
+            .ok()
+    };
+    // On a retry HEAD carries the new tag itself; look past it.
+    let previous_tag = match describe(&head) {
+        Some(found) if found == tag => head.parent(0).ok().and_then(|parent| describe(&parent)),
+        found => found,
+    };
+    let (remote, repo_name) = github_remote(path)?;
+    Ok(Preflight {
+        previous_tag,
+        remote,
+        repo_name,
+        tagged,
+    })
+}
+
+/// Run `program` in `path` and return its trimmed stdout, or the error it
+/// printed.
+async fn run(program: &str, args: &[&str], path: &str) -> Result<String, String> {
+    let mut command = Command::new(program);
+    apply_network_env(&mut command);
+    let output = command
+        .args(args)
+        .current_dir(path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        Err(format!("{program} {} failed.", args.first().unwrap_or(&"")))
+    } else {
+        Err(detail.to_string())
+    }
+}
+
+/// How a step's command is shown.
+fn command_line(program: &str, args: &[&str]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run each `(name, program, args)` step in order. After a failure the
+/// remaining steps are skipped; in a dry run nothing runs. Returns the
+/// steps and the stdout of the last one.
+async fn run_steps(
+    path: &str,
+    commands: &[(&str, &str, Vec<&str>)],
+    dry_run: bool,
+) -> (Vec<ReleaseStep>, Option<String>) {
+    let mut steps = Vec::new();
+    let mut last_output = None;
+    let mut failed = false;
+    for (name, program, args) in commands {
+        let command_line = command_line(program, args);
+        let (status, detail) = if failed {
+            (ReleaseStepStatus::Skipped, command_line)
+        } else if dry_run {
+            (ReleaseStepStatus::Planned, command_line)
+        } else {
+            match run(program, args, path).await {
+                Ok(output) => {
+                    last_output = Some(output);
+                    (ReleaseStepStatus::Done, command_line)
+                }
+                Err(err) => {
+                    failed = true;
+                    (ReleaseStepStatus::Failed, err)
+                }
+            }
+        };
+        steps.push(ReleaseStep {
+            name: name.to_string(),
+            status,
+            detail,
+        });
+    }
+    (steps, last_output)
+}
+
+/// Tag HEAD as `version`, push the tag and create a GitHub release for it
+/// with `gh`. Without `notes`, the release gets the changelog since the
+/// previous tag. A dry run only reports the tag, notes and commands.
+/// Failures after the checks are reported per step rather than as an
+/// error, since the earlier steps have already happened. A tag this call
+/// created is deleted again if its push fails; once pushed it is kept, and
+/// calling this again resumes from it. Anything but a dry run needs a
+/// `release_target` confirmation.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn create_release(
+    workspace_id: String,
+    version: String,
+    notes: Option<String>,
+    dry_run: Option<bool>,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ReleaseResult, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    if !dry_run {
+        ensure_not_observer(&state, "Creating a release").await?;
+        consume_confirmation(
+            &state,
+            confirmation.as_deref(),
+            "create_release",
+            &release_target(&workspace_id, &version),
+        )
+        .await?;
+    }
+    let tag = release_tag(&version)?;
+    let path = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces.get(&workspace_id).ok_or("workspace not found")?;
+        if entry.worktree.is_some() {
+            return Err("Create releases from the project, not a worktree.".to_string());
+        }
+        entry.path.clone()
+    };
+    let _git = state.git_locks.write(&workspace_id).await;
+    let Preflight {
+        previous_tag,
+        remote,
+        repo_name,
+        tagged,
+    } = {
+        let path = path.clone();
+        let tag = tag.clone();
+        tokio::task::spawn_blocking(move || preflight(&path, &tag))
+            .await
+            .map_err(|_| "release checks failed".to_string())??
+    };
+    let changelog = match (&notes, &previous_tag) {
+        (None, Some(previous)) => Some(
+            workspace_changelog(&state, &workspace_id, previous)
+                .await?
+                .markdown,
+        ),
+        _ => None,
+    };
+    let notes = release_notes(notes, changelog);
+    // Passed as a file: notes can be long, and an argument starting with
+    // `-` would be read as a flag.
+    let notes_file = TempFile::with_contents("release-notes", notes.as_bytes())?;
+    let notes_path = notes_file.path().to_string_lossy().to_string();
+    let message = format!("Release {tag}");
+    let mut commands = vec![
+        ("tag", "git", vec!["tag", "-a", &tag, "-m", &message]),
+        ("push", "git", vec!["push", &remote, &tag]),
+        (
+            "release",
+            "gh",
+            vec![
+                "release",
+                "create",
+                &tag,
+                "--repo",
+                &repo_name,
+                "--title",
+                &tag,
+                "--notes-file",
+                &notes_path,
+            ],
+        ),
+    ];
+    let mut steps = Vec::new();
+    if tagged {
+        commands.remove(0);
+        steps.push(ReleaseStep {
+            name: "tag".to_string(),
+            status: ReleaseStepStatus::Done,
+            detail: format!("{tag} is already on HEAD"),
+        });
+    }
+    let (ran, output) = run_steps(&path, &commands, dry_run).await;
+    steps.extend(ran);
+    let push_failed = steps
+        .iter()
+        .any(|step| step.name == "push" && step.status == ReleaseStepStatus::Failed);
+    if push_failed && !tagged {
+        // Nothing was published, so drop the tag rather than leave one that
+        // blocks the next attempt. A tag from an earlier attempt is left
+        // alone: it may be the one already on the remote.
+        let args = ["tag", "-d", tag.as_str()];
+        let (status, detail) = match run("git", &args, &path).await {
+            Ok(_) => (ReleaseStepStatus::Done, command_line("git", &args)),
+            Err(err) => (ReleaseStepStatus::Failed, err),
+        };
+        steps.push(ReleaseStep {
+            name: "untag".to_string(),
+            status,
+            detail,
+        });
+    }
+    // `gh release create` prints the release's URL.
+    let url = steps
+        .iter()
+        .find(|step| step.name == "release")
+        .filter(|step| step.status == ReleaseStepStatus::Done)
+        .and(output)
+        .filter(|output| output.starts_with("https://"));
+    Ok(ReleaseResult {
+        tag,
+        previous_tag,
+        notes,
+        dry_run,
+        steps,
+        url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        command_line, parse_release_target, preflight, release_notes, release_tag, release_target,
+        run_steps, ReleaseStepStatus,
+    };
+    use crate::utils::TempDir;
+
+    #[test]
+    fn normalizes_release_tags() {
+        assert_eq!(release_tag("1.2.0").unwrap(), "v1.2.0");
+        assert_eq!(release_tag(" v1.2.0-beta.1 ").unwrap(), "v1.2.0-beta.1");
+        assert!(release_tag("").is_err());
+        assert!(release_tag("next").is_err());
+        assert!(release_tag("1.2 final").is_err());
+        assert!(release_tag("1..2").is_err());
+        assert_eq!(
+            release_notes(Some("  ".to_string()), Some("## Features".to_string())),
+            "## Features"
+        );
+        assert_eq!(release_notes(None, None), "Initial release.");
+        assert_eq!(
+            parse_release_target(&release_target("ws-1", "v1.2.0")),
+            Some(("ws-1", "v1.2.0"))
+        );
+    }
+
+    #[test]
+    fn resumes_from_a_tag_already_on_head() {
+        let root = TempDir::new("release");
+        let repo = git2::Repository::init(&root).unwrap();
+        repo.remote("origin", "https://github.com/octo/app.git")
+            .unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let first = repo
+            .commit(Some("HEAD"), &signature, &signature, "first", &tree, &[])
+            .unwrap();
+        let first = repo.find_commit(first).unwrap();
+        repo.tag_lightweight("v1.0.0", first.as_object(), false)
+            .unwrap();
+        let second = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "second",
+                &tree,
+                &[&first],
+            )
+            .unwrap();
+        let path = root.to_str().unwrap();
+
+        let fresh = preflight(path, "v1.1.0").unwrap();
+        assert!(!fresh.tagged);
+        assert_eq!(fresh.previous_tag.as_deref(), Some("v1.0.0"));
+        assert_eq!(fresh.repo_name, "octo/app");
+
+        let second = repo.find_commit(second).unwrap();
+        repo.tag_lightweight("v1.1.0", second.as_object(), false)
+            .unwrap();
+        let retry = preflight(path, "v1.1.0").unwrap();
+        assert!(retry.tagged);
+        assert_eq!(retry.previous_tag.as_deref(), Some("v1.0.0"));
+        assert!(preflight(path, "v1.0.0")
+            .err()
+            .is_some_and(|err| err.contains("another commit")));
+    }
+
+    #[test]
+    fn skips_steps_after_a_failure() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let dir = std::env::temp_dir();
+        let path = dir.to_str().unwrap();
+        let commands = [
+            ("first", "git", vec!["--version"]),
+            ("second", "git", vec!["not-a-git-command"]),
+            ("third", "git", vec!["--version"]),
+        ];
+        let (steps, _) = runtime.block_on(run_steps(path, &commands, false));
+        let statuses: Vec<_> = steps.iter().map(|step| step.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ReleaseStepStatus::Done,
+                ReleaseStepStatus::Failed,
+                ReleaseStepStatus::Skipped
+            ]
+        );
+
+        let (steps, output) = runtime.block_on(run_steps(path, &commands, true));
+        assert!(output.is_none());
+        assert!(steps
+            .iter()
+            .all(|step| step.status == ReleaseStepStatus::Planned));
+        assert_eq!(steps[1].detail, "git not-a-git-command");
+        assert_eq!(
+            command_line(
+                "gh",
+                &["release", "create", "v1", "--notes-file", "notes.md"]
+            ),
+            "gh release create v1 --notes-file notes.md"
+        );
+    }
+}
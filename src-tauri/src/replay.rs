@@ -0,0 +1,401 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::claude::{emit_scoped, ClaudeEvent};
+use crate::command_metrics::CommandTimer;
+use crate::registry::now_millis;
+use crate::state::AppState;
+
+const REPLAY_FORMAT: &str = "claude-monitor-replay";
+const REPLAY_VERSION: u32 = 1;
+/// Events kept per session; the oldest are dropped past this.
+const MAX_RECORDED_EVENTS: usize = 20_000;
+/// Sessions kept; the one with the oldest last event is dropped past this.
+const MAX_RECORDED_SESSIONS: usize = 20;
+/// Longest pause between two replayed events, after scaling, so idle
+/// stretches in a session don't stall a demo.
+const MAX_REPLAY_GAP: Duration = Duration::from_secs(5);
+const MAX_REPLAY_SPEED: f64 = 100.0;
+
+/// One event as the frontend received it, with its offset from the first
+/// recorded event.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct RecordedEvent {
+    offset_ms: u64,
+    event: Value,
+}
+
+#[derive(Debug, Default)]
+struct SessionRecording {
+    workspace_id: String,
+    started_at: i64,
+    last_at: i64,
+    events: VecDeque<RecordedEvent>,
+    /// Events dropped from the front to stay under the cap.
+    dropped: usize,
+}
+
+/// Event streams of this run's sessions, and the replay in progress.
+/// Nothing is recorded unless `recordReplays` is on.
+#[derive(Default)]
+pub(crate) struct Recordings {
+    enabled: bool,
+    sessions: HashMap<String, SessionRecording>,
+    replay: Option<(JoinHandle<()>, ReplayStatus)>,
+}
+
+impl Recordings {
+    /// Follow the setting; turning it off drops what was recorded.
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.sessions.clear();
+        }
+    }
+
+    fn record(&mut self, session_id: &str, workspace_id: &str, timestamp: i64, event: Value) {
+        if !self.sessions.contains_key(session_id) && self.sessions.len() >= MAX_RECORDED_SESSIONS {
+            let stalest = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, recording)| recording.last_at)
+                .map(|(id, _)| id.clone());
+            if let Some(id) = stalest {
+                self.sessions.remove(&id);
+            }
+        }
+        let recording = self
+            .sessions
+            .entry(session_id.to_string())
+            .or_insert_with(|| SessionRecording {
+                workspace_id: workspace_id.to_string(),
+                started_at: timestamp,
+                ..SessionRecording::default()
+            });
+        if recording.events.len() >= MAX_RECORDED_EVENTS {
+            recording.events.pop_front();
+            recording.dropped += 1;
+        }
+        recording.last_at = recording.last_at.max(timestamp);
+        recording.events.push_back(RecordedEvent {
+            offset_ms: timestamp.saturating_sub(recording.started_at).max(0) as u64,
+            event,
+        });
+    }
+}
+
+/// A session's event stream in a self-contained file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SessionReplay {
+    format: String,
+    version: u32,
+    session_id: String,
+    workspace_id: String,
+    exported_at: u64,
+    /// Events missing from the start of the stream because of the cap.
+    dropped_events: usize,
+    events: Vec<RecordedEvent>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReplayExport {
+    path: String,
+    events: usize,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReplayStatus {
+    session_id: String,
+    workspace_id: String,
+    /// "playing", "finished" or "stopped".
+    status: String,
+    events: usize,
+}
+
+/// Record an event for `export_session_replay`. Called for every event the
+/// bridge emits, from sync code; a no-op while recording is off.
+pub(crate) fn capture_event(app_handle: &AppHandle, event: &ClaudeEvent) {
+    if event.session_id.is_empty() {
+        return;
+    }
+    let Some(state) = app_handle.try_state::<AppState>() else {
+        return;
+    };
+    if let Ok(mut recordings) = state.recordings.lock() {
+        if !recordings.enabled {
+            return;
+        }
+        let Ok(value) = serde_json::to_value(event) else {
+            return;
+        };
+        recordings.record(
+            &event.session_id,
+            &event.workspace_id,
+            event.timestamp,
+            value,
+        );
+    }
+}
+
+fn parse_replay(data: &str) -> Result<SessionReplay, String> {
+    let replay: SessionReplay =
+        serde_json::from_str(data).map_err(|e| format!("Not a session replay: {e}"))?;
+    if replay.format != REPLAY_FORMAT {
+        return Err("Not a session replay".to_string());
+    }
+    if replay.version > REPLAY_VERSION {
+        return Err(format!(
+            "Session replay version {} is newer than this app supports",
+            replay.version
+        ));
+    }
+    Ok(replay)
+}
+
+/// Wait before each event, scaled by `speed` and capped at
+/// `MAX_REPLAY_GAP`.
+fn replay_delays(events: &[RecordedEvent], speed: f64) -> Vec<Duration> {
+    let mut previous = 0;
+    events
+        .iter()
+        .map(|event| {
+            let gap = event.offset_ms.saturating_sub(previous);
+            previous = event.offset_ms;
+            Duration::from_secs_f64(gap as f64 / 1000.0 / speed).min(MAX_REPLAY_GAP)
+        })
+        .collect()
+}
+
+fn default_replay_path(state: &AppState, session_id: &str) -> PathBuf {
+    let dir = dirs::download_dir().unwrap_or_else(|| {
+        state
+            .storage_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("replays")
+    });
+    let short_id: String = session_id.chars().take(8).collect();
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(format!("replay-{short_id}-{stamp}.json"))
+}
+
+/// Write the events recorded for a session since the app started, with
+/// their timing, to a JSON file.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn export_session_replay(
+    session_id: String,
+    path: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ReplayExport, String> {
+    let replay = {
+        let recordings = state.recordings.lock().map_err(|e| e.to_string())?;
+        if !recordings.enabled {
+            return Err("Turn on session recording in Settings to export replays".to_string());
+        }
+        let recording = recordings
+            .sessions
+            .get(&session_id)
+            .ok_or("No events were recorded for this session")?;
+        SessionReplay {
+            format: REPLAY_FORMAT.to_string(),
+            version: REPLAY_VERSION,
+            session_id: session_id.clone(),
+            workspace_id: recording.workspace_id.clone(),
+            exported_at: now_millis(),
+            dropped_events: recording.dropped,
+            events: recording.events.iter().cloned().collect(),
+        }
+    };
+    let path = path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_replay_path(&state, &session_id));
+    let events = replay.events.len();
+    tokio::task::spawn_blocking(move || -> Result<ReplayExport, String> {
+        let data = serde_json::to_string(&replay).map_err(|e| e.to_string())?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&path, data).map_err(|e| e.to_string())?;
+        Ok(ReplayExport {
+            path: path.to_string_lossy().to_string(),
+            events,
+        })
+    })
+    .await
+    .map_err(|_| "replay export failed".to_string())?
+}
+
+/// Re-emit an exported session's events to the frontend at `speed` times
+/// their original pace (default 1). Emits `replay-status` when playback
+/// starts and ends. Starting a replay stops the one in progress.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn replay_session(
+    path: String,
+    speed: Option<f64>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ReplayStatus, String> {
+    let speed = speed.unwrap_or(1.0);
+    if speed <= 0.0 || speed > MAX_REPLAY_SPEED {
+        return Err(format!(
+            "Speed must be above 0 and at most {MAX_REPLAY_SPEED}"
+        ));
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let replay = parse_replay(&data)?;
+    let delays = replay_delays(&replay.events, speed);
+    let status = |status: &str| ReplayStatus {
+        session_id: replay.session_id.clone(),
+        workspace_id: replay.workspace_id.clone(),
+        status: status.to_string(),
+        events: replay.events.len(),
+    };
+    let playing = status("playing");
+    let finished = status("finished");
+
+    let app = app_handle.clone();
+    let workspace_id = replay.workspace_id.clone();
+    let events = replay.events;
+    let handle = tauri::async_runtime::spawn(async move {
+        for (event, delay) in events.into_iter().zip(delays) {
+            tokio::time::sleep(delay).await;
+            emit_scoped(&app, "claude-event", &workspace_id, event.event);
+        }
+        let _ = app.emit("replay-status", &finished);
+    });
+    let previous = state
+        .recordings
+        .lock()
+        .map_err(|e| e.to_string())?
+        .replay
+        .replace((handle, playing.clone()));
+    if let Some((previous, _)) = previous {
+        previous.abort();
+    }
+    let _ = app_handle.emit("replay-status", &playing);
+    Ok(playing)
+}
+
+/// Stop the replay in progress, if any.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn stop_replay(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let replay = state
+        .recordings
+        .lock()
+        .map_err(|e| e.to_string())?
+        .replay
+        .take();
+    if let Some((handle, mut status)) = replay {
+        if !handle.inner().is_finished() {
+            handle.abort();
+            status.status = "stopped".to_string();
+            let _ = app_handle.emit("replay-status", &status);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{
+        parse_replay, replay_delays, RecordedEvent, Recordings, MAX_RECORDED_EVENTS,
+        MAX_RECORDED_SESSIONS, MAX_REPLAY_GAP,
+    };
+
+    #[test]
+    fn records_offsets_and_caps_the_stream() {
+        let mut recordings = Recordings::default();
+        recordings.record("s1", "w1", 1_000, json!({ "n": 0 }));
+        recordings.record("s1", "w1", 1_250, json!({ "n": 1 }));
+        let recording = &recordings.sessions["s1"];
+        assert_eq!(recording.workspace_id, "w1");
+        let offsets: Vec<u64> = recording.events.iter().map(|e| e.offset_ms).collect();
+        assert_eq!(offsets, [0, 250]);
+
+        for n in 0..MAX_RECORDED_EVENTS {
+            recordings.record("s2", "w1", n as i64, json!({ "n": n }));
+        }
+        recordings.record("s2", "w1", 0, json!({ "n": "last" }));
+        let recording = &recordings.sessions["s2"];
+        assert_eq!(recording.events.len(), MAX_RECORDED_EVENTS);
+        assert_eq!(recording.dropped, 1);
+        assert_eq!(recording.events[0].event["n"], 1);
+    }
+
+    #[test]
+    fn keeps_the_most_recent_sessions() {
+        let mut recordings = Recordings::default();
+        recordings.set_enabled(true);
+        for n in 0..MAX_RECORDED_SESSIONS {
+            recordings.record(&format!("s{n}"), "w1", 100 + n as i64, json!({}));
+        }
+        recordings.record("s0", "w1", 1_000, json!({}));
+        recordings.record("new", "w1", 1_001, json!({}));
+        assert_eq!(recordings.sessions.len(), MAX_RECORDED_SESSIONS);
+        assert!(recordings.sessions.contains_key("s0"));
+        assert!(!recordings.sessions.contains_key("s1"));
+
+        recordings.set_enabled(false);
+        assert!(recordings.sessions.is_empty());
+    }
+
+    #[test]
+    fn scales_and_caps_delays() {
+        let event = |offset_ms| RecordedEvent {
+            offset_ms,
+            event: json!({}),
+        };
+        let events = [event(0), event(1_000), event(1_500), event(600_000)];
+        assert_eq!(
+            replay_delays(&events, 2.0),
+            [
+                Duration::ZERO,
+                Duration::from_millis(500),
+                Duration::from_millis(250),
+                MAX_REPLAY_GAP,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(parse_replay(r#"{"format":"claude-monitor-session"}"#).is_err());
+        let replay = json!({
+            "format": "claude-monitor-replay",
+            "version": 99,
+            "sessionId": "s1",
+            "workspaceId": "w1",
+            "exportedAt": 0,
+            "droppedEvents": 0,
+            "events": [],
+        });
+        assert!(parse_replay(&replay.to_string())
+            .unwrap_err()
+            .contains("newer"));
+    }
+}
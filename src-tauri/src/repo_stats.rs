@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{ObjectType, Patch, Repository, Sort, Tree, TreeWalkMode, TreeWalkResult};
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::git::{open_workspace_repo, workspace_relative_path};
+use crate::registry::now_millis;
+use crate::state::AppState;
+
+/// Commits whose authors are tallied; older history only adds to the count.
+const MAX_AUTHOR_COMMITS: usize = 20_000;
+/// Most recent commits diffed to find hotspots.
+const MAX_CHURN_COMMITS: usize = 1_000;
+const MAX_CONTRIBUTORS: usize = 50;
+const MAX_HOTSPOTS: usize = 20;
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Contributor {
+    name: String,
+    email: String,
+    commits: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LanguageShare {
+    /// Language name, or `.ext` for extensions without one.
+    language: String,
+    files: usize,
+    bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Hotspot {
+    path: String,
+    commits: usize,
+    lines_changed: usize,
+}
+
+/// Overview of a workspace's repository at `head`.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RepoStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    head: Option<String>,
+    commit_count: usize,
+    contributors: Vec<Contributor>,
+    /// Files at HEAD within the workspace, largest language first.
+    languages: Vec<LanguageShare>,
+    /// Size of the files at HEAD within the workspace.
+    tracked_bytes: u64,
+    /// Size of the `.git` directory, history included.
+    git_dir_bytes: u64,
+    /// Most-churned files across recent non-merge commits.
+    hotspots: Vec<Hotspot>,
+    computed_at: u64,
+}
+
+/// workspace id -> last computed stats, reused while HEAD is unchanged.
+pub(crate) type RepoStatsCache = HashMap<String, RepoStats>;
+
+fn language_for(name: &str) -> String {
+    let ext = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => ext.to_ascii_lowercase(),
+        _ => return "Other".to_string(),
+    };
+    let language = match ext.as_str() {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" | "pyi" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "html" | "htm" => "HTML",
+        "md" | "mdx" => "Markdown",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "yml" | "yaml" => "YAML",
+        "sql" => "SQL",
+        _ => return format!(".{ext}"),
+    };
+    language.to_string()
+}
+
+/// Per-language file counts and sizes for `tree`, plus the total size.
+fn language_breakdown(repo: &Repository, tree: &Tree) -> (Vec<LanguageShare>, u64) {
+    let odb = repo.odb().ok();
+    let mut by_language: HashMap<String, (usize, u64)> = HashMap::new();
+    let _ = tree.walk(TreeWalkMode::PreOrder, |_, entry| {
+        if entry.kind() == Some(ObjectType::Blob) {
+            let size = odb
+                .as_ref()
+                .and_then(|odb| odb.read_header(entry.id()).ok())
+                .map(|(size, _)| size as u64)
+                .unwrap_or(0);
+            let slot = by_language
+                .entry(language_for(entry.name().unwrap_or("")))
+                .or_default();
+            slot.0 += 1;
+            slot.1 += size;
+        }
+        TreeWalkResult::Ok
+    });
+    let total = by_language.values().map(|(_, bytes)| bytes).sum();
+    let mut languages: Vec<LanguageShare> = by_language
+        .into_iter()
+        .map(|(language, (files, bytes))| LanguageShare {
+            language,
+            files,
+            bytes,
+        })
+        .collect();
+    languages.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.language.cmp(&b.language)));
+    (languages, total)
+}
+
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Commit count and authors by email, most commits first.
+fn contributors(repo: &Repository) -> Result<(usize, Vec<Contributor>), git2::Error> {
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TIME)?;
+    walk.push_head()?;
+    let mut count = 0;
+    let mut by_email: HashMap<String, Contributor> = HashMap::new();
+    for oid in walk {
+        let oid = oid?;
+        count += 1;
+        if count > MAX_AUTHOR_COMMITS {
+            continue;
+        }
+        let commit = repo.find_commit(oid)?;
+        let author = commit.author();
+        let email = author.email().unwrap_or("").to_lowercase();
+        let entry = by_email
+            .entry(email.clone())
+            .or_insert_with(|| Contributor {
+                // The walk is newest first, so this is the latest spelling.
+                name: author.name().unwrap_or("").to_string(),
+                email,
+                commits: 0,
+            });
+        entry.commits += 1;
+    }
+    let mut list: Vec<Contributor> = by_email.into_values().collect();
+    list.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.name.cmp(&b.name)));
+    list.truncate(MAX_CONTRIBUTORS);
+    Ok((count, list))
+}
+
+/// Files touched by the most recent non-merge commits, most often first.
+fn hotspots(repo: &Repository, subdir: Option<&str>) -> Result<Vec<Hotspot>, git2::Error> {
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TIME)?;
+    walk.push_head()?;
+    let prefix = subdir.map(|subdir| format!("{subdir}/"));
+    let mut by_path: HashMap<String, Hotspot> = HashMap::new();
+    for oid in walk.take(MAX_CHURN_COMMITS) {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+        for (index, delta) in diff.deltas().enumerate() {
+            let Some(path) = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .and_then(|path| path.to_str())
+            else {
+                continue;
+            };
+            if prefix
+                .as_deref()
+                .is_some_and(|prefix| !path.starts_with(prefix))
+            {
+                continue;
+            }
+            let lines = Patch::from_diff(&diff, index)?
+                .map(|patch| patch.line_stats().map(|(_, add, del)| add + del))
+                .transpose()?
+                .unwrap_or(0);
+            let path = workspace_relative_path(path, subdir);
+            let entry = by_path.entry(path.clone()).or_insert_with(|| Hotspot {
+                path,
+                commits: 0,
+                lines_changed: 0,
+            });
+            entry.commits += 1;
+            entry.lines_changed += lines;
+        }
+    }
+    let mut list: Vec<Hotspot> = by_path.into_values().collect();
+    list.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then(b.lines_changed.cmp(&a.lines_changed))
+            .then(a.path.cmp(&b.path))
+    });
+    list.truncate(MAX_HOTSPOTS);
+    Ok(list)
+}
+
+fn head_sha(repo: &Repository) -> Option<String> {
+    repo.head().ok()?.target().map(|oid| oid.to_string())
+}
+
+fn compute_stats(workspace_path: &str) -> Result<RepoStats, String> {
+    let (repo, subdir) = open_workspace_repo(workspace_path)?;
+    let head = head_sha(&repo);
+    let git_dir_bytes = dir_size(repo.path());
+    let computed_at = now_millis();
+    let Some(head_tree) = repo.head().ok().and_then(|head| head.peel_to_tree().ok()) else {
+        // Unborn branch: nothing committed yet.
+        return Ok(RepoStats {
+            head,
+            commit_count: 0,
+            contributors: Vec::new(),
+            languages: Vec::new(),
+            tracked_bytes: 0,
+            git_dir_bytes,
+            hotspots: Vec::new(),
+            computed_at,
+        });
+    };
+    let tree = match subdir.as_deref() {
+        Some(subdir) => head_tree
+            .get_path(Path::new(subdir))
+            .and_then(|entry| entry.to_object(&repo))
+            .and_then(|object| object.peel_to_tree())
+            .ok(),
+        None => Some(head_tree),
+    };
+    let (languages, tracked_bytes) = match &tree {
+        Some(tree) => language_breakdown(&repo, tree),
+        None => (Vec::new(), 0),
+    };
+    let (commit_count, contributors) = contributors(&repo).map_err(|e| e.to_string())?;
+    let hotspots = hotspots(&repo, subdir.as_deref()).map_err(|e| e.to_string())?;
+    Ok(RepoStats {
+        head,
+        commit_count,
+        contributors,
+        languages,
+        tracked_bytes,
+        git_dir_bytes,
+        hotspots,
+        computed_at,
+    })
+}
+
+/// Commit, contributor, language, size and hotspot figures for a
+/// workspace overview. Computed off the runtime and cached per workspace
+/// until HEAD moves; `refresh` recomputes regardless.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_repo_stats(
+    workspace_id: String,
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<RepoStats, String> {
+    let _git = state.git_locks.read(&workspace_id).await;
+    let workspace_path = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(|entry| entry.path.clone())
+        .ok_or("workspace not found")?;
+    if !refresh.unwrap_or(false) {
+        let (repo, _) = open_workspace_repo(&workspace_path)?;
+        let head = head_sha(&repo);
+        if let Some(cached) = state.repo_stats.lock().await.get(&workspace_id) {
+            if cached.head == head {
+                return Ok(cached.clone());
+            }
+        }
+    }
+    let stats = tokio::task::spawn_blocking(move || compute_stats(&workspace_path))
+        .await
+        .map_err(|_| "repo stats failed".to_string())??;
+    state
+        .repo_stats
+        .lock()
+        .await
+        .insert(workspace_id, stats.clone());
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compute_stats;
+    use crate::utils::TempDir;
+    use git2::{Repository, Signature};
+    use std::path::Path;
+
+    fn commit(repo: &Repository, author: &str, files: &[(&str, &str)]) {
+        let root = repo.workdir().unwrap().to_path_buf();
+        let mut index = repo.index().unwrap();
+        for (path, contents) in files {
+            std::fs::write(root.join(path), contents).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now(author, &format!("{author}@example.com")).unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "change",
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn counts_commits_authors_languages_and_churn() {
+        let root = TempDir::new("repo-stats");
+        let repo = Repository::init(&root).unwrap();
+        commit(
+            &repo,
+            "ada",
+            &[("main.rs", "fn main() {}\n"), ("README.md", "# hi\n")],
+        );
+        commit(&repo, "ada", &[("main.rs", "fn main() { run(); }\n")]);
+        commit(&repo, "grace", &[("main.rs", "fn main() { go(); }\n")]);
+
+        let stats = compute_stats(root.to_str().unwrap()).unwrap();
+        assert_eq!(stats.commit_count, 3);
+        let authors: Vec<_> = stats
+            .contributors
+            .iter()
+            .map(|c| (c.name.as_str(), c.commits))
+            .collect();
+        assert_eq!(authors, [("ada", 2), ("grace", 1)]);
+        assert_eq!(stats.languages[0].language, "Rust");
+        assert_eq!(stats.hotspots[0].path, "main.rs");
+        assert_eq!(stats.hotspots[0].commits, 3);
+        assert!(stats.git_dir_bytes > 0);
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tauri::{State, WebviewWindow};
+use tokio::process::Command;
+use tokio::time::sleep;
+
+use crate::command_metrics::CommandTimer;
+use crate::images::{finish_image, new_attachment_path, ImageAttachment};
+use crate::state::AppState;
+
+/// Time for the window to leave the screen before capturing behind it.
+const HIDE_DELAY: Duration = Duration::from_millis(250);
+
+/// Screen rectangle in logical pixels.
+#[derive(Debug, Deserialize, Clone, Copy, specta::Type)]
+pub(crate) struct ScreenRegion {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// A PowerShell single-quoted string.
+fn powershell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// PowerShell that captures `region` with GDI+, or opens the Snipping Tool
+/// overlay and saves what it puts on the clipboard. A selection that is
+/// cancelled never changes the clipboard, so it gives up after two minutes.
+fn windows_capture_script(region: Option<ScreenRegion>, file: &str) -> String {
+    let file = powershell_quote(file);
+    match region {
+        Some(r) => format!(
+            "Add-Type -AssemblyName System.Drawing\n\
+             $bitmap = New-Object System.Drawing.Bitmap {}, {}\n\
+             $graphics = [System.Drawing.Graphics]::FromImage($bitmap)\n\
+             $graphics.CopyFromScreen({}, {}, 0, 0, $bitmap.Size)\n\
+             $bitmap.Save({file}, [System.Drawing.Imaging.ImageFormat]::Png)\n",
+            r.width, r.height, r.x, r.y
+        ),
+        None => format!(
+            "Add-Type -AssemblyName System.Windows.Forms, System.Drawing\n\
+             Add-Type -Name Clipboard -Namespace Native -MemberDefinition \
+             '[DllImport(\"user32.dll\")] public static extern uint GetClipboardSequenceNumber();'\n\
+             $start = [Native.Clipboard]::GetClipboardSequenceNumber()\n\
+             Start-Process 'ms-screenclip:'\n\
+             for ($i = 0; $i -lt 480; $i++) {{\n\
+               Start-Sleep -Milliseconds 250\n\
+               if ([Native.Clipboard]::GetClipboardSequenceNumber() -ne $start) {{\n\
+                 $image = [System.Windows.Forms.Clipboard]::GetImage()\n\
+                 if ($image) {{ $image.Save({file}, [System.Drawing.Imaging.ImageFormat]::Png); break }}\n\
+               }}\n\
+             }}\n"
+        ),
+    }
+}
+
+/// Screenshot tool invocation for the platform: a fixed `region`, or an
+/// interactive selection when it is `None`.
+fn capture_commands(region: Option<ScreenRegion>, file: &str, wayland: bool) -> Vec<Vec<String>> {
+    let owned = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    if cfg!(target_os = "windows") {
+        let script = windows_capture_script(region, file);
+        return vec![owned(&[
+            "powershell",
+            "-NoProfile",
+            "-STA",
+            "-Command",
+            &script,
+        ])];
+    }
+    if cfg!(target_os = "macos") {
+        return vec![match region {
+            Some(r) => {
+                let rect = format!("{},{},{},{}", r.x, r.y, r.width, r.height);
+                owned(&["screencapture", "-x", "-R", &rect, file])
+            }
+            None => owned(&["screencapture", "-i", "-x", file]),
+        }];
+    }
+    match (region, wayland) {
+        (Some(r), true) => {
+            let geometry = format!("{},{} {}x{}", r.x, r.y, r.width, r.height);
+            vec![owned(&["grim", "-g", &geometry, file])]
+        }
+        (None, true) => vec![owned(&[
+            "sh",
+            "-c",
+            "grim -g \"$(slurp)\" \"$1\"",
+            "sh",
+            file,
+        ])],
+        (Some(r), false) => {
+            let geometry = format!("{}x{}+{}+{}", r.width, r.height, r.x, r.y);
+            vec![
+                owned(&["maim", "-g", &geometry, file]),
+                owned(&["import", "-window", "root", "-crop", &geometry, file]),
+            ]
+        }
+        (None, false) => vec![
+            owned(&["maim", "-s", file]),
+            owned(&["gnome-screenshot", "-a", "-f", file]),
+            owned(&["scrot", "-s", file]),
+        ],
+    }
+}
+
+/// Run the first available capture tool. Cancelling a selection leaves no
+/// file, which is reported as such rather than as a failure; a tool that
+/// exits with an error is reported with what it printed.
+async fn capture_to(path: &Path, region: Option<ScreenRegion>) -> Result<(), String> {
+    let file = path.to_string_lossy().to_string();
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let mut missing = Vec::new();
+    for command in capture_commands(region, &file, wayland) {
+        let output = match Command::new(&command[0]).args(&command[1..]).output().await {
+            Ok(output) => output,
+            Err(_) => {
+                missing.push(command[0].clone());
+                continue;
+            }
+        };
+        if output.status.success() {
+            return if path.is_file() {
+                Ok(())
+            } else {
+                Err("Screen capture cancelled".to_string())
+            };
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        // Escaping out of an interactive selection exits non-zero (maim,
+        // slurp) without writing anything.
+        if region.is_none() && (stderr.is_empty() || stderr.to_lowercase().contains("cancel")) {
+            return Err("Screen capture cancelled".to_string());
+        }
+        return Err(if stderr.is_empty() {
+            format!("{} failed ({})", command[0], output.status)
+        } else {
+            format!("{} failed: {stderr}", command[0])
+        });
+    }
+    Err(format!(
+        "No screenshot tool found (tried {})",
+        missing.join(", ")
+    ))
+}
+
+/// Capture part of the screen into the attachment cache for sending. With
+/// no `region` the user drags one out. The app window is hidden meanwhile
+/// unless `hide_window` is false.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn capture_screen_region(
+    region: Option<ScreenRegion>,
+    hide_window: Option<bool>,
+    window: WebviewWindow,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<ImageAttachment, String> {
+    if let Some(region) = region {
+        if region.width == 0 || region.height == 0 {
+            return Err("Region must have a non-zero size".to_string());
+        }
+    }
+    let path = new_attachment_path(&state, "png")?;
+    let hide = hide_window.unwrap_or(true) && window.is_visible().unwrap_or(false);
+    if hide {
+        let _ = window.hide();
+        sleep(HIDE_DELAY).await;
+    }
+    let captured = capture_to(&path, region).await;
+    if hide {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    if let Err(err) = captured {
+        let _ = std::fs::remove_file(&path);
+        return Err(err);
+    }
+    finish_image(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture_commands, windows_capture_script, ScreenRegion};
+
+    #[test]
+    fn builds_region_geometry_per_tool() {
+        let region = ScreenRegion {
+            x: 10,
+            y: 20,
+            width: 300,
+            height: 200,
+        };
+        let commands = capture_commands(Some(region), "/tmp/shot.png", false);
+        if cfg!(target_os = "windows") {
+            assert!(commands[0][4].contains("CopyFromScreen(10, 20, 0, 0, $bitmap.Size)"));
+        } else if cfg!(target_os = "macos") {
+            assert_eq!(commands[0][3], "10,20,300,200");
+        } else {
+            assert_eq!(
+                commands[0],
+                ["maim", "-g", "300x200+10+20", "/tmp/shot.png"]
+            );
+            let wayland = capture_commands(Some(region), "/tmp/shot.png", true);
+            assert_eq!(wayland[0][2], "10,20 300x200");
+        }
+    }
+
+    #[test]
+    fn windows_scripts_quote_the_file() {
+        let script = windows_capture_script(None, "C:\\Users\\o'neil\\shot.png");
+        assert!(script.contains("$image.Save('C:\\Users\\o''neil\\shot.png', "));
+        assert!(script.contains("Start-Process 'ms-screenclip:'"));
+    }
+}
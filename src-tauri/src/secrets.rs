@@ -0,0 +1,259 @@
+#[cfg(target_os = "macos")]
+use security_framework::passwords;
+#[cfg(target_os = "linux")]
+use tokio::process::Command;
+
+/// Keychain service name used for every secret the app stores.
+const SECRET_SERVICE: &str = "com.dimillian.codexmonitor";
+
+#[cfg(target_os = "linux")]
+fn command_error(output: &std::process::Output, fallback: &str) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        fallback.to_string()
+    } else {
+        detail.to_string()
+    }
+}
+
+/// `errSecItemNotFound`.
+#[cfg(target_os = "macos")]
+const ITEM_NOT_FOUND: i32 = -25300;
+
+/// Read a secret from the OS keychain. Returns `None` when no entry exists.
+#[cfg(target_os = "macos")]
+pub(crate) async fn read_secret(key: &str) -> Result<Option<String>, String> {
+    let key = key.to_string();
+    tokio::task::spawn_blocking(move || {
+        match passwords::get_generic_password(SECRET_SERVICE, &key) {
+            Ok(value) => {
+                let value = String::from_utf8_lossy(&value).trim_end().to_string();
+                Ok(if value.is_empty() { None } else { Some(value) })
+            }
+            Err(err) if err.code() == ITEM_NOT_FOUND => Ok(None),
+            Err(err) => Err(format!("Failed to read keychain entry: {err}")),
+        }
+    })
+    .await
+    .map_err(|_| "keychain read failed".to_string())?
+}
+
+/// Store a secret in the OS keychain, replacing any existing entry. The
+/// value goes through the Security framework, never a command line.
+#[cfg(target_os = "macos")]
+pub(crate) async fn write_secret(key: &str, value: &str) -> Result<(), String> {
+    let key = key.to_string();
+    let value = value.to_string();
+    tokio::task::spawn_blocking(move || {
+        passwords::set_generic_password(SECRET_SERVICE, &key, value.as_bytes())
+            .map_err(|err| format!("Failed to write keychain entry: {err}"))
+    })
+    .await
+    .map_err(|_| "keychain write failed".to_string())?
+}
+
+/// Remove a secret from the OS keychain. Missing entries are not an error.
+#[cfg(target_os = "macos")]
+pub(crate) async fn delete_secret(key: &str) -> Result<(), String> {
+    let key = key.to_string();
+    tokio::task::spawn_blocking(move || {
+        match passwords::delete_generic_password(SECRET_SERVICE, &key) {
+            Ok(()) => Ok(()),
+            Err(err) if err.code() == ITEM_NOT_FOUND => Ok(()),
+            Err(err) => Err(format!("Failed to delete keychain entry: {err}")),
+        }
+    })
+    .await
+    .map_err(|_| "keychain delete failed".to_string())?
+}
+
+/// Read a secret via the Secret Service API (`secret-tool`).
+#[cfg(target_os = "linux")]
+pub(crate) async fn read_secret(key: &str) -> Result<Option<String>, String> {
+    let output = Command::new("secret-tool")
+        .args(["lookup", "service", SECRET_SERVICE, "account", key])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run secret-tool: {e}"))?;
+    if !output.status.success() {
+        // secret-tool exits non-zero with no output when the item is missing.
+        if output.stderr.is_empty() {
+            return Ok(None);
+        }
+        return Err(command_error(&output, "Failed to read secret."));
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
+    Ok(if value.is_empty() { None } else { Some(value) })
+}
+
+/// Store a secret via the Secret Service API (`secret-tool`).
+#[cfg(target_os = "linux")]
+pub(crate) async fn write_secret(key: &str, value: &str) -> Result<(), String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut child = Command::new("secret-tool")
+        .args([
+            "store",
+            &format!("--label=CodexMonitor {key}"),
+            "service",
+            SECRET_SERVICE,
+            "account",
+            key,
+        ])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run secret-tool: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(value.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write secret: {e}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run secret-tool: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(command_error(&output, "Failed to write secret."))
+    }
+}
+
+/// Remove a secret via the Secret Service API (`secret-tool`).
+#[cfg(target_os = "linux")]
+pub(crate) async fn delete_secret(key: &str) -> Result<(), String> {
+    let output = Command::new("secret-tool")
+        .args(["clear", "service", SECRET_SERVICE, "account", key])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run secret-tool: {e}"))?;
+    if output.status.success() || output.stderr.is_empty() {
+        Ok(())
+    } else {
+        Err(command_error(&output, "Failed to delete secret."))
+    }
+}
+
+/// Generic credentials in the Windows Credential Manager, one per key,
+/// named `<service>:<key>`.
+#[cfg(target_os = "windows")]
+mod credential_manager {
+    use windows::core::{HSTRING, PWSTR};
+    use windows::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows::Win32::Security::Credentials::{
+        CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE,
+        CRED_TYPE_GENERIC,
+    };
+
+    use super::SECRET_SERVICE;
+
+    fn target_name(key: &str) -> String {
+        format!("{SECRET_SERVICE}:{key}")
+    }
+
+    fn is_not_found(err: &windows::core::Error) -> bool {
+        err.code() == ERROR_NOT_FOUND.to_hresult()
+    }
+
+    pub(super) fn read(key: &str) -> Result<Option<String>, String> {
+        let target = HSTRING::from(target_name(key));
+        let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+        unsafe {
+            match CredReadW(&target, CRED_TYPE_GENERIC, 0, &mut credential) {
+                Ok(()) => {}
+                Err(err) if is_not_found(&err) => return Ok(None),
+                Err(err) => return Err(format!("Failed to read credential: {err}")),
+            }
+            let blob = std::slice::from_raw_parts(
+                (*credential).CredentialBlob,
+                (*credential).CredentialBlobSize as usize,
+            );
+            let value = String::from_utf8_lossy(blob).trim_end().to_string();
+            CredFree(credential as *const _);
+            Ok(if value.is_empty() { None } else { Some(value) })
+        }
+    }
+
+    pub(super) fn write(key: &str, value: &str) -> Result<(), String> {
+        let mut target: Vec<u16> = target_name(key).encode_utf16().chain([0]).collect();
+        let mut user: Vec<u16> = key.encode_utf16().chain([0]).collect();
+        let mut blob = value.as_bytes().to_vec();
+        let credential = CREDENTIALW {
+            Type: CRED_TYPE_GENERIC,
+            TargetName: PWSTR(target.as_mut_ptr()),
+            UserName: PWSTR(user.as_mut_ptr()),
+            CredentialBlobSize: blob.len() as u32,
+            CredentialBlob: blob.as_mut_ptr(),
+            Persist: CRED_PERSIST_LOCAL_MACHINE,
+            ..Default::default()
+        };
+        unsafe { CredWriteW(&credential, 0) }
+            .map_err(|err| format!("Failed to write credential: {err}"))
+    }
+
+    pub(super) fn delete(key: &str) -> Result<(), String> {
+        let target = HSTRING::from(target_name(key));
+        match unsafe { CredDeleteW(&target, CRED_TYPE_GENERIC, 0) } {
+            Ok(()) => Ok(()),
+            Err(err) if is_not_found(&err) => Ok(()),
+            Err(err) => Err(format!("Failed to delete credential: {err}")),
+        }
+    }
+}
+
+/// Read a secret from the Windows Credential Manager.
+#[cfg(target_os = "windows")]
+pub(crate) async fn read_secret(key: &str) -> Result<Option<String>, String> {
+    let key = key.to_string();
+    tokio::task::spawn_blocking(move || credential_manager::read(&key))
+        .await
+        .map_err(|_| "credential read failed".to_string())?
+}
+
+/// Store a secret in the Windows Credential Manager, replacing any
+/// existing entry.
+#[cfg(target_os = "windows")]
+pub(crate) async fn write_secret(key: &str, value: &str) -> Result<(), String> {
+    let key = key.to_string();
+    let value = value.to_string();
+    tokio::task::spawn_blocking(move || credential_manager::write(&key, &value))
+        .await
+        .map_err(|_| "credential write failed".to_string())?
+}
+
+/// Remove a secret from the Windows Credential Manager. Missing entries
+/// are not an error.
+#[cfg(target_os = "windows")]
+pub(crate) async fn delete_secret(key: &str) -> Result<(), String> {
+    let key = key.to_string();
+    tokio::task::spawn_blocking(move || credential_manager::delete(&key))
+        .await
+        .map_err(|_| "credential delete failed".to_string())?
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+const NO_SECRET_STORE: &str = "No secure credential store is available on this platform.";
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub(crate) async fn read_secret(_key: &str) -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub(crate) async fn write_secret(_key: &str, _value: &str) -> Result<(), String> {
+    Err(NO_SECRET_STORE.to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub(crate) async fn delete_secret(_key: &str) -> Result<(), String> {
+    Ok(())
+}
@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::registry::{matches_metadata, resolve_transcript_path};
+use crate::state::AppState;
+use crate::transcripts::transcript_messages;
+
+/// Approximate size of an indexed passage.
+const PASSAGE_CHARS: usize = 1_200;
+const DEFAULT_RESULT_LIMIT: usize = 10;
+/// Passages embedded per ONNX run.
+const EMBED_BATCH: usize = 32;
+
+/// Local sentence-embedding model, downloaded into the data dir on the
+/// first search and kept for the life of the app.
+static MODEL: OnceLock<TextEmbedding> = OnceLock::new();
+/// Held while the model loads, so concurrent searches load it once.
+static MODEL_LOAD: Mutex<()> = Mutex::new(());
+
+struct IndexedPassage {
+    role: String,
+    message_index: usize,
+    text: String,
+    vector: Vec<f32>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SemanticMatch {
+    session_id: String,
+    role: String,
+    /// Position of the passage's message among the session's messages.
+    message_index: usize,
+    score: f32,
+    text: String,
+}
+
+fn data_dir(state: &AppState) -> &Path {
+    state.storage_path.parent().unwrap_or(Path::new("."))
+}
+
+/// Where the embedding model is cached.
+fn model_dir(state: &AppState) -> PathBuf {
+    data_dir(state).join("models")
+}
+
+/// SQLite file holding every passage vector, so indexes survive restarts
+/// and only the sessions being searched are read back.
+pub(crate) fn store_path(state: &AppState) -> PathBuf {
+    data_dir(state).join("semantic-index.sqlite3")
+}
+
+/// Open the index store, creating its tables on first use. Connections
+/// are opened per search or indexer pass; WAL lets them overlap.
+pub(crate) fn open_store(path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let conn = Connection::open(path).map_err(|e| e.to_string())?;
+    conn.busy_timeout(Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         CREATE TABLE IF NOT EXISTS sessions (
+             session_id TEXT PRIMARY KEY,
+             transcript_bytes INTEGER NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS passages (
+             session_id TEXT NOT NULL,
+             role TEXT NOT NULL,
+             message_index INTEGER NOT NULL,
+             text TEXT NOT NULL,
+             vector BLOB NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS passages_by_session ON passages (session_id);",
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(conn)
+}
+
+fn vector_to_blob(vector: &[f32]) -> Vec<u8> {
+    vector
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+fn vector_from_blob(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect()
+}
+
+/// The embedding model, loading (and on first use downloading) it into
+/// `cache_dir` if needed. Blocks; call from `spawn_blocking`.
+fn load_model(cache_dir: &Path) -> Result<&'static TextEmbedding, String> {
+    if let Some(model) = MODEL.get() {
+        return Ok(model);
+    }
+    let _loading = MODEL_LOAD
+        .lock()
+        .map_err(|_| "embedding model load failed".to_string())?;
+    if let Some(model) = MODEL.get() {
+        return Ok(model);
+    }
+    let options = InitOptions::new(EmbeddingModel::AllMiniLML6V2)
+        .with_cache_dir(cache_dir.to_path_buf())
+        .with_show_download_progress(false);
+    let model = TextEmbedding::try_new(options)
+        .map_err(|e| format!("Failed to load the embedding model: {e}"))?;
+    Ok(MODEL.get_or_init(|| model))
+}
+
+/// The embedding model if a search has loaded it. The background indexer
+/// only embeds transcripts once semantic search is in use.
+pub(crate) fn loaded_model() -> Option<&'static TextEmbedding> {
+    MODEL.get()
+}
+
+fn embed_all(model: &TextEmbedding, texts: Vec<&str>) -> Result<Vec<Vec<f32>>, String> {
+    model
+        .embed(texts, Some(EMBED_BATCH))
+        .map_err(|e| format!("Embedding failed: {e}"))
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>() / denominator
+}
+
+/// Split each message into passages of roughly `PASSAGE_CHARS`, breaking on
+/// paragraph boundaries where possible.
+fn passages_for(messages: &[(String, String)]) -> Vec<(String, usize, String)> {
+    let mut passages = Vec::new();
+    for (index, (role, text)) in messages.iter().enumerate() {
+        let mut current = String::new();
+        for paragraph in text.split("\n\n") {
+            if !current.is_empty() && current.len() + paragraph.len() > PASSAGE_CHARS {
+                passages.push((role.clone(), index, std::mem::take(&mut current)));
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(paragraph);
+        }
+        if !current.trim().is_empty() {
+            passages.push((role.clone(), index, current));
+        }
+    }
+    passages
+}
+
+fn build_passages(model: &TextEmbedding, path: &Path) -> Result<Vec<IndexedPassage>, String> {
+    let messages = transcript_messages(path)?;
+    let passages = passages_for(&messages);
+    let vectors = embed_all(
+        model,
+        passages.iter().map(|(_, _, text)| text.as_str()).collect(),
+    )?;
+    Ok(passages
+        .into_iter()
+        .zip(vectors)
+        .map(|((role, message_index, text), vector)| IndexedPassage {
+            role,
+            message_index,
+            text,
+            vector,
+        })
+        .collect())
+}
+
+fn store_passages(
+    conn: &mut Connection,
+    session_id: &str,
+    transcript_bytes: u64,
+    passages: &[IndexedPassage],
+) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "DELETE FROM passages WHERE session_id = ?1",
+        params![session_id],
+    )
+    .map_err(|e| e.to_string())?;
+    {
+        let mut insert = tx
+            .prepare(
+                "INSERT INTO passages (session_id, role, message_index, text, vector)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .map_err(|e| e.to_string())?;
+        for passage in passages {
+            insert
+                .execute(params![
+                    session_id,
+                    passage.role,
+                    passage.message_index as i64,
+                    passage.text,
+                    vector_to_blob(&passage.vector),
+                ])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO sessions (session_id, transcript_bytes) VALUES (?1, ?2)",
+        params![session_id, transcript_bytes as i64],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Embed a transcript into the store unless it is already there at
+/// `transcript_bytes`; a transcript that grew is re-indexed.
+pub(crate) fn ensure_indexed(
+    model: &TextEmbedding,
+    conn: &mut Connection,
+    session_id: &str,
+    path: &Path,
+    transcript_bytes: u64,
+) -> Result<(), String> {
+    let stored: Option<i64> = conn
+        .query_row(
+            "SELECT transcript_bytes FROM sessions WHERE session_id = ?1",
+            params![session_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if stored == Some(transcript_bytes as i64) {
+        return Ok(());
+    }
+    let passages = build_passages(model, path)?;
+    store_passages(conn, session_id, transcript_bytes, &passages)
+}
+
+/// Score one session's stored passages against `query`, keeping only the
+/// best `limit` matches overall so memory stays bounded.
+fn score_session(
+    conn: &Connection,
+    session_id: &str,
+    query: &[f32],
+    limit: usize,
+    matches: &mut Vec<SemanticMatch>,
+) -> Result<(), String> {
+    let mut select = conn
+        .prepare("SELECT role, message_index, text, vector FROM passages WHERE session_id = ?1")
+        .map_err(|e| e.to_string())?;
+    let mut rows = select
+        .query(params![session_id])
+        .map_err(|e| e.to_string())?;
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        let vector: Vec<u8> = row.get(3).map_err(|e| e.to_string())?;
+        let score = cosine(query, &vector_from_blob(&vector));
+        if score <= 0.0 {
+            continue;
+        }
+        if matches.len() >= limit && matches.last().is_some_and(|worst| worst.score >= score) {
+            continue;
+        }
+        let message_index: i64 = row.get(1).map_err(|e| e.to_string())?;
+        matches.push(SemanticMatch {
+            session_id: session_id.to_string(),
+            role: row.get(0).map_err(|e| e.to_string())?,
+            message_index: message_index as usize,
+            score,
+            text: row.get(2).map_err(|e| e.to_string())?,
+        });
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+    }
+    Ok(())
+}
+
+fn search(
+    cache_dir: &Path,
+    store: &Path,
+    query: &str,
+    transcripts: &[(String, PathBuf)],
+    limit: usize,
+) -> Result<Vec<SemanticMatch>, String> {
+    let model = load_model(cache_dir)?;
+    let mut conn = open_store(store)?;
+    for (session_id, path) in transcripts {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        ensure_indexed(model, &mut conn, session_id, path, metadata.len())?;
+    }
+    let query_vector = embed_all(model, vec![query])?.pop().unwrap_or_default();
+    let mut matches = Vec::new();
+    for (session_id, _) in transcripts {
+        score_session(&conn, session_id, &query_vector, limit, &mut matches)?;
+    }
+    Ok(matches)
+}
+
+/// Rank transcript passages in a workspace's sessions by meaning rather
+/// than exact keywords, using a local embedding model (all-MiniLM-L6-v2 on
+/// ONNX Runtime) fetched on the first search. Passage vectors are kept in
+/// SQLite and read back one session at a time.
+/// `metadata` restricts the search to sessions carrying those fields.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn semantic_search(
+    query: String,
+    workspace_id: String,
+    limit: Option<usize>,
+    metadata: Option<BTreeMap<String, String>>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<SemanticMatch>, String> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let transcripts = {
+        let registry = state.registry.lock().await;
+        let workspace = registry
+            .workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        workspace
+            .visible_session_ids
+            .iter()
+            .filter(|id| {
+                registry
+                    .sessions
+                    .get(*id)
+                    .is_some_and(|session| matches_metadata(session, metadata.as_ref()))
+            })
+            .filter_map(|id| {
+                resolve_transcript_path(&registry, id)
+                    .ok()
+                    .map(|path| (id.clone(), path))
+            })
+            .collect::<Vec<_>>()
+    };
+    let cache_dir = model_dir(&state);
+    let store = store_path(&state);
+    let limit = limit.unwrap_or(DEFAULT_RESULT_LIMIT);
+    tokio::task::spawn_blocking(move || search(&cache_dir, &store, &query, &transcripts, limit))
+        .await
+        .map_err(|_| "semantic search failed".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cosine, open_store, passages_for, score_session, store_passages, vector_from_blob,
+        vector_to_blob, IndexedPassage,
+    };
+    use crate::utils::TempDir;
+
+    #[test]
+    fn cosine_ignores_vector_length() {
+        assert!((cosine(&[1.0, 0.0], &[3.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine(&[1.0, 0.0], &[0.0, 2.0]).abs() < 1e-6);
+        assert_eq!(cosine(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn splits_long_messages_into_passages() {
+        let long = vec!["x".repeat(800); 3].join("\n\n");
+        let passages = passages_for(&[("assistant".to_string(), long)]);
+        assert_eq!(passages.len(), 3);
+        assert!(passages.iter().all(|(_, index, _)| *index == 0));
+    }
+
+    #[test]
+    fn stored_passages_are_ranked_from_sqlite() {
+        let dir = TempDir::new("semantic");
+        let path = dir.join("index.sqlite3");
+        let mut conn = open_store(&path).unwrap();
+        let passage = |text: &str, vector: Vec<f32>| IndexedPassage {
+            role: "assistant".to_string(),
+            message_index: 0,
+            text: text.to_string(),
+            vector,
+        };
+        let passages = [
+            passage("close", vec![1.0, 0.1]),
+            passage("far", vec![0.1, 1.0]),
+            passage("closest", vec![1.0, 0.0]),
+        ];
+        store_passages(&mut conn, "s1", 10, &passages).unwrap();
+        // Re-indexing replaces the session's passages instead of adding.
+        store_passages(&mut conn, "s1", 20, &passages).unwrap();
+        assert_eq!(vector_from_blob(&vector_to_blob(&[0.5, -2.0])), [0.5, -2.0]);
+
+        let mut matches = Vec::new();
+        score_session(&conn, "s1", &[1.0, 0.0], 2, &mut matches).unwrap();
+        let texts: Vec<&str> = matches.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, ["closest", "close"]);
+    }
+}
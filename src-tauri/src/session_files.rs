@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_metrics::CommandTimer;
+use crate::registry::{now_millis, write_registry};
+use crate::state::AppState;
+use crate::types::{FileAction, SessionFile};
+
+/// Oldest-touched files are forgotten past this many per session.
+const MAX_FILES: usize = 500;
+
+/// File a tool call reads or changes, from its name and input.
+pub(crate) fn tool_file(tool_name: &str, input: &Value) -> Option<(String, FileAction)> {
+    let (key, action) = match tool_name {
+        "Read" => ("file_path", FileAction::Read),
+        "Edit" | "MultiEdit" => ("file_path", FileAction::Edit),
+        "Write" => ("file_path", FileAction::Write),
+        "NotebookEdit" => ("notebook_path", FileAction::Edit),
+        _ => return None,
+    };
+    let path = input.get(key)?.as_str()?.trim();
+    if path.is_empty() {
+        return None;
+    }
+    Some((path.to_string(), action))
+}
+
+fn display_path(path: &str, cwd: &str) -> String {
+    if cwd.is_empty() {
+        return path.to_string();
+    }
+    match Path::new(path).strip_prefix(cwd) {
+        Ok(relative) if !relative.as_os_str().is_empty() => {
+            crate::utils::normalize_git_path(&relative.to_string_lossy())
+        }
+        _ => path.to_string(),
+    }
+}
+
+fn touch(files: &mut Vec<SessionFile>, path: String, action: FileAction, now: u64) {
+    let index = match files.iter().position(|file| file.path == path) {
+        Some(index) => index,
+        None => {
+            if files.len() >= MAX_FILES {
+                if let Some(oldest) = files
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, file)| file.last_touched_at)
+                    .map(|(index, _)| index)
+                {
+                    files.remove(oldest);
+                }
+            }
+            files.push(SessionFile {
+                path,
+                reads: 0,
+                edits: 0,
+                last_action: action,
+                last_touched_at: now,
+            });
+            files.len() - 1
+        }
+    };
+    let file = &mut files[index];
+    match action {
+        FileAction::Read => file.reads += 1,
+        FileAction::Edit | FileAction::Write => file.edits += 1,
+    }
+    file.last_action = action;
+    file.last_touched_at = now;
+}
+
+/// Record the file a `tool/started` payload touches, if any.
+pub(crate) async fn record_tool_files(
+    app_handle: &AppHandle,
+    session_id: &str,
+    payload: &Value,
+) -> Result<(), String> {
+    let tool_name = payload
+        .get("toolName")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    let input = payload.get("input").unwrap_or(&Value::Null);
+    let Some((path, action)) = tool_file(tool_name, input) else {
+        return Ok(());
+    };
+    let state: State<'_, AppState> = app_handle.state();
+    let mut registry = state.registry.lock().await;
+    let Some(session) = registry.sessions.get_mut(session_id) else {
+        return Ok(());
+    };
+    let path = display_path(&path, &session.cwd);
+    touch(&mut session.files, path, action, now_millis());
+    write_registry(&state.registry_path, &registry)
+}
+
+/// Files the session's agent read or edited, most recently touched first.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_session_files(
+    session_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<SessionFile>, String> {
+    let registry = state.registry.lock().await;
+    let session = registry
+        .sessions
+        .get(&session_id)
+        .ok_or("session not found")?;
+    let mut files = session.files.clone();
+    files.sort_by(|a, b| b.last_touched_at.cmp(&a.last_touched_at));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{display_path, tool_file, touch};
+    use crate::types::FileAction;
+    use serde_json::json;
+
+    #[test]
+    fn counts_reads_and_edits_per_file() {
+        let mut files = Vec::new();
+        let (path, action) = tool_file("Read", &json!({ "file_path": "/repo/src/a.rs" })).unwrap();
+        touch(&mut files, display_path(&path, "/repo"), action, 1);
+        let (path, action) = tool_file("Edit", &json!({ "file_path": "/repo/src/a.rs" })).unwrap();
+        touch(&mut files, display_path(&path, "/repo"), action, 2);
+        touch(&mut files, "/etc/hosts".to_string(), FileAction::Read, 3);
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/a.rs");
+        assert_eq!((files[0].reads, files[0].edits), (1, 1));
+        assert_eq!(files[0].last_action, FileAction::Edit);
+        assert_eq!(files[1].path, "/etc/hosts");
+        assert!(tool_file("Bash", &json!({ "command": "ls" })).is_none());
+    }
+}
@@ -1,22 +1,113 @@
-use tauri::State;
+use std::path::Path;
 
+use tauri::{AppHandle, State};
+
+use crate::appearance::{emit_appearance, validate_appearance};
+use crate::command_metrics::CommandTimer;
+use crate::crash::set_capture_enabled;
+use crate::focus::refresh_focus;
+use crate::git_hooks::{hooks_wanted, sync_git_hooks};
+use crate::i18n::{parse_zone, set_locale, validate_locale};
+use crate::observer::set_observer_mode;
+use crate::proxy::{set_ca_bundle, set_proxy, validate_ca_bundle, validate_proxy_url};
 use crate::state::AppState;
 use crate::storage::write_settings;
 use crate::types::AppSettings;
 
+/// Prefix of errors from commands refused in observer mode, so the frontend
+/// can tell them apart from real failures.
+pub(crate) const OBSERVER_MODE_ERROR: &str = "OBSERVER_MODE";
+
+/// Refuse an action the app takes outside a command (Slack replies,
+/// workflow steps) or a command allows only in part, while observer mode
+/// is on. Whole commands are checked in `observer`.
+pub(crate) async fn ensure_not_observer(state: &AppState, action: &str) -> Result<(), String> {
+    if state.app_settings.lock().await.observer_mode {
+        return Err(format!(
+            "{OBSERVER_MODE_ERROR}: {action} is disabled in observer mode."
+        ));
+    }
+    Ok(())
+}
+
 #[tauri::command]
-pub(crate) async fn get_app_settings(state: State<'_, AppState>) -> Result<AppSettings, String> {
+#[specta::specta]
+pub(crate) async fn get_app_settings(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<AppSettings, String> {
     let settings = state.app_settings.lock().await;
     Ok(settings.clone())
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn update_app_settings(
-    settings: AppSettings,
+    mut settings: AppSettings,
+    app_handle: AppHandle,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<AppSettings, String> {
+    let (appearance_changed, git_hooks_changed) = {
+        // The do-not-disturb toggle has its own command; only quiet hours
+        // are edited here.
+        let current = state.app_settings.lock().await;
+        settings.do_not_disturb.enabled = current.do_not_disturb.enabled;
+        settings.do_not_disturb.until = current.do_not_disturb.until;
+        (
+            current.appearance != settings.appearance,
+            hooks_wanted(&current) != hooks_wanted(&settings),
+        )
+    };
+    validate_appearance(&settings.appearance)?;
+    if let Some(url) = settings
+        .proxy
+        .url
+        .as_deref()
+        .filter(|url| !url.trim().is_empty())
+    {
+        validate_proxy_url(url)?;
+    }
+    if let Some(path) = settings
+        .ca_bundle
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+    {
+        validate_ca_bundle(path)?;
+    }
+    if let Some(locale) = settings
+        .locale
+        .as_deref()
+        .filter(|locale| !locale.trim().is_empty())
+    {
+        validate_locale(locale.trim())?;
+    }
+    parse_zone(settings.time_zone.as_deref())?;
     write_settings(&state.settings_path, &settings)?;
-    let mut current = state.app_settings.lock().await;
-    *current = settings.clone();
+    if let Ok(mut telemetry) = state.telemetry.lock() {
+        telemetry.set_enabled(settings.telemetry.enabled);
+    }
+    if let Ok(mut recordings) = state.recordings.lock() {
+        recordings.set_enabled(settings.record_replays);
+    }
+    set_capture_enabled(settings.crash_reports.enabled);
+    set_observer_mode(settings.observer_mode);
+    set_proxy(&settings.proxy);
+    let data_dir = state.settings_path.parent().unwrap_or(Path::new("."));
+    set_ca_bundle(settings.ca_bundle.as_deref(), data_dir);
+    let locales_dir = data_dir.join("locales");
+    set_locale(
+        settings.locale.as_deref(),
+        settings.time_zone.as_deref(),
+        &locales_dir,
+    );
+    *state.app_settings.lock().await = settings.clone();
+    refresh_focus(&app_handle).await;
+    if appearance_changed {
+        emit_appearance(&app_handle, &settings.appearance);
+    }
+    if git_hooks_changed {
+        sync_git_hooks(&app_handle).await;
+    }
     Ok(settings)
 }
@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::claude::{build_session_env, check_claude_installation, ensure_bridge_running};
+use crate::codex::check_codex_installation;
+use crate::command_metrics::CommandTimer;
+use crate::registry::claude_project_dir;
+use crate::state::AppState;
+use crate::utils::canonical_path_key;
+use crate::workspaces::create_workspace_entry;
+
+/// Outcome of a single onboarding step, shaped for the wizard UI.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+pub(crate) struct SetupStepResult {
+    step: String,
+    ok: bool,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<Value>,
+}
+
+impl SetupStepResult {
+    fn new(step: &str, ok: bool, message: impl Into<String>, details: Option<Value>) -> Self {
+        Self {
+            step: step.to_string(),
+            ok,
+            message: message.into(),
+            details,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+pub(crate) struct ProjectCandidate {
+    name: String,
+    path: String,
+    #[serde(rename = "isGitRepo")]
+    is_git_repo: bool,
+    #[serde(rename = "hasClaudeHistory")]
+    has_claude_history: bool,
+}
+
+/// Folders people commonly keep checkouts in, relative to the home dir.
+const PROJECT_ROOTS: &[&str] = &[
+    "Developer",
+    "Projects",
+    "projects",
+    "Code",
+    "code",
+    "src",
+    "dev",
+    "git",
+    "repos",
+    "workspace",
+    "Documents/GitHub",
+];
+
+/// Git repos directly under `root`. `seen` holds the canonical keys of
+/// registered workspaces and of candidates already found, so a checkout
+/// reached through two roots (`code` and `Code` on a case-insensitive
+/// disk, or a symlink) is listed once.
+fn collect_candidates(root: &Path, seen: &mut HashSet<String>, out: &mut Vec<ProjectCandidate>) {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let is_git_repo = path.join(".git").exists();
+        if !is_git_repo {
+            continue;
+        }
+        let path_string = path.to_string_lossy().to_string();
+        if !seen.insert(canonical_path_key(&path_string)) {
+            continue;
+        }
+        let has_claude_history = claude_project_dir(&path_string)
+            .map(|dir| dir.is_dir())
+            .unwrap_or(false);
+        out.push(ProjectCandidate {
+            name,
+            path: path_string,
+            is_git_repo,
+            has_claude_history,
+        });
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn setup_detect_clis(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SetupStepResult, String> {
+    let (claude_bin, codex_bin) = {
+        let settings = state.app_settings.lock().await;
+        (settings.claude_code_bin.clone(), settings.codex_bin.clone())
+    };
+    let claude = check_claude_installation(claude_bin.as_deref()).await?;
+    let codex = check_codex_installation(codex_bin).await;
+    let claude_ok = claude.get("ok").and_then(|v| v.as_bool()).unwrap_or(false);
+    let message = if claude_ok {
+        "Node.js and Claude Code are installed."
+    } else {
+        "Node.js or Claude Code is missing."
+    };
+    Ok(SetupStepResult::new(
+        "detectClis",
+        claude_ok,
+        message,
+        Some(json!({
+            "claude": claude,
+            "codexVersion": codex.as_ref().ok().cloned().flatten(),
+            "codexDetails": codex.err(),
+        })),
+    ))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn setup_list_project_candidates(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<ProjectCandidate>, String> {
+    let registered: Vec<String> = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces.values().map(|entry| entry.path.clone()).collect()
+    };
+    let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
+    tokio::task::spawn_blocking(move || {
+        let mut seen: HashSet<String> = registered
+            .iter()
+            .map(|path| canonical_path_key(path))
+            .collect();
+        let mut candidates = Vec::new();
+        for root in PROJECT_ROOTS {
+            collect_candidates(&home.join(root), &mut seen, &mut candidates);
+        }
+        // Projects with existing Claude history first, then by name.
+        candidates.sort_by(|a, b| {
+            b.has_claude_history
+                .cmp(&a.has_claude_history)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        candidates
+    })
+    .await
+    .map_err(|_| "project discovery failed".to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn setup_check_claude_dir(
+    _timer: CommandTimer,
+) -> Result<SetupStepResult, String> {
+    let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
+    let claude_dir = home.join(".claude");
+    let projects_dir = claude_dir.join("projects");
+    let exists = claude_dir.is_dir();
+    let readable = std::fs::read_dir(&projects_dir).is_ok() || !projects_dir.exists();
+    let probe = claude_dir.join(".codex-monitor-write-test");
+    let writable = exists
+        && std::fs::write(&probe, b"ok")
+            .and_then(|_| std::fs::remove_file(&probe))
+            .is_ok();
+    let ok = exists && readable && writable;
+    let message = if !exists {
+        "~/.claude does not exist yet. Run `claude` once to sign in."
+    } else if !readable {
+        "~/.claude/projects is not readable. Check folder permissions."
+    } else if !writable {
+        "~/.claude is not writable. Check folder permissions."
+    } else {
+        "~/.claude is accessible."
+    };
+    Ok(SetupStepResult::new(
+        "claudeDir",
+        ok,
+        message,
+        Some(json!({
+            "path": claude_dir.to_string_lossy(),
+            "exists": exists,
+            "readable": readable,
+            "writable": writable,
+        })),
+    ))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn setup_create_workspace(
+    path: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SetupStepResult, String> {
+    match create_workspace_entry(&state, &path).await {
+        Ok(info) => Ok(SetupStepResult::new(
+            "createWorkspace",
+            true,
+            format!("Added {}.", info.name),
+            Some(serde_json::to_value(&info).map_err(|e| e.to_string())?),
+        )),
+        Err(err) => Ok(SetupStepResult::new("createWorkspace", false, err, None)),
+    }
+}
+
+/// Start and immediately close a bridge session to prove the whole chain
+/// (Node, bridge, Agent SDK, credentials) works before the first real prompt.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn setup_run_test_session(
+    workspace_id: String,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SetupStepResult, String> {
+    let cwd = {
+        let workspaces = state.workspaces.lock().await;
+        match workspaces.get(&workspace_id) {
+            Some(entry) => entry.path.clone(),
+            None => {
+                return Ok(SetupStepResult::new(
+                    "testSession",
+                    false,
+                    "Workspace not found.",
+                    None,
+                ))
+            }
+        }
+    };
+    let bridge = match ensure_bridge_running(&app_handle, &state).await {
+        Ok(bridge) => bridge,
+        Err(err) => {
+            return Ok(SetupStepResult::new(
+                "testSession",
+                false,
+                format!("Claude bridge failed to start: {err}"),
+                None,
+            ))
+        }
+    };
+    let (account_id, env) = match build_session_env(&state, &workspace_id).await {
+        Ok(result) => result,
+        Err(err) => return Ok(SetupStepResult::new("testSession", false, err, None)),
+    };
+    let claude_code_bin = {
+        let settings = state.app_settings.lock().await;
+        settings.claude_code_bin.clone()
+    };
+    let response = bridge
+        .send_request(
+            "session/start",
+            json!({
+                "workspaceId": workspace_id,
+                "cwd": cwd,
+                "permissionMode": "plan",
+                "claudeCodeBin": claude_code_bin,
+                "accountId": account_id,
+                "env": env,
+            }),
+        )
+        .await?;
+    if let Some(error) = response.get("error") {
+        return Ok(SetupStepResult::new(
+            "testSession",
+            false,
+            "Claude session failed to start.",
+            Some(error.clone()),
+        ));
+    }
+    let session_id = response
+        .get("result")
+        .and_then(|r| r.get("sessionId"))
+        .and_then(|id| id.as_str())
+        .unwrap_or("")
+        .to_string();
+    if !session_id.is_empty() {
+        let _ = bridge
+            .send_request("session/close", json!({ "sessionId": session_id }))
+            .await;
+    }
+    Ok(SetupStepResult::new(
+        "testSession",
+        true,
+        "Claude session started successfully.",
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collect_candidates;
+    use crate::utils::{canonical_path_key, TempDir};
+    use std::collections::HashSet;
+
+    #[cfg(unix)]
+    #[test]
+    fn lists_a_checkout_reached_through_two_roots_once() {
+        let base = TempDir::new("setup-roots");
+        let code = base.join("code");
+        std::fs::create_dir_all(code.join("app").join(".git")).unwrap();
+        std::fs::create_dir_all(code.join("registered").join(".git")).unwrap();
+        std::os::unix::fs::symlink(&code, base.join("src")).unwrap();
+
+        let registered = code.join("registered").to_string_lossy().to_string();
+        let mut seen = HashSet::from([canonical_path_key(&registered)]);
+        let mut candidates = Vec::new();
+        collect_candidates(&code, &mut seen, &mut candidates);
+        collect_candidates(&base.join("src"), &mut seen, &mut candidates);
+        let names: Vec<&str> = candidates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["app"]);
+    }
+}
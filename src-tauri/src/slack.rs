@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::time::sleep;
+
+use crate::claude::{ensure_bridge_running, ClaudeEvent};
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::events::{ClaudeEventPayload, ResultPayload, SlackReplyPayload};
+use crate::secrets::{delete_secret, read_secret, write_secret};
+use crate::settings::ensure_not_observer;
+use crate::state::AppState;
+use crate::turn_lanes::{acquire_turn_slot, release_turn_slot, Lane};
+use crate::utils::{curl_config_value, run_curl};
+
+const WEBHOOK_SECRET: &str = "slack-webhook-url";
+const BOT_TOKEN_SECRET: &str = "slack-bot-token";
+const REPLY_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Longest excerpt of a prompt or answer posted to Slack.
+const EXCERPT_CHARS: usize = 600;
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SlackMirror {
+    session_id: String,
+    workspace_id: String,
+    /// Thread the turns are posted to. With a bot token the first post
+    /// starts one when none is given.
+    thread_ts: Option<String>,
+    /// Inject thread replies into the session (bot token only).
+    allow_replies: bool,
+    #[serde(skip)]
+    last_reply_ts: Option<String>,
+    #[serde(skip)]
+    poll_id: String,
+}
+
+/// Mirrored sessions, keyed by session id.
+pub(crate) type SlackMirrors = HashMap<String, SlackMirror>;
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SlackStatus {
+    has_webhook: bool,
+    has_bot_token: bool,
+    channel: Option<String>,
+    mirrors: Vec<SlackMirror>,
+}
+
+/// Slack treats `&`, `<` and `>` as control characters in message text.
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn excerpt(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(EXCERPT_CHARS) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+fn quote(text: &str) -> String {
+    escape_text(&excerpt(text))
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn user_turn_text(message: &str) -> String {
+    format!(":bust_in_silhouette: *Prompt*\n{}", quote(message))
+}
+
+fn result_turn_text(result: &ResultPayload) -> String {
+    let status = if result.success {
+        ":white_check_mark: *Turn finished*"
+    } else {
+        ":x: *Turn failed*"
+    };
+    let mut text = format!(
+        "{status} in {:.0}s · {} turns · ${:.2}",
+        result.duration_ms as f64 / 1000.0,
+        result.num_turns,
+        result.total_cost_usd
+    );
+    if let Some(answer) = result
+        .result
+        .as_deref()
+        .filter(|answer| !answer.trim().is_empty())
+    {
+        text.push('\n');
+        text.push_str(&quote(answer));
+    }
+    if let Some(errors) = result.errors.as_ref().filter(|errors| !errors.is_empty()) {
+        text.push('\n');
+        text.push_str(&quote(&errors.join("\n")));
+    }
+    text
+}
+
+/// Slack timestamps are `seconds.micros`; compare them numerically.
+fn ts_after(ts: &str, other: &str) -> bool {
+    fn parts(ts: &str) -> (u64, u64) {
+        let (secs, micros) = ts.split_once('.').unwrap_or((ts, "0"));
+        (secs.parse().unwrap_or(0), micros.parse().unwrap_or(0))
+    }
+    parts(ts) > parts(other)
+}
+
+/// New human replies in a `conversations.replies` response, oldest first.
+fn new_replies<'a>(response: &'a Value, thread_ts: &str, after: Option<&str>) -> Vec<&'a Value> {
+    let Some(messages) = response.get("messages").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    messages
+        .iter()
+        .filter(|message| {
+            let Some(ts) = message.get("ts").and_then(Value::as_str) else {
+                return false;
+            };
+            ts != thread_ts
+                && message.get("bot_id").is_none()
+                && message.get("subtype").is_none()
+                && after.is_none_or(|after| ts_after(ts, after))
+        })
+        .collect()
+}
+
+/// Call Slack; the URL and token are passed to curl on stdin.
+async fn slack_request(
+    url: &str,
+    bot_token: Option<&str>,
+    body: Option<&Value>,
+) -> Result<String, String> {
+    let mut config = format!("url = {}\n", curl_config_value(url));
+    if let Some(token) = bot_token {
+        config.push_str(&format!(
+            "header = {}\n",
+            curl_config_value(&format!("Authorization: Bearer {token}"))
+        ));
+    }
+    let mut args = Vec::new();
+    if let Some(body) = body {
+        args.extend([
+            "-H".to_string(),
+            "Content-Type: application/json; charset=utf-8".to_string(),
+            "--data-binary".to_string(),
+            body.to_string(),
+        ]);
+    }
+    run_curl(&config, &args).await
+}
+
+fn slack_api_result(body: &str) -> Result<Value, String> {
+    let value: Value = serde_json::from_str(body).map_err(|_| "Unexpected Slack response")?;
+    if value.get("ok").and_then(Value::as_bool) == Some(true) {
+        Ok(value)
+    } else {
+        let error = value
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        Err(format!("Slack API error: {error}"))
+    }
+}
+
+/// Post to a mirror's thread, via the bot when one is configured and the
+/// webhook otherwise. Returns the posted message's ts when Slack reports it.
+async fn post_to_thread(
+    state: &AppState,
+    thread_ts: Option<&str>,
+    text: &str,
+) -> Result<Option<String>, String> {
+    let channel = state.app_settings.lock().await.slack_channel.clone();
+    if let (Some(token), Some(channel)) = (read_secret(BOT_TOKEN_SECRET).await?, channel) {
+        let mut body = json!({ "channel": channel, "text": text });
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = json!(ts);
+        }
+        let response = slack_request(
+            "https://slack.com/api/chat.postMessage",
+            Some(&token),
+            Some(&body),
+        )
+        .await?;
+        let value = slack_api_result(&response)?;
+        return Ok(value.get("ts").and_then(Value::as_str).map(str::to_string));
+    }
+    let webhook = read_secret(WEBHOOK_SECRET)
+        .await?
+        .ok_or("Slack is not configured")?;
+    let mut body = json!({ "text": text });
+    if let Some(ts) = thread_ts {
+        body["thread_ts"] = json!(ts);
+    }
+    let response = slack_request(&webhook, None, Some(&body)).await?;
+    if response.trim() != "ok" {
+        return Err(format!("Slack webhook error: {}", response.trim()));
+    }
+    Ok(None)
+}
+
+async fn post_for_session(app: &AppHandle, session_id: &str, text: String) {
+    let state = app.state::<AppState>();
+    let thread_ts = match state.slack_mirrors.lock().await.get(session_id) {
+        Some(mirror) => mirror.thread_ts.clone(),
+        None => return,
+    };
+    match post_to_thread(&state, thread_ts.as_deref(), &text).await {
+        Ok(Some(ts)) if thread_ts.is_none() => {
+            if let Some(mirror) = state.slack_mirrors.lock().await.get_mut(session_id) {
+                mirror.thread_ts = Some(ts.clone());
+                mirror.last_reply_ts = Some(ts);
+            }
+        }
+        Ok(_) => {}
+        Err(err) => log_line!("Slack mirror post failed: {err}"),
+    }
+}
+
+/// Mirror a finished turn. Only `result` events are posted; deltas and tool
+/// chatter stay in the app.
+pub(crate) fn mirror_event(app_handle: &AppHandle, event: &ClaudeEvent) {
+    let ClaudeEventPayload::Result(result) = &event.payload else {
+        return;
+    };
+    let text = result_turn_text(result);
+    let app = app_handle.clone();
+    let session_id = event.session_id.clone();
+    tauri::async_runtime::spawn(async move {
+        post_for_session(&app, &session_id, text).await;
+    });
+}
+
+/// Mirror a prompt the user sent from the app.
+pub(crate) fn mirror_user_message(app_handle: &AppHandle, session_id: &str, message: &str) {
+    let text = user_turn_text(message);
+    let app = app_handle.clone();
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        post_for_session(&app, &session_id, text).await;
+    });
+}
+
+async fn inject_reply(app: &AppHandle, mirror: &SlackMirror, reply: &Value) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    ensure_not_observer(&state, "Replying from Slack").await?;
+    let text = reply
+        .get("text")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .trim();
+    if text.is_empty() {
+        return Ok(());
+    }
+    let user = reply
+        .get("user")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let bridge = ensure_bridge_running(app, &state).await?;
+    acquire_turn_slot(&state, &mirror.session_id, Lane::Interactive).await?;
+    let sent = bridge
+        .send_request(
+            "message/send",
+            json!({
+                "sessionId": mirror.session_id,
+                "workspaceId": mirror.workspace_id,
+                "message": text,
+            }),
+        )
+        .await;
+    if let Err(err) = sent {
+        release_turn_slot(&state, &mirror.session_id).await;
+        return Err(err);
+    }
+    let _ = app.emit(
+        "claude-event",
+        ClaudeEvent {
+            event_type: "slack/reply".to_string(),
+            session_id: mirror.session_id.clone(),
+            workspace_id: mirror.workspace_id.clone(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            payload: ClaudeEventPayload::SlackReply(SlackReplyPayload {
+                user,
+                text: text.to_string(),
+            }),
+        },
+    );
+    Ok(())
+}
+
+/// Poll the mirror's thread for replies until the mirror is stopped or
+/// replaced (its `poll_id` changes).
+fn spawn_reply_poller(app: AppHandle, session_id: String, poll_id: String) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(REPLY_POLL_INTERVAL).await;
+            let state = app.state::<AppState>();
+            let mirror = match state.slack_mirrors.lock().await.get(&session_id) {
+                Some(mirror) if mirror.poll_id == poll_id => mirror.clone(),
+                _ => return,
+            };
+            let (Some(thread_ts), Some(channel)) = (
+                mirror.thread_ts.clone(),
+                state.app_settings.lock().await.slack_channel.clone(),
+            ) else {
+                continue;
+            };
+            let Ok(Some(token)) = read_secret(BOT_TOKEN_SECRET).await else {
+                continue;
+            };
+            let url = format!(
+                "https://slack.com/api/conversations.replies?channel={channel}&ts={thread_ts}"
+            );
+            let response = match slack_request(&url, Some(&token), None)
+                .await
+                .and_then(|body| slack_api_result(&body))
+            {
+                Ok(response) => response,
+                Err(err) => {
+                    log_line!("Slack reply poll failed: {err}");
+                    continue;
+                }
+            };
+            let replies = new_replies(&response, &thread_ts, mirror.last_reply_ts.as_deref());
+            let Some(newest) = replies
+                .last()
+                .and_then(|reply| reply.get("ts"))
+                .and_then(Value::as_str)
+            else {
+                continue;
+            };
+            if let Some(current) = state.slack_mirrors.lock().await.get_mut(&session_id) {
+                current.last_reply_ts = Some(newest.to_string());
+            }
+            for reply in replies {
+                if let Err(err) = inject_reply(&app, &mirror, reply).await {
+                    log_line!("Slack reply not delivered: {err}");
+                }
+            }
+        }
+    });
+}
+
+/// Store (or clear, with empty values) the incoming webhook URL and the bot
+/// token used for threads and replies.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_slack_credentials(
+    webhook_url: Option<String>,
+    bot_token: Option<String>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    for (secret, value) in [(WEBHOOK_SECRET, webhook_url), (BOT_TOKEN_SECRET, bot_token)] {
+        match value.as_deref().map(str::trim) {
+            Some("") => delete_secret(secret).await?,
+            Some(value) => write_secret(secret, value).await?,
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_slack_status(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SlackStatus, String> {
+    Ok(SlackStatus {
+        has_webhook: read_secret(WEBHOOK_SECRET).await?.is_some(),
+        has_bot_token: read_secret(BOT_TOKEN_SECRET).await?.is_some(),
+        channel: state.app_settings.lock().await.slack_channel.clone(),
+        mirrors: state.slack_mirrors.lock().await.values().cloned().collect(),
+    })
+}
+
+/// Start mirroring a session's turns to Slack.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn start_slack_mirror(
+    session_id: String,
+    workspace_id: String,
+    thread_ts: Option<String>,
+    allow_replies: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SlackMirror, String> {
+    let has_bot = read_secret(BOT_TOKEN_SECRET).await?.is_some()
+        && state.app_settings.lock().await.slack_channel.is_some();
+    if !has_bot && read_secret(WEBHOOK_SECRET).await?.is_none() {
+        return Err("Slack is not configured".to_string());
+    }
+    let allow_replies = allow_replies.unwrap_or(false);
+    if allow_replies && !has_bot {
+        return Err("Replying from Slack needs a bot token and channel".to_string());
+    }
+    let thread_ts = thread_ts.filter(|ts| !ts.trim().is_empty());
+    let mirror = SlackMirror {
+        session_id: session_id.clone(),
+        workspace_id,
+        last_reply_ts: thread_ts.clone(),
+        thread_ts,
+        allow_replies,
+        poll_id: uuid::Uuid::new_v4().to_string(),
+    };
+    state
+        .slack_mirrors
+        .lock()
+        .await
+        .insert(session_id.clone(), mirror.clone());
+    if allow_replies {
+        spawn_reply_poller(app, session_id, mirror.poll_id.clone());
+    }
+    Ok(mirror)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn stop_slack_mirror(
+    session_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    state.slack_mirrors.lock().await.remove(&session_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{excerpt, new_replies, quote, ts_after};
+
+    #[test]
+    fn formats_and_filters_thread_messages() {
+        assert_eq!(quote("a <b> & c\nnext"), "> a &lt;b&gt; &amp; c\n> next");
+        assert!(excerpt(&"x".repeat(700)).ends_with('…'));
+        assert!(ts_after("1700000000.000200", "1700000000.000100"));
+        assert!(!ts_after("1699999999.900000", "1700000000.000100"));
+
+        let response = json!({ "ok": true, "messages": [
+            { "ts": "100.000001", "text": "thread root" },
+            { "ts": "100.000002", "text": "old", "user": "U1" },
+            { "ts": "100.000003", "text": "posted by us", "bot_id": "B1" },
+            { "ts": "100.000004", "text": "joined", "subtype": "channel_join" },
+            { "ts": "100.000005", "text": "please also run tests", "user": "U2" },
+        ]});
+        let replies = new_replies(&response, "100.000001", Some("100.000002"));
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0]["text"], "please also run tests");
+    }
+}
@@ -0,0 +1,308 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::i18n::format_date;
+use crate::registry::now_millis;
+use crate::state::AppState;
+
+/// How deep `{{snippet:name}}` includes are followed.
+const MAX_INCLUDE_DEPTH: usize = 3;
+const MAX_NAME_LEN: usize = 64;
+
+/// A named block of context, e.g. an architecture overview or style rules,
+/// shared by every workspace.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Snippet {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<String>,
+    pub(crate) content: String,
+    pub(crate) created_at: u64,
+    pub(crate) updated_at: u64,
+}
+
+/// Saved snippets, kept in `snippets.json`.
+#[derive(Debug, Default)]
+pub(crate) struct Snippets {
+    entries: Vec<Snippet>,
+}
+
+impl Snippets {
+    pub(crate) fn load(path: &Path) -> Self {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|err| {
+                log_line!("Ignoring {}: {err}", path.display());
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { entries }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(&self.entries).map_err(|e| e.to_string())?;
+        std::fs::write(path, data).map_err(|e| e.to_string())
+    }
+
+    /// Every snippet, by name.
+    fn sorted(&self) -> Vec<Snippet> {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|snippet| snippet.name.to_lowercase());
+        entries
+    }
+
+    fn by_name(&self, name: &str) -> Option<&Snippet> {
+        self.entries
+            .iter()
+            .find(|snippet| snippet.name.eq_ignore_ascii_case(name))
+    }
+
+    /// A valid name not used by another snippet than `id`.
+    fn check_name(&self, name: &str, id: Option<&str>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty()
+            || name.len() > MAX_NAME_LEN
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(format!(
+                "Invalid snippet name: {name:?}. Use up to {MAX_NAME_LEN} letters, digits, '-', '_' or '.'."
+            ));
+        }
+        if self
+            .by_name(name)
+            .is_some_and(|snippet| Some(snippet.id.as_str()) != id)
+        {
+            return Err(format!("A snippet named {name} already exists"));
+        }
+        Ok(name.to_string())
+    }
+}
+
+/// Replace `{{snippet:name}}` with that snippet's content and `{{name}}`
+/// with a value from `vars`. Unknown placeholders are left as written.
+fn expand(content: &str, snippets: &Snippets, vars: &[(&str, String)], depth: usize) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + len].trim();
+        let end = start + len + 4;
+        out.push_str(&rest[..start]);
+        let include = key
+            .strip_prefix("snippet:")
+            .filter(|_| depth < MAX_INCLUDE_DEPTH)
+            .and_then(|name| snippets.by_name(name.trim()));
+        match (include, vars.iter().find(|(name, _)| *name == key)) {
+            (Some(snippet), _) => {
+                out.push_str(&expand(&snippet.content, snippets, vars, depth + 1))
+            }
+            (None, Some((_, value))) => out.push_str(value),
+            (None, None) => out.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn snippets_path(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("snippets.json")
+}
+
+/// Save and send the new list as `snippets-changed`.
+fn commit(app: &AppHandle, state: &AppState, snippets: &Snippets) -> Result<(), String> {
+    snippets.save(&snippets_path(state))?;
+    let _ = app.emit("snippets-changed", snippets.sorted());
+    Ok(())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_snippets(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<Snippet>, String> {
+    Ok(state.snippets.lock().await.sorted())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn create_snippet(
+    name: String,
+    content: String,
+    description: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Snippet, String> {
+    let mut snippets = state.snippets.lock().await;
+    let now = now_millis();
+    let snippet = Snippet {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: snippets.check_name(&name, None)?,
+        description: non_empty(description),
+        content,
+        created_at: now,
+        updated_at: now,
+    };
+    snippets.entries.push(snippet.clone());
+    commit(&app, &state, &snippets)?;
+    Ok(snippet)
+}
+
+/// Change a snippet's name, content or description; omitted fields are kept.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn update_snippet(
+    id: String,
+    name: Option<String>,
+    content: Option<String>,
+    description: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Snippet, String> {
+    let mut snippets = state.snippets.lock().await;
+    let name = name
+        .map(|name| snippets.check_name(&name, Some(&id)))
+        .transpose()?;
+    let snippet = snippets
+        .entries
+        .iter_mut()
+        .find(|snippet| snippet.id == id)
+        .ok_or_else(|| format!("Snippet {id} not found"))?;
+    if let Some(name) = name {
+        snippet.name = name;
+    }
+    if let Some(content) = content {
+        snippet.content = content;
+    }
+    if description.is_some() {
+        snippet.description = non_empty(description);
+    }
+    snippet.updated_at = now_millis();
+    let snippet = snippet.clone();
+    commit(&app, &state, &snippets)?;
+    Ok(snippet)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn delete_snippet(
+    id: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let mut snippets = state.snippets.lock().await;
+    let before = snippets.entries.len();
+    snippets.entries.retain(|snippet| snippet.id != id);
+    if snippets.entries.len() == before {
+        return Err(format!("Snippet {id} not found"));
+    }
+    commit(&app, &state, &snippets)
+}
+
+/// A snippet's text for the composer, with `{{snippet:name}}` includes and
+/// `{{workspace}}`, `{{workspacePath}}` and `{{date}}` filled in.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn insert_snippet(
+    name: String,
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<String, String> {
+    let workspace = match workspace_id {
+        Some(id) => state.workspaces.lock().await.get(&id).cloned(),
+        None => None,
+    };
+    let mut vars = vec![("date", format_date(chrono::Local::now().date_naive()))];
+    if let Some(workspace) = workspace {
+        vars.push(("workspace", workspace.name));
+        vars.push(("workspacePath", workspace.path));
+    }
+    let snippets = state.snippets.lock().await;
+    let snippet = snippets
+        .by_name(name.trim())
+        .ok_or_else(|| format!("No snippet named {name}"))?;
+    Ok(expand(&snippet.content, &snippets, &vars, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, Snippet, Snippets};
+
+    fn snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: None,
+            content: content.to_string(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn expands_includes_and_variables() {
+        let snippets = Snippets {
+            entries: vec![
+                snippet("style", "Use tabs. {{snippet:naming}}"),
+                snippet("naming", "snake_case in {{workspace}}."),
+                snippet("loop", "again {{snippet:loop}}"),
+            ],
+        };
+        let vars = [("workspace", "monitor".to_string())];
+        assert_eq!(
+            expand(
+                "Rules: {{snippet:STYLE}} {{unknown}} {{",
+                &snippets,
+                &vars,
+                0
+            ),
+            "Rules: Use tabs. snake_case in monitor. {{unknown}} {{"
+        );
+        assert_eq!(
+            expand("{{snippet:loop}}", &snippets, &vars, 0),
+            "again again again {{snippet:loop}}"
+        );
+    }
+
+    #[test]
+    fn names_are_unique_tokens() {
+        let snippets = Snippets {
+            entries: vec![snippet("arch", "")],
+        };
+        assert_eq!(
+            snippets.check_name(" api-rules ", None).unwrap(),
+            "api-rules"
+        );
+        assert!(snippets.check_name("two words", None).is_err());
+        assert!(snippets.check_name("ARCH", None).is_err());
+        assert!(snippets.check_name("ARCH", Some("arch")).is_ok());
+    }
+}
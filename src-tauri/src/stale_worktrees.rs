@@ -0,0 +1,442 @@
+use std::path::{Path, PathBuf};
+
+use git2::StatusOptions;
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::git::open_workspace_repo;
+use crate::registry::now_millis;
+use crate::repo_stats::dir_size;
+use crate::state::AppState;
+use crate::workspaces::{remove_worktree_entry, run_git_command};
+
+/// Days without activity after which a worktree counts as stale, when the
+/// caller doesn't say.
+const DEFAULT_INACTIVE_DAYS: u64 = 14;
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+/// Prefix of a cleanup confirmation target that also deletes merged branches.
+const DELETE_BRANCHES_PREFIX: &str = "branches:";
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StaleReason {
+    /// Every commit on the branch is already on the parent branch.
+    Merged,
+    /// No session activity or commits for the threshold.
+    Inactive,
+    /// The checkout is gone from disk.
+    Missing,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StaleWorktree {
+    pub(crate) worktree_id: String,
+    pub(crate) name: String,
+    pub(crate) parent_id: String,
+    pub(crate) branch: String,
+    pub(crate) path: String,
+    pub(crate) reasons: Vec<StaleReason>,
+    /// The branch the worktree's branch is merged into.
+    pub(crate) merged_into: Option<String>,
+    /// Latest session activity or commit, in ms.
+    pub(crate) last_activity: Option<u64>,
+    pub(crate) sessions: usize,
+    /// Changed or untracked files that removing the worktree would discard.
+    pub(crate) uncommitted: usize,
+    pub(crate) disk_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StaleWorktreeReport {
+    pub(crate) inactive_days: u64,
+    pub(crate) worktrees: Vec<StaleWorktree>,
+    pub(crate) total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeCleanup {
+    pub(crate) worktree_id: String,
+    pub(crate) name: String,
+    pub(crate) removed: bool,
+    pub(crate) branch_deleted: bool,
+    /// Why the worktree or its branch was kept.
+    pub(crate) detail: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct BranchState {
+    merged: bool,
+    /// Commit time of the branch tip, in ms.
+    head_time: Option<u64>,
+    uncommitted: usize,
+}
+
+/// Whether `branch` in the worktree at `path` is merged into
+/// `parent_branch`, when it was last committed to, and how much
+/// uncommitted work the worktree has.
+fn branch_state(path: &str, branch: &str, parent_branch: &str) -> Result<BranchState, String> {
+    let (repo, _) = open_workspace_repo(path)?;
+    let tip = repo
+        .find_branch(branch, git2::BranchType::Local)
+        .and_then(|branch| branch.get().peel_to_commit())
+        .map_err(|_| format!("Branch {branch} not found"))?;
+    let parent = repo
+        .find_branch(parent_branch, git2::BranchType::Local)
+        .and_then(|branch| branch.get().peel_to_commit())
+        .map_err(|_| format!("Branch {parent_branch} not found"))?;
+    // A branch still at the parent's tip was just created, not merged.
+    let merged = tip.id() != parent.id()
+        && repo
+            .graph_descendant_of(parent.id(), tip.id())
+            .map_err(|e| e.to_string())?;
+    let head_time = u64::try_from(tip.time().seconds())
+        .ok()
+        .map(|seconds| seconds * 1000);
+
+    let mut status_options = StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
+    let uncommitted = repo
+        .statuses(Some(&mut status_options))
+        .map_err(|e| e.to_string())?
+        .len();
+    Ok(BranchState {
+        merged,
+        head_time,
+        uncommitted,
+    })
+}
+
+/// Why a worktree is stale; empty when it isn't.
+fn stale_reasons(
+    missing: bool,
+    merged: bool,
+    last_activity: Option<u64>,
+    now: u64,
+    inactive_days: u64,
+) -> Vec<StaleReason> {
+    let mut reasons = Vec::new();
+    if missing {
+        reasons.push(StaleReason::Missing);
+    }
+    if merged {
+        reasons.push(StaleReason::Merged);
+    }
+    let inactive = last_activity
+        .is_some_and(|last| now.saturating_sub(last) >= inactive_days.saturating_mul(DAY_MS));
+    if inactive {
+        reasons.push(StaleReason::Inactive);
+    }
+    reasons
+}
+
+/// The confirmation target for cleaning up `worktree_ids`. It carries the
+/// branch choice so the prompt can say whether branches go too.
+pub(crate) fn cleanup_target(worktree_ids: &[String], delete_branches: bool) -> String {
+    let prefix = if delete_branches {
+        DELETE_BRANCHES_PREFIX
+    } else {
+        ""
+    };
+    format!("{prefix}{}", worktree_ids.join(","))
+}
+
+/// The worktree ids and branch choice in a `cleanup_target`.
+pub(crate) fn parse_cleanup_target(target: &str) -> (Vec<String>, bool) {
+    let (ids, delete_branches) = match target.strip_prefix(DELETE_BRANCHES_PREFIX) {
+        Some(ids) => (ids, true),
+        None => (target, false),
+    };
+    let ids = ids
+        .split(',')
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect();
+    (ids, delete_branches)
+}
+
+/// Worktrees that are probably done with: their branch is merged into the
+/// branch their project has checked out, they have seen no session
+/// activity or commits for `inactive_days` (default 14), or their checkout
+/// is gone. Worktrees with a turn running are left out. Largest first.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_stale_worktrees(
+    inactive_days: Option<u64>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<StaleWorktreeReport, String> {
+    let inactive_days = inactive_days.unwrap_or(DEFAULT_INACTIVE_DAYS);
+    let pairs: Vec<_> = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .values()
+            .filter(|entry| entry.kind.is_worktree())
+            .filter_map(|entry| {
+                let parent = workspaces.get(entry.parent_id.as_ref()?)?;
+                Some((entry.clone(), parent.clone()))
+            })
+            .collect()
+    };
+    let now = now_millis();
+    let mut worktrees = Vec::new();
+    for (entry, parent) in pairs {
+        let (sessions, session_activity, running) = {
+            let registry = state.registry.lock().await;
+            let turns = state.turns.lock().await;
+            let ids = registry
+                .workspaces
+                .get(&entry.id)
+                .map(|workspace| workspace.visible_session_ids.as_slice())
+                .unwrap_or_default();
+            let activity = ids
+                .iter()
+                .filter_map(|id| registry.sessions.get(id))
+                .map(|session| session.last_activity)
+                .max();
+            let running = ids.iter().any(|id| turns.is_running(id));
+            (ids.len(), activity, running)
+        };
+        if running {
+            continue;
+        }
+        let branch = entry
+            .worktree
+            .as_ref()
+            .map(|info| info.branch.clone())
+            .unwrap_or_default();
+
+        let missing = !Path::new(&entry.path).exists();
+        let (branch_info, parent_branch, disk_bytes) = if missing {
+            (BranchState::default(), None, 0)
+        } else {
+            let _parent_git = state.git_locks.read(&parent.id).await;
+            let _git = state.git_locks.read(&entry.id).await;
+            let parent_path = parent.path.clone();
+            let path = entry.path.clone();
+            let branch = branch.clone();
+            tokio::task::spawn_blocking(move || {
+                let parent_branch = open_workspace_repo(&parent_path)
+                    .ok()
+                    .and_then(|(repo, _)| {
+                        let head = repo.head().ok()?;
+                        head.shorthand()
+                            .filter(|_| head.is_branch())
+                            .map(str::to_string)
+                    });
+                let branch_state = parent_branch
+                    .as_deref()
+                    .and_then(|parent_branch| branch_state(&path, &branch, parent_branch).ok())
+                    .unwrap_or_default();
+                let disk_bytes = dir_size(Path::new(&path));
+                (branch_state, parent_branch, disk_bytes)
+            })
+            .await
+            .map_err(|_| "worktree scan failed".to_string())?
+        };
+
+        let last_activity = session_activity.max(branch_info.head_time);
+        let reasons = stale_reasons(
+            missing,
+            branch_info.merged,
+            last_activity,
+            now,
+            inactive_days,
+        );
+        if reasons.is_empty() {
+            continue;
+        }
+        worktrees.push(StaleWorktree {
+            worktree_id: entry.id,
+            name: entry.name,
+            parent_id: parent.id,
+            branch,
+            path: entry.path,
+            reasons,
+            merged_into: parent_branch.filter(|_| branch_info.merged),
+            last_activity,
+            sessions,
+            uncommitted: branch_info.uncommitted,
+            disk_bytes,
+        });
+    }
+    worktrees.sort_by(|a, b| b.disk_bytes.cmp(&a.disk_bytes));
+    let total_bytes = worktrees.iter().map(|worktree| worktree.disk_bytes).sum();
+    Ok(StaleWorktreeReport {
+        inactive_days,
+        worktrees,
+        total_bytes,
+    })
+}
+
+/// Remove the worktrees the user picked from `list_stale_worktrees`, and
+/// with `delete_branches` their branches too. Branches are deleted with
+/// `git branch -d`, so one with commits not on its upstream or the
+/// project's branch is kept. Each worktree is reported on; one failing
+/// doesn't stop the rest.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn clean_up_worktrees(
+    worktree_ids: Vec<String>,
+    delete_branches: bool,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<WorktreeCleanup>, String> {
+    let target = cleanup_target(&worktree_ids, delete_branches);
+    consume_confirmation(
+        &state,
+        confirmation.as_deref(),
+        "clean_up_worktrees",
+        &target,
+    )
+    .await?;
+    let mut results = Vec::new();
+    for id in worktree_ids {
+        let name = state
+            .workspaces
+            .lock()
+            .await
+            .get(&id)
+            .map(|entry| entry.name.clone())
+            .unwrap_or_else(|| id.clone());
+        let mut result = WorktreeCleanup {
+            worktree_id: id.clone(),
+            name,
+            removed: false,
+            branch_deleted: false,
+            detail: None,
+        };
+        let running = {
+            let registry = state.registry.lock().await;
+            let turns = state.turns.lock().await;
+            registry.workspaces.get(&id).is_some_and(|workspace| {
+                workspace
+                    .visible_session_ids
+                    .iter()
+                    .any(|session_id| turns.is_running(session_id))
+            })
+        };
+        if running {
+            result.detail = Some("A turn is running in this worktree.".to_string());
+            results.push(result);
+            continue;
+        }
+        let (entry, parent) = match remove_worktree_entry(&state, &id).await {
+            Ok(removed) => removed,
+            Err(err) => {
+                result.detail = Some(err);
+                results.push(result);
+                continue;
+            }
+        };
+        result.removed = true;
+        let branch = entry
+            .worktree
+            .map(|info| info.branch)
+            .filter(|branch| delete_branches && !branch.is_empty());
+        if let Some(branch) = branch {
+            let _git = state.git_locks.write(&parent.id).await;
+            match run_git_command(&PathBuf::from(&parent.path), &["branch", "-d", &branch]).await {
+                Ok(_) => result.branch_deleted = true,
+                Err(err) => result.detail = Some(format!("Kept branch {branch}: {err}")),
+            }
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use git2::{Repository, Signature};
+
+    use super::{
+        branch_state, cleanup_target, parse_cleanup_target, stale_reasons, StaleReason, DAY_MS,
+    };
+    use crate::utils::TempDir;
+
+    fn commit(repo: &Repository, root: &Path, file: &str, content: &str, message: &str) {
+        std::fs::write(root.join(file), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn finds_merged_and_unmerged_branches() {
+        let root = TempDir::new("stale");
+        let repo = Repository::init(&root).unwrap();
+        commit(&repo, &root, "app.txt", "one\n", "Initial");
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("fresh", &base, false).unwrap();
+        repo.branch("done", &base, false).unwrap();
+        repo.branch("work", &base, false).unwrap();
+        let path = root.to_str().unwrap();
+
+        // Not merged while it is still where the parent branch is.
+        let fresh = branch_state(path, "fresh", &main).unwrap();
+        assert!(!fresh.merged);
+
+        commit(&repo, &root, "app.txt", "two\n", "Main change");
+        repo.set_head("refs/heads/work").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit(&repo, &root, "work.txt", "wip\n", "Work change");
+        std::fs::write(root.join("notes.txt"), "draft\n").unwrap();
+
+        let done = branch_state(path, "done", &main).unwrap();
+        assert!(done.merged);
+        let work = branch_state(path, "work", &main).unwrap();
+        assert!(!work.merged);
+        assert_eq!(work.uncommitted, 1);
+        assert!(work.head_time.is_some());
+        assert!(branch_state(path, "missing", &main).is_err());
+    }
+
+    #[test]
+    fn classifies_stale_worktrees() {
+        let now = 100 * DAY_MS;
+        assert!(stale_reasons(false, false, Some(now - DAY_MS), now, 14).is_empty());
+        assert!(stale_reasons(false, false, None, now, 14).is_empty());
+        assert_eq!(
+            stale_reasons(false, true, Some(now - 20 * DAY_MS), now, 14),
+            [StaleReason::Merged, StaleReason::Inactive]
+        );
+        assert_eq!(
+            stale_reasons(true, false, None, now, 14),
+            [StaleReason::Missing]
+        );
+
+        let ids = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(cleanup_target(&ids, true), "branches:a,b");
+        assert_eq!(parse_cleanup_target("branches:a,b"), (ids.clone(), true));
+        assert_eq!(
+            parse_cleanup_target(&cleanup_target(&ids, false)),
+            (ids, false)
+        );
+        assert_eq!(parse_cleanup_target(""), (Vec::new(), false));
+    }
+}
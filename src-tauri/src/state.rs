@@ -1,27 +1,105 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use tauri::{AppHandle, Manager};
-use tokio::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{Mutex, Notify};
 
+use crate::auto_approve::AutoApprovals;
 use crate::claude::{ClaudeBridge, ClaudeSessionInfo};
-use crate::registry::read_registry;
+use crate::command_metrics::CommandMetrics;
+use crate::confirmations::ConfirmationGrants;
+use crate::crash::install_panic_hook;
+use crate::focus::FocusQueue;
+use crate::git_hooks::sync_git_hooks;
+use crate::git_locks::GitLocks;
+use crate::glossary::Glossaries;
+use crate::guardrails::AutonomousTurns;
+use crate::heartbeat::BridgeHealth;
+use crate::i18n::set_locale;
+use crate::lan_share::LanShares;
+use crate::message_queue::MessageQueues;
+use crate::observer::set_observer_mode;
+use crate::prompt_history::PromptHistory;
+use crate::proxy::{set_ca_bundle, set_proxy};
+use crate::registry::{read_registry, ScanCache};
+use crate::replay::Recordings;
+use crate::repo_stats::RepoStatsCache;
+use crate::slack::SlackMirrors;
+use crate::snippets::Snippets;
 use crate::storage::{read_settings, read_workspaces};
+use crate::telemetry::Telemetry;
+use crate::time_tracking::AttendedClock;
+use crate::turn_lanes::TurnLanes;
+use crate::turns::TurnTracker;
 use crate::types::{AppSettings, ThreadRegistry, WorkspaceEntry};
+use crate::workflows::WorkflowRuns;
 
 pub(crate) struct AppState {
-    pub(crate) workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
+    /// Filled in by `spawn_loader`, which holds the lock until it is read
+    pub(crate) workspaces: Arc<Mutex<HashMap<String, WorkspaceEntry>>>,
     pub(crate) sessions: Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>,
     /// Global Claude bridge process (single instance for all workspaces)
     pub(crate) claude_bridge: Mutex<Option<Arc<ClaudeBridge>>>,
+    /// Bridge restarts since a bridge last stayed up, for the backoff
+    pub(crate) bridge_restarts: Mutex<u32>,
     /// Map of session_id -> ClaudeSessionInfo for tracking active Claude sessions
     pub(crate) claude_sessions: Mutex<HashMap<String, ClaudeSessionInfo>>,
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
     pub(crate) registry_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
-    pub(crate) registry: Mutex<ThreadRegistry>,
+    /// Filled in by `spawn_loader`, like `workspaces`
+    pub(crate) registry: Arc<Mutex<ThreadRegistry>>,
+    /// Set once workspaces and the registry are loaded
+    pub(crate) ready: AtomicBool,
+    /// Transcript extraction results keyed by path, reused while unchanged
+    pub(crate) scan_cache: Mutex<ScanCache>,
+    /// Wakes the background indexer before its next poll
+    pub(crate) indexer_wake: Notify,
+    /// Native-dialog approvals awaiting the destructive command they unlock
+    pub(crate) confirmations: Mutex<ConfirmationGrants>,
+    /// LAN shares; a std mutex because events are broadcast from sync code
+    pub(crate) lan_shares: std::sync::Mutex<LanShares>,
+    /// Sessions whose turns are posted to Slack
+    pub(crate) slack_mirrors: Mutex<SlackMirrors>,
+    /// Metrics and spans awaiting OTLP export; recorded from sync code
+    pub(crate) telemetry: std::sync::Mutex<Telemetry>,
+    /// Orders git reads and writes within each workspace's repo
+    pub(crate) git_locks: GitLocks,
+    /// Repository overview figures per workspace, reused until HEAD moves
+    pub(crate) repo_stats: Mutex<RepoStatsCache>,
+    /// Command latency histograms reported by the frontend
+    pub(crate) command_metrics: std::sync::Mutex<CommandMetrics>,
+    /// Partial output of running turns, saved if they are interrupted
+    pub(crate) turns: Mutex<TurnTracker>,
+    /// Do-not-disturb state and held permission requests; a std mutex
+    /// because events are dispatched from sync code
+    pub(crate) focus: std::sync::Mutex<FocusQueue>,
+    /// Timed auto-approve windows by session id
+    pub(crate) auto_approvals: Mutex<AutoApprovals>,
+    /// User messages waiting for their session's turn to end
+    pub(crate) message_queues: Mutex<MessageQueues>,
+    /// Running workflows and the turns their prompt steps wait on
+    pub(crate) workflows: Mutex<WorkflowRuns>,
+    /// Automated turns since each session's last user message
+    pub(crate) autonomous_turns: Mutex<AutonomousTurns>,
+    /// The session the user is looking at, for attended time
+    pub(crate) attended: Mutex<AttendedClock>,
+    /// Event streams for replay export; a std mutex because events are
+    /// recorded from sync code
+    pub(crate) recordings: std::sync::Mutex<Recordings>,
+    /// Heartbeat result for the running bridge
+    pub(crate) bridge_health: Mutex<BridgeHealth>,
+    /// Turn slots under `maxConcurrentTurns`, by priority lane
+    pub(crate) turn_lanes: Mutex<TurnLanes>,
+    /// Prompts the user has sent, for search and reuse
+    pub(crate) prompt_history: Mutex<PromptHistory>,
+    /// Named context blocks shared across workspaces
+    pub(crate) snippets: Mutex<Snippets>,
+    /// Domain terms per workspace, appended to new sessions when enabled
+    pub(crate) glossaries: Mutex<Glossaries>,
 }
 
 impl AppState {
@@ -33,19 +111,93 @@ impl AppState {
         let storage_path = data_dir.join("workspaces.json");
         let settings_path = data_dir.join("settings.json");
         let registry_path = data_dir.join("threads.json");
-        let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
-        let registry = read_registry(&registry_path).unwrap_or_default();
+        let mut telemetry = Telemetry::default();
+        telemetry.set_enabled(app_settings.telemetry.enabled);
+        let mut recordings = Recordings::default();
+        recordings.set_enabled(app_settings.record_replays);
+        install_panic_hook(
+            data_dir.join("crash-reports"),
+            app_settings.crash_reports.enabled,
+        );
+        set_observer_mode(app_settings.observer_mode);
+        set_proxy(&app_settings.proxy);
+        set_ca_bundle(app_settings.ca_bundle.as_deref(), &data_dir);
+        set_locale(
+            app_settings.locale.as_deref(),
+            app_settings.time_zone.as_deref(),
+            &data_dir.join("locales"),
+        );
         Self {
-            workspaces: Mutex::new(workspaces),
+            workspaces: Arc::new(Mutex::new(HashMap::new())),
             sessions: Mutex::new(HashMap::new()),
             claude_bridge: Mutex::new(None),
+            bridge_restarts: Mutex::new(0),
             claude_sessions: Mutex::new(HashMap::new()),
             storage_path,
             settings_path,
             registry_path,
             app_settings: Mutex::new(app_settings),
-            registry: Mutex::new(registry),
+            registry: Arc::new(Mutex::new(ThreadRegistry::default())),
+            ready: AtomicBool::new(false),
+            scan_cache: Mutex::new(HashMap::new()),
+            indexer_wake: Notify::new(),
+            confirmations: Mutex::new(HashMap::new()),
+            lan_shares: std::sync::Mutex::new(LanShares::default()),
+            slack_mirrors: Mutex::new(HashMap::new()),
+            telemetry: std::sync::Mutex::new(telemetry),
+            git_locks: GitLocks::default(),
+            repo_stats: Mutex::new(HashMap::new()),
+            command_metrics: std::sync::Mutex::new(CommandMetrics::default()),
+            turns: Mutex::new(TurnTracker::default()),
+            focus: std::sync::Mutex::new(FocusQueue::default()),
+            auto_approvals: Mutex::new(HashMap::new()),
+            message_queues: Mutex::new(MessageQueues::default()),
+            workflows: Mutex::new(WorkflowRuns::default()),
+            autonomous_turns: Mutex::new(AutonomousTurns::default()),
+            attended: Mutex::new(AttendedClock::default()),
+            recordings: std::sync::Mutex::new(recordings),
+            bridge_health: Mutex::new(BridgeHealth::default()),
+            turn_lanes: Mutex::new(TurnLanes::default()),
+            prompt_history: Mutex::new(PromptHistory::load(
+                &data_dir.join("prompt-history.json"),
+            )),
+            snippets: Mutex::new(Snippets::load(&data_dir.join("snippets.json"))),
+            glossaries: Mutex::new(Glossaries::load(&data_dir.join("glossary.json"))),
         }
     }
 }
+
+/// Read workspaces and the thread registry off the setup path so the window
+/// shows before a large registry is parsed. Their locks are taken here,
+/// before any command can run, so callers wait for the data rather than see
+/// an empty map; `list_workspaces` answers with a placeholder instead.
+/// Emits `state/ready` once both are in place.
+pub(crate) fn spawn_loader(app: AppHandle) {
+    let state = app.state::<AppState>();
+    let (Ok(mut workspaces), Ok(mut registry)) = (
+        state.workspaces.clone().try_lock_owned(),
+        state.registry.clone().try_lock_owned(),
+    ) else {
+        return;
+    };
+    let storage_path = state.storage_path.clone();
+    let registry_path = state.registry_path.clone();
+    tauri::async_runtime::spawn(async move {
+        let loaded = tokio::task::spawn_blocking(move || {
+            (
+                read_workspaces(&storage_path).unwrap_or_default(),
+                read_registry(&registry_path).unwrap_or_default(),
+            )
+        })
+        .await;
+        if let Ok((loaded_workspaces, loaded_registry)) = loaded {
+            *workspaces = loaded_workspaces;
+            *registry = loaded_registry;
+        }
+        drop((workspaces, registry));
+        app.state::<AppState>().ready.store(true, Ordering::Release);
+        let _ = app.emit("state/ready", ());
+        sync_git_hooks(&app).await;
+    });
+}
@@ -0,0 +1,379 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tokio::time::sleep;
+
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::crash::log_line;
+use crate::power::schedulers_paused;
+use crate::registry::now_millis;
+use crate::state::AppState;
+use crate::workspaces::run_git_command;
+
+/// Never pull more often than this, whatever the setting says.
+const MIN_SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// How often the sync loop rechecks settings when no repo is configured.
+const IDLE_POLL: Duration = Duration::from_secs(60);
+
+/// Tool rules from the team repo's `permissions.json`, passed to every
+/// session. Deny rules apply as soon as they are synced; allow rules only
+/// once the user has accepted them (see `AcceptedTeamPermissions`). A
+/// workspace's own `.claude/settings*.json` still applies on top.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamPermissions {
+    #[serde(default)]
+    pub(crate) allow: Vec<String>,
+    #[serde(default)]
+    pub(crate) deny: Vec<String>,
+}
+
+/// Allow rules the user reviewed and accepted, pinned to the commit they
+/// were read from. Later pushes to the team repo don't change them until
+/// they are accepted again.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AcceptedTeamPermissions {
+    pub(crate) repo: String,
+    pub(crate) commit: String,
+    pub(crate) allow: Vec<String>,
+}
+
+/// How the synced allow rules differ from the accepted ones.
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingTeamPermissions {
+    /// Full hash of the mirror's HEAD; pass it to `accept_team_permissions`.
+    pub(crate) commit: String,
+    pub(crate) added: Vec<String>,
+    pub(crate) removed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamPromptsStatus {
+    repo: String,
+    commit: String,
+    synced_at: u64,
+    prompts: usize,
+    /// As synced; allow rules are in effect only once accepted.
+    permissions: TeamPermissions,
+    accepted_commit: Option<String>,
+    pending_permissions: Option<PendingTeamPermissions>,
+}
+
+/// Read-only mirror of the team repo under the app data dir.
+pub(crate) fn team_repo_dir(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("team-prompts")
+}
+
+/// Prompt directories in the mirror: `prompts/` and `templates/`, or the
+/// repo root when neither exists.
+pub(crate) fn team_prompt_dirs(repo_dir: &Path) -> Vec<PathBuf> {
+    let dirs: Vec<PathBuf> = ["prompts", "templates"]
+        .iter()
+        .map(|name| repo_dir.join(name))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    if dirs.is_empty() && repo_dir.is_dir() {
+        return vec![repo_dir.to_path_buf()];
+    }
+    dirs
+}
+
+fn parse_permissions(content: &str) -> Result<TeamPermissions, String> {
+    let permissions: TeamPermissions =
+        serde_json::from_str(content).map_err(|e| format!("Invalid permissions.json: {e}"))?;
+    let clean = |rules: Vec<String>| {
+        let mut out: Vec<String> = Vec::new();
+        for rule in rules {
+            let rule = rule.trim().to_string();
+            if !rule.is_empty() && !out.contains(&rule) {
+                out.push(rule);
+            }
+        }
+        out
+    };
+    Ok(TeamPermissions {
+        allow: clean(permissions.allow),
+        deny: clean(permissions.deny),
+    })
+}
+
+fn accepted_path(state: &AppState) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("team-permissions.json")
+}
+
+/// Accepted allow rules for `repo`; none if they were accepted for another
+/// repository.
+fn read_accepted(state: &AppState, repo: &str) -> Option<AcceptedTeamPermissions> {
+    let content = std::fs::read_to_string(accepted_path(state)).ok()?;
+    serde_json::from_str::<AcceptedTeamPermissions>(&content)
+        .ok()
+        .filter(|accepted| accepted.repo == repo)
+}
+
+fn write_accepted(state: &AppState, accepted: &AcceptedTeamPermissions) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(accepted).map_err(|e| e.to_string())?;
+    std::fs::write(accepted_path(state), data).map_err(|e| e.to_string())
+}
+
+fn configured_repo(repo: Option<String>) -> Option<String> {
+    repo.filter(|url| !url.trim().is_empty())
+}
+
+/// Team tool rules: the mirror's deny rules and the accepted allow rules.
+/// None when no repo is configured.
+pub(crate) async fn team_permissions(state: &AppState) -> TeamPermissions {
+    let repo = state.app_settings.lock().await.team_prompts_repo.clone();
+    let Some(repo) = configured_repo(repo) else {
+        return TeamPermissions::default();
+    };
+    let path = team_repo_dir(state).join("permissions.json");
+    let synced = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| parse_permissions(&content).ok())
+        .unwrap_or_default();
+    TeamPermissions {
+        allow: read_accepted(state, &repo)
+            .map(|accepted| accepted.allow)
+            .unwrap_or_default(),
+        deny: synced.deny,
+    }
+}
+
+/// Rules in `synced` but not `accepted`, and the other way round.
+fn permission_changes(accepted: &[String], synced: &[String]) -> (Vec<String>, Vec<String>) {
+    let added = synced
+        .iter()
+        .filter(|rule| !accepted.contains(rule))
+        .cloned()
+        .collect();
+    let removed = accepted
+        .iter()
+        .filter(|rule| !synced.contains(rule))
+        .cloned()
+        .collect();
+    (added, removed)
+}
+
+/// `permissions.json` as committed at `commit`, ignoring the working tree.
+async fn permissions_at(dir: &Path, commit: &str) -> Result<TeamPermissions, String> {
+    let spec = format!("{commit}:permissions.json");
+    match run_git_command(&dir.to_path_buf(), &["show", &spec]).await {
+        Ok(content) => parse_permissions(&content),
+        Err(_) => Ok(TeamPermissions::default()),
+    }
+}
+
+/// The synced allow rules awaiting review, if they differ from the
+/// accepted ones. `commit` must be the mirror's HEAD.
+pub(crate) async fn pending_permissions(
+    state: &AppState,
+    commit: Option<&str>,
+) -> Result<Option<PendingTeamPermissions>, String> {
+    let repo = state.app_settings.lock().await.team_prompts_repo.clone();
+    let repo = configured_repo(repo).ok_or("No team prompts repository configured")?;
+    let dir = team_repo_dir(state);
+    let head = run_git_command(&dir, &["rev-parse", "HEAD"]).await?;
+    if commit.is_some_and(|commit| commit != head) {
+        return Err("The team repository changed; review its permissions again.".to_string());
+    }
+    let synced = permissions_at(&dir, &head).await?;
+    let accepted = read_accepted(state, &repo)
+        .map(|accepted| accepted.allow)
+        .unwrap_or_default();
+    let (added, removed) = permission_changes(&accepted, &synced.allow);
+    if added.is_empty() && removed.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(PendingTeamPermissions {
+        commit: head,
+        added,
+        removed,
+    }))
+}
+
+/// Clone the configured repo, or fast-forward the mirror to its remote
+/// head. Local edits inside the mirror are discarded; overrides belong in
+/// the local prompts folder.
+async fn sync_team_repo(state: &AppState) -> Result<TeamPromptsStatus, String> {
+    let repo = state.app_settings.lock().await.team_prompts_repo.clone();
+    let repo = configured_repo(repo).ok_or("No team prompts repository configured")?;
+    let dir = team_repo_dir(state);
+
+    let current_remote = if dir.join(".git").exists() {
+        run_git_command(&dir, &["remote", "get-url", "origin"])
+            .await
+            .ok()
+    } else {
+        None
+    };
+    if current_remote.as_deref() == Some(repo.as_str()) {
+        run_git_command(&dir, &["fetch", "--depth", "1", "origin"]).await?;
+        run_git_command(&dir, &["reset", "--hard", "FETCH_HEAD"]).await?;
+    } else {
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+        }
+        let parent = dir.parent().unwrap_or(Path::new(".")).to_path_buf();
+        std::fs::create_dir_all(&parent).map_err(|e| e.to_string())?;
+        let target = dir.to_string_lossy().to_string();
+        run_git_command(&parent, &["clone", "--depth", "1", "--", &repo, &target]).await?;
+    }
+
+    read_status(state, repo).await
+}
+
+async fn read_status(state: &AppState, repo: String) -> Result<TeamPromptsStatus, String> {
+    let dir = team_repo_dir(state);
+    let commit = run_git_command(&dir, &["rev-parse", "--short", "HEAD"]).await?;
+    let permissions = match std::fs::read_to_string(dir.join("permissions.json")) {
+        Ok(content) => parse_permissions(&content)?,
+        Err(_) => TeamPermissions::default(),
+    };
+    let prompts = team_prompt_dirs(&dir)
+        .iter()
+        .map(|dir| crate::prompts::discover_prompts_in(dir).len())
+        .sum();
+    let accepted_commit = read_accepted(state, &repo).map(|accepted| accepted.commit);
+    let pending_permissions = pending_permissions(state, None).await?;
+    let synced_at = std::fs::metadata(dir.join(".git").join("FETCH_HEAD"))
+        .or_else(|_| std::fs::metadata(dir.join(".git").join("HEAD")))
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_else(now_millis);
+    Ok(TeamPromptsStatus {
+        repo,
+        commit,
+        synced_at,
+        prompts,
+        permissions,
+        accepted_commit,
+        pending_permissions,
+    })
+}
+
+/// Pull the team repo in the background on the configured interval.
+pub(crate) fn spawn_team_prompt_sync(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            let (configured, minutes) = {
+                let settings = state.app_settings.lock().await;
+                (
+                    settings.team_prompts_repo.is_some(),
+                    settings.team_prompts_sync_minutes,
+                )
+            };
+            if !configured || schedulers_paused(&state).await {
+                sleep(IDLE_POLL).await;
+                continue;
+            }
+            if let Err(err) = sync_team_repo(&state).await {
+                log_line!("Team prompt sync failed: {err}");
+            }
+            sleep(Duration::from_secs(minutes * 60).max(MIN_SYNC_INTERVAL)).await;
+        }
+    });
+}
+
+/// Pull the team prompts repo now.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn sync_team_prompts(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<TeamPromptsStatus, String> {
+    sync_team_repo(&state).await
+}
+
+/// The last synced state of the team repo, without pulling.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_team_prompts_status(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<TeamPromptsStatus>, String> {
+    let repo = state.app_settings.lock().await.team_prompts_repo.clone();
+    let Some(repo) = configured_repo(repo) else {
+        return Ok(None);
+    };
+    if !team_repo_dir(&state).join(".git").exists() {
+        return Ok(None);
+    }
+    read_status(&state, repo).await.map(Some)
+}
+
+/// Put the allow rules at `commit`, the mirror's HEAD, into effect. Needs
+/// a confirmation whose prompt lists the rules being added and removed.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn accept_team_permissions(
+    commit: String,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<TeamPromptsStatus, String> {
+    consume_confirmation(
+        &state,
+        confirmation.as_deref(),
+        "accept_team_permissions",
+        &commit,
+    )
+    .await?;
+    let repo = state.app_settings.lock().await.team_prompts_repo.clone();
+    let repo = configured_repo(repo).ok_or("No team prompts repository configured")?;
+    // Errors if the mirror moved since the prompt was shown.
+    pending_permissions(&state, Some(&commit)).await?;
+    let permissions = permissions_at(&team_repo_dir(&state), &commit).await?;
+    write_accepted(
+        &state,
+        &AcceptedTeamPermissions {
+            repo: repo.clone(),
+            commit,
+            allow: permissions.allow,
+        },
+    )?;
+    read_status(&state, repo).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_permissions, permission_changes};
+
+    #[test]
+    fn parses_and_dedupes_team_permissions() {
+        let permissions =
+            parse_permissions(r#"{ "allow": ["Read", " Bash(npm test) ", "Read", ""] }"#).unwrap();
+        assert_eq!(permissions.allow, vec!["Read", "Bash(npm test)"]);
+        assert!(permissions.deny.is_empty());
+        assert!(parse_permissions("[]").is_err());
+    }
+
+    #[test]
+    fn diffs_synced_allow_rules_against_accepted_ones() {
+        let accepted = vec!["Read".to_string(), "Bash(npm test)".to_string()];
+        let synced = vec!["Read".to_string(), "Bash(*)".to_string()];
+        let (added, removed) = permission_changes(&accepted, &synced);
+        assert_eq!(added, ["Bash(*)"]);
+        assert_eq!(removed, ["Bash(npm test)"]);
+        assert_eq!(
+            permission_changes(&accepted, &accepted),
+            (Vec::new(), Vec::new())
+        );
+    }
+}
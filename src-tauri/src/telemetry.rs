@@ -0,0 +1,564 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tokio::time::sleep;
+
+use crate::claude::ClaudeEvent;
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::events::ClaudeEventPayload;
+use crate::power::schedulers_paused;
+use crate::secrets::{delete_secret, read_secret, write_secret};
+use crate::state::AppState;
+use crate::types::TelemetrySettings;
+use crate::utils::{curl_config_value, run_curl, TempFile};
+
+// OTLP/HTTP with the JSON encoding, posted with curl. Nothing is recorded or
+// sent unless `telemetry.enabled` is set.
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+/// Keychain entry holding export headers, e.g. `api-key=...,x-team=...`.
+const HEADERS_SECRET: &str = "telemetry-headers";
+const SCOPE_NAME: &str = "claude-monitor";
+/// Upper bounds (ms) of the turn duration histogram buckets.
+const DURATION_BOUNDS_MS: &[f64] = &[
+    1_000.0, 5_000.0, 15_000.0, 30_000.0, 60_000.0, 120_000.0, 300_000.0, 600_000.0,
+];
+/// Spans kept while the collector is unreachable; older ones are dropped.
+const MAX_BUFFERED_SPANS: usize = 2_000;
+
+#[derive(Debug, Clone)]
+struct Span {
+    trace_id: String,
+    span_id: String,
+    parent_span_id: Option<String>,
+    name: String,
+    start_ns: u64,
+    end_ns: u64,
+    attributes: Vec<(String, Value)>,
+    error: bool,
+}
+
+#[derive(Debug, Clone)]
+struct OpenTurn {
+    trace_id: String,
+    span_id: String,
+}
+
+#[derive(Debug, Clone)]
+struct OpenTool {
+    session_id: String,
+    name: String,
+    start_ns: u64,
+}
+
+/// Metrics and spans recorded since the last export.
+#[derive(Debug, Default)]
+pub(crate) struct Telemetry {
+    enabled: bool,
+    window_start_ns: u64,
+    turns: HashMap<String, OpenTurn>,
+    tools: HashMap<String, OpenTool>,
+    spans: Vec<Span>,
+    /// (metric, attribute key, attribute value) -> count
+    counters: BTreeMap<(&'static str, &'static str, String), u64>,
+    durations_ms: Vec<f64>,
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+fn hex_id(bytes: usize) -> String {
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    id[..bytes * 2].to_string()
+}
+
+fn attribute(key: &str, value: &Value) -> Value {
+    let value = match value {
+        Value::Bool(flag) => json!({ "boolValue": flag }),
+        Value::Number(number) if number.is_i64() || number.is_u64() => {
+            json!({ "intValue": number.to_string() })
+        }
+        Value::Number(number) => json!({ "doubleValue": number.as_f64() }),
+        Value::String(text) => json!({ "stringValue": text }),
+        other => json!({ "stringValue": other.to_string() }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn resource(service_name: &str) -> Value {
+    json!({ "attributes": [attribute("service.name", &json!(service_name))] })
+}
+
+impl Telemetry {
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        if enabled != self.enabled {
+            *self = Telemetry {
+                enabled,
+                window_start_ns: now_ns(),
+                ..Telemetry::default()
+            };
+        }
+    }
+
+    fn count(&mut self, metric: &'static str, key: &'static str, value: &str, by: u64) {
+        if by > 0 {
+            *self
+                .counters
+                .entry((metric, key, value.to_string()))
+                .or_insert(0) += by;
+        }
+    }
+
+    fn turn(&mut self, session_id: &str) -> OpenTurn {
+        self.turns
+            .entry(session_id.to_string())
+            .or_insert_with(|| OpenTurn {
+                trace_id: hex_id(16),
+                span_id: hex_id(8),
+            })
+            .clone()
+    }
+
+    fn push_span(&mut self, span: Span) {
+        if self.spans.len() >= MAX_BUFFERED_SPANS {
+            self.spans.remove(0);
+        }
+        self.spans.push(span);
+    }
+
+    /// Fold one bridge event into the pending metrics and spans.
+    fn record(&mut self, event: &ClaudeEvent, now: u64) {
+        if !self.enabled || event.session_id.is_empty() {
+            return;
+        }
+        let session_id = event.session_id.as_str();
+        match &event.payload {
+            ClaudeEventPayload::ToolStarted(tool) => {
+                self.turn(session_id);
+                self.tools.insert(
+                    tool.tool_use_id.clone(),
+                    OpenTool {
+                        session_id: session_id.to_string(),
+                        name: tool.tool_name.clone(),
+                        start_ns: now,
+                    },
+                );
+            }
+            ClaudeEventPayload::ToolCompleted(tool) => {
+                let Some(open) = self.tools.remove(&tool.tool_use_id) else {
+                    return;
+                };
+                let turn = self.turn(&open.session_id);
+                let error = tool
+                    .output
+                    .get("is_error")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                self.count("claude_monitor.tool_calls", "tool", &open.name, 1);
+                self.push_span(Span {
+                    trace_id: turn.trace_id,
+                    span_id: hex_id(8),
+                    parent_span_id: Some(turn.span_id),
+                    name: format!("tool {}", open.name),
+                    start_ns: open.start_ns,
+                    end_ns: now,
+                    attributes: vec![
+                        ("tool.name".to_string(), json!(open.name)),
+                        ("tool.use_id".to_string(), json!(tool.tool_use_id)),
+                    ],
+                    error,
+                });
+            }
+            ClaudeEventPayload::Result(result) => {
+                let turn = self.turn(session_id);
+                self.turns.remove(session_id);
+                let outcome = if result.success { "success" } else { "error" };
+                self.count("claude_monitor.turns", "outcome", outcome, 1);
+                for (kind, tokens) in [
+                    ("input", result.usage.input_tokens),
+                    ("output", result.usage.output_tokens),
+                    ("cache_read", result.usage.cache_read_input_tokens),
+                    ("cache_creation", result.usage.cache_creation_input_tokens),
+                ] {
+                    self.count("claude_monitor.tokens", "type", kind, tokens);
+                }
+                self.durations_ms.push(result.duration_ms as f64);
+                self.push_span(Span {
+                    trace_id: turn.trace_id,
+                    span_id: turn.span_id,
+                    parent_span_id: None,
+                    name: "turn".to_string(),
+                    start_ns: now.saturating_sub(result.duration_ms * 1_000_000),
+                    end_ns: now,
+                    attributes: vec![
+                        ("session.id".to_string(), json!(session_id)),
+                        ("workspace.id".to_string(), json!(event.workspace_id)),
+                        ("turn.subtype".to_string(), json!(result.subtype)),
+                        ("turn.num_turns".to_string(), json!(result.num_turns)),
+                        ("turn.cost_usd".to_string(), json!(result.total_cost_usd)),
+                        (
+                            "gen_ai.usage.input_tokens".to_string(),
+                            json!(result.usage.input_tokens),
+                        ),
+                        (
+                            "gen_ai.usage.output_tokens".to_string(),
+                            json!(result.usage.output_tokens),
+                        ),
+                    ],
+                    error: !result.success,
+                });
+            }
+            ClaudeEventPayload::Error(error) => {
+                self.count("claude_monitor.errors", "code", &error.code, 1);
+            }
+            _ => {}
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.spans.is_empty() && self.counters.is_empty() && self.durations_ms.is_empty()
+    }
+
+    fn traces_body(&self, service_name: &str) -> Option<Value> {
+        if self.spans.is_empty() {
+            return None;
+        }
+        let spans: Vec<Value> = self
+            .spans
+            .iter()
+            .map(|span| {
+                let mut value = json!({
+                    "traceId": span.trace_id,
+                    "spanId": span.span_id,
+                    "name": span.name,
+                    "kind": 1,
+                    "startTimeUnixNano": span.start_ns.to_string(),
+                    "endTimeUnixNano": span.end_ns.to_string(),
+                    "attributes": span
+                        .attributes
+                        .iter()
+                        .map(|(key, value)| attribute(key, value))
+                        .collect::<Vec<_>>(),
+                    "status": { "code": if span.error { 2 } else { 1 } },
+                });
+                if let Some(parent) = &span.parent_span_id {
+                    value["parentSpanId"] = json!(parent);
+                }
+                value
+            })
+            .collect();
+        Some(json!({
+            "resourceSpans": [{
+                "resource": resource(service_name),
+                "scopeSpans": [{ "scope": { "name": SCOPE_NAME }, "spans": spans }],
+            }]
+        }))
+    }
+
+    fn metrics_body(&self, service_name: &str, now: u64) -> Option<Value> {
+        if self.counters.is_empty() && self.durations_ms.is_empty() {
+            return None;
+        }
+        let start = self.window_start_ns.to_string();
+        let time = now.to_string();
+        let mut by_metric: BTreeMap<&str, Vec<Value>> = BTreeMap::new();
+        for ((metric, key, value), count) in &self.counters {
+            by_metric.entry(*metric).or_default().push(json!({
+                "attributes": [attribute(key, &json!(value))],
+                "startTimeUnixNano": start,
+                "timeUnixNano": time,
+                "asInt": count.to_string(),
+            }));
+        }
+        let mut metrics: Vec<Value> = by_metric
+            .into_iter()
+            .map(|(name, points)| {
+                json!({
+                    "name": name,
+                    "unit": if name == "claude_monitor.tokens" { "{token}" } else { "1" },
+                    "sum": {
+                        "dataPoints": points,
+                        "aggregationTemporality": 1,
+                        "isMonotonic": true,
+                    },
+                })
+            })
+            .collect();
+        if !self.durations_ms.is_empty() {
+            let mut buckets = vec![0u64; DURATION_BOUNDS_MS.len() + 1];
+            for duration in &self.durations_ms {
+                let index = DURATION_BOUNDS_MS
+                    .iter()
+                    .position(|bound| duration <= bound)
+                    .unwrap_or(DURATION_BOUNDS_MS.len());
+                buckets[index] += 1;
+            }
+            metrics.push(json!({
+                "name": "claude_monitor.turn.duration",
+                "unit": "ms",
+                "histogram": {
+                    "dataPoints": [{
+                        "startTimeUnixNano": start,
+                        "timeUnixNano": time,
+                        "count": self.durations_ms.len().to_string(),
+                        "sum": self.durations_ms.iter().sum::<f64>(),
+                        "bucketCounts": buckets.iter().map(u64::to_string).collect::<Vec<_>>(),
+                        "explicitBounds": DURATION_BOUNDS_MS,
+                    }],
+                    "aggregationTemporality": 1,
+                },
+            }));
+        }
+        Some(json!({
+            "resourceMetrics": [{
+                "resource": resource(service_name),
+                "scopeMetrics": [{ "scope": { "name": SCOPE_NAME }, "metrics": metrics }],
+            }]
+        }))
+    }
+
+    /// Take everything recorded so far, leaving open turns and tool calls.
+    fn drain(&mut self, now: u64) -> Telemetry {
+        let drained = Telemetry {
+            enabled: self.enabled,
+            window_start_ns: self.window_start_ns,
+            spans: std::mem::take(&mut self.spans),
+            counters: std::mem::take(&mut self.counters),
+            durations_ms: std::mem::take(&mut self.durations_ms),
+            ..Telemetry::default()
+        };
+        self.window_start_ns = now;
+        drained
+    }
+}
+
+/// Record an event for export. Cheap no-op while telemetry is off.
+pub(crate) fn record_event(app_handle: &AppHandle, event: &ClaudeEvent) {
+    let Some(state) = app_handle.try_state::<AppState>() else {
+        return;
+    };
+    if let Ok(mut telemetry) = state.telemetry.lock() {
+        telemetry.record(event, now_ns());
+    };
+}
+
+/// Parse headers in `OTEL_EXPORTER_OTLP_HEADERS` form: `key=value,key=value`.
+fn parse_headers(raw: &str) -> Vec<(String, String)> {
+    raw.split(',')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| (name.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+async fn post(
+    settings: &TelemetrySettings,
+    headers: &[(String, String)],
+    path: &str,
+    body: &Value,
+) -> Result<(), String> {
+    let endpoint = settings
+        .endpoint
+        .as_deref()
+        .filter(|endpoint| !endpoint.trim().is_empty())
+        .ok_or("No OTLP endpoint configured")?
+        .trim_end_matches('/');
+    let mut config = format!(
+        "url = {}\n",
+        curl_config_value(&format!("{endpoint}{path}"))
+    );
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = {}\n",
+            curl_config_value(&format!("{name}: {value}"))
+        ));
+    }
+    // A batch can be megabytes of JSON: too big for argv.
+    let body = TempFile::with_contents("otlp", body.to_string().as_bytes())?;
+    let args = [
+        "--fail".to_string(),
+        "-H".to_string(),
+        "Content-Type: application/json".to_string(),
+        "--data-binary".to_string(),
+        format!("@{}", body.path().display()),
+    ];
+    run_curl(&config, &args).await.map(|_| ())
+}
+
+/// Export recorded telemetry to the OTLP collector every few seconds.
+pub(crate) fn spawn_telemetry_exporter(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(FLUSH_INTERVAL).await;
+            let state = app.state::<AppState>();
+            if schedulers_paused(&state).await {
+                continue;
+            }
+            let settings = state.app_settings.lock().await.telemetry.clone();
+            let now = now_ns();
+            let batch = match state.telemetry.lock() {
+                Ok(mut telemetry) => {
+                    telemetry.set_enabled(settings.enabled);
+                    if !settings.enabled || telemetry.is_empty() {
+                        continue;
+                    }
+                    telemetry.drain(now)
+                }
+                Err(_) => continue,
+            };
+            let headers = match read_secret(HEADERS_SECRET).await {
+                Ok(raw) => parse_headers(&raw.unwrap_or_default()),
+                Err(err) => {
+                    log_line!("OTLP headers unavailable: {err}");
+                    Vec::new()
+                }
+            };
+            let service_name = settings.service_name.as_str();
+            if let Some(body) = batch.traces_body(service_name) {
+                if let Err(err) = post(&settings, &headers, "/v1/traces", &body).await {
+                    log_line!("OTLP trace export failed: {err}");
+                }
+            }
+            if let Some(body) = batch.metrics_body(service_name, now) {
+                if let Err(err) = post(&settings, &headers, "/v1/metrics", &body).await {
+                    log_line!("OTLP metric export failed: {err}");
+                }
+            }
+        }
+    });
+}
+
+/// Store (or clear, with `None`) the OTLP export headers.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_telemetry_headers(
+    headers: Option<String>,
+    _timer: CommandTimer,
+) -> Result<bool, String> {
+    match headers
+        .as_deref()
+        .map(str::trim)
+        .filter(|headers| !headers.is_empty())
+    {
+        Some(headers) => {
+            if parse_headers(headers).is_empty() {
+                return Err("Headers must look like key=value,key=value".to_string());
+            }
+            write_secret(HEADERS_SECRET, headers).await?;
+            Ok(true)
+        }
+        None => {
+            delete_secret(HEADERS_SECRET).await?;
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{parse_headers, Telemetry};
+    use crate::claude::ClaudeEvent;
+    use crate::events::ClaudeEventPayload;
+
+    fn event(event_type: &str, payload: serde_json::Value) -> ClaudeEvent {
+        ClaudeEvent {
+            event_type: event_type.to_string(),
+            session_id: "s1".to_string(),
+            workspace_id: "w1".to_string(),
+            timestamp: 0,
+            payload: ClaudeEventPayload::from_bridge(event_type, &payload).unwrap(),
+        }
+    }
+
+    #[test]
+    fn records_turn_and_tool_spans_with_metrics() {
+        let mut telemetry = Telemetry::default();
+        telemetry.record(
+            &event(
+                "error",
+                json!({ "code": "X", "message": "m", "recoverable": true }),
+            ),
+            1,
+        );
+        assert!(telemetry.is_empty(), "nothing is recorded while disabled");
+
+        telemetry.set_enabled(true);
+        telemetry.record(
+            &event(
+                "tool/started",
+                json!({ "toolName": "Bash", "toolUseId": "t1", "input": {} }),
+            ),
+            1_000,
+        );
+        telemetry.record(
+            &event(
+                "tool/completed",
+                json!({ "toolName": "Bash", "toolUseId": "t1", "output": {} }),
+            ),
+            2_000,
+        );
+        telemetry.record(
+            &event(
+                "result",
+                json!({
+                    "success": true, "subtype": "success", "durationMs": 3, "numTurns": 1,
+                    "totalCostUsd": 0.01,
+                    "usage": { "inputTokens": 10, "outputTokens": 5,
+                               "cacheReadInputTokens": 0, "cacheCreationInputTokens": 0 },
+                }),
+            ),
+            5_000_000,
+        );
+
+        let batch = telemetry.drain(6_000_000);
+        assert!(telemetry.is_empty());
+        let traces = batch.traces_body("svc").unwrap();
+        let spans = traces["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0]["name"], "tool Bash");
+        assert_eq!(spans[0]["parentSpanId"], spans[1]["spanId"]);
+        assert_eq!(spans[0]["traceId"], spans[1]["traceId"]);
+        assert_eq!(spans[1]["startTimeUnixNano"], "2000000");
+
+        let metrics = batch.metrics_body("svc", 6_000_000).unwrap();
+        let names: Vec<&str> = metrics["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|metric| metric["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "claude_monitor.tokens",
+                "claude_monitor.tool_calls",
+                "claude_monitor.turns",
+                "claude_monitor.turn.duration"
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_otlp_header_pairs() {
+        assert_eq!(
+            parse_headers("api-key = abc, x-team=core=1,,bad"),
+            vec![
+                ("api-key".to_string(), "abc".to_string()),
+                ("x-team".to_string(), "core=1".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,639 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::command_metrics::CommandTimer;
+use crate::crash::log_line;
+use crate::registry::write_registry;
+use crate::secrets::{delete_secret, read_secret, write_secret};
+use crate::state::AppState;
+use crate::storage::write_workspaces;
+use crate::transcripts::summarize_session;
+use crate::types::{TicketProvider, TicketSettings};
+use crate::utils::{curl_config_value, run_curl};
+
+const JIRA_TOKEN_SECRET: &str = "jira-api-token";
+const LINEAR_KEY_SECRET: &str = "linear-api-key";
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+/// Session metadata field holding the linked ticket key.
+pub(crate) const TICKET_METADATA_KEY: &str = "ticket";
+const SEARCH_LIMIT: usize = 20;
+
+/// A Jira issue or Linear issue as shown next to a session.
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Ticket {
+    provider: TicketProvider,
+    /// e.g. `ENG-123`.
+    key: String,
+    title: String,
+    status: String,
+    url: String,
+    /// Linear's internal id, needed to comment.
+    #[serde(skip)]
+    id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TicketStatus {
+    provider: Option<TicketProvider>,
+    has_jira_token: bool,
+    has_linear_key: bool,
+}
+
+/// Issue keys are letters, digits and dashes; anything else would end up
+/// in a URL path or a query.
+fn valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 64
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn parse_jira_issue(value: &Value, base_url: &str) -> Option<Ticket> {
+    let key = value.get("key")?.as_str()?.to_string();
+    let fields = value.get("fields")?;
+    Some(Ticket {
+        provider: TicketProvider::Jira,
+        url: format!("{}/browse/{key}", base_url.trim_end_matches('/')),
+        title: fields
+            .get("summary")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string(),
+        status: fields
+            .pointer("/status/name")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string(),
+        key,
+        id: None,
+    })
+}
+
+fn parse_linear_issue(value: &Value) -> Option<Ticket> {
+    Some(Ticket {
+        provider: TicketProvider::Linear,
+        key: value.get("identifier")?.as_str()?.to_string(),
+        title: value
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string(),
+        status: value
+            .pointer("/state/name")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string(),
+        url: value
+            .get("url")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string(),
+        id: value.get("id").and_then(Value::as_str).map(str::to_string),
+    })
+}
+
+/// Jira reports failures as `errorMessages` and `errors` in a JSON body.
+fn jira_result(body: &str) -> Result<Value, String> {
+    let value: Value = serde_json::from_str(body).map_err(|_| "Unexpected Jira response")?;
+    let mut messages: Vec<String> = value
+        .get("errorMessages")
+        .and_then(Value::as_array)
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(errors) = value.get("errors").and_then(Value::as_object) {
+        messages.extend(
+            errors
+                .values()
+                .filter_map(Value::as_str)
+                .map(str::to_string),
+        );
+    }
+    if messages.is_empty() {
+        Ok(value)
+    } else {
+        Err(format!("Jira API error: {}", messages.join("; ")))
+    }
+}
+
+fn linear_result(body: &str) -> Result<Value, String> {
+    let value: Value = serde_json::from_str(body).map_err(|_| "Unexpected Linear response")?;
+    if let Some(error) = value.pointer("/errors/0/message").and_then(Value::as_str) {
+        return Err(format!("Linear API error: {error}"));
+    }
+    value
+        .get("data")
+        .cloned()
+        .ok_or_else(|| "Unexpected Linear response".to_string())
+}
+
+/// Wrap plain text in the Atlassian document format Jira comments use.
+fn jira_comment_body(text: &str) -> Value {
+    let paragraphs: Vec<Value> = text
+        .split("\n\n")
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .map(|paragraph| {
+            json!({
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": paragraph.trim() }],
+            })
+        })
+        .collect();
+    json!({ "body": { "type": "doc", "version": 1, "content": paragraphs } })
+}
+
+fn summary_comment(session_id: &str, summary: &str) -> String {
+    format!(
+        "Claude session {session_id} finished.\n\n{}",
+        summary.trim()
+    )
+}
+
+/// Credentials for the configured provider, read from the keychain.
+enum Client {
+    Jira {
+        base_url: String,
+        email: String,
+        token: String,
+    },
+    Linear {
+        key: String,
+    },
+}
+
+impl Client {
+    async fn from_settings(settings: &TicketSettings) -> Result<Self, String> {
+        match settings.provider {
+            Some(TicketProvider::Jira) => {
+                let base_url = settings
+                    .jira_base_url
+                    .clone()
+                    .filter(|url| !url.trim().is_empty())
+                    .ok_or("Jira site URL is not set")?;
+                let email = settings
+                    .jira_email
+                    .clone()
+                    .filter(|email| !email.trim().is_empty())
+                    .ok_or("Jira account email is not set")?;
+                let token = read_secret(JIRA_TOKEN_SECRET)
+                    .await?
+                    .ok_or("Jira API token is not set")?;
+                Ok(Client::Jira {
+                    base_url: base_url.trim().trim_end_matches('/').to_string(),
+                    email: email.trim().to_string(),
+                    token,
+                })
+            }
+            Some(TicketProvider::Linear) => {
+                let key = read_secret(LINEAR_KEY_SECRET)
+                    .await?
+                    .ok_or("Linear API key is not set")?;
+                Ok(Client::Linear { key })
+            }
+            None => Err("No ticket system is configured".to_string()),
+        }
+    }
+
+    /// Call the provider; credentials go to curl on stdin.
+    async fn request(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<&Value>,
+    ) -> Result<String, String> {
+        let mut config = format!("url = {}\n", curl_config_value(url));
+        match self {
+            Client::Jira { email, token, .. } => {
+                config.push_str(&format!(
+                    "user = {}\n",
+                    curl_config_value(&format!("{email}:{token}"))
+                ));
+            }
+            Client::Linear { key } => {
+                config.push_str(&format!(
+                    "header = {}\n",
+                    curl_config_value(&format!("Authorization: {key}"))
+                ));
+            }
+        }
+        let mut args = vec![
+            "-X".to_string(),
+            method.to_string(),
+            "-H".to_string(),
+            "Accept: application/json".to_string(),
+        ];
+        if let Some(body) = body {
+            args.extend([
+                "-H".to_string(),
+                "Content-Type: application/json".to_string(),
+                "--data-binary".to_string(),
+                body.to_string(),
+            ]);
+        }
+        run_curl(&config, &args).await
+    }
+
+    async fn linear_query(&self, query: &str, variables: Value) -> Result<Value, String> {
+        let body = json!({ "query": query, "variables": variables });
+        let response = self.request("POST", LINEAR_API_URL, Some(&body)).await?;
+        linear_result(&response)
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<Ticket>, String> {
+        match self {
+            Client::Jira { base_url, .. } => {
+                let escaped = query.replace('\\', "\\\\").replace('"', "\\\"");
+                let mut jql = format!("text ~ \"{escaped}\"");
+                if valid_key(query) {
+                    jql = format!("key = \"{escaped}\" OR {jql}");
+                }
+                let body = json!({
+                    "jql": format!("{jql} ORDER BY updated DESC"),
+                    "fields": ["summary", "status"],
+                    "maxResults": SEARCH_LIMIT,
+                });
+                let url = format!("{base_url}/rest/api/3/search/jql");
+                let value = jira_result(&self.request("POST", &url, Some(&body)).await?)?;
+                Ok(value
+                    .get("issues")
+                    .and_then(Value::as_array)
+                    .map(|issues| {
+                        issues
+                            .iter()
+                            .filter_map(|issue| parse_jira_issue(issue, base_url))
+                            .collect()
+                    })
+                    .unwrap_or_default())
+            }
+            Client::Linear { .. } => {
+                let data = self
+                    .linear_query(
+                        "query($term: String!, $first: Int) { searchIssues(term: $term, first: $first) \
+                         { nodes { id identifier title url state { name } } } }",
+                        json!({ "term": query, "first": SEARCH_LIMIT }),
+                    )
+                    .await?;
+                Ok(data
+                    .pointer("/searchIssues/nodes")
+                    .and_then(Value::as_array)
+                    .map(|nodes| nodes.iter().filter_map(parse_linear_issue).collect())
+                    .unwrap_or_default())
+            }
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Ticket, String> {
+        if !valid_key(key) {
+            return Err(format!("Invalid ticket key: {key}"));
+        }
+        let ticket = match self {
+            Client::Jira { base_url, .. } => {
+                let url = format!("{base_url}/rest/api/3/issue/{key}?fields=summary,status");
+                let value = jira_result(&self.request("GET", &url, None).await?)?;
+                parse_jira_issue(&value, base_url)
+            }
+            Client::Linear { .. } => {
+                let data = self
+                    .linear_query(
+                        "query($id: String!) { issue(id: $id) \
+                         { id identifier title url state { name } } }",
+                        json!({ "id": key }),
+                    )
+                    .await?;
+                data.get("issue").and_then(parse_linear_issue)
+            }
+        };
+        ticket.ok_or_else(|| format!("Ticket {key} not found"))
+    }
+
+    async fn comment(&self, ticket: &Ticket, text: &str) -> Result<(), String> {
+        match self {
+            Client::Jira { base_url, .. } => {
+                let url = format!("{base_url}/rest/api/3/issue/{}/comment", ticket.key);
+                let body = jira_comment_body(text);
+                jira_result(&self.request("POST", &url, Some(&body)).await?)?;
+            }
+            Client::Linear { .. } => {
+                let issue_id = ticket.id.as_deref().ok_or("Linear issue id is missing")?;
+                let data = self
+                    .linear_query(
+                        "mutation($issueId: String!, $body: String!) \
+                         { commentCreate(input: { issueId: $issueId, body: $body }) { success } }",
+                        json!({ "issueId": issue_id, "body": text }),
+                    )
+                    .await?;
+                if data
+                    .pointer("/commentCreate/success")
+                    .and_then(Value::as_bool)
+                    != Some(true)
+                {
+                    return Err("Linear did not accept the comment".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn client(state: &AppState) -> Result<Client, String> {
+    let settings = state.app_settings.lock().await.tickets.clone();
+    Client::from_settings(&settings).await
+}
+
+/// The ticket key for a session: its own link first, then its workspace's.
+/// `workspace_id` is looked up from the registry when not given.
+async fn linked_key(
+    state: &AppState,
+    session_id: &str,
+    workspace_id: Option<&str>,
+) -> Option<String> {
+    let workspace_id = {
+        let registry = state.registry.lock().await;
+        if let Some(key) = registry
+            .sessions
+            .get(session_id)
+            .and_then(|session| session.metadata.get(TICKET_METADATA_KEY))
+        {
+            return Some(key.clone());
+        }
+        match workspace_id {
+            Some(id) => id.to_string(),
+            None => registry
+                .workspaces
+                .iter()
+                .find(|(_, workspace)| {
+                    workspace
+                        .visible_session_ids
+                        .iter()
+                        .any(|id| id == session_id)
+                })
+                .map(|(id, _)| id.clone())?,
+        }
+    };
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .and_then(|entry| entry.settings.ticket.clone())
+}
+
+/// Post the session's summary to the ticket. Summarizes the session first
+/// unless a summary is cached.
+async fn post_summary(app: &AppHandle, session_id: &str, key: &str) -> Result<Ticket, String> {
+    let state = app.state::<AppState>();
+    let client = client(&state).await?;
+    let ticket = client.get(key).await?;
+    let summary = summarize_session(
+        session_id.to_string(),
+        None,
+        app.clone(),
+        state,
+        CommandTimer::untimed(),
+    )
+    .await?;
+    client
+        .comment(&ticket, &summary_comment(session_id, &summary.text))
+        .await?;
+    Ok(ticket)
+}
+
+/// Called when a session is archived: post its summary to the linked
+/// ticket if `commentOnComplete` is on.
+pub(crate) fn session_completed(app: &AppHandle, workspace_id: &str, session_id: &str) {
+    let app = app.clone();
+    let workspace_id = workspace_id.to_string();
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if !state.app_settings.lock().await.tickets.comment_on_complete {
+            return;
+        }
+        let Some(key) = linked_key(&state, &session_id, Some(&workspace_id)).await else {
+            return;
+        };
+        if let Err(err) = post_summary(&app, &session_id, &key).await {
+            log_line!("Ticket comment failed: {err}");
+        }
+    });
+}
+
+/// Store (or clear, with empty values) the Jira API token and Linear API key.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_ticket_credentials(
+    jira_api_token: Option<String>,
+    linear_api_key: Option<String>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    for (secret, value) in [
+        (JIRA_TOKEN_SECRET, jira_api_token),
+        (LINEAR_KEY_SECRET, linear_api_key),
+    ] {
+        match value.as_deref().map(str::trim) {
+            Some("") => delete_secret(secret).await?,
+            Some(value) => write_secret(secret, value).await?,
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_ticket_status(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<TicketStatus, String> {
+    Ok(TicketStatus {
+        provider: state.app_settings.lock().await.tickets.provider,
+        has_jira_token: read_secret(JIRA_TOKEN_SECRET).await?.is_some(),
+        has_linear_key: read_secret(LINEAR_KEY_SECRET).await?.is_some(),
+    })
+}
+
+/// Search the configured ticket system by text or issue key.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn search_tickets(
+    query: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<Ticket>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    client(&state).await?.search(query).await
+}
+
+/// Link a session to a ticket, or unlink it with no key. The key is kept
+/// in the session's `ticket` metadata field.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn link_session_ticket(
+    session_id: String,
+    key: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<Ticket>, String> {
+    let key = key
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty());
+    let ticket = match &key {
+        Some(key) => Some(client(&state).await?.get(key).await?),
+        None => None,
+    };
+    let mut registry = state.registry.lock().await;
+    let session = registry
+        .sessions
+        .get_mut(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    match ticket.as_ref() {
+        Some(ticket) => {
+            session
+                .metadata
+                .insert(TICKET_METADATA_KEY.to_string(), ticket.key.clone());
+        }
+        None => {
+            session.metadata.remove(TICKET_METADATA_KEY);
+        }
+    }
+    write_registry(&state.registry_path, &registry)?;
+    Ok(ticket)
+}
+
+/// Link a workspace to a ticket; its sessions without a link of their own
+/// use it.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn link_workspace_ticket(
+    workspace_id: String,
+    key: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<Ticket>, String> {
+    let key = key
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty());
+    let ticket = match &key {
+        Some(key) => Some(client(&state).await?.get(key).await?),
+        None => None,
+    };
+    let list = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get_mut(&workspace_id)
+            .ok_or("workspace not found")?;
+        entry.settings.ticket = ticket.as_ref().map(|ticket| ticket.key.clone());
+        workspaces.values().cloned().collect::<Vec<_>>()
+    };
+    write_workspaces(&state.storage_path, &list)?;
+    Ok(ticket)
+}
+
+/// The ticket linked to a session (directly or through its workspace),
+/// with its current status, for the session header.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_session_ticket(
+    session_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<Ticket>, String> {
+    let Some(key) = linked_key(&state, &session_id, None).await else {
+        return Ok(None);
+    };
+    client(&state).await?.get(&key).await.map(Some)
+}
+
+/// Post the session summary to its linked ticket now.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn post_ticket_summary(
+    session_id: String,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Ticket, String> {
+    let key = linked_key(&state, &session_id, None)
+        .await
+        .ok_or("Session has no linked ticket")?;
+    post_summary(&app_handle, &session_id, &key).await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        jira_comment_body, jira_result, linear_result, parse_jira_issue, parse_linear_issue,
+        valid_key,
+    };
+    use crate::types::TicketProvider;
+
+    #[test]
+    fn parses_issues_from_both_providers() {
+        let jira = json!({
+            "key": "ENG-12",
+            "fields": { "summary": "Fix login", "status": { "name": "In Progress" } },
+        });
+        let ticket = parse_jira_issue(&jira, "https://acme.atlassian.net/").unwrap();
+        assert_eq!(ticket.provider, TicketProvider::Jira);
+        assert_eq!(ticket.key, "ENG-12");
+        assert_eq!(ticket.status, "In Progress");
+        assert_eq!(ticket.url, "https://acme.atlassian.net/browse/ENG-12");
+
+        let linear = json!({
+            "id": "uuid-1",
+            "identifier": "APP-7",
+            "title": "Dark mode",
+            "url": "https://linear.app/acme/issue/APP-7",
+            "state": { "name": "Todo" },
+        });
+        let ticket = parse_linear_issue(&linear).unwrap();
+        assert_eq!(ticket.key, "APP-7");
+        assert_eq!(ticket.status, "Todo");
+        assert_eq!(ticket.id.as_deref(), Some("uuid-1"));
+        assert!(parse_linear_issue(&json!({ "title": "no key" })).is_none());
+    }
+
+    #[test]
+    fn surfaces_api_errors() {
+        assert_eq!(
+            jira_result(r#"{"errorMessages":["Issue does not exist"],"errors":{}}"#),
+            Err("Jira API error: Issue does not exist".to_string())
+        );
+        assert!(jira_result(r#"{"key":"ENG-1"}"#).is_ok());
+        assert_eq!(
+            linear_result(r#"{"errors":[{"message":"Authentication required"}]}"#),
+            Err("Linear API error: Authentication required".to_string())
+        );
+        assert_eq!(
+            linear_result(r#"{"data":{"issue":null}}"#),
+            Ok(json!({ "issue": null }))
+        );
+    }
+
+    #[test]
+    fn validates_keys_and_builds_comments() {
+        assert!(valid_key("ENG-123"));
+        assert!(!valid_key("ENG 123"));
+        assert!(!valid_key("../issue"));
+        assert!(!valid_key(""));
+
+        let body = jira_comment_body("First.\n\n- one\n- two\n\n");
+        let content = body["body"]["content"].as_array().unwrap();
+        assert_eq!(content.len(), 2);
+        assert_eq!(content[1]["content"][0]["text"], "- one\n- two");
+    }
+}
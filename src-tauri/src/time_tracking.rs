@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::registry::{now_millis, write_registry};
+use crate::state::AppState;
+use crate::types::{SessionEntry, SessionTime};
+use crate::usage::{billing_tag, UsageRange};
+
+/// Longest stretch counted as attended; past this the user has most
+/// likely walked away with the window still focused.
+const MAX_ATTENDED_STRETCH_MS: u64 = 30 * 60 * 1000;
+
+/// The session the user is looking at, and since when.
+#[derive(Debug, Default)]
+pub(crate) struct AttendedClock {
+    current: Option<(String, u64)>,
+}
+
+impl AttendedClock {
+    /// Switch the attended session (`None` when the window loses focus).
+    /// Returns the time to credit to the previous one.
+    fn switch(&mut self, session_id: Option<String>, now: u64) -> Option<(String, u64)> {
+        let previous = self.current.take();
+        self.current = session_id.map(|id| (id, now));
+        let (id, since) = previous?;
+        let elapsed = now.saturating_sub(since).min(MAX_ATTENDED_STRETCH_MS);
+        (elapsed > 0).then_some((id, elapsed))
+    }
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// Credit a finished turn's run time to today.
+pub(crate) fn add_agent_time(session: &mut SessionEntry, duration_ms: u64) {
+    if duration_ms > 0 {
+        session.time.entry(today()).or_default().agent_ms += duration_ms;
+    }
+}
+
+fn parse_day(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| format!("Invalid date: {date}"))
+}
+
+/// Inclusive day bounds of a range; an open end is `None`.
+fn day_range(range: &UsageRange) -> Result<(Option<NaiveDate>, Option<NaiveDate>), String> {
+    let bound = |date: Option<&str>| {
+        date.filter(|date| !date.trim().is_empty())
+            .map(parse_day)
+            .transpose()
+    };
+    let (from, to) = (bound(range.from.as_deref())?, bound(range.to.as_deref())?);
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err("Range ends before it starts".to_string());
+        }
+    }
+    Ok((from, to))
+}
+
+/// Sum a session's time over the days in range.
+fn time_in_range(
+    time: &BTreeMap<String, SessionTime>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> SessionTime {
+    let mut total = SessionTime::default();
+    for (day, spent) in time {
+        let Ok(day) = parse_day(day) else {
+            continue;
+        };
+        if from.is_some_and(|from| day < from) || to.is_some_and(|to| day > to) {
+            continue;
+        }
+        total.attended_ms += spent.attended_ms;
+        total.agent_ms += spent.agent_ms;
+    }
+    total
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionTimeRow {
+    session_id: String,
+    preview: Option<String>,
+    attended_ms: u64,
+    agent_ms: u64,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceTimeRow {
+    workspace_id: String,
+    workspace_name: String,
+    billing_tag: Option<String>,
+    attended_ms: u64,
+    agent_ms: u64,
+    sessions: Vec<SessionTimeRow>,
+}
+
+#[derive(Debug, Serialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TimeReport {
+    attended_ms: u64,
+    agent_ms: u64,
+    workspaces: Vec<WorkspaceTimeRow>,
+}
+
+/// Report which session the user is looking at: the selected session while
+/// the window is focused, `None` when it blurs or nothing is selected. Time
+/// since the last call is credited to the previous session.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_attended_session(
+    session_id: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<(), String> {
+    let credit = state.attended.lock().await.switch(session_id, now_millis());
+    let Some((session_id, elapsed)) = credit else {
+        return Ok(());
+    };
+    let mut registry = state.registry.lock().await;
+    let Some(session) = registry.sessions.get_mut(&session_id) else {
+        return Ok(());
+    };
+    session.time.entry(today()).or_default().attended_ms += elapsed;
+    write_registry(&state.registry_path, &registry)
+}
+
+/// Attended and agent time per workspace and session for the days in
+/// range, for reporting hours spent supervising agents.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_time_report(
+    range: Option<UsageRange>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<TimeReport, String> {
+    let (from, to) = day_range(&range.unwrap_or_default())?;
+    let workspaces = state.workspaces.lock().await.clone();
+    let registry = state.registry.lock().await;
+    let mut report = TimeReport::default();
+    for (workspace_id, workspace) in &registry.workspaces {
+        let Some(entry) = workspaces.get(workspace_id) else {
+            continue;
+        };
+        let sessions: Vec<SessionTimeRow> = workspace
+            .visible_session_ids
+            .iter()
+            .filter_map(|id| registry.sessions.get(id))
+            .map(|session| {
+                let spent = time_in_range(&session.time, from, to);
+                SessionTimeRow {
+                    session_id: session.session_id.clone(),
+                    preview: session.preview.clone(),
+                    attended_ms: spent.attended_ms,
+                    agent_ms: spent.agent_ms,
+                }
+            })
+            .filter(|row| row.attended_ms > 0 || row.agent_ms > 0)
+            .collect();
+        if sessions.is_empty() {
+            continue;
+        }
+        let row = WorkspaceTimeRow {
+            workspace_id: workspace_id.clone(),
+            workspace_name: entry.name.clone(),
+            billing_tag: billing_tag(&workspaces, entry),
+            attended_ms: sessions.iter().map(|row| row.attended_ms).sum(),
+            agent_ms: sessions.iter().map(|row| row.agent_ms).sum(),
+            sessions,
+        };
+        report.attended_ms += row.attended_ms;
+        report.agent_ms += row.agent_ms;
+        report.workspaces.push(row);
+    }
+    report
+        .workspaces
+        .sort_by(|a, b| a.workspace_name.cmp(&b.workspace_name));
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::NaiveDate;
+
+    use super::{time_in_range, AttendedClock, MAX_ATTENDED_STRETCH_MS};
+    use crate::types::SessionTime;
+
+    #[test]
+    fn credits_the_previous_session_on_switch() {
+        let mut clock = AttendedClock::default();
+        assert_eq!(clock.switch(Some("a".to_string()), 1_000), None);
+        assert_eq!(
+            clock.switch(Some("b".to_string()), 4_000),
+            Some(("a".to_string(), 3_000))
+        );
+        assert_eq!(clock.switch(None, 5_000), Some(("b".to_string(), 1_000)));
+        assert_eq!(clock.switch(None, 9_000), None);
+
+        clock.switch(Some("a".to_string()), 0);
+        assert_eq!(
+            clock.switch(None, 10 * MAX_ATTENDED_STRETCH_MS),
+            Some(("a".to_string(), MAX_ATTENDED_STRETCH_MS))
+        );
+    }
+
+    #[test]
+    fn sums_days_in_range() {
+        let day = |attended_ms, agent_ms| SessionTime {
+            attended_ms,
+            agent_ms,
+        };
+        let time = BTreeMap::from([
+            ("2026-03-01".to_string(), day(10, 100)),
+            ("2026-03-02".to_string(), day(20, 200)),
+            ("2026-03-05".to_string(), day(40, 400)),
+        ]);
+        let date = |value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok();
+        assert_eq!(time_in_range(&time, None, None), day(70, 700));
+        assert_eq!(
+            time_in_range(&time, date("2026-03-02"), date("2026-03-05")),
+            day(60, 600)
+        );
+        assert_eq!(time_in_range(&time, None, date("2026-03-01")), day(10, 100));
+    }
+}
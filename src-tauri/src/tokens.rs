@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::attachments::resolve_in_workspace;
+use crate::command_metrics::CommandTimer;
+use crate::state::AppState;
+
+/// Context window assumed when a model's is unknown.
+pub(crate) const DEFAULT_CONTEXT_WINDOW: u64 = 200_000;
+/// Models opted into the long context beta are suffixed `[1m]` by the CLI.
+const LONG_CONTEXT_WINDOW: u64 = 1_000_000;
+
+/// Rough Claude token count without the API: word pieces of about four
+/// characters, one token per symbol, and line breaks. Errs high on prose
+/// and close on code; good enough for budgets, not billing.
+pub(crate) fn estimate_tokens(text: &str) -> u64 {
+    let mut tokens = 0u64;
+    let mut word_chars = 0u64;
+    let flush = |word_chars: &mut u64, tokens: &mut u64| {
+        if *word_chars > 0 {
+            *tokens += word_chars.div_ceil(4);
+            *word_chars = 0;
+        }
+    };
+    for ch in text.chars() {
+        if ch.is_alphanumeric() || ch == '_' {
+            // Non-Latin scripts tokenize far more densely.
+            word_chars += if ch.is_ascii() { 1 } else { 3 };
+        } else {
+            flush(&mut word_chars, &mut tokens);
+            if ch == '\n' || !ch.is_whitespace() {
+                tokens += 1;
+            }
+        }
+    }
+    flush(&mut word_chars, &mut tokens);
+    tokens
+}
+
+pub(crate) fn context_window(model: Option<&str>) -> u64 {
+    match model {
+        Some(model) if model.trim_end().ends_with("[1m]") => LONG_CONTEXT_WINDOW,
+        _ => DEFAULT_CONTEXT_WINDOW,
+    }
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileTokenCount {
+    path: String,
+    tokens: u64,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TokenCount {
+    /// Text plus files.
+    tokens: u64,
+    text_tokens: u64,
+    files: Vec<FileTokenCount>,
+    context_window: u64,
+}
+
+/// Read and estimate each of `paths` in the workspace at `root`. Blocking.
+fn count_file_tokens(root: &Path, paths: &[String]) -> Result<Vec<FileTokenCount>, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        let (resolved, relative) = resolve_in_workspace(root, path)?;
+        let data = std::fs::read(&resolved).map_err(|e| e.to_string())?;
+        let tokens = match String::from_utf8(data) {
+            Ok(content) if !content.contains('\0') => estimate_tokens(&content),
+            // Binary files are not sent as text; count bytes as a bound.
+            Ok(content) => (content.len() as u64).div_ceil(4),
+            Err(err) => (err.as_bytes().len() as u64).div_ceil(4),
+        };
+        files.push(FileTokenCount {
+            path: relative,
+            tokens,
+        });
+    }
+    Ok(files)
+}
+
+/// Estimate tokens for composer text and/or workspace files. Offline and
+/// approximate; `model` only selects the context window.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn count_tokens(
+    text: Option<String>,
+    paths: Option<Vec<String>>,
+    workspace_id: Option<String>,
+    model: Option<String>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<TokenCount, String> {
+    let paths = paths.unwrap_or_default();
+    let files = if paths.is_empty() {
+        Vec::new()
+    } else {
+        let workspace_id = workspace_id.ok_or("workspaceId is required to count files")?;
+        let root = state
+            .workspaces
+            .lock()
+            .await
+            .get(&workspace_id)
+            .map(|entry| PathBuf::from(&entry.path))
+            .ok_or("workspace not found")?;
+        // Reading and scanning large files would stall the async runtime.
+        tokio::task::spawn_blocking(move || count_file_tokens(&root, &paths))
+            .await
+            .map_err(|_| "token count failed".to_string())??
+    };
+    let text_tokens = text.as_deref().map(estimate_tokens).unwrap_or(0);
+    Ok(TokenCount {
+        tokens: text_tokens + files.iter().map(|file| file.tokens).sum::<u64>(),
+        text_tokens,
+        files,
+        context_window: context_window(model.as_deref()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{context_window, count_file_tokens, estimate_tokens};
+    use crate::utils::TempDir;
+
+    #[test]
+    fn estimates_tokens_for_prose_and_code() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("hello world"), 4);
+        assert_eq!(estimate_tokens("fn main() {}\n"), 7);
+        assert_eq!(context_window(Some("claude-sonnet-4-5[1m]")), 1_000_000);
+        assert_eq!(context_window(None), 200_000);
+    }
+
+    #[test]
+    fn counts_workspace_files() {
+        let root = TempDir::new("tokens");
+        std::fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+        let files = count_file_tokens(&root, &["main.rs".to_string()]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].tokens, 7);
+        assert!(count_file_tokens(&root, &["missing.rs".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::State;
+
+use crate::command_metrics::CommandTimer;
+use crate::registry::{history_item, resolve_transcript_path};
+use crate::state::AppState;
+
+/// Bumped when the on-disk layout or the head hash changes; older indexes
+/// are rebuilt.
+const INDEX_VERSION: u32 = 2;
+/// Bytes hashed from the start of a transcript to notice rewrites.
+const HEAD_BYTES: usize = 4096;
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct MessageOffset {
+    offset: u64,
+    len: u32,
+    /// Zero-based line number, used for fallback message ids.
+    line: usize,
+    user: bool,
+}
+
+/// Byte offsets of every history message in a transcript. Only complete
+/// lines are indexed, so a transcript being appended to is picked up
+/// incrementally from `indexed_bytes`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranscriptIndex {
+    version: u32,
+    head_hash: u64,
+    indexed_bytes: u64,
+    indexed_lines: usize,
+    messages: Vec<MessageOffset>,
+}
+
+impl TranscriptIndex {
+    /// Line of the message with `message_id`, searching from the newest.
+    pub(crate) fn message_line(
+        &self,
+        session_id: &str,
+        transcript: &Path,
+        message_id: &str,
+    ) -> Result<Option<usize>, String> {
+        let map = map_transcript(transcript)?;
+        for message in self.messages.iter().rev() {
+            let items = read_items(session_id, &map, std::slice::from_ref(message))?;
+            if items
+                .first()
+                .and_then(|item| item.get("id"))
+                .and_then(|id| id.as_str())
+                == Some(message_id)
+            {
+                return Ok(Some(message.line));
+            }
+        }
+        Ok(None)
+    }
+
+    pub(crate) fn last_message_line(&self) -> Option<usize> {
+        self.messages.last().map(|message| message.line)
+    }
+
+    /// Assistant messages after transcript line `line`.
+    pub(crate) fn replies_after(&self, line: usize) -> usize {
+        self.messages
+            .iter()
+            .rev()
+            .take_while(|message| message.line > line)
+            .filter(|message| !message.user)
+            .count()
+    }
+}
+
+#[derive(Debug, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionHistoryPage {
+    items: Vec<Value>,
+    /// Total history messages in the transcript.
+    total: usize,
+    /// Index of the first returned item among all messages.
+    offset: usize,
+    preview: Option<String>,
+    last_activity: u64,
+}
+
+/// Hash of the first `bytes` of the transcript (capped at `HEAD_BYTES`).
+/// SHA-256 rather than `DefaultHasher`, whose output may change between
+/// Rust releases and would invalidate every saved index.
+fn head_hash(path: &Path, bytes: u64) -> Result<u64, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut head = Vec::new();
+    file.take(bytes.min(HEAD_BYTES as u64))
+        .read_to_end(&mut head)
+        .map_err(|e| e.to_string())?;
+    let digest = Sha256::digest(&head);
+    let mut first = [0u8; 8];
+    first.copy_from_slice(&digest[..8]);
+    Ok(u64::from_le_bytes(first))
+}
+
+/// Bring `index` up to date with the transcript, scanning only bytes past
+/// `indexed_bytes` unless the file shrank or was rewritten.
+pub(crate) fn update_index(index: &mut TranscriptIndex, path: &Path) -> Result<bool, String> {
+    let len = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+    if index.version != INDEX_VERSION
+        || len < index.indexed_bytes
+        || head_hash(path, index.indexed_bytes)? != index.head_hash
+    {
+        *index = TranscriptIndex::default();
+    }
+    if len == index.indexed_bytes {
+        return Ok(false);
+    }
+    index.version = INDEX_VERSION;
+
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(index.indexed_bytes))
+        .map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| e.to_string())?;
+        if read == 0 || !line.ends_with(b"\n") {
+            break;
+        }
+        let offset = index.indexed_bytes;
+        let line_number = index.indexed_lines;
+        index.indexed_bytes += read as u64;
+        index.indexed_lines += 1;
+        let Ok(entry) = serde_json::from_slice::<Value>(&line) else {
+            continue;
+        };
+        let Some(item) = history_item("", line_number, &entry) else {
+            continue;
+        };
+        index.messages.push(MessageOffset {
+            offset,
+            len: read as u32,
+            line: line_number,
+            user: item.get("role").and_then(|r| r.as_str()) == Some("user"),
+        });
+    }
+    index.head_hash = head_hash(path, index.indexed_bytes)?;
+    Ok(true)
+}
+
+pub(crate) fn index_path(state: &AppState, session_id: &str) -> PathBuf {
+    state
+        .storage_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("transcript-index")
+        .join(format!("{session_id}.json"))
+}
+
+fn load_index(path: &Path) -> TranscriptIndex {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(path: &Path, index: &TranscriptIndex) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let data = serde_json::to_string(index).map_err(|e| e.to_string())?;
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, data).map_err(|e| e.to_string())?;
+    std::fs::rename(&temp_path, path).map_err(|e| e.to_string())
+}
+
+/// Map a transcript into memory so messages are read by slicing instead of
+/// a seek and read each.
+fn map_transcript(path: &Path) -> Result<Mmap, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    // SAFETY: transcripts are only appended to while the app reads them,
+    // and every offset is checked against the mapped length before use.
+    unsafe { Mmap::map(&file) }.map_err(|e| e.to_string())
+}
+
+fn read_items(
+    session_id: &str,
+    transcript: &[u8],
+    offsets: &[MessageOffset],
+) -> Result<Vec<Value>, String> {
+    let mut items = Vec::with_capacity(offsets.len());
+    for message in offsets {
+        let start = message.offset as usize;
+        let line = transcript
+            .get(start..start + message.len as usize)
+            .ok_or("transcript shrank while reading")?;
+        let entry: Value = serde_json::from_slice(line).map_err(|e| e.to_string())?;
+        if let Some(item) = history_item(session_id, message.line, &entry) {
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+/// Load a transcript's index, update it, and persist it if it changed.
+pub(crate) fn indexed_transcript(
+    index_file: &Path,
+    transcript: &Path,
+) -> Result<TranscriptIndex, String> {
+    let mut index = load_index(index_file);
+    if update_index(&mut index, transcript)? {
+        save_index(index_file, &index)?;
+    }
+    Ok(index)
+}
+
+/// Page through a session's history using a persistent offset index, so
+/// large transcripts are scanned once and then read by seeking.
+/// `from_end` counts `offset` back from the newest message.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_session_history_page(
+    session_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    from_end: Option<bool>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SessionHistoryPage, String> {
+    let transcript = {
+        let registry = state.registry.lock().await;
+        resolve_transcript_path(&registry, &session_id)?
+    };
+    let index_file = index_path(&state, &session_id);
+    tokio::task::spawn_blocking(move || -> Result<SessionHistoryPage, String> {
+        let index = indexed_transcript(&index_file, &transcript)?;
+        let total = index.messages.len();
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        let offset = offset.unwrap_or(0).min(total);
+        let (start, end) = if from_end.unwrap_or(false) {
+            let end = total - offset;
+            (end.saturating_sub(limit), end)
+        } else {
+            (offset, (offset + limit).min(total))
+        };
+        let map = map_transcript(&transcript)?;
+        let items = read_items(&session_id, &map, &index.messages[start..end])?;
+        let preview = index
+            .messages
+            .iter()
+            .find(|message| message.user)
+            .or(index.messages.first())
+            .map(|message| read_items(&session_id, &map, std::slice::from_ref(message)))
+            .transpose()?
+            .and_then(|items| items.into_iter().next())
+            .and_then(|item| {
+                item.get("text")
+                    .and_then(|t| t.as_str())
+                    .map(str::to_string)
+            });
+        let last_activity = std::fs::metadata(&transcript)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        Ok(SessionHistoryPage {
+            items,
+            total,
+            offset: start,
+            preview,
+            last_activity,
+        })
+    })
+    .await
+    .map_err(|_| "history page failed".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::{map_transcript, read_items, update_index, TranscriptIndex};
+    use crate::utils::TempDir;
+
+    #[test]
+    fn indexes_incrementally_and_skips_partial_lines() {
+        let dir = TempDir::new("transcript-index");
+        let path = dir.join("s1.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                "{\"type\":\"user\",\"message\":{\"content\":[{\"type\":\"text\",\"text\":\"hi\"}]}}\n",
+                "{\"type\":\"system\"}\n",
+                "{\"type\":\"assistant\",\"message\":{\"content\":[{\"type\":\"text\",\"text\":\"hello\"}]}}\n",
+                "{\"type\":\"user\",\"mess",
+            ),
+        )
+        .unwrap();
+        let mut index = TranscriptIndex::default();
+        assert!(update_index(&mut index, &path).unwrap());
+        assert_eq!(index.messages.len(), 2);
+        assert_eq!(index.indexed_lines, 3);
+        assert!(index.messages[0].user);
+        assert_eq!(index.messages[1].line, 2);
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b"age\":{\"text\":\"more\"}}\n").unwrap();
+        assert!(update_index(&mut index, &path).unwrap());
+        assert!(!update_index(&mut index, &path).unwrap());
+        assert_eq!(index.messages.len(), 3);
+        assert_eq!(index.messages[2].line, 3);
+    }
+
+    #[test]
+    fn reads_messages_from_the_mapped_transcript() {
+        let dir = TempDir::new("transcript-map");
+        let path = dir.join("s1.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                "{\"type\":\"user\",\"uuid\":\"u1\",\"message\":{\"content\":[{\"type\":\"text\",\"text\":\"hi\"}]}}\n",
+                "{\"type\":\"assistant\",\"uuid\":\"a1\",\"message\":{\"content\":[{\"type\":\"text\",\"text\":\"hello\"}]}}\n",
+            ),
+        )
+        .unwrap();
+        let mut index = TranscriptIndex::default();
+        update_index(&mut index, &path).unwrap();
+        let map = map_transcript(&path).unwrap();
+        let items = read_items("s1", &map, &index.messages).unwrap();
+        assert_eq!(items.len(), 2);
+        let id = items[1]
+            .get("id")
+            .and_then(|id| id.as_str())
+            .unwrap()
+            .to_string();
+        assert_eq!(index.message_line("s1", &path, &id).unwrap(), Some(1));
+        assert!(read_items("s1", &map[..10], &index.messages).is_err());
+    }
+}
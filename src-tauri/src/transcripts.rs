@@ -0,0 +1,671 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, State};
+
+use crate::claude::complete_text;
+use crate::command_metrics::CommandTimer;
+use crate::registry::{now_millis, resolve_transcript_path, write_registry};
+use crate::state::AppState;
+use crate::types::{SessionSummary, SessionUsage};
+
+/// Small, fast model used for background summaries.
+pub(crate) const SUMMARY_MODEL: &str = "haiku";
+/// Characters of transcript text per first-level summary chunk.
+pub(crate) const SUMMARY_CHUNK_CHARS: usize = 24_000;
+const DEFAULT_SUMMARY_WORDS: u32 = 250;
+
+/// Tools whose `file_path`/`notebook_path` input means the file was changed.
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileTouch {
+    path: String,
+    edits: usize,
+    lines_added: usize,
+    lines_removed: usize,
+}
+
+/// Aggregate facts about one transcript, shared by comparison, summaries
+/// and digests.
+#[derive(Debug, Serialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranscriptStats {
+    pub(crate) prompts: Vec<String>,
+    pub(crate) message_count: usize,
+    pub(crate) started_at: Option<i64>,
+    pub(crate) ended_at: Option<i64>,
+    pub(crate) duration_ms: Option<i64>,
+    pub(crate) models: Vec<String>,
+    pub(crate) usage: SessionUsage,
+    pub(crate) tool_calls: BTreeMap<String, usize>,
+    pub(crate) files: Vec<FileTouch>,
+    pub(crate) lines_added: usize,
+    pub(crate) lines_removed: usize,
+    pub(crate) final_summary: Option<String>,
+}
+
+/// Plain text of a message whose content is a string or an array of blocks.
+pub(crate) fn message_text(message: &Value) -> String {
+    match message.get("content") {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+pub(crate) fn entry_timestamp(entry: &Value) -> Option<i64> {
+    let raw = entry.get("timestamp")?;
+    if let Some(millis) = raw.as_i64() {
+        return Some(millis);
+    }
+    chrono::DateTime::parse_from_rfc3339(raw.as_str()?)
+        .ok()
+        .map(|time| time.timestamp_millis())
+}
+
+fn count_lines(value: Option<&Value>) -> usize {
+    value
+        .and_then(|v| v.as_str())
+        .map(|text| text.lines().count())
+        .unwrap_or(0)
+}
+
+fn record_tool_use(stats: &mut TranscriptStats, files: &mut Vec<FileTouch>, block: &Value) {
+    let name = block
+        .get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or("unknown");
+    *stats.tool_calls.entry(name.to_string()).or_default() += 1;
+    if !EDIT_TOOLS.contains(&name) {
+        return;
+    }
+    let input = block.get("input").cloned().unwrap_or(Value::Null);
+    let Some(path) = input
+        .get("file_path")
+        .or_else(|| input.get("notebook_path"))
+        .and_then(|p| p.as_str())
+    else {
+        return;
+    };
+    let (added, removed) = match name {
+        "Write" => (count_lines(input.get("content")), 0),
+        "Edit" => (
+            count_lines(input.get("new_string")),
+            count_lines(input.get("old_string")),
+        ),
+        "MultiEdit" => input
+            .get("edits")
+            .and_then(|e| e.as_array())
+            .map(|edits| {
+                edits.iter().fold((0, 0), |(a, r), edit| {
+                    (
+                        a + count_lines(edit.get("new_string")),
+                        r + count_lines(edit.get("old_string")),
+                    )
+                })
+            })
+            .unwrap_or((0, 0)),
+        _ => (count_lines(input.get("new_source")), 0),
+    };
+    let index = match files.iter().position(|file| file.path == path) {
+        Some(index) => index,
+        None => {
+            files.push(FileTouch {
+                path: path.to_string(),
+                ..FileTouch::default()
+            });
+            files.len() - 1
+        }
+    };
+    let file = &mut files[index];
+    file.edits += 1;
+    file.lines_added += added;
+    file.lines_removed += removed;
+}
+
+pub(crate) fn read_transcript_stats(path: &Path) -> Result<TranscriptStats, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut stats = TranscriptStats::default();
+    let mut files = Vec::new();
+    let mut seen_message_ids = HashSet::new();
+
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if let Some(timestamp) = entry_timestamp(&entry) {
+            stats.started_at = Some(stats.started_at.map_or(timestamp, |t| t.min(timestamp)));
+            stats.ended_at = Some(stats.ended_at.map_or(timestamp, |t| t.max(timestamp)));
+        }
+        let entry_type = entry.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+        match entry_type {
+            "user" => {
+                let text = message_text(message);
+                if !text.trim().is_empty()
+                    && !entry
+                        .get("isMeta")
+                        .and_then(|m| m.as_bool())
+                        .unwrap_or(false)
+                {
+                    stats.prompts.push(text);
+                    stats.message_count += 1;
+                }
+            }
+            "assistant" => {
+                // Streaming writes one entry per content block with the same
+                // message id and usage; count each message once.
+                let message_id = message.get("id").and_then(|id| id.as_str());
+                let first_seen =
+                    message_id.map_or(true, |id| seen_message_ids.insert(id.to_string()));
+                if first_seen {
+                    stats.message_count += 1;
+                    if let Some(usage) = message.get("usage") {
+                        let read = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                        stats.usage.input_tokens += read("input_tokens");
+                        stats.usage.output_tokens += read("output_tokens");
+                        stats.usage.cache_read_input_tokens += read("cache_read_input_tokens");
+                        stats.usage.cache_creation_input_tokens +=
+                            read("cache_creation_input_tokens");
+                        stats.usage.requests += 1;
+                    }
+                    if let Some(model) = message.get("model").and_then(|m| m.as_str()) {
+                        if !stats.models.iter().any(|m| m == model) {
+                            stats.models.push(model.to_string());
+                        }
+                    }
+                }
+                let text = message_text(message);
+                if !text.trim().is_empty() {
+                    stats.final_summary = Some(text);
+                }
+                if let Some(blocks) = message.get("content").and_then(|c| c.as_array()) {
+                    for block in blocks {
+                        if block.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
+                            record_tool_use(&mut stats, &mut files, block);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    stats.duration_ms = match (stats.started_at, stats.ended_at) {
+        (Some(start), Some(end)) => Some(end - start),
+        _ => None,
+    };
+    stats.lines_added = files.iter().map(|f| f.lines_added).sum();
+    stats.lines_removed = files.iter().map(|f| f.lines_removed).sum();
+    stats.files = files;
+    Ok(stats)
+}
+
+/// User prompts and assistant replies as `(role, text)`, skipping tool
+/// results and meta entries.
+pub(crate) fn transcript_messages(path: &Path) -> Result<Vec<(String, String)>, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut messages = Vec::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let role = match entry.get("type").and_then(|t| t.as_str()) {
+            Some("user")
+                if !entry
+                    .get("isMeta")
+                    .and_then(|m| m.as_bool())
+                    .unwrap_or(false) =>
+            {
+                "user"
+            }
+            Some("assistant") => "assistant",
+            _ => continue,
+        };
+        let Some(message) = entry.get("message") else {
+            continue;
+        };
+        let text = message_text(message);
+        if !text.trim().is_empty() {
+            messages.push((role.to_string(), text));
+        }
+    }
+    Ok(messages)
+}
+
+/// Pack messages into chunks of roughly `max_chars`, splitting oversized
+/// messages so no chunk is unbounded.
+pub(crate) fn chunk_messages(messages: &[(String, String)], max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for (role, text) in messages {
+        let rendered = format!("{role}: {text}\n\n");
+        let mut pieces: Vec<String> = Vec::new();
+        let chars: Vec<char> = rendered.chars().collect();
+        for piece in chars.chunks(max_chars.max(1)) {
+            pieces.push(piece.iter().collect());
+        }
+        for piece in pieces {
+            if !current.is_empty() && current.chars().count() + piece.chars().count() > max_chars {
+                chunks.push(std::mem::take(&mut current));
+            }
+            current.push_str(&piece);
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Digest of a long session: chunk summaries first, then a summary of
+/// those. Cached on the registry entry until the transcript grows.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn summarize_session(
+    session_id: String,
+    max_words: Option<u32>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SessionSummary, String> {
+    let max_words = max_words.unwrap_or(DEFAULT_SUMMARY_WORDS).max(20);
+    let (path, cached, workspace_id) = {
+        let registry = state.registry.lock().await;
+        let path = resolve_transcript_path(&registry, &session_id)?;
+        let cached = registry
+            .sessions
+            .get(&session_id)
+            .and_then(|entry| entry.summary.clone());
+        let workspace_id = registry
+            .workspaces
+            .iter()
+            .find(|(_, workspace)| workspace.visible_session_ids.contains(&session_id))
+            .map(|(id, _)| id.clone());
+        (path, cached, workspace_id)
+    };
+    let transcript_bytes = std::fs::metadata(&path).map_err(|e| e.to_string())?.len();
+    if let Some(cached) = cached {
+        if cached.transcript_bytes == transcript_bytes && cached.max_words == max_words {
+            return Ok(cached);
+        }
+    }
+
+    let messages = tokio::task::spawn_blocking(move || transcript_messages(&path))
+        .await
+        .map_err(|_| "transcript read failed".to_string())??;
+    if messages.is_empty() {
+        return Err("Session has no messages to summarize.".to_string());
+    }
+    let chunks = chunk_messages(&messages, SUMMARY_CHUNK_CHARS);
+    let system_prompt = "You summarize coding-agent sessions for the developer who ran them. \
+        Be concrete: goals, decisions, files and components changed, open problems. \
+        Reply with the summary only.";
+
+    let mut partials = Vec::new();
+    if chunks.len() > 1 {
+        for (index, chunk) in chunks.iter().enumerate() {
+            let prompt = format!(
+                "Part {} of {} of a session transcript. Summarize this part in at most 150 words.\n\n{}",
+                index + 1,
+                chunks.len(),
+                chunk
+            );
+            partials.push(
+                complete_text(
+                    &app_handle,
+                    &state,
+                    workspace_id.as_deref(),
+                    SUMMARY_MODEL,
+                    system_prompt,
+                    prompt,
+                )
+                .await?,
+            );
+        }
+    }
+    let source = if partials.is_empty() {
+        chunks.concat()
+    } else {
+        partials
+            .iter()
+            .enumerate()
+            .map(|(index, part)| format!("Part {}:\n{}", index + 1, part))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+    let prompt = format!(
+        "Summarize this session in at most {max_words} words. Lead with one sentence on the \
+         overall outcome, then bullet points.\n\n{source}"
+    );
+    let text = complete_text(
+        &app_handle,
+        &state,
+        workspace_id.as_deref(),
+        SUMMARY_MODEL,
+        system_prompt,
+        prompt,
+    )
+    .await?;
+
+    let summary = SessionSummary {
+        text,
+        max_words,
+        transcript_bytes,
+        created_at: now_millis(),
+    };
+    let mut registry = state.registry.lock().await;
+    if let Some(entry) = registry.sessions.get_mut(&session_id) {
+        entry.summary = Some(summary.clone());
+        write_registry(&state.registry_path, &registry)?;
+    }
+    Ok(summary)
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionComparisonSide {
+    session_id: String,
+    cwd: String,
+    stats: TranscriptStats,
+    /// Cost as tracked by the app while the session ran, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionComparison {
+    a: SessionComparisonSide,
+    b: SessionComparisonSide,
+    shared_files: Vec<String>,
+    only_a_files: Vec<String>,
+    only_b_files: Vec<String>,
+}
+
+fn split_files(
+    a: &TranscriptStats,
+    b: &TranscriptStats,
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let a_paths: Vec<&String> = a.files.iter().map(|f| &f.path).collect();
+    let b_paths: Vec<&String> = b.files.iter().map(|f| &f.path).collect();
+    let shared = a_paths
+        .iter()
+        .filter(|p| b_paths.contains(p))
+        .map(|p| p.to_string())
+        .collect();
+    let only_a = a_paths
+        .iter()
+        .filter(|p| !b_paths.contains(p))
+        .map(|p| p.to_string())
+        .collect();
+    let only_b = b_paths
+        .iter()
+        .filter(|p| !a_paths.contains(p))
+        .map(|p| p.to_string())
+        .collect();
+    (shared, only_a, only_b)
+}
+
+/// Side-by-side facts for two sessions (e.g. the same task on two models,
+/// or a fork and its parent).
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn compare_sessions(
+    a: String,
+    b: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<SessionComparison, String> {
+    let sides = {
+        let registry = state.registry.lock().await;
+        [a, b]
+            .into_iter()
+            .map(|session_id| {
+                let path = resolve_transcript_path(&registry, &session_id)?;
+                let entry = registry.sessions.get(&session_id);
+                Ok((
+                    session_id,
+                    entry.map(|e| e.cwd.clone()).unwrap_or_default(),
+                    entry.and_then(|e| e.usage.as_ref()).map(|u| u.cost_usd),
+                    path,
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()?
+    };
+    tokio::task::spawn_blocking(move || -> Result<SessionComparison, String> {
+        let mut sides = sides
+            .into_iter()
+            .map(|(session_id, cwd, cost_usd, path)| {
+                Ok(SessionComparisonSide {
+                    session_id,
+                    cwd,
+                    stats: read_transcript_stats(&path)?,
+                    cost_usd,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let b = sides.pop().ok_or("missing session")?;
+        let a = sides.pop().ok_or("missing session")?;
+        let (shared_files, only_a_files, only_b_files) = split_files(&a.stats, &b.stats);
+        Ok(SessionComparison {
+            a,
+            b,
+            shared_files,
+            only_a_files,
+            only_b_files,
+        })
+    })
+    .await
+    .map_err(|_| "session comparison failed".to_string())?
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranscriptLineProblem {
+    /// 1-based line number.
+    line: usize,
+    reason: String,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranscriptIntegrity {
+    session_id: String,
+    path: String,
+    total_lines: usize,
+    problems: Vec<TranscriptLineProblem>,
+    /// The last line is cut off mid-write (no newline, not valid JSON).
+    truncated_tail: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repaired_path: Option<String>,
+}
+
+/// Check every line of a transcript; with `repair`, keep the parseable
+/// lines in `<transcript>.repaired` and leave the original untouched.
+pub(crate) fn verify_transcript(
+    session_id: &str,
+    path: &Path,
+    repair: bool,
+) -> Result<TranscriptIntegrity, String> {
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    let mut problems = Vec::new();
+    let mut kept: Vec<u8> = Vec::new();
+    let mut truncated_tail = false;
+    let ends_with_newline = data.ends_with(b"\n");
+    let lines: Vec<&[u8]> = data.split(|byte| *byte == b'\n').collect();
+    let line_count = if data.is_empty() {
+        0
+    } else if ends_with_newline {
+        lines.len() - 1
+    } else {
+        lines.len()
+    };
+    for (index, raw) in lines.iter().take(line_count).enumerate() {
+        let raw = raw.strip_suffix(b"\r").unwrap_or(raw);
+        if raw.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let reason = match std::str::from_utf8(raw) {
+            Err(_) => Some("invalid UTF-8".to_string()),
+            Ok(text) => match serde_json::from_str::<Value>(text) {
+                Ok(Value::Object(_)) => None,
+                Ok(_) => Some("not a JSON object".to_string()),
+                Err(error) => Some(error.to_string()),
+            },
+        };
+        match reason {
+            Some(reason) => {
+                if index + 1 == line_count && !ends_with_newline {
+                    truncated_tail = true;
+                }
+                problems.push(TranscriptLineProblem {
+                    line: index + 1,
+                    reason,
+                });
+            }
+            None => {
+                kept.extend_from_slice(raw);
+                kept.push(b'\n');
+            }
+        }
+    }
+    let repaired = if repair && !problems.is_empty() {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".repaired");
+        let repaired = PathBuf::from(name);
+        std::fs::write(&repaired, kept).map_err(|e| e.to_string())?;
+        Some(repaired)
+    } else {
+        None
+    };
+    Ok(TranscriptIntegrity {
+        session_id: session_id.to_string(),
+        path: path.to_string_lossy().to_string(),
+        total_lines: line_count,
+        problems,
+        truncated_tail,
+        repaired_path: repaired.map(|path| path.to_string_lossy().to_string()),
+    })
+}
+
+/// Integrity report for a workspace's transcripts, listing only sessions
+/// with problems. `repair` writes cleaned copies next to the originals.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn verify_transcripts(
+    workspace_id: String,
+    repair: Option<bool>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<TranscriptIntegrity>, String> {
+    let transcripts: Vec<(String, PathBuf)> = {
+        let registry = state.registry.lock().await;
+        let workspace = registry
+            .workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        workspace
+            .visible_session_ids
+            .iter()
+            .filter_map(|id| {
+                resolve_transcript_path(&registry, id)
+                    .ok()
+                    .map(|path| (id.clone(), path))
+            })
+            .collect()
+    };
+    let repair = repair.unwrap_or(false);
+    tokio::task::spawn_blocking(move || -> Result<Vec<TranscriptIntegrity>, String> {
+        let mut reports = Vec::new();
+        for (session_id, path) in transcripts {
+            let report = verify_transcript(&session_id, &path, repair)?;
+            if !report.problems.is_empty() {
+                reports.push(report);
+            }
+        }
+        Ok(reports)
+    })
+    .await
+    .map_err(|_| "transcript verification failed".to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chunk_messages, read_transcript_stats, verify_transcript};
+    use crate::utils::TempDir;
+
+    #[test]
+    fn chunks_respect_size_and_keep_order() {
+        let messages = vec![
+            ("user".to_string(), "a".repeat(30)),
+            ("assistant".to_string(), "b".repeat(30)),
+            ("user".to_string(), "c".repeat(100)),
+        ];
+        let chunks = chunk_messages(&messages, 50);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 50));
+        assert!(chunks[0].starts_with("user: aaa"));
+        assert_eq!(chunks.concat().matches('c').count(), 100);
+    }
+
+    #[test]
+    fn reads_prompts_usage_and_edits() {
+        let dir = TempDir::new("transcript-stats");
+        let path = dir.join("s1.jsonl");
+        let lines = [
+            r#"{"type":"user","timestamp":"2025-01-01T10:00:00Z","message":{"role":"user","content":"Add a cache"}}"#,
+            r#"{"type":"assistant","timestamp":"2025-01-01T10:00:05Z","message":{"id":"m1","model":"claude-sonnet-4","usage":{"input_tokens":10,"output_tokens":5},"content":[{"type":"tool_use","name":"Edit","input":{"file_path":"/repo/src/cache.rs","old_string":"a","new_string":"a\nb"}}]}}"#,
+            r#"{"type":"assistant","timestamp":"2025-01-01T10:00:06Z","message":{"id":"m1","model":"claude-sonnet-4","usage":{"input_tokens":10,"output_tokens":5},"content":[{"type":"text","text":"Added the cache."}]}}"#,
+            r#"{"type":"user","timestamp":"2025-01-01T10:01:00Z","message":{"role":"user","content":[{"type":"tool_result","content":"ok"}]}}"#,
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        let stats = read_transcript_stats(&path).unwrap();
+
+        assert_eq!(stats.prompts, vec!["Add a cache"]);
+        assert_eq!(stats.usage.input_tokens, 10);
+        assert_eq!(stats.usage.requests, 1);
+        assert_eq!(stats.duration_ms, Some(60_000));
+        assert_eq!(stats.files.len(), 1);
+        assert_eq!(stats.lines_added, 2);
+        assert_eq!(stats.lines_removed, 1);
+        assert_eq!(stats.tool_calls.get("Edit"), Some(&1));
+        assert_eq!(stats.final_summary.as_deref(), Some("Added the cache."));
+    }
+
+    #[test]
+    fn verifies_and_repairs_transcripts() {
+        let dir = TempDir::new("transcript-verify");
+        let path = dir.join("s1.jsonl");
+        std::fs::write(
+            &path,
+            "{\"type\":\"user\"}\nnot json\n[1,2]\n\n{\"type\":\"assistant\"}\n{\"type\":\"ass",
+        )
+        .unwrap();
+        let report = verify_transcript("s1", &path, true).unwrap();
+        let repaired = report.repaired_path.clone().unwrap();
+        let cleaned = std::fs::read_to_string(&repaired).unwrap();
+
+        assert_eq!(report.total_lines, 6);
+        let lines: Vec<usize> = report.problems.iter().map(|p| p.line).collect();
+        assert_eq!(lines, vec![2, 3, 6]);
+        assert_eq!(report.problems[1].reason, "not a JSON object");
+        assert!(report.truncated_tail);
+        assert_eq!(cleaned, "{\"type\":\"user\"}\n{\"type\":\"assistant\"}\n");
+    }
+}
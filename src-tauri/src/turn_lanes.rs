@@ -0,0 +1,205 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+use crate::state::AppState;
+
+/// Which queue a turn waits in for a slot under `maxConcurrentTurns`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Lane {
+    /// Messages the user sends or queues, and Slack replies.
+    Interactive,
+    /// Workflow prompts and other automation.
+    Background,
+}
+
+struct Waiter {
+    session_id: String,
+    lane: Lane,
+    ready: oneshot::Sender<()>,
+}
+
+/// Sessions with a turn holding a slot, and turns waiting for one.
+#[derive(Default)]
+pub(crate) struct TurnLanes {
+    running: HashMap<String, Lane>,
+    waiting: VecDeque<Waiter>,
+}
+
+impl TurnLanes {
+    /// Whether a turn in `lane` fits under `limit` (0 for no limit).
+    /// Background turns leave a slot free for interactive ones, unless the
+    /// limit is 1.
+    fn has_slot(&self, lane: Lane, limit: usize) -> bool {
+        if limit == 0 {
+            return true;
+        }
+        let slots = match lane {
+            Lane::Interactive => limit,
+            Lane::Background => (limit - 1).max(1),
+        };
+        self.running.len() < slots
+    }
+
+    /// Take a slot for `session_id`'s next turn, or queue for one behind
+    /// turns of the same or a higher priority. Returns the receiver to wait
+    /// on when queued.
+    fn start(
+        &mut self,
+        session_id: &str,
+        lane: Lane,
+        limit: usize,
+    ) -> Option<oneshot::Receiver<()>> {
+        if self.running.contains_key(session_id) {
+            return None;
+        }
+        let queued_ahead = self
+            .waiting
+            .iter()
+            .any(|waiter| lane == Lane::Background || waiter.lane == Lane::Interactive);
+        if !queued_ahead && self.has_slot(lane, limit) {
+            self.running.insert(session_id.to_string(), lane);
+            return None;
+        }
+        let (ready, wait) = oneshot::channel();
+        self.waiting.push_back(Waiter {
+            session_id: session_id.to_string(),
+            lane,
+            ready,
+        });
+        Some(wait)
+    }
+
+    /// Start waiting turns while slots are free, interactive ones first.
+    fn admit(&mut self, limit: usize) {
+        for lane in [Lane::Interactive, Lane::Background] {
+            while self.has_slot(lane, limit) {
+                let Some(index) = self.waiting.iter().position(|waiter| waiter.lane == lane) else {
+                    break;
+                };
+                let Some(waiter) = self.waiting.remove(index) else {
+                    break;
+                };
+                // A closed receiver means the send was given up.
+                if waiter.ready.send(()).is_ok() {
+                    self.running.insert(waiter.session_id, lane);
+                }
+            }
+            if self
+                .waiting
+                .iter()
+                .any(|waiter| waiter.lane == Lane::Interactive)
+            {
+                return;
+            }
+        }
+    }
+
+    fn finish(&mut self, session_id: &str, limit: usize) {
+        self.running.remove(session_id);
+        self.admit(limit);
+    }
+
+    /// Free a closed session's slot and drop its waiting turns, which fail.
+    fn closed(&mut self, session_id: &str, limit: usize) {
+        self.waiting
+            .retain(|waiter| waiter.session_id != session_id);
+        self.finish(session_id, limit);
+    }
+
+    /// The bridge went away with these sessions' running turns.
+    fn clear(&mut self, session_ids: &[String], limit: usize) {
+        for session_id in session_ids {
+            self.running.remove(session_id);
+        }
+        self.admit(limit);
+    }
+}
+
+async fn turn_limit(state: &AppState) -> usize {
+    state.app_settings.lock().await.max_concurrent_turns as usize
+}
+
+/// Wait until `session_id` may start a turn in `lane`. Call
+/// `release_turn_slot` if the message then fails to send.
+pub(crate) async fn acquire_turn_slot(
+    state: &AppState,
+    session_id: &str,
+    lane: Lane,
+) -> Result<(), String> {
+    let limit = turn_limit(state).await;
+    let wait = state.turn_lanes.lock().await.start(session_id, lane, limit);
+    match wait {
+        None => Ok(()),
+        Some(wait) => wait
+            .await
+            .map_err(|_| "The session closed while its turn was waiting to start".to_string()),
+    }
+}
+
+/// A turn ended or failed to start: hand its slot to the next one waiting.
+pub(crate) async fn release_turn_slot(state: &AppState, session_id: &str) {
+    let limit = turn_limit(state).await;
+    state.turn_lanes.lock().await.finish(session_id, limit);
+}
+
+pub(crate) async fn close_turn_slots(state: &AppState, session_id: &str) {
+    let limit = turn_limit(state).await;
+    state.turn_lanes.lock().await.closed(session_id, limit);
+}
+
+/// Free the slots of sessions whose bridge exited. Their waiting turns
+/// stay queued for the sessions resumed on the new bridge.
+pub(crate) async fn clear_turn_slots(state: &AppState, session_ids: &[String]) {
+    let limit = turn_limit(state).await;
+    state.turn_lanes.lock().await.clear(session_ids, limit);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Lane, TurnLanes};
+
+    #[test]
+    fn interactive_turns_go_first() {
+        let mut lanes = TurnLanes::default();
+        assert!(lanes.start("nightly-1", Lane::Background, 2).is_none());
+        // One slot stays free for interactive turns.
+        let mut nightly = lanes.start("nightly-2", Lane::Background, 2).unwrap();
+        assert!(lanes.start("fix", Lane::Interactive, 2).is_none());
+        let mut review = lanes.start("review", Lane::Interactive, 2).unwrap();
+
+        lanes.finish("nightly-1", 2);
+        assert!(review.try_recv().is_ok());
+        assert!(nightly.try_recv().is_err());
+
+        lanes.finish("fix", 2);
+        lanes.finish("review", 2);
+        assert!(nightly.try_recv().is_ok());
+        assert!(lanes.start("anything", Lane::Background, 0).is_none());
+    }
+
+    #[test]
+    fn closed_sessions_drop_their_waiters() {
+        let mut lanes = TurnLanes::default();
+        assert!(lanes.start("a", Lane::Interactive, 1).is_none());
+        let mut waiting = lanes.start("b", Lane::Interactive, 1).unwrap();
+        lanes.closed("b", 1);
+        assert!(waiting.try_recv().is_err());
+        lanes.clear(&["a".to_string()], 1);
+        assert!(lanes.start("c", Lane::Interactive, 1).is_none());
+    }
+
+    #[test]
+    fn clearing_a_dead_bridge_keeps_other_turns() {
+        let mut lanes = TurnLanes::default();
+        assert!(lanes.start("old", Lane::Interactive, 2).is_none());
+        assert!(lanes.start("new", Lane::Interactive, 2).is_none());
+        let mut waiting = lanes.start("next", Lane::Interactive, 2).unwrap();
+        lanes.clear(&["old".to_string()], 2);
+        assert!(waiting.try_recv().is_ok());
+        assert!(lanes.running.contains_key("new"));
+        assert!(lanes.start("more", Lane::Interactive, 2).is_some());
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::claude::BridgeLine;
+use crate::registry::{extract_text_from_message, now_millis, write_registry};
+use crate::state::AppState;
+use crate::types::{InterruptedTool, InterruptedToolStatus, InterruptedTurn};
+
+/// Oldest interrupted turns are dropped past this many per session.
+const MAX_INTERRUPTED_TURNS: usize = 50;
+
+/// Output of a session's running turn, built from bridge events.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct TurnProgress {
+    /// Text of completed assistant messages.
+    text: String,
+    /// Text streamed for the message in progress.
+    streaming: String,
+    tools: Vec<InterruptedTool>,
+    input_tokens: u64,
+    /// Output tokens of messages before the current one.
+    output_tokens: u64,
+    /// Output tokens of the current message; deltas report a running total.
+    message_output_tokens: u64,
+}
+
+impl TurnProgress {
+    fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.streaming.is_empty() && self.tools.is_empty()
+    }
+
+    fn stream_event(&mut self, event: &Value) {
+        let tokens = |usage: Option<&Value>, key: &str| {
+            usage
+                .and_then(|usage| usage.get(key))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0)
+        };
+        match event.get("type").and_then(|t| t.as_str()) {
+            Some("message_start") => {
+                let usage = event.get("message").and_then(|m| m.get("usage"));
+                self.input_tokens += tokens(usage, "input_tokens");
+                self.output_tokens += self.message_output_tokens;
+                self.message_output_tokens = tokens(usage, "output_tokens");
+            }
+            Some("message_delta") => {
+                self.message_output_tokens = tokens(event.get("usage"), "output_tokens");
+            }
+            Some("content_block_delta") => {
+                let delta = event.get("delta");
+                if delta.and_then(|d| d.get("type")).and_then(|t| t.as_str()) == Some("text_delta")
+                {
+                    if let Some(text) = delta.and_then(|d| d.get("text")).and_then(|t| t.as_str()) {
+                        self.streaming.push_str(text);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn complete_message(&mut self, message: &Value) {
+        let text = extract_text_from_message(message);
+        let text = if text.is_empty() {
+            std::mem::take(&mut self.streaming)
+        } else {
+            self.streaming.clear();
+            text
+        };
+        if text.is_empty() {
+            return;
+        }
+        if !self.text.is_empty() {
+            self.text.push_str("\n\n");
+        }
+        self.text.push_str(&text);
+    }
+
+    fn into_interrupted(self, transcript_line: usize) -> InterruptedTurn {
+        let mut text = self.text;
+        if !self.streaming.is_empty() {
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            text.push_str(&self.streaming);
+        }
+        let tools = self
+            .tools
+            .into_iter()
+            .map(|tool| InterruptedTool {
+                status: if tool.status == InterruptedToolStatus::Running {
+                    InterruptedToolStatus::Interrupted
+                } else {
+                    tool.status
+                },
+                ..tool
+            })
+            .collect();
+        InterruptedTurn {
+            id: format!("interrupted-{}", uuid::Uuid::new_v4()),
+            interrupted_at: now_millis(),
+            transcript_line,
+            text,
+            tools,
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens + self.message_output_tokens,
+        }
+    }
+}
+
+/// What the sessions are doing, for the dock badge and taskbar progress.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct TurnActivity {
+    /// Sessions with a turn in progress.
+    pub(crate) running: usize,
+    /// Permission requests waiting for an answer.
+    pub(crate) pending_permissions: usize,
+}
+
+/// Running turns by session id.
+#[derive(Debug, Default)]
+pub(crate) struct TurnTracker {
+    turns: HashMap<String, TurnProgress>,
+    /// Unanswered permission requests: session id -> tool use ids.
+    permissions: HashMap<String, HashSet<String>>,
+}
+
+impl TurnTracker {
+    /// Fold a bridge event into its session's turn. A `result` ends the turn.
+    pub(crate) fn observe(&mut self, line: &BridgeLine) {
+        if line.session_id.is_empty() {
+            return;
+        }
+        // Subagent output belongs to the tool call that started it.
+        let subagent = line
+            .payload
+            .get("parentToolUseId")
+            .is_some_and(|id| !id.is_null());
+        let payload = &line.payload;
+        let str_field = |key: &str| {
+            payload
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        match line.event_type.as_str() {
+            "result" | "session/closed" => {
+                self.turns.remove(&line.session_id);
+                self.permissions.remove(&line.session_id);
+            }
+            "permission/request" => {
+                self.turn(&line.session_id);
+                self.permissions
+                    .entry(line.session_id.clone())
+                    .or_default()
+                    .insert(str_field("toolUseId"));
+            }
+            "message/delta" if !subagent => {
+                if let Some(event) = payload.get("event") {
+                    self.turn(&line.session_id).stream_event(event);
+                }
+            }
+            "message/complete" if !subagent => {
+                if let Some(message) = payload.get("message") {
+                    self.turn(&line.session_id).complete_message(message);
+                }
+            }
+            "tool/started" => {
+                let tool_use_id = str_field("toolUseId");
+                self.permission_answered(&line.session_id, &tool_use_id);
+                let turn = self.turn(&line.session_id);
+                if !turn
+                    .tools
+                    .iter()
+                    .any(|tool| tool.tool_use_id == tool_use_id)
+                {
+                    turn.tools.push(InterruptedTool {
+                        tool_use_id,
+                        tool_name: str_field("toolName"),
+                        status: InterruptedToolStatus::Running,
+                    });
+                }
+            }
+            "tool/completed" => {
+                let tool_use_id = str_field("toolUseId");
+                self.permission_answered(&line.session_id, &tool_use_id);
+                if let Some(tool) = self
+                    .turn(&line.session_id)
+                    .tools
+                    .iter_mut()
+                    .find(|tool| tool.tool_use_id == tool_use_id)
+                {
+                    tool.status = InterruptedToolStatus::Completed;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Forget a permission request once it is answered.
+    pub(crate) fn permission_answered(&mut self, session_id: &str, tool_use_id: &str) {
+        if let Some(pending) = self.permissions.get_mut(session_id) {
+            pending.remove(tool_use_id);
+            if pending.is_empty() {
+                self.permissions.remove(session_id);
+            }
+        }
+    }
+
+    pub(crate) fn activity(&self) -> TurnActivity {
+        TurnActivity {
+            running: self.turns.len(),
+            pending_permissions: self.permissions.values().map(HashSet::len).sum(),
+        }
+    }
+
+    pub(crate) fn is_running(&self, session_id: &str) -> bool {
+        self.turns.contains_key(session_id)
+    }
+
+    fn turn(&mut self, session_id: &str) -> &mut TurnProgress {
+        self.turns.entry(session_id.to_string()).or_default()
+    }
+
+    /// Take a session's turn so far, if it produced anything.
+    pub(crate) fn take(&mut self, session_id: &str) -> Option<TurnProgress> {
+        self.turns
+            .remove(session_id)
+            .filter(|turn| !turn.is_empty())
+    }
+
+    /// Put back a turn whose interrupt did not go through.
+    pub(crate) fn restore(&mut self, session_id: &str, turn: TurnProgress) {
+        self.turns.insert(session_id.to_string(), turn);
+    }
+}
+
+fn transcript_line_count(path: &Path) -> usize {
+    std::fs::read(path)
+        .map(|data| data.iter().filter(|byte| **byte == b'\n').count())
+        .unwrap_or(0)
+}
+
+/// Save an interrupted turn on its session in the registry. Sessions not
+/// in the registry still get the turn back, unsaved.
+pub(crate) async fn save_interrupted_turn(
+    state: &AppState,
+    session_id: &str,
+    turn: TurnProgress,
+) -> Result<InterruptedTurn, String> {
+    let mut registry = state.registry.lock().await;
+    let Some(session) = registry.sessions.get_mut(session_id) else {
+        return Ok(turn.into_interrupted(0));
+    };
+    let transcript_line = session
+        .transcript_path
+        .as_deref()
+        .map(|path| transcript_line_count(Path::new(path)))
+        .unwrap_or(0);
+    let interrupted = turn.into_interrupted(transcript_line);
+    session.interrupted_turns.push(interrupted.clone());
+    let overflow = session
+        .interrupted_turns
+        .len()
+        .saturating_sub(MAX_INTERRUPTED_TURNS);
+    session.interrupted_turns.drain(..overflow);
+    write_registry(&state.registry_path, &registry)?;
+    Ok(interrupted)
+}
+
+/// History item for an interrupted turn.
+pub(crate) fn interrupted_turn_item(turn: &InterruptedTurn) -> Value {
+    serde_json::json!({
+        "id": turn.id,
+        "kind": "interrupted",
+        "text": turn.text,
+        "tools": turn.tools,
+        "inputTokens": turn.input_tokens,
+        "outputTokens": turn.output_tokens,
+        "interruptedAt": turn.interrupted_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{TurnActivity, TurnTracker};
+    use crate::claude::BridgeLine;
+    use crate::types::InterruptedToolStatus;
+
+    fn line(event_type: &str, payload: Value) -> BridgeLine {
+        BridgeLine {
+            event_type: event_type.to_string(),
+            session_id: "s1".to_string(),
+            workspace_id: "w1".to_string(),
+            timestamp: 0,
+            payload,
+        }
+    }
+
+    fn delta(event: Value) -> BridgeLine {
+        line(
+            "message/delta",
+            json!({ "event": event, "parentToolUseId": null }),
+        )
+    }
+
+    fn text(text: &str) -> BridgeLine {
+        delta(
+            json!({ "type": "content_block_delta", "delta": { "type": "text_delta", "text": text } }),
+        )
+    }
+
+    #[test]
+    fn keeps_partial_text_tools_and_usage() {
+        let mut tracker = TurnTracker::default();
+        tracker.observe(&delta(json!({
+            "type": "message_start",
+            "message": { "usage": { "input_tokens": 100, "output_tokens": 1 } },
+        })));
+        tracker.observe(&text("Let me "));
+        tracker.observe(&text("check."));
+        tracker.observe(&delta(
+            json!({ "type": "message_delta", "usage": { "output_tokens": 12 } }),
+        ));
+        tracker.observe(&line(
+            "message/complete",
+            json!({ "uuid": "m1", "message": { "content": [{ "type": "text", "text": "Let me check." }] } }),
+        ));
+        for tool_use_id in ["t1", "t2"] {
+            tracker.observe(&line(
+                "tool/started",
+                json!({ "toolName": "Bash", "toolUseId": tool_use_id, "input": {} }),
+            ));
+        }
+        tracker.observe(&line(
+            "tool/completed",
+            json!({ "toolName": "Bash", "toolUseId": "t1", "output": "ok" }),
+        ));
+        tracker.observe(&delta(json!({
+            "type": "message_start",
+            "message": { "usage": { "input_tokens": 150, "output_tokens": 1 } },
+        })));
+        tracker.observe(&text("Tests are"));
+        // Subagent text is not part of the reply.
+        tracker.observe(&line(
+            "message/delta",
+            json!({
+                "event": { "type": "content_block_delta", "delta": { "type": "text_delta", "text": "sub" } },
+                "parentToolUseId": "t2",
+            }),
+        ));
+
+        let turn = tracker.take("s1").unwrap().into_interrupted(7);
+        assert_eq!(turn.text, "Let me check.\n\nTests are");
+        assert_eq!((turn.input_tokens, turn.output_tokens), (250, 13));
+        assert_eq!(turn.transcript_line, 7);
+        let statuses: Vec<InterruptedToolStatus> = turn.tools.iter().map(|t| t.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                InterruptedToolStatus::Completed,
+                InterruptedToolStatus::Interrupted
+            ]
+        );
+        assert!(tracker.take("s1").is_none());
+    }
+
+    #[test]
+    fn result_ends_the_turn() {
+        let mut tracker = TurnTracker::default();
+        tracker.observe(&text("Done."));
+        tracker.observe(&line("result", json!({ "success": true })));
+        assert!(tracker.take("s1").is_none());
+
+        // Usage alone is not worth saving.
+        tracker.observe(&delta(json!({ "type": "message_start", "message": {} })));
+        assert!(tracker.take("s1").is_none());
+    }
+
+    #[test]
+    fn counts_running_turns_and_pending_permissions() {
+        let mut tracker = TurnTracker::default();
+        tracker.observe(&text("Working"));
+        let request = json!({ "toolName": "Bash", "toolUseId": "t1", "input": {} });
+        tracker.observe(&line("permission/request", request));
+        assert_eq!(
+            tracker.activity(),
+            TurnActivity {
+                running: 1,
+                pending_permissions: 1,
+            }
+        );
+        tracker.observe(&line(
+            "tool/started",
+            json!({ "toolName": "Bash", "toolUseId": "t1", "input": {} }),
+        ));
+        assert_eq!(tracker.activity().pending_permissions, 0);
+
+        tracker.observe(&line(
+            "permission/request",
+            json!({ "toolName": "Edit", "toolUseId": "t2", "input": {} }),
+        ));
+        tracker.permission_answered("s1", "t2");
+        assert_eq!(tracker.activity().pending_permissions, 0);
+        tracker.observe(&line("session/closed", json!({})));
+        assert_eq!(tracker.activity(), TurnActivity::default());
+    }
+}
@@ -1,29 +1,81 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct GitFileStatus {
     pub(crate) path: String,
     pub(crate) status: String,
     pub(crate) additions: i64,
     pub(crate) deletions: i64,
+    /// Owners from CODEOWNERS, in file order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) owners: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) lfs: Option<LfsInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Large-file concerns for a changed path; absent for ordinary files.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LfsInfo {
+    /// Matched by a `filter=lfs` attribute.
+    pub(crate) tracked: bool,
+    /// The working copy is an LFS pointer whose text changed, so the commit
+    /// would reference a different (possibly missing) object.
+    pub(crate) pointer_edited: bool,
+    /// Size in bytes when over the large-file threshold and not tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) oversized: Option<u64>,
+}
+
+/// Changed spans of one diff line: `line` indexes the diff's lines and
+/// each range is `[start, end)` in UTF-16 units after the `+`/`-` prefix.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+pub(crate) struct IntralineChange {
+    pub(crate) line: usize,
+    pub(crate) ranges: Vec<[u32; 2]>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct GitFileDiff {
     pub(crate) path: String,
     pub(crate) diff: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) owners: Vec<String>,
+    /// Syntax-highlighted HTML per line of `diff`, when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) highlighted: Option<Vec<String>>,
+    /// Word-level changes on modified lines, when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) intraline: Option<Vec<IntralineChange>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) lfs: Option<LfsInfo>,
+    /// Set when `diff` was cut short; the rest comes from `get_file_diff`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) truncated: Option<DiffTruncation>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// How much of an oversized diff was sent. Hunks are counted from 0, so
+/// the first hidden one is `shown_hunks`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiffTruncation {
+    pub(crate) total_lines: usize,
+    pub(crate) shown_lines: usize,
+    pub(crate) total_hunks: usize,
+    pub(crate) shown_hunks: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct GitLogEntry {
     pub(crate) sha: String,
     pub(crate) summary: String,
     pub(crate) author: String,
+    /// Commit time, epoch millis like every other timestamp.
     pub(crate) timestamp: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct GitLogResponse {
     pub(crate) total: usize,
     pub(crate) entries: Vec<GitLogEntry>,
@@ -39,7 +91,7 @@ pub(crate) struct GitLogResponse {
     pub(crate) upstream: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct GitHubIssue {
     pub(crate) number: u64,
     pub(crate) title: String,
@@ -48,7 +100,7 @@ pub(crate) struct GitHubIssue {
     pub(crate) updated_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct GitHubIssuesResponse {
     pub(crate) total: usize,
     pub(crate) issues: Vec<GitHubIssue>,
@@ -74,9 +126,12 @@ pub(crate) struct WorkspaceEntry {
     pub(crate) worktree: Option<WorktreeInfo>,
     #[serde(default)]
     pub(crate) settings: WorkspaceSettings,
+    /// Workflows, queued messages and auto-approval are on hold.
+    #[serde(default, rename = "automationPaused")]
+    pub(crate) automation_paused: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct WorkspaceInfo {
     pub(crate) id: String,
     pub(crate) name: String,
@@ -91,9 +146,11 @@ pub(crate) struct WorkspaceInfo {
     pub(crate) worktree: Option<WorktreeInfo>,
     #[serde(default)]
     pub(crate) settings: WorkspaceSettings,
+    #[serde(default, rename = "automationPaused")]
+    pub(crate) automation_paused: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum WorkspaceKind {
     Main,
@@ -112,13 +169,13 @@ impl WorkspaceKind {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct WorktreeInfo {
     pub(crate) branch: String,
 }
 
 /// MCP Server configuration for workspace-level settings
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
 pub(crate) struct McpServerConfig {
     #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
     pub(crate) server_type: Option<String>, // "stdio", "sse", "http"
@@ -135,14 +192,14 @@ pub(crate) struct McpServerConfig {
 }
 
 /// Plugin configuration for workspace-level settings
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct PluginConfig {
     #[serde(rename = "type")]
     pub(crate) plugin_type: String, // "local"
     pub(crate) path: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
 pub(crate) struct WorkspaceSettings {
     #[serde(default, rename = "sidebarCollapsed")]
     pub(crate) sidebar_collapsed: bool,
@@ -152,9 +209,476 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) mcp_servers: Option<HashMap<String, McpServerConfig>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) plugins: Option<Vec<PluginConfig>>,
+    #[serde(default, rename = "accountId", skip_serializing_if = "Option::is_none")]
+    pub(crate) account_id: Option<String>,
+    /// Custom API gateway (LiteLLM, corporate proxy) used instead of api.anthropic.com.
+    #[serde(default, rename = "apiBaseUrl", skip_serializing_if = "Option::is_none")]
+    pub(crate) api_base_url: Option<String>,
+    #[serde(default, rename = "apiHeaders", skip_serializing_if = "Option::is_none")]
+    pub(crate) api_headers: Option<HashMap<String, String>>,
+    /// Client or cost center that usage is billed to; worktrees inherit it.
+    #[serde(default, rename = "billingTag", skip_serializing_if = "Option::is_none")]
+    pub(crate) billing_tag: Option<String>,
+    /// CPU priority of the workspace's Claude process and the tools it runs.
+    #[serde(
+        default,
+        rename = "processPriority",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) process_priority: Option<ProcessPriority>,
+    /// What to do with Claude's thinking blocks in this workspace's sessions.
+    #[serde(
+        default,
+        rename = "thinkingBlocks",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) thinking_blocks: Option<ThinkingBlocks>,
+    /// Jira or Linear issue key; sessions without their own link use it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ticket: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ClaudeAccountKind {
+    /// Claude.ai subscription login managed by the Claude Code CLI.
+    Subscription,
+    /// Anthropic API key stored in the OS keychain.
+    ApiKey,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ClaudeAuthMode {
+    /// Use whatever login the Claude Code CLI already has.
+    Subscription,
+    /// Bill per token with the Anthropic API key from the OS keychain.
+    ApiKey,
+}
+
+/// Editor that `open_in_editor` launches.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum EditorKind {
+    Vscode,
+    Cursor,
+    Zed,
+    Jetbrains,
+    /// The OS default app for the file type.
+    System,
+}
+
+/// Which Claude event channels the webview subscribes to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum EventScope {
+    /// `claude-event`: every workspace, so background sessions stay live.
+    #[default]
+    All,
+    /// `claude-event:{workspace_id}` for the workspace on screen only.
+    Workspace,
+}
+
+/// CPU priority for agent processes, applied as a Unix nice level.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ProcessPriority {
+    #[default]
+    Normal,
+    Low,
+    /// Only runs when the machine is otherwise idle.
+    Background,
+}
+
+impl ProcessPriority {
+    pub(crate) fn niceness(self) -> i32 {
+        match self {
+            ProcessPriority::Normal => 0,
+            ProcessPriority::Low => 10,
+            ProcessPriority::Background => 19,
+        }
+    }
+}
+
+/// Handling of thinking (extended reasoning) blocks.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ThinkingBlocks {
+    /// Kept as reasoning items and shown in the conversation.
+    #[default]
+    Show,
+    /// Kept, but left out of the conversation view.
+    Hide,
+    /// Dropped from live sessions and loaded history.
+    Discard,
+}
+
+impl Default for ClaudeAuthMode {
+    fn default() -> Self {
+        ClaudeAuthMode::Subscription
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ClaudeProvider {
+    Anthropic,
+    Bedrock,
+    Vertex,
+}
+
+impl Default for ClaudeProvider {
+    fn default() -> Self {
+        ClaudeProvider::Anthropic
+    }
+}
+
+/// Provider-specific fields; only the ones for the selected provider are used.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProviderSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) aws_region: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) aws_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) vertex_project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) vertex_region: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TicketProvider {
+    Jira,
+    Linear,
+}
+
+/// Jira or Linear linkage. API tokens are in the keychain.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TicketSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) provider: Option<TicketProvider>,
+    /// e.g. `https://acme.atlassian.net`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) jira_base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) jira_email: Option<String>,
+    /// Post the session summary to its ticket when the session is archived.
+    #[serde(default)]
+    pub(crate) comment_on_complete: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DigestDelivery {
+    /// Write Markdown and HTML files to `folder`.
+    Folder,
+    /// Send through an SMTP server; the password is in the keychain.
+    Smtp,
+    /// Send through the Mailgun messages API; the key is in the keychain.
+    Mailgun,
+}
+
+impl Default for DigestDelivery {
+    fn default() -> Self {
+        DigestDelivery::Folder
+    }
+}
+
+/// Daily activity digest. Credentials never live here.
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DigestSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Local hour (0-23) after which the day's digest is sent.
+    #[serde(default = "default_digest_hour")]
+    pub(crate) hour: u8,
+    #[serde(default)]
+    pub(crate) delivery: DigestDelivery,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) folder: Option<String>,
+    /// e.g. `smtps://smtp.example.com:465`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) smtp_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) smtp_user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) mailgun_domain: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) from: Option<String>,
+    #[serde(default)]
+    pub(crate) to: Vec<String>,
+}
+
+fn default_digest_hour() -> u8 {
+    18
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hour: default_digest_hour(),
+            delivery: DigestDelivery::default(),
+            folder: None,
+            smtp_url: None,
+            smtp_user: None,
+            mailgun_domain: None,
+            from: None,
+            to: Vec::new(),
+        }
+    }
+}
+
+/// OTLP export of session metrics and spans. Off unless enabled; export
+/// headers are kept in the keychain.
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TelemetrySettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// OTLP/HTTP base URL, e.g. `http://localhost:4318`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) endpoint: Option<String>,
+    #[serde(default = "default_telemetry_service_name")]
+    pub(crate) service_name: String,
+}
+
+fn default_telemetry_service_name() -> String {
+    "claude-monitor".to_string()
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            service_name: default_telemetry_service_name(),
+        }
+    }
+}
+
+/// Opt-in capture of backend panics. Reports stay on disk until submitted.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CrashReportSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Endpoint `submit_crash_report` posts report JSON to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) submit_url: Option<String>,
+}
+
+/// Outbound proxy for the bridge, curl and gh. When unset, the standard
+/// `HTTPS_PROXY`/`NO_PROXY` environment variables apply.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProxySettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<String>,
+    /// Comma-separated hosts that bypass the proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) no_proxy: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ThemeMode {
+    /// Follow the OS color scheme.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Look of every window. Changes are broadcast as `appearance-changed`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppearanceSettings {
+    #[serde(default)]
+    pub(crate) theme: ThemeMode,
+    /// `#rrggbb`; the built-in blue when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) accent_color: Option<String>,
+    /// Message and composer text size in px.
+    #[serde(default = "default_font_size")]
+    pub(crate) font_size: u32,
+}
+
+fn default_font_size() -> u32 {
+    14
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self {
+            theme: ThemeMode::System,
+            accent_color: None,
+            font_size: default_font_size(),
+        }
+    }
+}
+
+/// Runtime that runs the bridge script.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BridgeRuntime {
+    /// `npx tsx` on the TypeScript source.
+    #[default]
+    Tsx,
+    Bun,
+    /// `node` on the compiled `dist/index.js`.
+    Node,
+    /// `command` with `args` as given.
+    Custom,
+}
+
+/// How the bridge process is launched, for setups the default `npx tsx`
+/// doesn't suit.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BridgeLaunchSettings {
+    #[serde(default)]
+    pub(crate) runtime: BridgeRuntime,
+    /// Program to run for the custom runtime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) command: Option<String>,
+    /// Passed before the script; for the custom runtime, `{bridge}` stands
+    /// for the script path.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) args: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) env: BTreeMap<String, String>,
+    /// Bridge script to run instead of the bundled one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) script: Option<String>,
+}
+
+/// Large-file safeguards, with or without Git LFS.
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LfsSettings {
+    /// Files over this size that LFS does not track are flagged.
+    #[serde(default = "default_large_file_mb")]
+    pub(crate) large_file_mb: u64,
+    /// Make `check_commit_size` and the pre-commit hook fail when staged
+    /// files are over the limit.
+    #[serde(default)]
+    pub(crate) block_large_commits: bool,
+    /// Install pre-commit and pre-push hooks in every workspace, so commits
+    /// and pushes made outside the app (e.g. by the agent's shell) are
+    /// checked too.
+    #[serde(default)]
+    pub(crate) git_hooks: bool,
+}
+
+fn default_large_file_mb() -> u64 {
+    50
+}
+
+impl LfsSettings {
+    pub(crate) fn threshold_bytes(&self) -> u64 {
+        self.large_file_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+impl Default for LfsSettings {
+    fn default() -> Self {
+        Self {
+            large_file_mb: default_large_file_mb(),
+            block_large_commits: false,
+            git_hooks: false,
+        }
+    }
+}
+
+/// Do-not-disturb: while on, permission requests wait in a queue instead of
+/// prompting.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DoNotDisturbSettings {
+    /// Turned on by hand; ends at `until` if set.
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Epoch ms when a manual focus period ends.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) until: Option<u64>,
+    /// Daily quiet hours, on without touching the toggle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) schedule: Option<QuietHours>,
+}
+
+/// Local time window as minutes past midnight; `end` before `start` wraps
+/// past midnight.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QuietHours {
+    pub(crate) start: u16,
+    pub(crate) end: u16,
+}
+
+/// Throttling while the machine runs on battery or is thermally throttled.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PowerSavingSettings {
+    #[serde(default = "default_true")]
+    pub(crate) enabled: bool,
+    /// Open sessions allowed while throttled; 0 for no limit.
+    #[serde(default = "default_throttled_sessions")]
+    pub(crate) max_sessions: u32,
+    /// Stop the background transcript indexer while throttled.
+    #[serde(default = "default_true")]
+    pub(crate) pause_indexing: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_throttled_sessions() -> u32 {
+    2
+}
+
+impl Default for PowerSavingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_sessions: default_throttled_sessions(),
+            pause_indexing: true,
+        }
+    }
+}
+
+/// Token rates for models matching `model` (an id or id prefix), in USD per
+/// million tokens.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelRate {
+    pub(crate) model: String,
+    pub(crate) input: f64,
+    pub(crate) output: f64,
+    #[serde(default)]
+    pub(crate) cache_read: f64,
+    #[serde(default)]
+    pub(crate) cache_write: f64,
+}
+
+/// Claude credential profile. Secrets never live here; API keys are kept
+/// in the OS keychain under the account id.
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+pub(crate) struct ClaudeAccount {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) kind: ClaudeAccountKind,
+    /// Separate Claude config dir so several subscription logins can coexist.
+    #[serde(default, rename = "configDir", skip_serializing_if = "Option::is_none")]
+    pub(crate) config_dir: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct AppSettings {
     #[serde(default, rename = "codexBin")]
     pub(crate) codex_bin: Option<String>,
@@ -169,6 +693,105 @@ pub(crate) struct AppSettings {
     pub(crate) default_permission_mode: String,
     #[serde(default = "default_ui_scale", rename = "uiScale")]
     pub(crate) ui_scale: f64,
+    #[serde(default)]
+    pub(crate) appearance: AppearanceSettings,
+    #[serde(default, rename = "claudeAccounts")]
+    pub(crate) claude_accounts: Vec<ClaudeAccount>,
+    #[serde(default, rename = "defaultAccountId")]
+    pub(crate) default_account_id: Option<String>,
+    #[serde(default, rename = "claudeAuthMode")]
+    pub(crate) claude_auth_mode: ClaudeAuthMode,
+    /// Model id sent verbatim to the API in API-key mode when none is picked.
+    #[serde(default, rename = "apiModel")]
+    pub(crate) api_model: Option<String>,
+    #[serde(default, rename = "claudeProvider")]
+    pub(crate) claude_provider: ClaudeProvider,
+    #[serde(default, rename = "providerSettings")]
+    pub(crate) provider_settings: ProviderSettings,
+    /// Bridge event type -> coalescing window in ms; 0 emits each event alone.
+    #[serde(default = "default_event_batch_windows", rename = "eventBatchWindows")]
+    pub(crate) event_batch_windows: HashMap<String, u64>,
+    /// Bridge request method -> seconds to wait for its reply; `*` covers
+    /// unlisted methods and 0 waits indefinitely.
+    #[serde(default = "default_request_timeouts", rename = "requestTimeouts")]
+    pub(crate) request_timeouts: HashMap<String, u64>,
+    /// Read-only mode: mutating commands are refused until it is turned off.
+    #[serde(default, rename = "observerMode")]
+    pub(crate) observer_mode: bool,
+    /// Keep each session's event stream in memory for replay export.
+    #[serde(default, rename = "recordReplays")]
+    pub(crate) record_replays: bool,
+    /// Allow streaming sessions to viewers on the local network.
+    #[serde(default, rename = "lanSharing")]
+    pub(crate) lan_sharing: bool,
+    /// Git URL of a shared prompts/templates/permissions repo.
+    #[serde(default, rename = "teamPromptsRepo")]
+    pub(crate) team_prompts_repo: Option<String>,
+    #[serde(default = "default_team_prompts_sync_minutes", rename = "teamPromptsSyncMinutes")]
+    pub(crate) team_prompts_sync_minutes: u64,
+    /// Slack channel id for bot-token threads and reply polling.
+    #[serde(default, rename = "slackChannel")]
+    pub(crate) slack_channel: Option<String>,
+    #[serde(default)]
+    pub(crate) digest: DigestSettings,
+    #[serde(default)]
+    pub(crate) telemetry: TelemetrySettings,
+    #[serde(default)]
+    pub(crate) tickets: TicketSettings,
+    #[serde(default, rename = "crashReports")]
+    pub(crate) crash_reports: CrashReportSettings,
+    #[serde(default, rename = "bridgeLaunch")]
+    pub(crate) bridge_launch: BridgeLaunchSettings,
+    #[serde(default)]
+    pub(crate) proxy: ProxySettings,
+    /// PEM bundle trusted by the bridge, curl and gh, for networks that
+    /// intercept TLS.
+    #[serde(default, rename = "caBundle", skip_serializing_if = "Option::is_none")]
+    pub(crate) ca_bundle: Option<String>,
+    /// BCP 47 tag for backend-generated text and dates, e.g. `en-US`; ISO
+    /// dates in English when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) locale: Option<String>,
+    /// `local`, `UTC` or a fixed offset like `+02:00`.
+    #[serde(default, rename = "timeZone", skip_serializing_if = "Option::is_none")]
+    pub(crate) time_zone: Option<String>,
+    /// Remote pricing JSON refreshed into the local cache on demand.
+    #[serde(default, rename = "pricingUrl", skip_serializing_if = "Option::is_none")]
+    pub(crate) pricing_url: Option<String>,
+    /// Rates that win over the bundled and remote tables, e.g. for gateways.
+    #[serde(default, rename = "pricingOverrides")]
+    pub(crate) pricing_overrides: Vec<ModelRate>,
+    /// Start a session right away in a folder dropped on the window.
+    #[serde(default, rename = "startSessionOnDrop")]
+    pub(crate) start_session_on_drop: bool,
+    /// Editor for clickable file names; detected from installed CLIs when
+    /// unset.
+    #[serde(default)]
+    pub(crate) editor: Option<EditorKind>,
+    #[serde(default)]
+    pub(crate) lfs: LfsSettings,
+    /// Refuse commits whose staged changes add credentials, checked by the
+    /// pre-commit hook.
+    #[serde(default, rename = "blockSecretCommits")]
+    pub(crate) block_secret_commits: bool,
+    #[serde(default, rename = "eventScope")]
+    pub(crate) event_scope: EventScope,
+    #[serde(default, rename = "doNotDisturb")]
+    pub(crate) do_not_disturb: DoNotDisturbSettings,
+    /// Turns automation may start in a session before the user sends a
+    /// message; 0 for no limit.
+    #[serde(default = "default_max_autonomous_turns", rename = "maxAutonomousTurns")]
+    pub(crate) max_autonomous_turns: u32,
+    /// Hold the digest, team prompt sync and telemetry export for a couple
+    /// of minutes after the system wakes from sleep.
+    #[serde(default, rename = "pauseSchedulersOnSleep")]
+    pub(crate) pause_schedulers_on_sleep: bool,
+    #[serde(default, rename = "powerSaving")]
+    pub(crate) power_saving: PowerSavingSettings,
+    /// Turns running at once across sessions; 0 for no limit. Turns over
+    /// the limit wait, the user's ahead of automation's.
+    #[serde(default, rename = "maxConcurrentTurns")]
+    pub(crate) max_concurrent_turns: u32,
 }
 
 fn default_access_mode() -> String {
@@ -183,6 +806,27 @@ fn default_permission_mode() -> String {
     "default".to_string()
 }
 
+fn default_event_batch_windows() -> HashMap<String, u64> {
+    HashMap::from([("message/delta".to_string(), 16)])
+}
+
+fn default_request_timeouts() -> HashMap<String, u64> {
+    HashMap::from([
+        ("*".to_string(), 60),
+        ("session/start".to_string(), 120),
+        ("session/resume".to_string(), 120),
+        ("text/complete".to_string(), 300),
+    ])
+}
+
+fn default_team_prompts_sync_minutes() -> u64 {
+    30
+}
+
+fn default_max_autonomous_turns() -> u32 {
+    10
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -191,13 +835,49 @@ impl Default for AppSettings {
             default_access_mode: "current".to_string(),
             default_permission_mode: "default".to_string(),
             ui_scale: 1.0,
+            appearance: AppearanceSettings::default(),
+            claude_accounts: Vec::new(),
+            default_account_id: None,
+            claude_auth_mode: ClaudeAuthMode::Subscription,
+            api_model: None,
+            claude_provider: ClaudeProvider::Anthropic,
+            provider_settings: ProviderSettings::default(),
+            event_batch_windows: default_event_batch_windows(),
+            request_timeouts: default_request_timeouts(),
+            observer_mode: false,
+            record_replays: false,
+            lan_sharing: false,
+            team_prompts_repo: None,
+            team_prompts_sync_minutes: default_team_prompts_sync_minutes(),
+            slack_channel: None,
+            digest: DigestSettings::default(),
+            telemetry: TelemetrySettings::default(),
+            tickets: TicketSettings::default(),
+            crash_reports: CrashReportSettings::default(),
+            bridge_launch: BridgeLaunchSettings::default(),
+            proxy: ProxySettings::default(),
+            ca_bundle: None,
+            locale: None,
+            time_zone: None,
+            pricing_url: None,
+            pricing_overrides: Vec::new(),
+            start_session_on_drop: false,
+            editor: None,
+            lfs: LfsSettings::default(),
+            block_secret_commits: false,
+            event_scope: EventScope::All,
+            do_not_disturb: DoNotDisturbSettings::default(),
+            max_autonomous_turns: default_max_autonomous_turns(),
+            pause_schedulers_on_sleep: false,
+            power_saving: PowerSavingSettings::default(),
+            max_concurrent_turns: 0,
         }
     }
 }
 
 // Registry types for Claude session management
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum SessionStatus {
     Active,
@@ -210,7 +890,7 @@ impl Default for SessionStatus {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub(crate) struct SessionEntry {
     #[serde(rename = "sessionId")]
     pub(crate) session_id: String,
@@ -227,6 +907,149 @@ pub(crate) struct SessionEntry {
     pub(crate) project_path: Option<String>,
     #[serde(default)]
     pub(crate) status: SessionStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) usage: Option<SessionUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) summary: Option<SessionSummary>,
+    /// Files the agent read or changed, in first-touched order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) files: Vec<SessionFile>,
+    /// Partial turns saved when the user interrupted them, oldest first.
+    #[serde(
+        default,
+        rename = "interruptedTurns",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub(crate) interrupted_turns: Vec<InterruptedTurn>,
+    /// Newest message the user has seen, set by `mark_session_read`.
+    #[serde(
+        default,
+        rename = "lastReadMessageId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) last_read_message_id: Option<String>,
+    /// Transcript line of `last_read_message_id`; later replies are unread.
+    #[serde(
+        default,
+        rename = "lastReadLine",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) last_read_line: Option<usize>,
+    /// Free-form fields set by `set_session_metadata`, e.g. a ticket id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) metadata: BTreeMap<String, String>,
+    /// Attended and agent time by local day (`YYYY-MM-DD`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) time: BTreeMap<String, SessionTime>,
+}
+
+/// Time spent on a session in one day.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionTime {
+    /// The window was focused with the session selected.
+    #[serde(default)]
+    pub(crate) attended_ms: u64,
+    /// Turns were running, from the `result` durations.
+    #[serde(default)]
+    pub(crate) agent_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum InterruptedToolStatus {
+    /// Only while the turn is being tracked; never part of a saved turn.
+    Running,
+    Completed,
+    Interrupted,
+}
+
+/// A tool call as it stood when its turn was interrupted.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InterruptedTool {
+    pub(crate) tool_use_id: String,
+    pub(crate) tool_name: String,
+    pub(crate) status: InterruptedToolStatus,
+}
+
+/// What a turn had produced when the user interrupted it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InterruptedTurn {
+    pub(crate) id: String,
+    pub(crate) interrupted_at: u64,
+    /// Transcript lines written before the interrupt; places the turn in
+    /// the session history.
+    pub(crate) transcript_line: usize,
+    /// Assistant text streamed so far.
+    pub(crate) text: String,
+    pub(crate) tools: Vec<InterruptedTool>,
+    pub(crate) input_tokens: u64,
+    pub(crate) output_tokens: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum FileAction {
+    Read,
+    Edit,
+    Write,
+}
+
+/// A file touched by a session's tools.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionFile {
+    /// Relative to the session's cwd when inside it.
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) reads: u64,
+    /// Edits and whole-file writes.
+    #[serde(default)]
+    pub(crate) edits: u64,
+    pub(crate) last_action: FileAction,
+    pub(crate) last_touched_at: u64,
+}
+
+/// Cached digest of a transcript; stale once the transcript grows past
+/// `transcript_bytes`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionSummary {
+    pub(crate) text: String,
+    pub(crate) max_words: u32,
+    pub(crate) transcript_bytes: u64,
+    pub(crate) created_at: u64,
+}
+
+/// Token usage and cost accumulated from the bridge's `result` events.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionUsage {
+    #[serde(default)]
+    pub(crate) input_tokens: u64,
+    #[serde(default)]
+    pub(crate) output_tokens: u64,
+    #[serde(default)]
+    pub(crate) cache_read_input_tokens: u64,
+    #[serde(default)]
+    pub(crate) cache_creation_input_tokens: u64,
+    #[serde(default)]
+    pub(crate) cost_usd: f64,
+    #[serde(default)]
+    pub(crate) requests: u64,
+    /// Requests whose `result` reported failure.
+    #[serde(default)]
+    pub(crate) failed_requests: u64,
+    #[serde(default)]
+    pub(crate) duration_ms: u64,
+    /// Subtype of the latest `result`, e.g. `success` or `error_max_turns`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) last_outcome: Option<String>,
+    /// Model reported when the session started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) model: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -263,7 +1086,12 @@ impl Default for ThreadRegistry {
 
 #[cfg(test)]
 mod tests {
-    use super::{AppSettings, WorkspaceEntry, WorkspaceKind, ThreadRegistry, SessionEntry, SessionStatus};
+    use std::collections::BTreeMap;
+
+    use super::{
+        AppSettings, ClaudeAccount, ClaudeAccountKind, ClaudeAuthMode, DoNotDisturbSettings,
+        EventScope, SessionEntry, SessionStatus, ThreadRegistry, WorkspaceEntry, WorkspaceKind,
+    };
 
     #[test]
     fn app_settings_defaults_from_empty_json() {
@@ -271,6 +1099,23 @@ mod tests {
         assert!(settings.codex_bin.is_none());
         assert_eq!(settings.default_access_mode, "current");
         assert!((settings.ui_scale - 1.0).abs() < f64::EPSILON);
+        assert!(settings.claude_accounts.is_empty());
+        assert!(settings.default_account_id.is_none());
+        assert_eq!(settings.claude_auth_mode, ClaudeAuthMode::Subscription);
+        assert_eq!(settings.event_batch_windows.get("message/delta"), Some(&16));
+        assert_eq!(settings.request_timeouts.get("*"), Some(&60));
+        assert!(!settings.observer_mode);
+        assert!(!settings.lan_sharing);
+        assert_eq!(settings.team_prompts_sync_minutes, 30);
+        assert_eq!(settings.digest.hour, 18);
+        assert!(!settings.start_session_on_drop);
+        assert!(settings.editor.is_none());
+        assert_eq!(settings.lfs.large_file_mb, 50);
+        assert!(!settings.lfs.block_large_commits);
+        assert_eq!(settings.event_scope, EventScope::All);
+        assert_eq!(settings.do_not_disturb, DoNotDisturbSettings::default());
+        assert!(!settings.telemetry.enabled);
+        assert_eq!(settings.telemetry.service_name, "claude-monitor");
     }
 
     #[test]
@@ -283,6 +1128,8 @@ mod tests {
         assert!(entry.parent_id.is_none());
         assert!(entry.worktree.is_none());
         assert!(entry.settings.sort_order.is_none());
+        assert!(entry.settings.process_priority.is_none());
+        assert!(entry.settings.thinking_blocks.is_none());
     }
 
     #[test]
@@ -304,10 +1151,30 @@ mod tests {
             transcript_path: Some("/path/to/transcript.json".to_string()),
             project_path: Some("/path/to/project".to_string()),
             status: SessionStatus::Active,
+            usage: None,
+            summary: None,
+            files: Vec::new(),
+            interrupted_turns: Vec::new(),
+            last_read_message_id: None,
+            last_read_line: None,
+            metadata: BTreeMap::new(),
+            time: BTreeMap::new(),
         };
         let json = serde_json::to_string(&session).expect("serialize");
         let parsed: SessionEntry = serde_json::from_str(&json).expect("deserialize");
         assert_eq!(parsed.session_id, "test-123");
         assert_eq!(parsed.status, SessionStatus::Active);
     }
+
+    #[test]
+    fn claude_account_kind_uses_camel_case() {
+        let account: ClaudeAccount = serde_json::from_str(
+            r#"{"id":"work","label":"Work","kind":"apiKey"}"#,
+        )
+        .expect("account deserialize");
+        assert_eq!(account.kind, ClaudeAccountKind::ApiKey);
+        assert!(account.config_dir.is_none());
+        let json = serde_json::to_string(&account).expect("serialize");
+        assert!(!json.contains("configDir"));
+    }
 }
@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::process::Command;
+use tokio::time::{sleep, timeout};
+
+use crate::command_metrics::CommandTimer;
+
+/// How often OS preferences are re-read; none of the platforms notify
+/// without native bindings.
+const PREFS_POLL: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Default, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Accessibility and appearance preferences set in the OS.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SystemUiPrefs {
+    pub(crate) reduced_motion: bool,
+    pub(crate) high_contrast: bool,
+    pub(crate) color_scheme: ColorScheme,
+}
+
+/// Stdout of a settings query, or `None` when it fails (unset keys make
+/// `defaults` and `reg` exit non-zero).
+pub(crate) async fn query(program: &str, args: &[&str]) -> Option<String> {
+    let output = timeout(
+        Duration::from_secs(3),
+        Command::new(program).args(args).output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The data of `reg query ... /v <name>` output, e.g. `0x1` or `126`.
+#[cfg(any(target_os = "windows", test))]
+fn reg_value(output: &str, name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        (parts.next()? == name)
+            .then(|| parts.nth(1).map(str::to_string))
+            .flatten()
+    })
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn reg_number(output: &str, name: &str) -> Option<u64> {
+    let value = reg_value(output, name)?;
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+async fn read_prefs() -> SystemUiPrefs {
+    let flag = |key| async move {
+        query("defaults", &["read", "com.apple.universalaccess", key])
+            .await
+            .is_some_and(|value| value == "1")
+    };
+    let dark = query("defaults", &["read", "-g", "AppleInterfaceStyle"])
+        .await
+        .is_some_and(|value| value.eq_ignore_ascii_case("dark"));
+    SystemUiPrefs {
+        reduced_motion: flag("reduceMotion").await,
+        high_contrast: flag("increaseContrast").await,
+        color_scheme: if dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn read_prefs() -> SystemUiPrefs {
+    let reg = |key, name| async move {
+        query("reg", &["query", key, "/v", name])
+            .await
+            .and_then(|output| reg_number(&output, name))
+    };
+    // MinAnimate is "Animate windows when minimizing and maximizing", which
+    // the "Animation effects" switch turns off.
+    let animate = reg("HKCU\\Control Panel\\Desktop\\WindowMetrics", "MinAnimate").await;
+    // Bit 0 of Flags is HCF_HIGHCONTRASTON.
+    let contrast = reg("HKCU\\Control Panel\\Accessibility\\HighContrast", "Flags").await;
+    let light = reg(
+        "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+        "AppsUseLightTheme",
+    )
+    .await;
+    SystemUiPrefs {
+        reduced_motion: animate == Some(0),
+        high_contrast: contrast.is_some_and(|flags| flags & 1 == 1),
+        color_scheme: if light == Some(0) {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+async fn read_prefs() -> SystemUiPrefs {
+    // GNOME keys; other desktops leave the defaults.
+    let gsettings = |schema, key| async move { query("gsettings", &["get", schema, key]).await };
+    let animations = gsettings("org.gnome.desktop.interface", "enable-animations").await;
+    let contrast = gsettings("org.gnome.desktop.a11y.interface", "high-contrast").await;
+    let scheme = gsettings("org.gnome.desktop.interface", "color-scheme").await;
+    SystemUiPrefs {
+        reduced_motion: animations.as_deref() == Some("false"),
+        high_contrast: contrast.as_deref() == Some("true"),
+        color_scheme: if scheme.is_some_and(|scheme| scheme.contains("dark")) {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+    }
+}
+
+/// Emit `system-ui-prefs-changed` whenever the OS preferences change.
+pub(crate) fn spawn_ui_prefs_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last = read_prefs().await;
+        loop {
+            sleep(PREFS_POLL).await;
+            let prefs = read_prefs().await;
+            if prefs != last {
+                let _ = app.emit("system-ui-prefs-changed", &prefs);
+                last = prefs;
+            }
+        }
+    });
+}
+
+/// Reduced motion, high contrast and color scheme as set in the OS.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_system_ui_prefs(_timer: CommandTimer) -> Result<SystemUiPrefs, String> {
+    Ok(read_prefs().await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reg_number, reg_value};
+
+    #[test]
+    fn reads_reg_query_output() {
+        let output = "\r\nHKEY_CURRENT_USER\\Control Panel\\Accessibility\\HighContrast\r\n    Flags    REG_SZ    127\r\n";
+        assert_eq!(reg_value(output, "Flags").as_deref(), Some("127"));
+        assert_eq!(reg_number(output, "Flags"), Some(127));
+        let output = "    AppsUseLightTheme    REG_DWORD    0x0\n";
+        assert_eq!(reg_number(output, "AppsUseLightTheme"), Some(0));
+        assert_eq!(reg_number(output, "MinAnimate"), None);
+    }
+}
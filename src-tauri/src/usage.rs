@@ -0,0 +1,608 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+use tokio::sync::oneshot;
+
+use crate::command_metrics::CommandTimer;
+use crate::digest::day_bounds;
+use crate::pricing::{model_pricing, ModelPricing};
+use crate::state::AppState;
+use crate::types::{SessionEntry, SessionUsage, WorkspaceEntry};
+
+const UNTAGGED: &str = "untagged";
+
+/// Inclusive local dates (`YYYY-MM-DD`); an open end is unbounded.
+#[derive(Debug, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageRange {
+    #[serde(default)]
+    pub(crate) from: Option<String>,
+    #[serde(default)]
+    pub(crate) to: Option<String>,
+}
+
+/// Usage totals for one workspace, or for one billing tag when grouped.
+#[derive(Debug, Serialize, Clone, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageRow {
+    billing_tag: String,
+    /// Empty when rows are grouped by tag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_name: Option<String>,
+    sessions: u64,
+    requests: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_input_tokens: u64,
+    cache_creation_input_tokens: u64,
+    cost_usd: f64,
+}
+
+/// One row per session for spreadsheets and invoicing.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionUsageRow {
+    session_id: String,
+    workspace_id: String,
+    workspace_name: String,
+    billing_tag: String,
+    model: String,
+    created_at: u64,
+    last_activity: u64,
+    requests: u64,
+    failed_requests: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_input_tokens: u64,
+    cache_creation_input_tokens: u64,
+    /// Cost reported by the SDK.
+    cost_usd: f64,
+    /// Cost from the pricing table, when the model is priced.
+    estimated_cost_usd: Option<f64>,
+    duration_ms: u64,
+    outcome: String,
+    /// Fields set with `set_session_metadata`.
+    metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum UsageFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageExport {
+    path: String,
+    rows: usize,
+    cost_usd: f64,
+}
+
+/// Millisecond bounds `[start, end)` covering the range's days.
+pub(crate) fn range_bounds(range: &UsageRange) -> Result<(u64, u64), String> {
+    let parse = |date: &str| {
+        NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {date}"))
+    };
+    let start = match range.from.as_deref().filter(|date| !date.trim().is_empty()) {
+        Some(date) => day_bounds(parse(date)?).0,
+        None => 0,
+    };
+    let end = match range.to.as_deref().filter(|date| !date.trim().is_empty()) {
+        Some(date) => day_bounds(parse(date)?).1,
+        None => u64::MAX,
+    };
+    if start >= end {
+        return Err("Range ends before it starts".to_string());
+    }
+    Ok((start, end))
+}
+
+/// A workspace's own tag, else its parent's (worktrees bill like their repo).
+pub(crate) fn billing_tag(
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    entry: &WorkspaceEntry,
+) -> Option<String> {
+    let own = |entry: &WorkspaceEntry| {
+        entry
+            .settings
+            .billing_tag
+            .as_deref()
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+    };
+    own(entry).or_else(|| {
+        entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent| workspaces.get(parent))
+            .and_then(own)
+    })
+}
+
+fn add_usage(row: &mut UsageRow, usage: &SessionUsage) {
+    row.sessions += 1;
+    row.requests += usage.requests;
+    row.input_tokens += usage.input_tokens;
+    row.output_tokens += usage.output_tokens;
+    row.cache_read_input_tokens += usage.cache_read_input_tokens;
+    row.cache_creation_input_tokens += usage.cache_creation_input_tokens;
+    row.cost_usd += usage.cost_usd;
+}
+
+fn merge_rows(into: &mut UsageRow, row: &UsageRow) {
+    into.sessions += row.sessions;
+    into.requests += row.requests;
+    into.input_tokens += row.input_tokens;
+    into.output_tokens += row.output_tokens;
+    into.cache_read_input_tokens += row.cache_read_input_tokens;
+    into.cache_creation_input_tokens += row.cache_creation_input_tokens;
+    into.cost_usd += row.cost_usd;
+}
+
+/// Collapse per-workspace rows into one row per billing tag.
+fn group_rows_by_tag(rows: Vec<UsageRow>) -> Vec<UsageRow> {
+    let mut grouped: BTreeMap<String, UsageRow> = BTreeMap::new();
+    for row in &rows {
+        let group = grouped
+            .entry(row.billing_tag.clone())
+            .or_insert_with(|| UsageRow {
+                billing_tag: row.billing_tag.clone(),
+                ..UsageRow::default()
+            });
+        merge_rows(group, row);
+    }
+    grouped.into_values().collect()
+}
+
+/// Sum session usage per workspace for sessions active within the range.
+/// Usage is cumulative per session, so a session counts in full wherever
+/// its last activity falls.
+async fn aggregate_usage(
+    state: &AppState,
+    range: &UsageRange,
+    group_by_tag: bool,
+) -> Result<Vec<UsageRow>, String> {
+    let (start, end) = range_bounds(range)?;
+    let workspaces = state.workspaces.lock().await.clone();
+    let registry = state.registry.lock().await;
+    let mut rows: Vec<UsageRow> = Vec::new();
+    for (workspace_id, workspace) in &registry.workspaces {
+        let Some(entry) = workspaces.get(workspace_id) else {
+            continue;
+        };
+        let mut row = UsageRow {
+            billing_tag: billing_tag(&workspaces, entry).unwrap_or_else(|| UNTAGGED.to_string()),
+            workspace_id: Some(workspace_id.clone()),
+            workspace_name: Some(entry.name.clone()),
+            ..UsageRow::default()
+        };
+        for session in workspace
+            .visible_session_ids
+            .iter()
+            .filter_map(|id| registry.sessions.get(id))
+            .filter(|session| session.last_activity >= start && session.last_activity < end)
+        {
+            if let Some(usage) = &session.usage {
+                add_usage(&mut row, usage);
+            }
+        }
+        if row.sessions > 0 {
+            rows.push(row);
+        }
+    }
+    rows.sort_by(|a, b| {
+        (&a.billing_tag, &a.workspace_name).cmp(&(&b.billing_tag, &b.workspace_name))
+    });
+    Ok(if group_by_tag {
+        group_rows_by_tag(rows)
+    } else {
+        rows
+    })
+}
+
+fn session_row(
+    workspace_id: &str,
+    workspace_name: &str,
+    billing_tag: &str,
+    session: &SessionEntry,
+    pricing: &ModelPricing,
+) -> SessionUsageRow {
+    let usage = session.usage.clone().unwrap_or_default();
+    let outcome = match (&usage.last_outcome, usage.requests) {
+        (_, 0) => "no requests".to_string(),
+        (Some(outcome), _) => outcome.clone(),
+        (None, _) if usage.failed_requests > 0 => "error".to_string(),
+        (None, _) => "success".to_string(),
+    };
+    SessionUsageRow {
+        session_id: session.session_id.clone(),
+        workspace_id: workspace_id.to_string(),
+        workspace_name: workspace_name.to_string(),
+        billing_tag: billing_tag.to_string(),
+        model: usage.model.clone().unwrap_or_default(),
+        created_at: session.created_at,
+        last_activity: session.last_activity,
+        requests: usage.requests,
+        failed_requests: usage.failed_requests,
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cache_read_input_tokens: usage.cache_read_input_tokens,
+        cache_creation_input_tokens: usage.cache_creation_input_tokens,
+        cost_usd: usage.cost_usd,
+        estimated_cost_usd: pricing.estimate(&usage),
+        duration_ms: usage.duration_ms,
+        outcome,
+        metadata: session.metadata.clone(),
+    }
+}
+
+/// Per-session rows from the registry for sessions active within the range.
+async fn session_usage_rows(
+    state: &AppState,
+    range: &UsageRange,
+) -> Result<Vec<SessionUsageRow>, String> {
+    let (start, end) = range_bounds(range)?;
+    let pricing = model_pricing(state).await;
+    let workspaces = state.workspaces.lock().await.clone();
+    let registry = state.registry.lock().await;
+    let mut rows = Vec::new();
+    for (workspace_id, workspace) in &registry.workspaces {
+        let Some(entry) = workspaces.get(workspace_id) else {
+            continue;
+        };
+        let tag = billing_tag(&workspaces, entry).unwrap_or_else(|| UNTAGGED.to_string());
+        rows.extend(
+            workspace
+                .visible_session_ids
+                .iter()
+                .filter_map(|id| registry.sessions.get(id))
+                .filter(|session| session.last_activity >= start && session.last_activity < end)
+                .map(|session| session_row(workspace_id, &entry.name, &tag, session, &pricing)),
+        );
+    }
+    rows.sort_by_key(|row| row.created_at);
+    Ok(rows)
+}
+
+fn session_usage_csv(rows: &[SessionUsageRow]) -> String {
+    let mut csv = [
+        "session_id",
+        "workspace_id",
+        "workspace",
+        "billing_tag",
+        "model",
+        "created_at",
+        "last_activity",
+        "requests",
+        "failed_requests",
+        "input_tokens",
+        "output_tokens",
+        "cache_read_input_tokens",
+        "cache_creation_input_tokens",
+        "cost_usd",
+        "estimated_cost_usd",
+        "duration_ms",
+        "outcome",
+        "metadata",
+    ]
+    .join(",");
+    csv.push('\n');
+    let timestamp = |millis: u64| {
+        chrono::DateTime::from_timestamp_millis(millis as i64)
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default()
+    };
+    for row in rows {
+        let fields = [
+            csv_field(&row.session_id),
+            csv_field(&row.workspace_id),
+            csv_field(&row.workspace_name),
+            csv_field(&row.billing_tag),
+            csv_field(&row.model),
+            timestamp(row.created_at),
+            timestamp(row.last_activity),
+            row.requests.to_string(),
+            row.failed_requests.to_string(),
+            row.input_tokens.to_string(),
+            row.output_tokens.to_string(),
+            row.cache_read_input_tokens.to_string(),
+            row.cache_creation_input_tokens.to_string(),
+            format!("{:.4}", row.cost_usd),
+            row.estimated_cost_usd
+                .map(|cost| format!("{cost:.4}"))
+                .unwrap_or_default(),
+            row.duration_ms.to_string(),
+            csv_field(&row.outcome),
+            csv_field(&metadata_field(&row.metadata)),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Flatten session metadata into one `key=value; key=value` column.
+fn metadata_field(metadata: &BTreeMap<String, String>) -> String {
+    metadata
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Quote a CSV field, and defuse values a spreadsheet would run as formulas.
+pub(crate) fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn usage_csv(rows: &[UsageRow], group_by_tag: bool) -> String {
+    let mut header = vec!["billing_tag"];
+    if !group_by_tag {
+        header.extend(["workspace_id", "workspace"]);
+    }
+    header.extend([
+        "sessions",
+        "requests",
+        "input_tokens",
+        "output_tokens",
+        "cache_read_input_tokens",
+        "cache_creation_input_tokens",
+        "cost_usd",
+    ]);
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for row in rows {
+        let mut fields = vec![csv_field(&row.billing_tag)];
+        if !group_by_tag {
+            fields.push(csv_field(row.workspace_id.as_deref().unwrap_or("")));
+            fields.push(csv_field(row.workspace_name.as_deref().unwrap_or("")));
+        }
+        fields.extend([
+            row.sessions.to_string(),
+            row.requests.to_string(),
+            row.input_tokens.to_string(),
+            row.output_tokens.to_string(),
+            row.cache_read_input_tokens.to_string(),
+            row.cache_creation_input_tokens.to_string(),
+            format!("{:.4}", row.cost_usd),
+        ]);
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+pub(crate) fn default_export_path(state: &AppState, stem: &str, extension: &str) -> PathBuf {
+    let dir = dirs::download_dir().unwrap_or_else(|| {
+        state
+            .storage_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("exports")
+    });
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(format!("{stem}-{stamp}.{extension}"))
+}
+
+/// Ask where to save an export in a native dialog, starting at
+/// `default_export_path`. The webview never supplies the path, so it
+/// cannot have files written outside what the user picked. `None` when
+/// the dialog is cancelled.
+async fn choose_export_path(
+    app: &AppHandle,
+    state: &AppState,
+    stem: &str,
+    extension: &str,
+) -> Result<Option<PathBuf>, String> {
+    let default = default_export_path(state, stem, extension);
+    let mut dialog = app
+        .dialog()
+        .file()
+        .add_filter(extension.to_uppercase(), &[extension]);
+    if let Some(dir) = default.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        dialog = dialog.set_directory(dir);
+    }
+    if let Some(name) = default.file_name() {
+        dialog = dialog.set_file_name(name.to_string_lossy());
+    }
+    let (tx, rx) = oneshot::channel();
+    dialog.save_file(move |path| {
+        let _ = tx.send(path);
+    });
+    match rx.await.ok().flatten() {
+        Some(path) => path.into_path().map(Some).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Usage totals per workspace, or per billing tag with `group_by_tag`.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn usage_summary(
+    range: Option<UsageRange>,
+    group_by_tag: Option<bool>,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<UsageRow>, String> {
+    aggregate_usage(
+        &state,
+        &range.unwrap_or_default(),
+        group_by_tag.unwrap_or(false),
+    )
+    .await
+}
+
+/// Write usage totals to a CSV file the user picks in a save dialog,
+/// which opens in the Downloads folder. `None` when cancelled.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn export_usage_csv(
+    range: Option<UsageRange>,
+    group_by_tag: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<UsageExport>, String> {
+    let group_by_tag = group_by_tag.unwrap_or(false);
+    let rows = aggregate_usage(&state, &range.unwrap_or_default(), group_by_tag).await?;
+    let Some(path) = choose_export_path(&app, &state, "usage", "csv").await? else {
+        return Ok(None);
+    };
+    std::fs::write(&path, usage_csv(&rows, group_by_tag)).map_err(|e| e.to_string())?;
+    Ok(Some(UsageExport {
+        path: path.to_string_lossy().to_string(),
+        rows: rows.len(),
+        cost_usd: rows.iter().map(|row| row.cost_usd).sum(),
+    }))
+}
+
+/// Write per-session usage rows as CSV or JSON to a file the user picks in
+/// a save dialog, which opens in the Downloads folder. `None` when
+/// cancelled.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn export_usage(
+    range: Option<UsageRange>,
+    format: UsageFormat,
+    app: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Option<UsageExport>, String> {
+    let rows = session_usage_rows(&state, &range.unwrap_or_default()).await?;
+    let (content, extension) = match format {
+        UsageFormat::Csv => (session_usage_csv(&rows), "csv"),
+        UsageFormat::Json => (
+            serde_json::to_string_pretty(&rows).map_err(|e| e.to_string())?,
+            "json",
+        ),
+    };
+    let Some(path) = choose_export_path(&app, &state, "session-usage", extension).await? else {
+        return Ok(None);
+    };
+    std::fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(Some(UsageExport {
+        path: path.to_string_lossy().to_string(),
+        rows: rows.len(),
+        cost_usd: rows.iter().map(|row| row.cost_usd).sum(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{
+        csv_field, group_rows_by_tag, session_row, session_usage_csv, usage_csv, UsageRow,
+    };
+    use crate::pricing::ModelPricing;
+    use crate::types::{SessionEntry, SessionStatus, SessionUsage};
+
+    fn row(tag: &str, workspace: &str, cost_usd: f64) -> UsageRow {
+        UsageRow {
+            billing_tag: tag.to_string(),
+            workspace_id: Some(workspace.to_string()),
+            workspace_name: Some(workspace.to_string()),
+            sessions: 1,
+            requests: 2,
+            input_tokens: 10,
+            output_tokens: 5,
+            cost_usd,
+            ..UsageRow::default()
+        }
+    }
+
+    #[test]
+    fn groups_usage_by_billing_tag() {
+        let grouped = group_rows_by_tag(vec![
+            row("acme", "api", 1.0),
+            row("untagged", "scratch", 0.25),
+            row("acme", "web", 0.5),
+        ]);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].billing_tag, "acme");
+        assert_eq!(grouped[0].sessions, 2);
+        assert_eq!(grouped[0].input_tokens, 20);
+        assert_eq!(grouped[0].cost_usd, 1.5);
+        assert_eq!(grouped[0].workspace_id, None);
+
+        let csv = usage_csv(&grouped, true);
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("billing_tag,sessions,"));
+        assert_eq!(lines.next(), Some("acme,2,4,20,10,0,0,1.5000"));
+    }
+
+    #[test]
+    fn escapes_csv_fields() {
+        assert_eq!(csv_field("Acme, Inc."), "\"Acme, Inc.\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("=HYPERLINK()"), "'=HYPERLINK()");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+
+    #[test]
+    fn exports_one_row_per_session() {
+        let session = SessionEntry {
+            session_id: "s1".to_string(),
+            cwd: "/repo".to_string(),
+            preview: None,
+            created_at: 0,
+            last_activity: 60_000,
+            transcript_path: None,
+            project_path: None,
+            status: SessionStatus::Active,
+            usage: Some(SessionUsage {
+                input_tokens: 100,
+                output_tokens: 40,
+                cost_usd: 0.12,
+                requests: 3,
+                failed_requests: 1,
+                duration_ms: 9_000,
+                last_outcome: Some("error_max_turns".to_string()),
+                model: Some("claude-sonnet-4-5".to_string()),
+                ..SessionUsage::default()
+            }),
+            summary: None,
+            files: Vec::new(),
+            interrupted_turns: Vec::new(),
+            last_read_message_id: None,
+            last_read_line: None,
+            metadata: BTreeMap::from([
+                ("reviewer".to_string(), "sam".to_string()),
+                ("ticket".to_string(), "ENG-12".to_string()),
+            ]),
+            time: BTreeMap::new(),
+        };
+        let pricing = ModelPricing::default();
+        let row = session_row("w1", "Acme, API", "acme", &session, &pricing);
+        assert_eq!(row.outcome, "error_max_turns");
+        let csv = session_usage_csv(&[row]);
+        assert_eq!(
+            csv.lines().nth(1),
+            Some(
+                "s1,w1,\"Acme, API\",acme,claude-sonnet-4-5,1970-01-01T00:00:00+00:00,\
+                 1970-01-01T00:01:00+00:00,3,1,100,40,0,0,0.1200,,9000,error_max_turns,\
+                 reviewer=sam; ticket=ENG-12"
+            )
+        );
+    }
+}
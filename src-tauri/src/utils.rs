@@ -1,13 +1,268 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::proxy::apply_network_env;
+
 pub(crate) fn normalize_git_path(path: &str) -> String {
     path.replace('\\', "/")
 }
 
+/// Resolve symlinks (e.g. /var -> /private/var on macOS) so the same
+/// directory always yields the same string. Falls back to the input,
+/// minus trailing slashes, when the path does not exist.
+pub(crate) fn canonicalize_path(path: &str) -> String {
+    let trimmed = trim_trailing_separators(path);
+    std::fs::canonicalize(trimmed)
+        .map(|resolved| resolved.to_string_lossy().to_string())
+        .unwrap_or_else(|_| trimmed.to_string())
+}
+
+pub(crate) fn trim_trailing_separators(path: &str) -> &str {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() && !path.is_empty() {
+        &path[..1]
+    } else {
+        trimmed
+    }
+}
+
+/// Comparison key for a path: separators normalized, trailing slashes
+/// dropped, and lowercased on platforms whose default filesystem is
+/// case-insensitive. Does not touch the filesystem.
+pub(crate) fn path_key(path: &str) -> String {
+    let normalized = normalize_git_path(trim_trailing_separators(path));
+    if cfg!(any(target_os = "macos", target_os = "windows")) {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
+/// `path_key` of the path with symlinks resolved, for deduplicating
+/// directories reached through different spellings.
+pub(crate) fn canonical_path_key(path: &str) -> String {
+    path_key(&canonicalize_path(path))
+}
+
+/// Both comparison keys of a path. Canonicalizing hits the filesystem, so
+/// build one per path and reuse it when matching against many others.
+#[derive(Debug, Clone)]
+pub(crate) struct PathKey {
+    path: String,
+    lexical: String,
+    canonical: String,
+}
+
+impl PathKey {
+    pub(crate) fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            lexical: path_key(path),
+            canonical: canonical_path_key(path),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether both paths refer to the same directory, following symlinks
+    /// when both exist and honoring platform case sensitivity.
+    pub(crate) fn matches(&self, other: &PathKey) -> bool {
+        self.lexical == other.lexical || self.canonical == other.canonical
+    }
+}
+
+/// Quote a value for a curl config file. Line breaks are escaped so a
+/// value can never end its line and start another directive; other control
+/// characters are dropped.
+pub(crate) fn curl_config_value(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {}
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A file in the temp dir, readable only by the user and removed on drop.
+/// Holds request bodies for curl (`--data-binary @path`, `-F name=<path`),
+/// which would otherwise sit in the process list and hit argv limits.
+pub(crate) struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    pub(crate) fn with_contents(label: &str, contents: &[u8]) -> Result<Self, String> {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!(
+            "claude-monitor-{label}-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = Self { path };
+        options
+            .open(&file.path)
+            .and_then(|mut handle| handle.write_all(contents))
+            .map_err(|e| e.to_string())?;
+        Ok(file)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// A fresh directory for a test, removed with its contents on drop so it
+/// is cleaned up even when an assert fails. Derefs to its path.
+#[cfg(test)]
+pub(crate) struct TempDir {
+    path: PathBuf,
+}
+
+#[cfg(test)]
+impl TempDir {
+    pub(crate) fn new(label: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "claude-monitor-{label}-{}",
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Run curl with `config` fed on stdin (`-K -`), so URLs with embedded keys
+/// and auth headers never appear in the process list. Returns stdout.
+pub(crate) async fn run_curl(config: &str, args: &[String]) -> Result<String, String> {
+    let mut command = Command::new("curl");
+    command.args(["-sS", "--max-time", "30", "-K", "-"]);
+    command.args(args);
+    apply_network_env(&mut command);
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run curl: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    let output = timeout(Duration::from_secs(35), child.wait_with_output())
+        .await
+        .map_err(|_| "Request timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::normalize_git_path;
+    use super::{curl_config_value, normalize_git_path, path_key, PathKey, TempDir, TempFile};
 
     #[test]
     fn normalize_git_path_replaces_backslashes() {
         assert_eq!(normalize_git_path("foo\\bar\\baz"), "foo/bar/baz");
     }
+
+    #[test]
+    fn path_key_ignores_trailing_separators() {
+        assert_eq!(path_key("/repo/app/"), path_key("/repo/app"));
+        assert_eq!(path_key("/"), "/");
+        let key = PathKey::new("/does-not-exist/a");
+        assert!(key.matches(&PathKey::new("/does-not-exist/a/")));
+        assert!(!key.matches(&PathKey::new("/does-not-exist/ab")));
+    }
+
+    #[test]
+    fn curl_config_values_stay_on_one_line() {
+        assert_eq!(curl_config_value(r#"a"b\c"#), r#""a\"b\\c""#);
+        let injected = curl_config_value("token\nurl = \"https://evil.example\"\r\x07");
+        assert!(!injected.contains(['\n', '\r', '\x07']));
+        assert_eq!(injected, r#""token\nurl = \"https://evil.example\"\r""#);
+    }
+
+    #[test]
+    fn temp_files_are_removed_on_drop() {
+        let file = TempFile::with_contents("test", b"{}").unwrap();
+        let path = file.path().to_path_buf();
+        assert_eq!(std::fs::read(&path).unwrap(), b"{}");
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn temp_dirs_are_removed_on_drop() {
+        let dir = TempDir::new("test");
+        std::fs::write(dir.join("file.txt"), "x").unwrap();
+        let path = dir.to_path_buf();
+        drop(dir);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_keys_follow_symlinks() {
+        let base = TempDir::new("path-match");
+        let real = base.join("real");
+        let link = base.join("link");
+        std::fs::create_dir_all(&real).unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        assert!(
+            PathKey::new(&link.to_string_lossy()).matches(&PathKey::new(&real.to_string_lossy()))
+        );
+    }
 }
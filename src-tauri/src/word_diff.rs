@@ -0,0 +1,186 @@
+use crate::types::IntralineChange;
+
+/// Lines with more tokens than this are left without word emphasis; the
+/// LCS table is quadratic.
+const MAX_TOKENS: usize = 400;
+/// Pairs sharing less than this share of their tokens are treated as
+/// rewritten rather than modified.
+const MIN_SIMILARITY: f64 = 0.3;
+
+/// Split into word, whitespace and single-punctuation tokens, each with its
+/// length in UTF-16 code units so ranges index JS strings directly.
+fn tokenize(text: &str) -> Vec<(&str, u32)> {
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        let kind = class(ch);
+        let mut end = start + ch.len_utf8();
+        if kind != 2 {
+            while let Some(&(next_start, next)) = chars.peek() {
+                if class(next) != kind {
+                    break;
+                }
+                end = next_start + next.len_utf8();
+                chars.next();
+            }
+        }
+        let token = &text[start..end];
+        tokens.push((token, token.encode_utf16().count() as u32));
+    }
+    tokens
+}
+
+/// Which tokens of `old` and `new` are outside their longest common
+/// subsequence.
+fn changed_tokens(old: &[(&str, u32)], new: &[(&str, u32)]) -> (Vec<bool>, Vec<bool>, usize) {
+    let (n, m) = (old.len(), new.len());
+    let mut table = vec![0u16; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[at(i, j)] = if old[i].0 == new[j].0 {
+                table[at(i + 1, j + 1)] + 1
+            } else {
+                table[at(i + 1, j)].max(table[at(i, j + 1)])
+            };
+        }
+    }
+    let mut old_changed = vec![true; n];
+    let mut new_changed = vec![true; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i].0 == new[j].0 {
+            old_changed[i] = false;
+            new_changed[j] = false;
+            i += 1;
+            j += 1;
+        } else if table[at(i + 1, j)] >= table[at(i, j + 1)] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    (old_changed, new_changed, table[at(0, 0)] as usize)
+}
+
+/// Merge changed tokens into `[start, end)` ranges. Whitespace neither
+/// starts nor ends a range, so `a b` -> `x y` is one range.
+fn ranges(tokens: &[(&str, u32)], changed: &[bool]) -> Vec<[u32; 2]> {
+    let mut ranges: Vec<[u32; 2]> = Vec::new();
+    let mut offset = 0;
+    let mut joinable = false;
+    for (&(token, len), &is_changed) in tokens.iter().zip(changed) {
+        if !token.trim().is_empty() {
+            if is_changed {
+                match ranges.last_mut() {
+                    Some(last) if joinable => last[1] = offset + len,
+                    _ => ranges.push([offset, offset + len]),
+                }
+            }
+            joinable = is_changed;
+        }
+        offset += len;
+    }
+    ranges
+}
+
+fn pair_changes(old: &str, new: &str) -> Option<(Vec<[u32; 2]>, Vec<[u32; 2]>)> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len() > MAX_TOKENS || new_tokens.len() > MAX_TOKENS {
+        return None;
+    }
+    let (old_changed, new_changed, common) = changed_tokens(&old_tokens, &new_tokens);
+    let total = old_tokens.len().max(new_tokens.len()).max(1);
+    if (common as f64) / (total as f64) < MIN_SIMILARITY {
+        return None;
+    }
+    Some((
+        ranges(&old_tokens, &old_changed),
+        ranges(&new_tokens, &new_changed),
+    ))
+}
+
+/// Word-level changes for a unified diff, like `git diff --word-diff`.
+/// Each run of removed lines is paired in order with the added lines that
+/// follow it; `line` indexes `diff.split('\n')` and ranges are UTF-16
+/// offsets into the line after its `+`/`-` prefix.
+pub(crate) fn intraline_changes(diff: &str) -> Vec<IntralineChange> {
+    let lines: Vec<&str> = diff.split('\n').collect();
+    let mut changes = Vec::new();
+    let mut in_hunk = false;
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].starts_with("@@") {
+            in_hunk = true;
+        }
+        if !in_hunk || !lines[index].starts_with('-') {
+            index += 1;
+            continue;
+        }
+        let removed_start = index;
+        while index < lines.len() && lines[index].starts_with('-') {
+            index += 1;
+        }
+        let added_start = index;
+        while index < lines.len() && lines[index].starts_with('+') {
+            index += 1;
+        }
+        let pairs = (added_start - removed_start).min(index - added_start);
+        for offset in 0..pairs {
+            let old_line = removed_start + offset;
+            let new_line = added_start + offset;
+            let Some((old_ranges, new_ranges)) =
+                pair_changes(&lines[old_line][1..], &lines[new_line][1..])
+            else {
+                continue;
+            };
+            if !old_ranges.is_empty() {
+                changes.push(IntralineChange {
+                    line: old_line,
+                    ranges: old_ranges,
+                });
+            }
+            if !new_ranges.is_empty() {
+                changes.push(IntralineChange {
+                    line: new_line,
+                    ranges: new_ranges,
+                });
+            }
+        }
+    }
+    changes.sort_by_key(|change| change.line);
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::intraline_changes;
+
+    #[test]
+    fn marks_changed_words_in_paired_lines() {
+        let diff = "@@ -1,2 +1,2 @@\n-let timeout = 30;\n-fn old() {}\n+let timeout = 45;\n+struct Completely;\n";
+        let changes = intraline_changes(diff);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|change| (change.line, change.ranges.clone()))
+            .collect();
+        // "30" -> "45" at offset 14; the second pair shares too little.
+        assert_eq!(summary, [(1, vec![[14, 16]]), (3, vec![[14, 16]])]);
+    }
+
+    #[test]
+    fn measures_offsets_in_utf16_units() {
+        let changes = intraline_changes("@@ -1 +1 @@\n-café = 1\n+café = 2");
+        assert_eq!(changes[1].ranges, vec![[7, 8]]);
+    }
+}
@@ -0,0 +1,952 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::process::Command;
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+
+use crate::automation::ensure_automation_allowed;
+use crate::claude::{ensure_bridge_running, reply_error, request_error, BRIDGE_DIED};
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::crash::log_line;
+use crate::guardrails::start_autonomous_turn;
+use crate::settings::ensure_not_observer;
+use crate::slack::mirror_user_message;
+use crate::state::AppState;
+use crate::turn_lanes::{acquire_turn_slot, release_turn_slot, Lane};
+
+const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 600;
+/// How long a `prompt` step waits for its turn to end before failing.
+const PROMPT_TURN_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+/// Upper bound on `repeat` iterations, so a bad definition can't loop forever.
+const MAX_REPEAT_ITERATIONS: u32 = 20;
+/// Command output kept for status events and `{{output}}`, from the end.
+const OUTPUT_TAIL_BYTES: usize = 16 * 1024;
+/// Workflow definitions are read from here, relative to the workspace root.
+const WORKFLOWS_DIR: &str = ".claude/workflows";
+
+/// Outcome of the most recent `run` step that a condition tests.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorkflowCondition {
+    Passed,
+    Failed,
+}
+
+/// One step of a workflow. `if` and `repeat` test the last `run` step.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum WorkflowStep {
+    /// Send `text` as a user message and wait for the turn to end.
+    /// `{{output}}` and `{{exitCode}}` expand to the last command's result.
+    Prompt { text: String },
+    /// Run a shell command in the session's working directory. A non-zero
+    /// exit fails the workflow unless `allowFailure` is set.
+    #[serde(rename_all = "camelCase")]
+    Run {
+        command: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+        #[serde(default)]
+        allow_failure: bool,
+    },
+    If {
+        when: WorkflowCondition,
+        #[serde(default)]
+        then: Vec<WorkflowStep>,
+        #[serde(default, rename = "else")]
+        otherwise: Vec<WorkflowStep>,
+    },
+    /// Run `steps` until the condition holds, at most `maxIterations` times.
+    #[serde(rename_all = "camelCase")]
+    Repeat {
+        until: WorkflowCondition,
+        max_iterations: u32,
+        steps: Vec<WorkflowStep>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Workflow {
+    #[serde(default)]
+    pub(crate) name: String,
+    pub(crate) steps: Vec<WorkflowStep>,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkflowFile {
+    pub(crate) path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) workflow: Option<Workflow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorkflowStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StepStatus {
+    Running,
+    Passed,
+    Failed,
+}
+
+/// Payload of `workflow-step`, sent as `prompt` and `run` steps start and end.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkflowStepEvent {
+    run_id: String,
+    session_id: String,
+    /// Index path into the definition, e.g. `2.then.0`.
+    path: String,
+    kind: &'static str,
+    /// Prompt text as sent, or the command.
+    label: String,
+    status: StepStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+}
+
+/// Payload of `workflow-status`.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkflowRunInfo {
+    run_id: String,
+    session_id: String,
+    workspace_id: String,
+    name: String,
+    status: WorkflowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+struct ActiveRun {
+    info: WorkflowRunInfo,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Running workflows by run id, and prompt steps waiting for their turn.
+#[derive(Default)]
+pub(crate) struct WorkflowRuns {
+    runs: HashMap<String, ActiveRun>,
+    /// Session id -> sender for how the running turn ended.
+    turn_waiters: HashMap<String, oneshot::Sender<Result<(), String>>>,
+}
+
+impl WorkflowRuns {
+    fn session_busy(&self, session_id: &str) -> bool {
+        self.runs
+            .values()
+            .any(|run| run.info.session_id == session_id)
+    }
+}
+
+/// Problems that would stop a workflow partway through.
+pub(crate) fn validate_workflow(workflow: &Workflow) -> Vec<String> {
+    fn check(steps: &[WorkflowStep], path: &str, errors: &mut Vec<String>) {
+        for (index, step) in steps.iter().enumerate() {
+            let label = step_path(path, &index.to_string());
+            match step {
+                WorkflowStep::Prompt { text } => {
+                    if text.trim().is_empty() {
+                        errors.push(format!("{label}: empty prompt"));
+                    }
+                }
+                WorkflowStep::Run {
+                    command,
+                    timeout_secs,
+                    ..
+                } => {
+                    if command.trim().is_empty() {
+                        errors.push(format!("{label}: empty command"));
+                    }
+                    if *timeout_secs == Some(0) {
+                        errors.push(format!("{label}: timeout must be positive"));
+                    }
+                }
+                WorkflowStep::If {
+                    then, otherwise, ..
+                } => {
+                    if then.is_empty() && otherwise.is_empty() {
+                        errors.push(format!("{label}: no steps in either branch"));
+                    }
+                    check(then, &step_path(&label, "then"), errors);
+                    check(otherwise, &step_path(&label, "else"), errors);
+                }
+                WorkflowStep::Repeat {
+                    max_iterations,
+                    steps,
+                    ..
+                } => {
+                    if !(1..=MAX_REPEAT_ITERATIONS).contains(max_iterations) {
+                        errors.push(format!(
+                            "{label}: maxIterations must be between 1 and {MAX_REPEAT_ITERATIONS}"
+                        ));
+                    }
+                    if steps.is_empty() {
+                        errors.push(format!("{label}: no steps to repeat"));
+                    }
+                    check(steps, &step_path(&label, "steps"), errors);
+                }
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    if workflow.steps.is_empty() {
+        errors.push("Workflow has no steps".to_string());
+    }
+    check(&workflow.steps, "", &mut errors);
+    errors
+}
+
+/// Parse and validate a JSON workflow definition.
+pub(crate) fn parse_workflow(definition: &str) -> Result<Workflow, String> {
+    let workflow: Workflow =
+        serde_json::from_str(definition).map_err(|e| format!("Invalid workflow: {e}"))?;
+    validated(workflow)
+}
+
+/// Parse and validate a YAML workflow definition, with the same fields.
+pub(crate) fn parse_workflow_yaml(definition: &str) -> Result<Workflow, String> {
+    let workflow: Workflow =
+        serde_yaml::from_str(definition).map_err(|e| format!("Invalid workflow: {e}"))?;
+    validated(workflow)
+}
+
+fn validated(workflow: Workflow) -> Result<Workflow, String> {
+    let errors = validate_workflow(&workflow);
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    Ok(workflow)
+}
+
+/// Every shell command the workflow can run, in order, including those in
+/// `if` and `repeat` steps.
+pub(crate) fn workflow_commands(workflow: &Workflow) -> Vec<&str> {
+    fn collect<'a>(steps: &'a [WorkflowStep], out: &mut Vec<&'a str>) {
+        for step in steps {
+            match step {
+                WorkflowStep::Prompt { .. } => {}
+                WorkflowStep::Run { command, .. } => out.push(command),
+                WorkflowStep::If {
+                    then, otherwise, ..
+                } => {
+                    collect(then, out);
+                    collect(otherwise, out);
+                }
+                WorkflowStep::Repeat { steps, .. } => collect(steps, out),
+            }
+        }
+    }
+    let mut commands = Vec::new();
+    collect(&workflow.steps, &mut commands);
+    commands
+}
+
+fn step_path(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else {
+        format!("{parent}.{child}")
+    }
+}
+
+/// Result of the last `run` step.
+#[derive(Debug, Clone, PartialEq)]
+struct CommandOutcome {
+    exit_code: Option<i32>,
+    output: String,
+}
+
+impl CommandOutcome {
+    fn passed(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+fn holds(condition: WorkflowCondition, last: Option<&CommandOutcome>) -> bool {
+    match (condition, last) {
+        (WorkflowCondition::Passed, Some(outcome)) => outcome.passed(),
+        (WorkflowCondition::Failed, Some(outcome)) => !outcome.passed(),
+        (_, None) => false,
+    }
+}
+
+fn expand_prompt(text: &str, last: Option<&CommandOutcome>) -> String {
+    let exit_code = last
+        .and_then(|outcome| outcome.exit_code)
+        .map(|code| code.to_string())
+        .unwrap_or_default();
+    text.replace("{{output}}", last.map(|o| o.output.as_str()).unwrap_or(""))
+        .replace("{{exitCode}}", &exit_code)
+}
+
+/// Keep the last `max` bytes, on a char boundary.
+fn tail(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut start = text.len() - max;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    text[start..].to_string()
+}
+
+struct Runner {
+    app: AppHandle,
+    run_id: String,
+    session_id: String,
+    workspace_id: String,
+    cwd: String,
+    last: Option<CommandOutcome>,
+}
+
+type StepFuture<'a> = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>>;
+
+impl Runner {
+    fn emit_step(
+        &self,
+        path: &str,
+        kind: &'static str,
+        label: &str,
+        status: StepStatus,
+        outcome: Option<&CommandOutcome>,
+    ) {
+        let _ = self.app.emit(
+            "workflow-step",
+            WorkflowStepEvent {
+                run_id: self.run_id.clone(),
+                session_id: self.session_id.clone(),
+                path: path.to_string(),
+                kind,
+                label: label.to_string(),
+                status,
+                exit_code: outcome.and_then(|o| o.exit_code),
+                output: outcome.map(|o| o.output.clone()),
+            },
+        );
+    }
+
+    // Boxed so `if` and `repeat` can recurse.
+    fn run_steps<'a>(&'a mut self, steps: &'a [WorkflowStep], path: &'a str) -> StepFuture<'a> {
+        Box::pin(async move {
+            for (index, step) in steps.iter().enumerate() {
+                let path = step_path(path, &index.to_string());
+                self.run_step(step, &path).await?;
+            }
+            Ok(())
+        })
+    }
+
+    async fn run_step(&mut self, step: &WorkflowStep, path: &str) -> Result<(), String> {
+        let state = self.app.state::<AppState>();
+        ensure_automation_allowed(&state, &self.workspace_id, "The workflow").await?;
+        match step {
+            WorkflowStep::Prompt { text } => {
+                let text = expand_prompt(text, self.last.as_ref());
+                self.emit_step(path, "prompt", &text, StepStatus::Running, None);
+                let result = self.prompt(&text).await;
+                let status = if result.is_ok() {
+                    StepStatus::Passed
+                } else {
+                    StepStatus::Failed
+                };
+                self.emit_step(path, "prompt", &text, status, None);
+                result.map_err(|err| format!("Step {path}: {err}"))
+            }
+            WorkflowStep::Run {
+                command,
+                timeout_secs,
+                allow_failure,
+            } => {
+                self.emit_step(path, "run", command, StepStatus::Running, None);
+                let outcome = self
+                    .run_command(
+                        command,
+                        timeout_secs.unwrap_or(DEFAULT_COMMAND_TIMEOUT_SECS),
+                    )
+                    .await;
+                let outcome = match outcome {
+                    Ok(outcome) => outcome,
+                    Err(err) => {
+                        self.emit_step(path, "run", command, StepStatus::Failed, None);
+                        return Err(format!("Step {path}: {err}"));
+                    }
+                };
+                let passed = outcome.passed();
+                let status = if passed {
+                    StepStatus::Passed
+                } else {
+                    StepStatus::Failed
+                };
+                self.emit_step(path, "run", command, status, Some(&outcome));
+                self.last = Some(outcome);
+                if !passed && !allow_failure {
+                    return Err(format!("Step {path}: command failed"));
+                }
+                Ok(())
+            }
+            WorkflowStep::If {
+                when,
+                then,
+                otherwise,
+            } => {
+                if holds(*when, self.last.as_ref()) {
+                    self.run_steps(then, &step_path(path, "then")).await
+                } else {
+                    self.run_steps(otherwise, &step_path(path, "else")).await
+                }
+            }
+            WorkflowStep::Repeat {
+                until,
+                max_iterations,
+                steps,
+            } => {
+                let path = step_path(path, "steps");
+                for _ in 0..*max_iterations {
+                    self.run_steps(steps, &path).await?;
+                    if holds(*until, self.last.as_ref()) {
+                        break;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Send a message and wait for its turn's `result`.
+    async fn prompt(&self, text: &str) -> Result<(), String> {
+        let state = self.app.state::<AppState>();
+        ensure_not_observer(&state, "Sending messages").await?;
+        start_autonomous_turn(&self.app, &self.session_id, "workflow").await?;
+        acquire_turn_slot(&state, &self.session_id, Lane::Background).await?;
+        let (tx, rx) = oneshot::channel();
+        state
+            .workflows
+            .lock()
+            .await
+            .turn_waiters
+            .insert(self.session_id.clone(), tx);
+        // Marked before sending: the turn's `result` can beat the reply.
+        state.message_queues.lock().await.started(&self.session_id);
+        let params = json!({
+            "sessionId": self.session_id,
+            "workspaceId": self.workspace_id,
+            "message": text,
+            "messageId": uuid::Uuid::new_v4().to_string(),
+        });
+        let sent = match ensure_bridge_running(&self.app, &state).await {
+            Ok(bridge) => bridge.send_request("message/send", params).await,
+            Err(err) => Err(err),
+        };
+        // A send the bridge could not make comes back as `{"error": …}`,
+        // and no `result` will follow it.
+        let sent = sent.and_then(|response| reply_error(&response).map_or(Ok(()), Err));
+        if let Err(err) = sent {
+            state.message_queues.lock().await.idle(&self.session_id);
+            release_turn_slot(&state, &self.session_id).await;
+            state
+                .workflows
+                .lock()
+                .await
+                .turn_waiters
+                .remove(&self.session_id);
+            return Err(err);
+        }
+        mirror_user_message(&self.app, &self.session_id, text);
+        match timeout(PROMPT_TURN_TIMEOUT, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err("session closed before the turn ended".to_string()),
+            Err(_) => {
+                state
+                    .workflows
+                    .lock()
+                    .await
+                    .turn_waiters
+                    .remove(&self.session_id);
+                Err(format!(
+                    "the turn did not end within {} minutes",
+                    PROMPT_TURN_TIMEOUT.as_secs() / 60
+                ))
+            }
+        }
+    }
+
+    async fn run_command(
+        &self,
+        command: &str,
+        timeout_secs: u64,
+    ) -> Result<CommandOutcome, String> {
+        let mut command_line = if cfg!(target_os = "windows") {
+            let mut shell = Command::new("cmd");
+            shell.arg("/C").arg(command);
+            shell
+        } else {
+            let mut shell = Command::new("sh");
+            shell.arg("-c").arg(command);
+            shell
+        };
+        command_line
+            .current_dir(&self.cwd)
+            .env("CLAUDE_PROJECT_DIR", &self.cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        // A group of its own, so `ProcessTree` can kill everything it starts.
+        #[cfg(unix)]
+        command_line.process_group(0);
+        let child = command_line
+            .spawn()
+            .map_err(|e| format!("Failed to run command: {e}"))?;
+        let mut tree = ProcessTree {
+            pid: child.id(),
+            exited: false,
+        };
+        let output = timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
+            .await
+            .map_err(|_| format!("command timed out after {timeout_secs}s"))?
+            .map_err(|e| e.to_string())?;
+        tree.exited = true;
+        let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(CommandOutcome {
+            exit_code: output.status.code(),
+            output: tail(&text, OUTPUT_TAIL_BYTES),
+        })
+    }
+}
+
+/// Kills a command's process tree when dropped before the command exits:
+/// on timeout, and when cancelling aborts the run. `kill_on_drop` only
+/// reaches `sh`, not the dev servers or test runners it started.
+struct ProcessTree {
+    pid: Option<u32>,
+    exited: bool,
+}
+
+impl Drop for ProcessTree {
+    fn drop(&mut self) {
+        let Some(pid) = self.pid.filter(|_| !self.exited) else {
+            return;
+        };
+        // The command leads its own process group (see `run_command`).
+        #[cfg(unix)]
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+        #[cfg(windows)]
+        let _ = std::process::Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &pid.to_string()])
+            .output();
+    }
+}
+
+fn emit_status(app_handle: &AppHandle, info: &WorkflowRunInfo) {
+    let _ = app_handle.emit("workflow-status", info);
+}
+
+async fn run_workflow(app: AppHandle, mut runner: Runner, workflow: Workflow) {
+    let result = runner.run_steps(&workflow.steps, "").await;
+    let state = app.state::<AppState>();
+    let Some(run) = state.workflows.lock().await.runs.remove(&runner.run_id) else {
+        return;
+    };
+    let mut info = run.info;
+    match result {
+        Ok(()) => info.status = WorkflowStatus::Succeeded,
+        Err(err) => {
+            info.status = WorkflowStatus::Failed;
+            info.error = Some(err);
+        }
+    }
+    emit_status(&app, &info);
+}
+
+/// A turn ended: wake the workflow step waiting on it, if any.
+pub(crate) async fn turn_finished(app_handle: &AppHandle, session_id: &str, success: bool) {
+    let state = app_handle.state::<AppState>();
+    let waiter = state.workflows.lock().await.turn_waiters.remove(session_id);
+    if let Some(tx) = waiter {
+        let _ = tx.send(if success {
+            Ok(())
+        } else {
+            Err("turn ended with an error".to_string())
+        });
+    }
+}
+
+/// The bridge exited during a turn: fail the workflow step waiting on it
+/// with `BRIDGE_DIED`.
+pub(crate) async fn turn_lost(app_handle: &AppHandle, session_id: &str) {
+    let state = app_handle.state::<AppState>();
+    let waiter = state.workflows.lock().await.turn_waiters.remove(session_id);
+    if let Some(tx) = waiter {
+        let _ = tx.send(Err(request_error(
+            BRIDGE_DIED,
+            "the Claude bridge exited during the turn",
+        )));
+    }
+}
+
+/// A session closed: fail the workflow step waiting on its turn.
+pub(crate) async fn session_closed(app_handle: &AppHandle, session_id: &str) {
+    let state = app_handle.state::<AppState>();
+    state.workflows.lock().await.turn_waiters.remove(session_id);
+}
+
+/// Workflow definitions saved under `.claude/workflows` in the workspace,
+/// as `.json`, `.yaml` or `.yml`. Files that fail to parse are listed with
+/// their error.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_workflows(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<WorkflowFile>, String> {
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .path
+            .clone()
+    };
+    let dir = Path::new(&root).join(WORKFLOWS_DIR);
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "json" || ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let yaml = path.extension().is_some_and(|ext| ext != "json");
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|data| {
+                    if yaml {
+                        parse_workflow_yaml(&data)
+                    } else {
+                        parse_workflow(&data)
+                    }
+                });
+            let (workflow, error) = match parsed {
+                Ok(workflow) => (Some(workflow), None),
+                Err(err) => (None, Some(err)),
+            };
+            WorkflowFile {
+                path: path.to_string_lossy().to_string(),
+                workflow,
+                error,
+            }
+        })
+        .collect())
+}
+
+/// Start a workflow against a session. `definition` is the workflow as
+/// JSON. Progress is reported on `workflow-step` and `workflow-status`.
+/// Needs a `run_workflow` confirmation for the same definition, since its
+/// `run` steps execute in the workspace.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn start_workflow(
+    session_id: String,
+    definition: String,
+    confirmation: Option<String>,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorkflowRunInfo, String> {
+    let workflow = parse_workflow(&definition)?;
+    consume_confirmation(&state, confirmation.as_deref(), "run_workflow", &definition).await?;
+    let (workspace_id, cwd) = state
+        .claude_sessions
+        .lock()
+        .await
+        .get(&session_id)
+        .map(|info| (info.workspace_id.clone(), info.cwd.clone()))
+        .ok_or_else(|| format!("Session {session_id} is not running"))?;
+    ensure_automation_allowed(&state, &workspace_id, "Running workflows").await?;
+    if state.message_queues.lock().await.is_running(&session_id) {
+        return Err("Wait for the session's turn to end before starting a workflow".to_string());
+    }
+
+    let mut workflows = state.workflows.lock().await;
+    if workflows.session_busy(&session_id) {
+        return Err("A workflow is already running in this session".to_string());
+    }
+    let run_id = uuid::Uuid::new_v4().to_string();
+    let info = WorkflowRunInfo {
+        run_id: run_id.clone(),
+        session_id: session_id.clone(),
+        workspace_id: workspace_id.clone(),
+        name: workflow.name.clone(),
+        status: WorkflowStatus::Running,
+        error: None,
+    };
+    let runner = Runner {
+        app: app_handle.clone(),
+        run_id: run_id.clone(),
+        session_id,
+        workspace_id,
+        cwd,
+        last: None,
+    };
+    // Registered under the lock, so the run can't finish before it is listed.
+    let handle = tauri::async_runtime::spawn(run_workflow(app_handle.clone(), runner, workflow));
+    workflows.runs.insert(
+        run_id,
+        ActiveRun {
+            info: info.clone(),
+            handle: Some(handle),
+        },
+    );
+    drop(workflows);
+    emit_status(&app_handle, &info);
+    Ok(info)
+}
+
+async fn cancel_run(
+    app_handle: &AppHandle,
+    state: &State<'_, AppState>,
+    run_id: &str,
+) -> Result<WorkflowRunInfo, String> {
+    let (mut run, awaiting_turn) = {
+        let mut workflows = state.workflows.lock().await;
+        let run = workflows
+            .runs
+            .remove(run_id)
+            .ok_or_else(|| format!("Workflow run {run_id} not found"))?;
+        let awaiting_turn = workflows
+            .turn_waiters
+            .remove(&run.info.session_id)
+            .is_some();
+        (run, awaiting_turn)
+    };
+    if let Some(handle) = run.handle.take() {
+        handle.abort();
+    }
+    run.info.status = WorkflowStatus::Cancelled;
+    emit_status(app_handle, &run.info);
+    if awaiting_turn {
+        let bridge = ensure_bridge_running(app_handle, state).await?;
+        bridge
+            .send_request(
+                "message/interrupt",
+                json!({ "sessionId": run.info.session_id }),
+            )
+            .await?;
+    }
+    Ok(run.info)
+}
+
+/// Cancel every workflow running in a workspace's sessions.
+pub(crate) async fn cancel_workspace_workflows(
+    app_handle: &AppHandle,
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+) {
+    let run_ids: Vec<String> = state
+        .workflows
+        .lock()
+        .await
+        .runs
+        .values()
+        .filter(|run| run.info.workspace_id == workspace_id)
+        .map(|run| run.info.run_id.clone())
+        .collect();
+    for run_id in run_ids {
+        if let Err(err) = cancel_run(app_handle, state, &run_id).await {
+            log_line!("Failed to cancel workflow {run_id}: {err}");
+        }
+    }
+}
+
+/// Stop a workflow. A running command is killed and a running turn is
+/// interrupted.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn cancel_workflow(
+    run_id: String,
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorkflowRunInfo, String> {
+    cancel_run(&app_handle, &state, &run_id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_workflow_runs(
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<Vec<WorkflowRunInfo>, String> {
+    let workflows = state.workflows.lock().await;
+    Ok(workflows
+        .runs
+        .values()
+        .map(|run| run.info.clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        expand_prompt, holds, parse_workflow, parse_workflow_yaml, tail, workflow_commands,
+        CommandOutcome, ProcessTree, WorkflowCondition,
+    };
+    use crate::utils::TempDir;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_and_validates_definitions() {
+        let workflow = parse_workflow(
+            r#"{
+                "name": "fix tests",
+                "steps": [
+                    { "type": "prompt", "text": "Implement the feature" },
+                    { "type": "repeat", "until": "passed", "maxIterations": 3, "steps": [
+                        { "type": "run", "command": "cargo test", "allowFailure": true },
+                        { "type": "if", "when": "failed", "then": [
+                            { "type": "prompt", "text": "Fix these failures:\n{{output}}" }
+                        ] }
+                    ] },
+                    { "type": "if", "when": "passed", "then": [
+                        { "type": "run", "command": "git commit -am wip" }
+                    ] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(workflow.name, "fix tests");
+        assert_eq!(workflow.steps.len(), 3);
+
+        let err = parse_workflow(
+            r#"{ "steps": [
+                { "type": "prompt", "text": " " },
+                { "type": "repeat", "until": "passed", "maxIterations": 0, "steps": [
+                    { "type": "run", "command": "" }
+                ] },
+                { "type": "if", "when": "failed" }
+            ] }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.lines().collect::<Vec<_>>(),
+            [
+                "0: empty prompt",
+                "1: maxIterations must be between 1 and 20",
+                "1.steps.0: empty command",
+                "2: no steps in either branch",
+            ]
+        );
+        assert!(parse_workflow(r#"{ "steps": [] }"#).is_err());
+        assert!(parse_workflow(r#"{ "steps": [{ "type": "deploy" }] }"#)
+            .unwrap_err()
+            .starts_with("Invalid workflow"));
+    }
+
+    #[test]
+    fn parses_yaml_definitions() {
+        let yaml = parse_workflow_yaml(
+            "name: fix tests\n\
+             steps:\n\
+             \x20 - type: run\n\
+             \x20   command: cargo test\n\
+             \x20   allowFailure: true\n\
+             \x20 - type: if\n\
+             \x20   when: failed\n\
+             \x20   then:\n\
+             \x20     - type: prompt\n\
+             \x20       text: \"Fix these failures:\\n{{output}}\"\n",
+        )
+        .unwrap();
+        let json = parse_workflow(
+            r#"{ "name": "fix tests", "steps": [
+                { "type": "run", "command": "cargo test", "allowFailure": true },
+                { "type": "if", "when": "failed", "then": [
+                    { "type": "prompt", "text": "Fix these failures:\n{{output}}" }
+                ] }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(yaml, json);
+        assert_eq!(workflow_commands(&json), ["cargo test"]);
+        assert!(parse_workflow_yaml("steps: []\n").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dropping_the_tree_kills_the_whole_process_group() {
+        use std::os::unix::process::CommandExt;
+
+        let dir = TempDir::new("workflow");
+        let marker = dir.join("marker");
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("(sleep 1; touch '{}') & wait", marker.display()))
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        drop(ProcessTree {
+            pid: Some(child.id()),
+            exited: false,
+        });
+        let _ = child.wait();
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn conditions_and_prompts_use_the_last_command() {
+        let failed = CommandOutcome {
+            exit_code: Some(101),
+            output: "test foo ... FAILED".to_string(),
+        };
+        assert!(holds(WorkflowCondition::Failed, Some(&failed)));
+        assert!(!holds(WorkflowCondition::Passed, Some(&failed)));
+        assert!(!holds(WorkflowCondition::Passed, None));
+        assert!(!holds(WorkflowCondition::Failed, None));
+        assert_eq!(
+            expand_prompt("Exit {{exitCode}}:\n{{output}}", Some(&failed)),
+            "Exit 101:\ntest foo ... FAILED"
+        );
+        assert_eq!(expand_prompt("Output: {{output}}", None), "Output: ");
+        assert_eq!(tail("abcdef", 3), "def");
+        assert_eq!(tail("aé", 1), "");
+    }
+}
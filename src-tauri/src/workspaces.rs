@@ -1,17 +1,25 @@
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 
+use serde::Serialize;
 use tauri::{AppHandle, State};
 use tokio::process::Command;
 use uuid::Uuid;
 
 use crate::codex::spawn_workspace_session;
+use crate::command_metrics::CommandTimer;
+use crate::confirmations::consume_confirmation;
+use crate::crash::log_line;
+use crate::deep_link::refresh_recent_menu;
+use crate::git_hooks::sync_git_hooks;
+use crate::registry::{apply_session_moves, move_transcripts, plan_session_moves, write_registry};
 use crate::state::AppState;
 use crate::storage::write_workspaces;
 use crate::types::{
     WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
 };
-use crate::utils::normalize_git_path;
+use crate::utils::{normalize_git_path, PathKey};
 
 fn sanitize_worktree_name(branch: &str) -> String {
     let mut result = String::new();
@@ -82,7 +90,7 @@ fn sort_workspaces(list: &mut Vec<WorkspaceInfo>) {
     });
 }
 
-async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Result<String, String> {
+pub(crate) async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Result<String, String> {
     let output = Command::new("git")
         .args(args)
         .current_dir(repo_path)
@@ -153,10 +161,68 @@ fn ensure_worktree_ignored(repo_path: &PathBuf) -> Result<(), String> {
     Ok(())
 }
 
+/// Register a workspace without spawning a Codex app-server, for flows
+/// (onboarding, imports) that only need Claude sessions.
+pub(crate) async fn create_workspace_entry(
+    state: &AppState,
+    path: &str,
+) -> Result<WorkspaceInfo, String> {
+    let path = path.trim();
+    if !PathBuf::from(path).is_dir() {
+        return Err(format!("{path} is not a directory."));
+    }
+    let key = PathKey::new(path);
+    let mut workspaces = state.workspaces.lock().await;
+    if workspaces
+        .values()
+        .any(|entry| key.matches(&PathKey::new(&entry.path)))
+    {
+        return Err(format!("{path} is already a workspace."));
+    }
+    let name = PathBuf::from(path)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Workspace")
+        .to_string();
+    let entry = WorkspaceEntry {
+        id: Uuid::new_v4().to_string(),
+        name,
+        path: path.to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+        automation_paused: false,
+    };
+    workspaces.insert(entry.id.clone(), entry.clone());
+    let list: Vec<_> = workspaces.values().cloned().collect();
+    write_workspaces(&state.storage_path, &list)?;
+
+    Ok(WorkspaceInfo {
+        id: entry.id,
+        name: entry.name,
+        path: entry.path,
+        codex_bin: entry.codex_bin,
+        connected: false,
+        kind: entry.kind,
+        parent_id: entry.parent_id,
+        worktree: entry.worktree,
+        settings: entry.settings,
+        automation_paused: entry.automation_paused,
+    })
+}
+
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn list_workspaces(
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Vec<WorkspaceInfo>, String> {
+    // Still loading: answer now and let `state/ready` trigger a refresh.
+    if !state.ready.load(Ordering::Acquire) {
+        return Ok(Vec::new());
+    }
     let workspaces = state.workspaces.lock().await;
     let sessions = state.sessions.lock().await;
     let mut result = Vec::new();
@@ -171,19 +237,40 @@ pub(crate) async fn list_workspaces(
             parent_id: entry.parent_id.clone(),
             worktree: entry.worktree.clone(),
             settings: entry.settings.clone(),
+            automation_paused: entry.automation_paused,
         });
     }
     sort_workspaces(&mut result);
     Ok(result)
 }
 
+/// Whether workspaces and the registry have finished loading, i.e. whether
+/// an empty `list_workspaces` is real.
 #[tauri::command]
+#[specta::specta]
+pub(crate) fn is_state_ready(state: State<'_, AppState>, _timer: CommandTimer) -> bool {
+    state.ready.load(Ordering::Acquire)
+}
+
+#[tauri::command]
+#[specta::specta]
 pub(crate) async fn add_workspace(
     path: String,
     codex_bin: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
+    _timer: CommandTimer,
 ) -> Result<WorkspaceInfo, String> {
+    {
+        let key = PathKey::new(&path);
+        let workspaces = state.workspaces.lock().await;
+        if workspaces
+            .values()
+            .any(|entry| key.matches(&PathKey::new(&entry.path)))
+        {
+            return Err(format!("{path} is already a workspace."));
+        }
+    }
     let name = PathBuf::from(&path)
         .file_name()
         .and_then(|s| s.to_str())
@@ -198,13 +285,14 @@ pub(crate) async fn add_workspace(
         parent_id: None,
         worktree: None,
         settings: WorkspaceSettings::default(),
+        automation_paused: false,
     };
 
     let default_bin = {
         let settings = state.app_settings.lock().await;
         settings.codex_bin.clone()
     };
-    let session = spawn_workspace_session(entry.clone(), default_bin, app).await?;
+    let session = spawn_workspace_session(entry.clone(), default_bin, app.clone()).await?;
     {
         let mut workspaces = state.workspaces.lock().await;
         workspaces.insert(entry.id.clone(), entry.clone());
@@ -216,6 +304,7 @@ pub(crate) async fn add_workspace(
         .lock()
         .await
         .insert(entry.id.clone(), session);
+    sync_git_hooks(&app).await;
 
     Ok(WorkspaceInfo {
         id: entry.id,
@@ -227,15 +316,18 @@ pub(crate) async fn add_workspace(
         parent_id: entry.parent_id,
         worktree: entry.worktree,
         settings: entry.settings,
+        automation_paused: entry.automation_paused,
     })
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn add_worktree(
     parent_id: String,
     branch: String,
     state: State<'_, AppState>,
     app: AppHandle,
+    _timer: CommandTimer,
 ) -> Result<WorkspaceInfo, String> {
     let branch = branch.trim();
     if branch.is_empty() {
@@ -263,6 +355,7 @@ pub(crate) async fn add_worktree(
     let worktree_path = unique_worktree_path(&worktree_root, &safe_name);
     let worktree_path_string = worktree_path.to_string_lossy().to_string();
 
+    let _git = state.git_locks.write(&parent_id).await;
     let branch_exists = git_branch_exists(&PathBuf::from(&parent_entry.path), branch).await?;
     if branch_exists {
         run_git_command(
@@ -289,6 +382,7 @@ pub(crate) async fn add_worktree(
             branch: branch.to_string(),
         }),
         settings: WorkspaceSettings::default(),
+        automation_paused: false,
     };
 
     let default_bin = {
@@ -318,14 +412,20 @@ pub(crate) async fn add_worktree(
         parent_id: entry.parent_id,
         worktree: entry.worktree,
         settings: entry.settings,
+        automation_paused: entry.automation_paused,
     })
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn remove_workspace(
     id: String,
+    confirmation: Option<String>,
     state: State<'_, AppState>,
+    app: AppHandle,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
+    consume_confirmation(&state, confirmation.as_deref(), "remove_workspace", &id).await?;
     let (entry, child_worktrees) = {
         let workspaces = state.workspaces.lock().await;
         let entry = workspaces
@@ -344,6 +444,7 @@ pub(crate) async fn remove_workspace(
     };
 
     let parent_path = PathBuf::from(&entry.path);
+    let git = state.git_locks.write(&id).await;
     for child in &child_worktrees {
         if let Some(session) = state.sessions.lock().await.remove(&child.id) {
             let mut child_process = session.child.lock().await;
@@ -365,28 +466,47 @@ pub(crate) async fn remove_workspace(
         let _ = child.kill().await;
     }
 
+    drop(git);
+    state.git_locks.forget(&id);
+
     {
         let mut workspaces = state.workspaces.lock().await;
         workspaces.remove(&id);
         for child in child_worktrees {
+            state.git_locks.forget(&child.id);
             workspaces.remove(&child.id);
         }
         let list: Vec<_> = workspaces.values().cloned().collect();
         write_workspaces(&state.storage_path, &list)?;
     }
+    refresh_recent_menu(&app).await;
 
     Ok(())
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn remove_worktree(
     id: String,
+    confirmation: Option<String>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
+    consume_confirmation(&state, confirmation.as_deref(), "remove_worktree", &id).await?;
+    remove_worktree_entry(&state, &id).await?;
+    Ok(())
+}
+
+/// Stop a worktree's agent, delete its checkout and forget it. Returns the
+/// removed entry and its parent. Callers check permission first.
+pub(crate) async fn remove_worktree_entry(
+    state: &AppState,
+    id: &str,
+) -> Result<(WorkspaceEntry, WorkspaceEntry), String> {
     let (entry, parent) = {
         let workspaces = state.workspaces.lock().await;
         let entry = workspaces
-            .get(&id)
+            .get(id)
             .cloned()
             .ok_or("workspace not found")?;
         if !entry.kind.is_worktree() {
@@ -410,6 +530,9 @@ pub(crate) async fn remove_worktree(
 
     let parent_path = PathBuf::from(&parent.path);
     let entry_path = PathBuf::from(&entry.path);
+    // Reads in the worktree itself wait too, not just those in the parent.
+    let parent_git = state.git_locks.write(&parent.id).await;
+    let entry_git = state.git_locks.write(&entry.id).await;
     if entry_path.exists() {
         run_git_command(
             &parent_path,
@@ -418,6 +541,8 @@ pub(crate) async fn remove_worktree(
         .await?;
     }
     let _ = run_git_command(&parent_path, &["worktree", "prune", "--expire", "now"]).await;
+    drop((entry_git, parent_git));
+    state.git_locks.forget(&entry.id);
 
     {
         let mut workspaces = state.workspaces.lock().await;
@@ -426,14 +551,16 @@ pub(crate) async fn remove_worktree(
         write_workspaces(&state.storage_path, &list)?;
     }
 
-    Ok(())
+    Ok((entry, parent))
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn update_workspace_settings(
     id: String,
     settings: WorkspaceSettings,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<WorkspaceInfo, String> {
     let (entry_snapshot, list) = {
         let mut workspaces = state.workspaces.lock().await;
@@ -460,14 +587,17 @@ pub(crate) async fn update_workspace_settings(
         parent_id: entry_snapshot.parent_id,
         worktree: entry_snapshot.worktree,
         settings: entry_snapshot.settings,
+        automation_paused: entry_snapshot.automation_paused,
     })
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn update_workspace_codex_bin(
     id: String,
     codex_bin: Option<String>,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<WorkspaceInfo, String> {
     let (entry_snapshot, list) = {
         let mut workspaces = state.workspaces.lock().await;
@@ -494,14 +624,115 @@ pub(crate) async fn update_workspace_codex_bin(
         parent_id: entry_snapshot.parent_id,
         worktree: entry_snapshot.worktree,
         settings: entry_snapshot.settings,
+        automation_paused: entry_snapshot.automation_paused,
+    })
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+pub(crate) struct WorkspaceRelocation {
+    workspace: WorkspaceInfo,
+    warnings: Vec<String>,
+}
+
+/// Point a workspace at the directory its repo was moved or renamed to and
+/// carry its Claude sessions along, instead of leaving them all Missing.
+/// Transcripts that had to be copied rather than moved come back as
+/// warnings, since their originals are still in the old project directory.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn relocate_workspace(
+    workspace_id: String,
+    new_path: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorkspaceRelocation, String> {
+    let new_path = new_path.trim().trim_end_matches('/').to_string();
+    if !PathBuf::from(&new_path).is_dir() {
+        return Err(format!("{new_path} is not a directory."));
+    }
+
+    let (old_path, entry_snapshot, list) = {
+        let key = PathKey::new(&new_path);
+        let mut workspaces = state.workspaces.lock().await;
+        if workspaces
+            .values()
+            .any(|entry| entry.id != workspace_id && key.matches(&PathKey::new(&entry.path)))
+        {
+            return Err(format!("{new_path} is already a workspace."));
+        }
+        let (old_path, entry_snapshot) = match workspaces.get_mut(&workspace_id) {
+            Some(entry) => {
+                let old_path = std::mem::replace(&mut entry.path, new_path.clone());
+                (old_path, entry.clone())
+            }
+            None => return Err("workspace not found".to_string()),
+        };
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (old_path, entry_snapshot, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+
+    let moves = plan_session_moves(&state.registry.lock().await, &old_path, &new_path);
+    let (moves, moved) = tokio::task::spawn_blocking(move || {
+        let moved = move_transcripts(&moves);
+        (moves, moved)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let mut warnings = Vec::new();
+    if !moved.copied.is_empty() {
+        log_line!(
+            "Copied transcripts that could not be moved: {:?}",
+            moved.copied
+        );
+        warnings.push(format!(
+            "{} session transcript(s) could not be moved and were copied; the originals are still in the old project directory.",
+            moved.copied.len()
+        ));
+    }
+    if !moved.failed.is_empty() {
+        log_line!("Failed to move transcripts: {:?}", moved.failed);
+        warnings.push(format!(
+            "{} session transcript(s) could not be moved and are marked missing.",
+            moved.failed.len()
+        ));
+    }
+
+    {
+        let mut registry = state.registry.lock().await;
+        apply_session_moves(&mut registry, moves, &moved);
+        if let Some(workspace_reg) = registry.workspaces.get_mut(&workspace_id) {
+            workspace_reg.project_path = Some(new_path.clone());
+        }
+        write_registry(&state.registry_path, &registry)?;
+    }
+
+    let connected = state.sessions.lock().await.contains_key(&workspace_id);
+    let workspace = WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+        automation_paused: entry_snapshot.automation_paused,
+    };
+    Ok(WorkspaceRelocation {
+        workspace,
+        warnings,
     })
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn connect_workspace(
     id: String,
     state: State<'_, AppState>,
     app: AppHandle,
+    _timer: CommandTimer,
 ) -> Result<(), String> {
     let entry = {
         let workspaces = state.workspaces.lock().await;
@@ -521,9 +752,11 @@ pub(crate) async fn connect_workspace(
 }
 
 #[tauri::command]
+#[specta::specta]
 pub(crate) async fn list_workspace_files(
     workspace_id: String,
     state: State<'_, AppState>,
+    _timer: CommandTimer,
 ) -> Result<Vec<String>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
@@ -551,7 +784,9 @@ mod tests {
             settings: WorkspaceSettings {
                 sidebar_collapsed: false,
                 sort_order,
+                ..WorkspaceSettings::default()
             },
+            automation_paused: false,
         }
     }
 
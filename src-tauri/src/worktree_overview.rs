@@ -0,0 +1,579 @@
+use git2::{DiffOptions, Oid, Repository, StatusOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::State;
+use tokio::process::Command;
+
+use crate::command_metrics::CommandTimer;
+use crate::digest::session_title;
+use crate::git::{github_remote, open_workspace_repo};
+use crate::proxy::apply_network_env;
+use crate::state::AppState;
+use crate::types::GitFileStatus;
+use crate::utils::normalize_git_path;
+
+/// Conflicting paths beyond this are counted but not listed.
+const MAX_CONFLICTS: usize = 50;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CheckStatus {
+    Pass,
+    /// Worth a look, but does not block the merge.
+    Warn,
+    Fail,
+    /// Could not be determined, e.g. no pull request to read CI from.
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MergeCheck {
+    /// `uncommitted`, `conflicts`, `behind`, `ci` or `sessions`.
+    pub(crate) name: String,
+    pub(crate) status: CheckStatus,
+    pub(crate) detail: String,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OverviewSession {
+    pub(crate) session_id: String,
+    pub(crate) title: String,
+    pub(crate) last_activity: u64,
+    /// A turn is in progress.
+    pub(crate) running: bool,
+}
+
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PullRequest {
+    pub(crate) number: u64,
+    pub(crate) url: String,
+    pub(crate) state: String,
+}
+
+/// Everything needed to decide whether a worktree is ready to merge into
+/// the branch its project has checked out.
+#[derive(Debug, Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeOverview {
+    pub(crate) worktree_id: String,
+    pub(crate) branch: String,
+    pub(crate) parent_id: String,
+    pub(crate) parent_branch: String,
+    pub(crate) merge_base: Option<String>,
+    /// Commits on the worktree branch that the parent branch lacks.
+    pub(crate) ahead: usize,
+    /// Commits on the parent branch that the worktree branch lacks.
+    pub(crate) behind: usize,
+    /// Changes since the merge base, including uncommitted ones.
+    pub(crate) files: Vec<GitFileStatus>,
+    pub(crate) total_additions: i64,
+    pub(crate) total_deletions: i64,
+    pub(crate) sessions: Vec<OverviewSession>,
+    pub(crate) pull_request: Option<PullRequest>,
+    pub(crate) checks: Vec<MergeCheck>,
+    /// No check failed.
+    pub(crate) ready: bool,
+}
+
+/// What git says about a worktree branch against its parent branch.
+#[derive(Debug, Default)]
+struct BranchReport {
+    merge_base: Option<String>,
+    ahead: usize,
+    behind: usize,
+    files: Vec<GitFileStatus>,
+    uncommitted: usize,
+    conflicts: Vec<String>,
+    /// Conflicting paths in total, listed or not.
+    conflict_count: usize,
+}
+
+fn delta_status(status: git2::Delta) -> &'static str {
+    match status {
+        git2::Delta::Added | git2::Delta::Untracked => "A",
+        git2::Delta::Deleted => "D",
+        git2::Delta::Renamed => "R",
+        git2::Delta::Typechange => "T",
+        _ => "M",
+    }
+}
+
+/// Compare the worktree at `path` with `parent_branch`: the diff from the
+/// merge base to the working tree, and whether merging the worktree's HEAD
+/// into the parent branch would conflict.
+fn branch_report(path: &str, parent_branch: &str) -> Result<BranchReport, String> {
+    let (repo, _) = open_workspace_repo(path)?;
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    let parent = repo
+        .find_branch(parent_branch, git2::BranchType::Local)
+        .and_then(|branch| branch.get().peel_to_commit())
+        .map_err(|_| format!("Branch {parent_branch} not found"))?;
+    let mut report = BranchReport::default();
+
+    let mut status_options = StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
+    report.uncommitted = repo
+        .statuses(Some(&mut status_options))
+        .map_err(|e| e.to_string())?
+        .len();
+
+    let Ok(base) = repo.merge_base(head.id(), parent.id()) else {
+        // Unrelated histories: nothing to diff against or merge cleanly.
+        return Ok(report);
+    };
+    report.merge_base = Some(base.to_string());
+    (report.ahead, report.behind) = repo
+        .graph_ahead_behind(head.id(), parent.id())
+        .map_err(|e| e.to_string())?;
+    report.files = diff_since(&repo, base)?;
+
+    if report.ahead > 0 {
+        let index = repo
+            .merge_commits(&parent, &head, None)
+            .map_err(|e| e.to_string())?;
+        if index.has_conflicts() {
+            let conflicts = index.conflicts().map_err(|e| e.to_string())?;
+            for conflict in conflicts.flatten() {
+                let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+                let Some(entry) = entry else {
+                    continue;
+                };
+                report.conflict_count += 1;
+                if report.conflicts.len() < MAX_CONFLICTS {
+                    report
+                        .conflicts
+                        .push(normalize_git_path(&String::from_utf8_lossy(&entry.path)));
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Per-file changes from `base` to the working tree.
+fn diff_since(repo: &Repository, base: Oid) -> Result<Vec<GitFileStatus>, String> {
+    let base_tree = repo
+        .find_commit(base)
+        .and_then(|commit| commit.tree())
+        .map_err(|e| e.to_string())?;
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let mut files = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let (additions, deletions) = git2::Patch::from_diff(&diff, index)
+            .ok()
+            .flatten()
+            .and_then(|patch| patch.line_stats().ok())
+            .map(|(_, additions, deletions)| (additions as i64, deletions as i64))
+            .unwrap_or((0, 0));
+        files.push(GitFileStatus {
+            path: normalize_git_path(&path.to_string_lossy()),
+            status: delta_status(delta.status()).to_string(),
+            additions,
+            deletions,
+            owners: Vec::new(),
+            lfs: None,
+        });
+    }
+    Ok(files)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestView {
+    number: u64,
+    url: String,
+    state: String,
+    #[serde(default)]
+    status_check_rollup: Vec<Value>,
+}
+
+/// Fold a pull request's `statusCheckRollup` (check runs and commit
+/// statuses) into one check.
+fn ci_check(rollup: &[Value]) -> MergeCheck {
+    let field = |check: &Value, key: &str| {
+        check
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_ascii_uppercase()
+    };
+    let mut failing = Vec::new();
+    let mut pending = 0;
+    for check in rollup {
+        let name = check
+            .get("name")
+            .or_else(|| check.get("context"))
+            .and_then(Value::as_str)
+            .unwrap_or("check");
+        // Check runs report `status` and `conclusion`; statuses a `state`.
+        let outcome = match field(check, "status").as_str() {
+            "" | "COMPLETED" => {
+                let conclusion = field(check, "conclusion");
+                if conclusion.is_empty() {
+                    field(check, "state")
+                } else {
+                    conclusion
+                }
+            }
+            _ => "PENDING".to_string(),
+        };
+        match outcome.as_str() {
+            "SUCCESS" | "NEUTRAL" | "SKIPPED" => {}
+            "PENDING" | "EXPECTED" | "QUEUED" | "IN_PROGRESS" | "" => pending += 1,
+            _ => failing.push(name.to_string()),
+        }
+    }
+    let (status, detail) = if rollup.is_empty() {
+        (CheckStatus::Unknown, "No CI checks reported".to_string())
+    } else if !failing.is_empty() {
+        (
+            CheckStatus::Fail,
+            format!("Failing: {}", failing.join(", ")),
+        )
+    } else if pending > 0 {
+        (
+            CheckStatus::Warn,
+            format!("{pending} of {} checks still running", rollup.len()),
+        )
+    } else {
+        (CheckStatus::Pass, format!("{} checks passed", rollup.len()))
+    };
+    MergeCheck {
+        name: "ci".to_string(),
+        status,
+        detail,
+    }
+}
+
+/// The open pull request for `branch` and its CI status, read with `gh`.
+async fn pull_request(path: &str, branch: &str) -> Result<PullRequestView, String> {
+    let (_, repo_name) = github_remote(path)?;
+    let mut command = Command::new("gh");
+    apply_network_env(&mut command);
+    let output = command
+        .args([
+            "pr",
+            "view",
+            branch,
+            "--repo",
+            &repo_name,
+            "--json",
+            "number,url,state,statusCheckRollup",
+        ])
+        .current_dir(path)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err(format!("No pull request for {branch}"));
+        }
+        return Err(detail.to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+fn merge_checks(
+    report: &BranchReport,
+    parent_branch: &str,
+    sessions: &[OverviewSession],
+    ci: MergeCheck,
+) -> Vec<MergeCheck> {
+    let check = |name: &str, status: CheckStatus, detail: String| MergeCheck {
+        name: name.to_string(),
+        status,
+        detail,
+    };
+    let uncommitted = match report.uncommitted {
+        0 => check(
+            "uncommitted",
+            CheckStatus::Pass,
+            "Working tree clean".to_string(),
+        ),
+        count => check(
+            "uncommitted",
+            CheckStatus::Fail,
+            format!("{count} uncommitted file(s) would be left out of the merge"),
+        ),
+    };
+    let conflicts = if report.merge_base.is_none() {
+        check(
+            "conflicts",
+            CheckStatus::Unknown,
+            format!("No common history with {parent_branch}"),
+        )
+    } else if report.conflict_count > 0 {
+        check(
+            "conflicts",
+            CheckStatus::Fail,
+            format!(
+                "{} file(s) conflict with {parent_branch}: {}",
+                report.conflict_count,
+                report.conflicts.join(", ")
+            ),
+        )
+    } else if report.ahead == 0 {
+        check(
+            "conflicts",
+            CheckStatus::Warn,
+            format!("No commits to merge into {parent_branch}"),
+        )
+    } else {
+        check(
+            "conflicts",
+            CheckStatus::Pass,
+            format!("Merges cleanly into {parent_branch}"),
+        )
+    };
+    let behind = match report.behind {
+        0 => check(
+            "behind",
+            CheckStatus::Pass,
+            format!("Up to date with {parent_branch}"),
+        ),
+        count => check(
+            "behind",
+            CheckStatus::Warn,
+            format!("{count} commit(s) behind {parent_branch}"),
+        ),
+    };
+    let running = sessions.iter().filter(|session| session.running).count();
+    let sessions = match running {
+        0 => check(
+            "sessions",
+            CheckStatus::Pass,
+            "No turns running".to_string(),
+        ),
+        count => check(
+            "sessions",
+            CheckStatus::Warn,
+            format!("{count} session(s) still working"),
+        ),
+    };
+    vec![uncommitted, conflicts, behind, ci, sessions]
+}
+
+/// A "ready to merge?" summary of a worktree: its diff against the branch
+/// its project has checked out, its sessions, and merge checks for
+/// uncommitted work, conflicts, drift from the parent branch, CI on the
+/// branch's pull request, and turns still running.
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_worktree_overview(
+    worktree_id: String,
+    state: State<'_, AppState>,
+    _timer: CommandTimer,
+) -> Result<WorktreeOverview, String> {
+    let (worktree, parent) = {
+        let workspaces = state.workspaces.lock().await;
+        let worktree = workspaces
+            .get(&worktree_id)
+            .cloned()
+            .ok_or("workspace not found")?;
+        let parent = worktree
+            .parent_id
+            .as_ref()
+            .filter(|_| worktree.kind.is_worktree())
+            .and_then(|id| workspaces.get(id))
+            .cloned()
+            .ok_or("Workspace is not a worktree")?;
+        (worktree, parent)
+    };
+    let branch = worktree
+        .worktree
+        .as_ref()
+        .map(|info| info.branch.clone())
+        .unwrap_or_default();
+
+    let report = {
+        let _parent_git = state.git_locks.read(&parent.id).await;
+        let _git = state.git_locks.read(&worktree.id).await;
+        let parent_path = parent.path.clone();
+        let path = worktree.path.clone();
+        tokio::task::spawn_blocking(move || {
+            let (repo, _) = open_workspace_repo(&parent_path)?;
+            let head = repo.head().map_err(|e| e.to_string())?;
+            let parent_branch = head
+                .shorthand()
+                .filter(|_| head.is_branch())
+                .ok_or("The project has no branch checked out")?
+                .to_string();
+            branch_report(&path, &parent_branch).map(|report| (parent_branch, report))
+        })
+        .await
+        .map_err(|_| "worktree comparison failed".to_string())?
+    };
+    let (parent_branch, report) = report?;
+
+    let sessions: Vec<OverviewSession> = {
+        let registry = state.registry.lock().await;
+        let turns = state.turns.lock().await;
+        let mut sessions: Vec<OverviewSession> = registry
+            .workspaces
+            .get(&worktree.id)
+            .map(|workspace| workspace.visible_session_ids.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|id| registry.sessions.get(id))
+            .map(|entry| OverviewSession {
+                session_id: entry.session_id.clone(),
+                title: session_title(entry),
+                last_activity: entry.last_activity,
+                running: turns.is_running(&entry.session_id),
+            })
+            .collect();
+        sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        sessions
+    };
+
+    let (pull_request, ci) = match pull_request(&worktree.path, &branch).await {
+        Ok(view) => {
+            let ci = ci_check(&view.status_check_rollup);
+            let pull_request = PullRequest {
+                number: view.number,
+                url: view.url,
+                state: view.state,
+            };
+            (Some(pull_request), ci)
+        }
+        Err(err) => (
+            None,
+            MergeCheck {
+                name: "ci".to_string(),
+                status: CheckStatus::Unknown,
+                detail: err,
+            },
+        ),
+    };
+    let checks = merge_checks(&report, &parent_branch, &sessions, ci);
+    let ready = checks.iter().all(|check| check.status != CheckStatus::Fail);
+    let total_additions = report.files.iter().map(|file| file.additions).sum();
+    let total_deletions = report.files.iter().map(|file| file.deletions).sum();
+    Ok(WorktreeOverview {
+        worktree_id,
+        branch,
+        parent_id: parent.id,
+        parent_branch,
+        merge_base: report.merge_base,
+        ahead: report.ahead,
+        behind: report.behind,
+        files: report.files,
+        total_additions,
+        total_deletions,
+        sessions,
+        pull_request,
+        checks,
+        ready,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use git2::{Repository, Signature};
+    use serde_json::json;
+
+    use super::{branch_report, ci_check, CheckStatus};
+    use crate::utils::TempDir;
+
+    fn commit(repo: &Repository, root: &Path, file: &str, content: &str, message: &str) {
+        std::fs::write(root.join(file), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn reports_diff_and_conflicts_against_the_parent_branch() {
+        let root = TempDir::new("overview");
+        let repo = Repository::init(&root).unwrap();
+        commit(&repo, &root, "app.txt", "one\n", "Initial");
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &base, false).unwrap();
+
+        commit(&repo, &root, "app.txt", "main\n", "Main change");
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit(&repo, &root, "app.txt", "feature\n", "Feature change");
+        std::fs::write(root.join("notes.txt"), "draft\n").unwrap();
+
+        let report = branch_report(root.to_str().unwrap(), &main).unwrap();
+        assert_eq!(report.merge_base, Some(base.id().to_string()));
+        assert_eq!((report.ahead, report.behind), (1, 1));
+        assert_eq!(report.uncommitted, 1);
+        assert_eq!(report.conflicts, ["app.txt"]);
+        let files: Vec<(&str, &str, i64, i64)> = report
+            .files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.as_str(),
+                    file.status.as_str(),
+                    file.additions,
+                    file.deletions,
+                )
+            })
+            .collect();
+        assert_eq!(files, [("app.txt", "M", 1, 1), ("notes.txt", "A", 1, 0)]);
+    }
+
+    #[test]
+    fn folds_check_runs_and_statuses() {
+        let passing = [
+            json!({"__typename": "CheckRun", "name": "build", "status": "COMPLETED", "conclusion": "SUCCESS"}),
+            json!({"__typename": "StatusContext", "context": "lint", "state": "SUCCESS"}),
+        ];
+        assert_eq!(ci_check(&passing).status, CheckStatus::Pass);
+
+        let running = [
+            passing[0].clone(),
+            json!({"__typename": "CheckRun", "name": "test", "status": "IN_PROGRESS", "conclusion": ""}),
+        ];
+        assert_eq!(ci_check(&running).status, CheckStatus::Warn);
+
+        let failing = [
+            running[1].clone(),
+            json!({"__typename": "StatusContext", "context": "deploy", "state": "ERROR"}),
+        ];
+        let check = ci_check(&failing);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(check.detail, "Failing: deploy");
+        assert_eq!(ci_check(&[]).status, CheckStatus::Unknown);
+    }
+}
@@ -1,5 +1,4 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
-import { getCurrentWindow } from "@tauri-apps/api/window";
 import "./styles/base.css";
 import "./styles/buttons.css";
 import "./styles/sidebar.css";
@@ -8,12 +7,12 @@ import "./styles/main.css";
 import "./styles/messages.css";
 import "./styles/approval-toasts.css";
 import "./styles/update-toasts.css";
+import "./styles/bridge-recovery.css";
 import "./styles/composer.css";
 import "./styles/diff.css";
 import "./styles/diff-viewer.css";
 import "./styles/debug.css";
 import "./styles/plan.css";
-import "./styles/about.css";
 import "./styles/tabbar.css";
 import "./styles/worktree-modal.css";
 import "./styles/settings.css";
@@ -22,8 +21,8 @@ import "./styles/compact-phone.css";
 import "./styles/compact-tablet.css";
 import "./styles/import-sessions.css";
 import { WorktreePrompt } from "./components/WorktreePrompt";
+import { HelpDialog } from "./components/HelpDialog";
 import { ImportSessionsModal } from "./components/ImportSessionsModal";
-import { AboutView } from "./components/AboutView";
 import { SettingsView } from "./components/SettingsView";
 import { DesktopLayout } from "./components/layouts/DesktopLayout";
 import { TabletLayout } from "./components/layouts/TabletLayout";
@@ -37,6 +36,7 @@ import { useGitStatus } from "./hooks/useGitStatus";
 import { useGitDiffs } from "./hooks/useGitDiffs";
 import { useGitLog } from "./hooks/useGitLog";
 import { useGitHubIssues } from "./hooks/useGitHubIssues";
+import { useWorktreeOverview } from "./hooks/useWorktreeOverview";
 import { useGitRemote } from "./hooks/useGitRemote";
 import { useModels } from "./hooks/useModels";
 import { useSkills } from "./hooks/useSkills";
@@ -45,31 +45,34 @@ import { useWorkspaceFiles } from "./hooks/useWorkspaceFiles";
 import { useGitBranches } from "./hooks/useGitBranches";
 import { useDebugLog } from "./hooks/useDebugLog";
 import { useWorkspaceRefreshOnFocus } from "./hooks/useWorkspaceRefreshOnFocus";
+import { useAttendedTime } from "./hooks/useAttendedTime";
+import { useAppearance } from "./hooks/useAppearance";
+import { useSystemUiPrefs } from "./hooks/useSystemUiPrefs";
 import { useWorkspaceRestore } from "./hooks/useWorkspaceRestore";
 import { useResizablePanels } from "./hooks/useResizablePanels";
 import { useLayoutMode } from "./hooks/useLayoutMode";
 import { useAppSettings } from "./hooks/useAppSettings";
 import { useUpdater } from "./hooks/useUpdater";
+import { useBridgeRecovery } from "./hooks/useBridgeRecovery";
 import { useComposerImages } from "./hooks/useComposerImages";
 import { useQueuedSend } from "./hooks/useQueuedSend";
 import { useWorktreePrompt } from "./hooks/useWorktreePrompt";
 import { useUiScaleShortcuts } from "./hooks/useUiScaleShortcuts";
 import { useWorkspaceSelection } from "./hooks/useWorkspaceSelection";
 import { useNewAgentShortcut } from "./hooks/useNewAgentShortcut";
+import { useFileDropOnboarding } from "./hooks/useFileDropOnboarding";
+import { useDoNotDisturb } from "./hooks/useDoNotDisturb";
+import { useAutoApprove } from "./hooks/useAutoApprove";
+import { useHelpMenu } from "./hooks/useHelpMenu";
+import { useSystemWake } from "./hooks/useSystemWake";
+import { useRecentPrompts } from "./hooks/useRecentPrompts";
+import { useSnippets } from "./hooks/useSnippets";
+import { useDeepLinks } from "./hooks/useDeepLinks";
+import { insertSnippet, openInEditor } from "./services/tauri";
 import type { AccessMode, DiffLineReference, QueuedMessage, WorkspaceInfo } from "./types";
 
-function useWindowLabel() {
-  const [label, setLabel] = useState("main");
-  useEffect(() => {
-    try {
-      const window = getCurrentWindow();
-      setLabel(window.label ?? "main");
-    } catch {
-      setLabel("main");
-    }
-  }, []);
-  return label;
-}
+/** Length of the auto-approve window started from a permission toast. */
+const AUTO_APPROVE_SECONDS = 10 * 60;
 
 function MainApp() {
   const {
@@ -104,9 +107,9 @@ function MainApp() {
   const isPhone = layoutMode === "phone";
   const [centerMode, setCenterMode] = useState<"chat" | "diff">("chat");
   const [selectedDiffPath, setSelectedDiffPath] = useState<string | null>(null);
-  const [gitPanelMode, setGitPanelMode] = useState<"diff" | "log" | "issues">(
-    "diff"
-  );
+  const [gitPanelMode, setGitPanelMode] = useState<
+    "diff" | "log" | "issues" | "merge"
+  >("diff");
   const [accessMode, setAccessMode] = useState<AccessMode>("current");
   const [activeTab, setActiveTab] = useState<
     "projects" | "claude" | "git" | "log"
@@ -133,10 +136,14 @@ function MainApp() {
     handleCopyDebug,
     clearDebugEntries
   } = useDebugLog();
+  const doNotDisturb = useDoNotDisturb();
+  const helpMenu = useHelpMenu();
+  const autoApprove = useAutoApprove();
 
   const composerInputRef = useRef<HTMLTextAreaElement | null>(null);
 
   const updater = useUpdater({ onDebug: addDebugEntry });
+  const bridgeRecovery = useBridgeRecovery();
 
   const {
     workspaces,
@@ -149,6 +156,7 @@ function MainApp() {
     markWorkspaceConnected,
     updateWorkspaceSettings,
     updateWorkspaceCodexBin,
+    setWorkspaceAutomationPaused,
     removeWorkspace,
     removeWorktree,
     hasLoaded,
@@ -177,7 +185,8 @@ function MainApp() {
   const {
     diffs: gitDiffs,
     isLoading: isDiffLoading,
-    error: diffError
+    error: diffError,
+    loadFullDiff,
   } = useGitDiffs(activeWorkspace, gitStatus.files, shouldLoadDiffs);
   const {
     entries: gitLogEntries,
@@ -197,6 +206,11 @@ function MainApp() {
     error: gitIssuesError
   } = useGitHubIssues(activeWorkspace, gitPanelMode === "issues");
   const { remote: gitRemoteUrl } = useGitRemote(activeWorkspace);
+  const {
+    overview: worktreeOverview,
+    isLoading: worktreeOverviewLoading,
+    error: worktreeOverviewError
+  } = useWorktreeOverview(activeWorkspace, gitPanelMode === "merge");
   const {
     models,
     selectedModel,
@@ -230,6 +244,14 @@ function MainApp() {
         } changed`
       : "Working tree clean";
 
+  const thinkingBlocksByWorkspace = useMemo(
+    () =>
+      Object.fromEntries(
+        workspaces.map((workspace) => [workspace.id, workspace.settings.thinkingBlocks]),
+      ),
+    [workspaces],
+  );
+
   const {
     setActiveThreadId,
     activeThreadId,
@@ -241,6 +263,7 @@ function MainApp() {
     activeTurnIdByThread,
     tokenUsageByThread,
     rateLimitsByWorkspace,
+    refreshAccountRateLimits,
     planByThread,
     lastAgentMessageByThread,
     interruptTurn,
@@ -259,7 +282,9 @@ function MainApp() {
     accessMode,
     permissionMode: appSettings.defaultPermissionMode,
     customPrompts: prompts,
-    onMessageActivity: refreshGitStatus
+    onMessageActivity: refreshGitStatus,
+    eventScope: appSettings.eventScope,
+    thinkingBlocksByWorkspace,
   });
   const {
     activeImages,
@@ -395,7 +420,7 @@ function MainApp() {
   const isReviewing = activeThreadId
     ? threadStatusById[activeThreadId]?.isReviewing ?? false
     : false;
-  const { activeQueue, handleSend, removeQueuedMessage } = useQueuedSend({
+  const { activeQueue, handleSend, removeQueuedMessage, moveQueuedMessage } = useQueuedSend({
     activeThreadId,
     isProcessing,
     isReviewing,
@@ -405,6 +430,20 @@ function MainApp() {
     startReview,
     clearActiveImages,
   });
+  const recentPrompts = useRecentPrompts(activeWorkspaceId);
+  const rememberPrompt = recentPrompts.remember;
+  const handleSendAndRemember = useCallback(
+    (text: string, images: string[]) => {
+      rememberPrompt(text);
+      return handleSend(text, images);
+    },
+    [handleSend, rememberPrompt],
+  );
+  const snippets = useSnippets();
+  const expandSnippet = useCallback(
+    (name: string) => insertSnippet(name, activeWorkspaceId),
+    [activeWorkspaceId],
+  );
   const draftFallbackKey = activeWorkspaceId
     ? `draft-${activeWorkspaceId}`
     : "draft-none";
@@ -427,6 +466,12 @@ function MainApp() {
     ? activeWorkspace?.worktree?.branch ?? activeWorkspace?.name ?? null
     : null;
 
+  useEffect(() => {
+    if (gitPanelMode === "merge" && !isWorktreeWorkspace) {
+      setGitPanelMode("diff");
+    }
+  }, [gitPanelMode, isWorktreeWorkspace]);
+
   useEffect(() => {
     if (!isPhone) {
       return;
@@ -473,6 +518,10 @@ function MainApp() {
     connectWorkspace,
     listThreadsForWorkspace: listClaudeSessions
   });
+
+  useAttendedTime(activeThreadId);
+  const systemUiPrefs = useSystemUiPrefs();
+  useAppearance(systemUiPrefs.colorScheme);
   useWorkspaceRefreshOnFocus({
     workspaces,
     refreshWorkspaces,
@@ -511,6 +560,92 @@ function MainApp() {
   }
 
 
+  useFileDropOnboarding({
+    onWorkspaceAdded: (workspace, startSession) => {
+      void refreshWorkspaces();
+      if (startSession) {
+        void handleAddAgent(workspace);
+      } else {
+        selectWorkspace(workspace.id);
+        setActiveThreadId(null, workspace.id);
+      }
+    },
+    onWorkspaceExisting: (workspaceId, startSession) => {
+      const workspace = workspaces.find((entry) => entry.id === workspaceId);
+      if (workspace && startSession) {
+        void handleAddAgent(workspace);
+      } else {
+        selectWorkspace(workspaceId);
+      }
+    },
+    onDropError: (path, error) => {
+      addDebugEntry({
+        id: `${Date.now()}-client-drop-workspace-error`,
+        timestamp: Date.now(),
+        source: "error",
+        label: "workspace/drop error",
+        payload: { path, error },
+      });
+      alert(`Failed to add ${path} as a workspace.\n\n${error}`);
+    },
+    onFilesDropped: (paths) => {
+      // Images are attached by the composer's own drop target.
+      const files = paths.filter(
+        (path) => !/\.(png|jpe?g|gif|webp|bmp|tiff?)$/i.test(path),
+      );
+      if (!activeWorkspace || !activeThreadId || files.length === 0) {
+        return;
+      }
+      const root = activeWorkspace.path.replace(/[\\/]+$/, "");
+      const mentions = files
+        .map((path) =>
+          path.startsWith(`${root}/`) || path.startsWith(`${root}\\`)
+            ? path.slice(root.length + 1)
+            : path,
+        )
+        .map((path) => `@${path}`)
+        .join(" ");
+      const separator = activeDraft && !/\s$/.test(activeDraft) ? " " : "";
+      handleDraftChange(`${activeDraft}${separator}${mentions} `);
+    },
+  });
+
+  useSystemWake((wake) => {
+    addDebugEntry({
+      id: `${Date.now()}-server-system-wake`,
+      timestamp: Date.now(),
+      source: "server",
+      label: "system/wake",
+      payload: wake,
+    });
+    void refreshWorkspaces();
+    if (activeWorkspace?.connected) {
+      void refreshAccountRateLimits(activeWorkspace.id);
+    }
+    if (wake.runningTurns > 0) {
+      const turns = wake.runningTurns === 1 ? "1 turn was" : `${wake.runningTurns} turns were`;
+      alert(
+        `${turns} running when the computer went to sleep. Check those sessions; their requests may have been interrupted.`,
+      );
+    }
+  });
+
+  useDeepLinks(
+    {
+      onOpenWorkspace: (workspaceId) => {
+        exitDiffView();
+        selectWorkspace(workspaceId);
+      },
+      onNewSession: (workspaceId) => {
+        const workspace = workspaces.find((entry) => entry.id === workspaceId);
+        if (workspace) {
+          void handleAddAgent(workspace);
+        }
+      },
+    },
+    hasLoaded,
+  );
+
   async function handleAddAgent(workspace: (typeof workspaces)[number]) {
     exitDiffView();
     selectWorkspace(workspace.id);
@@ -660,6 +795,7 @@ function MainApp() {
     composerNode,
     approvalToastsNode,
     updateToastNode,
+    bridgeRecoveryNode,
     homeNode,
     mainHeaderNode,
     desktopTopbarLeftNode,
@@ -682,14 +818,58 @@ function MainApp() {
     activeWorkspaceId,
     activeThreadId,
     activeItems,
+    onOpenReference: (reference) => {
+      if (!activeWorkspace) {
+        return;
+      }
+      void openInEditor(activeWorkspace.id, reference.path, reference.line).catch((error) => {
+        addDebugEntry({
+          id: `${Date.now()}-client-open-in-editor-error`,
+          timestamp: Date.now(),
+          source: "error",
+          label: "editor/open error",
+          payload: error instanceof Error ? error.message : String(error),
+        });
+      });
+    },
     activeRateLimits,
     showArchivedSessions,
     onToggleShowArchived: handleToggleShowArchived,
     approvals,
     handleApprovalDecision,
+    autoApproveWindows: autoApprove.windows,
+    onAutoApprove: (request) => {
+      void autoApprove
+        .enable(request.session_id, AUTO_APPROVE_SECONDS, "edits")
+        .then(() => handleApprovalDecision(request, "accept"))
+        .catch((error) => {
+          addDebugEntry({
+            id: `${Date.now()}-client-auto-approve-error`,
+            timestamp: Date.now(),
+            source: "error",
+            label: "autoApprove/enable error",
+            payload: error instanceof Error ? error.message : String(error),
+          });
+        });
+    },
+    onStopAutoApprove: (sessionId) => {
+      void autoApprove.disable(sessionId);
+    },
     onOpenSettings: handleOpenSettings,
     onOpenDebug: handleDebugClick,
     hasDebugAlerts,
+    focusStatus: doNotDisturb.status,
+    onToggleFocus: () => {
+      void doNotDisturb.toggle().catch((error) => {
+        addDebugEntry({
+          id: `${Date.now()}-client-focus-error`,
+          timestamp: Date.now(),
+          source: "error",
+          label: "focus/toggle error",
+          payload: error instanceof Error ? error.message : String(error),
+        });
+      });
+    },
     onAddWorkspace: handleAddWorkspace,
     onSelectHome: selectHome,
     onSelectWorkspace: (workspaceId) => {
@@ -738,9 +918,14 @@ function MainApp() {
     onDeleteWorktree: (workspaceId) => {
       void removeWorktree(workspaceId);
     },
+    onToggleWorkspaceAutomation: (workspaceId, paused) => {
+      void setWorkspaceAutomationPaused(workspaceId, paused);
+    },
     updaterState: updater.state,
     onUpdate: updater.startUpdate,
     onDismissUpdate: updater.dismiss,
+    bridgeRecoveryState: bridgeRecovery.state,
+    onDismissBridgeRecovery: bridgeRecovery.dismiss,
     latestAgentRuns,
     isLoadingLatestAgents,
     onSelectHomeThread: (workspaceId, threadId) => {
@@ -787,18 +972,25 @@ function MainApp() {
     gitIssuesLoading,
     gitIssuesError,
     gitRemoteUrl,
+    worktreeOverview,
+    worktreeOverviewLoading,
+    worktreeOverviewError,
     gitDiffs,
     gitDiffLoading: isDiffLoading,
     gitDiffError: diffError,
     onDiffLineReference: handleDiffLineReference,
     onDiffActivePathChange: handleActiveDiffPath,
-    onSend: handleSend,
+    onLoadFullDiff: loadFullDiff,
+    onSend: handleSendAndRemember,
     onStop: interruptTurn,
     canStop: canInterrupt,
     isReviewing,
     isProcessing,
     activeTokenUsage,
     activeQueue,
+    recentPrompts: recentPrompts.prompts,
+    snippets,
+    onExpandSnippet: expandSnippet,
     draftText: activeDraft,
     onDraftChange: handleDraftChange,
     activeImages,
@@ -831,6 +1023,12 @@ function MainApp() {
       }
       removeQueuedMessage(activeThreadId, id);
     },
+    onMoveQueued: (id, index) => {
+      if (!activeThreadId) {
+        return;
+      }
+      moveQueuedMessage(activeThreadId, id, index);
+    },
     models,
     selectedModelId,
     onSelectModel: setSelectedModelId,
@@ -875,6 +1073,7 @@ function MainApp() {
         <PhoneLayout
           approvalToastsNode={approvalToastsNode}
           updateToastNode={updateToastNode}
+          bridgeRecoveryNode={bridgeRecoveryNode}
           tabBarNode={tabBarNode}
           sidebarNode={sidebarNode}
           activeTab={activeTab}
@@ -895,6 +1094,7 @@ function MainApp() {
           tabletNavNode={tabletNavNode}
           approvalToastsNode={approvalToastsNode}
           updateToastNode={updateToastNode}
+          bridgeRecoveryNode={bridgeRecoveryNode}
           homeNode={homeNode}
           showHome={showHome}
           showWorkspace={Boolean(activeWorkspace && !showHome)}
@@ -912,6 +1112,7 @@ function MainApp() {
         <DesktopLayout
           sidebarNode={sidebarNode}
           updateToastNode={updateToastNode}
+          bridgeRecoveryNode={bridgeRecoveryNode}
           approvalToastsNode={approvalToastsNode}
           homeNode={homeNode}
           showHome={showHome}
@@ -941,6 +1142,9 @@ function MainApp() {
           onConfirm={confirmWorktreePrompt}
         />
       )}
+      {helpMenu.event && (
+        <HelpDialog event={helpMenu.event} onClose={helpMenu.dismiss} />
+      )}
       {settingsOpen && (
         <SettingsView
           workspaces={workspaces}
@@ -959,6 +1163,9 @@ function MainApp() {
           onUpdateWorkspaceCodexBin={async (id, codexBin) => {
             await updateWorkspaceCodexBin(id, codexBin);
           }}
+          onWorktreesRemoved={() => {
+            void refreshWorkspaces();
+          }}
           scaleShortcutTitle={scaleShortcutTitle}
           scaleShortcutText={scaleShortcutText}
         />
@@ -978,10 +1185,6 @@ function MainApp() {
 }
 
 function App() {
-  const windowLabel = useWindowLabel();
-  if (windowLabel === "about") {
-    return <AboutView />;
-  }
   return <MainApp />;
 }
 
@@ -0,0 +1,17 @@
+import React from "react";
+import ReactDOM from "react-dom/client";
+import "./styles/base.css";
+import "./styles/about.css";
+import { AboutView } from "./components/AboutView";
+
+document.documentElement.dataset.theme = window.matchMedia(
+  "(prefers-color-scheme: light)",
+).matches
+  ? "light"
+  : "dark";
+
+ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+  <React.StrictMode>
+    <AboutView />
+  </React.StrictMode>,
+);
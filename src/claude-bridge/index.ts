@@ -5,6 +5,7 @@ import type {
   SessionStartParams,
   SessionResumeParams,
   SessionCloseParams,
+  SessionSetPriorityParams,
   MessageSendParams,
   MessageInterruptParams,
   PermissionRespondParams,
@@ -14,6 +15,9 @@ import type {
   McpStatusParams,
   RewindFilesParams,
   SetMcpServersParams,
+  TextCompleteParams,
+  TextCompleteResult,
+  PingResult,
 } from "./types.js";
 import { emitResponse, emitError, log, logError } from "./event-emitter.js";
 import { sessionManager } from "./session-manager.js";
@@ -45,6 +49,9 @@ async function handleCommand(command: AnyBridgeCommand): Promise<unknown> {
     case "session/close":
       return handleSessionClose(params as SessionCloseParams);
 
+    case "session/setPriority":
+      return handleSessionSetPriority(params as SessionSetPriorityParams);
+
     case "message/send":
       return handleMessageSend(params as MessageSendParams);
 
@@ -72,6 +79,12 @@ async function handleCommand(command: AnyBridgeCommand): Promise<unknown> {
     case "mcp/set":
       return handleSetMcpServers(params as SetMcpServersParams);
 
+    case "text/complete":
+      return handleTextComplete(params as TextCompleteParams);
+
+    case "ping":
+      return handlePing();
+
     default:
       throw new Error(`Unknown method: ${method}`);
   }
@@ -81,6 +94,10 @@ async function handleCommand(command: AnyBridgeCommand): Promise<unknown> {
 // Command Handlers
 // ============================================================================
 
+function handlePing(): PingResult {
+  return { pong: true, uptimeMs: Math.round(process.uptime() * 1000) };
+}
+
 function handleInitialize(params: InitializeParams): { capabilities: string[] } {
   if (initialized) {
     throw new Error("Bridge already initialized");
@@ -98,6 +115,7 @@ function handleInitialize(params: InitializeParams): { capabilities: string[] }
       "session/resume",
       "session/close",
       "session/rewind",
+      "session/setPriority",
       "message/send",
       "message/interrupt",
       "permission/respond",
@@ -106,6 +124,7 @@ function handleInitialize(params: InitializeParams): { capabilities: string[] }
       "command/list",
       "mcp/status",
       "mcp/set",
+      "text/complete",
     ],
   };
 }
@@ -127,6 +146,11 @@ async function handleSessionStart(
     mcpServers,
     plugins,
     agents,
+    env,
+    allowedTools,
+    disallowedTools,
+    niceness,
+    appendSystemPrompt,
   } = params;
   const sessionId = await sessionManager.startSession(workspaceId, cwd, {
     model,
@@ -136,6 +160,11 @@ async function handleSessionStart(
     mcpServers,
     plugins,
     agents,
+    env,
+    allowedTools,
+    disallowedTools,
+    niceness,
+    appendSystemPrompt,
   });
 
   return { sessionId };
@@ -148,9 +177,22 @@ async function handleSessionResume(
     throw new Error("Bridge not initialized");
   }
 
-  const { workspaceId, sessionId, cwd, claudeCodeBin } = params;
+  const {
+    workspaceId,
+    sessionId,
+    cwd,
+    claudeCodeBin,
+    env,
+    allowedTools,
+    disallowedTools,
+    niceness,
+  } = params;
   await sessionManager.resumeSession(workspaceId, sessionId, cwd, {
     claudeCodeBin,
+    env,
+    allowedTools,
+    disallowedTools,
+    niceness,
   });
 
   return { success: true };
@@ -164,6 +206,14 @@ async function handleSessionClose(
   return { success: true };
 }
 
+async function handleSessionSetPriority(
+  params: SessionSetPriorityParams
+): Promise<{ success: boolean }> {
+  const { sessionId, niceness } = params;
+  sessionManager.setPriority(sessionId, niceness);
+  return { success: true };
+}
+
 async function handleMessageSend(
   params: MessageSendParams
 ): Promise<{ success: boolean }> {
@@ -259,6 +309,15 @@ async function handleSetMcpServers(
   return sessionManager.setMcpServers(sessionId, servers);
 }
 
+async function handleTextComplete(
+  params: TextCompleteParams
+): Promise<TextCompleteResult> {
+  if (!initialized) {
+    throw new Error("Bridge not initialized");
+  }
+  return sessionManager.complete(params);
+}
+
 // ============================================================================
 // Main Loop
 // ============================================================================
@@ -304,8 +363,13 @@ async function main(): Promise<void> {
       continue;
     }
 
-    // Validate command structure
-    if (typeof command.id !== "number" || typeof command.method !== "string") {
+    // Validate command structure; `null` and other non-objects are valid JSON
+    if (
+      typeof command !== "object" ||
+      command === null ||
+      typeof command.id !== "number" ||
+      typeof command.method !== "string"
+    ) {
       logError("Invalid command structure", command);
       emitError("", "", {
         code: "INVALID_COMMAND",
@@ -0,0 +1,76 @@
+import { spawn, execFileSync } from "child_process";
+import { setPriority } from "os";
+import type { SpawnOptions, SpawnedProcess } from "@anthropic-ai/claude-agent-sdk";
+import { log, logError } from "./event-emitter.js";
+
+/**
+ * The process and everything below it. Tools the agent already started
+ * (builds, test runners) would otherwise keep their old priority.
+ */
+function processTree(pid: number): number[] {
+  if (process.platform === "win32") return [pid];
+  let output: string;
+  try {
+    output = execFileSync("ps", ["-A", "-o", "pid=,ppid="], { encoding: "utf8" });
+  } catch {
+    return [pid];
+  }
+  const children = new Map<number, number[]>();
+  for (const line of output.split("\n")) {
+    const [child, parent] = line.trim().split(/\s+/).map(Number);
+    if (!child || Number.isNaN(parent)) continue;
+    children.set(parent, [...(children.get(parent) ?? []), child]);
+  }
+  const tree = [pid];
+  for (let i = 0; i < tree.length; i++) {
+    tree.push(...(children.get(tree[i]) ?? []));
+  }
+  return tree;
+}
+
+/**
+ * Set the nice level of a process tree. Fails only if the root process
+ * can't be changed; lowering the nice level usually needs elevated rights.
+ */
+export function applyNiceness(pid: number, niceness: number): void {
+  const [root, ...descendants] = processTree(pid);
+  setPriority(root, niceness);
+  for (const child of descendants) {
+    try {
+      setPriority(child, niceness);
+    } catch (error) {
+      // Children may have exited or belong to another user.
+      logError(`Could not set priority of process ${child}`, error);
+    }
+  }
+}
+
+/**
+ * Spawn the Claude Code process ourselves so its pid is known and new
+ * sessions start at the workspace's priority. Processes the agent runs
+ * inherit the nice level from it.
+ */
+export function spawnWithNiceness(
+  options: SpawnOptions,
+  niceness: number | undefined,
+  onSpawn: (pid: number) => void
+): SpawnedProcess {
+  const child = spawn(options.command, options.args, {
+    cwd: options.cwd,
+    env: options.env,
+    signal: options.signal,
+    stdio: ["pipe", "pipe", "ignore"],
+  });
+  if (child.pid !== undefined) {
+    onSpawn(child.pid);
+    if (niceness) {
+      try {
+        setPriority(child.pid, niceness);
+        log(`Started Claude Code process ${child.pid} at nice ${niceness}`);
+      } catch (error) {
+        logError(`Could not set priority of process ${child.pid}`, error);
+      }
+    }
+  }
+  return child;
+}
@@ -17,6 +17,8 @@ import type {
   AgentDefinition,
   RewindFilesResult,
   SetMcpServersResult,
+  TextCompleteParams,
+  TextCompleteResult,
 } from "./types.js";
 import {
   emitSessionStarted,
@@ -30,6 +32,7 @@ import {
   logError,
 } from "./event-emitter.js";
 import { permissionHandler } from "./permission-handler.js";
+import { applyNiceness, spawnWithNiceness } from "./process-priority.js";
 
 /**
  * Creates an async iterable input stream for multi-turn conversations.
@@ -106,6 +109,15 @@ export class SessionManager {
       mcpServers?: Record<string, McpServerConfig>;
       plugins?: PluginConfig[];
       agents?: Record<string, AgentDefinition>;
+      // Extra environment for the Claude Code process (auth context)
+      env?: Record<string, string>;
+      // Team permission rules
+      allowedTools?: string[];
+      disallowedTools?: string[];
+      // Nice level for the Claude Code process and the tools it runs
+      niceness?: number;
+      // Added to the Claude Code system prompt
+      appendSystemPrompt?: string;
     } = {}
   ): Promise<string> {
     log(`Starting session for workspace: ${workspaceId}, cwd: ${cwd}`);
@@ -135,6 +147,7 @@ export class SessionManager {
       closeInput: close,
       createdAt: Date.now(),
       status: "starting",
+      niceness: options.niceness,
     };
     this.sessions.set(tempSessionId, sessionState);
 
@@ -156,6 +169,16 @@ export class SessionManager {
           mcpServers: options.mcpServers,
           plugins: options.plugins,
           agents: options.agents,
+          env: options.env ? { ...process.env, ...options.env } : undefined,
+          allowedTools: options.allowedTools?.length ? options.allowedTools : undefined,
+          disallowedTools: options.disallowedTools?.length ? options.disallowedTools : undefined,
+          systemPrompt: options.appendSystemPrompt
+            ? { type: "preset", preset: "claude_code", append: options.appendSystemPrompt }
+            : undefined,
+          spawnClaudeCodeProcess: (spawnOptions) =>
+            spawnWithNiceness(spawnOptions, sessionState.niceness, (pid) => {
+              sessionState.pid = pid;
+            }),
         },
       });
 
@@ -182,6 +205,11 @@ export class SessionManager {
     cwd: string,
     options: {
       claudeCodeBin?: string;
+      env?: Record<string, string>;
+      allowedTools?: string[];
+      disallowedTools?: string[];
+      // Nice level for the Claude Code process and the tools it runs
+      niceness?: number;
     } = {}
   ): Promise<void> {
     log(`Resuming session: ${sessionId} for workspace: ${workspaceId}`);
@@ -206,6 +234,7 @@ export class SessionManager {
       closeInput: close,
       createdAt: Date.now(),
       status: "starting",
+      niceness: options.niceness,
     };
     this.sessions.set(sessionId, sessionState);
     this.workspaceToSession.set(workspaceId, sessionId);
@@ -220,6 +249,13 @@ export class SessionManager {
           canUseTool: permissionHandler.createCallback(() => sessionId, workspaceId),
           includePartialMessages: true,
           persistSession: true,
+          env: options.env ? { ...process.env, ...options.env } : undefined,
+          allowedTools: options.allowedTools?.length ? options.allowedTools : undefined,
+          disallowedTools: options.disallowedTools?.length ? options.disallowedTools : undefined,
+          spawnClaudeCodeProcess: (spawnOptions) =>
+            spawnWithNiceness(spawnOptions, sessionState.niceness, (pid) => {
+              sessionState.pid = pid;
+            }),
         },
       });
 
@@ -436,6 +472,60 @@ export class SessionManager {
     };
   }
 
+  /**
+   * Change the nice level of a running session's process tree.
+   */
+  setPriority(sessionId: string, niceness: number): void {
+    const session = this.sessions.get(sessionId);
+    if (!session) {
+      throw new Error(`Session not found: ${sessionId}`);
+    }
+    session.niceness = niceness;
+    // Not spawned yet: the process starts at the new level.
+    if (session.pid === undefined) return;
+    log(`Setting session ${sessionId} to nice ${niceness}`);
+    applyNiceness(session.pid, niceness);
+  }
+
+  /**
+   * Run a single tool-less turn outside any session and return its text.
+   * `tools: []` leaves the model no built-in tools; `allowedTools` only
+   * skips the permission prompt, so an empty list would not disable any.
+   */
+  async complete(params: TextCompleteParams): Promise<TextCompleteResult> {
+    const q = query({
+      prompt: params.prompt,
+      options: {
+        cwd: params.cwd,
+        model: params.model,
+        systemPrompt: params.systemPrompt,
+        pathToClaudeCodeExecutable: params.claudeCodeBin || undefined,
+        tools: [],
+        maxTurns: 1,
+        persistSession: false,
+        env: params.env ? { ...process.env, ...params.env } : undefined,
+      },
+    });
+
+    let text = "";
+    for await (const msg of q) {
+      if (msg.type === "result") {
+        if (msg.subtype !== "success") {
+          throw new Error(`Completion failed: ${msg.subtype}`);
+        }
+        return { text: msg.result, costUsd: msg.total_cost_usd, usage: msg.usage };
+      }
+      if (msg.type === "assistant") {
+        for (const block of msg.message.content) {
+          if (block.type === "text") {
+            text += block.text;
+          }
+        }
+      }
+    }
+    return { text };
+  }
+
   /**
    * Get a session by ID.
    */
@@ -50,7 +50,9 @@ export type CommandMethod =
   | "model/set"
   | "command/list"
   | "mcp/status"
-  | "mcp/set";
+  | "mcp/set"
+  | "text/complete"
+  | "ping";
 
 export type BridgeCommand<
   M extends CommandMethod = CommandMethod,
@@ -103,6 +105,16 @@ export type SessionStartParams = {
   mcpServers?: Record<string, McpServerConfig>;
   plugins?: PluginConfig[];
   agents?: Record<string, AgentDefinition>;
+  // Auth context resolved by the app (account profile)
+  accountId?: string;
+  env?: Record<string, string>;
+  // Team permission rules synced by the app
+  allowedTools?: string[];
+  disallowedTools?: string[];
+  // Nice level for the Claude Code process and the tools it runs
+  niceness?: number;
+  // Added to the Claude Code system prompt (workspace glossary)
+  appendSystemPrompt?: string;
 };
 
 export type SessionResumeParams = {
@@ -110,6 +122,11 @@ export type SessionResumeParams = {
   sessionId: string;
   cwd: string;
   claudeCodeBin?: string;
+  accountId?: string;
+  env?: Record<string, string>;
+  allowedTools?: string[];
+  disallowedTools?: string[];
+  niceness?: number;
 };
 
 export type SessionCloseParams = {
@@ -141,6 +158,11 @@ export type ModelSetParams = {
   model: string;
 };
 
+export type SessionSetPriorityParams = {
+  sessionId: string;
+  niceness: number;
+};
+
 export type CommandListParams = {
   sessionId?: string;
 };
@@ -180,6 +202,31 @@ export type SetMcpServersResult = {
   errors: Record<string, string>;
 };
 
+// One-shot completion with no tools and no persisted session, used for
+// summaries and other background text generation.
+export type TextCompleteParams = {
+  prompt: string;
+  systemPrompt?: string;
+  model?: string;
+  cwd?: string;
+  claudeCodeBin?: string;
+  env?: Record<string, string>;
+};
+
+export type TextCompleteResult = {
+  text: string;
+  costUsd?: number;
+  usage?: unknown;
+};
+
+// Heartbeat from the app; answered before initialize too.
+export type PingParams = Record<string, never>;
+
+export type PingResult = {
+  pong: true;
+  uptimeMs: number;
+};
+
 // Type-safe command definitions
 export type InitializeCommand = BridgeCommand<"initialize", InitializeParams>;
 export type SessionStartCommand = BridgeCommand<
@@ -194,6 +241,10 @@ export type SessionCloseCommand = BridgeCommand<
   "session/close",
   SessionCloseParams
 >;
+export type SessionSetPriorityCommand = BridgeCommand<
+  "session/setPriority",
+  SessionSetPriorityParams
+>;
 export type MessageSendCommand = BridgeCommand<
   "message/send",
   MessageSendParams
@@ -212,12 +263,15 @@ export type CommandListCommand = BridgeCommand<"command/list", CommandListParams
 export type McpStatusCommand = BridgeCommand<"mcp/status", McpStatusParams>;
 export type RewindFilesCommand = BridgeCommand<"session/rewind", RewindFilesParams>;
 export type SetMcpServersCommand = BridgeCommand<"mcp/set", SetMcpServersParams>;
+export type TextCompleteCommand = BridgeCommand<"text/complete", TextCompleteParams>;
+export type PingCommand = BridgeCommand<"ping", PingParams>;
 
 export type AnyBridgeCommand =
   | InitializeCommand
   | SessionStartCommand
   | SessionResumeCommand
   | SessionCloseCommand
+  | SessionSetPriorityCommand
   | RewindFilesCommand
   | MessageSendCommand
   | MessageInterruptCommand
@@ -226,7 +280,9 @@ export type AnyBridgeCommand =
   | ModelSetCommand
   | CommandListCommand
   | McpStatusCommand
-  | SetMcpServersCommand;
+  | SetMcpServersCommand
+  | TextCompleteCommand
+  | PingCommand;
 
 // ============================================================================
 // Stdout Events (Bridge -> Tauri)
@@ -402,6 +458,9 @@ export type SessionState = {
   closeInput: (() => void) | null;
   createdAt: number;
   status: "starting" | "active" | "closing" | "closed";
+  /** Pid of the Claude Code process, once spawned */
+  pid?: number;
+  niceness?: number;
 };
 
 // ============================================================================
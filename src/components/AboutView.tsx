@@ -1,24 +1,38 @@
-import { useEffect, useState } from "react";
-import { getVersion } from "@tauri-apps/api/app";
+import { useEffect, useMemo, useState } from "react";
 import { openUrl } from "@tauri-apps/plugin-opener";
+import type { AboutInfo } from "../types";
+import { getAboutInfo } from "../services/tauri";
+import { useAppearance } from "../hooks/useAppearance";
+import { useSystemUiPrefs } from "../hooks/useSystemUiPrefs";
 
 const GITHUB_URL = "https://github.com/Dimillian/CodexMonitor";
 const TWITTER_URL = "https://x.com/dimillian";
 
+function repositoryUrl(repository: string | null) {
+  if (!repository) {
+    return null;
+  }
+  const url = repository.replace(/^git\+/, "").replace(/\.git$/, "");
+  return url.startsWith("https://") ? url : null;
+}
+
 export function AboutView() {
-  const [version, setVersion] = useState<string | null>(null);
+  const [info, setInfo] = useState<AboutInfo | null>(null);
+  const [filter, setFilter] = useState("");
+  const systemUiPrefs = useSystemUiPrefs();
+  useAppearance(systemUiPrefs.colorScheme);
 
   useEffect(() => {
     let active = true;
-    getVersion()
+    getAboutInfo()
       .then((value) => {
         if (active) {
-          setVersion(value);
+          setInfo(value);
         }
       })
       .catch(() => {
         if (active) {
-          setVersion(null);
+          setInfo(null);
         }
       });
     return () => {
@@ -26,6 +40,14 @@ export function AboutView() {
     };
   }, []);
 
+  const acknowledgements = useMemo(() => {
+    const query = filter.trim().toLowerCase();
+    const all = info?.acknowledgements ?? [];
+    return query
+      ? all.filter((item) => item.name.toLowerCase().includes(query))
+      : all;
+  }, [filter, info]);
+
   return (
     <div className="about">
       <div className="about-card">
@@ -38,11 +60,26 @@ export function AboutView() {
           <div className="about-title">Claude Monitor</div>
         </div>
         <div className="about-version">
-          {version ? `Version ${version}` : "Version —"}
+          {info ? `Version ${info.version}` : "Version —"}
+          {info?.commit ? ` (${info.commit})` : ""}
         </div>
         <div className="about-tagline">
           Monitor the situation of your Claude agents
         </div>
+        {info && (
+          <dl className="about-details">
+            {info.buildDate && (
+              <>
+                <dt>Built</dt>
+                <dd>{info.buildDate}</dd>
+              </>
+            )}
+            <dt>Bridge</dt>
+            <dd>{info.bridgeVersion ?? "not found"}</dd>
+            <dt>Agent SDK</dt>
+            <dd>{info.sdkVersion ?? "not installed"}</dd>
+          </dl>
+        )}
         <div className="about-divider" />
         <div className="about-links">
           <button
@@ -61,6 +98,43 @@ export function AboutView() {
             Twitter
           </button>
         </div>
+        {info && info.acknowledgements.length > 0 && (
+          <details className="about-acknowledgements">
+            <summary>
+              Acknowledgements ({info.acknowledgements.length})
+            </summary>
+            <input
+              className="about-filter"
+              value={filter}
+              placeholder="Filter packages"
+              aria-label="Filter packages"
+              onChange={(event) => setFilter(event.target.value)}
+            />
+            <ul>
+              {acknowledgements.map((item) => {
+                const url = repositoryUrl(item.repository);
+                return (
+                  <li key={`${item.kind}:${item.name}@${item.version}`}>
+                    {url ? (
+                      <button
+                        type="button"
+                        className="about-link"
+                        onClick={() => openUrl(url)}
+                      >
+                        {item.name}
+                      </button>
+                    ) : (
+                      <span>{item.name}</span>
+                    )}
+                    <span className="about-package-meta">
+                      {item.version} · {item.license ?? "unknown license"}
+                    </span>
+                  </li>
+                );
+              })}
+            </ul>
+          </details>
+        )}
         <div className="about-footer">Made with ♥ by Claude & Dimillian</div>
       </div>
     </div>
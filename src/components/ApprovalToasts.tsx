@@ -1,4 +1,4 @@
-import type { WorkspaceInfo } from "../types";
+import type { AutoApproveWindow, ClaudeApprovalRequest, WorkspaceInfo } from "../types";
 import {
   type UnifiedApprovalRequest,
   isClaudeApproval,
@@ -9,6 +9,15 @@ type ApprovalToastsProps = {
   approvals: UnifiedApprovalRequest[];
   workspaces: WorkspaceInfo[];
   onDecision: (request: UnifiedApprovalRequest, decision: "accept" | "decline") => void;
+  autoApproveWindows: Record<string, AutoApproveWindow>;
+  onAutoApprove: (request: ClaudeApprovalRequest) => void;
+  onStopAutoApprove: (sessionId: string) => void;
+};
+
+const SCOPE_LABELS: Record<AutoApproveWindow["scope"], string> = {
+  reads: "reads",
+  edits: "edits",
+  all: "all tools",
 };
 
 function getApprovalMethod(request: UnifiedApprovalRequest): string {
@@ -29,8 +38,12 @@ export function ApprovalToasts({
   approvals,
   workspaces,
   onDecision,
+  autoApproveWindows,
+  onAutoApprove,
+  onStopAutoApprove,
 }: ApprovalToastsProps) {
-  if (!approvals.length) {
+  const windows = Object.values(autoApproveWindows);
+  if (!approvals.length && !windows.length) {
     return null;
   }
 
@@ -40,6 +53,25 @@ export function ApprovalToasts({
 
   return (
     <div className="approval-toasts" role="region" aria-live="assertive">
+      {windows.map((window) => (
+        <div key={window.sessionId} className="approval-toast auto-approve-toast" role="status">
+          <div className="approval-toast-title">
+            Auto-approving {SCOPE_LABELS[window.scope]} until{" "}
+            {new Date(window.expiresAt).toLocaleTimeString([], {
+              hour: "numeric",
+              minute: "2-digit",
+            })}
+          </div>
+          <div className="approval-toast-reason">
+            {window.approved} approved in {window.root}
+          </div>
+          <div className="approval-toast-actions">
+            <button className="secondary" onClick={() => onStopAutoApprove(window.sessionId)}>
+              Stop
+            </button>
+          </div>
+        </div>
+      ))}
       {approvals.map((request) => {
         const workspaceName = workspaceLabels.get(request.workspace_id);
         const method = getApprovalMethod(request);
@@ -70,6 +102,15 @@ export function ApprovalToasts({
               >
                 Decline
               </button>
+              {isClaudeApproval(request) && (
+                <button
+                  className="secondary"
+                  onClick={() => onAutoApprove(request)}
+                  title="Approve, then approve file reads and edits inside the workspace for 10 minutes"
+                >
+                  Approve edits for 10 min
+                </button>
+              )}
               <button
                 className="primary"
                 onClick={() => onDecision(request, "accept")}
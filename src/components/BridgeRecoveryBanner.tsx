@@ -0,0 +1,62 @@
+import type { BridgeRecoveryState } from "../hooks/useBridgeRecovery";
+
+type BridgeRecoveryBannerProps = {
+  state: BridgeRecoveryState;
+  onDismiss: () => void;
+};
+
+function plural(count: number, noun: string) {
+  return `${count} ${noun}${count === 1 ? "" : "s"}`;
+}
+
+export function BridgeRecoveryBanner({ state, onDismiss }: BridgeRecoveryBannerProps) {
+  if (state.stage === "idle") {
+    return null;
+  }
+
+  if (state.stage === "restarting") {
+    const { exitCode, failedRequests, sessions } = state.died;
+    return (
+      <div className="bridge-recovery-banner is-restarting" role="alert">
+        <div className="bridge-recovery-body">
+          <div className="bridge-recovery-title">
+            Claude stopped unexpectedly
+            {exitCode !== null ? ` (exit code ${exitCode})` : ""}. Restarting…
+          </div>
+          <div className="bridge-recovery-detail">
+            {sessions.length > 0
+              ? `${plural(sessions.length, "session")} will be reattached. `
+              : ""}
+            {failedRequests.length > 0
+              ? `Interrupted: ${failedRequests.map((request) => request.method).join(", ")}.`
+              : ""}
+          </div>
+        </div>
+        <button type="button" className="ghost" onClick={onDismiss}>
+          Hide
+        </button>
+      </div>
+    );
+  }
+
+  const { resumed, failed } = state.reconnected;
+  return (
+    <div
+      className={`bridge-recovery-banner ${failed.length > 0 ? "is-partial" : "is-recovered"}`}
+      role="status"
+    >
+      <div className="bridge-recovery-body">
+        <div className="bridge-recovery-title">Claude is back.</div>
+        <div className="bridge-recovery-detail">
+          {resumed.length > 0 ? `${plural(resumed.length, "session")} reattached. ` : ""}
+          {failed.length > 0
+            ? `${plural(failed.length, "session")} could not be reattached; resume them to continue.`
+            : ""}
+        </div>
+      </div>
+      <button type="button" className="ghost" onClick={onDismiss}>
+        Dismiss
+      </button>
+    </div>
+  );
+}
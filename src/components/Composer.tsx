@@ -1,5 +1,10 @@
 import { useCallback, useEffect, useRef, useState } from "react";
-import type { CustomPromptOption, QueuedMessage, ThreadTokenUsage } from "../types";
+import type {
+  CustomPromptOption,
+  QueuedMessage,
+  Snippet,
+  ThreadTokenUsage,
+} from "../types";
 import { useComposerAutocompleteState } from "../hooks/useComposerAutocompleteState";
 import { ComposerInput } from "./ComposerInput";
 import { ComposerMetaBar } from "./ComposerMetaBar";
@@ -25,6 +30,7 @@ type ComposerProps = {
   queuedMessages?: QueuedMessage[];
   onEditQueued?: (item: QueuedMessage) => void;
   onDeleteQueued?: (id: string) => void;
+  onMoveQueued?: (id: string, index: number) => void;
   sendLabel?: string;
   draftText?: string;
   onDraftChange?: (text: string) => void;
@@ -40,6 +46,10 @@ type ComposerProps = {
   subAgents?: { id: string; name: string }[];
   selectedSubAgentId?: string | null;
   onSelectSubAgent?: (id: string) => void;
+  /** Prompts sent before, newest first; Up and Down recall them. */
+  recentPrompts?: string[];
+  snippets?: Snippet[];
+  onExpandSnippet?: (name: string) => Promise<string>;
 };
 
 export function Composer({
@@ -62,6 +72,7 @@ export function Composer({
   queuedMessages = [],
   onEditQueued,
   onDeleteQueued,
+  onMoveQueued,
   sendLabel = "Send",
   draftText = "",
   onDraftChange,
@@ -77,8 +88,12 @@ export function Composer({
   subAgents,
   selectedSubAgentId,
   onSelectSubAgent,
+  recentPrompts = [],
+  snippets,
+  onExpandSnippet,
 }: ComposerProps) {
   const [text, setText] = useState(draftText);
+  const [historyIndex, setHistoryIndex] = useState(-1);
   const [selectionStart, setSelectionStart] = useState<number | null>(null);
   const internalRef = useRef<HTMLTextAreaElement | null>(null);
   const textareaRef = externalTextareaRef ?? internalRef;
@@ -108,8 +123,29 @@ export function Composer({
     }
     onSend(trimmed, attachedImages);
     setComposerText("");
+    setHistoryIndex(-1);
   }, [attachedImages, disabled, onSend, setComposerText, text]);
 
+  // Up on an empty composer (or a recalled prompt) steps back through
+  // recent prompts; Down steps forward and clears past the newest.
+  const recallPrompt = useCallback(
+    (key: string) => {
+      const recalled = historyIndex >= 0 && text === recentPrompts[historyIndex];
+      if (!recalled && text !== "") {
+        return false;
+      }
+      const start = recalled ? historyIndex : -1;
+      const next = key === "ArrowUp" ? start + 1 : start - 1;
+      if (next >= recentPrompts.length || (next < 0 && start < 0)) {
+        return false;
+      }
+      setHistoryIndex(next);
+      setComposerText(next >= 0 ? recentPrompts[next] : "");
+      return true;
+    },
+    [historyIndex, recentPrompts, setComposerText, text],
+  );
+
   const {
     isAutocompleteOpen,
     autocompleteMatches,
@@ -126,6 +162,8 @@ export function Composer({
     skills,
     prompts,
     files,
+    snippets,
+    onExpandSnippet,
     textareaRef,
     setText: setComposerText,
     setSelectionStart,
@@ -153,6 +191,7 @@ export function Composer({
         queuedMessages={queuedMessages}
         onEditQueued={onEditQueued}
         onDeleteQueued={onDeleteQueued}
+        onMoveQueued={onMoveQueued}
       />
       <ComposerInput
         text={text}
@@ -175,6 +214,14 @@ export function Composer({
           if (event.key === "Enter" && !event.shiftKey) {
             event.preventDefault();
             handleSend();
+            return;
+          }
+          if (
+            (event.key === "ArrowUp" || event.key === "ArrowDown") &&
+            !event.shiftKey &&
+            recallPrompt(event.key)
+          ) {
+            event.preventDefault();
           }
         }}
         textareaRef={textareaRef}
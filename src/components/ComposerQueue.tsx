@@ -8,15 +8,17 @@ type ComposerQueueProps = {
   queuedMessages: QueuedMessage[];
   onEditQueued?: (item: QueuedMessage) => void;
   onDeleteQueued?: (id: string) => void;
+  onMoveQueued?: (id: string, index: number) => void;
 };
 
 export function ComposerQueue({
   queuedMessages,
   onEditQueued,
   onDeleteQueued,
+  onMoveQueued,
 }: ComposerQueueProps) {
   const handleQueueMenu = useCallback(
-    async (event: React.MouseEvent, item: QueuedMessage) => {
+    async (event: React.MouseEvent, item: QueuedMessage, index: number) => {
       event.preventDefault();
       event.stopPropagation();
       const { clientX, clientY } = event;
@@ -28,12 +30,29 @@ export function ComposerQueue({
         text: "Delete",
         action: () => onDeleteQueued?.(item.id),
       });
-      const menu = await Menu.new({ items: [editItem, deleteItem] });
+      const items = [editItem, deleteItem];
+      if (onMoveQueued && index > 0) {
+        items.push(
+          await MenuItem.new({
+            text: "Send Sooner",
+            action: () => onMoveQueued(item.id, index - 1),
+          }),
+        );
+      }
+      if (onMoveQueued && index < queuedMessages.length - 1) {
+        items.push(
+          await MenuItem.new({
+            text: "Send Later",
+            action: () => onMoveQueued(item.id, index + 1),
+          }),
+        );
+      }
+      const menu = await Menu.new({ items });
       const window = getCurrentWindow();
       const position = new LogicalPosition(clientX, clientY);
       await menu.popup(position, window);
     },
-    [onDeleteQueued, onEditQueued],
+    [onDeleteQueued, onEditQueued, onMoveQueued, queuedMessages.length],
   );
 
   if (queuedMessages.length === 0) {
@@ -44,7 +63,7 @@ export function ComposerQueue({
     <div className="composer-queue">
       <div className="composer-queue-title">Queued</div>
       <div className="composer-queue-list">
-        {queuedMessages.map((item) => (
+        {queuedMessages.map((item, index) => (
           <div key={item.id} className="composer-queue-item">
             <span className="composer-queue-text">
               {item.text ||
@@ -59,7 +78,7 @@ export function ComposerQueue({
             </span>
             <button
               className="composer-queue-menu"
-              onClick={(event) => handleQueueMenu(event, item)}
+              onClick={(event) => handleQueueMenu(event, item, index)}
               aria-label="Queue item menu"
             >
               ...
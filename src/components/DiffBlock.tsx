@@ -1,6 +1,6 @@
 import type { KeyboardEvent, MouseEvent } from "react";
 import { useMemo } from "react";
-import { parseDiff, type ParsedDiffLine } from "../utils/diff";
+import { markWordChanges, parseDiff, type ParsedDiffLine } from "../utils/diff";
 import { highlightLine } from "../utils/syntax";
 
 type DiffBlockProps = {
@@ -33,7 +33,9 @@ export function DiffBlock({
       {parsed.map((line, index) => {
         const shouldHighlight =
           line.type === "add" || line.type === "del" || line.type === "context";
-        const html = highlightLine(line.text, shouldHighlight ? language : null);
+        const html = line.changes?.length
+          ? markWordChanges(line.text, line.changes)
+          : line.html ?? highlightLine(line.text, shouldHighlight ? language : null);
         const isSelectable = Boolean(onLineSelect) && shouldHighlight;
         const isSelected = Boolean(
           isSelectable &&
@@ -1,15 +1,26 @@
-import type { GitHubIssue, GitLogEntry } from "../types";
+import type {
+  GitHubIssue,
+  GitLogEntry,
+  LargeObject,
+  MergeCheck,
+  WorktreeOverview,
+} from "../types";
 import type { MouseEvent as ReactMouseEvent } from "react";
 import { Menu, MenuItem } from "@tauri-apps/api/menu";
 import { LogicalPosition } from "@tauri-apps/api/dpi";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { openUrl } from "@tauri-apps/plugin-opener";
 import { GitBranch } from "lucide-react";
+import { generateChangelog } from "../services/tauri";
+import { useLargeObjects } from "../hooks/useLargeObjects";
+import { useSecretFindings } from "../hooks/useSecretFindings";
+import { formatBytes } from "../utils/bytes";
 import { formatRelativeTime } from "../utils/time";
 
 type GitDiffPanelProps = {
-  mode: "diff" | "log" | "issues";
-  onModeChange: (mode: "diff" | "log" | "issues") => void;
+  workspaceId?: string | null;
+  mode: "diff" | "log" | "issues" | "merge";
+  onModeChange: (mode: "diff" | "log" | "issues" | "merge") => void;
   branchName: string;
   totalAdditions: number;
   totalDeletions: number;
@@ -28,6 +39,10 @@ type GitDiffPanelProps = {
   issuesLoading?: boolean;
   issuesError?: string | null;
   gitRemoteUrl?: string | null;
+  isWorktree?: boolean;
+  overview?: WorktreeOverview | null;
+  overviewLoading?: boolean;
+  overviewError?: string | null;
   selectedPath?: string | null;
   onSelectFile?: (path: string) => void;
   files: {
@@ -39,6 +54,11 @@ type GitDiffPanelProps = {
   logEntries: GitLogEntry[];
 };
 
+function largeObjectLabel(object: LargeObject) {
+  const lfs = object.lfs ? ", should be an LFS pointer" : "";
+  return `${object.path} (${formatBytes(object.size)}${lfs})`;
+}
+
 function splitPath(path: string) {
   const parts = path.split("/");
   if (parts.length === 1) {
@@ -75,6 +95,21 @@ function getStatusSymbol(status: string) {
   }
 }
 
+const MERGE_CHECK_LABELS: Record<MergeCheck["name"], string> = {
+  uncommitted: "Uncommitted changes",
+  conflicts: "Conflicts",
+  behind: "Parent branch",
+  ci: "CI",
+  sessions: "Sessions",
+};
+
+const MERGE_CHECK_SYMBOLS: Record<MergeCheck["status"], string> = {
+  pass: "✓",
+  warn: "!",
+  fail: "✕",
+  unknown: "?",
+};
+
 function getStatusClass(status: string) {
   switch (status) {
     case "A":
@@ -93,6 +128,7 @@ function getStatusClass(status: string) {
 }
 
 export function GitDiffPanel({
+  workspaceId = null,
   mode,
   onModeChange,
   branchName,
@@ -117,7 +153,19 @@ export function GitDiffPanel({
   issuesTotal = 0,
   issuesLoading = false,
   issuesError = null,
+  isWorktree = false,
+  overview = null,
+  overviewLoading = false,
+  overviewError = null,
 }: GitDiffPanelProps) {
+  const largeObjects = useLargeObjects(
+    workspaceId,
+    `${files.length}:${totalAdditions}:${totalDeletions}:${logAhead}`,
+  );
+  const secretFindings = useSecretFindings(
+    workspaceId,
+    `${files.length}:${totalAdditions}:${totalDeletions}`,
+  );
   const githubBaseUrl = (() => {
     if (!gitRemoteUrl) {
       return null;
@@ -151,6 +199,16 @@ export function GitDiffPanel({
       },
     });
     const items = [copyItem];
+    if (workspaceId) {
+      const changelogItem = await MenuItem.new({
+        text: "Copy Changelog Since This Commit",
+        action: async () => {
+          const changelog = await generateChangelog(workspaceId, entry.sha);
+          await navigator.clipboard.writeText(changelog.markdown);
+        },
+      });
+      items.push(changelogItem);
+    }
     if (githubBaseUrl) {
       const openItem = await MenuItem.new({
         text: "Open on GitHub",
@@ -176,6 +234,7 @@ export function GitDiffPanel({
   const logUpstreamLabel = logUpstream ? `Upstream ${logUpstream}` : "";
   const showAheadSection = logUpstream && logAhead > 0;
   const showBehindSection = logUpstream && logBehind > 0;
+  const pullRequest = overview?.pullRequest ?? null;
   const hasDiffTotals = totalAdditions > 0 || totalDeletions > 0;
   const diffTotalsLabel = `+${totalAdditions} / -${totalDeletions}`;
   const diffStatusLabel = hasDiffTotals
@@ -220,6 +279,17 @@ export function GitDiffPanel({
           >
             Issues
           </button>
+          {isWorktree && (
+            <button
+              type="button"
+              role="tab"
+              aria-selected={mode === "merge"}
+              className={mode === "merge" ? "active" : ""}
+              onClick={() => onModeChange("merge")}
+            >
+              Merge
+            </button>
+          )}
         </div>
       </div>
       {mode === "diff" ? (
@@ -239,6 +309,28 @@ export function GitDiffPanel({
             )}
           </div>
         </>
+      ) : mode === "merge" ? (
+        <>
+          <div className="diff-status diff-status-issues">
+            <span>
+              {overview
+                ? overview.ready
+                  ? "Ready to merge"
+                  : "Not ready to merge"
+                : "Ready to merge?"}
+            </span>
+            {overviewLoading && <span className="git-panel-spinner" aria-hidden />}
+          </div>
+          {overview && (
+            <div className="git-log-sync">
+              <span>
+                {overview.branch} → {overview.parentBranch}
+              </span>
+              <span className="git-log-sep">·</span>
+              <span>{`↑${overview.ahead} ↓${overview.behind}`}</span>
+            </div>
+          )}
+        </>
       ) : (
         <>
           <div className="diff-status diff-status-issues">
@@ -250,12 +342,30 @@ export function GitDiffPanel({
           </div>
         </>
       )}
-      {mode !== "issues" && (
+      {(mode === "diff" || mode === "log") && (
         <div className="diff-branch">{branchName || "unknown"}</div>
       )}
       {mode === "diff" ? (
         <div className="diff-list">
           {error && <div className="diff-error">{error}</div>}
+          {largeObjects.blocked ? (
+            <div className="diff-warning">{largeObjects.blocked}</div>
+          ) : (
+            largeObjects.staged.length > 0 && (
+              <div className="diff-warning">
+                Staged files over the large file limit:{" "}
+                {largeObjects.staged.map(largeObjectLabel).join(", ")}
+              </div>
+            )
+          )}
+          {secretFindings.length > 0 && (
+            <div className="diff-warning">
+              Possible credentials in the changes:{" "}
+              {secretFindings
+                .map((finding) => `${finding.path}:${finding.line} (${finding.kind})`)
+                .join(", ")}
+            </div>
+          )}
           {!error && !files.length && (
             <div className="diff-empty">No changes detected.</div>
           )}
@@ -305,6 +415,12 @@ export function GitDiffPanel({
       ) : mode === "log" ? (
         <div className="git-log-list">
           {logError && <div className="diff-error">{logError}</div>}
+          {showAheadSection && largeObjects.unpushed.length > 0 && (
+            <div className="diff-warning">
+              Pushing uploads large files as plain git objects:{" "}
+              {largeObjects.unpushed.map(largeObjectLabel).join(", ")}
+            </div>
+          )}
           {!logError && logLoading && (
             <div className="diff-viewer-loading">Loading commits...</div>
           )}
@@ -338,7 +454,7 @@ export function GitDiffPanel({
                       </span>
                       <span className="git-log-sep">·</span>
                       <span className="git-log-date">
-                        {formatRelativeTime(entry.timestamp * 1000)}
+                        {formatRelativeTime(entry.timestamp)}
                       </span>
                     </div>
                   </div>
@@ -369,7 +485,7 @@ export function GitDiffPanel({
                       </span>
                       <span className="git-log-sep">·</span>
                       <span className="git-log-date">
-                        {formatRelativeTime(entry.timestamp * 1000)}
+                        {formatRelativeTime(entry.timestamp)}
                       </span>
                     </div>
                   </div>
@@ -398,7 +514,7 @@ export function GitDiffPanel({
                       </span>
                       <span className="git-log-sep">·</span>
                       <span className="git-log-date">
-                        {formatRelativeTime(entry.timestamp * 1000)}
+                        {formatRelativeTime(entry.timestamp)}
                       </span>
                     </div>
                   </div>
@@ -407,6 +523,84 @@ export function GitDiffPanel({
             </div>
           )}
         </div>
+      ) : mode === "merge" ? (
+        <div className="git-merge-overview">
+          {overviewError && <div className="diff-error">{overviewError}</div>}
+          {overview && (
+            <>
+              <div className="git-log-section">
+                <div className="git-log-section-title">Checks</div>
+                {overview.checks.map((check) => (
+                  <div key={check.name} className={`git-merge-check is-${check.status}`}>
+                    <span className="git-merge-check-icon" aria-hidden>
+                      {MERGE_CHECK_SYMBOLS[check.status]}
+                    </span>
+                    <div>
+                      <div className="git-merge-check-name">
+                        {MERGE_CHECK_LABELS[check.name]}
+                      </div>
+                      <div className="git-merge-check-detail">{check.detail}</div>
+                    </div>
+                  </div>
+                ))}
+                {pullRequest && (
+                  <a
+                    className="git-issue-entry"
+                    href={pullRequest.url}
+                    onClick={(event) => {
+                      event.preventDefault();
+                      void openUrl(pullRequest.url);
+                    }}
+                  >
+                    <span className="git-issue-title">
+                      <span className="git-issue-number">#{pullRequest.number}</span>{" "}
+                      Pull request · {pullRequest.state.toLowerCase()}
+                    </span>
+                  </a>
+                )}
+              </div>
+              <div className="git-log-section">
+                <div className="git-log-section-title">Sessions</div>
+                {!overview.sessions.length && (
+                  <div className="diff-empty">No sessions in this worktree.</div>
+                )}
+                {overview.sessions.map((session) => (
+                  <div key={session.sessionId} className="git-log-entry git-log-entry-compact">
+                    <div className="git-log-summary">{session.title}</div>
+                    <div className="git-log-meta">
+                      <span>{session.running ? "Working" : "Idle"}</span>
+                      <span className="git-log-sep">·</span>
+                      <span className="git-log-date">
+                        {formatRelativeTime(session.lastActivity)}
+                      </span>
+                    </div>
+                  </div>
+                ))}
+              </div>
+              <div className="git-log-section">
+                <div className="git-log-section-title">
+                  {`Changes · +${overview.totalAdditions} / -${overview.totalDeletions}`}
+                </div>
+                {!overview.files.length && (
+                  <div className="diff-empty">No changes since {overview.parentBranch}.</div>
+                )}
+                {overview.files.map((file) => (
+                  <div key={file.path} className="git-merge-file">
+                    <span className={`diff-icon ${getStatusClass(file.status)}`} aria-hidden>
+                      {getStatusSymbol(file.status)}
+                    </span>
+                    <span className="git-merge-file-path">{file.path}</span>
+                    <span className="diff-counts-inline">
+                      <span className="diff-add">+{file.additions}</span>
+                      <span className="diff-sep">/</span>
+                      <span className="diff-del">-{file.deletions}</span>
+                    </span>
+                  </div>
+                ))}
+              </div>
+            </>
+          )}
+        </div>
       ) : (
         <div className="git-issues-list">
           {issuesError && <div className="diff-error">{issuesError}</div>}
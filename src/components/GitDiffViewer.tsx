@@ -2,13 +2,22 @@ import { useCallback, useEffect, useRef, useState } from "react";
 import { DiffBlock } from "./DiffBlock";
 import { parseDiff } from "../utils/diff";
 import { languageFromPath } from "../utils/syntax";
-import type { DiffLineReference } from "../types";
+import type {
+  DiffLineReference,
+  DiffTruncation,
+  IntralineChange,
+  LfsInfo,
+} from "../types";
 import type { ParsedDiffLine } from "../utils/diff";
 
 type GitDiffViewerItem = {
   path: string;
   status: string;
   diff: string;
+  highlighted?: string[];
+  intraline?: IntralineChange[];
+  truncated?: DiffTruncation;
+  lfs?: LfsInfo;
 };
 
 type GitDiffViewerProps = {
@@ -18,6 +27,7 @@ type GitDiffViewerProps = {
   error: string | null;
   onLineReference?: (reference: DiffLineReference) => void;
   onActivePathChange?: (path: string) => void;
+  onLoadFullDiff?: (path: string) => void;
 };
 
 type SelectedRange = {
@@ -31,6 +41,19 @@ type SelectableDiffLine = ParsedDiffLine & {
   type: "add" | "del" | "context";
 };
 
+function lfsWarning(lfs: LfsInfo | undefined): string | null {
+  if (!lfs) {
+    return null;
+  }
+  if (lfs.pointerEdited) {
+    return "LFS pointer edited";
+  }
+  if (lfs.oversized !== undefined) {
+    return `${Math.round(lfs.oversized / (1024 * 1024))} MB, not in LFS`;
+  }
+  return lfs.tracked ? "LFS" : null;
+}
+
 function isSelectableLine(line: ParsedDiffLine): line is SelectableDiffLine {
   return line.type === "add" || line.type === "del" || line.type === "context";
 }
@@ -42,6 +65,7 @@ export function GitDiffViewer({
   error,
   onLineReference,
   onActivePathChange,
+  onLoadFullDiff,
 }: GitDiffViewerProps) {
   const containerRef = useRef<HTMLDivElement>(null);
   const itemRefs = useRef(new Map<string, HTMLDivElement>());
@@ -233,7 +257,10 @@ export function GitDiffViewer({
           const isSelected = entry.path === selectedPath;
           const hasDiff = entry.diff.trim().length > 0;
           const language = languageFromPath(entry.path);
-          const parsedLines = parseDiff(entry.diff);
+          const parsedLines = parseDiff(entry.diff, {
+            highlighted: entry.highlighted,
+            intraline: entry.intraline,
+          });
           const selectedRangeForEntry =
             selectedRange?.path === entry.path
               ? { start: selectedRange.start, end: selectedRange.end }
@@ -253,6 +280,15 @@ export function GitDiffViewer({
               <div className="diff-viewer-header">
                 <span className="diff-viewer-status">{entry.status}</span>
                 <span className="diff-viewer-path">{entry.path}</span>
+                {lfsWarning(entry.lfs) && (
+                  <span
+                    className={`diff-viewer-lfs${
+                      entry.lfs?.tracked && !entry.lfs.pointerEdited ? "" : " warning"
+                    }`}
+                  >
+                    {lfsWarning(entry.lfs)}
+                  </span>
+                )}
               </div>
               {hasDiff ? (
                 <div className="diff-viewer-output">
@@ -271,6 +307,23 @@ export function GitDiffViewer({
                     }
                     selectedRange={selectedRangeForEntry}
                   />
+                  {entry.truncated && (
+                    <div className="diff-viewer-truncated">
+                      <span>
+                        Showing {entry.truncated.shownLines} of{" "}
+                        {entry.truncated.totalLines} lines.
+                      </span>
+                      {onLoadFullDiff && (
+                        <button
+                          type="button"
+                          className="ghost"
+                          onClick={() => onLoadFullDiff(entry.path)}
+                        >
+                          Load full diff
+                        </button>
+                      )}
+                    </div>
+                  )}
                 </div>
               ) : (
                 <div className="diff-viewer-placeholder">Diff unavailable.</div>
@@ -0,0 +1,209 @@
+import { useEffect, useState } from "react";
+import { Pencil, Trash2 } from "lucide-react";
+import type { GlossaryTerm, WorkspaceGlossary, WorkspaceInfo } from "../types";
+import {
+  deleteGlossaryTerm,
+  getGlossary,
+  setGlossaryEnabled,
+  upsertGlossaryTerm,
+} from "../services/tauri";
+
+type GlossarySettingsProps = {
+  projects: WorkspaceInfo[];
+};
+
+type TermDraft = {
+  id: string | null;
+  term: string;
+  definition: string;
+};
+
+const EMPTY_GLOSSARY: WorkspaceGlossary = { enabled: false, terms: [] };
+const EMPTY_DRAFT: TermDraft = { id: null, term: "", definition: "" };
+
+export function GlossarySettings({ projects }: GlossarySettingsProps) {
+  const [workspaceId, setWorkspaceId] = useState<string | null>(projects[0]?.id ?? null);
+  const [glossary, setGlossary] = useState<WorkspaceGlossary>(EMPTY_GLOSSARY);
+  const [draft, setDraft] = useState<TermDraft>(EMPTY_DRAFT);
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setGlossary(EMPTY_GLOSSARY);
+    setDraft(EMPTY_DRAFT);
+    setError(null);
+    if (!workspaceId) {
+      return;
+    }
+    let canceled = false;
+    void getGlossary(workspaceId)
+      .then((loaded) => {
+        if (!canceled) {
+          setGlossary(loaded);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+    };
+  }, [workspaceId]);
+
+  const reload = async (id: string) => {
+    setGlossary(await getGlossary(id));
+  };
+
+  const run = async (action: (id: string) => Promise<unknown>) => {
+    if (!workspaceId) {
+      return;
+    }
+    setIsSaving(true);
+    setError(null);
+    try {
+      await action(workspaceId);
+      await reload(workspaceId);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleSave = () =>
+    run(async (id) => {
+      await upsertGlossaryTerm(id, draft.term, draft.definition, draft.id);
+      setDraft(EMPTY_DRAFT);
+    });
+
+  const handleDelete = (term: GlossaryTerm) =>
+    run(async (id) => {
+      await deleteGlossaryTerm(id, term.id);
+      if (draft.id === term.id) {
+        setDraft(EMPTY_DRAFT);
+      }
+    });
+
+  return (
+    <section className="settings-section">
+      <div className="settings-section-title">Glossary</div>
+      <div className="settings-section-subtitle">
+        Domain terms and what they mean in a project. When enabled, they&apos;re added to the
+        system prompt of every new session; worktrees use their project&apos;s glossary.
+      </div>
+      {projects.length === 0 ? (
+        <div className="settings-empty">No projects yet.</div>
+      ) : (
+        <>
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="glossary-workspace">
+              Project
+            </label>
+            <select
+              id="glossary-workspace"
+              className="settings-select"
+              value={workspaceId ?? ""}
+              onChange={(event) => setWorkspaceId(event.target.value)}
+            >
+              {projects.map((workspace) => (
+                <option key={workspace.id} value={workspace.id}>
+                  {workspace.name}
+                </option>
+              ))}
+            </select>
+          </div>
+          <div className="settings-toggle-row">
+            <div>
+              <div className="settings-toggle-title">Add to new sessions</div>
+              <div className="settings-toggle-subtitle">
+                Sessions already running keep the prompt they started with.
+              </div>
+            </div>
+            <button
+              type="button"
+              className={`settings-toggle ${glossary.enabled ? "on" : ""}`}
+              onClick={() => void run((id) => setGlossaryEnabled(id, !glossary.enabled))}
+              aria-pressed={glossary.enabled}
+              disabled={isSaving}
+            >
+              <span className="settings-toggle-knob" />
+            </button>
+          </div>
+          <div className="settings-projects settings-glossary-terms">
+            {glossary.terms.map((term) => (
+              <div key={term.id} className="settings-project-row">
+                <div className="settings-project-info">
+                  <div className="settings-project-name">{term.term}</div>
+                  <div className="settings-project-path">{term.definition}</div>
+                </div>
+                <div className="settings-project-actions">
+                  <button
+                    type="button"
+                    className="ghost icon-button"
+                    onClick={() =>
+                      setDraft({ id: term.id, term: term.term, definition: term.definition })
+                    }
+                    aria-label={`Edit ${term.term}`}
+                  >
+                    <Pencil aria-hidden />
+                  </button>
+                  <button
+                    type="button"
+                    className="ghost icon-button"
+                    onClick={() => void handleDelete(term)}
+                    aria-label={`Delete ${term.term}`}
+                  >
+                    <Trash2 aria-hidden />
+                  </button>
+                </div>
+              </div>
+            ))}
+            {glossary.terms.length === 0 && (
+              <div className="settings-empty">No terms yet.</div>
+            )}
+          </div>
+          <div className="settings-field settings-glossary-editor">
+            <label className="settings-field-label" htmlFor="glossary-term">
+              {draft.id ? "Edit term" : "Add a term"}
+            </label>
+            <div className="settings-field-row">
+              <input
+                id="glossary-term"
+                className="settings-input settings-glossary-name"
+                value={draft.term}
+                placeholder="Term"
+                onChange={(event) => setDraft({ ...draft, term: event.target.value })}
+              />
+              <input
+                className="settings-input"
+                value={draft.definition}
+                placeholder="Definition"
+                aria-label="Definition"
+                onChange={(event) => setDraft({ ...draft, definition: event.target.value })}
+              />
+            </div>
+            <div className="settings-field-actions">
+              <button
+                type="button"
+                className="primary"
+                onClick={() => void handleSave()}
+                disabled={isSaving || !draft.term.trim() || !draft.definition.trim()}
+              >
+                {draft.id ? "Save" : "Add"}
+              </button>
+              {draft.id && (
+                <button
+                  type="button"
+                  className="ghost"
+                  onClick={() => setDraft(EMPTY_DRAFT)}
+                  disabled={isSaving}
+                >
+                  Cancel
+                </button>
+              )}
+            </div>
+          </div>
+        </>
+      )}
+      {error && <div className="settings-help">{error}</div>}
+    </section>
+  );
+}
@@ -0,0 +1,105 @@
+import { useEffect } from "react";
+import type { ClaudeDoctorResult, HelpMenuEvent } from "../types";
+
+type HelpDialogProps = {
+  event: HelpMenuEvent;
+  onClose: () => void;
+};
+
+const isMac = navigator.platform.toLowerCase().includes("mac");
+const MOD = isMac ? "⌘" : "Ctrl";
+
+const SHORTCUTS: [string, string][] = [
+  [`${MOD}+N`, "New agent"],
+  ["Enter", "Send message"],
+  ["Shift+Enter", "New line"],
+  ["Tab", "Accept autocomplete"],
+  ["Esc", "Close autocomplete"],
+  [`${MOD}++ / ${MOD}+-`, "Zoom in / out"],
+  [`${MOD}+0`, "Reset zoom"],
+  [`${MOD}+/`, "Keyboard shortcuts"],
+];
+
+function doctorLine(ok: boolean, version: string | null, details: string | null) {
+  return ok ? `ok (${version ?? "unknown"})` : `missing${details ? ` — ${details}` : ""}`;
+}
+
+function DoctorResult({ result }: { result: ClaudeDoctorResult }) {
+  return (
+    <dl className="help-dialog-list">
+      <dt>Claude Code</dt>
+      <dd>{doctorLine(result.claudeOk, result.claudeVersion, result.claudeDetails)}</dd>
+      <dt>Node.js</dt>
+      <dd>{doctorLine(result.nodeOk, result.nodeVersion, result.nodeDetails)}</dd>
+      <dt>Bridge</dt>
+      <dd>{doctorLine(result.bridgeOk, result.bridgeCommand, result.bridgeDetails)}</dd>
+    </dl>
+  );
+}
+
+export function HelpDialog({ event, onClose }: HelpDialogProps) {
+  useEffect(() => {
+    const handleKey = (keyEvent: KeyboardEvent) => {
+      if (keyEvent.key === "Escape") {
+        onClose();
+      }
+    };
+    window.addEventListener("keydown", handleKey);
+    return () => window.removeEventListener("keydown", handleKey);
+  }, [onClose]);
+
+  let title: string;
+  let body: React.ReactNode;
+  switch (event.action) {
+    case "doctor":
+      title = event.result.ok ? "Doctor: all good" : "Doctor: problems found";
+      body = <DoctorResult result={event.result} />;
+      break;
+    case "doctorFailed":
+      title = "Doctor failed";
+      body = <div className="worktree-modal-error">{event.error}</div>;
+      break;
+    case "shortcuts":
+      title = "Keyboard shortcuts";
+      body = (
+        <dl className="help-dialog-list">
+          {SHORTCUTS.map(([keys, action]) => (
+            <div key={keys} className="help-dialog-row">
+              <dt>
+                <kbd>{keys}</kbd>
+              </dt>
+              <dd>{action}</dd>
+            </div>
+          ))}
+        </dl>
+      );
+      break;
+    case "diagnosticsExported":
+      title = "Diagnostics exported";
+      body = (
+        <div className="worktree-modal-subtitle">
+          Saved to <code>{event.path}</code>. Attach it to your issue report.
+        </div>
+      );
+      break;
+  }
+
+  return (
+    <div className="worktree-modal" role="dialog" aria-modal="true">
+      <div className="worktree-modal-backdrop" onClick={onClose} />
+      <div className="worktree-modal-card">
+        <div className="worktree-modal-title">{title}</div>
+        {body}
+        <div className="worktree-modal-actions">
+          <button
+            className="primary worktree-modal-button"
+            onClick={onClose}
+            type="button"
+          >
+            Close
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
 import { memo, useEffect, useRef, useState } from "react";
-import type { ConversationItem } from "../types";
+import type { ConversationItem, FileReference } from "../types";
 import { Markdown } from "./Markdown";
 import { DiffBlock } from "./DiffBlock";
 import { languageFromPath } from "../utils/syntax";
@@ -12,6 +12,7 @@ type MessagesProps = {
   lastDurationMs?: number | null;
   onRewindToMessage?: (itemId: string, itemIndex: number) => void;
   isRewinding?: boolean;
+  onOpenReference?: (reference: FileReference) => void;
 };
 
 type ToolSummary = {
@@ -208,6 +209,8 @@ function scrollKeyForItems(items: ConversationItem[]) {
       return `${last.id}-${last.status ?? ""}-${last.diff.length}`;
     case "review":
       return `${last.id}-${last.state}-${last.text.length}`;
+    case "interrupted":
+      return last.id;
     default:
       const _exhaustive: never = last;
       return _exhaustive;
@@ -221,6 +224,7 @@ export const Messages = memo(function Messages({
   lastDurationMs = null,
   onRewindToMessage,
   isRewinding = false,
+  onOpenReference,
 }: MessagesProps) {
   const bottomRef = useRef<HTMLDivElement | null>(null);
   const [expandedItems, setExpandedItems] = useState<Set<string>>(new Set());
@@ -297,6 +301,24 @@ export const Messages = memo(function Messages({
             <div key={item.id} className={`message ${item.role}`}>
               <div className="bubble">
                 <Markdown value={item.text} className="markdown" />
+                {onOpenReference && item.references?.some((reference) => reference.exists) && (
+                  <div className="message-references">
+                    {item.references
+                      .filter((reference) => reference.exists)
+                      .map((reference) => (
+                        <button
+                          key={reference.text}
+                          type="button"
+                          className="message-reference"
+                          onClick={() => onOpenReference(reference)}
+                          title={`Open ${reference.path}`}
+                        >
+                          {basename(reference.path)}
+                          {reference.line ? `:${reference.line}` : ""}
+                        </button>
+                      ))}
+                  </div>
+                )}
               </div>
               {onRewindToMessage && item.role === "user" && (
                 <button
@@ -393,6 +415,30 @@ export const Messages = memo(function Messages({
             </div>
           );
         }
+        if (item.kind === "interrupted") {
+          const stoppedTools = item.tools.filter((tool) => tool.status === "interrupted");
+          return (
+            <div key={item.id} className="item-card interrupted">
+              <div className="review-header">
+                <span className="review-title">Turn interrupted</span>
+                <span className="review-badge">
+                  {item.inputTokens.toLocaleString()} in ·{" "}
+                  {item.outputTokens.toLocaleString()} out
+                </span>
+              </div>
+              {item.text && (
+                <Markdown value={item.text} className="item-text markdown" />
+              )}
+              {item.tools.length > 0 && (
+                <div className="interrupted-tools">
+                  {item.tools.length} tool call{item.tools.length === 1 ? "" : "s"}
+                  {stoppedTools.length > 0 &&
+                    `, stopped: ${stoppedTools.map((tool) => tool.toolName).join(", ")}`}
+                </div>
+              )}
+            </div>
+          );
+        }
         if (item.kind === "diff") {
           return (
             <div key={item.id} className="item-card diff">
@@ -3,6 +3,9 @@ import { open } from "@tauri-apps/plugin-dialog";
 import {
   ChevronDown,
   ChevronUp,
+  BookOpen,
+  FileText,
+  GitBranch,
   Laptop2,
   LayoutGrid,
   Stethoscope,
@@ -11,7 +14,29 @@ import {
   X,
   Zap,
 } from "lucide-react";
-import type { AppSettings, ClaudeDoctorResult, CodexDoctorResult, WorkspaceInfo } from "../types";
+import type {
+  AppSettings,
+  AppearanceSettings,
+  BridgeRuntime,
+  ClaudeDoctorResult,
+  CodexDoctorResult,
+  PowerSavingSettings,
+  QuietHours,
+  TeamPromptsStatus,
+  WorkspaceInfo,
+} from "../types";
+import {
+  acceptTeamPermissions,
+  checkCaBundle,
+  getTeamPromptsStatus,
+  sendDigest,
+  syncTeamPrompts,
+  testProxy,
+} from "../services/tauri";
+import { usePowerStatus } from "../hooks/usePowerStatus";
+import { GlossarySettings } from "./GlossarySettings";
+import { SnippetsSettings } from "./SnippetsSettings";
+import { WorktreeCleanupSettings } from "./WorktreeCleanupSettings";
 import {
   clampUiScale,
 } from "../utils/uiScale";
@@ -28,12 +53,32 @@ type SettingsViewProps = {
   onRunDoctor: (codexBin: string | null) => Promise<CodexDoctorResult>;
   onRunClaudeDoctor?: (claudeCodeBin: string | null) => Promise<ClaudeDoctorResult>;
   onUpdateWorkspaceCodexBin: (id: string, codexBin: string | null) => Promise<void>;
+  onWorktreesRemoved: () => void;
   scaleShortcutTitle: string;
   scaleShortcutText: string;
 };
 
 type SettingsSection = "projects" | "display";
-type CodexSection = SettingsSection | "codex" | "claude-code" | "mcp-servers";
+type CodexSection =
+  | SettingsSection
+  | "codex"
+  | "claude-code"
+  | "mcp-servers"
+  | "snippets"
+  | "glossary"
+  | "worktrees";
+
+const DEFAULT_QUIET_HOURS: QuietHours = { start: 22 * 60, end: 7 * 60 };
+
+function minutesToTime(minutes: number) {
+  const hours = Math.floor(minutes / 60);
+  return `${String(hours).padStart(2, "0")}:${String(minutes % 60).padStart(2, "0")}`;
+}
+
+function timeToMinutes(value: string) {
+  const [hours, minutes] = value.split(":").map(Number);
+  return (hours || 0) * 60 + (minutes || 0);
+}
 
 function orderValue(workspace: WorkspaceInfo) {
   const value = workspace.settings.sortOrder;
@@ -52,12 +97,23 @@ export function SettingsView({
   onRunDoctor,
   onRunClaudeDoctor,
   onUpdateWorkspaceCodexBin,
+  onWorktreesRemoved,
   scaleShortcutTitle,
   scaleShortcutText,
 }: SettingsViewProps) {
   const [activeSection, setActiveSection] = useState<CodexSection>("projects");
+  const powerStatus = usePowerStatus();
   const [codexPathDraft, setCodexPathDraft] = useState(appSettings.codexBin ?? "");
   const [claudeCodePathDraft, setClaudeCodePathDraft] = useState(appSettings.claudeCodeBin ?? "");
+  const [teamRepoDraft, setTeamRepoDraft] = useState(appSettings.teamPromptsRepo ?? "");
+  const [telemetryEndpointDraft, setTelemetryEndpointDraft] = useState(
+    appSettings.telemetry?.endpoint ?? "",
+  );
+  const [teamSyncState, setTeamSyncState] = useState<{
+    status: "idle" | "running" | "done";
+    message: string | null;
+  }>({ status: "idle", message: null });
+  const [teamStatus, setTeamStatus] = useState<TeamPromptsStatus | null>(null);
   const [scaleDraft, setScaleDraft] = useState(
     `${Math.round(clampUiScale(appSettings.uiScale) * 100)}%`,
   );
@@ -93,6 +149,13 @@ export function SettingsView({
     setClaudeCodePathDraft(appSettings.claudeCodeBin ?? "");
   }, [appSettings.claudeCodeBin]);
 
+  useEffect(() => {
+    setTeamRepoDraft(appSettings.teamPromptsRepo ?? "");
+    getTeamPromptsStatus()
+      .then(setTeamStatus)
+      .catch(() => setTeamStatus(null));
+  }, [appSettings.teamPromptsRepo]);
+
   useEffect(() => {
     setScaleDraft(`${Math.round(clampUiScale(appSettings.uiScale) * 100)}%`);
   }, [appSettings.uiScale]);
@@ -160,6 +223,19 @@ export function SettingsView({
     });
   };
 
+  const appearance: AppearanceSettings = appSettings.appearance ?? {
+    theme: "system",
+    accentColor: null,
+    fontSize: 14,
+  };
+
+  const handleUpdateAppearance = async (next: Partial<AppearanceSettings>) => {
+    await onUpdateAppSettings({
+      ...appSettings,
+      appearance: { ...appearance, ...next },
+    });
+  };
+
   const handleBrowseCodex = async () => {
     const selection = await open({ multiple: false, directory: false });
     if (!selection || Array.isArray(selection)) {
@@ -180,6 +256,257 @@ export function SettingsView({
     }
   };
 
+  const [digestMessage, setDigestMessage] = useState<string | null>(null);
+
+  const doNotDisturb = appSettings.doNotDisturb ?? { enabled: false };
+  const updateQuietHours = (schedule: QuietHours | undefined) => {
+    void onUpdateAppSettings({
+      ...appSettings,
+      doNotDisturb: { ...doNotDisturb, schedule },
+    });
+  };
+
+  const powerSaving: PowerSavingSettings = appSettings.powerSaving ?? {
+    enabled: true,
+    maxSessions: 2,
+    pauseIndexing: true,
+  };
+  const updatePowerSaving = (changes: Partial<PowerSavingSettings>) =>
+    onUpdateAppSettings({
+      ...appSettings,
+      powerSaving: { ...powerSaving, ...changes },
+    });
+
+  const handleToggleDigest = () => {
+    const digest = appSettings.digest ?? { enabled: false, hour: 18, delivery: "folder", to: [] };
+    void onUpdateAppSettings({
+      ...appSettings,
+      digest: { ...digest, enabled: !digest.enabled },
+    });
+  };
+
+  const handleSendDigest = async () => {
+    setDigestMessage("Sending...");
+    try {
+      const destination = await sendDigest();
+      setDigestMessage(`Digest sent to ${destination}.`);
+    } catch (error) {
+      setDigestMessage(error instanceof Error ? error.message : String(error));
+    }
+  };
+
+  const lfs = appSettings.lfs ?? { largeFileMb: 50, blockLargeCommits: false, gitHooks: false };
+  const telemetry = appSettings.telemetry ?? { enabled: false, serviceName: "claude-monitor" };
+  const telemetryEndpointDirty =
+    (telemetryEndpointDraft.trim() || null) !== (telemetry.endpoint ?? null);
+
+  const handleToggleTelemetry = () => {
+    void onUpdateAppSettings({
+      ...appSettings,
+      telemetry: { ...telemetry, enabled: !telemetry.enabled },
+    });
+  };
+
+  const crashReports = appSettings.crashReports ?? { enabled: false };
+  const bridgeLaunch = appSettings.bridgeLaunch ?? { runtime: "tsx" };
+  const [bridgeCommandDraft, setBridgeCommandDraft] = useState(bridgeLaunch.command ?? "");
+
+  useEffect(() => {
+    setBridgeCommandDraft(appSettings.bridgeLaunch?.command ?? "");
+  }, [appSettings.bridgeLaunch?.command]);
+
+  const handleChangeBridgeRuntime = (runtime: BridgeRuntime) => {
+    void onUpdateAppSettings({
+      ...appSettings,
+      bridgeLaunch: { ...bridgeLaunch, runtime },
+    });
+  };
+
+  const handleSaveBridgeCommand = () => {
+    const command = bridgeCommandDraft.trim() || null;
+    if (command === (bridgeLaunch.command ?? null)) {
+      return;
+    }
+    void onUpdateAppSettings({
+      ...appSettings,
+      bridgeLaunch: { ...bridgeLaunch, command },
+    });
+  };
+
+  const handleToggleCrashReports = () => {
+    void onUpdateAppSettings({
+      ...appSettings,
+      crashReports: { ...crashReports, enabled: !crashReports.enabled },
+    });
+  };
+
+  const proxy = appSettings.proxy ?? {};
+  const [proxyUrlDraft, setProxyUrlDraft] = useState(proxy.url ?? "");
+  const [noProxyDraft, setNoProxyDraft] = useState(proxy.noProxy ?? "");
+  const [proxyTestMessage, setProxyTestMessage] = useState<string | null>(null);
+  const proxyDirty =
+    (proxyUrlDraft.trim() || null) !== (proxy.url ?? null) ||
+    (noProxyDraft.trim() || null) !== (proxy.noProxy ?? null);
+
+  useEffect(() => {
+    setProxyUrlDraft(appSettings.proxy?.url ?? "");
+    setNoProxyDraft(appSettings.proxy?.noProxy ?? "");
+  }, [appSettings.proxy?.url, appSettings.proxy?.noProxy]);
+
+  const handleSaveProxy = async () => {
+    setIsSavingSettings(true);
+    try {
+      await onUpdateAppSettings({
+        ...appSettings,
+        proxy: {
+          url: proxyUrlDraft.trim() || null,
+          noProxy: noProxyDraft.trim() || null,
+        },
+      });
+    } finally {
+      setIsSavingSettings(false);
+    }
+  };
+
+  const [caBundleDraft, setCaBundleDraft] = useState(appSettings.caBundle ?? "");
+  const [caBundleMessage, setCaBundleMessage] = useState<string | null>(null);
+  const caBundleDirty = (caBundleDraft.trim() || null) !== (appSettings.caBundle ?? null);
+
+  useEffect(() => {
+    setCaBundleDraft(appSettings.caBundle ?? "");
+  }, [appSettings.caBundle]);
+
+  const handleBrowseCaBundle = async () => {
+    const selection = await open({ multiple: false, directory: false });
+    if (!selection || Array.isArray(selection)) {
+      return;
+    }
+    setCaBundleDraft(selection);
+  };
+
+  const handleSaveCaBundle = async () => {
+    const path = caBundleDraft.trim() || null;
+    setCaBundleMessage(null);
+    setIsSavingSettings(true);
+    try {
+      if (path) {
+        const info = await checkCaBundle(path);
+        setCaBundleMessage(`${info.certificates} certificate(s) loaded.`);
+      }
+      await onUpdateAppSettings({ ...appSettings, caBundle: path });
+    } catch (error) {
+      setCaBundleMessage(error instanceof Error ? error.message : String(error));
+    } finally {
+      setIsSavingSettings(false);
+    }
+  };
+
+  const [localeDraft, setLocaleDraft] = useState(appSettings.locale ?? "");
+  const [timeZoneDraft, setTimeZoneDraft] = useState(appSettings.timeZone ?? "");
+  const [localeError, setLocaleError] = useState<string | null>(null);
+  const localeDirty =
+    (localeDraft.trim() || null) !== (appSettings.locale ?? null) ||
+    (timeZoneDraft.trim() || null) !== (appSettings.timeZone ?? null);
+
+  useEffect(() => {
+    setLocaleDraft(appSettings.locale ?? "");
+    setTimeZoneDraft(appSettings.timeZone ?? "");
+  }, [appSettings.locale, appSettings.timeZone]);
+
+  const handleSaveLocale = async () => {
+    setLocaleError(null);
+    setIsSavingSettings(true);
+    try {
+      await onUpdateAppSettings({
+        ...appSettings,
+        locale: localeDraft.trim() || null,
+        timeZone: timeZoneDraft.trim() || null,
+      });
+    } catch (error) {
+      setLocaleError(error instanceof Error ? error.message : String(error));
+    } finally {
+      setIsSavingSettings(false);
+    }
+  };
+
+  const handleTestProxy = async () => {
+    setProxyTestMessage("Testing...");
+    try {
+      const result = await testProxy(proxyUrlDraft.trim() || null);
+      const via = result.proxy ? `via ${result.proxy}` : "without a proxy";
+      setProxyTestMessage(
+        result.ok
+          ? `Reached ${result.url} ${via} (HTTP ${result.status}, ${result.latencyMs ?? "?"} ms).`
+          : `Could not reach ${result.url} ${via}: ${result.details ?? "unknown error"}`,
+      );
+    } catch (error) {
+      setProxyTestMessage(error instanceof Error ? error.message : String(error));
+    }
+  };
+
+  const handleSaveTelemetryEndpoint = async () => {
+    setIsSavingSettings(true);
+    try {
+      await onUpdateAppSettings({
+        ...appSettings,
+        telemetry: {
+          ...telemetry,
+          endpoint: telemetryEndpointDraft.trim() ? telemetryEndpointDraft.trim() : null,
+        },
+      });
+    } finally {
+      setIsSavingSettings(false);
+    }
+  };
+
+  const teamRepoDirty =
+    (teamRepoDraft.trim() || null) !== (appSettings.teamPromptsRepo ?? null);
+
+  const handleSaveTeamRepo = async () => {
+    setIsSavingSettings(true);
+    try {
+      await onUpdateAppSettings({
+        ...appSettings,
+        teamPromptsRepo: teamRepoDraft.trim() ? teamRepoDraft.trim() : null,
+      });
+    } finally {
+      setIsSavingSettings(false);
+    }
+  };
+
+  const handleSyncTeamPrompts = async () => {
+    setTeamSyncState({ status: "running", message: null });
+    try {
+      const status = await syncTeamPrompts();
+      setTeamStatus(status);
+      setTeamSyncState({
+        status: "done",
+        message: `Synced ${status.commit}: ${status.prompts} prompts, ${
+          status.permissions.allow.length + status.permissions.deny.length
+        } permission rules.`,
+      });
+    } catch (error) {
+      setTeamSyncState({
+        status: "done",
+        message: error instanceof Error ? error.message : String(error),
+      });
+    }
+  };
+
+  const handleAcceptTeamPermissions = async (commit: string) => {
+    try {
+      const status = await acceptTeamPermissions(commit);
+      if (status) {
+        setTeamStatus(status);
+      }
+    } catch (error) {
+      setTeamSyncState({
+        status: "done",
+        message: error instanceof Error ? error.message : String(error),
+      });
+    }
+  };
+
   const handleBrowseClaudeCode = async () => {
     const selection = await open({ multiple: false, directory: false });
     if (!selection || Array.isArray(selection)) {
@@ -289,6 +616,30 @@ export function SettingsView({
               <Zap aria-hidden />
               MCP Servers
             </button>
+            <button
+              type="button"
+              className={`settings-nav ${activeSection === "snippets" ? "active" : ""}`}
+              onClick={() => setActiveSection("snippets")}
+            >
+              <FileText aria-hidden />
+              Snippets
+            </button>
+            <button
+              type="button"
+              className={`settings-nav ${activeSection === "glossary" ? "active" : ""}`}
+              onClick={() => setActiveSection("glossary")}
+            >
+              <BookOpen aria-hidden />
+              Glossary
+            </button>
+            <button
+              type="button"
+              className={`settings-nav ${activeSection === "worktrees" ? "active" : ""}`}
+              onClick={() => setActiveSection("worktrees")}
+            >
+              <GitBranch aria-hidden />
+              Worktrees
+            </button>
             <button
               type="button"
               className={`settings-nav ${activeSection === "codex" ? "active" : ""}`}
@@ -410,6 +761,71 @@ export function SettingsView({
                     </button>
                   </div>
                 </div>
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="theme">
+                    Theme
+                  </label>
+                  <select
+                    id="theme"
+                    className="settings-select"
+                    value={appearance.theme}
+                    onChange={(event) =>
+                      void handleUpdateAppearance({
+                        theme: event.target.value as AppearanceSettings["theme"],
+                      })
+                    }
+                  >
+                    <option value="system">System</option>
+                    <option value="light">Light</option>
+                    <option value="dark">Dark</option>
+                  </select>
+                </div>
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="accent-color">
+                    Accent color
+                  </label>
+                  <div className="settings-field-row">
+                    <input
+                      id="accent-color"
+                      type="color"
+                      value={appearance.accentColor ?? "#4d99ff"}
+                      onChange={(event) =>
+                        void handleUpdateAppearance({
+                          accentColor: event.target.value,
+                        })
+                      }
+                    />
+                    <button
+                      type="button"
+                      className="ghost"
+                      disabled={!appearance.accentColor}
+                      onClick={() => void handleUpdateAppearance({ accentColor: null })}
+                    >
+                      Reset
+                    </button>
+                  </div>
+                </div>
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="font-size">
+                    Message font size
+                  </label>
+                  <select
+                    id="font-size"
+                    className="settings-select"
+                    value={appearance.fontSize}
+                    onChange={(event) =>
+                      void handleUpdateAppearance({
+                        fontSize: Number(event.target.value),
+                      })
+                    }
+                  >
+                    {[12, 13, 14, 15, 16, 18, 20].map((size) => (
+                      <option key={size} value={size}>
+                        {size}px
+                      </option>
+                    ))}
+                  </select>
+                </div>
               </section>
             )}
             {activeSection === "codex" && (
@@ -657,9 +1073,18 @@ export function SettingsView({
                             ? `ok (${claudeDoctorState.result.nodeVersion ?? "unknown"})`
                             : "missing"}
                         </div>
+                        <div>
+                          Bridge:{" "}
+                          {claudeDoctorState.result.bridgeOk
+                            ? `ok (${claudeDoctorState.result.bridgeCommand ?? "unknown"})`
+                            : "failed"}
+                        </div>
                         {claudeDoctorState.result.claudeDetails && (
                           <div>{claudeDoctorState.result.claudeDetails}</div>
                         )}
+                        {claudeDoctorState.result.bridgeDetails && (
+                          <div>{claudeDoctorState.result.bridgeDetails}</div>
+                        )}
                         {claudeDoctorState.result.nodeDetails && (
                           <div>{claudeDoctorState.result.nodeDetails}</div>
                         )}
@@ -673,6 +1098,41 @@ export function SettingsView({
                   )}
                 </div>
 
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="bridge-runtime">
+                    Bridge runtime
+                  </label>
+                  <div className="settings-field-row">
+                    <select
+                      id="bridge-runtime"
+                      className="settings-select"
+                      value={bridgeLaunch.runtime}
+                      onChange={(event) =>
+                        handleChangeBridgeRuntime(event.target.value as BridgeRuntime)
+                      }
+                    >
+                      <option value="tsx">npx tsx</option>
+                      <option value="bun">bun</option>
+                      <option value="node">node (dist/index.js)</option>
+                      <option value="custom">Custom command</option>
+                    </select>
+                    {bridgeLaunch.runtime === "custom" && (
+                      <input
+                        className="settings-input"
+                        value={bridgeCommandDraft}
+                        placeholder="deno"
+                        aria-label="Bridge command"
+                        onChange={(event) => setBridgeCommandDraft(event.target.value)}
+                        onBlur={handleSaveBridgeCommand}
+                      />
+                    )}
+                  </div>
+                  <div className="settings-help">
+                    Applies the next time the bridge starts. Extra args and env can be set
+                    under bridgeLaunch in the settings file.
+                  </div>
+                </div>
+
                 <div className="settings-field">
                   <label className="settings-field-label" htmlFor="default-permission-mode">
                     Default permission mode
@@ -697,44 +1157,757 @@ export function SettingsView({
                     Controls how Claude Code handles tool permission requests.
                   </div>
                 </div>
-              </section>
-            )}
-            {activeSection === "mcp-servers" && (
-              <section className="settings-section">
-                <div className="settings-section-title">MCP Servers</div>
-                <div className="settings-section-subtitle">
-                  Configure Model Context Protocol servers for extended capabilities.
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="default-editor">
+                    Editor
+                  </label>
+                  <select
+                    id="default-editor"
+                    className="settings-select"
+                    value={appSettings.editor ?? ""}
+                    onChange={(event) =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        editor: (event.target.value || null) as AppSettings["editor"],
+                      })
+                    }
+                  >
+                    <option value="">Auto-detect</option>
+                    <option value="vscode">VS Code</option>
+                    <option value="cursor">Cursor</option>
+                    <option value="zed">Zed</option>
+                    <option value="jetbrains">JetBrains IDE</option>
+                    <option value="system">System default app</option>
+                  </select>
+                  <div className="settings-help">
+                    Where file names in diffs and tool output open when clicked.
+                  </div>
                 </div>
-                {(appSettings.mcpServers ?? []).length > 0 ? (
-                  <div className="settings-mcp-list">
-                    {(appSettings.mcpServers ?? []).map((server) => (
-                      <div key={server.id} className="settings-toggle-row">
-                        <div>
-                          <div className="settings-toggle-title">{server.name}</div>
-                          <div className="settings-toggle-subtitle">
-                            {server.command ?? "No command configured"}
-                            {server.args && server.args.length > 0
-                              ? ` ${server.args.join(" ")}`
-                              : ""}
-                          </div>
-                        </div>
-                        <button
-                          type="button"
-                          className={`settings-toggle ${server.enabled ? "on" : ""}`}
-                          onClick={() => {
-                            const nextServers = (appSettings.mcpServers ?? []).map((s) =>
-                              s.id === server.id ? { ...s, enabled: !s.enabled } : s
-                            );
-                            void onUpdateAppSettings({
-                              ...appSettings,
-                              mcpServers: nextServers,
-                            });
-                          }}
-                          aria-pressed={server.enabled}
-                        >
-                          <span className="settings-toggle-knob" />
-                        </button>
-                      </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="event-scope">
+                    Live updates
+                  </label>
+                  <select
+                    id="event-scope"
+                    className="settings-select"
+                    value={appSettings.eventScope ?? "all"}
+                    onChange={(event) =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        eventScope: event.target.value as AppSettings["eventScope"],
+                      })
+                    }
+                  >
+                    <option value="all">All workspaces</option>
+                    <option value="workspace">Active workspace only</option>
+                  </select>
+                  <div className="settings-help">
+                    Active workspace only skips live output from other workspaces, which
+                    cuts load when many sessions run at once.
+                  </div>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="large-file-limit">
+                    Large file limit
+                  </label>
+                  <select
+                    id="large-file-limit"
+                    className="settings-select"
+                    value={lfs.largeFileMb}
+                    onChange={(event) =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        lfs: { ...lfs, largeFileMb: Number(event.target.value) },
+                      })
+                    }
+                  >
+                    <option value={10}>10 MB</option>
+                    <option value={25}>25 MB</option>
+                    <option value={50}>50 MB</option>
+                    <option value={100}>100 MB</option>
+                  </select>
+                  <div className="settings-help">
+                    Changed files over this size that Git LFS does not track are flagged
+                    in the diff, as are edited LFS pointer files.
+                  </div>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Block large commits</div>
+                    <div className="settings-toggle-subtitle">
+                      Refuse commits that stage files over the large file limit.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${lfs.blockLargeCommits ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        lfs: { ...lfs, blockLargeCommits: !lfs.blockLargeCommits },
+                      })
+                    }
+                    aria-pressed={lfs.blockLargeCommits}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Check commits with git hooks</div>
+                    <div className="settings-toggle-subtitle">
+                      Add pre-commit and pre-push hooks to every workspace, so commits the
+                      agent or a terminal makes are checked too. Existing hooks are kept.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${lfs.gitHooks ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        lfs: { ...lfs, gitHooks: !lfs.gitHooks },
+                      })
+                    }
+                    aria-pressed={lfs.gitHooks}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Block commits that add secrets</div>
+                    <div className="settings-toggle-subtitle">
+                      The pre-commit hook refuses staged changes that add API keys, tokens or
+                      private keys.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.blockSecretCommits ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        blockSecretCommits: !appSettings.blockSecretCommits,
+                      })
+                    }
+                    aria-pressed={appSettings.blockSecretCommits ?? false}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="max-autonomous-turns">
+                    Autonomous turn limit
+                  </label>
+                  <select
+                    id="max-autonomous-turns"
+                    className="settings-select"
+                    value={appSettings.maxAutonomousTurns ?? 10}
+                    onChange={(event) =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        maxAutonomousTurns: Number(event.target.value),
+                      })
+                    }
+                  >
+                    <option value={3}>3 turns</option>
+                    <option value={10}>10 turns</option>
+                    <option value={25}>25 turns</option>
+                    <option value={0}>No limit</option>
+                  </select>
+                  <div className="settings-help">
+                    Workflows stop after this many turns in a row without a message from
+                    you. Sending a message resets the count.
+                  </div>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="max-concurrent-turns">
+                    Concurrent turns
+                  </label>
+                  <select
+                    id="max-concurrent-turns"
+                    className="settings-select"
+                    value={appSettings.maxConcurrentTurns ?? 0}
+                    onChange={(event) =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        maxConcurrentTurns: Number(event.target.value),
+                      })
+                    }
+                  >
+                    <option value={0}>No limit</option>
+                    <option value={2}>2 turns</option>
+                    <option value={4}>4 turns</option>
+                    <option value={8}>8 turns</option>
+                  </select>
+                  <div className="settings-help">
+                    Turns over the limit wait for a free slot. Your messages go ahead of
+                    workflows, which always leave one slot free for you.
+                  </div>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Observer mode</div>
+                    <div className="settings-toggle-subtitle">
+                      Read-only: block sending messages, approving tools, git changes, and
+                      file writes. Useful for screensharing and demos.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.observerMode ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        observerMode: !appSettings.observerMode,
+                      })
+                    }
+                    aria-pressed={Boolean(appSettings.observerMode)}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Record sessions for replay</div>
+                    <div className="settings-toggle-subtitle">
+                      Keep the events of the last 20 sessions in memory so they can be
+                      exported and replayed. Turning this off discards them.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.recordReplays ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        recordReplays: !appSettings.recordReplays,
+                      })
+                    }
+                    aria-pressed={Boolean(appSettings.recordReplays)}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Pause schedulers after sleep</div>
+                    <div className="settings-toggle-subtitle">
+                      Hold the daily digest, team prompt sync and telemetry export for two
+                      minutes after the computer wakes, while the network reconnects.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.pauseSchedulersOnSleep ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        pauseSchedulersOnSleep: !appSettings.pauseSchedulersOnSleep,
+                      })
+                    }
+                    aria-pressed={Boolean(appSettings.pauseSchedulersOnSleep)}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Power saving</div>
+                    <div className="settings-toggle-subtitle">
+                      On battery or when the system is thermally throttled, limit open
+                      sessions and pause background indexing.
+                      {powerStatus.throttled
+                        ? ` Active now (${
+                            powerStatus.onBattery ? "on battery" : "thermal throttling"
+                          }).`
+                        : ""}
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${powerSaving.enabled ? "on" : ""}`}
+                    onClick={() => void updatePowerSaving({ enabled: !powerSaving.enabled })}
+                    aria-pressed={powerSaving.enabled}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+                {powerSaving.enabled && (
+                  <>
+                    <div className="settings-field">
+                      <label className="settings-field-label" htmlFor="power-max-sessions">
+                        Open sessions while saving power
+                      </label>
+                      <select
+                        id="power-max-sessions"
+                        className="settings-select"
+                        value={powerSaving.maxSessions}
+                        onChange={(event) =>
+                          void updatePowerSaving({ maxSessions: Number(event.target.value) })
+                        }
+                      >
+                        <option value={1}>1 session</option>
+                        <option value={2}>2 sessions</option>
+                        <option value={4}>4 sessions</option>
+                        <option value={0}>No limit</option>
+                      </select>
+                    </div>
+                    <div className="settings-toggle-row">
+                      <div>
+                        <div className="settings-toggle-title">Pause indexing</div>
+                        <div className="settings-toggle-subtitle">
+                          Stop building search and usage indexes until power saving ends.
+                        </div>
+                      </div>
+                      <button
+                        type="button"
+                        className={`settings-toggle ${powerSaving.pauseIndexing ? "on" : ""}`}
+                        onClick={() =>
+                          void updatePowerSaving({ pauseIndexing: !powerSaving.pauseIndexing })
+                        }
+                        aria-pressed={powerSaving.pauseIndexing}
+                      >
+                        <span className="settings-toggle-knob" />
+                      </button>
+                    </div>
+                  </>
+                )}
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Quiet hours</div>
+                    <div className="settings-toggle-subtitle">
+                      Do not disturb every day: permission requests wait behind a badge
+                      instead of prompting. The moon button turns it on by hand.
+                    </div>
+                    {doNotDisturb.schedule && (
+                      <div className="settings-field-row">
+                        <input
+                          type="time"
+                          className="settings-input settings-input--compact"
+                          value={minutesToTime(doNotDisturb.schedule.start)}
+                          aria-label="Quiet hours start"
+                          onChange={(event) =>
+                            updateQuietHours({
+                              ...DEFAULT_QUIET_HOURS,
+                              ...doNotDisturb.schedule,
+                              start: timeToMinutes(event.target.value),
+                            })
+                          }
+                        />
+                        <input
+                          type="time"
+                          className="settings-input settings-input--compact"
+                          value={minutesToTime(doNotDisturb.schedule.end)}
+                          aria-label="Quiet hours end"
+                          onChange={(event) =>
+                            updateQuietHours({
+                              ...DEFAULT_QUIET_HOURS,
+                              ...doNotDisturb.schedule,
+                              end: timeToMinutes(event.target.value),
+                            })
+                          }
+                        />
+                      </div>
+                    )}
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${doNotDisturb.schedule ? "on" : ""}`}
+                    onClick={() =>
+                      updateQuietHours(doNotDisturb.schedule ? undefined : DEFAULT_QUIET_HOURS)
+                    }
+                    aria-pressed={Boolean(doNotDisturb.schedule)}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Start a session on folder drop</div>
+                    <div className="settings-toggle-subtitle">
+                      Folders dropped on the window are added as workspaces; also open a new
+                      agent in them right away.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.startSessionOnDrop ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        startSessionOnDrop: !appSettings.startSessionOnDrop,
+                      })
+                    }
+                    aria-pressed={Boolean(appSettings.startSessionOnDrop)}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">LAN session sharing</div>
+                    <div className="settings-toggle-subtitle">
+                      Let a teammate on the same network follow a session live, read-only,
+                      with the access token you give them.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.lanSharing ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        lanSharing: !appSettings.lanSharing,
+                      })
+                    }
+                    aria-pressed={Boolean(appSettings.lanSharing)}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="team-prompts-repo">
+                    Team prompts repository
+                  </label>
+                  <div className="settings-field-row">
+                    <input
+                      id="team-prompts-repo"
+                      className="settings-input"
+                      value={teamRepoDraft}
+                      placeholder="git@github.com:org/agent-playbooks.git"
+                      onChange={(event) => setTeamRepoDraft(event.target.value)}
+                    />
+                  </div>
+                  <div className="settings-help">
+                    Prompts, templates and permission rules pulled every{" "}
+                    {appSettings.teamPromptsSyncMinutes ?? 30} minutes. Team prompts are
+                    read-only; a local prompt with the same name takes precedence.
+                  </div>
+                  <div className="settings-field-actions">
+                    {teamRepoDirty && (
+                      <button
+                        type="button"
+                        className="primary"
+                        onClick={handleSaveTeamRepo}
+                        disabled={isSavingSettings}
+                      >
+                        {isSavingSettings ? "Saving..." : "Save"}
+                      </button>
+                    )}
+                    {appSettings.teamPromptsRepo && !teamRepoDirty && (
+                      <button
+                        type="button"
+                        className="ghost settings-button-compact"
+                        onClick={handleSyncTeamPrompts}
+                        disabled={teamSyncState.status === "running"}
+                      >
+                        {teamSyncState.status === "running" ? "Syncing..." : "Sync now"}
+                      </button>
+                    )}
+                  </div>
+                  {teamSyncState.message && (
+                    <div className="settings-help">{teamSyncState.message}</div>
+                  )}
+                  {teamStatus?.pendingPermissions && (
+                    <div className="settings-team-permissions">
+                      <div className="settings-help">
+                        The team repository changed its allow rules. They take effect once
+                        you accept them.
+                      </div>
+                      {teamStatus.pendingPermissions.added.map((rule) => (
+                        <div key={`+${rule}`} className="settings-team-rule is-added">
+                          + {rule}
+                        </div>
+                      ))}
+                      {teamStatus.pendingPermissions.removed.map((rule) => (
+                        <div key={`-${rule}`} className="settings-team-rule is-removed">
+                          − {rule}
+                        </div>
+                      ))}
+                      <div className="settings-field-actions">
+                        <button
+                          type="button"
+                          className="ghost settings-button-compact"
+                          onClick={() =>
+                            void handleAcceptTeamPermissions(
+                              teamStatus.pendingPermissions?.commit ?? "",
+                            )
+                          }
+                        >
+                          Review and accept
+                        </button>
+                      </div>
+                    </div>
+                  )}
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Daily digest</div>
+                    <div className="settings-toggle-subtitle">
+                      Summarize each day's sessions, cost and changes after{" "}
+                      {appSettings.digest?.hour ?? 18}:00 and deliver them by{" "}
+                      {appSettings.digest?.delivery ?? "folder"}.{" "}
+                      <button
+                        type="button"
+                        className="ghost settings-button-compact"
+                        onClick={handleSendDigest}
+                      >
+                        Send now
+                      </button>
+                    </div>
+                    {digestMessage && <div className="settings-help">{digestMessage}</div>}
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.digest?.enabled ? "on" : ""}`}
+                    onClick={handleToggleDigest}
+                    aria-pressed={Boolean(appSettings.digest?.enabled)}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">OpenTelemetry export</div>
+                    <div className="settings-toggle-subtitle">
+                      Send turn durations, token usage, error counts and per-turn and
+                      tool-call spans to an OTLP/HTTP collector.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${telemetry.enabled ? "on" : ""}`}
+                    onClick={handleToggleTelemetry}
+                    aria-pressed={telemetry.enabled}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+                {telemetry.enabled && (
+                  <div className="settings-field">
+                    <label className="settings-field-label" htmlFor="telemetry-endpoint">
+                      OTLP endpoint
+                    </label>
+                    <div className="settings-field-row">
+                      <input
+                        id="telemetry-endpoint"
+                        className="settings-input"
+                        value={telemetryEndpointDraft}
+                        placeholder="http://localhost:4318"
+                        onChange={(event) => setTelemetryEndpointDraft(event.target.value)}
+                      />
+                    </div>
+                    {telemetryEndpointDirty && (
+                      <div className="settings-field-actions">
+                        <button
+                          type="button"
+                          className="primary"
+                          onClick={handleSaveTelemetryEndpoint}
+                          disabled={isSavingSettings}
+                        >
+                          {isSavingSettings ? "Saving..." : "Save"}
+                        </button>
+                      </div>
+                    )}
+                  </div>
+                )}
+
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Crash reports</div>
+                    <div className="settings-toggle-subtitle">
+                      Save a report with the backtrace and recent log lines when the
+                      backend crashes. Reports stay on this machine unless you submit
+                      them.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${crashReports.enabled ? "on" : ""}`}
+                    onClick={handleToggleCrashReports}
+                    aria-pressed={crashReports.enabled}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="proxy-url">
+                    Proxy
+                  </label>
+                  <div className="settings-field-row">
+                    <input
+                      id="proxy-url"
+                      className="settings-input"
+                      value={proxyUrlDraft}
+                      placeholder="http://proxy.example.com:3128"
+                      onChange={(event) => setProxyUrlDraft(event.target.value)}
+                    />
+                    <input
+                      className="settings-input"
+                      value={noProxyDraft}
+                      placeholder="localhost,.internal"
+                      aria-label="Hosts that bypass the proxy"
+                      onChange={(event) => setNoProxyDraft(event.target.value)}
+                    />
+                  </div>
+                  <div className="settings-help">
+                    Used by the bridge, GitHub CLI calls, webhooks and the updater. Leave
+                    empty to use HTTPS_PROXY and NO_PROXY from the environment.
+                  </div>
+                  <div className="settings-field-actions">
+                    {proxyDirty && (
+                      <button
+                        type="button"
+                        className="primary"
+                        onClick={handleSaveProxy}
+                        disabled={isSavingSettings}
+                      >
+                        {isSavingSettings ? "Saving..." : "Save"}
+                      </button>
+                    )}
+                    <button
+                      type="button"
+                      className="ghost settings-button-compact"
+                      onClick={handleTestProxy}
+                    >
+                      Test connection
+                    </button>
+                  </div>
+                  {proxyTestMessage && <div className="settings-help">{proxyTestMessage}</div>}
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="ca-bundle">
+                    Custom CA bundle
+                  </label>
+                  <div className="settings-field-row">
+                    <input
+                      id="ca-bundle"
+                      className="settings-input"
+                      value={caBundleDraft}
+                      placeholder="/etc/ssl/certs/corp-ca.pem"
+                      onChange={(event) => setCaBundleDraft(event.target.value)}
+                    />
+                    <button type="button" className="ghost" onClick={handleBrowseCaBundle}>
+                      Browse
+                    </button>
+                  </div>
+                  <div className="settings-help">
+                    PEM certificates to trust when your network intercepts TLS. Applies to
+                    the bridge after it restarts.
+                  </div>
+                  {caBundleDirty && (
+                    <div className="settings-field-actions">
+                      <button
+                        type="button"
+                        className="primary"
+                        onClick={handleSaveCaBundle}
+                        disabled={isSavingSettings}
+                      >
+                        {isSavingSettings ? "Saving..." : "Save"}
+                      </button>
+                    </div>
+                  )}
+                  {caBundleMessage && <div className="settings-help">{caBundleMessage}</div>}
+                </div>
+
+                <div className="settings-field">
+                  <label className="settings-field-label" htmlFor="backend-locale">
+                    Locale and time zone
+                  </label>
+                  <div className="settings-field-row">
+                    <input
+                      id="backend-locale"
+                      className="settings-input"
+                      value={localeDraft}
+                      placeholder={navigator.language}
+                      onChange={(event) => setLocaleDraft(event.target.value)}
+                    />
+                    <input
+                      className="settings-input"
+                      value={timeZoneDraft}
+                      placeholder="local"
+                      aria-label="Time zone"
+                      onChange={(event) => setTimeZoneDraft(event.target.value)}
+                    />
+                  </div>
+                  <div className="settings-help">
+                    Used for dates in digests and exports, and for backend messages when a
+                    translation is installed. Time zone is local, UTC or an offset like +02:00.
+                  </div>
+                  {localeDirty && (
+                    <div className="settings-field-actions">
+                      <button
+                        type="button"
+                        className="primary"
+                        onClick={handleSaveLocale}
+                        disabled={isSavingSettings}
+                      >
+                        {isSavingSettings ? "Saving..." : "Save"}
+                      </button>
+                    </div>
+                  )}
+                  {localeError && <div className="settings-help">{localeError}</div>}
+                </div>
+              </section>
+            )}
+            {activeSection === "mcp-servers" && (
+              <section className="settings-section">
+                <div className="settings-section-title">MCP Servers</div>
+                <div className="settings-section-subtitle">
+                  Configure Model Context Protocol servers for extended capabilities.
+                </div>
+                {(appSettings.mcpServers ?? []).length > 0 ? (
+                  <div className="settings-mcp-list">
+                    {(appSettings.mcpServers ?? []).map((server) => (
+                      <div key={server.id} className="settings-toggle-row">
+                        <div>
+                          <div className="settings-toggle-title">{server.name}</div>
+                          <div className="settings-toggle-subtitle">
+                            {server.command ?? "No command configured"}
+                            {server.args && server.args.length > 0
+                              ? ` ${server.args.join(" ")}`
+                              : ""}
+                          </div>
+                        </div>
+                        <button
+                          type="button"
+                          className={`settings-toggle ${server.enabled ? "on" : ""}`}
+                          onClick={() => {
+                            const nextServers = (appSettings.mcpServers ?? []).map((s) =>
+                              s.id === server.id ? { ...s, enabled: !s.enabled } : s
+                            );
+                            void onUpdateAppSettings({
+                              ...appSettings,
+                              mcpServers: nextServers,
+                            });
+                          }}
+                          aria-pressed={server.enabled}
+                        >
+                          <span className="settings-toggle-knob" />
+                        </button>
+                      </div>
                     ))}
                   </div>
                 ) : (
@@ -749,6 +1922,11 @@ export function SettingsView({
                 </div>
               </section>
             )}
+            {activeSection === "snippets" && <SnippetsSettings />}
+            {activeSection === "glossary" && <GlossarySettings projects={projects} />}
+            {activeSection === "worktrees" && (
+              <WorktreeCleanupSettings onWorktreesRemoved={onWorktreesRemoved} />
+            )}
           </div>
         </div>
       </div>
@@ -1,5 +1,5 @@
-import type { RateLimitSnapshot, ThreadSummary, WorkspaceInfo } from "../types";
-import { FolderKanban, Layers, Settings, TerminalSquare } from "lucide-react";
+import type { FocusStatus, RateLimitSnapshot, ThreadSummary, WorkspaceInfo } from "../types";
+import { FolderKanban, Layers, Moon, Settings, TerminalSquare } from "lucide-react";
 import { createPortal } from "react-dom";
 import { useCallback, useEffect, useRef, useState } from "react";
 import { Menu, MenuItem } from "@tauri-apps/api/menu";
@@ -24,6 +24,8 @@ type SidebarProps = {
   onOpenSettings: () => void;
   onOpenDebug: () => void;
   hasDebugAlerts: boolean;
+  focusStatus: FocusStatus;
+  onToggleFocus: () => void;
   onAddWorkspace: () => void;
   onSelectHome: () => void;
   onSelectWorkspace: (id: string) => void;
@@ -37,6 +39,7 @@ type SidebarProps = {
   onUnarchiveThread: (workspaceId: string, threadId: string) => void;
   onDeleteWorkspace: (workspaceId: string) => void;
   onDeleteWorktree: (workspaceId: string) => void;
+  onToggleWorkspaceAutomation: (workspaceId: string, paused: boolean) => void;
 };
 
 export function Sidebar({
@@ -53,6 +56,8 @@ export function Sidebar({
   onOpenSettings,
   onOpenDebug,
   hasDebugAlerts,
+  focusStatus,
+  onToggleFocus,
   onAddWorkspace,
   onSelectHome,
   onSelectWorkspace,
@@ -66,6 +71,7 @@ export function Sidebar({
   onUnarchiveThread,
   onDeleteWorkspace,
   onDeleteWorktree,
+  onToggleWorkspaceAutomation,
 }: SidebarProps) {
   const [expandedWorkspaces, setExpandedWorkspaces] = useState(
     new Set<string>(),
@@ -145,15 +151,20 @@ export function Sidebar({
 
   async function showWorkspaceMenu(
     event: React.MouseEvent,
-    workspaceId: string,
+    workspace: WorkspaceInfo,
   ) {
     event.preventDefault();
     event.stopPropagation();
+    const paused = Boolean(workspace.automationPaused);
+    const automationItem = await MenuItem.new({
+      text: paused ? "Resume automation" : "Pause automation",
+      action: () => onToggleWorkspaceAutomation(workspace.id, !paused),
+    });
     const deleteItem = await MenuItem.new({
       text: "Delete",
-      action: () => onDeleteWorkspace(workspaceId),
+      action: () => onDeleteWorkspace(workspace.id),
     });
-    const menu = await Menu.new({ items: [deleteItem] });
+    const menu = await Menu.new({ items: [automationItem, deleteItem] });
     const window = getCurrentWindow();
     const position = new LogicalPosition(event.clientX, event.clientY);
     await menu.popup(position, window);
@@ -331,7 +342,7 @@ export function Sidebar({
                   role="button"
                   tabIndex={0}
                   onClick={() => onSelectWorkspace(entry.id)}
-                  onContextMenu={(event) => showWorkspaceMenu(event, entry.id)}
+                  onContextMenu={(event) => showWorkspaceMenu(event, entry)}
                   onKeyDown={(event) => {
                     if (event.key === "Enter" || event.key === " ") {
                       event.preventDefault();
@@ -343,6 +354,14 @@ export function Sidebar({
                     <div className="workspace-name-row">
                       <div className="workspace-title">
                         <span className="workspace-name">{entry.name}</span>
+                        {entry.automationPaused && (
+                          <span
+                            className="workspace-paused"
+                            title="Automation paused: workflows, queued messages and auto-approval are on hold"
+                          >
+                            Paused
+                          </span>
+                        )}
                         <button
                           className={`workspace-toggle ${
                             isCollapsed ? "" : "expanded"
@@ -836,6 +855,34 @@ export function Sidebar({
         >
           <Settings size={14} aria-hidden />
         </button>
+        <button
+          className={`ghost sidebar-corner-button sidebar-focus-button${
+            focusStatus.active ? " active" : ""
+          }`}
+          type="button"
+          onClick={onToggleFocus}
+          aria-pressed={focusStatus.active}
+          aria-label={
+            focusStatus.held > 0
+              ? `Do not disturb, ${focusStatus.held} permission requests waiting`
+              : "Do not disturb"
+          }
+          title={
+            focusStatus.active
+              ? focusStatus.until
+                ? `Do not disturb until ${new Date(focusStatus.until).toLocaleTimeString([], {
+                    hour: "numeric",
+                    minute: "2-digit",
+                  })}`
+                : "Do not disturb is on"
+              : "Do not disturb"
+          }
+        >
+          <Moon size={14} aria-hidden />
+          {focusStatus.held > 0 && (
+            <span className="sidebar-focus-badge">{focusStatus.held}</span>
+          )}
+        </button>
         {hasDebugAlerts && (
           <button
             className="ghost sidebar-corner-button"
@@ -0,0 +1,182 @@
+import { useState } from "react";
+import { Pencil, Trash2 } from "lucide-react";
+import type { Snippet } from "../types";
+import { createSnippet, deleteSnippet, updateSnippet } from "../services/tauri";
+import { useSnippets } from "../hooks/useSnippets";
+
+type SnippetDraft = {
+  id: string | null;
+  name: string;
+  description: string;
+  content: string;
+};
+
+const EMPTY_DRAFT: SnippetDraft = { id: null, name: "", description: "", content: "" };
+
+export function SnippetsSettings() {
+  const snippets = useSnippets();
+  const [draft, setDraft] = useState<SnippetDraft | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const editSnippet = (snippet: Snippet) => {
+    setError(null);
+    setDraft({
+      id: snippet.id,
+      name: snippet.name,
+      description: snippet.description ?? "",
+      content: snippet.content,
+    });
+  };
+
+  const handleSave = async () => {
+    if (!draft) {
+      return;
+    }
+    setIsSaving(true);
+    setError(null);
+    try {
+      if (draft.id) {
+        await updateSnippet(draft.id, {
+          name: draft.name,
+          content: draft.content,
+          description: draft.description,
+        });
+      } else {
+        await createSnippet(draft.name, draft.content, draft.description);
+      }
+      setDraft(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleDelete = async (snippet: Snippet) => {
+    setError(null);
+    try {
+      await deleteSnippet(snippet.id);
+      if (draft?.id === snippet.id) {
+        setDraft(null);
+      }
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  };
+
+  return (
+    <section className="settings-section">
+      <div className="settings-section-title">Snippets</div>
+      <div className="settings-section-subtitle">
+        Reusable blocks of context, like architecture notes or style rules, shared by every
+        workspace. Type /snippets: in the composer to insert one.
+      </div>
+      <div className="settings-projects">
+        {snippets.map((snippet) => (
+          <div key={snippet.id} className="settings-project-row">
+            <div className="settings-project-info">
+              <div className="settings-project-name">{snippet.name}</div>
+              <div className="settings-project-path">
+                {snippet.description ?? snippet.content}
+              </div>
+            </div>
+            <div className="settings-project-actions">
+              <button
+                type="button"
+                className="ghost icon-button"
+                onClick={() => editSnippet(snippet)}
+                aria-label={`Edit snippet ${snippet.name}`}
+              >
+                <Pencil aria-hidden />
+              </button>
+              <button
+                type="button"
+                className="ghost icon-button"
+                onClick={() => void handleDelete(snippet)}
+                aria-label={`Delete snippet ${snippet.name}`}
+              >
+                <Trash2 aria-hidden />
+              </button>
+            </div>
+          </div>
+        ))}
+        {snippets.length === 0 && <div className="settings-empty">No snippets yet.</div>}
+      </div>
+      {draft ? (
+        <div className="settings-snippet-editor">
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="snippet-name">
+              Name
+            </label>
+            <input
+              id="snippet-name"
+              className="settings-input"
+              value={draft.name}
+              placeholder="architecture"
+              onChange={(event) => setDraft({ ...draft, name: event.target.value })}
+            />
+          </div>
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="snippet-description">
+              Description
+            </label>
+            <input
+              id="snippet-description"
+              className="settings-input"
+              value={draft.description}
+              onChange={(event) => setDraft({ ...draft, description: event.target.value })}
+            />
+          </div>
+          <div className="settings-field">
+            <label className="settings-field-label" htmlFor="snippet-content">
+              Content
+            </label>
+            <textarea
+              id="snippet-content"
+              className="settings-input settings-snippet-content"
+              value={draft.content}
+              rows={8}
+              onChange={(event) => setDraft({ ...draft, content: event.target.value })}
+            />
+            <div className="settings-help">
+              {"{{snippet:name}} includes another snippet. {{workspace}}, {{workspacePath}} and {{date}} are filled in when inserted."}
+            </div>
+          </div>
+          <div className="settings-field-actions">
+            <button
+              type="button"
+              className="primary"
+              onClick={() => void handleSave()}
+              disabled={isSaving || !draft.name.trim()}
+            >
+              {isSaving ? "Saving..." : "Save"}
+            </button>
+            <button
+              type="button"
+              className="ghost"
+              onClick={() => setDraft(null)}
+              disabled={isSaving}
+            >
+              Cancel
+            </button>
+          </div>
+        </div>
+      ) : (
+        <div className="settings-field-actions settings-snippet-add">
+          <button
+            type="button"
+            className="ghost settings-button-compact"
+            onClick={() => {
+              setError(null);
+              setDraft(EMPTY_DRAFT);
+            }}
+          >
+            New snippet
+          </button>
+        </div>
+      )}
+      {error && <div className="settings-help">{error}</div>}
+    </section>
+  );
+}
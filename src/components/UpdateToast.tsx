@@ -1,4 +1,5 @@
 import type { UpdateState } from "../hooks/useUpdater";
+import { formatBytes } from "../utils/bytes";
 
 type UpdateToastProps = {
   state: UpdateState;
@@ -6,20 +7,6 @@ type UpdateToastProps = {
   onDismiss: () => void;
 };
 
-function formatBytes(value: number) {
-  if (!Number.isFinite(value) || value <= 0) {
-    return "0 B";
-  }
-  const units = ["B", "KB", "MB", "GB"];
-  let size = value;
-  let unitIndex = 0;
-  while (size >= 1024 && unitIndex < units.length - 1) {
-    size /= 1024;
-    unitIndex += 1;
-  }
-  return `${size.toFixed(size >= 10 ? 0 : 1)} ${units[unitIndex]}`;
-}
-
 export function UpdateToast({ state, onUpdate, onDismiss }: UpdateToastProps) {
   if (state.stage === "idle") {
     return null;
@@ -0,0 +1,223 @@
+import { useCallback, useEffect, useState } from "react";
+import type {
+  StaleWorktree,
+  StaleWorktreeReason,
+  StaleWorktreeReport,
+  WorktreeCleanup,
+} from "../types";
+import { cleanUpWorktrees, listStaleWorktrees } from "../services/tauri";
+import { formatBytes } from "../utils/bytes";
+import { formatRelativeTime } from "../utils/time";
+
+type WorktreeCleanupSettingsProps = {
+  onWorktreesRemoved: () => void;
+};
+
+const DEFAULT_INACTIVE_DAYS = 14;
+
+function reasonLabel(reason: StaleWorktreeReason, worktree: StaleWorktree) {
+  switch (reason) {
+    case "merged":
+      return worktree.mergedInto ? `Merged into ${worktree.mergedInto}` : "Merged";
+    case "inactive":
+      return worktree.lastActivity
+        ? `Inactive since ${formatRelativeTime(worktree.lastActivity)}`
+        : "Inactive";
+    case "missing":
+      return "Folder missing";
+  }
+}
+
+// Worktrees with uncommitted changes start unselected.
+function defaultSelection(report: StaleWorktreeReport) {
+  return new Set(
+    report.worktrees
+      .filter((worktree) => worktree.uncommitted === 0)
+      .map((worktree) => worktree.worktreeId),
+  );
+}
+
+export function WorktreeCleanupSettings({ onWorktreesRemoved }: WorktreeCleanupSettingsProps) {
+  const [daysDraft, setDaysDraft] = useState(String(DEFAULT_INACTIVE_DAYS));
+  const [report, setReport] = useState<StaleWorktreeReport | null>(null);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [deleteBranches, setDeleteBranches] = useState(true);
+  const [results, setResults] = useState<WorktreeCleanup[] | null>(null);
+  const [isBusy, setIsBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const scan = useCallback(async (days: number) => {
+    setIsBusy(true);
+    setError(null);
+    try {
+      const next = await listStaleWorktrees(days);
+      setReport(next);
+      setSelected(defaultSelection(next));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsBusy(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    void scan(DEFAULT_INACTIVE_DAYS);
+  }, [scan]);
+
+  const inactiveDays = () => {
+    const days = Number.parseInt(daysDraft, 10);
+    return Number.isFinite(days) && days > 0 ? days : DEFAULT_INACTIVE_DAYS;
+  };
+
+  const toggle = (worktreeId: string) => {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(worktreeId)) {
+        next.delete(worktreeId);
+      } else {
+        next.add(worktreeId);
+      }
+      return next;
+    });
+  };
+
+  const chosen = report?.worktrees.filter((worktree) => selected.has(worktree.worktreeId)) ?? [];
+  const chosenBytes = chosen.reduce((sum, worktree) => sum + worktree.diskBytes, 0);
+
+  const handleCleanUp = async () => {
+    if (chosen.length === 0) {
+      return;
+    }
+    setIsBusy(true);
+    setError(null);
+    try {
+      const cleaned = await cleanUpWorktrees(
+        chosen.map((worktree) => worktree.worktreeId),
+        deleteBranches,
+      );
+      if (!cleaned) {
+        return;
+      }
+      setResults(cleaned);
+      onWorktreesRemoved();
+      await scan(inactiveDays());
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      setIsBusy(false);
+    }
+  };
+
+  return (
+    <section className="settings-section">
+      <div className="settings-section-title">Worktree cleanup</div>
+      <div className="settings-section-subtitle">
+        Worktrees whose branch is merged into their project&apos;s branch, that have had no
+        session activity or commits for a while, or whose folder is gone. Worktrees with a
+        running turn are never listed.
+      </div>
+      <div className="settings-field">
+        <label className="settings-field-label" htmlFor="stale-worktree-days">
+          Inactive after (days)
+        </label>
+        <div className="settings-field-row">
+          <input
+            id="stale-worktree-days"
+            className="settings-input settings-input--compact"
+            type="number"
+            min={1}
+            value={daysDraft}
+            onChange={(event) => setDaysDraft(event.target.value)}
+          />
+          <button
+            type="button"
+            className="ghost"
+            onClick={() => void scan(inactiveDays())}
+            disabled={isBusy}
+          >
+            Scan
+          </button>
+        </div>
+      </div>
+      {report && (
+        <div className="settings-projects settings-stale-worktrees">
+          {report.worktrees.map((worktree) => (
+            <label key={worktree.worktreeId} className="settings-project-row">
+              <input
+                type="checkbox"
+                checked={selected.has(worktree.worktreeId)}
+                onChange={() => toggle(worktree.worktreeId)}
+                disabled={isBusy}
+              />
+              <div className="settings-project-info">
+                <div className="settings-project-name">
+                  {worktree.name}
+                  <span className="settings-stale-size">{formatBytes(worktree.diskBytes)}</span>
+                </div>
+                <div className="settings-project-path">
+                  {worktree.branch} ·{" "}
+                  {worktree.reasons.map((reason) => reasonLabel(reason, worktree)).join(" · ")}
+                </div>
+                {worktree.uncommitted > 0 && (
+                  <div className="settings-stale-warning">
+                    {worktree.uncommitted} uncommitted{" "}
+                    {worktree.uncommitted === 1 ? "change" : "changes"} will be lost.
+                  </div>
+                )}
+              </div>
+            </label>
+          ))}
+          {report.worktrees.length === 0 && (
+            <div className="settings-empty">No stale worktrees.</div>
+          )}
+        </div>
+      )}
+      {report && report.worktrees.length > 0 && (
+        <>
+          <div className="settings-toggle-row">
+            <div>
+              <div className="settings-toggle-title">Delete their branches too</div>
+              <div className="settings-toggle-subtitle">
+                Branches with commits that aren&apos;t merged are kept.
+              </div>
+            </div>
+            <button
+              type="button"
+              className={`settings-toggle ${deleteBranches ? "on" : ""}`}
+              onClick={() => setDeleteBranches((value) => !value)}
+              aria-pressed={deleteBranches}
+              disabled={isBusy}
+            >
+              <span className="settings-toggle-knob" />
+            </button>
+          </div>
+          <div className="settings-field-actions">
+            <button
+              type="button"
+              className="primary"
+              onClick={() => void handleCleanUp()}
+              disabled={isBusy || chosen.length === 0}
+            >
+              Clean up {chosen.length} {chosen.length === 1 ? "worktree" : "worktrees"} (
+              {formatBytes(chosenBytes)})
+            </button>
+          </div>
+        </>
+      )}
+      {results && (
+        <div className="settings-help">
+          {results.map((result) => (
+            <div key={result.worktreeId}>
+              {result.name}:{" "}
+              {result.removed
+                ? `removed${result.branchDeleted ? ", branch deleted" : ""}`
+                : "kept"}
+              {result.detail ? ` (${result.detail})` : ""}
+            </div>
+          ))}
+        </div>
+      )}
+      {error && <div className="settings-help">{error}</div>}
+    </section>
+  );
+}
@@ -4,6 +4,7 @@ import { MainTopbar } from "../MainTopbar";
 type DesktopLayoutProps = {
   sidebarNode: ReactNode;
   updateToastNode: ReactNode;
+  bridgeRecoveryNode: ReactNode;
   approvalToastsNode: ReactNode;
   homeNode: ReactNode;
   showHome: boolean;
@@ -25,6 +26,7 @@ type DesktopLayoutProps = {
 export function DesktopLayout({
   sidebarNode,
   updateToastNode,
+  bridgeRecoveryNode,
   approvalToastsNode,
   homeNode,
   showHome,
@@ -55,6 +57,7 @@ export function DesktopLayout({
 
       <section className="main">
         {updateToastNode}
+        {bridgeRecoveryNode}
         {showHome && homeNode}
 
         {showWorkspace && (
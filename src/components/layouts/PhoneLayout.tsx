@@ -4,6 +4,7 @@ import { MainTopbar } from "../MainTopbar";
 type PhoneLayoutProps = {
   approvalToastsNode: ReactNode;
   updateToastNode: ReactNode;
+  bridgeRecoveryNode: ReactNode;
   tabBarNode: ReactNode;
   sidebarNode: ReactNode;
   activeTab: "projects" | "claude" | "git" | "log";
@@ -23,6 +24,7 @@ type PhoneLayoutProps = {
 export function PhoneLayout({
   approvalToastsNode,
   updateToastNode,
+  bridgeRecoveryNode,
   tabBarNode,
   sidebarNode,
   activeTab,
@@ -42,6 +44,7 @@ export function PhoneLayout({
     <div className="compact-shell">
       {approvalToastsNode}
       {updateToastNode}
+      {bridgeRecoveryNode}
       {activeTab === "projects" && <div className="compact-panel">{sidebarNode}</div>}
       {activeTab === "claude" && (
         <div className="compact-panel">
@@ -5,6 +5,7 @@ type TabletLayoutProps = {
   tabletNavNode: ReactNode;
   approvalToastsNode: ReactNode;
   updateToastNode: ReactNode;
+  bridgeRecoveryNode: ReactNode;
   homeNode: ReactNode;
   showHome: boolean;
   showWorkspace: boolean;
@@ -23,6 +24,7 @@ export function TabletLayout({
   tabletNavNode,
   approvalToastsNode,
   updateToastNode,
+  bridgeRecoveryNode,
   homeNode,
   showHome,
   showWorkspace,
@@ -50,6 +52,7 @@ export function TabletLayout({
       <section className="tablet-main">
         {approvalToastsNode}
         {updateToastNode}
+        {bridgeRecoveryNode}
         {showHome && homeNode}
         {showWorkspace && (
           <>
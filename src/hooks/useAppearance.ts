@@ -0,0 +1,61 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { AppearanceSettings, ColorScheme } from "../types";
+import { getAppearance } from "../services/tauri";
+
+const DEFAULT_APPEARANCE: AppearanceSettings = {
+  theme: "system",
+  accentColor: null,
+  fontSize: 14,
+};
+
+/**
+ * Appearance settings from the backend, kept in sync across windows by
+ * `appearance-changed` events. The theme is applied as `data-theme` on the
+ * root element, with `system` resolved to the OS `colorScheme`.
+ */
+export function useAppearance(colorScheme: ColorScheme) {
+  const [appearance, setAppearance] =
+    useState<AppearanceSettings>(DEFAULT_APPEARANCE);
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<AppearanceSettings>("appearance-changed", (event) => {
+      setAppearance(event.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    void getAppearance()
+      .then((initial) => {
+        if (!canceled) {
+          setAppearance(initial);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    const root = document.documentElement;
+    root.dataset.theme =
+      appearance.theme === "system" ? colorScheme : appearance.theme;
+    if (appearance.accentColor) {
+      root.dataset.accent = "custom";
+      root.style.setProperty("--accent-color", appearance.accentColor);
+    } else {
+      delete root.dataset.accent;
+      root.style.removeProperty("--accent-color");
+    }
+    root.style.setProperty("--font-size-message", `${appearance.fontSize}px`);
+  }, [appearance, colorScheme]);
+
+  return appearance;
+}
@@ -0,0 +1,29 @@
+import { useEffect } from "react";
+import { setAttendedSession } from "../services/tauri";
+
+/**
+ * Report the selected session as attended while the window is focused,
+ * so the backend can total the time spent supervising it.
+ */
+export function useAttendedTime(activeThreadId: string | null) {
+  useEffect(() => {
+    const report = () => {
+      const attended =
+        document.hasFocus() && document.visibilityState === "visible"
+          ? activeThreadId
+          : null;
+      void setAttendedSession(attended).catch(() => {});
+    };
+
+    report();
+    window.addEventListener("focus", report);
+    window.addEventListener("blur", report);
+    document.addEventListener("visibilitychange", report);
+    return () => {
+      window.removeEventListener("focus", report);
+      window.removeEventListener("blur", report);
+      document.removeEventListener("visibilitychange", report);
+      void setAttendedSession(null).catch(() => {});
+    };
+  }, [activeThreadId]);
+}
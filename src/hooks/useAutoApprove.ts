@@ -0,0 +1,65 @@
+import { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { AutoApproveScope, AutoApproveWindow } from "../types";
+import { disableAutoApprove, enableAutoApprove } from "../services/tauri";
+
+type AutoApproveChanged = {
+  sessionId: string;
+  window: AutoApproveWindow | null;
+};
+
+/**
+ * Open auto-approve windows by session id, kept current by
+ * `auto-approve-changed` events (including the backend closing a window
+ * when it runs out).
+ */
+export function useAutoApprove() {
+  const [windows, setWindows] = useState<Record<string, AutoApproveWindow>>({});
+
+  const applyWindow = useCallback((sessionId: string, window: AutoApproveWindow | null) => {
+    setWindows((prev) => {
+      const next = { ...prev };
+      if (window) {
+        next[sessionId] = window;
+      } else {
+        delete next[sessionId];
+      }
+      return next;
+    });
+  }, []);
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<AutoApproveChanged>("auto-approve-changed", (event) => {
+      applyWindow(event.payload.sessionId, event.payload.window);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, [applyWindow]);
+
+  const enable = useCallback(
+    async (sessionId: string, seconds: number, scope: AutoApproveScope) => {
+      applyWindow(sessionId, await enableAutoApprove(sessionId, seconds, scope));
+    },
+    [applyWindow],
+  );
+
+  const disable = useCallback(
+    async (sessionId: string) => {
+      await disableAutoApprove(sessionId);
+      applyWindow(sessionId, null);
+    },
+    [applyWindow],
+  );
+
+  return { windows, enable, disable };
+}
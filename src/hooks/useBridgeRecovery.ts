@@ -0,0 +1,41 @@
+import { useCallback, useMemo, useState } from "react";
+import type {
+  BridgeDiedPayload,
+  BridgeReconnectedPayload,
+  ClaudeBridgeDiedEvent,
+  ClaudeBridgeReconnectedEvent,
+} from "../types";
+import { useClaudeEvents } from "./useClaudeEvents";
+
+export type BridgeRecoveryState =
+  | { stage: "idle" }
+  | { stage: "restarting"; died: BridgeDiedPayload }
+  | { stage: "reconnected"; reconnected: BridgeReconnectedPayload };
+
+const IDLE: BridgeRecoveryState = { stage: "idle" };
+
+/**
+ * Tracks an unexpected bridge exit from `bridge/died` until the bridge is
+ * back (`bridge/reconnected`), for the recovery banner.
+ */
+export function useBridgeRecovery() {
+  const [state, setState] = useState<BridgeRecoveryState>(IDLE);
+
+  const handlers = useMemo(
+    () => ({
+      onBridgeDied: (event: ClaudeBridgeDiedEvent) => {
+        setState({ stage: "restarting", died: event.payload });
+      },
+      onBridgeReconnected: (event: ClaudeBridgeReconnectedEvent) => {
+        setState({ stage: "reconnected", reconnected: event.payload });
+      },
+    }),
+    [],
+  );
+
+  useClaudeEvents(handlers);
+
+  const dismiss = useCallback(() => setState(IDLE), []);
+
+  return { state, dismiss };
+}
@@ -2,6 +2,8 @@ import { useEffect } from "react";
 import { listen } from "@tauri-apps/api/event";
 import type {
   ClaudeBridgeEvent,
+  ClaudeBridgeDiedEvent,
+  ClaudeBridgeReconnectedEvent,
   ClaudeApprovalRequest,
   ClaudeMessageDeltaEvent,
   ClaudeMessageCompleteEvent,
@@ -12,6 +14,10 @@ import type {
   ClaudeToolProgressEvent,
   ClaudeToolCompletedEvent,
   ClaudeErrorEvent,
+  ClaudeTurnInterruptedEvent,
+  ClaudeUnreadChangedEvent,
+  ClaudeQueueDispatchedEvent,
+  ClaudeGuardrailTriggeredEvent,
 } from "../types";
 
 export type ClaudeEventHandlers = {
@@ -25,23 +31,34 @@ export type ClaudeEventHandlers = {
   onToolCompleted?: (event: ClaudeToolCompletedEvent) => void;
   onResult?: (event: ClaudeResultEvent) => void;
   onError?: (event: ClaudeErrorEvent) => void;
+  onTurnInterrupted?: (event: ClaudeTurnInterruptedEvent) => void;
+  onUnreadChanged?: (event: ClaudeUnreadChangedEvent) => void;
+  onQueueDispatched?: (event: ClaudeQueueDispatchedEvent) => void;
+  onGuardrailTriggered?: (event: ClaudeGuardrailTriggeredEvent) => void;
   onBridgeConnected?: (workspaceId: string, payload: unknown) => void;
+  onBridgeDied?: (event: ClaudeBridgeDiedEvent) => void;
+  onBridgeReconnected?: (event: ClaudeBridgeReconnectedEvent) => void;
   onBridgeStderr?: (workspaceId: string, message: string) => void;
   onRawEvent?: (event: ClaudeBridgeEvent) => void;
 };
 
 /**
  * Hook to listen for Claude bridge events from the Tauri backend.
- * Event name: "claude-event" with payload BridgeEvent.
+ * Event names: "claude-event" with payload BridgeEvent, and
+ * "claude-event-batch" with coalesced BridgeEvent[] (e.g. message deltas).
+ * With `workspaceId`, only that workspace's channels
+ * ("claude-event:{id}", "claude-event-batch:{id}") and bridge events
+ * ("claude-event:bridge") are received.
  */
-export function useClaudeEvents(handlers: ClaudeEventHandlers) {
+export function useClaudeEvents(
+  handlers: ClaudeEventHandlers,
+  workspaceId: string | null = null,
+) {
   useEffect(() => {
-    let unlisten: (() => void) | null = null;
+    const unlisteners: (() => void)[] = [];
     let canceled = false;
 
-    // Listen to "claude-event" from Tauri backend (Rust ClaudeEvent struct)
-    listen<ClaudeBridgeEvent>("claude-event", (tauriEvent) => {
-      const message = tauriEvent.payload;
+    const handleMessage = (message: ClaudeBridgeEvent) => {
       handlers.onRawEvent?.(message);
 
       switch (message.type) {
@@ -59,10 +76,10 @@ export function useClaudeEvents(handlers: ClaudeEventHandlers) {
             session_id: message.sessionId,
             tool_use_id: message.payload.toolUseId,
             tool_name: message.payload.toolName,
-            tool_input: message.payload.input,
-            suggestions: message.payload.suggestions,
-            blocked_path: message.payload.blockedPath,
-            decision_reason: message.payload.decisionReason,
+            tool_input: message.payload.input as Record<string, unknown>,
+            suggestions: message.payload.suggestions ?? undefined,
+            blocked_path: message.payload.blockedPath ?? undefined,
+            decision_reason: message.payload.decisionReason ?? undefined,
           });
           break;
 
@@ -94,15 +111,41 @@ export function useClaudeEvents(handlers: ClaudeEventHandlers) {
           handlers.onError?.(message);
           break;
 
+        case "turn/interrupted":
+          handlers.onTurnInterrupted?.(message);
+          break;
+
+        case "unread/changed":
+          handlers.onUnreadChanged?.(message);
+          break;
+
+        case "queue/dispatched":
+          handlers.onQueueDispatched?.(message);
+          break;
+
+        case "guardrail/triggered":
+          handlers.onGuardrailTriggered?.(message);
+          break;
+
         case "bridge/connected":
           handlers.onBridgeConnected?.(message.workspaceId, message.payload);
           break;
 
+        case "bridge/died":
+          handlers.onBridgeDied?.(message);
+          break;
+
+        case "bridge/reconnected":
+          handlers.onBridgeReconnected?.(message);
+          break;
+
         case "bridge/stderr":
           handlers.onBridgeStderr?.(message.workspaceId, message.payload.message);
           break;
       }
-    }).then((handler) => {
+    };
+
+    const track = (handler: () => void) => {
       if (canceled) {
         try {
           handler();
@@ -110,13 +153,30 @@ export function useClaudeEvents(handlers: ClaudeEventHandlers) {
           // Ignore unlisten errors when already removed.
         }
       } else {
-        unlisten = handler;
+        unlisteners.push(handler);
       }
-    });
+    };
+
+    const suffix = workspaceId ? `:${workspaceId}` : "";
+
+    // Listen to "claude-event" from Tauri backend (Rust ClaudeEvent struct)
+    listen<ClaudeBridgeEvent>(`claude-event${suffix}`, (tauriEvent) => {
+      handleMessage(tauriEvent.payload);
+    }).then(track);
+
+    listen<ClaudeBridgeEvent[]>(`claude-event-batch${suffix}`, (tauriEvent) => {
+      tauriEvent.payload.forEach(handleMessage);
+    }).then(track);
+
+    if (workspaceId) {
+      listen<ClaudeBridgeEvent>("claude-event:bridge", (tauriEvent) => {
+        handleMessage(tauriEvent.payload);
+      }).then(track);
+    }
 
     return () => {
       canceled = true;
-      if (unlisten) {
+      for (const unlisten of unlisteners) {
         try {
           unlisten();
         } catch {
@@ -124,5 +184,5 @@ export function useClaudeEvents(handlers: ClaudeEventHandlers) {
         }
       }
     };
-  }, [handlers]);
+  }, [handlers, workspaceId]);
 }
@@ -7,6 +7,8 @@ export type AutocompleteItem = {
   insertText?: string;
   hint?: string;
   cursorOffset?: number;
+  /** Fetches the text to insert in place of the trigger and query. */
+  resolveText?: () => Promise<string>;
 };
 
 export type AutocompleteTrigger = {
@@ -1,7 +1,7 @@
 import { useCallback, useMemo } from "react";
 import type { AutocompleteItem } from "./useComposerAutocomplete";
 import { useComposerAutocomplete } from "./useComposerAutocomplete";
-import type { CustomPromptOption } from "../types";
+import type { CustomPromptOption, Snippet } from "../types";
 import {
   buildPromptInsertText,
   findNextPromptArgCursor,
@@ -17,6 +17,8 @@ type UseComposerAutocompleteStateArgs = {
   skills: Skill[];
   prompts: CustomPromptOption[];
   files: string[];
+  snippets?: Snippet[];
+  onExpandSnippet?: (name: string) => Promise<string>;
   textareaRef: React.RefObject<HTMLTextAreaElement | null>;
   setText: (next: string) => void;
   setSelectionStart: (next: number | null) => void;
@@ -29,6 +31,8 @@ export function useComposerAutocompleteState({
   skills,
   prompts,
   files,
+  snippets = [],
+  onExpandSnippet,
   textareaRef,
   setText,
   setSelectionStart,
@@ -72,6 +76,20 @@ export function useComposerAutocompleteState({
     [prompts],
   );
 
+  const snippetItems = useMemo<AutocompleteItem[]>(
+    () =>
+      snippets.map((snippet) => ({
+        id: `snippet:${snippet.name}`,
+        label: `snippets:${snippet.name}`,
+        description: snippet.description,
+        resolveText: () =>
+          onExpandSnippet
+            ? onExpandSnippet(snippet.name)
+            : Promise.resolve(snippet.content),
+      })),
+    [onExpandSnippet, snippets],
+  );
+
   const reviewItems = useMemo<AutocompleteItem[]>(
     () => [
       {
@@ -145,8 +163,8 @@ export function useComposerAutocompleteState({
   );
 
   const slashItems = useMemo<AutocompleteItem[]>(
-    () => [...reviewItems, ...claudeCommands, ...promptItems],
-    [claudeCommands, promptItems, reviewItems],
+    () => [...reviewItems, ...claudeCommands, ...promptItems, ...snippetItems],
+    [claudeCommands, promptItems, reviewItems, snippetItems],
   );
 
   const triggers = useMemo(
@@ -179,6 +197,29 @@ export function useComposerAutocompleteState({
       }
       const triggerIndex = Math.max(0, autocompleteRange.start - 1);
       const triggerChar = text[triggerIndex] ?? "";
+      if (item.resolveText) {
+        // Resolved text stands on its own, so the trigger goes too.
+        const before = text.slice(0, triggerIndex);
+        const after = text.slice(autocompleteRange.end);
+        closeAutocomplete();
+        void item
+          .resolveText()
+          .then((resolved) => {
+            setText(`${before}${resolved}${after}`);
+            requestAnimationFrame(() => {
+              const textarea = textareaRef.current;
+              if (!textarea) {
+                return;
+              }
+              const cursor = before.length + resolved.length;
+              textarea.focus();
+              textarea.setSelectionRange(cursor, cursor);
+              setSelectionStart(cursor);
+            });
+          })
+          .catch(() => {});
+        return;
+      }
       const cursor = selectionStart ?? autocompleteRange.end;
       const promptRange =
         triggerChar === "@" ? findPromptArgRangeAtCursor(text, cursor) : null;
@@ -58,6 +58,7 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
             ? String(item.argument_hint)
             : undefined,
         content: String(item.content ?? ""),
+        source: item.source === "team" ? "team" : "local",
       }));
       setPrompts(data);
       lastFetchedWorkspaceId.current = workspaceId;
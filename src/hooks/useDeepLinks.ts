@@ -0,0 +1,53 @@
+import { useEffect, useRef } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { DeepLink } from "../types";
+import { takeLaunchDeepLink } from "../services/tauri";
+
+type DeepLinkHandlers = {
+  onOpenWorkspace: (workspaceId: string) => void;
+  onNewSession: (workspaceId: string) => void;
+};
+
+/**
+ * Route `claude-monitor://` links from `deep-link` events, and the one the
+ * app was launched with, to the workspace handlers.
+ */
+export function useDeepLinks(handlers: DeepLinkHandlers, isReady: boolean) {
+  const handlersRef = useRef(handlers);
+  handlersRef.current = handlers;
+
+  useEffect(() => {
+    if (!isReady) {
+      return;
+    }
+    const route = (link: DeepLink) => {
+      if (link.action === "newSession") {
+        handlersRef.current.onNewSession(link.workspaceId);
+      } else {
+        handlersRef.current.onOpenWorkspace(link.workspaceId);
+      }
+    };
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<DeepLink>("deep-link", (event) => {
+      route(event.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    void takeLaunchDeepLink()
+      .then((link) => {
+        if (link && !canceled) {
+          route(link);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, [isReady]);
+}
@@ -0,0 +1,50 @@
+import { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { FocusStatus } from "../types";
+import { getDoNotDisturb, setDoNotDisturb } from "../services/tauri";
+
+const IDLE: FocusStatus = { active: false, until: null, held: 0 };
+
+/**
+ * Do-not-disturb status from the backend, kept current by `focus-changed`
+ * events. Permission requests held during a focus period arrive as normal
+ * events once it ends.
+ */
+export function useDoNotDisturb() {
+  const [status, setStatus] = useState<FocusStatus>(IDLE);
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<FocusStatus>("focus-changed", (event) => {
+      setStatus(event.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    void getDoNotDisturb()
+      .then((initial) => {
+        if (!canceled) {
+          setStatus(initial);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const toggle = useCallback(
+    async (minutes?: number) => {
+      const next = await setDoNotDisturb(!status.active, minutes);
+      setStatus(next);
+    },
+    [status.active],
+  );
+
+  return { status, toggle };
+}
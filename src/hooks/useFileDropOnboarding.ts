@@ -0,0 +1,53 @@
+import { useEffect, useRef } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { FilesDropEvent, WorkspaceDropEvent, WorkspaceInfo } from "../types";
+
+type FileDropHandlers = {
+  onWorkspaceAdded: (workspace: WorkspaceInfo, startSession: boolean) => void;
+  onWorkspaceExisting: (workspaceId: string, startSession: boolean) => void;
+  onDropError: (path: string, error: string) => void;
+  onFilesDropped: (paths: string[]) => void;
+};
+
+/**
+ * Folders dropped on the window are added as workspaces by the backend;
+ * other dropped files are handed to the composer.
+ */
+export function useFileDropOnboarding(handlers: FileDropHandlers) {
+  const handlersRef = useRef(handlers);
+  handlersRef.current = handlers;
+
+  useEffect(() => {
+    const unlisteners: Array<() => void> = [];
+    let canceled = false;
+    const register = async () => {
+      const unlistenWorkspace = await listen<WorkspaceDropEvent>(
+        "workspace-dropped",
+        (event) => {
+          const { workspace, existingId, startSession, error, path } = event.payload;
+          if (error) {
+            handlersRef.current.onDropError(path, error);
+          } else if (workspace) {
+            handlersRef.current.onWorkspaceAdded(workspace, startSession);
+          } else if (existingId) {
+            handlersRef.current.onWorkspaceExisting(existingId, startSession);
+          }
+        },
+      );
+      const unlistenFiles = await listen<FilesDropEvent>("files-dropped", (event) => {
+        handlersRef.current.onFilesDropped(event.payload.paths);
+      });
+      if (canceled) {
+        unlistenWorkspace();
+        unlistenFiles();
+        return;
+      }
+      unlisteners.push(unlistenWorkspace, unlistenFiles);
+    };
+    void register();
+    return () => {
+      canceled = true;
+      unlisteners.forEach((unlisten) => unlisten());
+    };
+  }, []);
+}
@@ -1,6 +1,6 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import type { GitFileDiff, GitFileStatus, WorkspaceInfo } from "../types";
-import { getGitDiffs } from "../services/tauri";
+import { getFileDiff, getGitDiffs } from "../services/tauri";
 
 type GitDiffState = {
   diffs: GitFileDiff[];
@@ -45,7 +45,10 @@ export function useGitDiffs(
     requestIdRef.current = requestId;
     setState((prev) => ({ ...prev, isLoading: true, error: null }));
     try {
-      const diffs = await getGitDiffs(workspaceId);
+      const diffs = await getGitDiffs(workspaceId, {
+        highlight: true,
+        wordDiff: true,
+      });
       if (
         requestIdRef.current !== requestId ||
         workspaceIdRef.current !== workspaceId
@@ -69,6 +72,32 @@ export function useGitDiffs(
     }
   }, [activeWorkspace]);
 
+  const loadFullDiff = useCallback(
+    async (path: string) => {
+      if (!activeWorkspace) {
+        return;
+      }
+      const workspaceId = activeWorkspace.id;
+      const requestId = requestIdRef.current;
+      try {
+        const full = await getFileDiff(workspaceId, path);
+        if (
+          requestIdRef.current !== requestId ||
+          workspaceIdRef.current !== workspaceId
+        ) {
+          return;
+        }
+        setState((prev) => ({
+          ...prev,
+          diffs: prev.diffs.map((entry) => (entry.path === path ? full : entry)),
+        }));
+      } catch (error) {
+        console.error("Failed to load full diff", error);
+      }
+    },
+    [activeWorkspace],
+  );
+
   useEffect(() => {
     const workspaceId = activeWorkspace?.id ?? null;
     if (workspaceIdRef.current !== workspaceId) {
@@ -86,13 +115,15 @@ export function useGitDiffs(
   }, [enabled, fileKey, refresh]);
 
   const orderedDiffs = useMemo(() => {
-    const diffByPath = new Map(
-      state.diffs.map((entry) => [entry.path, entry.diff]),
-    );
+    const diffByPath = new Map(state.diffs.map((entry) => [entry.path, entry]));
     return files.map((file) => ({
       path: file.path,
       status: file.status,
-      diff: diffByPath.get(file.path) ?? "",
+      diff: diffByPath.get(file.path)?.diff ?? "",
+      highlighted: diffByPath.get(file.path)?.highlighted,
+      intraline: diffByPath.get(file.path)?.intraline,
+      truncated: diffByPath.get(file.path)?.truncated,
+      lfs: diffByPath.get(file.path)?.lfs,
     }));
   }, [files, state.diffs]);
 
@@ -101,5 +132,6 @@ export function useGitDiffs(
     isLoading: state.isLoading,
     error: state.error,
     refresh,
+    loadFullDiff,
   };
 }
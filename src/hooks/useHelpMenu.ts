@@ -0,0 +1,33 @@
+import { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { HelpMenuEvent } from "../types";
+
+/**
+ * The latest Help menu result to show (doctor output, keyboard shortcuts,
+ * or where diagnostics were saved), from `help-menu` events.
+ */
+export function useHelpMenu() {
+  const [event, setEvent] = useState<HelpMenuEvent | null>(null);
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<HelpMenuEvent>("help-menu", (message) => {
+      setEvent(message.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const dismiss = useCallback(() => setEvent(null), []);
+
+  return { event, dismiss };
+}
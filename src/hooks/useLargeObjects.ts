@@ -0,0 +1,48 @@
+import { useEffect, useState } from "react";
+import type { LargeObject } from "../types";
+import { checkCommitSize, checkPushObjects } from "../services/tauri";
+
+type LargeObjectsState = {
+  staged: LargeObject[];
+  unpushed: LargeObject[];
+  // Set when `lfs.blockLargeCommits` refuses the staged files
+  blocked: string | null;
+};
+
+const emptyState: LargeObjectsState = { staged: [], unpushed: [], blocked: null };
+
+/**
+ * Staged files and unpushed commits over the large file limit, rechecked
+ * whenever `refreshKey` changes (e.g. the git status totals).
+ */
+export function useLargeObjects(workspaceId: string | null, refreshKey: string) {
+  const [state, setState] = useState<LargeObjectsState>(emptyState);
+
+  useEffect(() => {
+    if (!workspaceId) {
+      setState(emptyState);
+      return;
+    }
+    let cancelled = false;
+    const staged = checkCommitSize(workspaceId).then(
+      (objects) => ({ objects, blocked: null }),
+      (err) => ({ objects: [], blocked: err instanceof Error ? err.message : String(err) }),
+    );
+    const unpushed = checkPushObjects(workspaceId).catch(() => []);
+    void Promise.all([staged, unpushed]).then(([stagedResult, unpushedObjects]) => {
+      if (cancelled) {
+        return;
+      }
+      setState({
+        staged: stagedResult.objects,
+        unpushed: unpushedObjects,
+        blocked: stagedResult.blocked,
+      });
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [workspaceId, refreshKey]);
+
+  return state;
+}
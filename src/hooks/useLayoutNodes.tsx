@@ -6,6 +6,7 @@ import { MainHeader } from "../components/MainHeader";
 import { Messages } from "../components/Messages";
 import { ApprovalToasts } from "../components/ApprovalToasts";
 import { UpdateToast } from "../components/UpdateToast";
+import { BridgeRecoveryBanner } from "../components/BridgeRecoveryBanner";
 import { Composer } from "../components/Composer";
 import { GitDiffPanel } from "../components/GitDiffPanel";
 import { GitDiffViewer } from "../components/GitDiffViewer";
@@ -15,25 +16,35 @@ import { TabBar } from "../components/TabBar";
 import { TabletNav } from "../components/TabletNav";
 import type {
   AccessMode,
+  AutoApproveWindow,
   BranchInfo,
+  ClaudeApprovalRequest,
   ConversationItem,
   CustomPromptOption,
   DebugEntry,
   DiffLineReference,
+  DiffTruncation,
+  FileReference,
+  FocusStatus,
   GitFileStatus,
   GitHubIssue,
   GitLogEntry,
+  IntralineChange,
+  LfsInfo,
   ModelOption,
   QueuedMessage,
   RateLimitSnapshot,
   SkillOption,
+  Snippet,
   ThreadSummary,
   ThreadTokenUsage,
   TurnPlan,
   WorkspaceInfo,
+  WorktreeOverview,
 } from "../types";
 import type { UnifiedApprovalRequest } from "./useThreadsReducer";
 import type { UpdateState } from "./useUpdater";
+import type { BridgeRecoveryState } from "./useBridgeRecovery";
 
 type ThreadActivityStatus = {
   isProcessing: boolean;
@@ -47,6 +58,10 @@ type GitDiffViewerItem = {
   path: string;
   status: string;
   diff: string;
+  highlighted?: string[];
+  intraline?: IntralineChange[];
+  truncated?: DiffTruncation;
+  lfs?: LfsInfo;
 };
 
 type LayoutNodesOptions = {
@@ -58,6 +73,7 @@ type LayoutNodesOptions = {
   activeWorkspaceId: string | null;
   activeThreadId: string | null;
   activeItems: ConversationItem[];
+  onOpenReference?: (reference: FileReference) => void;
   activeRateLimits: RateLimitSnapshot | null;
   showArchivedSessions: boolean;
   onToggleShowArchived: () => void;
@@ -66,9 +82,14 @@ type LayoutNodesOptions = {
     request: UnifiedApprovalRequest,
     decision: "accept" | "decline",
   ) => void;
+  autoApproveWindows: Record<string, AutoApproveWindow>;
+  onAutoApprove: (request: ClaudeApprovalRequest) => void;
+  onStopAutoApprove: (sessionId: string) => void;
   onOpenSettings: () => void;
   onOpenDebug: () => void;
   hasDebugAlerts: boolean;
+  focusStatus: FocusStatus;
+  onToggleFocus: () => void;
   onAddWorkspace: () => void;
   onSelectHome: () => void;
   onSelectWorkspace: (workspaceId: string) => void;
@@ -82,9 +103,12 @@ type LayoutNodesOptions = {
   onUnarchiveThread: (workspaceId: string, threadId: string) => void;
   onDeleteWorkspace: (workspaceId: string) => void;
   onDeleteWorktree: (workspaceId: string) => void;
+  onToggleWorkspaceAutomation: (workspaceId: string, paused: boolean) => void;
   updaterState: UpdateState;
   onUpdate: () => void;
   onDismissUpdate: () => void;
+  bridgeRecoveryState: BridgeRecoveryState;
+  onDismissBridgeRecovery: () => void;
   latestAgentRuns: Array<{
     threadId: string;
     message: string;
@@ -108,8 +132,8 @@ type LayoutNodesOptions = {
   activeTab: "projects" | "claude" | "git" | "log";
   onSelectTab: (tab: "projects" | "claude" | "git" | "log") => void;
   tabletNavTab: "claude" | "git" | "log";
-  gitPanelMode: "diff" | "log" | "issues";
-  onGitPanelModeChange: (mode: "diff" | "log" | "issues") => void;
+  gitPanelMode: "diff" | "log" | "issues" | "merge";
+  onGitPanelModeChange: (mode: "diff" | "log" | "issues" | "merge") => void;
   gitStatus: {
     branchName: string;
     files: GitFileStatus[];
@@ -134,11 +158,15 @@ type LayoutNodesOptions = {
   gitIssuesLoading: boolean;
   gitIssuesError: string | null;
   gitRemoteUrl: string | null;
+  worktreeOverview: WorktreeOverview | null;
+  worktreeOverviewLoading: boolean;
+  worktreeOverviewError: string | null;
   gitDiffs: GitDiffViewerItem[];
   gitDiffLoading: boolean;
   gitDiffError: string | null;
   onDiffLineReference: (reference: DiffLineReference) => void;
   onDiffActivePathChange: (path: string) => void;
+  onLoadFullDiff: (path: string) => void;
   onSend: (text: string, images: string[]) => void | Promise<void>;
   onStop: () => void;
   canStop: boolean;
@@ -146,6 +174,9 @@ type LayoutNodesOptions = {
   isProcessing: boolean;
   activeTokenUsage: ThreadTokenUsage | null;
   activeQueue: QueuedMessage[];
+  recentPrompts: string[];
+  snippets: Snippet[];
+  onExpandSnippet: (name: string) => Promise<string>;
   draftText: string;
   onDraftChange: (next: string) => void;
   activeImages: string[];
@@ -158,6 +189,7 @@ type LayoutNodesOptions = {
   onInsertHandled: (id: string) => void;
   onEditQueued: (item: QueuedMessage) => void;
   onDeleteQueued: (id: string) => void;
+  onMoveQueued: (id: string, index: number) => void;
   models: ModelOption[];
   selectedModelId: string | null;
   onSelectModel: (id: string | null) => void;
@@ -187,6 +219,7 @@ type LayoutNodesResult = {
   composerNode: ReactNode;
   approvalToastsNode: ReactNode;
   updateToastNode: ReactNode;
+  bridgeRecoveryNode: ReactNode;
   homeNode: ReactNode;
   mainHeaderNode: ReactNode;
   desktopTopbarLeftNode: ReactNode;
@@ -222,6 +255,8 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onOpenSettings={options.onOpenSettings}
       onOpenDebug={options.onOpenDebug}
       hasDebugAlerts={options.hasDebugAlerts}
+      focusStatus={options.focusStatus}
+      onToggleFocus={options.onToggleFocus}
       onAddWorkspace={options.onAddWorkspace}
       onSelectHome={options.onSelectHome}
       onSelectWorkspace={options.onSelectWorkspace}
@@ -235,6 +270,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onUnarchiveThread={options.onUnarchiveThread}
       onDeleteWorkspace={options.onDeleteWorkspace}
       onDeleteWorktree={options.onDeleteWorktree}
+      onToggleWorkspaceAutomation={options.onToggleWorkspaceAutomation}
     />
   );
 
@@ -248,6 +284,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       }
       processingStartedAt={activeThreadStatus?.processingStartedAt ?? null}
       lastDurationMs={activeThreadStatus?.lastDurationMs ?? null}
+      onOpenReference={options.onOpenReference}
     />
   );
 
@@ -259,6 +296,9 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       disabled={options.isReviewing}
       contextUsage={options.activeTokenUsage}
       queuedMessages={options.activeQueue}
+      recentPrompts={options.recentPrompts}
+      snippets={options.snippets}
+      onExpandSnippet={options.onExpandSnippet}
       sendLabel={options.isProcessing ? "Queue" : "Send"}
       draftText={options.draftText}
       onDraftChange={options.onDraftChange}
@@ -272,6 +312,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onInsertHandled={options.onInsertHandled}
       onEditQueued={options.onEditQueued}
       onDeleteQueued={options.onDeleteQueued}
+      onMoveQueued={options.onMoveQueued}
       models={options.models}
       selectedModelId={options.selectedModelId}
       onSelectModel={options.onSelectModel}
@@ -292,6 +333,9 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       approvals={options.approvals}
       workspaces={options.workspaces}
       onDecision={options.handleApprovalDecision}
+      autoApproveWindows={options.autoApproveWindows}
+      onAutoApprove={options.onAutoApprove}
+      onStopAutoApprove={options.onStopAutoApprove}
     />
   );
 
@@ -303,6 +347,13 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
     />
   );
 
+  const bridgeRecoveryNode = (
+    <BridgeRecoveryBanner
+      state={options.bridgeRecoveryState}
+      onDismiss={options.onDismissBridgeRecovery}
+    />
+  );
+
   const homeNode = (
     <Home
       onOpenProject={options.onAddWorkspace}
@@ -353,6 +404,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
 
   const gitDiffPanelNode = (
     <GitDiffPanel
+      workspaceId={options.activeWorkspaceId}
       mode={options.gitPanelMode}
       onModeChange={options.onGitPanelModeChange}
       branchName={options.gitStatus.branchName || "unknown"}
@@ -377,6 +429,10 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       issuesLoading={options.gitIssuesLoading}
       issuesError={options.gitIssuesError}
       gitRemoteUrl={options.gitRemoteUrl}
+      isWorktree={options.isWorktreeWorkspace}
+      overview={options.worktreeOverview}
+      overviewLoading={options.worktreeOverviewLoading}
+      overviewError={options.worktreeOverviewError}
     />
   );
 
@@ -388,6 +444,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       error={options.gitDiffError}
       onLineReference={options.onDiffLineReference}
       onActivePathChange={options.onDiffActivePathChange}
+      onLoadFullDiff={options.onLoadFullDiff}
     />
   );
 
@@ -446,6 +503,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
     composerNode,
     approvalToastsNode,
     updateToastNode,
+    bridgeRecoveryNode,
     homeNode,
     mainHeaderNode,
     desktopTopbarLeftNode,
@@ -0,0 +1,47 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { PowerStatus } from "../types";
+import { getPowerStatus } from "../services/tauri";
+
+const UNTHROTTLED: PowerStatus = {
+  onBattery: false,
+  thermalPressure: false,
+  throttled: false,
+  maxSessions: null,
+  indexingPaused: false,
+};
+
+/**
+ * Battery and thermal state from the backend, kept current by
+ * `power-status-changed` events.
+ */
+export function usePowerStatus() {
+  const [status, setStatus] = useState<PowerStatus>(UNTHROTTLED);
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<PowerStatus>("power-status-changed", (event) => {
+      setStatus(event.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    void getPowerStatus()
+      .then((initial) => {
+        if (!canceled) {
+          setStatus(initial);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  return status;
+}
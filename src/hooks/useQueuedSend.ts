@@ -1,5 +1,12 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
-import type { QueuedMessage, WorkspaceInfo } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import type { MessageQueueChanged, QueuedMessage, WorkspaceInfo } from "../types";
+import {
+  dropQueuedMessage,
+  listQueuedMessages,
+  queueMessage,
+  reorderQueuedMessage,
+} from "../services/tauri";
 
 type UseQueuedSendOptions = {
   activeThreadId: string | null;
@@ -17,8 +24,14 @@ type UseQueuedSendResult = {
   activeQueue: QueuedMessage[];
   handleSend: (text: string, images?: string[]) => Promise<void>;
   removeQueuedMessage: (threadId: string, messageId: string) => void;
+  moveQueuedMessage: (threadId: string, messageId: string, index: number) => void;
 };
 
+/**
+ * Messages sent while a turn is running are queued in the backend, which
+ * sends them one at a time as turns end. The queues here mirror it through
+ * `message-queue-changed` events.
+ */
 export function useQueuedSend({
   activeThreadId,
   isProcessing,
@@ -32,22 +45,52 @@ export function useQueuedSend({
   const [queuedByThread, setQueuedByThread] = useState<
     Record<string, QueuedMessage[]>
   >({});
-  const [flushingByThread, setFlushingByThread] = useState<
-    Record<string, boolean>
-  >({});
 
   const activeQueue = useMemo(
     () => (activeThreadId ? queuedByThread[activeThreadId] ?? [] : []),
     [activeThreadId, queuedByThread],
   );
 
-  const enqueueMessage = useCallback((threadId: string, item: QueuedMessage) => {
-    setQueuedByThread((prev) => ({
-      ...prev,
-      [threadId]: [...(prev[threadId] ?? []), item],
-    }));
+  const setQueue = useCallback((threadId: string, queue: QueuedMessage[]) => {
+    setQueuedByThread((prev) => ({ ...prev, [threadId]: queue }));
   }, []);
 
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<MessageQueueChanged>("message-queue-changed", (event) => {
+      setQueue(event.payload.sessionId, event.payload.queue);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, [setQueue]);
+
+  useEffect(() => {
+    if (!activeThreadId) {
+      return;
+    }
+    const threadId = activeThreadId;
+    let canceled = false;
+    listQueuedMessages(threadId)
+      .then((queue) => {
+        if (!canceled) {
+          setQueue(threadId, queue);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+    };
+  }, [activeThreadId, setQueue]);
+
   const removeQueuedMessage = useCallback(
     (threadId: string, messageId: string) => {
       setQueuedByThread((prev) => ({
@@ -56,16 +99,19 @@ export function useQueuedSend({
           (entry) => entry.id !== messageId,
         ),
       }));
+      void dropQueuedMessage(threadId, messageId).catch(() => {});
     },
     [],
   );
 
-  const prependQueuedMessage = useCallback((threadId: string, item: QueuedMessage) => {
-    setQueuedByThread((prev) => ({
-      ...prev,
-      [threadId]: [item, ...(prev[threadId] ?? [])],
-    }));
-  }, []);
+  const moveQueuedMessage = useCallback(
+    (threadId: string, messageId: string, index: number) => {
+      void reorderQueuedMessage(threadId, messageId, index)
+        .then((queue) => setQueue(threadId, queue))
+        .catch(() => {});
+    },
+    [setQueue],
+  );
 
   const handleSend = useCallback(
     async (text: string, images: string[] = []) => {
@@ -79,13 +125,7 @@ export function useQueuedSend({
         return;
       }
       if (isProcessing && activeThreadId) {
-        const item: QueuedMessage = {
-          id: `${Date.now()}-${Math.random().toString(36).slice(2, 8)}`,
-          text: trimmed,
-          createdAt: Date.now(),
-          images: nextImages,
-        };
-        enqueueMessage(activeThreadId, item);
+        await queueMessage(activeThreadId, trimmed, nextImages);
         clearActiveImages();
         return;
       }
@@ -109,7 +149,6 @@ export function useQueuedSend({
       activeWorkspace,
       clearActiveImages,
       connectWorkspace,
-      enqueueMessage,
       isProcessing,
       isReviewing,
       sendUserMessage,
@@ -117,52 +156,11 @@ export function useQueuedSend({
     ],
   );
 
-  useEffect(() => {
-    if (!activeThreadId || isProcessing || isReviewing) {
-      return;
-    }
-    if (flushingByThread[activeThreadId]) {
-      return;
-    }
-    const queue = queuedByThread[activeThreadId] ?? [];
-    if (queue.length === 0) {
-      return;
-    }
-    const threadId = activeThreadId;
-    const nextItem = queue[0];
-    setFlushingByThread((prev) => ({ ...prev, [threadId]: true }));
-    setQueuedByThread((prev) => ({
-      ...prev,
-      [threadId]: (prev[threadId] ?? []).slice(1),
-    }));
-    (async () => {
-      try {
-        if (nextItem.text.trim().startsWith("/review")) {
-          await startReview(nextItem.text);
-        } else {
-          await sendUserMessage(nextItem.text, nextItem.images ?? []);
-        }
-      } catch {
-        prependQueuedMessage(threadId, nextItem);
-      } finally {
-        setFlushingByThread((prev) => ({ ...prev, [threadId]: false }));
-      }
-    })();
-  }, [
-    activeThreadId,
-    flushingByThread,
-    isProcessing,
-    isReviewing,
-    prependQueuedMessage,
-    queuedByThread,
-    sendUserMessage,
-    startReview,
-  ]);
-
   return {
     queuedByThread,
     activeQueue,
     handleSend,
     removeQueuedMessage,
+    moveQueuedMessage,
   };
 }
@@ -0,0 +1,39 @@
+import { useCallback, useEffect, useState } from "react";
+import { getRecentPrompts } from "../services/tauri";
+
+/**
+ * Prompts last sent in a workspace, newest first, for recalling in the
+ * composer. `remember` adds a prompt right away; the backend records it
+ * once the message is sent.
+ */
+export function useRecentPrompts(workspaceId: string | null) {
+  const [prompts, setPrompts] = useState<string[]>([]);
+
+  useEffect(() => {
+    setPrompts([]);
+    if (!workspaceId) {
+      return;
+    }
+    let canceled = false;
+    void getRecentPrompts(workspaceId)
+      .then((entries) => {
+        if (!canceled) {
+          setPrompts(entries.map((entry) => entry.text));
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+    };
+  }, [workspaceId]);
+
+  const remember = useCallback((text: string) => {
+    const trimmed = text.trim();
+    if (!trimmed) {
+      return;
+    }
+    setPrompts((prev) => [trimmed, ...prev.filter((entry) => entry !== trimmed)]);
+  }, []);
+
+  return { prompts, remember };
+}
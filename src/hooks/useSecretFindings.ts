@@ -0,0 +1,31 @@
+import { useEffect, useState } from "react";
+import type { SecretFinding } from "../types";
+import { scanDiffForSecrets } from "../services/tauri";
+
+/**
+ * Credentials the working diff adds, rescanned whenever `refreshKey`
+ * changes (e.g. the git status totals).
+ */
+export function useSecretFindings(workspaceId: string | null, refreshKey: string) {
+  const [findings, setFindings] = useState<SecretFinding[]>([]);
+
+  useEffect(() => {
+    if (!workspaceId) {
+      setFindings([]);
+      return;
+    }
+    let cancelled = false;
+    void scanDiffForSecrets(workspaceId)
+      .catch(() => [])
+      .then((next) => {
+        if (!cancelled) {
+          setFindings(next);
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [workspaceId, refreshKey]);
+
+  return findings;
+}
@@ -0,0 +1,39 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { Snippet } from "../types";
+import { listSnippets } from "../services/tauri";
+
+/**
+ * The shared snippets library, kept current by `snippets-changed` events
+ * so edits in settings reach every composer.
+ */
+export function useSnippets() {
+  const [snippets, setSnippets] = useState<Snippet[]>([]);
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<Snippet[]>("snippets-changed", (event) => {
+      setSnippets(event.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    void listSnippets()
+      .then((initial) => {
+        if (!canceled) {
+          setSnippets(initial);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  return snippets;
+}
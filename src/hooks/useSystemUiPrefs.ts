@@ -0,0 +1,55 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { SystemUiPrefs } from "../types";
+import { getSystemUiPrefs } from "../services/tauri";
+
+const DEFAULT_PREFS: SystemUiPrefs = {
+  reducedMotion: false,
+  highContrast: false,
+  colorScheme: window.matchMedia("(prefers-color-scheme: light)").matches
+    ? "light"
+    : "dark",
+};
+
+/**
+ * OS accessibility preferences from the backend, kept current by
+ * `system-ui-prefs-changed` events and mirrored to `data-*` attributes on
+ * the root element so styles can follow them.
+ */
+export function useSystemUiPrefs() {
+  const [prefs, setPrefs] = useState<SystemUiPrefs>(DEFAULT_PREFS);
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<SystemUiPrefs>("system-ui-prefs-changed", (event) => {
+      setPrefs(event.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    void getSystemUiPrefs()
+      .then((initial) => {
+        if (!canceled) {
+          setPrefs(initial);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    const root = document.documentElement;
+    root.dataset.reducedMotion = String(prefs.reducedMotion);
+    root.dataset.highContrast = String(prefs.highContrast);
+    root.dataset.colorScheme = prefs.colorScheme;
+  }, [prefs]);
+
+  return prefs;
+}
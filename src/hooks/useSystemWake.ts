@@ -0,0 +1,27 @@
+import { useEffect, useRef } from "react";
+import { listen } from "@tauri-apps/api/event";
+import type { SystemWake } from "../types";
+
+/** Calls `onWake` with each `system-wake` event from the backend. */
+export function useSystemWake(onWake: (wake: SystemWake) => void) {
+  const onWakeRef = useRef(onWake);
+  onWakeRef.current = onWake;
+
+  useEffect(() => {
+    let canceled = false;
+    let unlisten: (() => void) | null = null;
+    void listen<SystemWake>("system-wake", (event) => {
+      onWakeRef.current(event.payload);
+    }).then((handler) => {
+      if (canceled) {
+        handler();
+      } else {
+        unlisten = handler;
+      }
+    });
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
+  }, []);
+}
@@ -11,9 +11,15 @@ import type {
   ClaudeToolStartedEvent,
   ClaudeToolProgressEvent,
   ClaudeErrorEvent,
+  ClaudeTurnInterruptedEvent,
+  ClaudeUnreadChangedEvent,
+  ClaudeQueueDispatchedEvent,
+  ClaudeBridgeReconnectedEvent,
+  ClaudeGuardrailTriggeredEvent,
   ConversationItem,
   CustomPromptOption,
   DebugEntry,
+  EventScope,
   ResultPayload,
   RateLimitSnapshot,
   RewindDiffResult,
@@ -21,6 +27,7 @@ import type {
   TurnPlan,
   TurnPlanStep,
   TurnPlanStepStatus,
+  ThinkingBlocks,
   WorkspaceInfo,
 } from "../types";
 import {
@@ -42,6 +49,7 @@ import {
   claudeRewindToMessage,
   // Registry service functions (Agent C)
   getVisibleSessions,
+  markSessionRead,
   registryArchiveSession,
   getSessionHistory,
 } from "../services/tauri";
@@ -56,6 +64,7 @@ import {
   mergeThreadItems,
   previewThreadName,
 } from "../utils/threadItems";
+import { parseBridgeError } from "../utils/bridgeErrors";
 import { expandCustomPromptText } from "../utils/customPrompts";
 import { initialState, threadReducer } from "./useThreadsReducer";
 
@@ -106,6 +115,8 @@ type UseThreadsOptions = {
   permissionMode?: "default" | "acceptEdits" | "plan" | "dontAsk";
   customPrompts?: CustomPromptOption[];
   onMessageActivity?: () => void;
+  eventScope?: EventScope;
+  thinkingBlocksByWorkspace?: Record<string, ThinkingBlocks | null | undefined>;
 };
 
 function asString(value: unknown) {
@@ -399,11 +410,17 @@ export function useThreads({
   permissionMode,
   customPrompts = [],
   onMessageActivity,
+  eventScope = "all",
+  thinkingBlocksByWorkspace = {},
 }: UseThreadsOptions) {
   const [state, dispatch] = useReducer(threadReducer, initialState);
   const loadedThreads = useRef<Record<string, boolean>>({});
   const threadActivityRef = useRef<ThreadActivityMap>(loadThreadActivity());
   const streamingMessageIds = useRef<Record<string, string>>({});
+  // Reasoning item ids for the thinking blocks streamed in the current turn.
+  const streamingThinkingIds = useRef<Record<string, string[]>>({});
+  const thinkingBlocksRef = useRef(thinkingBlocksByWorkspace);
+  thinkingBlocksRef.current = thinkingBlocksByWorkspace;
   const pendingSessionByWorkspace = useRef<Record<string, string>>({});
 
   const recordThreadActivity = useCallback(
@@ -430,10 +447,12 @@ export function useThreads({
     return state.activeThreadIdByWorkspace[activeWorkspaceId] ?? null;
   }, [activeWorkspaceId, state.activeThreadIdByWorkspace]);
 
-  const activeItems = useMemo(
-    () => (activeThreadId ? state.itemsByThread[activeThreadId] ?? [] : []),
-    [activeThreadId, state.itemsByThread],
-  );
+  const hideThinking =
+    activeWorkspaceId !== null && thinkingBlocksByWorkspace[activeWorkspaceId] === "hide";
+  const activeItems = useMemo(() => {
+    const items = activeThreadId ? state.itemsByThread[activeThreadId] ?? [] : [];
+    return hideThinking ? items.filter((item) => item.kind !== "reasoning") : items;
+  }, [activeThreadId, hideThinking, state.itemsByThread]);
 
   const refreshAccountRateLimits = useCallback(
     async (workspaceId?: string) => {
@@ -803,9 +822,39 @@ export function useThreads({
         // The payload.event is a BetaRawMessageStreamEvent which may have delta content
         const sdkEvent = event.payload.event as {
           type?: string;
-          delta?: { type?: string; text?: string };
+          delta?: { type?: string; text?: string; thinking?: string };
           content_block?: { type?: string };
         };
+        const keepThinking = thinkingBlocksRef.current[event.workspaceId] !== "discard";
+        if (
+          keepThinking
+          && sdkEvent?.type === "content_block_start"
+          && sdkEvent.content_block?.type === "thinking"
+        ) {
+          const ids = streamingThinkingIds.current[event.sessionId] ?? [];
+          streamingThinkingIds.current[event.sessionId] = [
+            ...ids,
+            `thinking-${event.sessionId}-${Date.now()}-${ids.length}`,
+          ];
+        }
+        if (
+          keepThinking
+          && sdkEvent?.type === "content_block_delta"
+          && sdkEvent.delta?.type === "thinking_delta"
+          && sdkEvent.delta.thinking
+        ) {
+          const ids = streamingThinkingIds.current[event.sessionId] ?? [];
+          if (ids.length === 0) {
+            ids.push(`thinking-${event.sessionId}-${Date.now()}-0`);
+            streamingThinkingIds.current[event.sessionId] = ids;
+          }
+          dispatch({
+            type: "appendReasoningContent",
+            threadId: event.sessionId,
+            itemId: ids[ids.length - 1],
+            delta: sdkEvent.delta.thinking,
+          });
+        }
         if (sdkEvent?.type === "message_start") {
           if (!streamingMessageIds.current[event.sessionId]) {
             const streamingId = event.payload.uuid
@@ -852,6 +901,32 @@ export function useThreads({
           (c) => (c as { type?: string }).type === "text",
         ) as { text?: unknown } | undefined;
         const text = typeof textContent?.text === "string" ? normalizeStreamingText(textContent.text) : "";
+        // Thinking blocks arrive whole here when deltas were not streamed;
+        // streamed ones are completed in place.
+        const thinkingIds = streamingThinkingIds.current[event.sessionId] ?? [];
+        delete streamingThinkingIds.current[event.sessionId];
+        if (thinkingBlocksRef.current[event.workspaceId] !== "discard") {
+          (betaMessage?.content ?? [])
+            .filter((block) => block.type === "thinking" && typeof block.thinking === "string")
+            .forEach((block, index) => {
+              const thinking = block.thinking as string;
+              if (!thinking.trim()) {
+                return;
+              }
+              dispatch({
+                type: "upsertItem",
+                threadId: event.sessionId,
+                item: {
+                  id:
+                    thinkingIds[index]
+                    ?? `thinking-${event.sessionId}-${event.payload.uuid}-${index}`,
+                  kind: "reasoning",
+                  summary: "",
+                  content: thinking,
+                },
+              });
+            });
+        }
         const itemId =
           streamingMessageIds.current[event.sessionId]
           ?? (event.payload.uuid
@@ -1017,6 +1092,21 @@ export function useThreads({
         }
         dispatch({ type: "completeRunningTools", threadId: event.sessionId });
         delete streamingMessageIds.current[event.sessionId];
+        if (event.sessionId === activeThreadId) {
+          void markSessionRead(event.sessionId).catch(() => {});
+        }
+      },
+
+      onUnreadChanged: (event: ClaudeUnreadChangedEvent) => {
+        // The open thread is read as it streams; a count for it is stale.
+        if (event.sessionId === activeThreadId) {
+          return;
+        }
+        dispatch({
+          type: "markUnread",
+          threadId: event.sessionId,
+          hasUnread: event.payload.unreadCount > 0,
+        });
       },
 
       onApprovalRequest: (request: ClaudeApprovalRequest) => {
@@ -1030,6 +1120,48 @@ export function useThreads({
         dispatch({ type: "addApproval", approval: request });
       },
 
+      onQueueDispatched: (event: ClaudeQueueDispatchedEvent) => {
+        // A queued message went out when the previous turn ended.
+        const { id, text, images } = event.payload;
+        dispatch({ type: "ensureThread", workspaceId: event.workspaceId, threadId: event.sessionId });
+        recordThreadActivity(event.workspaceId, event.sessionId);
+        dispatch({
+          type: "addUserMessage",
+          workspaceId: event.workspaceId,
+          threadId: event.sessionId,
+          text,
+          images: images ?? [],
+          messageId: id,
+        });
+        markProcessing(event.sessionId, true);
+        safeMessageActivity();
+      },
+
+      onTurnInterrupted: (event: ClaudeTurnInterruptedEvent) => {
+        onDebug?.({
+          id: `${Date.now()}-claude-turn-interrupted`,
+          timestamp: Date.now(),
+          source: "event",
+          label: "claude/turn/interrupted",
+          payload: event,
+        });
+        const { id, text, tools, inputTokens, outputTokens, interruptedAt } = event.payload;
+        dispatch({
+          type: "upsertItem",
+          threadId: event.sessionId,
+          item: { id, kind: "interrupted", text, tools, inputTokens, outputTokens, interruptedAt },
+        });
+      },
+
+      onGuardrailTriggered: (event: ClaudeGuardrailTriggeredEvent) => {
+        dispatch({ type: "ensureThread", workspaceId: event.workspaceId, threadId: event.sessionId });
+        pushThreadErrorMessage(
+          event.sessionId,
+          `Paused ${event.payload.source}: ${event.payload.limit} turns ran without your input. Send a message to continue.`,
+        );
+        safeMessageActivity();
+      },
+
       onError: (event: ClaudeErrorEvent) => {
         onDebug?.({
           id: `${Date.now()}-claude-error`,
@@ -1057,6 +1189,31 @@ export function useThreads({
         });
       },
 
+      onBridgeReconnected: (event: ClaudeBridgeReconnectedEvent) => {
+        onDebug?.({
+          id: `${Date.now()}-claude-bridge-reconnected`,
+          timestamp: Date.now(),
+          source: "event",
+          label: "claude/bridge/reconnected",
+          payload: event.payload,
+        });
+        // Turns running on the old bridge died with it.
+        const { resumed, failed } = event.payload;
+        [...resumed, ...failed].forEach((sessionId) => {
+          markProcessing(sessionId, false);
+          dispatch({ type: "setActiveTurnId", threadId: sessionId, turnId: null });
+        });
+        failed.forEach((sessionId) => {
+          pushThreadErrorMessage(
+            sessionId,
+            "Claude restarted after a crash and this session could not be reattached. Resume it to continue.",
+          );
+        });
+        if (failed.length > 0) {
+          safeMessageActivity();
+        }
+      },
+
       onBridgeStderr: (workspaceId: string, message: string) => {
         onDebug?.({
           id: `${Date.now()}-claude-bridge-stderr`,
@@ -1087,7 +1244,10 @@ export function useThreads({
     ],
   );
 
-  useClaudeEvents(claudeHandlers);
+  useClaudeEvents(
+    claudeHandlers,
+    eventScope === "workspace" ? activeWorkspace?.id ?? null : null,
+  );
 
   // Claude-native: Codex functions kept for reference but not used
   const _startThreadForWorkspace = useCallback(
@@ -1819,6 +1979,8 @@ export function useThreads({
       if (threadId) {
         void resumeClaudeSession(targetId, threadId, true);
         void loadClaudeHistory(targetId, threadId, false);
+        // New sessions have no transcript to mark yet.
+        void markSessionRead(threadId).catch(() => {});
       }
     },
     [activeWorkspaceId, loadClaudeHistory, resumeClaudeSession],
@@ -1893,9 +2055,12 @@ export function useThreads({
           label: "claude/message/send error",
           payload: error instanceof Error ? error.message : String(error),
         });
+        const { code, message } = parseBridgeError(error);
         pushThreadErrorMessage(
           sessionId,
-          error instanceof Error ? error.message : String(error),
+          code === "BRIDGE_DIED"
+            ? "Claude exited before this message was delivered. It restarts on its own; send the message again once it's back."
+            : message,
         );
         safeMessageActivity();
       }
@@ -1988,6 +2153,9 @@ export function useThreads({
         });
         // Update last agent messages for each session
         sessions.forEach((session) => {
+          if (session.unreadCount > 0) {
+            dispatch({ type: "markUnread", threadId: session.sessionId, hasUnread: true });
+          }
           if (session.preview) {
             dispatch({
               type: "setLastAgentMessage",
@@ -3,6 +3,7 @@ import { isTauri } from "@tauri-apps/api/core";
 import { check } from "@tauri-apps/plugin-updater";
 import { relaunch } from "@tauri-apps/plugin-process";
 import type { DownloadEvent, Update } from "@tauri-apps/plugin-updater";
+import { getProxyStatus } from "../services/tauri";
 import type { DebugEntry } from "../types";
 
 type UpdateStage =
@@ -46,7 +47,10 @@ export function useUpdater({ enabled = true, onDebug }: UseUpdaterOptions) {
     let update: Awaited<ReturnType<typeof check>> | null = null;
     try {
       setState({ stage: "checking" });
-      update = await check();
+      // Environment proxies are picked up by the updater itself; only the
+      // in-app setting needs passing along.
+      const proxy = await getProxyStatus().catch(() => null);
+      update = await check(proxy?.source === "settings" ? { proxy: proxy.url } : undefined);
       if (!update) {
         setState({ stage: "idle" });
         return;
@@ -1,15 +1,19 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
 import type { DebugEntry } from "../types";
 import type { WorkspaceInfo, WorkspaceSettings } from "../types";
-import { ask } from "@tauri-apps/plugin-dialog";
 import {
   addWorkspace as addWorkspaceService,
   addWorktree as addWorktreeService,
   connectWorkspace as connectWorkspaceService,
+  isStateReady,
   listWorkspaces,
+  pauseWorkspaceAutomation,
   pickWorkspacePath,
   removeWorkspace as removeWorkspaceService,
   removeWorktree as removeWorktreeService,
+  requestConfirmation,
+  resumeWorkspaceAutomation,
   updateWorkspaceCodexBin as updateWorkspaceCodexBinService,
   updateWorkspaceSettings as updateWorkspaceSettingsService,
 } from "../services/tauri";
@@ -28,6 +32,10 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
   const refreshWorkspaces = useCallback(async () => {
     try {
       const entries = await listWorkspaces();
+      // An empty list may be the placeholder sent while loading.
+      if (entries.length === 0 && !(await isStateReady())) {
+        return entries;
+      }
       setWorkspaces(entries);
       setActiveWorkspaceId((prev) => {
         if (!prev) {
@@ -45,7 +53,24 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
   }, []);
 
   useEffect(() => {
-    void refreshWorkspaces();
+    let unlisten: (() => void) | null = null;
+    let canceled = false;
+    // Listen before the first load so a `state/ready` that lands in between
+    // is not missed.
+    void listen("state/ready", () => {
+      void refreshWorkspaces();
+    }).then((stop) => {
+      if (canceled) {
+        stop();
+        return;
+      }
+      unlisten = stop;
+      void refreshWorkspaces();
+    });
+    return () => {
+      canceled = true;
+      unlisten?.();
+    };
   }, [refreshWorkspaces]);
 
   const activeWorkspace = useMemo(
@@ -223,35 +248,47 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
     }
   }
 
+  async function setWorkspaceAutomationPaused(workspaceId: string, paused: boolean) {
+    onDebug?.({
+      id: `${Date.now()}-client-workspace-automation`,
+      timestamp: Date.now(),
+      source: "client",
+      label: paused ? "workspace/automation pause" : "workspace/automation resume",
+      payload: { workspaceId },
+    });
+    try {
+      const updated = paused
+        ? await pauseWorkspaceAutomation(workspaceId)
+        : await resumeWorkspaceAutomation(workspaceId);
+      setWorkspaces((prev) =>
+        prev.map((entry) => (entry.id === workspaceId ? updated : entry)),
+      );
+      return updated;
+    } catch (error) {
+      onDebug?.({
+        id: `${Date.now()}-client-workspace-automation-error`,
+        timestamp: Date.now(),
+        source: "error",
+        label: "workspace/automation error",
+        payload: error instanceof Error ? error.message : String(error),
+      });
+      throw error;
+    }
+  }
+
   async function removeWorkspace(workspaceId: string) {
-    const workspace = workspaces.find((entry) => entry.id === workspaceId);
-    const workspaceName = workspace?.name || "this workspace";
-    const worktreeCount = workspaces.filter(
-      (entry) => entry.parentId === workspaceId,
-    ).length;
     const childIds = new Set(
       workspaces
         .filter((entry) => entry.parentId === workspaceId)
         .map((entry) => entry.id),
     );
-    const detail =
-      worktreeCount > 0
-        ? `\n\nThis will also delete ${worktreeCount} worktree${
-            worktreeCount === 1 ? "" : "s"
-          } on disk.`
-        : "";
 
-    const confirmed = await ask(
-      `Are you sure you want to delete "${workspaceName}"?\n\nThis will remove the workspace from Claude Monitor.${detail}`,
-      {
-        title: "Delete Workspace",
-        kind: "warning",
-        okLabel: "Delete",
-        cancelLabel: "Cancel",
-      },
+    // The backend shows the native confirmation and issues the token.
+    const confirmation = await requestConfirmation(
+      "remove_workspace",
+      workspaceId,
     );
-
-    if (!confirmed) {
+    if (!confirmation) {
       return;
     }
 
@@ -263,7 +300,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
       payload: { workspaceId },
     });
     try {
-      await removeWorkspaceService(workspaceId);
+      await removeWorkspaceService(workspaceId, confirmation);
       setWorkspaces((prev) =>
         prev.filter(
           (entry) =>
@@ -286,20 +323,11 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
   }
 
   async function removeWorktree(workspaceId: string) {
-    const workspace = workspaces.find((entry) => entry.id === workspaceId);
-    const workspaceName = workspace?.name || "this worktree";
-
-    const confirmed = await ask(
-      `Are you sure you want to delete "${workspaceName}"?\n\nThis will close the agent, remove its worktree, and delete it from Claude Monitor.`,
-      {
-        title: "Delete Worktree",
-        kind: "warning",
-        okLabel: "Delete",
-        cancelLabel: "Cancel",
-      },
+    const confirmation = await requestConfirmation(
+      "remove_worktree",
+      workspaceId,
     );
-
-    if (!confirmed) {
+    if (!confirmation) {
       return;
     }
 
@@ -311,7 +339,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
       payload: { workspaceId },
     });
     try {
-      await removeWorktreeService(workspaceId);
+      await removeWorktreeService(workspaceId, confirmation);
       setWorkspaces((prev) => prev.filter((entry) => entry.id !== workspaceId));
       setActiveWorkspaceId((prev) => (prev === workspaceId ? null : prev));
     } catch (error) {
@@ -337,6 +365,7 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
     markWorkspaceConnected,
     updateWorkspaceSettings,
     updateWorkspaceCodexBin,
+    setWorkspaceAutomationPaused,
     removeWorkspace,
     removeWorktree,
     hasLoaded,
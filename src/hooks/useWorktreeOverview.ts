@@ -0,0 +1,82 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import type { WorkspaceInfo, WorktreeOverview } from "../types";
+import { getWorktreeOverview } from "../services/tauri";
+
+type WorktreeOverviewState = {
+  overview: WorktreeOverview | null;
+  isLoading: boolean;
+  error: string | null;
+};
+
+const emptyState: WorktreeOverviewState = {
+  overview: null,
+  isLoading: false,
+  error: null,
+};
+
+export function useWorktreeOverview(
+  activeWorkspace: WorkspaceInfo | null,
+  enabled: boolean,
+) {
+  const [state, setState] = useState<WorktreeOverviewState>(emptyState);
+  const requestIdRef = useRef(0);
+  const workspaceIdRef = useRef<string | null>(activeWorkspace?.id ?? null);
+  const isWorktree = activeWorkspace?.kind === "worktree";
+
+  const refresh = useCallback(async () => {
+    if (!activeWorkspace || !isWorktree) {
+      setState(emptyState);
+      return;
+    }
+    const workspaceId = activeWorkspace.id;
+    const requestId = requestIdRef.current + 1;
+    requestIdRef.current = requestId;
+    setState((prev) => ({ ...prev, isLoading: true, error: null }));
+    try {
+      const overview = await getWorktreeOverview(workspaceId);
+      if (
+        requestIdRef.current !== requestId ||
+        workspaceIdRef.current !== workspaceId
+      ) {
+        return;
+      }
+      setState({ overview, isLoading: false, error: null });
+    } catch (error) {
+      console.error("Failed to load worktree overview", error);
+      if (
+        requestIdRef.current !== requestId ||
+        workspaceIdRef.current !== workspaceId
+      ) {
+        return;
+      }
+      setState({
+        overview: null,
+        isLoading: false,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }, [activeWorkspace, isWorktree]);
+
+  useEffect(() => {
+    const workspaceId = activeWorkspace?.id ?? null;
+    if (workspaceIdRef.current !== workspaceId) {
+      workspaceIdRef.current = workspaceId;
+      requestIdRef.current += 1;
+      setState(emptyState);
+    }
+  }, [activeWorkspace?.id]);
+
+  useEffect(() => {
+    if (!enabled) {
+      return;
+    }
+    void refresh();
+  }, [enabled, refresh]);
+
+  return {
+    overview: state.overview,
+    isLoading: state.isLoading,
+    error: state.error,
+    refresh,
+  };
+}
@@ -2,6 +2,13 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
 
+// Until appearance settings load, follow the OS theme without a flash.
+document.documentElement.dataset.theme = window.matchMedia(
+  "(prefers-color-scheme: light)",
+).matches
+  ? "light"
+  : "dark";
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <App />
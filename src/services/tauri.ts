@@ -1,11 +1,64 @@
-import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
+import { commands } from "./bindings";
 import type {
+  AboutInfo,
   AppSettings,
+  AppearanceSettings,
+  AttachedMessage,
+  AuditEntry,
+  AutoApproveScope,
+  AutoApproveWindow,
+  BridgeHealth,
+  CaBundleInfo,
+  Changelog,
   ClaudeDoctorResult,
+  CommandMetricsReport,
+  CrashReport,
+  DeepLink,
+  Digest,
+  EditorKind,
+  FocusStatus,
+  GlossaryTerm,
+  HighlightedFile,
+  ImageAttachment,
+  LocaleInfo,
+  LanShareInfo,
+  ModelPricing,
+  PendingBridgeRequest,
+  PowerStatus,
+  ProcessPriority,
+  PromptHistoryEntry,
+  ProxyStatus,
+  ProxyTestResult,
+  QueuedMessage,
+  ReleaseResult,
+  ReplayExport,
+  ReplayStatus,
   CodexDoctorResult,
+  SessionBundleExport,
+  SessionBundleImport,
   SessionEntry,
+  SessionFile,
+  SlackMirror,
+  SlackStatus,
+  Snippet,
+  StaleWorktreeReport,
+  SystemUiPrefs,
   ConversationItem,
+  TeamPromptsStatus,
+  Ticket,
+  TicketStatus,
+  TimeReport,
+  TokenCount,
+  UsageExport,
+  UsageRange,
+  UsageRow,
+  VisibleSession,
+  WorkflowFile,
+  WorkflowRunInfo,
+  WorktreeCleanup,
+  WorktreeOverview,
+  WorkspaceGlossary,
   WorkspaceInfo,
   WorkspaceSettings,
 } from "../types";
@@ -14,7 +67,10 @@ import type {
   GitFileStatus,
   GitHubIssuesResponse,
   GitLogResponse,
+  LargeObject,
+  RepoStats,
   ReviewTarget,
+  SecretFinding,
 } from "../types";
 
 export async function pickWorkspacePath(): Promise<string | null> {
@@ -42,51 +98,89 @@ export async function pickImageFiles(): Promise<string[]> {
 }
 
 export async function listWorkspaces(): Promise<WorkspaceInfo[]> {
-  return invoke<WorkspaceInfo[]>("list_workspaces");
+  return commands.listWorkspaces();
+}
+
+/**
+ * Whether workspaces have finished loading; until then `listWorkspaces`
+ * returns an empty placeholder and `state/ready` is emitted when done.
+ */
+export async function isStateReady(): Promise<boolean> {
+  return commands.isStateReady();
 }
 
 export async function addWorkspace(
   path: string,
   codex_bin: string | null,
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("add_workspace", { path, codex_bin });
+  return commands.addWorkspace(path, codex_bin ?? null);
 }
 
 export async function addWorktree(
   parentId: string,
   branch: string,
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("add_worktree", { parentId, branch });
+  return commands.addWorktree(parentId, branch);
 }
 
 export async function updateWorkspaceSettings(
   id: string,
   settings: WorkspaceSettings,
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("update_workspace_settings", { id, settings });
+  return commands.updateWorkspaceSettings(id, settings);
+}
+
+/**
+ * Hold a workspace's automation: running workflows are cancelled, queued
+ * messages wait and auto-approve windows stop answering.
+ */
+export async function pauseWorkspaceAutomation(workspaceId: string): Promise<WorkspaceInfo> {
+  return commands.pauseWorkspaceAutomation(workspaceId);
+}
+
+export async function resumeWorkspaceAutomation(workspaceId: string): Promise<WorkspaceInfo> {
+  return commands.resumeWorkspaceAutomation(workspaceId);
 }
 
 export async function updateWorkspaceCodexBin(
   id: string,
   codex_bin: string | null,
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("update_workspace_codex_bin", { id, codex_bin });
+  return commands.updateWorkspaceCodexBin(id, codex_bin ?? null);
+}
+
+/**
+ * Ask the user in a native dialog to allow a destructive action.
+ * Resolves to a single-use token for the matching command, or null if declined.
+ * Tauri command: request_confirmation
+ */
+export async function requestConfirmation(
+  action: string,
+  target: string,
+): Promise<string | null> {
+  return commands.requestConfirmation(action, target);
 }
 
-export async function removeWorkspace(id: string): Promise<void> {
-  return invoke("remove_workspace", { id });
+export async function removeWorkspace(
+  id: string,
+  confirmation: string,
+): Promise<void> {
+  await commands.removeWorkspace(id, confirmation ?? null);
 }
 
-export async function removeWorktree(id: string): Promise<void> {
-  return invoke("remove_worktree", { id });
+export async function removeWorktree(
+  id: string,
+  confirmation: string,
+): Promise<void> {
+  await commands.removeWorktree(id, confirmation ?? null);
 }
 
 export async function connectWorkspace(id: string): Promise<void> {
-  return invoke("connect_workspace", { id });
+  await commands.connectWorkspace(id);
 }
 
 export async function startThread(workspaceId: string) {
-  return invoke<any>("start_thread", { workspaceId });
+  return commands.startThread(workspaceId) as Promise<any>;
 }
 
 export async function sendUserMessage(
@@ -100,15 +194,15 @@ export async function sendUserMessage(
     images?: string[];
   },
 ) {
-  return invoke("send_user_message", {
+  return commands.sendUserMessage(
     workspaceId,
     threadId,
     text,
-    model: options?.model ?? null,
-    effort: options?.effort ?? null,
-    accessMode: options?.accessMode ?? null,
-    images: options?.images ?? null,
-  });
+    options?.model ?? null,
+    options?.effort ?? null,
+    options?.accessMode ?? null,
+    options?.images ?? null,
+  );
 }
 
 export async function interruptTurn(
@@ -116,7 +210,7 @@ export async function interruptTurn(
   threadId: string,
   turnId: string,
 ) {
-  return invoke("turn_interrupt", { workspaceId, threadId, turnId });
+  return commands.turnInterrupt(workspaceId, threadId, turnId);
 }
 
 export async function startReview(
@@ -125,11 +219,7 @@ export async function startReview(
   target: ReviewTarget,
   delivery?: "inline" | "detached",
 ) {
-  const payload: Record<string, unknown> = { workspaceId, threadId, target };
-  if (delivery) {
-    payload.delivery = delivery;
-  }
-  return invoke("start_review", payload);
+  return commands.startReview(workspaceId, threadId, target, delivery ?? null);
 }
 
 export async function respondToServerRequest(
@@ -137,11 +227,7 @@ export async function respondToServerRequest(
   requestId: number,
   decision: "accept" | "decline",
 ) {
-  return invoke("respond_to_server_request", {
-    workspaceId,
-    requestId,
-    result: { decision },
-  });
+  return commands.respondToServerRequest(workspaceId, requestId, { decision });
 }
 
 export async function getGitStatus(workspace_id: string): Promise<{
@@ -150,76 +236,234 @@ export async function getGitStatus(workspace_id: string): Promise<{
   totalAdditions: number;
   totalDeletions: number;
 }> {
-  return invoke("get_git_status", { workspaceId: workspace_id });
+  return commands.getGitStatus(workspace_id) as ReturnType<typeof getGitStatus>;
 }
 
 export async function getGitDiffs(
   workspace_id: string,
+  options?: { highlight?: boolean; wordDiff?: boolean },
 ): Promise<GitFileDiff[]> {
-  return invoke("get_git_diffs", { workspaceId: workspace_id });
+  return commands.getGitDiffs(
+    workspace_id,
+    options?.highlight ?? false,
+    options?.wordDiff ?? false,
+  );
+}
+
+export async function getWorktreeOverview(worktreeId: string): Promise<WorktreeOverview> {
+  return commands.getWorktreeOverview(worktreeId);
+}
+
+export async function listStaleWorktrees(
+  inactiveDays?: number,
+): Promise<StaleWorktreeReport> {
+  return commands.listStaleWorktrees(inactiveDays ?? null);
+}
+
+/**
+ * Remove the given worktrees after a native confirmation, and with
+ * `deleteBranches` their merged branches. Returns null when the user
+ * declines.
+ */
+export async function cleanUpWorktrees(
+  worktreeIds: string[],
+  deleteBranches: boolean,
+): Promise<WorktreeCleanup[] | null> {
+  const target = `${deleteBranches ? "branches:" : ""}${worktreeIds.join(",")}`;
+  const confirmation = await requestConfirmation("clean_up_worktrees", target);
+  if (!confirmation) {
+    return null;
+  }
+  return commands.cleanUpWorktrees(worktreeIds, deleteBranches, confirmation ?? null);
 }
 
 export async function getGitLog(
   workspace_id: string,
   limit = 40,
 ): Promise<GitLogResponse> {
-  return invoke("get_git_log", { workspaceId: workspace_id, limit });
+  return commands.getGitLog(workspace_id, limit ?? null);
+}
+
+// Commits since a tag, branch or sha grouped into release notes, each with
+// the sessions that produced it
+export async function generateChangelog(
+  workspaceId: string,
+  sinceRef: string,
+): Promise<Changelog> {
+  return commands.generateChangelog(workspaceId, sinceRef);
+}
+
+export async function createRelease(
+  workspaceId: string,
+  version: string,
+  notes: string | null,
+  dryRun: boolean,
+): Promise<ReleaseResult | null> {
+  let confirmation: string | null = null;
+  if (!dryRun) {
+    confirmation = await requestConfirmation(
+      "create_release",
+      `${workspaceId}:${version}`,
+    );
+    if (!confirmation) {
+      return null;
+    }
+  }
+  return commands.createRelease(
+    workspaceId,
+    version,
+    notes ?? null,
+    dryRun ?? null,
+    confirmation,
+  );
 }
 
 export async function getGitRemote(workspace_id: string): Promise<string | null> {
-  return invoke("get_git_remote", { workspaceId: workspace_id });
+  return commands.getGitRemote(workspace_id);
 }
 
 export async function getGitHubIssues(
   workspace_id: string,
 ): Promise<GitHubIssuesResponse> {
-  return invoke("get_github_issues", { workspaceId: workspace_id });
+  return commands.getGithubIssues(workspace_id);
 }
 
 export async function getModelList(workspaceId: string) {
-  return invoke<any>("model_list", { workspaceId });
+  return commands.modelList(workspaceId) as Promise<any>;
 }
 
 export async function getAccountRateLimits(workspaceId: string) {
-  return invoke<any>("account_rate_limits", { workspaceId });
+  return commands.accountRateLimits(workspaceId) as Promise<any>;
 }
 
 export async function getSkillsList(workspaceId: string) {
-  return invoke<any>("skills_list", { workspaceId });
+  return commands.skillsList(workspaceId) as Promise<any>;
 }
 
 export async function getPromptsList(workspaceId: string) {
-  return invoke<any>("prompts_list", { workspaceId });
+  return commands.promptsList(workspaceId);
+}
+
+/** Past prompts containing every word of `query`, newest first. */
+export async function searchPromptHistory(
+  query: string,
+  workspaceId?: string | null,
+  limit?: number,
+): Promise<PromptHistoryEntry[]> {
+  return commands.searchPromptHistory(query, workspaceId ?? null, limit ?? null);
+}
+
+export async function getRecentPrompts(
+  workspaceId: string,
+  limit?: number,
+): Promise<PromptHistoryEntry[]> {
+  return commands.getRecentPrompts(workspaceId, limit ?? null);
+}
+
+export async function listSnippets(): Promise<Snippet[]> {
+  return commands.listSnippets();
+}
+
+export async function createSnippet(
+  name: string,
+  content: string,
+  description?: string | null,
+): Promise<Snippet> {
+  return commands.createSnippet(name, content, description ?? null);
+}
+
+export async function updateSnippet(
+  id: string,
+  changes: { name?: string; content?: string; description?: string },
+): Promise<Snippet> {
+  return commands.updateSnippet(
+    id,
+    changes.name ?? null,
+    changes.content ?? null,
+    changes.description ?? null,
+  );
+}
+
+export async function deleteSnippet(id: string): Promise<void> {
+  await commands.deleteSnippet(id);
+}
+
+// Snippet text with its includes and variables filled in
+export async function insertSnippet(
+  name: string,
+  workspaceId?: string | null,
+): Promise<string> {
+  return commands.insertSnippet(name, workspaceId ?? null);
+}
+
+export async function getGlossary(workspaceId: string): Promise<WorkspaceGlossary> {
+  return commands.getGlossary(workspaceId);
+}
+
+// Adds a term, or updates the one with `id`
+export async function upsertGlossaryTerm(
+  workspaceId: string,
+  term: string,
+  definition: string,
+  id?: string | null,
+): Promise<GlossaryTerm> {
+  return commands.upsertGlossaryTerm(workspaceId, term, definition, id ?? null);
+}
+
+export async function deleteGlossaryTerm(workspaceId: string, id: string): Promise<void> {
+  await commands.deleteGlossaryTerm(workspaceId, id);
+}
+
+export async function setGlossaryEnabled(
+  workspaceId: string,
+  enabled: boolean,
+): Promise<void> {
+  await commands.setGlossaryEnabled(workspaceId, enabled);
 }
 
 export async function getAppSettings(): Promise<AppSettings> {
-  return invoke<AppSettings>("get_app_settings");
+  return commands.getAppSettings();
 }
 
 export async function updateAppSettings(settings: AppSettings): Promise<AppSettings> {
-  return invoke<AppSettings>("update_app_settings", { settings });
+  return commands.updateAppSettings(settings);
+}
+
+export async function getDoNotDisturb(): Promise<FocusStatus> {
+  return commands.getDoNotDisturb();
+}
+
+/**
+ * Turn do-not-disturb on or off. `minutes` bounds the focus period; without
+ * it, it lasts until turned off. Permission requests are held meanwhile.
+ */
+export async function setDoNotDisturb(
+  enabled: boolean,
+  minutes?: number,
+): Promise<FocusStatus> {
+  return commands.setDoNotDisturb(enabled, minutes ?? null);
 }
 
 export async function runCodexDoctor(
   codexBin: string | null,
 ): Promise<CodexDoctorResult> {
-  return invoke<CodexDoctorResult>("codex_doctor", { codexBin });
+  return commands.codexDoctor(codexBin ?? null) as Promise<CodexDoctorResult>;
 }
 
 export async function getWorkspaceFiles(workspaceId: string) {
-  return invoke<string[]>("list_workspace_files", { workspaceId });
+  return commands.listWorkspaceFiles(workspaceId);
 }
 
 export async function listGitBranches(workspaceId: string) {
-  return invoke<any>("list_git_branches", { workspaceId });
+  return commands.listGitBranches(workspaceId) as Promise<any>;
 }
 
 export async function checkoutGitBranch(workspaceId: string, name: string) {
-  return invoke("checkout_git_branch", { workspaceId, name });
+  return commands.checkoutGitBranch(workspaceId, name);
 }
 
 export async function createGitBranch(workspaceId: string, name: string) {
-  return invoke("create_git_branch", { workspaceId, name });
+  return commands.createGitBranch(workspaceId, name);
 }
 
 export async function listThreads(
@@ -227,15 +471,15 @@ export async function listThreads(
   cursor?: string | null,
   limit?: number | null,
 ) {
-  return invoke<any>("list_threads", { workspaceId, cursor, limit });
+  return commands.listThreads(workspaceId, cursor ?? null, limit ?? null) as Promise<any>;
 }
 
 export async function resumeThread(workspaceId: string, threadId: string) {
-  return invoke<any>("resume_thread", { workspaceId, threadId });
+  return commands.resumeThread(workspaceId, threadId) as Promise<any>;
 }
 
 export async function archiveThread(workspaceId: string, threadId: string) {
-  return invoke<any>("archive_thread", { workspaceId, threadId });
+  return commands.archiveThread(workspaceId, threadId) as Promise<any>;
 }
 
 // ============================================================================
@@ -252,12 +496,16 @@ export async function claudeStartSession(
   cwd: string,
   options?: { model?: string; permissionMode?: string },
 ) {
-  return invoke<{ result?: { sessionId: string } }>("claude_start_session", {
+  return commands.claudeStartSession(
     workspaceId,
     cwd,
-    model: options?.model ?? null,
-    permissionMode: options?.permissionMode ?? null,
-  });
+    options?.model ?? null,
+    options?.permissionMode ?? null,
+    null,
+    null,
+    null,
+    null,
+  ) as Promise<{ result?: { sessionId: string } }>;
 }
 
 /**
@@ -268,10 +516,7 @@ export async function claudeResumeSession(
   workspaceId: string,
   sessionId: string,
 ) {
-  return invoke<unknown>("claude_resume_session", {
-    workspaceId,
-    sessionId,
-  });
+  return commands.claudeResumeSession(workspaceId, sessionId);
 }
 
 /**
@@ -285,13 +530,80 @@ export async function claudeSendMessage(
   images?: string[],
   messageId?: string,
 ) {
-  return invoke("claude_send_message", {
+  return commands.claudeSendMessage(
     sessionId,
     workspaceId,
     message,
-    images: images ?? null,
-    messageId: messageId ?? null,
-  });
+    images ?? null,
+    messageId ?? null,
+  );
+}
+
+/**
+ * Queue a message for the session's next turn. Sent right away if the
+ * session is idle; otherwise sent when the turns ahead of it end.
+ */
+export async function queueMessage(
+  sessionId: string,
+  text: string,
+  images?: string[],
+): Promise<QueuedMessage> {
+  return commands.queueMessage(sessionId, text, images ?? null);
+}
+
+export async function listQueuedMessages(sessionId: string): Promise<QueuedMessage[]> {
+  return commands.listQueuedMessages(sessionId);
+}
+
+export async function reorderQueuedMessage(
+  sessionId: string,
+  messageId: string,
+  index: number,
+): Promise<QueuedMessage[]> {
+  return commands.reorderQueuedMessage(sessionId, messageId, index);
+}
+
+/**
+ * Remove a message that hasn't been sent. Resolves to `null` if it already
+ * went out.
+ */
+export async function dropQueuedMessage(
+  sessionId: string,
+  messageId: string,
+): Promise<QueuedMessage | null> {
+  return commands.dropQueuedMessage(sessionId, messageId);
+}
+
+/**
+ * Workflow definitions saved as JSON or YAML under `.claude/workflows` in the
+ * workspace. Files that fail validation carry an `error` instead.
+ */
+export async function listWorkflows(workspaceId: string): Promise<WorkflowFile[]> {
+  return commands.listWorkflows(workspaceId);
+}
+
+/**
+ * Run a workflow (JSON text) against a session. Progress arrives on the
+ * `workflow-step` and `workflow-status` events. Confirmed natively first,
+ * since `run` steps execute commands; returns null when the user declines.
+ */
+export async function startWorkflow(
+  sessionId: string,
+  definition: string,
+): Promise<WorkflowRunInfo | null> {
+  const confirmation = await requestConfirmation("run_workflow", definition);
+  if (!confirmation) {
+    return null;
+  }
+  return commands.startWorkflow(sessionId, definition, confirmation);
+}
+
+export async function cancelWorkflow(runId: string): Promise<WorkflowRunInfo> {
+  return commands.cancelWorkflow(runId);
+}
+
+export async function listWorkflowRuns(): Promise<WorkflowRunInfo[]> {
+  return commands.listWorkflowRuns();
 }
 
 /**
@@ -299,7 +611,7 @@ export async function claudeSendMessage(
  * Tauri command: claude_interrupt
  */
 export async function claudeInterrupt(sessionId: string) {
-  return invoke("claude_interrupt", { sessionId });
+  return commands.claudeInterrupt(sessionId);
 }
 
 /**
@@ -312,12 +624,48 @@ export async function claudeRespondPermission(
   decision: "allow" | "deny",
   message?: string,
 ) {
-  return invoke("claude_respond_permission", {
-    sessionId,
-    toolUseId,
-    decision,
-    message: message ?? null,
-  });
+  return commands.claudeRespondPermission(sessionId, toolUseId, decision, message ?? null);
+}
+
+/**
+ * Auto-accept a session's permission requests within `scope` for `duration`
+ * seconds. Reverts by itself; each decision goes to the audit trail.
+ */
+export async function enableAutoApprove(
+  sessionId: string,
+  duration: number,
+  scope: AutoApproveScope,
+): Promise<AutoApproveWindow> {
+  return commands.enableAutoApprove(sessionId, duration, scope);
+}
+
+export async function disableAutoApprove(sessionId: string): Promise<void> {
+  await commands.disableAutoApprove(sessionId);
+}
+
+export async function getAutoApprove(sessionId: string): Promise<AutoApproveWindow | null> {
+  return commands.getAutoApprove(sessionId);
+}
+
+/**
+ * Audit trail of decisions taken without a prompt, newest first.
+ */
+export async function getAuditLog(
+  sessionId?: string,
+  limit?: number,
+): Promise<AuditEntry[]> {
+  return commands.getAuditLog(sessionId ?? null, limit ?? null);
+}
+
+/**
+ * Change the CPU priority of a running session and the tools it runs.
+ * Tauri command: set_session_priority
+ */
+export async function setSessionPriority(
+  sessionId: string,
+  level: ProcessPriority,
+) {
+  return commands.setSessionPriority(sessionId, level);
 }
 
 /**
@@ -325,7 +673,23 @@ export async function claudeRespondPermission(
  * Tauri command: claude_close_session
  */
 export async function claudeCloseSession(sessionId: string) {
-  return invoke("claude_close_session", { sessionId });
+  return commands.claudeCloseSession(sessionId);
+}
+
+/**
+ * Bridge requests still waiting for a reply.
+ * Tauri command: claude_list_requests
+ */
+export async function claudeListRequests(): Promise<PendingBridgeRequest[]> {
+  return commands.claudeListRequests();
+}
+
+/**
+ * Stop waiting for a bridge request; its command fails with REQUEST_CANCELED.
+ * Tauri command: claude_cancel_request
+ */
+export async function claudeCancelRequest(requestId: number): Promise<boolean> {
+  return commands.claudeCancelRequest(requestId);
 }
 
 /**
@@ -342,8 +706,17 @@ export async function claudeRewindToMessage(
   filesChanged?: string[];
   insertions?: number;
   deletions?: number;
-}> {
-  return invoke("claude_rewind_files", { sessionId, userMessageId: messageId });
+} | null> {
+  const confirmation = await requestConfirmation("rewind_files", sessionId);
+  if (!confirmation) {
+    return null;
+  }
+  return commands.claudeRewindFiles(
+    sessionId,
+    messageId,
+    null,
+    confirmation ?? null,
+  ) as ReturnType<typeof claudeRewindToMessage>;
 }
 
 /**
@@ -351,9 +724,9 @@ export async function claudeRewindToMessage(
  * Tauri command: claude_list_models
  */
 export async function claudeListModels(sessionId?: string) {
-  return invoke<{ result?: { models: unknown[] } }>("claude_list_models", {
-    sessionId: sessionId ?? null,
-  });
+  return commands.claudeListModels(
+    sessionId ?? null,
+  ) as Promise<{ result?: { models: unknown[] } }>;
 }
 
 /**
@@ -363,9 +736,7 @@ export async function claudeListModels(sessionId?: string) {
 export async function runClaudeDoctor(
   claudeCodeBin: string | null,
 ): Promise<ClaudeDoctorResult> {
-  return invoke<ClaudeDoctorResult>("claude_doctor", {
-    claudeCodeBin,
-  });
+  return commands.claudeDoctor(claudeCodeBin ?? null) as Promise<ClaudeDoctorResult>;
 }
 
 // Legacy aliases for compatibility during transition
@@ -384,8 +755,32 @@ export const respondToPermission = claudeRespondPermission;
  */
 export async function getVisibleSessions(
   workspaceId: string,
-): Promise<SessionEntry[]> {
-  return invoke<SessionEntry[]>("get_visible_sessions", { workspaceId });
+  metadata?: Record<string, string>,
+): Promise<VisibleSession[]> {
+  return commands.getVisibleSessions(workspaceId, metadata ?? null);
+}
+
+/**
+ * Set a metadata field on a session; an empty value removes it.
+ * Returns the session's metadata after the change.
+ */
+export async function setSessionMetadata(
+  sessionId: string,
+  key: string,
+  value: string | null,
+): Promise<Record<string, string>> {
+  return commands.setSessionMetadata(sessionId, key, value ?? null);
+}
+
+/**
+ * Mark a session read up to a message (the newest one if omitted).
+ * Returns the replies still unread.
+ */
+export async function markSessionRead(
+  sessionId: string,
+  messageId?: string,
+): Promise<number> {
+  return commands.markSessionRead(sessionId, messageId ?? null);
 }
 
 /**
@@ -394,7 +789,7 @@ export async function getVisibleSessions(
 export async function scanAvailableSessions(
   workspaceId: string,
 ): Promise<SessionEntry[]> {
-  return invoke<SessionEntry[]>("scan_available_sessions", { workspaceId });
+  return commands.scanAvailableSessions(workspaceId);
 }
 
 /**
@@ -405,7 +800,7 @@ export async function importSessions(
   sessionIds: string[],
   sessionsData: SessionEntry[],
 ): Promise<void> {
-  return invoke("import_sessions", { workspaceId, sessionIds, sessionsData });
+  await commands.importSessions(workspaceId, sessionIds, sessionsData);
 }
 
 /**
@@ -415,7 +810,7 @@ export async function registryArchiveSession(
   workspaceId: string,
   sessionId: string,
 ): Promise<void> {
-  return invoke("registry_archive_session", { workspaceId, sessionId });
+  await commands.registryArchiveSession(workspaceId, sessionId);
 }
 
 /**
@@ -425,7 +820,7 @@ export async function registerSession(
   workspaceId: string,
   session: SessionEntry,
 ): Promise<void> {
-  return invoke("register_session", { workspaceId, session });
+  await commands.registerSession(workspaceId, session);
 }
 
 /**
@@ -435,7 +830,7 @@ export async function updateSessionActivity(
   sessionId: string,
   preview?: string,
 ): Promise<void> {
-  return invoke("update_session_activity", { sessionId, preview: preview ?? null });
+  await commands.updateSessionActivity(sessionId, preview ?? null);
 }
 
 export async function getSessionHistory(sessionId: string): Promise<{
@@ -443,7 +838,7 @@ export async function getSessionHistory(sessionId: string): Promise<{
   preview: string | null;
   lastActivity: number;
 }> {
-  return invoke("get_session_history", { sessionId });
+  return commands.getSessionHistory(sessionId);
 }
 
 /**
@@ -452,7 +847,7 @@ export async function getSessionHistory(sessionId: string): Promise<{
 export async function getArchivedSessions(
   workspaceId: string,
 ): Promise<SessionEntry[]> {
-  return invoke<SessionEntry[]>("get_archived_sessions", { workspaceId });
+  return commands.getArchivedSessions(workspaceId);
 }
 
 /**
@@ -462,5 +857,519 @@ export async function registryUnarchiveSession(
   workspaceId: string,
   sessionId: string,
 ): Promise<void> {
-  return invoke("registry_unarchive_session", { workspaceId, sessionId });
+  await commands.registryUnarchiveSession(workspaceId, sessionId);
+}
+
+/**
+ * Write a session's transcript, diff and metadata to one shareable file.
+ */
+export async function exportSessionBundle(
+  sessionId: string,
+  options: { path?: string; redact?: boolean; includeDiff?: boolean } = {},
+): Promise<SessionBundleExport> {
+  return commands.exportSessionBundle(
+    sessionId,
+    options.path ?? null,
+    options.redact ?? null,
+    options.includeDiff ?? null,
+  );
+}
+
+/**
+ * Write the events recorded for a session this run, with their timing,
+ * to a replayable JSON file.
+ */
+export async function exportSessionReplay(
+  sessionId: string,
+  path?: string,
+): Promise<ReplayExport> {
+  return commands.exportSessionReplay(sessionId, path ?? null);
+}
+
+/**
+ * Re-emit an exported session's events at `speed` times their original pace.
+ */
+export async function replaySession(
+  path: string,
+  speed?: number,
+): Promise<ReplayStatus> {
+  return commands.replaySession(path, speed ?? null);
+}
+
+export async function stopReplay(): Promise<void> {
+  await commands.stopReplay();
+}
+
+/**
+ * Load a session bundle exported by a teammate into a workspace.
+ */
+export async function importSessionBundle(
+  path: string,
+  workspaceId: string,
+): Promise<SessionBundleImport> {
+  return commands.importSessionBundle(path, workspaceId);
+}
+
+/**
+ * Stream a session's events, read-only, to viewers on the local network.
+ */
+export async function startLanShare(sessionId: string): Promise<LanShareInfo> {
+  return commands.startLanShare(sessionId);
+}
+
+export async function stopLanShare(sessionId: string): Promise<void> {
+  await commands.stopLanShare(sessionId);
+}
+
+export async function listLanShares(): Promise<LanShareInfo[]> {
+  return commands.listLanShares();
+}
+
+/**
+ * Follow a session shared by another instance. Events arrive on
+ * "lan-share-event"; returns the viewer id to pass to leaveLanShare.
+ */
+export async function joinLanShare(
+  host: string,
+  port: number,
+  sessionId: string,
+  token: string,
+): Promise<string> {
+  return commands.joinLanShare(host, port, sessionId, token);
+}
+
+export async function leaveLanShare(viewerId: string): Promise<void> {
+  await commands.leaveLanShare(viewerId);
+}
+
+/**
+ * Pull the team prompts repository now instead of waiting for the next sync.
+ */
+export async function syncTeamPrompts(): Promise<TeamPromptsStatus> {
+  return commands.syncTeamPrompts();
+}
+
+export async function getTeamPromptsStatus(): Promise<TeamPromptsStatus | null> {
+  return commands.getTeamPromptsStatus();
+}
+
+export async function acceptTeamPermissions(
+  commit: string,
+): Promise<TeamPromptsStatus | null> {
+  const confirmation = await requestConfirmation("accept_team_permissions", commit);
+  if (!confirmation) {
+    return null;
+  }
+  return commands.acceptTeamPermissions(commit, confirmation ?? null);
+}
+
+/**
+ * Store Slack credentials in the keychain. Pass "" to clear one, omit to keep it.
+ */
+export async function setSlackCredentials(credentials: {
+  webhookUrl?: string;
+  botToken?: string;
+}): Promise<void> {
+  await commands.setSlackCredentials(credentials.webhookUrl ?? null, credentials.botToken ?? null);
+}
+
+export async function getSlackStatus(): Promise<SlackStatus> {
+  return commands.getSlackStatus();
+}
+
+/**
+ * Post a session's prompts and turn summaries to Slack. With allowReplies,
+ * thread replies are sent to the session as messages.
+ */
+export async function startSlackMirror(
+  sessionId: string,
+  workspaceId: string,
+  options: { threadTs?: string; allowReplies?: boolean } = {},
+): Promise<SlackMirror> {
+  return commands.startSlackMirror(
+    sessionId,
+    workspaceId,
+    options.threadTs ?? null,
+    options.allowReplies ?? null,
+  );
+}
+
+export async function stopSlackMirror(sessionId: string): Promise<void> {
+  await commands.stopSlackMirror(sessionId);
+}
+
+/**
+ * Backend crash reports on disk, newest first.
+ */
+export async function listCrashReports(): Promise<CrashReport[]> {
+  return commands.listCrashReports();
+}
+
+/**
+ * Send a crash report to the configured endpoint.
+ */
+export async function submitCrashReport(id: string): Promise<CrashReport> {
+  return commands.submitCrashReport(id);
+}
+
+export async function deleteCrashReport(id: string): Promise<void> {
+  await commands.deleteCrashReport(id);
+}
+
+/**
+ * Latest heartbeat result for the bridge. Changes also arrive as
+ * `bridge/health` events.
+ */
+export async function getBridgeHealth(): Promise<BridgeHealth> {
+  return commands.getBridgeHealth();
+}
+
+/**
+ * The proxy in effect (from settings or the environment), or null.
+ */
+export async function getProxyStatus(): Promise<ProxyStatus | null> {
+  return commands.getProxyStatus();
+}
+
+/**
+ * The backend locale and how much of it is translated.
+ */
+export async function getLocaleInfo(): Promise<LocaleInfo> {
+  return commands.getLocaleInfo();
+}
+
+/**
+ * Format epoch millis the way backend exports and digests do, in the
+ * configured locale and time zone.
+ */
+export async function formatTimestamp(millis: number): Promise<string> {
+  return commands.formatTimestamp(millis);
+}
+
+/**
+ * Validate a PEM CA bundle before saving it as `caBundle`.
+ */
+export async function checkCaBundle(path: string): Promise<CaBundleInfo> {
+  return commands.checkCaBundle(path);
+}
+
+/**
+ * Reach `url` (the Anthropic API by default) through `proxy`, or the proxy
+ * in effect when omitted.
+ */
+export async function testProxy(proxy?: string | null, url?: string | null): Promise<ProxyTestResult> {
+  return commands.testProxy(proxy ?? null, url ?? null);
+}
+
+/**
+ * Store ticket system credentials in the keychain. Pass "" to clear one,
+ * omit to keep it.
+ */
+export async function setTicketCredentials(credentials: {
+  jiraApiToken?: string;
+  linearApiKey?: string;
+}): Promise<void> {
+  await commands.setTicketCredentials(
+    credentials.jiraApiToken ?? null,
+    credentials.linearApiKey ?? null,
+  );
+}
+
+export async function getTicketStatus(): Promise<TicketStatus> {
+  return commands.getTicketStatus();
+}
+
+export async function searchTickets(query: string): Promise<Ticket[]> {
+  return commands.searchTickets(query);
+}
+
+/**
+ * Link a session to a ticket, or unlink it with null.
+ */
+export async function linkSessionTicket(
+  sessionId: string,
+  key: string | null,
+): Promise<Ticket | null> {
+  return commands.linkSessionTicket(sessionId, key ?? null);
+}
+
+/**
+ * Link a workspace to a ticket; its sessions without their own link use it.
+ */
+export async function linkWorkspaceTicket(
+  workspaceId: string,
+  key: string | null,
+): Promise<Ticket | null> {
+  return commands.linkWorkspaceTicket(workspaceId, key ?? null);
+}
+
+/**
+ * The ticket linked to a session, with its current status.
+ */
+export async function getSessionTicket(
+  sessionId: string,
+): Promise<Ticket | null> {
+  return commands.getSessionTicket(sessionId);
+}
+
+/**
+ * Post the session summary as a comment on its linked ticket.
+ */
+export async function postTicketSummary(sessionId: string): Promise<Ticket> {
+  return commands.postTicketSummary(sessionId);
+}
+
+/**
+ * Build the activity digest for a day (YYYY-MM-DD, default today).
+ */
+export async function generateDigest(date?: string): Promise<Digest> {
+  return commands.generateDigest(date ?? null);
+}
+
+/**
+ * Build and deliver a digest now. Resolves to where it was sent.
+ */
+export async function sendDigest(date?: string): Promise<string> {
+  return commands.sendDigest(date ?? null);
+}
+
+/**
+ * Store (or clear, with null) the SMTP password or Mailgun API key.
+ */
+export async function setDigestCredential(secret: string | null): Promise<boolean> {
+  return commands.setDigestCredential(secret ?? null);
+}
+
+/**
+ * Store (or clear, with null) OTLP export headers as `key=value,key=value`.
+ */
+export async function setTelemetryHeaders(headers: string | null): Promise<boolean> {
+  return commands.setTelemetryHeaders(headers ?? null);
+}
+
+/**
+ * Usage totals per workspace, or per billing tag with groupByTag.
+ */
+export async function usageSummary(
+  range?: UsageRange,
+  groupByTag?: boolean,
+): Promise<UsageRow[]> {
+  return commands.usageSummary(range ?? null, groupByTag ?? null);
+}
+
+/**
+ * Write usage totals to a CSV file chosen in a native save dialog.
+ * Resolves to null if the dialog is cancelled.
+ */
+export async function exportUsageCsv(
+  range?: UsageRange,
+  groupByTag?: boolean,
+): Promise<UsageExport | null> {
+  return commands.exportUsageCsv(range ?? null, groupByTag ?? null);
+}
+
+/**
+ * Write per-session usage rows as CSV or JSON to a file chosen in a native
+ * save dialog. Resolves to null if the dialog is cancelled.
+ */
+export async function exportUsage(
+  format: "csv" | "json",
+  range?: UsageRange,
+): Promise<UsageExport | null> {
+  return commands.exportUsage(range ?? null, format);
+}
+
+/**
+ * Tell the backend which session the user is looking at; null when the
+ * window is blurred or nothing is selected.
+ */
+export async function setAttendedSession(
+  sessionId: string | null,
+): Promise<void> {
+  await commands.setAttendedSession(sessionId ?? null);
+}
+
+/**
+ * Attended and agent time per workspace and session over a date range.
+ */
+export async function getTimeReport(range?: UsageRange): Promise<TimeReport> {
+  return commands.getTimeReport(range ?? null);
+}
+
+/**
+ * The pricing table used for cost estimates, with each rate's source.
+ */
+export async function getModelPricing(): Promise<ModelPricing> {
+  return commands.getModelPricing();
+}
+
+/**
+ * Download the configured pricing JSON and return the updated table.
+ */
+export async function refreshModelPricing(): Promise<ModelPricing> {
+  return commands.refreshModelPricing();
+}
+
+/**
+ * Compose a message with workspace files inlined or @-mentioned. Nothing is
+ * sent; check `warnings` before sending the returned message.
+ */
+export async function attachFilesToMessage(
+  sessionId: string,
+  paths: string[],
+  message?: string,
+  mode?: "inline" | "mention",
+): Promise<AttachedMessage> {
+  return commands.attachFilesToMessage(sessionId, paths, message ?? null, mode ?? null);
+}
+
+/**
+ * Estimate tokens for text and/or workspace files without calling the API.
+ */
+export async function countTokens(options: {
+  text?: string;
+  paths?: string[];
+  workspaceId?: string;
+  model?: string;
+}): Promise<TokenCount> {
+  return commands.countTokens(
+    options.text ?? null,
+    options.paths ?? null,
+    options.workspaceId ?? null,
+    options.model ?? null,
+  );
+}
+
+/**
+ * Save the clipboard's image for sending. Rejects when there is none.
+ */
+export async function readClipboardImage(): Promise<ImageAttachment> {
+  return commands.readClipboardImage();
+}
+
+/**
+ * Capture a screen region for sending. Without `region` the user selects
+ * one; the app window hides meanwhile unless `hideWindow` is false.
+ */
+export async function captureScreenRegion(options?: {
+  region?: { x: number; y: number; width: number; height: number };
+  hideWindow?: boolean;
+}): Promise<ImageAttachment> {
+  return commands.captureScreenRegion(options?.region ?? null, options?.hideWindow ?? null);
+}
+
+/** Files the session's agent read or edited, most recently touched first. */
+export async function getSessionFiles(sessionId: string): Promise<SessionFile[]> {
+  return commands.getSessionFiles(sessionId);
+}
+
+/** Open a workspace file in the configured editor, at `line` when given. */
+export async function openInEditor(
+  workspaceId: string,
+  path: string,
+  line?: number,
+): Promise<void> {
+  await commands.openInEditor(workspaceId, path, line ?? null);
+}
+
+/** Editors whose command-line launcher is installed. */
+export async function listEditors(): Promise<EditorKind[]> {
+  return commands.listEditors();
+}
+
+/** Syntax-highlighted lines of a workspace file, for previews. */
+export async function highlightFile(
+  workspaceId: string,
+  path: string,
+): Promise<HighlightedFile> {
+  return commands.highlightFile(workspaceId, path);
+}
+
+/**
+ * One file's untruncated diff. `context` widens the lines kept around each
+ * change and `range` selects hunks `[start, end)`, counted from 0.
+ */
+export async function getFileDiff(
+  workspaceId: string,
+  path: string,
+  options?: { context?: number; range?: { start: number; end: number } },
+): Promise<GitFileDiff> {
+  return commands.getFileDiff(workspaceId, path, options?.context ?? null, options?.range ?? null);
+}
+
+/** Blobs over the large-file limit that a push of the current branch would upload. */
+export async function checkPushObjects(workspaceId: string): Promise<LargeObject[]> {
+  return commands.checkPushObjects(workspaceId);
+}
+
+/**
+ * Staged files over the large-file limit. Rejects instead when
+ * `lfs.blockLargeCommits` is set.
+ */
+export async function checkCommitSize(workspaceId: string): Promise<LargeObject[]> {
+  return commands.checkCommitSize(workspaceId);
+}
+
+/** Credentials the working diff adds, with their new-file line numbers. */
+export async function scanDiffForSecrets(workspaceId: string): Promise<SecretFinding[]> {
+  return commands.scanDiffForSecrets(workspaceId);
+}
+
+/**
+ * Commit, contributor, language, size and hotspot figures for a workspace.
+ * Cached until HEAD moves unless `refresh` is set.
+ */
+export async function getRepoStats(
+  workspaceId: string,
+  refresh = false,
+): Promise<RepoStats> {
+  return commands.getRepoStats(workspaceId, refresh ?? null);
+}
+
+/**
+ * Latency per command since launch, slowest total time first.
+ * Slow calls are also logged by the backend with redacted arguments.
+ */
+export async function getCommandMetrics(): Promise<CommandMetricsReport> {
+  return commands.getCommandMetrics();
+}
+
+/**
+ * Reduced motion, high contrast and color scheme as set in the OS. Changes
+ * arrive as `system-ui-prefs-changed` events.
+ */
+export async function getSystemUiPrefs(): Promise<SystemUiPrefs> {
+  return commands.getSystemUiPrefs();
+}
+
+/**
+ * Theme, accent color and font size. Changes arrive as
+ * `appearance-changed` events in every window.
+ */
+export async function getAppearance(): Promise<AppearanceSettings> {
+  return commands.getAppearance();
+}
+
+/**
+ * Version, commit, build date, bridge and SDK versions and third-party
+ * licenses for the About window.
+ */
+export async function getAboutInfo(): Promise<AboutInfo> {
+  return commands.getAboutInfo();
+}
+
+/**
+ * The `claude-monitor://` link the app was launched with, once. Links
+ * opened while running arrive as `deep-link` events.
+ */
+export async function takeLaunchDeepLink(): Promise<DeepLink | null> {
+  return commands.takeLaunchDeepLink();
+}
+
+/**
+ * Power source and the limits power saving applies now. Changes arrive as
+ * `power-status-changed` events.
+ */
+export async function getPowerStatus(): Promise<PowerStatus> {
+  return commands.getPowerStatus();
 }
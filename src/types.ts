@@ -1,3 +1,33 @@
+import type {
+  BridgeDiedPayload,
+  BridgeHealth,
+  BridgeHealthStatus,
+  BridgeReconnectedPayload,
+  BridgeStderrPayload,
+  ErrorPayload,
+  GuardrailTriggeredPayload,
+  IndexUpdatedPayload,
+  InterruptedTool,
+  InterruptedTurn,
+  JsonValue,
+  MessageCompletePayload,
+  MessageDeltaPayload,
+  PendingRequestInfo,
+  PermissionRequestPayload,
+  ResultPayload,
+  ScanProgressPayload,
+  SessionCloseReason,
+  SessionClosedPayload,
+  SessionStartedPayload,
+  SessionUsage,
+  SlackReplyPayload,
+  ToolCompletedPayload,
+  ToolProgressPayload,
+  ToolStartedPayload,
+  UnreadChangedPayload,
+  UsageUpdatedPayload,
+} from "./services/bindings";
+
 // MCP Server configuration for workspace-level settings
 export type WorkspaceMcpServerConfig = {
   type?: "stdio" | "sse" | "http";
@@ -19,8 +49,20 @@ export type WorkspaceSettings = {
   sortOrder?: number | null;
   mcpServers?: Record<string, WorkspaceMcpServerConfig>;
   plugins?: WorkspacePluginConfig[];
+  // Client or cost center usage is billed to; worktrees inherit it
+  billingTag?: string | null;
+  // CPU priority of the workspace's Claude process and the tools it runs
+  processPriority?: ProcessPriority | null;
+  // Whether thinking blocks are shown, kept but hidden, or dropped
+  thinkingBlocks?: ThinkingBlocks | null;
+  // Jira or Linear issue key; sessions without their own link use it
+  ticket?: string | null;
 };
 
+export type ProcessPriority = "normal" | "low" | "background";
+
+export type ThinkingBlocks = "show" | "hide" | "discard";
+
 export type WorkspaceKind = "main" | "worktree";
 
 export type WorktreeInfo = {
@@ -37,6 +79,8 @@ export type WorkspaceInfo = {
   parentId?: string | null;
   worktree?: WorktreeInfo | null;
   settings: WorkspaceSettings;
+  /** Workflows, queued messages and auto-approval are on hold. */
+  automationPaused?: boolean;
 };
 
 export type AppServerEvent = {
@@ -50,8 +94,22 @@ export type Message = {
   text: string;
 };
 
+export type FileReference = {
+  text: string;
+  path: string;
+  line?: number;
+  endLine?: number;
+  exists: boolean;
+};
+
 export type ConversationItem =
-  | { id: string; kind: "message"; role: "user" | "assistant"; text: string }
+  | {
+      id: string;
+      kind: "message";
+      role: "user" | "assistant";
+      text: string;
+      references?: FileReference[];
+    }
   | { id: string; kind: "reasoning"; summary: string; content: string }
   | { id: string; kind: "diff"; title: string; diff: string; status?: string }
   | { id: string; kind: "review"; state: "started" | "completed"; text: string }
@@ -65,6 +123,16 @@ export type ConversationItem =
       output?: string;
       elapsedSeconds?: number;
       changes?: { path: string; kind?: string; diff?: string }[];
+    }
+  | {
+      id: string;
+      kind: "interrupted";
+      // Assistant text streamed before the interrupt
+      text: string;
+      tools: InterruptedTool[];
+      inputTokens: number;
+      outputTokens: number;
+      interruptedAt: number;
     };
 
 export type ThreadSummary = {
@@ -107,9 +175,99 @@ export type AppSettings = {
   defaultAccessMode: AccessMode;
   defaultPermissionMode: PermissionMode;
   uiScale: number;
+  appearance?: AppearanceSettings;
   mcpServers?: MCPServerConfig[];
+  eventBatchWindows?: Record<string, number>;
+  // Bridge method -> seconds to wait for a reply; "*" covers the rest, 0 waits
+  requestTimeouts?: Record<string, number>;
+  observerMode?: boolean;
+  recordReplays?: boolean;
+  lanSharing?: boolean;
+  teamPromptsRepo?: string | null;
+  teamPromptsSyncMinutes?: number;
+  slackChannel?: string | null;
+  digest?: DigestSettings;
+  telemetry?: TelemetrySettings;
+  tickets?: TicketSettings;
+  crashReports?: CrashReportSettings;
+  bridgeLaunch?: BridgeLaunchSettings;
+  proxy?: ProxySettings;
+  // PEM bundle trusted by the bridge, curl and gh
+  caBundle?: string | null;
+  // BCP 47 tag for backend-generated text and dates, e.g. "en-US"
+  locale?: string | null;
+  // "local", "UTC" or a fixed offset like "+02:00"
+  timeZone?: string | null;
+  pricingUrl?: string | null;
+  pricingOverrides?: ModelRate[];
+  startSessionOnDrop?: boolean;
+  editor?: EditorKind | null;
+  lfs?: LfsSettings;
+  // Pre-commit hook refuses commits that add credentials
+  blockSecretCommits?: boolean;
+  eventScope?: EventScope;
+  doNotDisturb?: DoNotDisturbSettings;
+  /** Turns automation may run in a session without user input; 0 = no limit. */
+  maxAutonomousTurns?: number;
+  /** Hold background schedulers for a couple of minutes after a wake. */
+  pauseSchedulersOnSleep?: boolean;
+  powerSaving?: PowerSavingSettings;
+  /** Turns running at once across sessions; 0 = no limit. User turns go first. */
+  maxConcurrentTurns?: number;
+};
+
+/** Throttling while on battery or thermally throttled. */
+export type PowerSavingSettings = {
+  enabled: boolean;
+  // Open sessions allowed while throttled; 0 = no limit
+  maxSessions: number;
+  pauseIndexing: boolean;
+};
+
+/** Local time window in minutes past midnight; may wrap past midnight. */
+export type QuietHours = {
+  start: number;
+  end: number;
+};
+
+export type ThemeMode = "system" | "light" | "dark";
+
+export type AppearanceSettings = {
+  theme: ThemeMode;
+  // #rrggbb; the built-in blue when unset
+  accentColor?: string | null;
+  // Message and composer text size in px
+  fontSize: number;
 };
 
+export type DoNotDisturbSettings = {
+  enabled: boolean;
+  until?: number;
+  schedule?: QuietHours;
+};
+
+/** Do-not-disturb right now, with the permission requests it is holding. */
+export type FocusStatus = {
+  active: boolean;
+  until: number | null;
+  held: number;
+};
+
+/**
+ * "all" listens to every workspace's Claude events; "workspace" only to the
+ * one on screen, leaving background sessions quiet until revisited.
+ */
+export type EventScope = "all" | "workspace";
+
+export type LfsSettings = {
+  largeFileMb: number;
+  blockLargeCommits: boolean;
+  // Check commits and pushes made outside the app with git hooks
+  gitHooks: boolean;
+};
+
+export type EditorKind = "vscode" | "cursor" | "zed" | "jetbrains" | "system";
+
 export type CodexDoctorResult = {
   ok: boolean;
   codexBin: string | null;
@@ -134,11 +292,144 @@ export type GitFileStatus = {
   status: string;
   additions: number;
   deletions: number;
+  lfs?: LfsInfo;
+};
+
+export type MergeCheck = {
+  name: "uncommitted" | "conflicts" | "behind" | "ci" | "sessions";
+  status: "pass" | "warn" | "fail" | "unknown";
+  detail: string;
+};
+
+export type WorktreeOverviewSession = {
+  sessionId: string;
+  title: string;
+  lastActivity: number;
+  running: boolean;
+};
+
+/** A worktree compared with the branch its project has checked out. */
+export type WorktreeOverview = {
+  worktreeId: string;
+  branch: string;
+  parentId: string;
+  parentBranch: string;
+  mergeBase: string | null;
+  ahead: number;
+  behind: number;
+  files: GitFileStatus[];
+  totalAdditions: number;
+  totalDeletions: number;
+  sessions: WorktreeOverviewSession[];
+  pullRequest: { number: number; url: string; state: string } | null;
+  checks: MergeCheck[];
+  ready: boolean;
+};
+
+export type StaleWorktreeReason = "merged" | "inactive" | "missing";
+
+/** A worktree that looks done with, from `list_stale_worktrees`. */
+export type StaleWorktree = {
+  worktreeId: string;
+  name: string;
+  parentId: string;
+  branch: string;
+  path: string;
+  reasons: StaleWorktreeReason[];
+  mergedInto: string | null;
+  lastActivity: number | null;
+  sessions: number;
+  /** Changed or untracked files that removing it would discard. */
+  uncommitted: number;
+  diskBytes: number;
+};
+
+export type StaleWorktreeReport = {
+  inactiveDays: number;
+  worktrees: StaleWorktree[];
+  totalBytes: number;
+};
+
+export type WorktreeCleanup = {
+  worktreeId: string;
+  name: string;
+  removed: boolean;
+  branchDeleted: boolean;
+  /** Why the worktree or its branch was kept. */
+  detail: string | null;
+};
+
+/** Large-file concerns for a changed path; absent for ordinary files. */
+export type LfsInfo = {
+  /** Matched by a `filter=lfs` attribute. */
+  tracked: boolean;
+  /** The working copy is an LFS pointer whose text changed. */
+  pointerEdited: boolean;
+  /** Size in bytes when over the large-file limit and not tracked by LFS. */
+  oversized?: number;
+};
+
+export type RepoStats = {
+  head?: string;
+  commitCount: number;
+  contributors: { name: string; email: string; commits: number }[];
+  /** Files at HEAD within the workspace, largest language first. */
+  languages: { language: string; files: number; bytes: number }[];
+  trackedBytes: number;
+  /** Size of the `.git` directory, history included. */
+  gitDirBytes: number;
+  /** Most-churned files across recent non-merge commits. */
+  hotspots: { path: string; commits: number; linesChanged: number }[];
+  computedAt: number;
+};
+
+/** A blob over the large-file limit, staged or in an unpushed commit. */
+export type LargeObject = {
+  path: string;
+  size: number;
+  commit?: string;
+  lfs: boolean;
+};
+
+/** Something in the working diff that looks like a credential. */
+export type SecretFinding = {
+  path: string;
+  line: number;
+  kind: string;
+  redacted: string;
 };
 
 export type GitFileDiff = {
   path: string;
   diff: string;
+  lfs?: LfsInfo;
+  /** Highlighted HTML per line of `diff`, when requested. */
+  highlighted?: string[];
+  /** Word-level changes on modified lines, when requested. */
+  intraline?: IntralineChange[];
+  /** Set when `diff` was cut short; fetch the rest with `getFileDiff`. */
+  truncated?: DiffTruncation;
+};
+
+export type DiffTruncation = {
+  totalLines: number;
+  shownLines: number;
+  totalHunks: number;
+  /** Hunks are counted from 0, so this is also the first hidden one. */
+  shownHunks: number;
+};
+
+export type IntralineChange = {
+  /** Index into the diff's lines. */
+  line: number;
+  /** `[start, end)` offsets into the line text after its `+`/`-` prefix. */
+  ranges: [number, number][];
+};
+
+export type HighlightedFile = {
+  path: string;
+  language?: string;
+  lines: string[];
 };
 
 export type DiffLineReference = {
@@ -233,6 +524,54 @@ export type QueuedMessage = {
   images?: string[];
 };
 
+export type MessageQueueChanged = {
+  sessionId: string;
+  queue: QueuedMessage[];
+};
+
+export type WorkflowCondition = "passed" | "failed";
+
+export type WorkflowStep =
+  | { type: "prompt"; text: string }
+  | { type: "run"; command: string; timeoutSecs?: number; allowFailure?: boolean }
+  | { type: "if"; when: WorkflowCondition; then?: WorkflowStep[]; else?: WorkflowStep[] }
+  | {
+      type: "repeat";
+      until: WorkflowCondition;
+      maxIterations: number;
+      steps: WorkflowStep[];
+    };
+
+export type Workflow = {
+  name: string;
+  steps: WorkflowStep[];
+};
+
+export type WorkflowFile = {
+  path: string;
+  workflow?: Workflow;
+  error?: string;
+};
+
+export type WorkflowRunInfo = {
+  runId: string;
+  sessionId: string;
+  name: string;
+  status: "running" | "succeeded" | "failed" | "cancelled";
+  error?: string;
+};
+
+export type WorkflowStepEvent = {
+  runId: string;
+  sessionId: string;
+  path: string;
+  kind: "prompt" | "run";
+  label: string;
+  status: "running" | "passed" | "failed";
+  exitCode?: number;
+  output?: string;
+};
+
 export type ModelOption = {
   id: string;
   model: string;
@@ -255,6 +594,17 @@ export type CustomPromptOption = {
   description?: string;
   argumentHint?: string;
   content: string;
+  // "team" prompts are synced from the team repo and read-only
+  source?: "local" | "team";
+};
+
+/** A sent prompt; sending the same text again in a workspace updates it. */
+export type PromptHistoryEntry = {
+  text: string;
+  workspaceId: string;
+  firstUsedAt: number;
+  lastUsedAt: number;
+  useCount: number;
 };
 
 export type BranchInfo = {
@@ -286,92 +636,41 @@ export type ClaudeBridgeEventBase<T extends string, P> = {
 // Tauri event payload (from Rust ClaudeEvent struct)
 export type ClaudeTauriEvent = ClaudeBridgeEvent;
 
-// Event payload types
-export type SessionStartedPayload = {
-  model: string;
-  tools: string[];
-  cwd: string;
-  claudeCodeVersion: string;
-  permissionMode: string;
-  mcpServers: { name: string; status: string }[];
-  transcriptPath?: string;
-  projectPath?: string;
-};
-
-export type SessionClosedPayload = {
-  reason: "user" | "error" | "completed";
-};
-
-export type MessageDeltaPayload = {
-  event: unknown; // BetaRawMessageStreamEvent from SDK
-  parentToolUseId: string | null;
-  uuid?: string;
-};
-
-export type MessageCompletePayload = {
-  uuid: string;
-  message: unknown; // BetaMessage from SDK
-  parentToolUseId: string | null;
-  error?: string;
-};
-
-export type ToolStartedPayload = {
-  toolName: string;
-  toolUseId: string;
-  input: unknown;
-  parentToolUseId: string | null;
-};
-
-export type ToolProgressPayload = {
-  toolName: string;
-  toolUseId: string;
-  elapsedSeconds: number;
-  parentToolUseId: string | null;
-};
-
-export type ToolCompletedPayload = {
-  toolName: string;
-  toolUseId: string;
-  output: unknown;
+// Event payload types, generated from src-tauri/src/events.rs
+export type {
+  BridgeDiedPayload,
+  BridgeHealth,
+  BridgeHealthStatus,
+  BridgeReconnectedPayload,
+  BridgeStderrPayload,
+  ErrorPayload,
+  GuardrailTriggeredPayload,
+  IndexUpdatedPayload,
+  InterruptedTool,
+  MessageCompletePayload,
+  MessageDeltaPayload,
+  PermissionRequestPayload,
+  ResultPayload,
+  ScanProgressPayload,
+  SessionCloseReason,
+  SessionClosedPayload,
+  SessionStartedPayload,
+  SessionUsage,
+  SlackReplyPayload,
+  ToolCompletedPayload,
+  ToolProgressPayload,
+  ToolStartedPayload,
+  UnreadChangedPayload,
+  UsageUpdatedPayload,
 };
 
-export type PermissionRequestPayload = {
-  toolName: string;
-  toolUseId: string;
-  input: Record<string, unknown>;
-  suggestions?: unknown[];
-  blockedPath?: string;
-  decisionReason?: string;
-  agentId?: string;
-};
-
-export type ResultPayload = {
-  success: boolean;
-  subtype: string;
-  result?: string;
-  durationMs: number;
-  numTurns: number;
-  totalCostUsd: number;
-  usage: {
-    inputTokens: number;
-    outputTokens: number;
-    cacheReadInputTokens: number;
-    cacheCreationInputTokens: number;
-  };
-  errors?: string[];
-};
-
-export type ErrorPayload = {
-  code: string;
-  message: string;
-  recoverable: boolean;
-};
+// A bridge request still waiting for its reply
+export type PendingBridgeRequest = PendingRequestInfo;
 
-export type BridgeStderrPayload = {
-  message: string;
-};
+export type TurnInterruptedPayload = InterruptedTurn;
 
-export type BridgeConnectedPayload = unknown;
+// `bridge/connected` has no schema and is forwarded as-is
+export type BridgeConnectedPayload = JsonValue;
 
 // Typed event definitions
 export type ClaudeSessionStartedEvent = ClaudeBridgeEventBase<"session/started", SessionStartedPayload>;
@@ -386,6 +685,18 @@ export type ClaudeResultEvent = ClaudeBridgeEventBase<"result", ResultPayload>;
 export type ClaudeErrorEvent = ClaudeBridgeEventBase<"error", ErrorPayload>;
 export type ClaudeBridgeStderrEvent = ClaudeBridgeEventBase<"bridge/stderr", BridgeStderrPayload>;
 export type ClaudeBridgeConnectedEvent = ClaudeBridgeEventBase<"bridge/connected", BridgeConnectedPayload>;
+export type ClaudeUsageUpdatedEvent = ClaudeBridgeEventBase<"usage/updated", UsageUpdatedPayload>;
+export type ClaudeScanProgressEvent = ClaudeBridgeEventBase<"scan/progress", ScanProgressPayload>;
+export type ClaudeIndexUpdatedEvent = ClaudeBridgeEventBase<"index/updated", IndexUpdatedPayload>;
+export type ClaudeTurnInterruptedEvent = ClaudeBridgeEventBase<"turn/interrupted", TurnInterruptedPayload>;
+export type ClaudeUnreadChangedEvent = ClaudeBridgeEventBase<"unread/changed", UnreadChangedPayload>;
+export type ClaudeQueueDispatchedEvent = ClaudeBridgeEventBase<"queue/dispatched", QueuedMessage>;
+export type ClaudeGuardrailTriggeredEvent = ClaudeBridgeEventBase<"guardrail/triggered", GuardrailTriggeredPayload>;
+export type ClaudeBridgeHealthEvent = ClaudeBridgeEventBase<"bridge/health", BridgeHealth>;
+export type ClaudeBridgeDiedEvent = ClaudeBridgeEventBase<"bridge/died", BridgeDiedPayload>;
+export type ClaudeBridgeReconnectedEvent = ClaudeBridgeEventBase<"bridge/reconnected", BridgeReconnectedPayload>;
+
+export type ClaudeSlackReplyEvent = ClaudeBridgeEventBase<"slack/reply", SlackReplyPayload>;
 
 // Union of all bridge events
 export type ClaudeBridgeEvent =
@@ -400,7 +711,18 @@ export type ClaudeBridgeEvent =
   | ClaudeResultEvent
   | ClaudeErrorEvent
   | ClaudeBridgeStderrEvent
-  | ClaudeBridgeConnectedEvent;
+  | ClaudeBridgeConnectedEvent
+  | ClaudeUsageUpdatedEvent
+  | ClaudeScanProgressEvent
+  | ClaudeIndexUpdatedEvent
+  | ClaudeSlackReplyEvent
+  | ClaudeTurnInterruptedEvent
+  | ClaudeUnreadChangedEvent
+  | ClaudeQueueDispatchedEvent
+  | ClaudeGuardrailTriggeredEvent
+  | ClaudeBridgeHealthEvent
+  | ClaudeBridgeDiedEvent
+  | ClaudeBridgeReconnectedEvent;
 
 // Claude approval request (used in UI state, derived from PermissionRequestEvent)
 export type ClaudeApprovalRequest = {
@@ -414,6 +736,26 @@ export type ClaudeApprovalRequest = {
   decision_reason?: string;
 };
 
+/** Permission requests an auto-approve window accepts. */
+export type AutoApproveScope = "reads" | "edits" | "all";
+
+export type AutoApproveWindow = {
+  sessionId: string;
+  scope: AutoApproveScope;
+  root: string;
+  expiresAt: number;
+  approved: number;
+};
+
+export type AuditEntry = {
+  at: number;
+  sessionId: string;
+  action: string;
+  toolName?: string;
+  toolUseId?: string;
+  detail?: string;
+};
+
 // Registry-based session info
 export type SessionInfo = {
   sessionId: string;
@@ -435,6 +777,10 @@ export type ClaudeDoctorResult = {
   claudeOk: boolean;
   claudeVersion: string | null;
   claudeDetails: string | null;
+  bridgeOk: boolean;
+  bridgeCommand: string | null;
+  bridgeVersion: string | null;
+  bridgeDetails: string | null;
   path: string | null;
 };
 
@@ -450,4 +796,499 @@ export type SessionEntry = {
   transcriptPath: string | null;
   projectPath: string | null;
   status: SessionStatus;
+  files?: SessionFile[];
+  lastReadMessageId?: string;
+  lastReadLine?: number;
+  // Free-form fields set with setSessionMetadata, e.g. a ticket id
+  metadata?: Record<string, string>;
+  // Attended and agent time by local day (YYYY-MM-DD)
+  time?: Record<string, SessionTime>;
+};
+
+export type SessionTime = {
+  // Window focused with the session selected
+  attendedMs: number;
+  // Turns running
+  agentMs: number;
+};
+
+export type TimeReport = {
+  attendedMs: number;
+  agentMs: number;
+  workspaces: {
+    workspaceId: string;
+    workspaceName: string;
+    billingTag: string | null;
+    attendedMs: number;
+    agentMs: number;
+    sessions: {
+      sessionId: string;
+      preview: string | null;
+      attendedMs: number;
+      agentMs: number;
+    }[];
+  }[];
+};
+
+/** A sidebar session with replies since it was last read. */
+export type VisibleSession = SessionEntry & {
+  unreadCount: number;
+};
+
+export type FileAction = "read" | "edit" | "write";
+
+export type SessionFile = {
+  path: string;
+  reads: number;
+  edits: number;
+  lastAction: FileAction;
+  lastTouchedAt: number;
+};
+
+export type SessionBundleExport = {
+  path: string;
+  bytes: number;
+  redactions: number;
+};
+
+export type ReplayExport = {
+  path: string;
+  events: number;
+};
+
+export type ReplayStatus = {
+  sessionId: string;
+  workspaceId: string;
+  status: "playing" | "finished" | "stopped";
+  events: number;
+};
+
+export type SessionBundleImport = {
+  session: SessionEntry;
+  workspaceName?: string;
+  exportedAt: number;
+  diffs: GitFileDiff[];
+  redacted: boolean;
+};
+
+export type LanShareInfo = {
+  sessionId: string;
+  port: number;
+  token: string;
+  address?: string;
+  viewers: number;
+};
+
+export type LanShareEvent = {
+  viewerId: string;
+  event: ClaudeBridgeEvent;
+};
+
+export type TeamPromptsStatus = {
+  repo: string;
+  commit: string;
+  syncedAt: number;
+  prompts: number;
+  permissions: { allow: string[]; deny: string[] };
+  acceptedCommit: string | null;
+  pendingPermissions: PendingTeamPermissions | null;
+};
+
+export type PendingTeamPermissions = {
+  commit: string;
+  added: string[];
+  removed: string[];
+};
+
+export type SlackMirror = {
+  sessionId: string;
+  workspaceId: string;
+  threadTs: string | null;
+  allowReplies: boolean;
+};
+
+export type SlackStatus = {
+  hasWebhook: boolean;
+  hasBotToken: boolean;
+  channel: string | null;
+  mirrors: SlackMirror[];
+};
+
+export type DigestSettings = {
+  enabled: boolean;
+  // Local hour (0-23) after which the day's digest is sent
+  hour: number;
+  delivery: "folder" | "smtp" | "mailgun";
+  folder?: string;
+  smtpUrl?: string;
+  smtpUser?: string;
+  mailgunDomain?: string;
+  from?: string;
+  to: string[];
+};
+
+// Rates in USD per million tokens; `model` is an id or id prefix
+export type ModelRate = {
+  model: string;
+  input: number;
+  output: number;
+  cacheRead: number;
+  cacheWrite: number;
+};
+
+export type ModelPricing = {
+  // Highest precedence first
+  models: (ModelRate & { source: "override" | "remote" | "bundled" })[];
+  remoteUrl?: string;
+  remoteFetchedAt?: number;
+};
+
+export type MessageAttachment = {
+  // Relative to the workspace root
+  path: string;
+  bytes: number;
+  tokens: number;
+  inlined: boolean;
+};
+
+export type AttachedMessage = {
+  message: string;
+  attachments: MessageAttachment[];
+  totalTokens: number;
+  budgetTokens: number;
+  contextWindow: number;
+  warnings: string[];
+};
+
+// An image in the attachment cache; pass `path` in claude_send_message images
+export type ImageAttachment = {
+  path: string;
+  mediaType: "image/png" | "image/jpeg";
+  bytes: number;
+  width?: number;
+  height?: number;
+};
+
+export type WorkspaceDropEvent = {
+  path: string;
+  gitRoot?: string;
+  workspace?: WorkspaceInfo;
+  existingId?: string;
+  startSession: boolean;
+  error?: string;
+};
+
+export type FilesDropEvent = {
+  paths: string[];
+};
+
+export type TokenCount = {
+  // Text plus files; an offline estimate
+  tokens: number;
+  textTokens: number;
+  files: { path: string; tokens: number }[];
+  contextWindow: number;
+};
+
+export type UsageRange = {
+  // Inclusive local dates, YYYY-MM-DD; omit for an open end
+  from?: string;
+  to?: string;
+};
+
+export type UsageRow = {
+  billingTag: string;
+  workspaceId?: string;
+  workspaceName?: string;
+  sessions: number;
+  requests: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadInputTokens: number;
+  cacheCreationInputTokens: number;
+  costUsd: number;
+};
+
+export type SessionUsageRow = {
+  sessionId: string;
+  workspaceId: string;
+  workspaceName: string;
+  billingTag: string;
+  model: string;
+  createdAt: number;
+  lastActivity: number;
+  requests: number;
+  failedRequests: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadInputTokens: number;
+  cacheCreationInputTokens: number;
+  // Cost reported by the SDK
+  costUsd: number;
+  // Cost from the pricing table; null when the model is not priced
+  estimatedCostUsd: number | null;
+  durationMs: number;
+  outcome: string;
+  metadata: Record<string, string>;
+};
+
+export type UsageExport = {
+  path: string;
+  rows: number;
+  costUsd: number;
+};
+
+export type TelemetrySettings = {
+  enabled: boolean;
+  // OTLP/HTTP base URL, e.g. http://localhost:4318
+  endpoint?: string | null;
+  serviceName: string;
+};
+
+export type CrashReportSettings = {
+  enabled: boolean;
+  // Endpoint submitCrashReport posts report JSON to
+  submitUrl?: string | null;
+};
+
+export type LocaleInfo = {
+  locale: string | null;
+  // Backend messages translated for the locale, out of `messages`
+  translated: number;
+  messages: number;
+};
+
+export type ProxySettings = {
+  url?: string | null;
+  // Comma-separated hosts that bypass the proxy
+  noProxy?: string | null;
+};
+
+export type ProxyStatus = {
+  url: string;
+  noProxy: string | null;
+  source: "settings" | "environment";
+};
+
+export type CaBundleInfo = {
+  path: string;
+  certificates: number;
+};
+
+export type ProxyTestResult = {
+  ok: boolean;
+  url: string;
+  proxy: string | null;
+  status: number | null;
+  latencyMs: number | null;
+  details: string | null;
+};
+
+export type BridgeRuntime = "tsx" | "bun" | "node" | "custom";
+
+export type BridgeLaunchSettings = {
+  runtime: BridgeRuntime;
+  // Program to run for the custom runtime
+  command?: string | null;
+  // Passed before the script; "{bridge}" stands for it with a custom runtime
+  args?: string[];
+  env?: Record<string, string>;
+  // Bridge script to run instead of the bundled one
+  script?: string | null;
+};
+
+export type CrashReport = {
+  id: string;
+  createdAt: number;
+  appVersion: string;
+  os: string;
+  arch: string;
+  thread: string;
+  message: string;
+  location?: string;
+  backtrace: string;
+  // Last log lines before the crash
+  log: string[];
+  submitted: boolean;
+};
+
+export type TicketProvider = "jira" | "linear";
+
+export type TicketSettings = {
+  provider?: TicketProvider | null;
+  // e.g. https://acme.atlassian.net
+  jiraBaseUrl?: string | null;
+  jiraEmail?: string | null;
+  // Post the session summary to its ticket when the session is archived
+  commentOnComplete: boolean;
+};
+
+export type Ticket = {
+  provider: TicketProvider;
+  key: string;
+  title: string;
+  status: string;
+  url: string;
+};
+
+export type TicketStatus = {
+  provider: TicketProvider | null;
+  hasJiraToken: boolean;
+  hasLinearKey: boolean;
+};
+
+export type Digest = {
+  date: string;
+  workspaces: {
+    workspaceId: string;
+    name: string;
+    sessions: {
+      sessionId: string;
+      title: string;
+      missing: boolean;
+      lastActivity: number;
+      costUsd: number;
+      requests: number;
+    }[];
+    costUsd: number;
+    changes: { path: string; added: number; removed: number }[];
+  }[];
+  sessionCount: number;
+  costUsd: number;
+  markdown: string;
+  html: string;
+};
+
+export type CommandMetric = {
+  command: string;
+  count: number;
+  slow: number;
+  meanMs: number;
+  maxMs: number;
+  // Upper bound of the bucket holding the percentile; null past the last
+  p50Ms: number | null;
+  p95Ms: number | null;
+  buckets: number[];
+};
+
+export type CommandMetricsReport = {
+  bucketBoundsMs: number[];
+  slowThresholdMs: number;
+  commands: CommandMetric[];
+};
+
+export type ColorScheme = "light" | "dark";
+
+export type SystemUiPrefs = {
+  reducedMotion: boolean;
+  highContrast: boolean;
+  colorScheme: ColorScheme;
+};
+
+export type Acknowledgement = {
+  name: string;
+  version: string;
+  license: string | null;
+  repository: string | null;
+  kind: "crate" | "npm";
+};
+
+export type AboutInfo = {
+  version: string;
+  commit: string | null;
+  // Formatted in the configured locale
+  buildDate: string | null;
+  bridgeVersion: string | null;
+  sdkVersion: string | null;
+  acknowledgements: Acknowledgement[];
+};
+
+// Payload of `help-menu` events from the Help menu
+export type HelpMenuEvent =
+  | { action: "doctor"; result: ClaudeDoctorResult }
+  | { action: "doctorFailed"; error: string }
+  | { action: "shortcuts" }
+  | { action: "diagnosticsExported"; path: string };
+
+// `claude-monitor://` link from the menu, a jump list task or the command line
+export type DeepLink =
+  | { action: "openWorkspace"; workspaceId: string }
+  | { action: "newSession"; workspaceId: string };
+
+// Payload of `system-wake` events, sent when the computer wakes from sleep
+export type SystemWake = {
+  sleptMs: number;
+  // Turns that were running when it went to sleep
+  runningTurns: number;
+  // Whether the bridge answered after waking; null when it wasn't running
+  bridgeOk: boolean | null;
+};
+
+// Payload of `power-status-changed` events
+export type PowerStatus = {
+  onBattery: boolean;
+  thermalPressure: boolean;
+  throttled: boolean;
+  // Open sessions allowed right now; null = no limit
+  maxSessions: number | null;
+  indexingPaused: boolean;
+};
+
+// Named block of context shared by every workspace, inserted from the
+// composer with /snippets:<name>
+export type Snippet = {
+  id: string;
+  name: string;
+  description?: string;
+  content: string;
+  createdAt: number;
+  updatedAt: number;
+};
+
+export type GlossaryTerm = {
+  id: string;
+  term: string;
+  definition: string;
+  updatedAt: number;
+};
+
+// A workspace's domain terms; when enabled they're appended to the system
+// prompt of new sessions
+export type WorkspaceGlossary = {
+  enabled: boolean;
+  terms: GlossaryTerm[];
+};
+
+export type ChangelogEntry = {
+  sha: string;
+  // Commit summary without its conventional-commit prefix
+  summary: string;
+  author: string;
+  timestamp: number;
+  kind: "feature" | "fix" | "other";
+  // Sessions that likely produced the commit
+  sessions: { sessionId: string; title: string }[];
+};
+
+// Draft release notes for the commits after `sinceRef`
+export type Changelog = {
+  sinceRef: string;
+  entries: ChangelogEntry[];
+  truncated: boolean;
+  markdown: string;
+};
+
+export type ReleaseStep = {
+  name: "tag" | "push" | "release";
+  status: "done" | "planned" | "failed" | "skipped";
+  detail: string;
+};
+
+export type ReleaseResult = {
+  tag: string;
+  previousTag: string | null;
+  notes: string;
+  dryRun: boolean;
+  steps: ReleaseStep[];
+  url: string | null;
 };
@@ -0,0 +1,29 @@
+// Bridge requests that end without a reply fail with `CODE: message`; other
+// errors are passed through as the bridge or backend wrote them. BRIDGE_DIED
+// means the bridge exited; it is restarted and the request can be retried.
+// OBSERVER_MODE means the command was refused because observer mode is on.
+
+export type BridgeErrorCode =
+  | "REQUEST_TIMEOUT"
+  | "REQUEST_CANCELED"
+  | "BRIDGE_DIED"
+  | "OBSERVER_MODE";
+
+export type BridgeError = {
+  code: BridgeErrorCode | null;
+  message: string;
+};
+
+const CODE_PATTERN = /^(REQUEST_TIMEOUT|REQUEST_CANCELED|BRIDGE_DIED|OBSERVER_MODE): /;
+
+export function parseBridgeError(error: unknown): BridgeError {
+  const text = error instanceof Error ? error.message : String(error);
+  const match = CODE_PATTERN.exec(text);
+  if (!match) {
+    return { code: null, message: text };
+  }
+  return {
+    code: match[1] as BridgeErrorCode,
+    message: text.slice(match[0].length),
+  };
+}
@@ -1,20 +1,37 @@
+import type { IntralineChange } from "../types";
+import { escapeHtml } from "./syntax";
+
 export type ParsedDiffLine = {
   type: "add" | "del" | "context" | "hunk" | "meta";
   oldLine: number | null;
   newLine: number | null;
   text: string;
+  /** Server-highlighted HTML for `text`. */
+  html?: string;
+  /** Changed `[start, end)` spans of `text` on modified lines. */
+  changes?: [number, number][];
+};
+
+type DiffAnnotations = {
+  highlighted?: string[];
+  intraline?: IntralineChange[];
 };
 
 const HUNK_REGEX = /^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@/;
 
-export function parseDiff(diff: string): ParsedDiffLine[] {
+export function parseDiff(diff: string, annotations?: DiffAnnotations): ParsedDiffLine[] {
   const lines = diff.split("\n");
   const parsed: ParsedDiffLine[] = [];
+  const changesByLine = new Map(
+    (annotations?.intraline ?? []).map((change) => [change.line, change.ranges]),
+  );
   let oldLine = 0;
   let newLine = 0;
   let inHunk = false;
 
-  for (const line of lines) {
+  for (const [index, line] of lines.entries()) {
+    const html = annotations?.highlighted?.[index];
+    const changes = changesByLine.get(index);
     if (line.startsWith("@@")) {
       const match = HUNK_REGEX.exec(line);
       if (match) {
@@ -41,6 +58,8 @@ export function parseDiff(diff: string): ParsedDiffLine[] {
         oldLine: null,
         newLine,
         text: line.slice(1),
+        html,
+        changes,
       });
       newLine += 1;
       continue;
@@ -52,6 +71,8 @@ export function parseDiff(diff: string): ParsedDiffLine[] {
         oldLine,
         newLine: null,
         text: line.slice(1),
+        html,
+        changes,
       });
       oldLine += 1;
       continue;
@@ -63,6 +84,7 @@ export function parseDiff(diff: string): ParsedDiffLine[] {
         oldLine,
         newLine,
         text: line.slice(1),
+        html,
       });
       oldLine += 1;
       newLine += 1;
@@ -81,3 +103,15 @@ export function parseDiff(diff: string): ParsedDiffLine[] {
 
   return parsed;
 }
+
+/** HTML for `text` with the changed spans wrapped for emphasis. */
+export function markWordChanges(text: string, changes: [number, number][]) {
+  let html = "";
+  let cursor = 0;
+  for (const [start, end] of changes) {
+    html += escapeHtml(text.slice(cursor, start));
+    html += `<span class="diff-word-change">${escapeHtml(text.slice(start, end))}</span>`;
+    cursor = end;
+  }
+  return html + escapeHtml(text.slice(cursor));
+}
@@ -50,7 +50,7 @@ const EXTENSION_TO_LANGUAGE: Record<string, string> = {
   yml: "yaml",
 };
 
-function escapeHtml(value: string) {
+export function escapeHtml(value: string) {
   return value
     .replace(/&/g, "&amp;")
     .replace(/</g, "&lt;")